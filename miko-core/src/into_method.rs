@@ -1,16 +1,32 @@
-use std::str::FromStr;
-
 use hyper::Method;
 
 pub trait IntoMethods {
     fn into_methods(self) -> Vec<Method>;
 }
 
+/// 解析以逗号分隔的方法列表，如 `"get, post"`、`"PROPFIND,MKCOL"`
+///
+/// 方法名统一转为大写，因此 `"get"` 与 `"GET"` 等价；非标准方法（WebDAV 等）按扩展方法处理
+pub fn parse_methods(s: &str) -> Result<Vec<Method>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(|m| {
+            Method::from_bytes(m.to_ascii_uppercase().as_bytes())
+                .map_err(|_| format!("invalid HTTP method `{}`", m))
+        })
+        .collect()
+}
+
 impl IntoMethods for &str {
     fn into_methods(self) -> Vec<Method> {
-        self.split(',')
-            .map(|m| Method::from_str(m.trim()).unwrap())
-            .collect::<Vec<_>>()
+        parse_methods(self).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl IntoMethods for String {
+    fn into_methods(self) -> Vec<Method> {
+        self.as_str().into_methods()
     }
 }
 
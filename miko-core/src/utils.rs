@@ -7,7 +7,8 @@ const SAFE_CHARS: &AsciiSet = &NON_ALPHANUMERIC
     .remove(b':')
     .remove(b'.')
     .remove(b'{')
-    .remove(b'}');
+    .remove(b'}')
+    .remove(b'*');
 
 pub fn encode_route(path: &str) -> String {
     utf8_percent_encode(path, SAFE_CHARS).to_string()
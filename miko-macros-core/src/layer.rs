@@ -0,0 +1,83 @@
+use syn::{Expr, Lit, parse::Parse, parse::ParseStream, parse_quote};
+
+/// Layer 属性信息
+#[derive(Debug, Clone)]
+pub struct LayerAttr {
+    /// Layer 表达式，可以是任意有效的 Rust 表达式
+    /// 例如: TimeoutLayer::new(Duration::from_secs(30))
+    /// 或: timeout_layer()
+    pub layer_expr: Expr,
+}
+
+impl Parse for LayerAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let layer_expr: Expr = input.parse()?;
+        Ok(LayerAttr {
+            layer_expr: expand_sugar(layer_expr),
+        })
+    }
+}
+
+/// 展开内置 layer 的简写：`timeout("5s")` -> `TimeoutLayer::new(Duration::from_millis(5000))`
+fn expand_sugar(expr: Expr) -> Expr {
+    let Expr::Call(call) = &expr else {
+        return expr;
+    };
+    let Expr::Path(func) = &*call.func else {
+        return expr;
+    };
+    if !func.path.is_ident("timeout") || call.args.len() != 1 {
+        return expr;
+    }
+    let Some(Expr::Lit(syn::ExprLit {
+        lit: Lit::Str(value),
+        ..
+    })) = call.args.first()
+    else {
+        return expr;
+    };
+    match parse_duration_ms(&value.value()) {
+        Some(ms) => parse_quote! {
+            ::miko::middleware::timeout::TimeoutLayer::new(::core::time::Duration::from_millis(#ms))
+        },
+        None => {
+            let message = format!(
+                "invalid timeout `{}`, expected e.g. \"500ms\", \"5s\" or \"2m\"",
+                value.value()
+            );
+            let error = quote::quote_spanned!(value.span()=> ::core::compile_error!(#message));
+            parse_quote!(#error)
+        }
+    }
+}
+
+/// 解析 `500ms`、`5s`、`1.5s`、`2m`、`1h` 等时长，返回毫秒数
+fn parse_duration_ms(duration: &str) -> Option<u64> {
+    let duration = duration.trim();
+    let split = duration
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(duration.len());
+    let (number, unit) = duration.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier = match unit.trim() {
+        "ms" => 1.0,
+        "s" => 1_000.0,
+        "m" => 60_000.0,
+        "h" => 3_600_000.0,
+        _ => return None,
+    };
+    Some((number * multiplier) as u64)
+}
+
+/// 从函数属性中提取所有 #[layer(...)] 标记
+pub fn extract_layer_attrs(attrs: &[syn::Attribute]) -> Vec<LayerAttr> {
+    let mut layers = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident("layer")
+            && let Ok(layer_attr) = attr.parse_args::<LayerAttr>()
+        {
+            layers.push(layer_attr);
+        }
+    }
+    layers
+}
@@ -0,0 +1,38 @@
+//! 编写自定义 miko 路由宏的基础组件
+//!
+//! `miko-macros` 内部使用的属性解析与路由注册代码生成都在这里公开，第三方属性宏
+//! 可以直接复用，生成的代码与内置宏一致（需开启 miko 的 `auto` feature）。
+//!
+//! ```rust,ignore
+//! use miko_macros_core::{Method, RouteAttr, RouteRegistration};
+//! use proc_macro::TokenStream;
+//! use quote::quote;
+//! use syn::{ItemFn, parse_macro_input, parse_quote};
+//!
+//! /// `#[authorized_get("/admin")]`：GET 路由并套上鉴权 layer
+//! #[proc_macro_attribute]
+//! pub fn authorized_get(attr: TokenStream, item: TokenStream) -> TokenStream {
+//!     let attr = parse_macro_input!(attr as RouteAttr);
+//!     let func = parse_macro_input!(item as ItemFn);
+//!     let register = RouteRegistration::from_attr(attr, func.sig.ident.clone())
+//!         .method(Method::GET)
+//!         .layer(parse_quote!(crate::auth::AuthLayer::new()))
+//!         .build();
+//!     quote!(#func #register).into()
+//! }
+//! ```
+
+pub mod attr;
+pub mod layer;
+pub mod route;
+
+pub use attr::StrAttrMap;
+pub use hyper::Method;
+pub use layer::{LayerAttr, extract_layer_attrs};
+pub use miko_core::encode_route;
+pub use route::{
+    RouteAlias, RouteAttr, RouteRegistration, build_register_expr, extract_alias_attrs,
+};
+
+#[cfg(test)]
+mod tests;
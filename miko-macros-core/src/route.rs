@@ -0,0 +1,232 @@
+use crate::attr::StrAttrMap;
+use crate::layer::LayerAttr;
+use hyper::Method;
+use miko_core::parse_methods;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+
+/// 路由属性：`("/path", method = "get,post", lenient_query)`
+#[derive(Debug)]
+pub struct RouteAttr {
+    pub path: String,
+    pub method: Option<Vec<Method>>,
+    /// `lenient_query`：查询参数按宽松规则解析
+    pub lenient_query: bool,
+}
+impl Parse for RouteAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attr_map = StrAttrMap::from_parse_stream(input);
+        let path = attr_map.get_or_default("path").unwrap();
+        let methods = attr_map.get("method");
+        let methods = match methods {
+            Some(methods) => {
+                parse_methods(methods).map_err(|e| syn::Error::new(input.span(), e))?
+            }
+            None => {
+                vec![]
+            }
+        };
+        Ok(RouteAttr {
+            path,
+            lenient_query: attr_map.get("lenient_query").is_some(),
+            method: if methods.is_empty() {
+                None
+            } else {
+                Some(methods)
+            },
+        })
+    }
+}
+
+/// 别名属性：`("/path")` 或 `("/path", locale = "de")`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteAlias {
+    pub path: String,
+    /// 通过该路径访问时暴露给 `RouteLocale` 提取器的语言
+    pub locale: Option<String>,
+}
+impl Parse for RouteAlias {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attr_map = StrAttrMap::from_parse_stream(input);
+        let path = attr_map
+            .get_or_default("path")
+            .ok_or_else(|| syn::Error::new(input.span(), "alias path is required"))?;
+        Ok(RouteAlias {
+            path,
+            locale: attr_map.get("locale").cloned(),
+        })
+    }
+}
+
+/// 为路由属性生成注册路由到全局路由器（inventory 提交）的代码片段。
+///
+/// 会根据 `RouteAttr` 中的 method 列表生成对不同 HTTP 方法的 `router.route(...)` 调用。
+/// 如果提供了 layers，会自动包装 handler。
+pub fn build_register_expr(
+    ra: &RouteAttr,
+    fn_name: &Ident,
+    layers: &[LayerAttr],
+    aliases: &[RouteAlias],
+) -> TokenStream {
+    let paths: Vec<(String, Option<String>)> = std::iter::once((ra.path.clone(), None))
+        .chain(aliases.iter().map(|a| (a.path.clone(), a.locale.clone())))
+        .collect();
+    let methods = if let Some(method) = ra.method.clone() {
+        method
+    } else {
+        vec![Method::GET]
+    };
+
+    let mut stmts = Vec::new();
+
+    for ((path, locale), method) in paths
+        .iter()
+        .flat_map(|p| methods.iter().map(move |m| (p, m)))
+    {
+        let method_name = method.as_str();
+        if layers.is_empty() && locale.is_none() && !ra.lenient_query {
+            // 没有 layer，直接注册
+            stmts.push(quote! {
+                router.route(#method_name, #path, #fn_name);
+            });
+        } else {
+            // 有 layers 或语言，使用已有的 service 方法；语言扩展位于最外层
+            let mut layer_exprs: Vec<syn::Expr> =
+                layers.iter().map(|l| l.layer_expr.clone()).collect();
+            if ra.lenient_query {
+                layer_exprs.push(syn::parse_quote! {
+                    ::miko::middleware::extension::ExtensionLayer::new(::miko::extractor::LenientQuery)
+                });
+            }
+            if let Some(locale) = locale {
+                layer_exprs.push(syn::parse_quote! {
+                    ::miko::middleware::extension::ExtensionLayer::new(
+                        ::miko::extractor::RouteLocale::new(#locale)
+                    )
+                });
+            }
+            stmts.push(quote! {
+                {
+                    let __handler = #fn_name;
+                    let __svc = ::miko::handler::handler_to_svc(
+                        ::std::sync::Arc::new(
+                            ::miko::handler::TypedHandler::new(__handler, ::std::sync::Arc::new(()))
+                        )
+                    );
+                    #(
+                        let __svc = {
+                            let layered = ::miko::tower::Layer::layer(&#layer_exprs, __svc);
+                            ::miko::tower::ServiceExt::map_response(layered, |resp| {
+                                let (parts, body) = resp.into_parts();
+                                let body = ::miko::http_body_util::BodyExt::map_err(body, |err| {
+                                    ::miko::miko_core::MikoError(::std::boxed::Box::new(err))
+                                });
+                                let boxed_body = ::miko::http_body_util::BodyExt::boxed_unsync(body);
+                                ::miko::hyper::Response::from_parts(parts, boxed_body)
+                            })
+                        };
+                    )*
+                    let __boxed = ::miko::tower::util::BoxCloneService::new(__svc);
+                    router.service_with(#path, #method_name, __boxed);
+                    router.name_route(#method_name, #path, ::std::any::type_name_of_val(&#fn_name));
+                }
+            });
+        }
+    }
+
+    quote! {
+        ::miko::inventory::submit! {
+            ::miko::auto::RouteFlag {
+                register: |mut router| {
+                    #(#stmts)*
+                    router
+                }
+            }
+        }
+    }
+}
+
+/// 从函数属性中提取所有 #[alias("/path")] 与 #[alias("/path", locale = "de")] 标记
+pub fn extract_alias_attrs(attrs: &[syn::Attribute]) -> Vec<RouteAlias> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("alias"))
+        .map(|attr| {
+            attr.parse_args::<RouteAlias>().unwrap_or_else(|_| {
+                panic!(
+                    "alias must be like #[alias(\"/path\")] or #[alias(\"/path\", locale = \"de\")]"
+                )
+            })
+        })
+        .collect()
+}
+
+/// 路由注册的构建器，生成与 `#[get]` 等宏相同的 inventory 注册代码
+///
+/// 处理函数的参数需自行实现 `FromRequest`，不会经过 `#[path]`、`#[body]` 等参数标注的改写。
+#[derive(Debug)]
+pub struct RouteRegistration {
+    attr: RouteAttr,
+    handler: Ident,
+    layers: Vec<LayerAttr>,
+    aliases: Vec<RouteAlias>,
+}
+
+impl RouteRegistration {
+    pub fn new(path: impl Into<String>, handler: Ident) -> Self {
+        Self::from_attr(
+            RouteAttr {
+                path: path.into(),
+                method: None,
+                lenient_query: false,
+            },
+            handler,
+        )
+    }
+
+    /// 从已解析的路由属性创建
+    pub fn from_attr(attr: RouteAttr, handler: Ident) -> Self {
+        Self {
+            attr,
+            handler,
+            layers: Vec::new(),
+            aliases: Vec::new(),
+        }
+    }
+
+    /// 追加 HTTP 方法，未指定时为 GET
+    pub fn method(mut self, method: Method) -> Self {
+        self.attr.method.get_or_insert_with(Vec::new).push(method);
+        self
+    }
+
+    /// 追加一个 layer 表达式，先追加的在内层
+    pub fn layer(mut self, layer: syn::Expr) -> Self {
+        self.layers.push(LayerAttr { layer_expr: layer });
+        self
+    }
+
+    /// 以相同的处理函数注册额外路径
+    pub fn alias(mut self, path: impl Into<String>) -> Self {
+        self.aliases.push(RouteAlias {
+            path: path.into(),
+            locale: None,
+        });
+        self
+    }
+
+    /// 以相同的处理函数注册本地化路径，处理函数可通过 `RouteLocale` 取得 `locale`
+    pub fn localized_alias(mut self, path: impl Into<String>, locale: impl Into<String>) -> Self {
+        self.aliases.push(RouteAlias {
+            path: path.into(),
+            locale: Some(locale.into()),
+        });
+        self
+    }
+
+    /// 生成 `inventory::submit!` 注册代码
+    pub fn build(&self) -> TokenStream {
+        build_register_expr(&self.attr, &self.handler, &self.layers, &self.aliases)
+    }
+}
@@ -0,0 +1,81 @@
+//! 测试路由注册代码生成
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod tests {
+    use crate::{LayerAttr, Method, RouteAttr, RouteRegistration};
+    use proc_macro2::Span;
+    use quote::ToTokens;
+    use syn::{Ident, parse_quote};
+
+    fn handler() -> Ident {
+        Ident::new("handler", Span::call_site())
+    }
+
+    #[test]
+    fn test_registration_without_layers() {
+        let tokens = RouteRegistration::new("/users", handler())
+            .method(Method::POST)
+            .alias("/members")
+            .build()
+            .to_string();
+
+        assert!(tokens.contains(":: miko :: auto :: RouteFlag"));
+        assert!(tokens.contains("router . route (\"POST\" , \"/users\" , handler)"));
+        assert!(tokens.contains("router . route (\"POST\" , \"/members\" , handler)"));
+    }
+
+    #[test]
+    fn test_registration_with_layers_from_attr() {
+        let attr: RouteAttr = syn::parse_str(r#""/admin", method = "get,put""#).unwrap();
+        let tokens = RouteRegistration::from_attr(attr, handler())
+            .layer(parse_quote!(AuthLayer::new()))
+            .build()
+            .to_string();
+
+        assert!(tokens.contains("AuthLayer :: new ()"));
+        assert!(tokens.contains("router . service_with (\"/admin\" , \"GET\" , __boxed)"));
+        assert!(tokens.contains("router . service_with (\"/admin\" , \"PUT\" , __boxed)"));
+    }
+
+    #[test]
+    fn test_lenient_query_flag_adds_marker_extension() {
+        let attr: RouteAttr = syn::parse_str(r#""/search", lenient_query"#).unwrap();
+        assert!(attr.lenient_query);
+        let tokens = RouteRegistration::from_attr(attr, handler())
+            .build()
+            .to_string();
+
+        assert!(tokens.contains(":: miko :: extractor :: LenientQuery"));
+        assert!(tokens.contains("router . service_with (\"/search\" , \"GET\" , __boxed)"));
+    }
+
+    #[test]
+    fn test_localized_alias_adds_locale_extension() {
+        let tokens = RouteRegistration::new("/about", handler())
+            .localized_alias("/über-uns", "de")
+            .build()
+            .to_string();
+
+        assert!(tokens.contains("router . route (\"GET\" , \"/about\" , handler)"));
+        assert!(tokens.contains(":: miko :: extractor :: RouteLocale :: new (\"de\")"));
+        assert!(tokens.contains("router . service_with (\"/über-uns\" , \"GET\" , __boxed)"));
+    }
+
+    #[test]
+    fn test_timeout_layer_sugar() {
+        let layer: LayerAttr = syn::parse_str(r#"timeout("1.5s")"#).unwrap();
+        let tokens = layer.layer_expr.to_token_stream().to_string();
+        assert!(tokens.contains(":: miko :: middleware :: timeout :: TimeoutLayer :: new"));
+        assert!(tokens.contains("from_millis (1500u64)"));
+
+        let layer: LayerAttr = syn::parse_str(r#"timeout("soon")"#).unwrap();
+        let tokens = layer.layer_expr.to_token_stream().to_string();
+        assert!(tokens.contains("compile_error"));
+
+        // 其他表达式原样保留
+        let layer: LayerAttr = syn::parse_str("my::timeout(Duration::from_secs(1))").unwrap();
+        let tokens = layer.layer_expr.to_token_stream().to_string();
+        assert_eq!(tokens, "my :: timeout (Duration :: from_secs (1))");
+    }
+}
@@ -1,12 +1,47 @@
 use crate::toolkit::rout_arg::{FnArgResult, RouteFnArg};
-use syn::{Type, parse_quote};
+use syn::{FnArg, Type, parse_quote};
 
 /// 处理带有 `#[body]` 标记或默认按类型推断的 body 参数。
 ///
 /// - 如果显式标注 `str` 则保持原始类型（字符串）；
 /// - 否则默认替换为 Json 提取器 `Json<T>`；
-/// - 对于未标注但类型为 `String` 的参数也直接保持字符串类型。
+/// - 对于未标注但类型为 `String` 的参数也直接保持字符串类型；
+/// - `limit = "2MB"` 时再包一层 `WithBodyLimit`，为该路由设置请求体大小限制。
 pub fn deal_with_body_attr(rfa: &RouteFnArg) -> FnArgResult {
+    let result = body_arg(rfa);
+    let limit = rfa.mark.get("body").and_then(|map| map.get("limit"));
+    match (result, limit) {
+        (FnArgResult::Replace(FnArg::Typed(pat)), Some(limit)) => {
+            let bytes = parse_size(limit)
+                .unwrap_or_else(|| panic!("invalid body limit `{}`, expected e.g. \"2MB\"", limit));
+            let (inner_pat, inner_ty) = (&pat.pat, &pat.ty);
+            FnArgResult::Replace(parse_quote!(
+                ::miko::extractor::body_limit::WithBodyLimit(#inner_pat): ::miko::extractor::body_limit::WithBodyLimit<#inner_ty, #bytes>
+            ))
+        }
+        (result, _) => result,
+    }
+}
+
+/// 解析 `512KB`、`2MB` 等大小字符串（1024 进制），与运行时的 `parse_size` 保持一致
+fn parse_size(size: &str) -> Option<usize> {
+    let size = size.trim();
+    let split = size
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return None,
+    };
+    Some((number * multiplier as f64) as usize)
+}
+
+fn body_arg(rfa: &RouteFnArg) -> FnArgResult {
     if rfa.mark.contains_key("body") {
         let ident = rfa.ident.clone();
         let ty = rfa.ty.clone();
@@ -1,17 +1,48 @@
-use crate::toolkit::rout_arg::{FnArgResult, RouteFnArg};
-use syn::parse_quote;
+use crate::toolkit::rout_arg::RouteFnArg;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::FnArg;
 
-/// 处理带有 `#[path]` 标记的参数，将其替换为 Path 提取器形式（`Path(ident): Path<T>`）。
+/// 为带有 `#[path]` / `#[path("name")]` 的参数生成参数名类型与 `NamedPath` 提取器参数。
 ///
-/// 若参数未标记为 `path` 则返回 `FnArgResult::Remove`，表示宏应去掉该参数。
-pub fn deal_with_path_attr(rfa: &RouteFnArg) -> FnArgResult {
-    if rfa.mark.contains_key("path") {
-        let ident = rfa.ident.clone();
-        let ty = rfa.ty.clone();
-        FnArgResult::Replace(parse_quote!(
-            ::miko::extractor::Path(#ident): ::miko::extractor::Path<#ty>
-        ))
-    } else {
-        FnArgResult::Remove
+/// 按名称匹配路由中的同名参数（未指定名称时使用参数名），路由中没有同名参数时按声明顺序取值。
+/// `#[path(default = ..)]` / `#[path(default_fn = "f")]` 的参数在路由中没有对应参数时使用缺省值。
+pub fn build_path_extractors(
+    rfa: &Vec<RouteFnArg>,
+    fn_name: &syn::Ident,
+) -> (Vec<TokenStream>, Vec<FnArg>) {
+    let mut items = Vec::new();
+    let mut inputs = Vec::new();
+    for rfa in rfa {
+        if rfa.mark.contains_key("path") {
+            let name = path_name(rfa);
+            let ident = &rfa.ident;
+            let ty = &rfa.ty;
+            let key = quote::format_ident!("__{}_{}_PathKey", fn_name, ident);
+            items.push(quote! {
+                #[allow(non_camel_case_types)]
+                struct #key;
+                impl ::miko::extractor::path_params::PathKey for #key {
+                    const NAME: &'static str = #name;
+                }
+            });
+            // 声明了缺省值时按 Option 取值，由 build_default_stmts 补上缺省值
+            inputs.push(match rfa.default_expr("path") {
+                Some(_) => syn::parse_quote! {
+                    ::miko::extractor::path_params::OptionalNamedPath(#ident, _): ::miko::extractor::path_params::OptionalNamedPath<#ty, #key>
+                },
+                None => syn::parse_quote! {
+                    ::miko::extractor::path_params::NamedPath(#ident, _): ::miko::extractor::path_params::NamedPath<#ty, #key>
+                },
+            });
+        }
     }
+    (items, inputs)
+}
+
+/// `#[path]` 参数对应的路由参数名
+fn path_name(rfa: &RouteFnArg) -> String {
+    rfa.mark["path"]
+        .get_or_default("name")
+        .unwrap_or_else(|| rfa.ident.to_string().trim_start_matches("r#").to_string())
 }
@@ -2,7 +2,7 @@ use crate::route::RouteAttr;
 use crate::route::core::route_handler;
 use crate::toolkit::attr::StrAttrMap;
 #[cfg(feature = "auto")]
-use crate::toolkit::impl_operation::{get_constructor, inject_deps};
+use crate::toolkit::impl_operation::{get_constructor, inject_deps, split_bind_args};
 use crate::toolkit::rout_arg::{
     FnArgResult, IntoFnArgs, RouteFnArg, build_clone_stmt, build_config_value_injector,
     build_dep_injector,
@@ -14,6 +14,7 @@ use syn::{ItemFn, ItemMod, parse_macro_input};
 mod extractor;
 mod mod_transform;
 mod route;
+mod sse_event;
 mod toolkit;
 
 #[cfg(feature = "utoipa")]
@@ -27,11 +28,15 @@ mod utoipa;
 /// 参数标注：
 /// - `#[path]`：从路径中提取（如 `/users/{id}`）；
 /// - `#[query]`：从查询字符串构建结构并注入；
-/// - `#[body]`：从请求体反序列化（默认 JSON；标记 `str` 可保留为 String）；
+/// - `#[body]`：从请求体反序列化（默认 JSON；标记 `str` 可保留为 String，`limit = "2MB"` 设置请求体大小限制）；
 /// - `#[dep]`：注入全局依赖（参数类型通常为 `Arc<T>`，需先注册该组件）；
 /// - `#[config("key")]`/`#[config(path = "key")]`：从应用配置读取并解析为参数类型。
+/// - `#[header]`/`#[header("name")]`：按名称提取单个请求头（缺省为参数名，`_` 视为 `-`），`Option<T>` 表示可选；
 /// - `#[desc("描述")]`：为参数添加描述（启用 utoipa 时会生成 OpenAPI 文档）；
 ///
+/// 路由参数 `lenient_query`（如 `#[get("/search", lenient_query)]`）使 `#[query]` 与 `Query<T>`
+/// 宽松解析：布尔值接受 `1`/`on`/`yes`，数字忽略两侧空白，`Option` 参数的空字符串视为 `None`。
+///
 /// 注意：
 /// - 仅当同时启用 `auto` feature 且应用通过 `#[miko]` 启动时，框架才会自动收集并注册由这些宏生成的路由；
 /// - 若未启用 `auto`，`route`/派生宏及 `#[dep]` 不会触发框架级的自动注册或依赖注入——此时需要在你的初始化代码中手动注册路由与依赖；
@@ -54,6 +59,68 @@ pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
     route_handler(args, fn_item)
 }
 
+/// # WebSocket 路由宏
+///
+/// 注册一个 `GET` 路由并完成 WebSocket 升级，处理器直接接收 `WsSocket`，
+/// 函数体在升级成功后运行。其余参数照常使用 `#[dep]`、`#[query]`、`#[path]` 等，在升级前提取。
+///
+/// `protocols = "chat,json"` 声明服务端支持的子协议。
+///
+/// ```rust,ignore
+/// #[ws("/chat/{room}")]
+/// async fn chat(mut socket: WsSocket, #[path] room: String, #[dep] hub: Arc<ChatHub>) {
+///     while let Some(Ok(msg)) = socket.recv().await {
+///         // ...
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn ws(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let fn_item = parse_macro_input!(item as ItemFn);
+    route::ws::ws_handler(attr, fn_item)
+}
+
+/// SSE 路由宏：注册为 GET 路由，以 `text/event-stream` 推送事件
+///
+/// 处理器可以声明 `TypedSseSender<E>`（或无类型的 `SseSender`）参数，函数体在后台任务中运行；
+/// 也可以返回 `impl Stream<Item = E>`。`E` 通常是派生了 [`IntoSseEvent`](macro@IntoSseEvent) 的枚举，
+/// 启用 utoipa 时其事件名会写入 OpenAPI 文档。其余参数与 `#[get]` 相同。
+///
+/// ```rust,ignore
+/// #[sse("/jobs/{id}/events")]
+/// async fn job_events(tx: TypedSseSender<JobEvent>, #[path] id: u64, #[dep] jobs: Arc<Jobs>) {
+///     while let Some(event) = jobs.next_event(id).await {
+///         tx.send(event).await.or_break();
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn sse(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let fn_item = parse_macro_input!(item as ItemFn);
+    route::sse::sse_handler(attr, fn_item)
+}
+
+/// 为事件枚举派生 `IntoSseEvent` 与 `SseEventTypes`
+///
+/// - 事件名默认为变体名的 snake_case，`#[sse_event(name = "...")]` 覆盖；
+/// - `#[sse_event(retry = 3000)]` 为该事件附带重试时间（毫秒）；
+/// - 字段上的 `#[sse_event(id)]` 作为事件 id（需实现 `Display`）；
+/// - data 为变体负载的 JSON。
+///
+/// ```rust,ignore
+/// #[derive(IntoSseEvent)]
+/// enum JobEvent {
+///     Progress { #[sse_event(id)] seq: u64, percent: u8 },
+///     #[sse_event(name = "done", retry = 5000)]
+///     Finished(String),
+/// }
+/// ```
+#[proc_macro_derive(IntoSseEvent, attributes(sse_event))]
+pub fn derive_into_sse_event(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as syn::DeriveInput);
+    sse_event::derive_into_sse_event(input)
+}
+
 /// # Miko宏
 /// 自动配置
 /// - 展开出#\[tokio::main]
@@ -92,8 +159,10 @@ pub fn miko(attr: TokenStream, item: TokenStream) -> TokenStream {
         None
     };
     let build_sign = str_attr_map.map.contains_key("build");
+    // 先执行自动注册的插件，插件注册的组件才能参与依赖校验
     let dep_init = if cfg!(feature = "auto") {
         quote! {
+            ::miko::app::plugin::configure_registered_plugins(&mut router, &mut _config);
             ::miko::auto::init_container().await;
         }
     } else {
@@ -175,6 +244,16 @@ derive_route_macro!(connect, CONNECT);
 /// - 构造函数应为 `async fn new(...) -> Self`；构造函数的参数可以声明其它组件的依赖（以 `Arc<T>` 形式）；
 /// - 注册后的组件可在处理器参数上使用 `#[dep]` 标注注入（当启用 `auto` 时）。
 ///
+/// - 生命周期通过 `lifetime = "singleton" | "transient" | "scoped"`（或 `transient`、`scoped` 简写）指定，
+///   默认单例；`scoped` 在一次请求内共享同一实例，只能在处理器的 `#[dep]` 中注入；
+/// - `bind = dyn MyTrait` 同时以 trait 对象登记，处理器可用 `#[dep] svc: Arc<dyn MyTrait>` 注入，
+///   trait 需要 `Send + Sync`；可重复使用以绑定多个 trait；
+/// - `name = "primary"` 以名称注册，同一类型可注册多个实例，配合 `#[dep(name = "primary")]` 注入；
+///   此时可用 `constructor = "primary"` 指定其它构造函数（默认为 `new`）；
+/// - 同一类型与名称只能注册一次，重复注册会在启动时报错；`replace` 标记有意的覆盖，该定义优先生效；
+/// - `health_check` 将类型的 `async fn health(&self)` 注册为就绪检查（需要 `ext`），
+///   检查名默认为类型名，可用 `health_check = "db"` 指定；不支持 `scoped`。
+///
 /// `prewarm` 生效条件：仅在应用通过 `#[miko]` 启动（并启用 `auto`）时才会在启动阶段触发预热。
 ///
 /// 示例：
@@ -191,66 +270,74 @@ derive_route_macro!(connect, CONNECT);
 pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
     use quote::format_ident;
     use syn::{ItemImpl, TypePath};
-    let args = syn::parse_macro_input!(attr as StrAttrMap);
-    let input_struct = parse_macro_input!(input as ItemImpl);
+    let (attr, binds) = split_bind_args(attr.into());
+    let args: StrAttrMap = match syn::parse2(attr) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let mut input_struct = parse_macro_input!(input as ItemImpl);
     let prewarm = args.get("prewarm").is_some();
-    let mut lifetime_singleton = true;
-    let mut lifetime_specified = false;
-
+    let replace = args.get("replace").is_some();
+    let name = args
+        .get("name")
+        .cloned()
+        .unwrap_or_else(|| "___".to_string());
+    let constructor = args
+        .get("constructor")
+        .cloned()
+        .unwrap_or_else(|| "new".to_string());
+    let mut lifetime: Option<String> = None;
     let mut set_lifetime = |mode: &str| {
         let normalized = mode.to_ascii_lowercase();
-        match normalized.as_str() {
-            "singleton" => {
-                if lifetime_specified && !lifetime_singleton {
-                    panic!(
-                        "Conflicting #[component] lifetime: both singleton and transient specified"
-                    );
-                }
-                lifetime_singleton = true;
-                lifetime_specified = true;
-            }
-            "transient" => {
-                if lifetime_specified && lifetime_singleton {
-                    panic!(
-                        "Conflicting #[component] lifetime: both singleton and transient specified"
-                    );
-                }
-                lifetime_singleton = false;
-                lifetime_specified = true;
-            }
-            _ => panic!(
-                "Invalid #[component] lifetime '{}'. Expected `singleton` or `transient`.",
+        if !matches!(normalized.as_str(), "singleton" | "transient" | "scoped") {
+            panic!(
+                "Invalid #[component] lifetime '{}'. Expected `singleton`, `transient` or `scoped`.",
                 mode
-            ),
+            );
         }
+        if let Some(prev) = &lifetime
+            && *prev != normalized
+        {
+            panic!(
+                "Conflicting #[component] lifetime: both {} and {} specified",
+                prev, normalized
+            );
+        }
+        lifetime = Some(normalized);
     };
 
-    if let Some(mode) = args.get("mode") {
+    if let Some(mode) = args.get("lifetime").or(args.get("mode")) {
         set_lifetime(mode);
     } else if let Some(default_mode) = args.default.as_ref() {
         set_lifetime(default_mode);
     }
 
-    if args.map.contains_key("singleton") {
-        set_lifetime("singleton");
-    }
-    if args.map.contains_key("transient") {
-        set_lifetime("transient");
+    for mode in ["singleton", "transient", "scoped"] {
+        if args.map.contains_key(mode) {
+            set_lifetime(mode);
+        }
     }
+    let lifetime = lifetime.unwrap_or_else(|| "singleton".to_string());
 
-    if prewarm && !lifetime_singleton {
+    if prewarm && lifetime != "singleton" {
         panic!(
-            "`#[component(prewarm, transient)]` is invalid because transient components cannot be prewarmed"
+            "`#[component(prewarm, {})]` is invalid because only singleton components can be prewarmed",
+            lifetime
         );
     }
 
-    let lifetime_tokens = if lifetime_singleton {
-        quote!(::miko::dependency_container::DependencyLifetime::Singleton)
-    } else {
-        quote!(::miko::dependency_container::DependencyLifetime::Transient)
+    if args.get("health_check").is_some() && lifetime == "scoped" {
+        panic!("`#[component(health_check)]` is invalid for scoped components");
+    }
+
+    let lifetime_tokens = match lifetime.as_str() {
+        "transient" => quote!(::miko::dependency_container::DependencyLifetime::Transient),
+        "scoped" => quote!(::miko::dependency_container::DependencyLifetime::Scoped),
+        _ => quote!(::miko::dependency_container::DependencyLifetime::Singleton),
     };
     let mut depend_get_stmts = Vec::new();
     let mut arg_idents = Vec::new();
+    let mut dep_refs = Vec::new();
     let type_ident = match *input_struct.self_ty.clone() {
         syn::Type::Path(TypePath { path, .. }) => path
             .segments
@@ -259,38 +346,230 @@ pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
             .unwrap_or_else(|| format_ident!("UnknowType")),
         _ => format_ident!("UnknowType"),
     };
-    if let Some(method) = get_constructor(&input_struct.items) {
+    if let Some(method) = get_constructor(&mut input_struct.items, &constructor) {
         if method.sig.asyncness.is_none() {
-            panic!("service method new must be async")
+            panic!("service method {} must be async", constructor)
         }
-        let args = &method.sig.inputs;
-        inject_deps(args, &mut depend_get_stmts, &mut arg_idents);
+        inject_deps(
+            &mut method.sig.inputs,
+            &mut depend_get_stmts,
+            &mut arg_idents,
+            &mut dep_refs,
+        );
     }
+    let constructor = format_ident!("{}", constructor);
+    // 以 trait 对象的 TypeId 再登记一次，实例以 `Arc<dyn Trait>` 存放；
+    // 单例复用具体类型的实例，其余生命周期直接构造
+    let bind_defs = binds.iter().map(|bind| {
+        let bind_deps = if lifetime == "singleton" {
+            quote!(vec![::miko::dependency_container::DependencyRef::of::<#type_ident>(#name)])
+        } else {
+            quote!(vec![#(#dep_refs),*])
+        };
+        let build = if lifetime == "singleton" {
+            quote! {
+                let container = ::miko::dependency_container::CONTAINER.get().unwrap().read().await;
+                let val: ::std::sync::Arc<#bind> = container.get_::<#type_ident>(#name).await;
+            }
+        } else {
+            quote! {
+                #(#depend_get_stmts)*
+                let val: ::std::sync::Arc<#bind> =
+                    ::std::sync::Arc::new(#type_ident::#constructor(#(#arg_idents),*).await);
+            }
+        };
+        quote! {
+            ::miko::inventory::submit! {
+                ::miko::dependency_container::DependencyDefFn(|| {
+                    ::miko::dependency_container::DependencyDef {
+                        type_id: std::any::TypeId::of::<#bind>(),
+                        type_name: std::any::type_name::<#bind>(),
+                        prewarm: false,
+                        name: #name,
+                        lifetime: #lifetime_tokens,
+                        deps: || #bind_deps,
+                        source: concat!(file!(), ":", line!()),
+                        replace: #replace,
+                        init_fn: || {
+                            Box::pin(async move {
+                                #build
+                                ::std::sync::Arc::new(val) as ::std::sync::Arc<dyn ::std::any::Any + Send + Sync>
+                            })
+                        }
+                    }
+                })
+            }
+        }
+    });
+    // 就绪检查时从容器取得实例并调用其 `health()`
+    let health_def = args.get("health_check").map(|check| {
+        let check_name = if check != "health_check" {
+            check.clone()
+        } else if name != "___" {
+            name.clone()
+        } else {
+            type_ident.to_string()
+        };
+        quote! {
+            ::miko::inventory::submit! {
+                ::miko::ext::health::HealthCheckDef {
+                    name: #check_name,
+                    check: || {
+                        Box::pin(async move {
+                            let val: ::std::sync::Arc<#type_ident> = {
+                                let container = ::miko::dependency_container::CONTAINER
+                                    .get()
+                                    .ok_or_else(|| "dependency container is not initialized".to_string())?
+                                    .read()
+                                    .await;
+                                container.get_::<#type_ident>(#name).await
+                            };
+                            ::miko::ext::health::IntoHealth::into_health(val.health().await)
+                        })
+                    }
+                }
+            }
+        }
+    });
     quote! {
         #input_struct
+        #health_def
         ::miko::inventory::submit! {
             ::miko::dependency_container::DependencyDefFn(|| {
                 ::miko::dependency_container::DependencyDef {
                     type_id: std::any::TypeId::of::<#type_ident>(),
+                    type_name: std::any::type_name::<#type_ident>(),
                     prewarm: #prewarm,
-                    name: "___",
+                    name: #name,
                     lifetime: #lifetime_tokens,
+                    deps: || vec![#(#dep_refs),*],
+                    source: concat!(file!(), ":", line!()),
+                    replace: #replace,
                     init_fn: || {
                         Box::pin(async move {
                             #(#depend_get_stmts)*
-                            let val: #type_ident = #type_ident::new(#(#arg_idents),*).await;
+                            let val: #type_ident = #type_ident::#constructor(#(#arg_idents),*).await;
                             ::std::sync::Arc::new(val) as ::std::sync::Arc<dyn ::std::any::Any + Send + Sync>
                         })
                     }
                 }
             })
         }
+        #(#bind_defs)*
     }.into()
 }
 
+#[cfg(feature = "auto")]
+/// 启动任务宏：将异步函数登记为启动任务，在依赖容器初始化之后、开始监听之前执行一次。
+///
+/// - 参数只能使用 `#[dep]`（`Arc<T>`，可带 `name = "..."`）与 `#[config("path")]` 标注，启动时注入；
+/// - 返回值可以是 `()` 或 `Result<(), E>`，返回错误时服务不会启动；
+/// - `order = 10` 指定执行顺序，越小越先执行，默认 0。
+///
+/// 仅在应用通过 `#[miko]` 启动（并启用 `auto`）时执行；`#[miko(build)]` 构建的应用可调用
+/// `miko::app::startup::run_startup_tasks()` 手动执行。
+///
+/// 示例：
+/// ```rust,ignore
+/// #[on_startup(order = 10)]
+/// async fn prime_cache(#[dep] cache: Arc<Cache>, #[config("cache.preload")] keys: Vec<String>) -> anyhow::Result<()> {
+///     cache.load(&keys).await
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn on_startup(attr: TokenStream, item: TokenStream) -> TokenStream {
+    use crate::toolkit::rout_arg::{build_dep_requirement, is_arc, is_dyn};
+    let args = parse_macro_input!(attr as StrAttrMap);
+    let mut input_fn = parse_macro_input!(item as ItemFn);
+    if input_fn.sig.asyncness.is_none() {
+        panic!(
+            "#[on_startup] function {} must be async",
+            input_fn.sig.ident
+        );
+    }
+    let order: i32 = args
+        .get("order")
+        .map(|o| o.parse().expect("#[on_startup] order must be an integer"))
+        .unwrap_or(0);
+    let fn_name = input_fn.sig.ident.clone();
+    let fn_args = RouteFnArg::from_punctuated(&mut input_fn.sig.inputs);
+    let mut inject_stmts = Vec::new();
+    let mut arg_idents = Vec::new();
+    let mut has_deps = false;
+    for rfa in &fn_args {
+        let ident = &rfa.ident;
+        let ty = &rfa.ty;
+        if rfa.marked_by("dep") {
+            let Some(inner) = is_arc(ty).1 else {
+                panic!("dep param must be a Arc<T>");
+            };
+            let getter = match (rfa.mark["dep"].get("name"), is_dyn(&inner)) {
+                (Some(name), false) => quote!(get_::<#inner>(#name)),
+                (None, false) => quote!(get::<#inner>()),
+                (Some(name), true) => quote!(get_dyn_::<#inner>(#name)),
+                (None, true) => quote!(get_dyn::<#inner>()),
+            };
+            inject_stmts.push(quote! {
+                let #ident: #ty = __dep_container.#getter.await;
+            });
+            has_deps = true;
+        } else if rfa.marked_by("config") {
+            let path = rfa.mark["config"]
+                .get_or_default("path")
+                .unwrap_or_else(|| {
+                    panic!("config param must be like #[config(\"xx\")] or #[config(path=\"xx\")] ")
+                });
+            inject_stmts.push(quote! {
+                let #ident = ::miko::app::config::get_settings_value::<#ty>(#path)?;
+            });
+        } else {
+            panic!("#[on_startup] only supports #[dep] or #[config] parameters");
+        }
+        arg_idents.push(ident.clone());
+    }
+    let container = has_deps.then(|| {
+        quote! {
+            let __dep_container = ::miko::dependency_container::get_global_dc().await;
+        }
+    });
+    // 参数上的标注只供本宏读取，保留函数本身以便直接调用
+    for input in input_fn.sig.inputs.iter_mut() {
+        if let syn::FnArg::Typed(pat) = input {
+            pat.attrs.clear();
+        }
+    }
+    let dep_requirement = build_dep_requirement(&fn_args, &fn_name);
+    quote! {
+        #input_fn
+        #dep_requirement
+        ::miko::inventory::submit! {
+            ::miko::app::startup::StartupTask {
+                name: concat!(module_path!(), "::", stringify!(#fn_name)),
+                order: #order,
+                source: concat!(file!(), ":", line!()),
+                run: || {
+                    Box::pin(async move {
+                        let (#(#arg_idents,)*) = {
+                            #container
+                            #(#inject_stmts)*
+                            (#(#arg_idents,)*)
+                        };
+                        ::miko::app::startup::IntoStartupResult::into_startup_result(
+                            #fn_name(#(#arg_idents),*).await,
+                        )
+                    })
+                }
+            }
+        }
+    }
+    .into()
+}
+
 // ==================== Utoipa 辅助宏 ====================
+//
+// 这些宏只是供路由宏读取的标记，本身不做转换。未启用 `utoipa` feature 时同样可用，
+// 标注过的处理器在两种配置下都能编译。
 
-#[cfg(feature = "utoipa")]
 /// 标记响应信息
 ///
 /// 用法：
@@ -303,7 +582,6 @@ pub fn u_response(_attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
-#[cfg(feature = "utoipa")]
 /// 标记 API 标签
 ///
 /// 用法：
@@ -315,7 +593,18 @@ pub fn u_tag(_attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
-#[cfg(feature = "utoipa")]
+/// 标记路由所属的 OpenAPI 文档，未标记的路由属于默认文档
+///
+/// 用法：
+/// ```rust,ignore
+/// #[u_doc("internal")]
+/// #[u_doc("internal", "admin")]
+/// ```
+#[proc_macro_attribute]
+pub fn u_doc(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
 /// 标记 API 摘要
 ///
 /// 用法：
@@ -327,7 +616,6 @@ pub fn u_summary(_attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
-#[cfg(feature = "utoipa")]
 /// 标记 API 详细描述
 ///
 /// 用法：
@@ -339,7 +627,6 @@ pub fn u_description(_attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
-#[cfg(feature = "utoipa")]
 /// 标记自定义请求体
 ///
 /// 用于处理无法自动推断的请求体类型，比如 `Multipart`。
@@ -369,7 +656,6 @@ pub fn u_request_body(_attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
-#[cfg(feature = "utoipa")]
 /// 标记参数补充信息
 ///
 /// 用法：
@@ -381,7 +667,6 @@ pub fn u_param(_attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
-#[cfg(feature = "utoipa")]
 /// 标记 API 已弃用
 ///
 /// 用法：
@@ -515,6 +800,207 @@ pub fn layer(attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
+/// # Alias 宏：为路由函数注册额外路径
+///
+/// 与路由宏一起使用，同一个处理函数会以相同的方法与 layer 注册到每个别名路径下，
+/// 无需复制函数。位于 `#[prefix]` 模块中时别名同样会加上前缀。
+///
+/// ```rust,ignore
+/// #[get("/users/{id}")]
+/// #[alias("/legacy/user/{id}")]
+/// #[alias("/u/{id}")]
+/// async fn get_user(#[path] id: i32) -> String { /* ... */ }
+/// ```
+///
+/// 指定 `locale` 时作为本地化路径注册，处理函数可通过 `RouteLocale` 提取器取得匹配的语言：
+///
+/// ```rust,ignore
+/// #[get("/about")]
+/// #[alias("/über-uns", locale = "de")]
+/// #[alias("/a-propos", locale = "fr")]
+/// async fn about(locale: RouteLocale) -> String { /* ... */ }
+/// ```
+///
+/// 注意：OpenAPI 文档只包含主路径。
+#[proc_macro_attribute]
+pub fn alias(attr: TokenStream, item: TokenStream) -> TokenStream {
+    // 写在路由宏之前时，把自身移到路由宏之后，交由路由宏处理
+    if let Ok(mut fn_item) = syn::parse::<ItemFn>(item.clone())
+        && fn_item.attrs.iter().any(|a| {
+            a.path()
+                .get_ident()
+                .is_some_and(|i| mod_transform::ROUTE_MACROS.contains(&i.to_string().as_str()))
+        })
+    {
+        let attr = proc_macro2::TokenStream::from(attr);
+        fn_item.attrs.push(syn::parse_quote!(#[alias(#attr)]));
+        return quote! { #fn_item }.into();
+    }
+    item
+}
+
+/// # RequireHeader 宏：声明路由必需的请求头
+///
+/// 在提取器之前校验请求头：缺失时返回 400；指定 `matches` 时值需完整匹配该正则，
+/// 不匹配时返回 `status`（默认 400）。启用 `utoipa` 时会自动生成对应的 header 参数文档。
+///
+/// 可用于路由函数，也可用于模块（作用于模块内所有路由）。
+///
+/// ```rust,ignore
+/// #[get("/items")]
+/// #[require_header("X-Api-Version", matches = "2\\.\\d+", status = 426)]
+/// async fn items() -> &'static str { "v2" }
+///
+/// #[require_header("X-Tenant")]
+/// mod tenant_api {
+///     #[get("/orders")]
+///     async fn orders() { }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn require_header(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if let Ok(mut mod_item) = syn::parse::<ItemMod>(item.clone()) {
+        mod_transform::apply_transform_to_module(
+            &mut mod_item,
+            mod_transform::TransformOp::RequireHeader(attr.to_string()),
+        );
+        return quote! { #mod_item }.into();
+    }
+    // 写在路由宏之前时，把自身移到路由宏之后，交由路由宏处理
+    if let Ok(mut fn_item) = syn::parse::<ItemFn>(item.clone())
+        && fn_item.attrs.iter().any(|a| {
+            a.path()
+                .get_ident()
+                .is_some_and(|i| mod_transform::ROUTE_MACROS.contains(&i.to_string().as_str()))
+        })
+    {
+        let attr = proc_macro2::TokenStream::from(attr);
+        fn_item
+            .attrs
+            .push(syn::parse_quote!(#[require_header(#attr)]));
+        return quote! { #fn_item }.into();
+    }
+    item
+}
+
+/// # Guard 宏：要求请求已认证或拥有指定角色
+///
+/// 使用 `miko::ext::auth` 中安装的认证器（或外层 `RequireAuthLayer` 的结果）判断，
+/// 未认证返回 401，缺少角色返回 403。多个 `#[guard]` 需同时满足。
+///
+/// 可用于路由函数，也可用于模块（作用于模块内所有路由）。
+///
+/// ```rust,ignore
+/// #[delete("/users/{id}")]
+/// #[guard(role = "admin")]
+/// async fn remove_user(#[path] id: u64) { }
+///
+/// #[guard]
+/// mod account {
+///     #[get("/me")]
+///     async fn me(principal: Principal) -> String { principal.subject }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn guard(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if let Ok(mut mod_item) = syn::parse::<ItemMod>(item.clone()) {
+        mod_transform::apply_transform_to_module(
+            &mut mod_item,
+            mod_transform::TransformOp::Guard(attr.to_string()),
+        );
+        return quote! { #mod_item }.into();
+    }
+    // 写在路由宏之前时，把自身移到路由宏之后，交由路由宏处理
+    if let Ok(mut fn_item) = syn::parse::<ItemFn>(item.clone())
+        && fn_item.attrs.iter().any(|a| {
+            a.path()
+                .get_ident()
+                .is_some_and(|i| mod_transform::ROUTE_MACROS.contains(&i.to_string().as_str()))
+        })
+    {
+        let attr = proc_macro2::TokenStream::from(attr);
+        fn_item.attrs.push(syn::parse_quote!(#[guard(#attr)]));
+        return quote! { #fn_item }.into();
+    }
+    item
+}
+
+/// # DeprecatedRoute 宏：标记路由已弃用
+///
+/// 响应附带 `Deprecation`、`Sunset`（指定 `sunset` 时）与 `Link`（指定 `link` 时）头，
+/// 每次调用计入 `miko::middleware::deprecation::deprecated_route_hits()`；
+/// 启用 `utoipa` 时同时在 OpenAPI 中标记为 deprecated。
+///
+/// 可用于路由函数，也可用于模块（作用于模块内所有路由）。
+///
+/// ```rust,ignore
+/// #[get("/v1/users")]
+/// #[deprecated_route(sunset = "2025-12-31", link = "https://example.com/migrate")]
+/// async fn list_users_v1() -> &'static str { "[]" }
+/// ```
+#[proc_macro_attribute]
+pub fn deprecated_route(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if let Ok(mut mod_item) = syn::parse::<ItemMod>(item.clone()) {
+        mod_transform::apply_transform_to_module(
+            &mut mod_item,
+            mod_transform::TransformOp::DeprecatedRoute(attr.to_string()),
+        );
+        return quote! { #mod_item }.into();
+    }
+    // 写在路由宏之前时，把自身移到路由宏之后，交由路由宏处理
+    if let Ok(mut fn_item) = syn::parse::<ItemFn>(item.clone())
+        && fn_item.attrs.iter().any(|a| {
+            a.path()
+                .get_ident()
+                .is_some_and(|i| mod_transform::ROUTE_MACROS.contains(&i.to_string().as_str()))
+        })
+    {
+        let attr = proc_macro2::TokenStream::from(attr);
+        fn_item
+            .attrs
+            .push(syn::parse_quote!(#[deprecated_route(#attr)]));
+        return quote! { #fn_item }.into();
+    }
+    item
+}
+
+/// # Cached 宏：缓存路由的响应
+///
+/// 使用 `miko::ext::cache::Cache::global()` 缓存成功的 `GET`/`HEAD` 响应 `ttl` 秒（缺省 60），
+/// 以方法、路径与查询字符串为键（并入响应 `Vary` 列出的请求头），命中时不调用处理函数并附带 `X-Cache: HIT`。
+/// 带 `Authorization` 或 `Cookie` 的请求不走缓存；确认响应与调用者无关时可用 `allow_credentials = true` 开启。
+///
+/// 可用于路由函数，也可用于模块（作用于模块内所有路由）。
+///
+/// ```rust,ignore
+/// #[get("/products")]
+/// #[cached(ttl = 60)]
+/// async fn list_products(#[query] page: u32) -> Json<Vec<Product>> { /* ... */ }
+/// ```
+#[proc_macro_attribute]
+pub fn cached(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if let Ok(mut mod_item) = syn::parse::<ItemMod>(item.clone()) {
+        mod_transform::apply_transform_to_module(
+            &mut mod_item,
+            mod_transform::TransformOp::Cached(attr.to_string()),
+        );
+        return quote! { #mod_item }.into();
+    }
+    // 写在路由宏之前时，把自身移到路由宏之后，交由路由宏处理
+    if let Ok(mut fn_item) = syn::parse::<ItemFn>(item.clone())
+        && fn_item.attrs.iter().any(|a| {
+            a.path()
+                .get_ident()
+                .is_some_and(|i| mod_transform::ROUTE_MACROS.contains(&i.to_string().as_str()))
+        })
+    {
+        let attr = proc_macro2::TokenStream::from(attr);
+        fn_item.attrs.push(syn::parse_quote!(#[cached(#attr)]));
+        return quote! { #fn_item }.into();
+    }
+    item
+}
+
 #[cfg(feature = "utoipa")]
 /// 仅生成 OpenAPI 文档，不自动注册路由
 ///
@@ -1,6 +1,7 @@
 use syn::{Item, ItemFn, ItemMod, LitStr, parse::Parse};
 
 use crate::StrAttrMap;
+use crate::route::RouteAlias;
 
 #[derive(Clone)]
 pub enum TransformOp {
@@ -8,6 +9,14 @@ pub enum TransformOp {
     Prefix(String),
     /// 添加 layer
     Layer(String),
+    /// 添加请求头要求
+    RequireHeader(String),
+    /// 添加认证守卫
+    Guard(String),
+    /// 标记为弃用
+    DeprecatedRoute(String),
+    /// 缓存响应
+    Cached(String),
 }
 
 /// Prefix 属性解析
@@ -55,13 +64,27 @@ pub fn apply_transform_to_module(mod_item: &mut ItemMod, op: TransformOp) {
         }
     }
 }
-static ROUTE_MACROS: &[&str] = &[
-    "get", "post", "put", "delete", "patch", "head", "options", "route",
+pub(crate) static ROUTE_MACROS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "route", "ws", "sse",
 ];
 fn apply_transform_to_fn(func: &mut ItemFn, op: &TransformOp) {
     match op {
         TransformOp::Prefix(prefix) => {
             for attr in &mut func.attrs {
+                if attr.path().is_ident("alias")
+                    && let Ok(alias) = attr.parse_args::<RouteAlias>()
+                {
+                    let new_path = format!("{}{}", prefix, alias.path);
+                    *attr = match alias.locale {
+                        Some(locale) => syn::parse_quote! {
+                            #[alias(#new_path, locale = #locale)]
+                        },
+                        None => syn::parse_quote! {
+                            #[alias(#new_path)]
+                        },
+                    };
+                    continue;
+                }
                 if let Some(ident) = attr.path().get_ident() {
                     let attr_name = ident.to_string();
                     if ROUTE_MACROS.contains(&attr_name.as_str())
@@ -97,6 +120,30 @@ fn apply_transform_to_fn(func: &mut ItemFn, op: &TransformOp) {
                 #[layer(#layer_tokens)]
             });
         }
+        TransformOp::RequireHeader(args) => {
+            let tokens: proc_macro2::TokenStream = args.parse().unwrap_or_default();
+            func.attrs.push(syn::parse_quote! {
+                #[require_header(#tokens)]
+            });
+        }
+        TransformOp::Guard(args) => {
+            let tokens: proc_macro2::TokenStream = args.parse().unwrap_or_default();
+            func.attrs.push(syn::parse_quote! {
+                #[guard(#tokens)]
+            });
+        }
+        TransformOp::DeprecatedRoute(args) => {
+            let tokens: proc_macro2::TokenStream = args.parse().unwrap_or_default();
+            func.attrs.push(syn::parse_quote! {
+                #[deprecated_route(#tokens)]
+            });
+        }
+        TransformOp::Cached(args) => {
+            let tokens: proc_macro2::TokenStream = args.parse().unwrap_or_default();
+            func.attrs.push(syn::parse_quote! {
+                #[cached(#tokens)]
+            });
+        }
     }
 }
 
@@ -123,5 +170,29 @@ fn apply_transform_to_submodule(mod_item: &mut ItemMod, op: &TransformOp) {
                 #[layer(#layer_tokens)]
             });
         }
+        TransformOp::RequireHeader(args) => {
+            let tokens: proc_macro2::TokenStream = args.parse().unwrap_or_default();
+            mod_item.attrs.push(syn::parse_quote! {
+                #[require_header(#tokens)]
+            });
+        }
+        TransformOp::Guard(args) => {
+            let tokens: proc_macro2::TokenStream = args.parse().unwrap_or_default();
+            mod_item.attrs.push(syn::parse_quote! {
+                #[guard(#tokens)]
+            });
+        }
+        TransformOp::DeprecatedRoute(args) => {
+            let tokens: proc_macro2::TokenStream = args.parse().unwrap_or_default();
+            mod_item.attrs.push(syn::parse_quote! {
+                #[deprecated_route(#tokens)]
+            });
+        }
+        TransformOp::Cached(args) => {
+            let tokens: proc_macro2::TokenStream = args.parse().unwrap_or_default();
+            mod_item.attrs.push(syn::parse_quote! {
+                #[cached(#tokens)]
+            });
+        }
     }
 }
@@ -0,0 +1,73 @@
+use crate::route::LayerAttr;
+use syn::parse::{Parse, ParseStream};
+use syn::{LitBool, LitInt, Token, parse_quote};
+
+/// `#[cached(ttl = 60, allow_credentials = true)]` 属性信息
+#[derive(Debug, Clone)]
+pub struct CachedAttr {
+    /// 缓存时间（秒）
+    pub ttl: u64,
+    /// 是否缓存带 `Authorization`/`Cookie` 的请求
+    pub allow_credentials: bool,
+}
+
+impl Default for CachedAttr {
+    fn default() -> Self {
+        Self {
+            ttl: 60,
+            allow_credentials: false,
+        }
+    }
+}
+
+impl Parse for CachedAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attr = CachedAttr::default();
+        while !input.is_empty() {
+            let key: syn::Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "ttl" => attr.ttl = input.parse::<LitInt>()?.base10_parse()?,
+                "allow_credentials" => attr.allow_credentials = input.parse::<LitBool>()?.value,
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected `ttl = seconds` or `allow_credentials = bool`",
+                    ));
+                }
+            }
+            if input.parse::<Option<Token![,]>>()?.is_none() {
+                break;
+            }
+        }
+        Ok(attr)
+    }
+}
+
+impl CachedAttr {
+    /// 转换为对应的 `CacheLayer`
+    pub fn to_layer(&self) -> LayerAttr {
+        let ttl = self.ttl;
+        let allow_credentials = self.allow_credentials;
+        LayerAttr {
+            layer_expr: parse_quote!(::miko::ext::cache::CacheLayer::new(
+                ::std::time::Duration::from_secs(#ttl)
+            )
+            .allow_credentials(#allow_credentials)),
+        }
+    }
+}
+
+/// 从函数属性中提取 #[cached(...)] 标记
+pub fn extract_cached_attr(attrs: &[syn::Attribute]) -> Option<CachedAttr> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("cached"))
+        .map(|attr| {
+            let parsed = match &attr.meta {
+                syn::Meta::Path(_) => Ok(CachedAttr::default()),
+                _ => attr.parse_args::<CachedAttr>(),
+            };
+            parsed.unwrap_or_else(|e| panic!("cached must be like #[cached(ttl = 60)]: {}", e))
+        })
+}
@@ -1,10 +1,15 @@
 use crate::extractor::body::deal_with_body_attr;
-use crate::extractor::path::deal_with_path_attr;
+use crate::extractor::path::build_path_extractors;
+use crate::route::cached::extract_cached_attr;
+use crate::route::deprecated_route::extract_deprecated_route_attr;
+use crate::route::guard::extract_guard_attrs;
 use crate::route::layer::extract_layer_attrs;
-use crate::route::{RouteAttr, build_register_expr};
-use crate::toolkit::exactors::build_struct_from_query;
+use crate::route::require_header::extract_require_header_attrs;
+use crate::route::{RouteAttr, build_register_expr, extract_alias_attrs};
+use crate::toolkit::exactors::{build_default_stmts, build_struct_from_query};
 use crate::toolkit::rout_arg::{
-    FnArgResult, IntoFnArgs, RouteFnArg, build_config_value_injector, build_dep_injector,
+    FnArgResult, IntoFnArgs, RouteFnArg, build_config_extractors, build_dep_injector,
+    build_dep_requirement, build_header_extractors,
 };
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
@@ -28,12 +33,40 @@ use crate::utoipa::{
 /// - 将用户函数体和自动生成的注入语句合并为最终的宏展开。
 pub fn route_handler(args: RouteAttr, mut fn_item: ItemFn) -> TokenStream {
     let fn_name = fn_item.sig.ident.clone();
-    let layer_attrs = extract_layer_attrs(&fn_item.attrs);
-    fn_item.attrs.retain(|attr| !attr.path().is_ident("layer"));
+    let mut layer_attrs = extract_layer_attrs(&fn_item.attrs);
+    // 响应缓存在最内层，命中前仍经过守卫等其他 layer
+    if let Some(cached) = extract_cached_attr(&fn_item.attrs) {
+        layer_attrs.insert(0, cached.to_layer());
+    }
+    let alias_attrs = extract_alias_attrs(&fn_item.attrs);
+    // 请求头校验放在最外层，先于其他 layer 与提取器执行
+    layer_attrs.extend(
+        extract_require_header_attrs(&fn_item.attrs)
+            .iter()
+            .map(|h| h.to_layer()),
+    );
+    // 认证守卫在请求头校验之外，最先执行
+    layer_attrs.extend(
+        extract_guard_attrs(&fn_item.attrs)
+            .iter()
+            .map(|g| g.to_layer()),
+    );
+    // 弃用标记在最外层，被拒绝的请求同样带有弃用响应头
+    layer_attrs
+        .extend(extract_deprecated_route_attr(&fn_item.attrs).map(|d| d.to_layer(&args.path)));
+    fn_item
+        .attrs
+        .retain(|attr| !attr.path().is_ident("layer") && !attr.path().is_ident("alias"));
 
     // utoipa: 在处理前保存原始签名和属性用于推断
     #[cfg(feature = "utoipa")]
     let original_attrs = fn_item.attrs.clone();
+    fn_item.attrs.retain(|attr| {
+        !attr.path().is_ident("require_header")
+            && !attr.path().is_ident("guard")
+            && !attr.path().is_ident("deprecated_route")
+            && !attr.path().is_ident("cached")
+    });
     #[cfg(feature = "utoipa")]
     let original_inputs = fn_item.sig.inputs.clone();
     #[cfg(feature = "utoipa")]
@@ -44,10 +77,11 @@ pub fn route_handler(args: RouteAttr, mut fn_item: ItemFn) -> TokenStream {
     if matches!(sig.output, syn::ReturnType::Default) {
         sig.output = parse_quote!(-> impl ::miko::http::response::into_response::IntoResponse)
     }
-    let inject_segs: Vec<Stmt> = Vec::new();
     let rfa = RouteFnArg::from_punctuated(&mut sig.inputs);
+    // 处理 #[query] / #[path] 缺省值
+    let inject_segs: Vec<Stmt> = build_default_stmts(&rfa);
     //处理路由
-    let path_inputs = rfa.gen_fn_args(deal_with_path_attr);
+    let (path_items, path_inputs) = build_path_extractors(&rfa, &fn_name);
     //处理body
     let body_inputs = rfa.gen_fn_args(deal_with_body_attr);
     let plain_inputs = rfa.gen_fn_args(|rfa| {
@@ -59,10 +93,12 @@ pub fn route_handler(args: RouteAttr, mut fn_item: ItemFn) -> TokenStream {
     });
     //处理dep
     let mut dep_stmts = Vec::new();
-    build_dep_injector(&rfa, &mut dep_stmts);
+    let dep_scope_input = build_dep_injector(&rfa, &mut dep_stmts);
+    let dep_requirement = build_dep_requirement(&rfa, &fn_name);
     // 处理config_value
-    let mut config_value_stmts = Vec::new();
-    build_config_value_injector(&rfa, &mut config_value_stmts);
+    let (config_items, config_inputs) = build_config_extractors(&rfa, &fn_name);
+    // 处理header
+    let (header_items, header_inputs) = build_header_extractors(&rfa, &fn_name);
     // 清空参数
     sig.inputs.clear();
     // 获取无修饰参数
@@ -75,6 +111,12 @@ pub fn route_handler(args: RouteAttr, mut fn_item: ItemFn) -> TokenStream {
     if q_struct.is_some() {
         sig.inputs.push(q_struct_exactor.unwrap());
     }
+    // 组装config提取器
+    sig.inputs.extend(config_inputs);
+    // 组装header提取器
+    sig.inputs.extend(header_inputs);
+    // 组装依赖作用域提取器
+    sig.inputs.extend(dep_scope_input);
     // 组装plain_inputs
     sig.inputs.extend(plain_inputs);
     // 最后组装body
@@ -82,7 +124,12 @@ pub fn route_handler(args: RouteAttr, mut fn_item: ItemFn) -> TokenStream {
     // 展开
     let user_stmts = &fn_item.block.stmts.clone();
     let inventory_collect: Option<proc_macro2::TokenStream> = if cfg!(feature = "auto") {
-        Some(build_register_expr(&args, &fn_name.clone(), &layer_attrs))
+        Some(build_register_expr(
+            &args,
+            &fn_name.clone(),
+            &layer_attrs,
+            &alias_attrs,
+        ))
     } else {
         None
     };
@@ -91,41 +138,50 @@ pub fn route_handler(args: RouteAttr, mut fn_item: ItemFn) -> TokenStream {
     #[cfg(feature = "utoipa")]
     let utoipa_attr =
         generate_utoipa_attr(&args, &original_attrs, &original_inputs, &original_output);
+    #[cfg(feature = "utoipa")]
+    let openapi_collect = build_openapi_register_expr(&fn_name, &original_attrs);
 
     #[cfg(feature = "utoipa")]
     {
         quote! {
-          #q_struct
-
-          #utoipa_attr
-          #sig {
-            #(#inject_segs)*
-            #(#dep_stmts)*
-            #(#config_value_stmts)*
-            #(#user_stmts)*
-          }
+            #q_struct
+        #(#config_items)*
+        #(#header_items)*
+        #(#path_items)*
 
-          #inventory_collect
+            #utoipa_attr
+            #sig {
+              #(#inject_segs)*
+              #(#dep_stmts)*
+              #(#user_stmts)*
+            }
 
-        }
+            #inventory_collect
+            #dep_requirement
+            #openapi_collect
+
+          }
         .into()
     }
 
     #[cfg(not(feature = "utoipa"))]
     {
         quote! {
-          #q_struct
+            #q_struct
+        #(#config_items)*
+        #(#header_items)*
+        #(#path_items)*
 
-          #sig {
-            #(#inject_segs)*
-            #(#dep_stmts)*
-            #(#config_value_stmts)*
-            #(#user_stmts)*
-          }
+            #sig {
+              #(#inject_segs)*
+              #(#dep_stmts)*
+              #(#user_stmts)*
+            }
 
-          #inventory_collect
+            #inventory_collect
+            #dep_requirement
 
-        }
+          }
         .into()
     }
 }
@@ -136,22 +192,31 @@ pub fn route_handler(args: RouteAttr, mut fn_item: ItemFn) -> TokenStream {
 pub fn route_handler_no_register(args: RouteAttr, mut fn_item: ItemFn) -> TokenStream {
     let fn_name = fn_item.sig.ident.clone();
     let _layer_attrs = extract_layer_attrs(&fn_item.attrs);
-    fn_item.attrs.retain(|attr| !attr.path().is_ident("layer"));
+    fn_item
+        .attrs
+        .retain(|attr| !attr.path().is_ident("layer") && !attr.path().is_ident("alias"));
 
     // 保存原始签名用于 OpenAPI 推断
     let original_attrs = fn_item.attrs.clone();
+    fn_item.attrs.retain(|attr| {
+        !attr.path().is_ident("require_header")
+            && !attr.path().is_ident("guard")
+            && !attr.path().is_ident("deprecated_route")
+            && !attr.path().is_ident("cached")
+    });
     let original_inputs = fn_item.sig.inputs.clone();
     let original_output = fn_item.sig.output.clone();
 
     // 自动返回值
     let sig = &mut fn_item.sig;
     if matches!(sig.output, syn::ReturnType::Default) {
-        (*sig).output = parse_quote!(-> impl ::miko::http::response::into_response::IntoResponse)
+        sig.output = parse_quote!(-> impl ::miko::http::response::into_response::IntoResponse)
     }
-    let inject_segs: Vec<Stmt> = Vec::new();
     let rfa = RouteFnArg::from_punctuated(&mut sig.inputs);
+    // 处理 #[query] / #[path] 缺省值
+    let inject_segs: Vec<Stmt> = build_default_stmts(&rfa);
     //处理路由
-    let path_inputs = rfa.gen_fn_args(deal_with_path_attr);
+    let (path_items, path_inputs) = build_path_extractors(&rfa, &fn_name);
     //处理body
     let body_inputs = rfa.gen_fn_args(deal_with_body_attr);
     let plain_inputs = rfa.gen_fn_args(|rfa| {
@@ -163,25 +228,29 @@ pub fn route_handler_no_register(args: RouteAttr, mut fn_item: ItemFn) -> TokenS
     });
     //处理dep
     let mut dep_stmts = Vec::new();
-    build_dep_injector(&rfa, &mut dep_stmts);
+    let dep_scope_input = build_dep_injector(&rfa, &mut dep_stmts);
     // 处理config_value
-    let mut config_value_stmts = Vec::new();
-    build_config_value_injector(&rfa, &mut config_value_stmts);
+    let (config_items, config_inputs) = build_config_extractors(&rfa, &fn_name);
+    // 处理header
+    let (header_items, header_inputs) = build_header_extractors(&rfa, &fn_name);
     // 清空参数
     sig.inputs.clear();
     // 获取无修饰参数
     // 组装path
     sig.inputs.extend(path_inputs);
     // 构建 Query 结构体和解构提取器
-    let q_struct_ident = Ident::new(
-        &format!("__{}_QueryStruct", fn_name.to_string()),
-        Span::call_site(),
-    );
+    let q_struct_ident = Ident::new(&format!("__{}_QueryStruct", fn_name), Span::call_site());
     // 重组Query
     let (q_struct, q_struct_exactor) = build_struct_from_query(&rfa, q_struct_ident);
     if q_struct.is_some() {
         sig.inputs.push(q_struct_exactor.unwrap());
     }
+    // 组装config提取器
+    sig.inputs.extend(config_inputs);
+    // 组装header提取器
+    sig.inputs.extend(header_inputs);
+    // 组装依赖作用域提取器
+    sig.inputs.extend(dep_scope_input);
     // 组装plain_inputs
     sig.inputs.extend(plain_inputs);
     // 最后组装body
@@ -189,24 +258,60 @@ pub fn route_handler_no_register(args: RouteAttr, mut fn_item: ItemFn) -> TokenS
     // 展开
     let user_stmts = &fn_item.block.stmts.clone();
 
-    // 生成 OpenAPI 文档 (不生成 inventory 注册)
+    // 生成 OpenAPI 文档 (不注册路由，仅登记文档)
     let utoipa_attr =
         generate_utoipa_attr(&args, &original_attrs, &original_inputs, &original_output);
+    let openapi_collect = build_openapi_register_expr(&fn_name, &original_attrs);
 
     quote! {
       #q_struct
+      #(#config_items)*
+      #(#header_items)*
+      #(#path_items)*
 
       #utoipa_attr
       #sig {
         #(#inject_segs)*
         #(#dep_stmts)*
-        #(#config_value_stmts)*
         #(#user_stmts)*
       }
+
+      #openapi_collect
     }
     .into()
 }
 
+/// 开启 auto 时，将 `#[utoipa::path]` 生成的操作登记到 `miko::openapi::auto_doc`
+///
+/// `#[u_doc(...)]` 指定所属文档，未指定时属于默认文档。
+#[cfg(feature = "utoipa")]
+fn build_openapi_register_expr(
+    fn_name: &Ident,
+    original_attrs: &[syn::Attribute],
+) -> Option<proc_macro2::TokenStream> {
+    if !cfg!(feature = "auto") {
+        return None;
+    }
+    let path_struct = Ident::new(&format!("__path_{}", fn_name), fn_name.span());
+    let config = parse_utoipa_attrs(original_attrs);
+    let docs = config.user_docs;
+    // utoipa 只从 Rust 的 `#[deprecated]` 读取弃用标记，这里在登记时补上
+    let deprecated = config.deprecated.then(|| quote!(.deprecated()));
+    // `#[sse]` 记录的事件枚举
+    let sse_events = original_attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("sse_events"))
+        .and_then(|attr| attr.parse_args::<syn::Type>().ok())
+        .map(|ty| {
+            quote!(.sse_events(<#ty as ::miko::http::response::sse::SseEventTypes>::EVENT_NAMES))
+        });
+    Some(quote! {
+        ::miko::inventory::submit! {
+            ::miko::openapi::OpenApiPath::of::<#path_struct>().docs(&[#(#docs),*]) #sse_events #deprecated
+        }
+    })
+}
+
 #[cfg(feature = "utoipa")]
 fn generate_utoipa_attr(
     args: &RouteAttr,
@@ -224,7 +329,7 @@ fn generate_utoipa_attr(
     user_config.auto_summary = inferred.auto_summary;
     user_config.auto_description = inferred.auto_description;
     user_config.auto_params = inferred.auto_params;
-    user_config.auto_response = inferred.auto_response;
+    user_config.auto_responses = inferred.auto_responses;
     user_config.auto_request_body = inferred.auto_request_body;
 
     // 4. 确定 HTTP 方法
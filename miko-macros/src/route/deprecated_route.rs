@@ -0,0 +1,71 @@
+use crate::route::LayerAttr;
+use syn::parse::{Parse, ParseStream};
+use syn::{LitStr, Token, parse_quote};
+
+/// `#[deprecated_route(sunset = "2025-12-31", link = "https://...")]` 属性信息
+#[derive(Debug, Clone, Default)]
+pub struct DeprecatedRouteAttr {
+    /// 计划下线日期
+    pub sunset: Option<String>,
+    /// 迁移说明链接
+    pub link: Option<String>,
+}
+
+impl Parse for DeprecatedRouteAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attr = DeprecatedRouteAttr::default();
+        while !input.is_empty() {
+            let key: syn::Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value: LitStr = input.parse()?;
+            match key.to_string().as_str() {
+                "sunset" => attr.sunset = Some(value.value()),
+                "link" => attr.link = Some(value.value()),
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected `sunset = \"YYYY-MM-DD\"` or `link = \"url\"`",
+                    ));
+                }
+            }
+            if input.parse::<Option<Token![,]>>()?.is_none() {
+                break;
+            }
+        }
+        Ok(attr)
+    }
+}
+
+impl DeprecatedRouteAttr {
+    /// 转换为对应的 `DeprecationLayer`，以路由路径作为计数名称
+    pub fn to_layer(&self, route: &str) -> LayerAttr {
+        let mut expr: syn::Expr =
+            parse_quote!(::miko::middleware::deprecation::DeprecationLayer::new(#route));
+        if let Some(sunset) = &self.sunset {
+            expr = parse_quote!(#expr.sunset(#sunset));
+        }
+        if let Some(link) = &self.link {
+            expr = parse_quote!(#expr.link(#link));
+        }
+        LayerAttr { layer_expr: expr }
+    }
+}
+
+/// 从函数属性中提取 #[deprecated_route(...)] 标记
+pub fn extract_deprecated_route_attr(attrs: &[syn::Attribute]) -> Option<DeprecatedRouteAttr> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("deprecated_route"))
+        .map(|attr| {
+            let parsed = match &attr.meta {
+                syn::Meta::Path(_) => Ok(DeprecatedRouteAttr::default()),
+                _ => attr.parse_args::<DeprecatedRouteAttr>(),
+            };
+            parsed.unwrap_or_else(|e| {
+                panic!(
+                    "deprecated_route must be like #[deprecated_route(sunset = \"2025-12-31\", link = \"url\")]: {}",
+                    e
+                )
+            })
+        })
+}
@@ -0,0 +1,58 @@
+use crate::route::LayerAttr;
+use syn::parse::{Parse, ParseStream};
+use syn::{LitStr, Token, parse_quote};
+
+/// `#[guard]` / `#[guard(role = "admin")]` 属性信息
+#[derive(Debug, Clone)]
+pub struct GuardAttr {
+    /// 要求的角色，缺省时只要求已认证
+    pub role: Option<String>,
+}
+
+impl Parse for GuardAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(GuardAttr { role: None });
+        }
+        let key: syn::Ident = input.parse()?;
+        if key != "role" {
+            return Err(syn::Error::new(key.span(), "expected `role = \"name\"`"));
+        }
+        input.parse::<Token![=]>()?;
+        let role: LitStr = input.parse()?;
+        Ok(GuardAttr {
+            role: Some(role.value()),
+        })
+    }
+}
+
+impl GuardAttr {
+    /// 转换为对应的 `GuardLayer`
+    pub fn to_layer(&self) -> LayerAttr {
+        let expr: syn::Expr = match &self.role {
+            Some(role) => parse_quote!(::miko::ext::auth::GuardLayer::role(#role)),
+            None => parse_quote!(::miko::ext::auth::GuardLayer::authenticated()),
+        };
+        LayerAttr { layer_expr: expr }
+    }
+}
+
+/// 从函数属性中提取所有 #[guard(...)] 标记
+pub fn extract_guard_attrs(attrs: &[syn::Attribute]) -> Vec<GuardAttr> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("guard"))
+        .map(|attr| {
+            let parsed = match &attr.meta {
+                syn::Meta::Path(_) => Ok(GuardAttr { role: None }),
+                _ => attr.parse_args::<GuardAttr>(),
+            };
+            parsed.unwrap_or_else(|e| {
+                panic!(
+                    "guard must be like #[guard] or #[guard(role = \"admin\")]: {}",
+                    e
+                )
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,72 @@
+use crate::route::LayerAttr;
+use syn::parse::{Parse, ParseStream};
+use syn::{Lit, LitStr, Token, parse_quote};
+
+/// `#[require_header("X-Api-Version", matches = "2\\.\\d+", status = 426)]` 属性信息
+#[derive(Debug, Clone)]
+pub struct RequireHeaderAttr {
+    /// 请求头名称（小写）
+    pub name: String,
+    /// 值需完整匹配的正则
+    pub matches: Option<String>,
+    /// 值不匹配时返回的状态码，缺省为 400
+    pub status: Option<u16>,
+}
+
+impl Parse for RequireHeaderAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: LitStr = input.parse()?;
+        let mut attr = RequireHeaderAttr {
+            name: name.value().to_ascii_lowercase(),
+            matches: None,
+            status: None,
+        };
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: syn::Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match (key.to_string().as_str(), input.parse::<Lit>()?) {
+                ("matches", Lit::Str(s)) => attr.matches = Some(s.value()),
+                ("status", Lit::Int(i)) => attr.status = Some(i.base10_parse()?),
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected `matches = \"regex\"` or `status = 426`",
+                    ));
+                }
+            }
+        }
+        Ok(attr)
+    }
+}
+
+impl RequireHeaderAttr {
+    /// 转换为对应的 `RequireHeaderLayer`
+    pub fn to_layer(&self) -> LayerAttr {
+        let name = &self.name;
+        let mut expr: syn::Expr =
+            parse_quote!(::miko::middleware::require_header::RequireHeaderLayer::new(#name));
+        if let Some(pattern) = &self.matches {
+            expr = parse_quote!(#expr.matches(#pattern));
+        }
+        if let Some(status) = self.status {
+            expr = parse_quote!(#expr.status(#status));
+        }
+        LayerAttr { layer_expr: expr }
+    }
+}
+
+/// 从函数属性中提取所有 #[require_header(...)] 标记
+pub fn extract_require_header_attrs(attrs: &[syn::Attribute]) -> Vec<RequireHeaderAttr> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("require_header"))
+        .map(|attr| {
+            attr.parse_args::<RequireHeaderAttr>().unwrap_or_else(|e| {
+                panic!(
+                    "require_header must be like #[require_header(\"X-Name\", matches = \"regex\")]: {}",
+                    e
+                )
+            })
+        })
+        .collect()
+}
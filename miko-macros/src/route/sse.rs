@@ -0,0 +1,138 @@
+use crate::route::RouteAttr;
+use crate::route::core::route_handler;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    FnArg, GenericArgument, ItemFn, PathArguments, ReturnType, Type, TypeParamBound, TypePath,
+    parse_quote,
+};
+
+/// 处理 `#[sse(...)]`：注册为 GET 路由，响应为 `text/event-stream`。
+///
+/// 两种写法：
+/// - 声明 `SseSender` / `TypedSseSender<E>` 参数：函数体在后台任务中运行，通过发送端推送事件；
+/// - 返回 `impl Stream<Item = E>`：流中的每一项经 `IntoSseEvent` 转换后推送。
+///
+/// 其余参数仍由 [`route_handler`] 提取。能确定事件枚举 `E` 时，其事件名会写入 OpenAPI 文档。
+pub fn sse_handler(attr: TokenStream, mut fn_item: ItemFn) -> TokenStream {
+    let mut args: RouteAttr = match syn::parse(attr) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    args.method = Some(vec![hyper::Method::GET]);
+
+    let sender_pos = fn_item
+        .sig
+        .inputs
+        .iter()
+        .position(|arg| matches!(arg, FnArg::Typed(pat) if sender_kind(&pat.ty).is_some()));
+    let event_ty = match sender_pos {
+        Some(pos) => {
+            if !matches!(fn_item.sig.output, ReturnType::Default) {
+                return syn::Error::new_spanned(
+                    &fn_item.sig.output,
+                    "#[sse] handlers with a sender run in a background task and cannot return a value",
+                )
+                .to_compile_error()
+                .into();
+            }
+            let inputs = std::mem::take(&mut fn_item.sig.inputs);
+            let mut sender = None;
+            for (i, arg) in inputs.into_iter().enumerate() {
+                match arg {
+                    FnArg::Typed(pat) if i == pos => sender = Some(pat),
+                    arg => fn_item.sig.inputs.push(arg),
+                }
+            }
+            let sender = sender.unwrap();
+            let (pat, ty) = (&sender.pat, &sender.ty);
+            let event_ty = sender_kind(ty).flatten();
+            let bind = match &event_ty {
+                Some(_) => {
+                    quote!(let #pat: #ty = ::miko::http::response::sse::TypedSseSender::new(__sse_tx);)
+                }
+                None => quote!(let #pat: #ty = __sse_tx;),
+            };
+            let stmts = &fn_item.block.stmts;
+            fn_item.block = parse_quote!({
+                ::miko::http::response::sse::spawn_sse_event(move |__sse_tx| async move {
+                    #bind
+                    #(#stmts)*
+                })
+            });
+            event_ty
+        }
+        None => {
+            let ReturnType::Type(_, ty) = &fn_item.sig.output else {
+                return syn::Error::new_spanned(
+                    &fn_item.sig,
+                    "#[sse] handlers need a `SseSender`/`TypedSseSender<E>` parameter or must return a stream of events",
+                )
+                .to_compile_error()
+                .into();
+            };
+            let event_ty = stream_item(ty);
+            let stmts = &fn_item.block.stmts;
+            fn_item.block = parse_quote!({
+                ::miko::http::response::sse::Sse::from_events(async move { #(#stmts)* }.await)
+            });
+            fn_item.sig.output =
+                parse_quote!(-> impl ::miko::http::response::into_response::IntoResponse);
+            event_ty
+        }
+    };
+
+    #[cfg(feature = "utoipa")]
+    {
+        fn_item.attrs.push(parse_quote!(
+            #[u_response(status = 200, description = "Server-sent events", body = String, content_type = "text/event-stream")]
+        ));
+        if let Some(ty) = &event_ty {
+            fn_item.attrs.push(parse_quote!(#[sse_events(#ty)]));
+        }
+    }
+    #[cfg(not(feature = "utoipa"))]
+    let _ = event_ty;
+    route_handler(args, fn_item)
+}
+
+/// `SseSender` 返回 `Some(None)`，`TypedSseSender<E>` 返回 `Some(Some(E))`
+fn sender_kind(ty: &Type) -> Option<Option<Type>> {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return None;
+    };
+    let seg = path.segments.last()?;
+    if seg.ident == "SseSender" {
+        return Some(None);
+    }
+    if seg.ident != "TypedSseSender" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(generics) = &seg.arguments else {
+        return Some(None);
+    };
+    Some(generics.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    }))
+}
+
+/// 从 `impl Stream<Item = E>` 中取出 `E`
+fn stream_item(ty: &Type) -> Option<Type> {
+    let Type::ImplTrait(impl_trait) = ty else {
+        return None;
+    };
+    impl_trait.bounds.iter().find_map(|bound| {
+        let TypeParamBound::Trait(bound) = bound else {
+            return None;
+        };
+        let seg = bound.path.segments.last()?;
+        let PathArguments::AngleBracketed(generics) = &seg.arguments else {
+            return None;
+        };
+        generics.args.iter().find_map(|arg| match arg {
+            GenericArgument::AssocType(assoc) if assoc.ident == "Item" => Some(assoc.ty.clone()),
+            _ => None,
+        })
+    })
+}
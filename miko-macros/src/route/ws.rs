@@ -0,0 +1,75 @@
+use crate::route::RouteAttr;
+use crate::route::core::route_handler;
+use crate::toolkit::attr::StrAttrMap;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{FnArg, ItemFn, Type, TypePath, parse_quote};
+
+/// 处理 `#[ws(...)]`：把 `WsSocket` 参数换成 `WsUpgrade` 提取器，函数体改为升级后运行的任务。
+///
+/// 其余参数（`#[dep]`、`#[query]`、`#[path]` 等）仍由 [`route_handler`] 在升级前提取，
+/// 任务以 `move` 闭包捕获它们。`protocols = "a,b"` 声明支持的子协议。
+pub fn ws_handler(attr: TokenStream, mut fn_item: ItemFn) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    let mut args: RouteAttr = match syn::parse2(attr.clone()) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let options: StrAttrMap = syn::parse2(attr).unwrap_or_default();
+    args.method = Some(vec![hyper::Method::GET]);
+
+    if !matches!(fn_item.sig.output, syn::ReturnType::Default) {
+        return syn::Error::new_spanned(
+            &fn_item.sig.output,
+            "#[ws] handlers run after the upgrade and cannot return a value",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let socket_pos = fn_item
+        .sig
+        .inputs
+        .iter()
+        .position(|arg| matches!(arg, FnArg::Typed(pat) if is_ws_socket(&pat.ty)));
+    let Some(socket_pos) = socket_pos else {
+        return syn::Error::new_spanned(&fn_item.sig, "#[ws] handlers need a `WsSocket` parameter")
+            .to_compile_error()
+            .into();
+    };
+    let inputs = std::mem::take(&mut fn_item.sig.inputs);
+    let mut socket_pat = None;
+    for (i, arg) in inputs.into_iter().enumerate() {
+        match arg {
+            FnArg::Typed(pat) if i == socket_pos => socket_pat = Some(pat),
+            arg => fn_item.sig.inputs.push(arg),
+        }
+    }
+    let socket_pat = socket_pat.unwrap();
+    let (pat, ty) = (&socket_pat.pat, &socket_pat.ty);
+    fn_item
+        .sig
+        .inputs
+        .insert(0, parse_quote!(__ws_upgrade: ::miko::ws::WsUpgrade));
+
+    let protocols: Vec<String> = options
+        .get("protocols")
+        .map(|p| p.split(',').map(|s| s.trim().to_string()).collect())
+        .unwrap_or_default();
+    let protocols = (!protocols.is_empty()).then(|| quote!(.protocols([#(#protocols),*])));
+    let stmts = &fn_item.block.stmts;
+    fn_item.block = parse_quote!({
+        __ws_upgrade #protocols .on(move |#pat: #ty| async move {
+            #(#stmts)*
+        })
+    });
+    route_handler(args, fn_item)
+}
+
+fn is_ws_socket(ty: &Type) -> bool {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return false;
+    };
+    path.segments
+        .last()
+        .is_some_and(|seg| seg.ident == "WsSocket")
+}
@@ -0,0 +1,174 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, LitInt, LitStr};
+
+#[derive(Default)]
+struct VariantOptions {
+    name: Option<String>,
+    retry: Option<u32>,
+}
+
+/// 展开 `#[derive(IntoSseEvent)]`。
+///
+/// 每个变体对应一种事件：事件名默认为变体名的 snake_case，可用 `#[sse_event(name = "...")]` 覆盖，
+/// `#[sse_event(retry = 3000)]` 附带重试时间；字段上的 `#[sse_event(id)]` 作为事件 id。
+/// data 为变体负载的 JSON：单元变体为 `null`，单字段元组变体为该字段，多字段为数组，具名字段为对象。
+pub fn derive_into_sse_event(input: DeriveInput) -> TokenStream {
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "IntoSseEvent can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "IntoSseEvent cannot be derived for generic enums",
+        ));
+    }
+    let ident = &input.ident;
+    let mut names = Vec::new();
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let options = parse_variant_options(&variant.attrs)?;
+        let name = options
+            .name
+            .unwrap_or_else(|| to_snake_case(&variant.ident.to_string()));
+        if names.contains(&name) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!("duplicate SSE event name `{}`", name),
+            ));
+        }
+        let retry = options.retry.map(|r| quote!(.retry(#r)));
+
+        let v = &variant.ident;
+        let mut id_field = None;
+        let bindings: Vec<_> = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                f.ident
+                    .clone()
+                    .unwrap_or_else(|| format_ident!("__field{}", i))
+            })
+            .collect();
+        for (field, binding) in variant.fields.iter().zip(&bindings) {
+            if is_id_field(&field.attrs)? {
+                if id_field.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "only one field can be marked #[sse_event(id)]",
+                    ));
+                }
+                id_field = Some(binding.clone());
+            }
+        }
+        let id = id_field.map(|f| quote!(.id(::std::string::ToString::to_string(&#f))));
+
+        let (pattern, prelude, payload) = match &variant.fields {
+            Fields::Unit => (quote!(#ident::#v), None, quote!(&())),
+            Fields::Unnamed(_) if bindings.len() == 1 => {
+                let f = &bindings[0];
+                (quote!(#ident::#v(#f)), None, quote!(&#f))
+            }
+            Fields::Unnamed(_) => (
+                quote!(#ident::#v(#(#bindings),*)),
+                None,
+                quote!(&(#(&#bindings,)*)),
+            ),
+            Fields::Named(fields) => {
+                // 借用字段的局部结构体，序列化为 JSON 对象
+                let tys = fields.named.iter().map(|f| &f.ty);
+                let prelude = quote! {
+                    #[derive(::miko::serde::Serialize)]
+                    #[serde(crate = "::miko::serde")]
+                    struct __SseData<'__a> {
+                        #(#bindings: &'__a #tys,)*
+                    }
+                };
+                (
+                    quote!(#ident::#v { #(#bindings),* }),
+                    Some(prelude),
+                    quote!(&__SseData { #(#bindings: &#bindings),* }),
+                )
+            }
+        };
+        arms.push(quote! {
+            #pattern => {
+                #prelude
+                ::miko::http::response::sse::SseEvent::json(#payload).event(#name) #id #retry
+            }
+        });
+        names.push(name);
+    }
+
+    Ok(quote! {
+        impl ::miko::http::response::sse::IntoSseEvent for #ident {
+            fn into_sse_event(self) -> ::miko::http::response::sse::SseEvent {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+
+        impl ::miko::http::response::sse::SseEventTypes for #ident {
+            const EVENT_NAMES: &'static [&'static str] = &[#(#names),*];
+        }
+    })
+}
+
+fn parse_variant_options(attrs: &[syn::Attribute]) -> syn::Result<VariantOptions> {
+    let mut options = VariantOptions::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("sse_event")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("retry") {
+                options.retry = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            } else {
+                return Err(meta.error("expected `name` or `retry`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+fn is_id_field(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut id = false;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("sse_event")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                id = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `id`"))
+            }
+        })?;
+    }
+    Ok(id)
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
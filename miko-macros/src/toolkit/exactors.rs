@@ -1,7 +1,7 @@
-use crate::toolkit::rout_arg::RouteFnArg;
+use crate::toolkit::rout_arg::{RouteFnArg, is_option, is_vec};
 use proc_macro2::Ident;
 use quote::quote;
-use syn::{FnArg, ItemStruct, parse_quote};
+use syn::{FnArg, ItemStruct, Stmt, Type, parse_quote};
 
 /// 根据带有 `#[query]` 标记的参数构建一个临时的查询结构体和对应的提取器参数。
 ///
@@ -9,23 +9,60 @@ use syn::{FnArg, ItemStruct, parse_quote};
 /// - `struct_name`：为生成的结构体提供标识符。
 ///
 /// 返回值为 (Option<ItemStruct>, Option<FnArg>)，当没有带 `#[query]` 的参数时返回 (None, None)。
+/// 参数类型为 `Vec<T>` 时改用 `QueryMulti` 提取，重复的键会被收集为数组。
+/// `#[query(default = ..)]` / `#[query(default_fn = "f")]` 的字段按 `Option<T>` 解析，缺省值由 [`build_default_stmts`] 补上。
+/// 任一参数标注 `#[query(validate)]` 时结构体派生 garde 的 `Validate` 并改用 `ValidatedQuery` 提取，
+/// 参数上的 `#[garde(...)]` 规则转移到对应字段，未标注规则的字段跳过验证。
 pub fn build_struct_from_query(
     rfa: &Vec<RouteFnArg>,
     struct_name: Ident,
 ) -> (Option<ItemStruct>, Option<FnArg>) {
     let mut fields = Vec::new();
     let mut idents = Vec::new();
+    let mut multi = false;
+    let validate = rfa
+        .iter()
+        .filter_map(|rfa| rfa.mark.get("query"))
+        .any(|map| map.get("validate").is_some());
     for rfa in rfa {
         if rfa.mark.contains_key("query") {
             let name = &rfa.ident;
-            let ty = rfa.ty.clone();
+            // 声明了缺省值时字段改为可选，缺失时在函数体开头补上缺省值
+            let ty: Type = match rfa.default_expr("query") {
+                Some(_) => {
+                    let ty = &rfa.ty;
+                    parse_quote!(::core::option::Option<#ty>)
+                }
+                None => rfa.ty.clone(),
+            };
             idents.push(name.clone());
 
             // 提取参数上的 #[desc] 注释
             let desc_attr = extract_desc_attr(&rfa.attrs);
 
+            // Vec 字段收集重复键的所有值，缺省时为空
+            let (is_opt, inner) = is_option(&ty);
+            let default_attr = if is_vec(&ty) {
+                multi = true;
+                quote! { #[serde(default)] }
+            } else {
+                if is_opt && inner.as_ref().is_some_and(is_vec) {
+                    multi = true;
+                }
+                quote! {}
+            };
+
+            // 验证时转移参数上的 #[garde] 规则
+            let garde_attrs = if validate {
+                extract_garde_attrs(rfa)
+            } else {
+                quote! {}
+            };
+
             fields.push(quote! {
                 #desc_attr
+                #default_attr
+                #garde_attrs
                 pub #name: #ty
             })
         }
@@ -42,14 +79,34 @@ pub fn build_struct_from_query(
             #[derive(::miko::serde::Deserialize)]
         };
 
+        let derives = if validate {
+            quote! {
+                #derives
+                #[derive(::miko::garde::Validate)]
+            }
+        } else {
+            derives
+        };
+
         let q_struct: ItemStruct = parse_quote! {
             #derives
             struct #struct_name {
                 #(#fields),*
             }
         };
+        // 含 Vec 参数时改用 QueryMulti，serde_urlencoded 无法聚合重复键
+        let extractor = if validate {
+            if multi {
+                panic!("#[query(validate)] does not support Vec query parameters");
+            }
+            quote! { ::miko::extractor::ValidatedQuery }
+        } else if multi {
+            quote! { ::miko::extractor::QueryMulti }
+        } else {
+            quote! { ::miko::extractor::Query }
+        };
         let stmt: FnArg = parse_quote! {
-            ::miko::extractor::Query(#struct_name { #(#idents),* }): ::miko::extractor::Query<#struct_name>
+            #extractor(#struct_name { #(#idents),* }): #extractor<#struct_name>
         };
         (Some(q_struct), Some(stmt))
     } else {
@@ -57,17 +114,58 @@ pub fn build_struct_from_query(
     }
 }
 
+/// 为声明了 `default` / `default_fn` 的 `#[query]`、`#[path]` 参数生成取缺省值的语句。
+///
+/// 提取器按 `Option<T>` 取值，这里再将同名变量还原为 `T`。
+pub fn build_default_stmts(rfa: &Vec<RouteFnArg>) -> Vec<Stmt> {
+    let mut stmts = Vec::new();
+    for rfa in rfa {
+        let default = ["query", "path"]
+            .into_iter()
+            .filter(|key| rfa.mark.contains_key(*key))
+            .find_map(|key| rfa.default_expr(key));
+        if let Some(default) = default {
+            let ident = &rfa.ident;
+            let ty = &rfa.ty;
+            stmts.push(parse_quote! {
+                let #ident: #ty = match #ident {
+                    ::core::option::Option::Some(value) => value,
+                    ::core::option::Option::None => #default,
+                };
+            });
+        }
+    }
+    stmts
+}
+
+/// 取出参数上的 #[garde(...)] 属性，没有时生成 #[garde(skip)]
+fn extract_garde_attrs(rfa: &RouteFnArg) -> proc_macro2::TokenStream {
+    let FnArg::Typed(pat) = &rfa.origin else {
+        return quote! {};
+    };
+    let attrs: Vec<_> = pat
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("garde"))
+        .collect();
+    if attrs.is_empty() {
+        quote! { #[garde(skip)] }
+    } else {
+        quote! { #(#attrs)* }
+    }
+}
+
 /// 从属性中提取 #[desc("...")] 并转换为 utoipa 的 #[schema(description = "...")]
 fn extract_desc_attr(attrs: &[syn::Attribute]) -> proc_macro2::TokenStream {
     #[cfg(feature = "utoipa")]
     {
         for attr in attrs {
-            if attr.path().is_ident("desc") {
-                if let Ok(lit) = attr.parse_args::<syn::LitStr>() {
-                    return quote! {
-                        #[schema(description = #lit)]
-                    };
-                }
+            if attr.path().is_ident("desc")
+                && let Ok(lit) = attr.parse_args::<syn::LitStr>()
+            {
+                return quote! {
+                    #[schema(description = #lit)]
+                };
             }
         }
     }
@@ -1,19 +1,20 @@
 #![cfg(feature = "auto")]
-use crate::toolkit::rout_arg::is_arc;
+use crate::toolkit::attr::StrAttrMap;
+use crate::toolkit::rout_arg::{is_arc, is_dyn};
 use proc_macro2::Ident;
-use proc_macro2::TokenStream;
+use proc_macro2::{TokenStream, TokenTree};
 use quote::quote;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
-use syn::{FnArg, ImplItem, ImplItemFn, Pat, PatIdent};
+use syn::{FnArg, ImplItem, ImplItemFn, Meta, Pat, PatIdent, Type};
 
-/// 在 `impl` 项目列表中查找名为 `new` 的构造函数并返回其引用（如果存在）。
+/// 在 `impl` 项目列表中查找名为 `name`（默认为 `new`）的构造函数并返回其可变引用（如果存在）。
 ///
 /// 用于在宏中检测并提取异步构造函数以进行依赖注入分析。
-pub fn get_constructor(items: &Vec<ImplItem>) -> Option<&ImplItemFn> {
+pub fn get_constructor<'a>(items: &'a mut [ImplItem], name: &str) -> Option<&'a mut ImplItemFn> {
     for item in items {
         if let ImplItem::Fn(method) = item
-            && method.sig.ident == "new"
+            && method.sig.ident == name
         {
             return Some(method);
         }
@@ -25,13 +26,27 @@ pub fn get_constructor(items: &Vec<ImplItem>) -> Option<&ImplItemFn> {
 ///
 /// 要求构造函数参数为 `Arc<T>` 形式；该函数会为第一个依赖注入语句插入读取全局容器的代码片段，
 /// 并为每个参数追加 `let <ident> = container.get::<T>().await.clone();` 之类的语句，同时收集参数名到 `arg_idents`。
+/// 参数上的 `#[dep(name = "...")]` 会改为按名称获取，并从构造函数签名中移除；
+/// 依赖的类型与名称收集到 `dep_refs`，用于启动时校验依赖图。
 pub fn inject_deps(
-    args: &Punctuated<FnArg, Comma>,
+    args: &mut Punctuated<FnArg, Comma>,
     depend_get_stmts: &mut Vec<TokenStream>,
     arg_idents: &mut Vec<Ident>,
+    dep_refs: &mut Vec<TokenStream>,
 ) {
     for arg in args {
         if let FnArg::Typed(pat) = arg {
+            let mut name = None;
+            pat.attrs.retain(|attr| {
+                if !attr.path().is_ident("dep") {
+                    return true;
+                }
+                if let Meta::List(list) = &attr.meta {
+                    let sam: StrAttrMap = syn::parse2(list.tokens.clone()).unwrap();
+                    name = sam.get("name").cloned();
+                }
+                false
+            });
             let arg_ident = match &*pat.pat {
                 Pat::Ident(PatIdent { ident, .. }) => ident.clone(),
                 _ => {
@@ -48,10 +63,60 @@ pub fn inject_deps(
                     let container = ::miko::dependency_container::CONTAINER.get().unwrap().read().await;
                 })
             }
+            let ref_name = name.clone().unwrap_or_else(|| "___".to_string());
+            dep_refs.push(quote! {
+                ::miko::dependency_container::DependencyRef::of::<#pat_ident>(#ref_name)
+            });
+            let getter = match (name, is_dyn(pat_ident)) {
+                (Some(name), false) => quote!(container.get_::<#pat_ident>(#name)),
+                (None, false) => quote!(container.get::<#pat_ident>()),
+                (Some(name), true) => quote!(container.get_dyn_::<#pat_ident>(#name)),
+                (None, true) => quote!(container.get_dyn::<#pat_ident>()),
+            };
             depend_get_stmts.push(quote! {
-                let #arg_ident = container.get::<#pat_ident>().await.clone();
+                let #arg_ident = #getter.await.clone();
             });
             arg_idents.push(arg_ident);
         }
     }
 }
+
+/// 从 `#[component(...)]` 的参数中取出 `bind = dyn Trait`，返回其余参数与绑定的类型。
+///
+/// `dyn Trait` 不是合法的表达式，无法交给 `StrAttrMap` 解析，因此先按顶层逗号拆分。
+pub fn split_bind_args(attr: TokenStream) -> (TokenStream, Vec<Type>) {
+    let mut rest = TokenStream::new();
+    let mut binds = Vec::new();
+    let mut segment: Vec<TokenTree> = Vec::new();
+    let mut flush = |segment: &mut Vec<TokenTree>| {
+        if let [TokenTree::Ident(key), TokenTree::Punct(eq), ty @ ..] = segment.as_slice()
+            && key == "bind"
+            && eq.as_char() == '='
+        {
+            let ty: TokenStream = ty.iter().cloned().collect();
+            binds.push(syn::parse2(ty).expect("`bind` expects a type, e.g. `bind = dyn MyTrait`"));
+        } else if !segment.is_empty() {
+            rest.extend(segment.iter().cloned());
+            rest.extend(quote!(,));
+        }
+        segment.clear();
+    };
+    // 泛型参数中的逗号不是分隔符
+    let mut depth = 0usize;
+    for tt in attr {
+        match &tt {
+            TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => flush(&mut segment),
+            TokenTree::Punct(p) => {
+                match p.as_char() {
+                    '<' => depth += 1,
+                    '>' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                segment.push(tt)
+            }
+            _ => segment.push(tt),
+        }
+    }
+    flush(&mut segment);
+    (rest, binds)
+}
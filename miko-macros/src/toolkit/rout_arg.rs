@@ -4,7 +4,7 @@ use quote::{ToTokens, quote};
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
-use syn::{FnArg, Meta, Type, TypePath};
+use syn::{FnArg, Meta, Type, TypePath, parse_quote};
 #[allow(dead_code)]
 #[derive(Clone)]
 pub struct RouteFnArg {
@@ -83,6 +83,61 @@ impl RouteFnArg {
     pub fn marked_by(&self, key: &str) -> bool {
         self.mark.contains_key(key)
     }
+
+    /// 解析 `#[key(default = expr)]` / `#[key(default_fn = "path")]` 声明的缺省值，返回求值表达式。
+    ///
+    /// 字符串字面量会经过 `Into::into` 转换，便于直接作为 `String` 的缺省值。
+    pub fn default_expr(&self, key: &str) -> Option<syn::Expr> {
+        let FnArg::Typed(pat) = &self.origin else {
+            return None;
+        };
+        let attr = pat.attrs.iter().find(|attr| attr.path().is_ident(key))?;
+        let Meta::List(list) = &attr.meta else {
+            return None;
+        };
+        let parser = |input: syn::parse::ParseStream| {
+            let mut expr = None;
+            while !input.is_empty() {
+                if input.peek(syn::LitStr) {
+                    input.parse::<syn::LitStr>()?;
+                } else {
+                    match input.parse::<Meta>()? {
+                        Meta::NameValue(nv) if nv.path.is_ident("default") => {
+                            let value = nv.value;
+                            expr = Some(match value {
+                                syn::Expr::Lit(syn::ExprLit {
+                                    lit: syn::Lit::Str(_),
+                                    ..
+                                }) => parse_quote!(::core::convert::Into::into(#value)),
+                                value => value,
+                            });
+                        }
+                        Meta::NameValue(nv) if nv.path.is_ident("default_fn") => {
+                            let syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(name),
+                                ..
+                            }) = nv.value
+                            else {
+                                return Err(syn::Error::new_spanned(
+                                    nv.value,
+                                    "default_fn expects a function path string",
+                                ));
+                            };
+                            let path: syn::Path = name.parse()?;
+                            expr = Some(parse_quote!(#path()));
+                        }
+                        _ => {}
+                    }
+                }
+                if !input.is_empty() {
+                    input.parse::<syn::Token![,]>()?;
+                }
+            }
+            Ok(expr)
+        };
+        syn::parse::Parser::parse2(parser, list.tokens.clone())
+            .unwrap_or_else(|e| panic!("invalid #[{}] attribute: {}", key, e))
+    }
 }
 
 pub trait IntoFnArgs {
@@ -158,6 +213,11 @@ pub fn is_arc(ty: &Type) -> (bool, Option<Type>) {
     }
 }
 
+/// 判断类型是否为 trait 对象（`dyn Trait`），此时依赖需按 trait 绑定获取
+pub fn is_dyn(ty: &Type) -> bool {
+    matches!(ty, Type::TraitObject(_))
+}
+
 #[allow(dead_code)]
 pub enum FnArgResult {
     Remove,
@@ -168,8 +228,14 @@ pub enum FnArgResult {
 
 /// 为带有 `#[dep]` 标记的参数生成依赖注入的语句。
 ///
-/// 该函数会为每个标记为 `dep` 的参数生成从全局依赖容器中异步获取该依赖的语句片段，并追加到 `dep_stmts`。
-pub fn build_dep_injector(rfa: &Vec<RouteFnArg>, dep_stmts: &mut Vec<TokenStream>) {
+/// 该函数会为每个标记为 `dep` 的参数生成从全局依赖容器中异步获取该依赖的语句片段，并追加到 `dep_stmts`；
+/// `#[dep(name = "...")]` 按名称获取同一类型的不同实例。
+///
+/// 存在依赖时返回额外的 `RequestScope` 提取器参数，请求作用域（`scoped`）的组件由此在同一请求内共享。
+pub fn build_dep_injector(
+    rfa: &Vec<RouteFnArg>,
+    dep_stmts: &mut Vec<TokenStream>,
+) -> Option<FnArg> {
     for rfa in rfa {
         if rfa.mark.contains_key("dep") {
             let dep_ty = rfa.ty.clone();
@@ -179,57 +245,155 @@ pub fn build_dep_injector(rfa: &Vec<RouteFnArg>, dep_stmts: &mut Vec<TokenStream
             }
             let inner = inner.unwrap();
             let dep_ident = rfa.ident.clone();
+            let getter = match (rfa.mark["dep"].get("name"), is_dyn(&inner)) {
+                (Some(name), false) => quote!(get_in_::<#inner>(#name, &__dep_scope)),
+                (None, false) => quote!(get_in::<#inner>(&__dep_scope)),
+                (Some(name), true) => quote!(get_dyn_in_::<#inner>(#name, &__dep_scope)),
+                (None, true) => quote!(get_dyn_in::<#inner>(&__dep_scope)),
+            };
             let stmt = quote! {
-                let #dep_ident = __dep_container.get::<#inner>().await;
+                let #dep_ident = __dep_container.#getter.await;
             };
             dep_stmts.push(stmt);
         }
     }
-    if !dep_stmts.is_empty() {
-        dep_stmts.insert(
-            0,
-            quote! {
-                let __dep_container = ::miko::dependency_container::get_global_dc().await;
-            },
-        );
+    if dep_stmts.is_empty() {
+        return None;
+    }
+    dep_stmts.insert(
+        0,
+        quote! {
+            let __dep_container = ::miko::dependency_container::get_global_dc().await;
+        },
+    );
+    Some(parse_quote!(__dep_scope: ::miko::dependency_container::RequestScope))
+}
+
+/// 开启 auto 时，登记处理器通过 `#[dep]` 声明的依赖，供启动时校验依赖图。
+pub fn build_dep_requirement(rfa: &[RouteFnArg], fn_name: &syn::Ident) -> Option<TokenStream> {
+    if !cfg!(feature = "auto") {
+        return None;
+    }
+    let refs: Vec<TokenStream> = rfa
+        .iter()
+        .filter(|rfa| rfa.mark.contains_key("dep"))
+        .filter_map(|rfa| {
+            let inner = is_arc(&rfa.ty).1?;
+            let name = rfa.mark["dep"]
+                .get("name")
+                .cloned()
+                .unwrap_or_else(|| "___".to_string());
+            Some(quote!(::miko::dependency_container::DependencyRef::of::<#inner>(#name)))
+        })
+        .collect();
+    if refs.is_empty() {
+        return None;
     }
+    let site = fn_name.to_string();
+    Some(quote! {
+        ::miko::inventory::submit! {
+            ::miko::dependency_container::DependencyRequirement {
+                site: #site,
+                deps: || vec![#(#refs),*],
+            }
+        }
+    })
 }
 
-/// 为带有 `#[config(...)]` 的参数生成从配置读取并解析值的语句。
+/// 为带有 `#[config(...)]` 的参数生成从配置读取并解析值的语句（用于返回 `AppResult` 的中间件）。
 ///
-/// 支持所有实现 `serde::de::DeserializeOwned` 的类型,包括基础类型、集合、自定义结构体等。
+/// 支持所有实现 `serde::de::DeserializeOwned` 的类型,包括基础类型、集合、自定义结构体等；
+/// `Option<T>` 在配置缺失时为 `None`。
 pub fn build_config_value_injector(
     rfa: &Vec<RouteFnArg>,
     config_value_stmts: &mut Vec<TokenStream>,
 ) {
     for rfa in rfa {
-        let mark_item = rfa.mark.get("config");
-        if let Some(item) = mark_item {
-            if let Some(path) = item.get_or_default("path") {
-                let (is_option, inner) = is_option(&rfa.ty);
-                let parse_expr = if is_option {
-                    parse_expr_by_type(&inner.unwrap(), path, rfa.ident.clone(), false)
-                } else {
-                    parse_expr_by_type(&rfa.ty, path, rfa.ident.clone(), true)
-                };
-                config_value_stmts.push(parse_expr);
-            } else {
-                panic!("config param must be like #[config(\"xx\")] or #[config(path=\"xx\")] ");
-            }
+        if rfa.mark.contains_key("config") {
+            let path = config_path(rfa);
+            let ident = &rfa.ident;
+            let ty = &rfa.ty;
+            config_value_stmts.push(quote! {
+                let #ident = ::miko::app::config::get_settings_value::<#ty>(#path)?;
+            });
         }
     }
 }
 
-fn parse_expr_by_type(ty: &Type, path: String, ident: syn::Ident, unwrap: bool) -> TokenStream {
-    if unwrap {
-        quote! {
-            let #ident = ::miko::app::config::get_settings_value::<#ty>(#path)?;
+/// 为路由处理函数中带有 `#[config(...)]` 的参数生成配置键类型与 `ConfigValue` 提取器参数。
+///
+/// 读取失败由提取器返回 500，处理函数无需返回 `Result`。
+pub fn build_config_extractors(
+    rfa: &Vec<RouteFnArg>,
+    fn_name: &syn::Ident,
+) -> (Vec<TokenStream>, Vec<FnArg>) {
+    let mut items = Vec::new();
+    let mut inputs = Vec::new();
+    for rfa in rfa {
+        if rfa.mark.contains_key("config") {
+            let path = config_path(rfa);
+            let ident = &rfa.ident;
+            let ty = &rfa.ty;
+            let key = quote::format_ident!("__{}_{}_ConfigKey", fn_name, ident);
+            items.push(quote! {
+                #[allow(non_camel_case_types)]
+                struct #key;
+                impl ::miko::app::config::ConfigKey for #key {
+                    const PATH: &'static str = #path;
+                }
+            });
+            inputs.push(syn::parse_quote! {
+                ::miko::app::config::ConfigValue(#ident, _): ::miko::app::config::ConfigValue<#ty, #key>
+            });
         }
-    } else {
-        quote! {
-            let #ident = ::miko::app::config::get_settings_value::<#ty>(#path);
+    }
+    (items, inputs)
+}
+
+/// 为带有 `#[header]` / `#[header("name")]` 的参数生成请求头键类型与 `NamedHeader` 提取器参数。
+///
+/// 未指定名称时使用参数名，并将 `_` 替换为 `-`；`Option<T>` 在请求头缺失时为 `None`。
+pub fn build_header_extractors(
+    rfa: &Vec<RouteFnArg>,
+    fn_name: &syn::Ident,
+) -> (Vec<TokenStream>, Vec<FnArg>) {
+    let mut items = Vec::new();
+    let mut inputs = Vec::new();
+    for rfa in rfa {
+        if rfa.mark.contains_key("header") {
+            let name = header_name(rfa);
+            let ident = &rfa.ident;
+            let ty = &rfa.ty;
+            let key = quote::format_ident!("__{}_{}_HeaderKey", fn_name, ident);
+            items.push(quote! {
+                #[allow(non_camel_case_types)]
+                struct #key;
+                impl ::miko::extractor::typed_header::HeaderKey for #key {
+                    const NAME: &'static str = #name;
+                }
+            });
+            inputs.push(syn::parse_quote! {
+                ::miko::extractor::typed_header::NamedHeader(#ident, _): ::miko::extractor::typed_header::NamedHeader<#ty, #key>
+            });
         }
     }
+    (items, inputs)
+}
+
+/// `#[header]` 参数对应的请求头名称（小写）
+pub fn header_name(rfa: &RouteFnArg) -> String {
+    rfa.mark["header"]
+        .get_or_default("name")
+        .unwrap_or_else(|| rfa.ident.to_string().replace('_', "-"))
+        .to_ascii_lowercase()
+}
+
+fn config_path(rfa: &RouteFnArg) -> String {
+    rfa.mark["config"]
+        .get_or_default("path")
+        .unwrap_or_else(|| {
+            panic!("config param must be like #[config(\"xx\")] or #[config(path=\"xx\")] ")
+        })
 }
 
 #[allow(unused)]
@@ -244,3 +408,11 @@ pub fn build_clone_stmt(rfa: &RouteFnArg, stmts: &mut Vec<TokenStream>) {
         let #ident = #ident.clone();
     })
 }
+
+/// 判断给定类型是否为 `Vec<T>`（不展开 `Option`）
+pub fn is_vec(ty: &Type) -> bool {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return false;
+    };
+    path.segments.last().is_some_and(|last| last.ident == "Vec")
+}
@@ -86,6 +86,21 @@ impl Parse for UTagAttr {
     }
 }
 
+/// 解析 #[u_doc("internal", "admin")]
+#[derive(Debug, Clone)]
+pub struct UDocAttr {
+    pub docs: Vec<String>,
+}
+
+impl Parse for UDocAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let docs = input.parse_terminated(|input| input.parse::<LitStr>(), Token![,])?;
+        Ok(UDocAttr {
+            docs: docs.iter().map(LitStr::value).collect(),
+        })
+    }
+}
+
 /// 解析 #[u_summary("获取用户信息")]
 #[derive(Debug, Clone)]
 pub struct USummaryAttr {
@@ -240,6 +255,10 @@ pub fn parse_utoipa_attrs(attrs: &[Attribute]) -> crate::utoipa::config::OpenApi
             if let Ok(tag) = attr.parse_args::<UTagAttr>() {
                 config.user_tags.push(tag.tag);
             }
+        } else if path.is_ident("u_doc") {
+            if let Ok(doc) = attr.parse_args::<UDocAttr>() {
+                config.user_docs.extend(doc.docs);
+            }
         } else if path.is_ident("u_summary") {
             if let Ok(summary) = attr.parse_args::<USummaryAttr>() {
                 config.user_summary = Some(summary.summary);
@@ -248,19 +267,19 @@ pub fn parse_utoipa_attrs(attrs: &[Attribute]) -> crate::utoipa::config::OpenApi
             if let Ok(desc) = attr.parse_args::<UDescriptionAttr>() {
                 config.user_description = Some(desc.description);
             }
-        } else if path.is_ident("u_deprecated") {
+        } else if path.is_ident("u_deprecated") || path.is_ident("deprecated_route") {
             config.deprecated = true;
-        } else if path.is_ident("u_request_body") {
-            if let Ok(req_body) = attr.parse_args::<URequestBodyAttr>() {
-                config.user_request_body = Some(crate::utoipa::config::RequestBodyConfig {
-                    ty: req_body.content,
-                    description: req_body.description,
-                    required: true,
-                    content_type: req_body
-                        .content_type
-                        .unwrap_or_else(|| "application/json".to_string()),
-                });
-            }
+        } else if path.is_ident("u_request_body")
+            && let Ok(req_body) = attr.parse_args::<URequestBodyAttr>()
+        {
+            config.user_request_body = Some(crate::utoipa::config::RequestBodyConfig {
+                ty: req_body.content,
+                description: req_body.description,
+                required: true,
+                content_type: req_body
+                    .content_type
+                    .unwrap_or_else(|| "application/json".to_string()),
+            });
         }
     }
 
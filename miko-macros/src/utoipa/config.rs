@@ -65,6 +65,8 @@ pub struct OpenApiConfig {
     pub user_request_body: Option<RequestBodyConfig>,
     /// 是否弃用
     pub deprecated: bool,
+    /// 所属的文档名，为空时属于默认文档
+    pub user_docs: Vec<String>,
 
     // 自动推断的信息
     /// 从文档注释提取的 summary
@@ -73,8 +75,8 @@ pub struct OpenApiConfig {
     pub auto_description: Option<String>,
     /// 从函数参数推断的参数配置
     pub auto_params: Vec<ParamConfig>,
-    /// 从返回类型推断的成功响应
-    pub auto_response: Option<ResponseConfig>,
+    /// 从返回类型推断的响应
+    pub auto_responses: Vec<ResponseConfig>,
     /// 从 #[body] 参数推断的请求体
     pub auto_request_body: Option<RequestBodyConfig>,
 }
@@ -130,14 +132,14 @@ impl OpenApiConfig {
         params
     }
 
-    /// 合并响应：自动推断的 200 响应 + 用户定义的其他响应
+    /// 合并响应：自动推断的响应 + 用户定义的响应，同一状态码以用户定义为准
     pub fn final_responses(&self) -> Vec<ResponseConfig> {
-        let mut responses = Vec::new();
-
-        // 添加自动推断的成功响应
-        if let Some(ref auto_resp) = self.auto_response {
-            responses.push(auto_resp.clone());
-        }
+        let mut responses: Vec<ResponseConfig> = self
+            .auto_responses
+            .iter()
+            .filter(|auto| !self.user_responses.iter().any(|u| u.status == auto.status))
+            .cloned()
+            .collect();
 
         // 添加用户定义的响应
         responses.extend(self.user_responses.iter().cloned());
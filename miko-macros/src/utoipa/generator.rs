@@ -69,13 +69,6 @@ pub fn generate_utoipa_path_attr(
         quote!()
     };
 
-    // Deprecated
-    let deprecated = if config.deprecated {
-        quote!(deprecated,)
-    } else {
-        quote!()
-    };
-
     // Params
     let params = generate_params_tokens(config);
 
@@ -92,7 +85,6 @@ pub fn generate_utoipa_path_attr(
             #summary
             #description
             #tags
-            #deprecated
             #params
             #request_body
             #responses
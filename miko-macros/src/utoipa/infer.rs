@@ -7,18 +7,16 @@ pub fn extract_doc_comments(attrs: &[Attribute]) -> (Option<String>, Option<Stri
     let mut lines = Vec::new();
 
     for attr in attrs {
-        if attr.path().is_ident("doc") {
-            if let Ok(meta) = attr.meta.require_name_value() {
-                if let Expr::Lit(ExprLit {
-                    lit: Lit::Str(lit_str),
-                    ..
-                }) = &meta.value
-                {
-                    let line = lit_str.value().trim().to_string();
-                    if !line.is_empty() {
-                        lines.push(line);
-                    }
-                }
+        if attr.path().is_ident("doc")
+            && let Ok(meta) = attr.meta.require_name_value()
+            && let Expr::Lit(ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) = &meta.value
+        {
+            let line = lit_str.value().trim().to_string();
+            if !line.is_empty() {
+                lines.push(line);
             }
         }
     }
@@ -64,14 +62,12 @@ pub fn infer_params_from_fn_args(
                 let mut content_type = "application/json".to_string();
                 use crate::toolkit::attr::StrAttrMap;
                 for attr in &pat_type.attrs {
-                    if attr.path().is_ident("body") {
-                        if let Meta::List(list) = &attr.meta {
-                            if let Ok(sam) = syn::parse2::<StrAttrMap>(list.tokens.clone()) {
-                                if sam.map.contains_key("str") {
-                                    content_type = "text/plain".to_string();
-                                }
-                            }
-                        }
+                    if attr.path().is_ident("body")
+                        && let Meta::List(list) = &attr.meta
+                        && let Ok(sam) = syn::parse2::<StrAttrMap>(list.tokens.clone())
+                        && sam.map.contains_key("str")
+                    {
+                        content_type = "text/plain".to_string();
                     }
                 }
 
@@ -88,25 +84,25 @@ pub fn infer_params_from_fn_args(
             let (extractor_info, inner_type) = analyze_extractor_type(&pat_type.ty);
 
             // 特殊处理：检查是否是 Json<T> 或 Form<T>
-            if let Type::Path(type_path) = &*pat_type.ty {
-                if let Some(last_segment) = type_path.path.segments.last() {
-                    let type_name = last_segment.ident.to_string();
-                    if matches!(type_name.as_str(), "Json" | "Form") {
-                        let description = extract_desc_from_attrs(&pat_type.attrs);
-                        let content_type = if type_name.as_str() == "Form" {
-                            "application/x-www-form-urlencoded".to_string()
-                        } else {
-                            "application/json".to_string()
-                        };
+            if let Type::Path(type_path) = &*pat_type.ty
+                && let Some(last_segment) = type_path.path.segments.last()
+            {
+                let type_name = last_segment.ident.to_string();
+                if matches!(type_name.as_str(), "Json" | "Form") {
+                    let description = extract_desc_from_attrs(&pat_type.attrs);
+                    let content_type = if type_name.as_str() == "Form" {
+                        "application/x-www-form-urlencoded".to_string()
+                    } else {
+                        "application/json".to_string()
+                    };
 
-                        request_body = Some(crate::utoipa::config::RequestBodyConfig {
-                            ty: inner_type.unwrap_or_else(|| (*pat_type.ty).clone()),
-                            description,
-                            required: true,
-                            content_type,
-                        });
-                        continue;
-                    }
+                    request_body = Some(crate::utoipa::config::RequestBodyConfig {
+                        ty: inner_type.unwrap_or_else(|| (*pat_type.ty).clone()),
+                        description,
+                        required: true,
+                        content_type,
+                    });
+                    continue;
                 }
             }
 
@@ -116,13 +112,23 @@ pub fn infer_params_from_fn_args(
             if let Some(loc) = location {
                 // 提取参数名
                 if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    let param_name = pat_ident.ident.to_string();
+                    let mut param_name = pat_ident.ident.to_string();
 
                     // 提取 #[desc] 描述
                     let description = extract_desc_from_attrs(&pat_type.attrs);
 
                     // 获取实际类型(可能包含 extractor 的内部类型)
-                    let base_type = inner_type.unwrap_or_else(|| (*pat_type.ty).clone());
+                    let mut base_type = inner_type.unwrap_or_else(|| (*pat_type.ty).clone());
+
+                    // 请求头使用真实的头名称，值在文档中统一描述为字符串
+                    if matches!(loc, ParamLocation::Header) {
+                        param_name = header_param_name(&pat_type.attrs, &param_name);
+                        base_type = if is_option_type(&base_type) {
+                            parse_quote!(Option<String>)
+                        } else {
+                            parse_quote!(String)
+                        };
+                    }
 
                     // 判断类型是否是 Option<T>
                     // 注意:我们保持类型为 Option<T>,不提取内部类型
@@ -143,21 +149,19 @@ pub fn infer_params_from_fn_args(
         }
     }
     // 如果还没有 request_body，检查最后一个参数（且无属性）是否为 String
-    if request_body.is_none() {
-        if let Some(last_arg) = inputs.last() {
-            if let FnArg::Typed(pat_type) = last_arg {
-                if pat_type.attrs.is_empty() {
-                    let ty = (*pat_type.ty).clone();
-                    if is_string_type(&ty) {
-                        request_body = Some(crate::utoipa::config::RequestBodyConfig {
-                            ty,
-                            description: None,
-                            required: true,
-                            content_type: "text/plain".to_string(),
-                        });
-                    }
-                }
-            }
+    if request_body.is_none()
+        && let Some(last_arg) = inputs.last()
+        && let FnArg::Typed(pat_type) = last_arg
+        && pat_type.attrs.is_empty()
+    {
+        let ty = (*pat_type.ty).clone();
+        if is_string_type(&ty) {
+            request_body = Some(crate::utoipa::config::RequestBodyConfig {
+                ty,
+                description: None,
+                required: true,
+                content_type: "text/plain".to_string(),
+            });
         }
     }
 
@@ -165,42 +169,42 @@ pub fn infer_params_from_fn_args(
 }
 
 /// 分析提取器类型，返回 (位置, 内部类型)
-/// 支持：Path<T>, Query<T>, Json<T>, Form<T>, State<T>
+/// 支持：Path<T>, PathDe<T>, Query<T>, Json<T>, Form<T>, State<T>
 /// 特殊返回：如果是 Json/Form，返回 (None, Some(T))，调用者应该将其作为 request body
 fn analyze_extractor_type(ty: &Type) -> (Option<ParamLocation>, Option<Type>) {
-    if let Type::Path(type_path) = ty {
-        if let Some(last_segment) = type_path.path.segments.last() {
-            let extractor_name = last_segment.ident.to_string();
-
-            // 先检查是否是已知的提取器类型
-            let location = match extractor_name.as_str() {
-                "Path" => Some(ParamLocation::Path),
-                "Query" => Some(ParamLocation::Query),
-                "Json" | "Form" => None, // 返回 None 表示是 request body
-                "State" | "Extension" | "Extensions" | "Method" | "Uri" => None, // 忽略这些
-                _ => return (None, None), // 不是提取器，直接返回
-            };
-
-            // 只有当确认是提取器时，才提取泛型参数
-            let inner_type = if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
-                args.args.first().and_then(|arg| {
-                    if let GenericArgument::Type(inner) = arg {
-                        Some(inner.clone())
-                    } else {
-                        None
-                    }
-                })
-            } else {
-                None
-            };
-
-            // 对于 Json/Form，我们在外部特殊处理
-            if matches!(extractor_name.as_str(), "Json" | "Form") {
-                return (None, inner_type);
-            }
-
-            return (location, inner_type);
+    if let Type::Path(type_path) = ty
+        && let Some(last_segment) = type_path.path.segments.last()
+    {
+        let extractor_name = last_segment.ident.to_string();
+
+        // 先检查是否是已知的提取器类型
+        let location = match extractor_name.as_str() {
+            "Path" | "PathDe" => Some(ParamLocation::Path),
+            "Query" => Some(ParamLocation::Query),
+            "Json" | "Form" => None, // 返回 None 表示是 request body
+            "State" | "Extension" | "Extensions" | "Method" | "Uri" => None, // 忽略这些
+            _ => return (None, None), // 不是提取器，直接返回
+        };
+
+        // 只有当确认是提取器时，才提取泛型参数
+        let inner_type = if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
+            args.args.first().and_then(|arg| {
+                if let GenericArgument::Type(inner) = arg {
+                    Some(inner.clone())
+                } else {
+                    None
+                }
+            })
+        } else {
+            None
+        };
+
+        // 对于 Json/Form，我们在外部特殊处理
+        if matches!(extractor_name.as_str(), "Json" | "Form") {
+            return (None, inner_type);
         }
+
+        return (location, inner_type);
     }
 
     (None, None)
@@ -213,10 +217,10 @@ fn has_attr(attrs: &[Attribute], name: &str) -> bool {
 
 /// 检查类型是否是 Option<T>
 fn is_option_type(ty: &Type) -> bool {
-    if let Type::Path(type_path) = ty {
-        if let Some(last_segment) = type_path.path.segments.last() {
-            return last_segment.ident == "Option";
-        }
+    if let Type::Path(type_path) = ty
+        && let Some(last_segment) = type_path.path.segments.last()
+    {
+        return last_segment.ident == "Option";
     }
     false
 }
@@ -250,26 +254,97 @@ fn determine_param_location(attrs: &[Attribute]) -> Option<ParamLocation> {
     None
 }
 
+/// 从函数上的 `#[require_header(...)]` 推断必填的请求头参数
+pub fn infer_required_headers(fn_attrs: &[Attribute]) -> Vec<ParamConfig> {
+    crate::route::require_header::extract_require_header_attrs(fn_attrs)
+        .into_iter()
+        .map(|h| ParamConfig {
+            name: h.name,
+            ty: parse_quote!(String),
+            location: ParamLocation::Header,
+            description: h.matches.map(|p| format!("Must match `{}`", p)),
+            required: true,
+            deprecated: false,
+            example: None,
+        })
+        .collect()
+}
+
+/// `#[header]` / `#[header("name")]` 对应的请求头名称
+fn header_param_name(attrs: &[Attribute], ident: &str) -> String {
+    use crate::toolkit::attr::StrAttrMap;
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("header"))
+        .and_then(|attr| match &attr.meta {
+            Meta::List(list) => syn::parse2::<StrAttrMap>(list.tokens.clone()).ok(),
+            _ => None,
+        })
+        .and_then(|sam| sam.get_or_default("name"))
+        .unwrap_or_else(|| ident.replace('_', "-"))
+        .to_ascii_lowercase()
+}
+
 /// 从属性中提取 #[desc("...")] 描述
 fn extract_desc_from_attrs(attrs: &[Attribute]) -> Option<String> {
     for attr in attrs {
-        if attr.path().is_ident("desc") {
-            if let Ok(lit) = attr.parse_args::<LitStr>() {
-                return Some(lit.value());
-            }
+        if attr.path().is_ident("desc")
+            && let Ok(lit) = attr.parse_args::<LitStr>()
+        {
+            return Some(lit.value());
         }
     }
     None
 }
 
 /// 从返回类型推断响应配置
-/// 注意：由于 miko 使用 IntoResponse trait，实际上无法可靠地推断响应类型
-/// 这个函数保留但可能返回 None，建议用户使用 #[u_response] 明确指定
-#[allow(unused_variables)]
-pub fn infer_response_from_return_type(_output: &ReturnType) -> Option<ResponseConfig> {
-    // 由于返回类型是 impl IntoResponse，我们无法推断具体类型
-    // 用户应该使用 #[u_response] 明确指定响应
-    None
+/// 注意：由于 miko 使用 IntoResponse trait，大多数返回类型无法可靠推断，
+/// 目前只识别 `Option<T>`（200 / 404）与 `OrNoContent<T>`（200 / 204），
+/// 外层的 `Result<_, E>` 会被展开；其余情况建议使用 #[u_response] 明确指定
+pub fn infer_response_from_return_type(output: &ReturnType) -> Vec<ResponseConfig> {
+    let ReturnType::Type(_, ty) = output else {
+        return Vec::new();
+    };
+    let Some(wrapper) = optional_response_wrapper(ty) else {
+        return Vec::new();
+    };
+    let (status, description) = match wrapper.as_str() {
+        "Option" => (404, "Not Found"),
+        _ => (204, "No Content"),
+    };
+    vec![
+        ResponseConfig {
+            status: 200,
+            description: "Success".to_string(),
+            body: None,
+            content_type: None,
+        },
+        ResponseConfig {
+            status,
+            description: description.to_string(),
+            body: None,
+            content_type: None,
+        },
+    ]
+}
+
+/// 识别 `Option<T>` / `OrNoContent<T>`（可包在 `Result` 中），返回包装类型名
+fn optional_response_wrapper(ty: &Type) -> Option<String> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let last_segment = type_path.path.segments.last()?;
+    let PathArguments::AngleBracketed(args) = &last_segment.arguments else {
+        return None;
+    };
+    let Some(GenericArgument::Type(inner)) = args.args.first() else {
+        return None;
+    };
+    match last_segment.ident.to_string().as_str() {
+        "Result" | "AppResult" => optional_response_wrapper(inner),
+        name @ ("Option" | "OrNoContent") => Some(name.to_string()),
+        _ => None,
+    }
 }
 
 /// 从类型中提取响应体类型
@@ -282,26 +357,26 @@ fn extract_response_body_type(ty: &Type) -> Option<Type> {
         match last_segment.ident.to_string().as_str() {
             "Result" => {
                 // Result<Json<User>, Error> -> 提取 Ok 类型
-                if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
-                    if let Some(GenericArgument::Type(ok_type)) = args.args.first() {
-                        return extract_response_body_type(ok_type);
-                    }
+                if let PathArguments::AngleBracketed(args) = &last_segment.arguments
+                    && let Some(GenericArgument::Type(ok_type)) = args.args.first()
+                {
+                    return extract_response_body_type(ok_type);
                 }
             }
             "Json" => {
                 // Json<User> -> 提取 User
-                if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
-                    if let Some(GenericArgument::Type(inner_type)) = args.args.first() {
-                        return Some(inner_type.clone());
-                    }
+                if let PathArguments::AngleBracketed(args) = &last_segment.arguments
+                    && let Some(GenericArgument::Type(inner_type)) = args.args.first()
+                {
+                    return Some(inner_type.clone());
                 }
             }
             "Response" => {
                 // Response<Body> -> 提取 Body
-                if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
-                    if let Some(GenericArgument::Type(inner_type)) = args.args.first() {
-                        return Some(inner_type.clone());
-                    }
+                if let PathArguments::AngleBracketed(args) = &last_segment.arguments
+                    && let Some(GenericArgument::Type(inner_type)) = args.args.first()
+                {
+                    return Some(inner_type.clone());
                 }
             }
             _ => {}
@@ -376,10 +451,11 @@ pub fn infer_openapi_config(
     // 推断参数和请求体
     let (params, request_body) = infer_params_from_fn_args(fn_inputs);
     config.auto_params = params;
+    config.auto_params.extend(infer_required_headers(fn_attrs));
     config.auto_request_body = request_body;
 
-    // 推断响应（当前返回 None）
-    config.auto_response = infer_response_from_return_type(fn_output);
+    // 推断响应（仅识别可选响应）
+    config.auto_responses = infer_response_from_return_type(fn_output);
 
     config
 }
@@ -11,4 +11,5 @@ pub mod generator;
 pub mod infer;
 
 #[cfg(test)]
-mod tests;
+#[path = "tests.rs"]
+mod infer_tests;
@@ -6,7 +6,7 @@
 mod tests {
     use crate::utoipa::{
         config::{OpenApiConfig, ParamLocation, ResponseConfig},
-        infer::{extract_doc_comments, infer_params_from_fn_args},
+        infer::{extract_doc_comments, infer_params_from_fn_args, infer_response_from_return_type},
     };
     use syn::punctuated::Punctuated;
     use syn::token::Comma;
@@ -62,6 +62,41 @@ mod tests {
         assert_eq!(params[0].location, ParamLocation::Query);
     }
 
+    #[test]
+    fn test_infer_header_param() {
+        let inputs: Punctuated<FnArg, Comma> = parse_quote! {
+            #[header] user_agent: UserAgent, #[header("X-Api-Key")] key: Option<String>
+        };
+
+        let (params, _) = infer_params_from_fn_args(&inputs);
+
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].name, "user-agent");
+        assert_eq!(params[0].location, ParamLocation::Header);
+        assert!(params[0].required);
+        assert_eq!(params[1].name, "x-api-key");
+        assert!(!params[1].required);
+    }
+
+    #[test]
+    fn test_infer_required_headers() {
+        let attrs: Vec<syn::Attribute> = vec![
+            parse_quote!(#[require_header("X-Api-Version", matches = "2\\.\\d+", status = 426)]),
+            parse_quote!(#[doc = " items"]),
+        ];
+
+        let params = crate::utoipa::infer::infer_required_headers(&attrs);
+
+        assert_eq!(params.len(), 1);
+        assert_eq!(params[0].name, "x-api-version");
+        assert_eq!(params[0].location, ParamLocation::Header);
+        assert!(params[0].required);
+        assert_eq!(
+            params[0].description.as_deref(),
+            Some("Must match `2\\.\\d+`")
+        );
+    }
+
     // 注意：由于 Miko 使用 impl IntoResponse，无法推断响应类型
     // 因此移除了 test_infer_json_response 和 test_infer_result_json_response
 
@@ -137,4 +172,57 @@ mod tests {
             "application/x-www-form-urlencoded"
         );
     }
+
+    #[test]
+    fn test_infer_optional_responses() {
+        let statuses = |output: syn::ReturnType| {
+            infer_response_from_return_type(&output)
+                .iter()
+                .map(|r| r.status)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            statuses(parse_quote!(-> Option<Json<User>>)),
+            vec![200, 404]
+        );
+        assert_eq!(
+            statuses(parse_quote!(-> AppResult<Option<Json<User>>>)),
+            vec![200, 404]
+        );
+        assert_eq!(
+            statuses(parse_quote!(-> OrNoContent<String>)),
+            vec![200, 204]
+        );
+        assert!(statuses(parse_quote!(-> Json<User>)).is_empty());
+        assert!(statuses(parse_quote!()).is_empty());
+    }
+
+    #[test]
+    fn test_user_response_overrides_inferred_status() {
+        let mut config = OpenApiConfig::new();
+        config.auto_responses = infer_response_from_return_type(&parse_quote!(-> Option<String>));
+        config.user_responses.push(ResponseConfig {
+            status: 404,
+            description: "用户不存在".to_string(),
+            body: None,
+            content_type: None,
+        });
+
+        let responses = config.final_responses();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[1].description, "用户不存在");
+    }
+
+    #[test]
+    fn test_parse_u_doc_attrs() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[u_doc("internal")]),
+            parse_quote!(#[u_doc("admin", "ops")]),
+        ];
+
+        let config = crate::utoipa::attributes::parse_utoipa_attrs(&attrs);
+
+        assert_eq!(config.user_docs, vec!["internal", "admin", "ops"]);
+    }
 }
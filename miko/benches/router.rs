@@ -0,0 +1,31 @@
+//! 路由与 handler 热路径基准
+//!
+//! cargo bench -p miko --bench router
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use miko::ext::bench::{bench_service, drive, scenarios};
+use std::hint::black_box;
+
+fn router(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let svc = bench_service();
+    let mut group = c.benchmark_group("router");
+    for scenario in scenarios() {
+        assert!(rt.block_on(drive(&svc, &scenario)).is_success());
+        group.bench_function(scenario.name, |b| {
+            b.to_async(&rt)
+                .iter(|| async { black_box(drive(&svc, &scenario).await) })
+        });
+    }
+    group.finish();
+}
+
+fn build(c: &mut Criterion) {
+    c.bench_function("router/build", |b| b.iter(|| black_box(bench_service())));
+}
+
+criterion_group!(benches, router, build);
+criterion_main!(benches);
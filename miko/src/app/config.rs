@@ -1,36 +1,135 @@
+use crate::error::AppError;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
 use anyhow::{Context, Error};
-use config::Config;
+use hyper::http::request::Parts;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
-use std::sync::OnceLock;
-
-pub fn load_config_sources() -> Result<Config, Error> {
-    let env = env::var("CONFIG_ENV").unwrap_or_else(|_| {
-        if cfg!(debug_assertions) {
-            "dev".to_string()
-        } else {
-            "prod".to_string()
-        }
-    });
-    Ok(Config::builder()
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// 当前运行环境：`MIKO_ENV`，其次 `CONFIG_ENV`，都未设置时 debug 构建为 `dev`、release 构建为 `prod`
+pub fn config_env() -> String {
+    env::var("MIKO_ENV")
+        .or_else(|_| env::var("CONFIG_ENV"))
+        .unwrap_or_else(|_| {
+            if cfg!(debug_assertions) {
+                "dev".to_string()
+            } else {
+                "prod".to_string()
+            }
+        })
+}
+
+static OVERRIDES: Mutex<Vec<(String, config::Value)>> = Mutex::new(Vec::new());
+
+/// 按优先级从低到高合并配置源：
+///
+/// 1. 内置默认值（`server.host`、`server.port`）
+/// 2. `config.{toml,yaml,json}`
+/// 3. 当前环境的 `config.{env}.*`，见 [`config_env`]
+/// 4. `MIKO__` 前缀的环境变量，`MIKO__SERVER__PORT=8080` 对应 `server.port`
+/// 5. [`override_setting`] 设置的值
+pub fn load_config_sources() -> Result<config::Config, Error> {
+    let env = config_env();
+    let mut builder = config::Config::builder()
         .set_default("server.host", "0.0.0.0")?
         .set_default("server.port", 8080)?
         .add_source(config::File::with_name("./config").required(false))
         .add_source(config::File::with_name(&format!("./config.{}", env)).required(false))
-        .add_source(config::Environment::with_prefix("MIKO").separator("__"))
-        .build()?)
+        .add_source(config::Environment::with_prefix("MIKO").separator("__"));
+    for (key, value) in OVERRIDES.lock().unwrap().iter() {
+        builder = builder.set_override(key.as_str(), value.clone())?;
+    }
+    Ok(builder.build()?)
+}
+
+/// 以代码覆盖配置项，优先于所有配置文件与环境变量
+///
+/// 全局配置已加载时立即重新加载，订阅者会收到变更。
+pub fn override_setting(
+    key: impl Into<String>,
+    value: impl Into<config::Value>,
+) -> Result<(), Error> {
+    let key = key.into();
+    {
+        let mut overrides = OVERRIDES.lock().unwrap();
+        overrides.retain(|(k, _)| *k != key);
+        overrides.push((key, value.into()));
+    }
+    if SETTINGS.get().is_some() {
+        reload_settings()?;
+    }
+    Ok(())
 }
 
-static SETTINGS: OnceLock<Config> = OnceLock::new();
+static SETTINGS: OnceLock<watch::Sender<Arc<config::Config>>> = OnceLock::new();
 
-pub fn get_settings() -> &'static Config {
+fn settings_channel() -> &'static watch::Sender<Arc<config::Config>> {
     SETTINGS.get_or_init(|| {
-        load_config_sources().expect("Failed to initialize configuration. Check your config files.")
+        let config = load_config_sources()
+            .expect("Failed to initialize configuration. Check your config files.");
+        watch::Sender::new(Arc::new(config))
+    })
+}
+
+/// 当前的全局配置，[`reload_settings`] 之后返回新的配置
+pub fn get_settings() -> Arc<config::Config> {
+    settings_channel().borrow().clone()
+}
+
+/// 重新读取配置源并替换全局配置，读取失败时保留原配置
+///
+/// 已创建的组件（如 `Mailer`）不会自动重建，需要的组件可通过 [`subscribe_settings`] 自行处理。
+pub fn reload_settings() -> Result<(), Error> {
+    let config = load_config_sources()?;
+    settings_channel().send_replace(Arc::new(config));
+    Ok(())
+}
+
+/// 订阅配置变更，每次 [`reload_settings`] 成功后收到新的配置
+pub fn subscribe_settings() -> watch::Receiver<Arc<config::Config>> {
+    settings_channel().subscribe()
+}
+
+/// 在配置项 `key` 的值变化后调用 `f`，新值反序列化失败时记录警告并跳过
+///
+/// 返回后台任务的句柄，abort 后停止监听。需要在 Tokio 运行时中调用。
+pub fn on_settings_change<T, F>(key: &str, mut f: F) -> JoinHandle<()>
+where
+    T: DeserializeOwned + Send + 'static,
+    F: FnMut(T) + Send + 'static,
+{
+    let key = key.to_string();
+    let mut rx = subscribe_settings();
+    let mut last = rx.borrow_and_update().get::<serde_json::Value>(&key).ok();
+    tokio::spawn(async move {
+        while rx.changed().await.is_ok() {
+            let config = rx.borrow_and_update().clone();
+            let value = config.get::<serde_json::Value>(&key).ok();
+            if value == last {
+                continue;
+            }
+            last = value;
+            match config.get::<T>(&key) {
+                Ok(value) => f(value),
+                Err(e) => tracing::warn!(key, error = %e, "ignoring invalid config change"),
+            }
+        }
     })
 }
+/// 读取配置项并反序列化为 `T`，支持 `key:default` 形式的字面量默认值
+///
+/// `T` 可以是任意 `Deserialize` 类型，包括对应整个子表（含嵌套表与数组）的结构体；
+/// 配置项缺失且没有默认值时，`Option<_>` 得到 `None`
 pub fn get_settings_value<T: DeserializeOwned>(path: &str) -> Result<T, Error> {
-    let (path, default_val) = match path.rsplit_once(":") {
+    // 默认值本身可能包含 `:`（如 URL），以第一个 `:` 分隔
+    let (path, default_val) = match path.split_once(":") {
         Some((p, d)) => (p, Some(d)),
         None => (path, None),
     };
@@ -49,6 +148,9 @@ pub fn get_settings_value<T: DeserializeOwned>(path: &str) -> Result<T, Error> {
                         path, def_str
                     )
                 })
+            } else if let config::ConfigError::NotFound(_) = config_err {
+                // 配置缺失时，Option 等可由 null 构造的类型使用其空值
+                T::deserialize(serde_json::Value::Null).map_err(|_| config_err.into())
             } else {
                 // 没有默认值，直接抛出原始错误
                 Err(config_err.into())
@@ -56,6 +158,75 @@ pub fn get_settings_value<T: DeserializeOwned>(path: &str) -> Result<T, Error> {
         }
     }
 }
+/// `#[config]` 注入使用的配置键，由宏为每个参数生成
+pub trait ConfigKey: Send + 'static {
+    const PATH: &'static str;
+}
+
+/// `#[config("key")]` 参数展开后的提取器
+///
+/// 读取失败（缺失、类型不匹配等）时返回 500，而不要求处理函数返回 `Result`。
+pub struct ConfigValue<T, K>(pub T, pub PhantomData<K>);
+
+impl<S, T, K> FromRequestParts<S> for ConfigValue<T, K>
+where
+    T: DeserializeOwned + Send + 'static,
+    K: ConfigKey,
+{
+    fn from_request_parts(_req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let value = get_settings_value::<T>(K::PATH)
+            .map(|v| ConfigValue(v, PhantomData))
+            .map_err(|e| {
+                AppError::InternalServerError(format!(
+                    "Failed to load config `{}`: {:#}",
+                    K::PATH,
+                    e
+                ))
+            });
+        Box::pin(async move { value })
+    }
+}
+
+/// 将整个配置反序列化为 `T` 的提取器，失败时返回 500
+///
+/// `T` 只需声明关心的字段，子表可使用嵌套结构体：
+///
+/// ```rust,ignore
+/// #[derive(Deserialize)]
+/// struct Settings {
+///     server: ServerSection,
+///     #[serde(default)]
+///     features: Vec<String>,
+/// }
+///
+/// #[get("/features")]
+/// async fn features(Config(settings): Config<Settings>) -> Json<Vec<String>> {
+///     Json(settings.features)
+/// }
+/// ```
+pub struct Config<T>(pub T);
+
+impl<T> std::ops::Deref for Config<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<S, T> FromRequestParts<S> for Config<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    fn from_request_parts(_req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let value = config::Config::clone(&get_settings())
+            .try_deserialize::<T>()
+            .map(Config)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to load config: {}", e)));
+        Box::pin(async move { value })
+    }
+}
+
 fn try_parse_default_value<T: DeserializeOwned>(val: &str) -> Result<T, serde_json::Error> {
     let res = serde_json::from_str::<T>(val);
     res.or_else(|_| serde_json::from_value(serde_json::Value::String(val.to_string())))
@@ -65,12 +236,309 @@ fn try_parse_default_value<T: DeserializeOwned>(val: &str) -> Result<T, serde_js
 pub struct ServerSettings {
     pub host: String,
     pub port: u16,
+    /// 访问日志落盘配置（`[server.access_log]`），缺省时不写文件
+    #[serde(default)]
+    pub access_log: Option<AccessLogSettings>,
+    /// 启动时注册的重定向（`[server.redirects]`），键为原路径
+    #[serde(default)]
+    pub redirects: BTreeMap<String, RedirectRule>,
+    /// 全局请求体大小限制，如 `"2MB"`，缺省时不限制
+    #[serde(default)]
+    pub body_limit: Option<String>,
+    /// 全局处理超时（毫秒），超时返回 504，路由级超时优先
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// HTTP/1 与 HTTP/2 连接参数（`[server.http]`）
+    #[serde(default)]
+    pub http: HttpSettings,
+    /// 启动时、开始接受连接前在进程内执行的预热请求（`[[server.warmup]]`）
+    #[serde(default)]
+    pub warmup: Vec<WarmupRequest>,
+    /// 按路由覆盖的超时、请求体限制、限流与认证要求（`[server.routes."/path"]`），键为注册时的路由模式
+    #[serde(default)]
+    pub routes: BTreeMap<String, RouteOverride>,
+    /// 本地化路由别名（`[server.route_aliases."/about"]`，`de = "/über-uns"`），键为已注册的路由
+    #[serde(default)]
+    pub route_aliases: BTreeMap<String, BTreeMap<String, String>>,
+    /// 启动时打印路由表（方法、路径、处理函数与注册位置）
+    #[serde(default)]
+    pub print_routes: bool,
+    /// 存活与就绪探针（`[server.health]`），需要 `ext`
+    #[serde(default)]
+    pub health: HealthSettings,
 }
 impl Default for ServerSettings {
     fn default() -> Self {
         ServerSettings {
             host: "0.0.0.0".to_string(),
             port: 8080,
+            access_log: None,
+            redirects: BTreeMap::new(),
+            body_limit: None,
+            timeout_ms: None,
+            http: HttpSettings::default(),
+            warmup: Vec::new(),
+            routes: BTreeMap::new(),
+            route_aliases: BTreeMap::new(),
+            print_routes: false,
+            health: HealthSettings::default(),
+        }
+    }
+}
+
+/// 健康检查端点配置，已注册同一路径的 GET 路由时不会覆盖
+///
+/// ```toml
+/// [server.health]
+/// enabled = true
+/// liveness_path = "/healthz"
+/// readiness_path = "/readyz"
+/// timeout_ms = 5000            # 单项就绪检查的超时
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct HealthSettings {
+    /// 是否挂载探针端点
+    pub enabled: bool,
+    /// 存活探针路径
+    pub liveness_path: String,
+    /// 就绪探针路径
+    pub readiness_path: String,
+    /// 单项就绪检查的超时（毫秒）
+    pub timeout_ms: u64,
+}
+impl Default for HealthSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            liveness_path: "/healthz".to_string(),
+            readiness_path: "/readyz".to_string(),
+            timeout_ms: 5000,
+        }
+    }
+}
+
+/// 单个路由的配置覆盖，作用于该路径下的所有方法
+///
+/// ```toml
+/// [server.routes."/api/upload"]
+/// timeout_ms = 30000
+/// body_limit = "50MB"
+/// lenient_query = true
+/// rate_limit = { limit = 10, period_secs = 60, key = "header:x-api-key" }
+/// role = "uploader"          # 需要 `ext`，`auth = true` 只要求已认证
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct RouteOverride {
+    /// 处理超时（毫秒），超时返回 504
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// 请求体大小限制，如 `"50MB"`，优先于全局限制
+    #[serde(default)]
+    pub body_limit: Option<String>,
+    /// 宽松解析查询参数，见 [`LenientQuery`](crate::extractor::LenientQuery)
+    #[serde(default)]
+    pub lenient_query: bool,
+    #[cfg(feature = "ext")]
+    #[serde(default)]
+    pub rate_limit: Option<RouteRateLimit>,
+    /// 要求已认证
+    #[cfg(feature = "ext")]
+    #[serde(default)]
+    pub auth: bool,
+    /// 要求具有该角色（隐含 `auth`）
+    #[cfg(feature = "ext")]
+    #[serde(default)]
+    pub role: Option<String>,
+}
+
+/// 路由级限流：每个客户端在 `period_secs` 秒内最多 `limit` 个请求
+#[cfg(feature = "ext")]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RouteRateLimit {
+    pub limit: u32,
+    #[serde(default = "default_rate_limit_period")]
+    pub period_secs: u64,
+    /// 允许的突发请求数，缺省等于 `limit`
+    #[serde(default)]
+    pub burst: Option<u32>,
+    /// 客户端标识：`"ip"`（缺省）、`"global"`（共享额度）或 `"header:<name>"`
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+#[cfg(feature = "ext")]
+fn default_rate_limit_period() -> u64 {
+    1
+}
+
+/// 重定向规则，仅写目标路径时使用 301
+///
+/// ```toml
+/// [server.redirects]
+/// "/old" = "/new"
+/// "/blog/{slug}" = { to = "/posts/{slug}", status = 308 }
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum RedirectRule {
+    To(String),
+    Detailed {
+        to: String,
+        #[serde(default = "default_redirect_status")]
+        status: u16,
+    },
+}
+
+fn default_redirect_status() -> u16 {
+    301
+}
+
+impl RedirectRule {
+    /// 目标路径
+    pub fn to(&self) -> &str {
+        match self {
+            RedirectRule::To(to) | RedirectRule::Detailed { to, .. } => to,
+        }
+    }
+
+    /// 重定向状态码
+    pub fn status(&self) -> u16 {
+        match self {
+            RedirectRule::To(_) => default_redirect_status(),
+            RedirectRule::Detailed { status, .. } => *status,
+        }
+    }
+}
+
+/// 预热请求，请求会带上 `x-miko-warmup: 1` 头
+///
+/// ```toml
+/// [[server.warmup]]
+/// path = "/"
+///
+/// [[server.warmup]]
+/// method = "POST"
+/// path = "/search"
+/// body = '{"q":"warmup"}'
+/// headers = { content-type = "application/json" }
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct WarmupRequest {
+    #[serde(default = "default_warmup_method")]
+    pub method: String,
+    pub path: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+fn default_warmup_method() -> String {
+    "GET".to_string()
+}
+
+/// 服务端接受的 HTTP 协议
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpMode {
+    /// 自动识别 HTTP/1 与 HTTP/2（h2c）
+    #[default]
+    Auto,
+    /// 仅 HTTP/1
+    Http1,
+    /// 仅 HTTP/2
+    Http2,
+}
+
+/// HTTP 连接参数，未设置的项使用 hyper 的默认值
+///
+/// ```toml
+/// [server.http]
+/// mode = "auto"
+/// http1_keep_alive = true
+/// http1_max_headers = 100
+/// http1_header_read_timeout_ms = 30000
+/// http2_max_concurrent_streams = 200
+/// http2_keep_alive_interval_ms = 20000
+/// http2_keep_alive_timeout_ms = 20000
+/// http2_max_header_list_size = 16384
+/// stream_idle_timeout_ms = 60000
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct HttpSettings {
+    pub mode: HttpMode,
+    /// 是否启用 HTTP/1 keep-alive
+    pub http1_keep_alive: Option<bool>,
+    /// HTTP/1 单个请求允许的最大请求头数量
+    pub http1_max_headers: Option<usize>,
+    /// HTTP/1 读缓冲区上限（字节），同时限制请求头总大小
+    pub http1_max_buf_size: Option<usize>,
+    /// HTTP/1 读取完整请求头的超时（毫秒）
+    pub http1_header_read_timeout_ms: Option<u64>,
+    /// HTTP/2 单连接最大并发流数
+    pub http2_max_concurrent_streams: Option<u32>,
+    /// HTTP/2 keep-alive ping 间隔（毫秒）
+    pub http2_keep_alive_interval_ms: Option<u64>,
+    /// HTTP/2 keep-alive ping 的应答超时（毫秒）
+    pub http2_keep_alive_timeout_ms: Option<u64>,
+    /// HTTP/2 请求头列表的最大字节数
+    pub http2_max_header_list_size: Option<u32>,
+    /// 响应体连续无数据的最长时间（毫秒），超过后断开连接；不限制流的总时长
+    pub stream_idle_timeout_ms: Option<u64>,
+}
+
+/// 访问日志文件的按时间轮转策略
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// 不按时间轮转（仍可按大小轮转）
+    #[default]
+    Never,
+    /// 每小时轮转一次
+    Hourly,
+    /// 每天轮转一次（UTC）
+    Daily,
+}
+
+/// 访问日志文件输出配置
+///
+/// ```toml
+/// [server.access_log]
+/// path = "./logs/access.log"
+/// max_size = 10485760
+/// rotation = "daily"
+/// max_files = 7
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct AccessLogSettings {
+    /// 是否启用
+    pub enabled: bool,
+    /// 日志文件路径，轮转后的文件以 `{path}.{unix_secs}` 命名
+    pub path: String,
+    /// 单个文件的最大字节数，超过后轮转
+    pub max_size: Option<u64>,
+    /// 按时间轮转策略
+    pub rotation: LogRotation,
+    /// 保留的历史文件数量，0 表示不清理
+    pub max_files: usize,
+    /// 写入队列容量，队列满时丢弃新日志而不阻塞请求
+    pub buffer: usize,
+    /// 缓冲区刷盘间隔（毫秒）
+    pub flush_interval_ms: u64,
+}
+impl Default for AccessLogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: "./logs/access.log".to_string(),
+            max_size: None,
+            rotation: LogRotation::Never,
+            max_files: 0,
+            buffer: 1024,
+            flush_interval_ms: 1000,
         }
     }
 }
@@ -82,4 +550,152 @@ impl ServerSettings {
             ServerSettings::default()
         })
     }
+
+    /// 以默认值为起点构建配置
+    ///
+    /// ```rust,ignore
+    /// let settings = ServerSettings::builder().port(3000).body_limit("2MB").build();
+    /// let app = Application::new(settings, router);
+    /// ```
+    pub fn builder() -> ServerSettingsBuilder {
+        ServerSettingsBuilder {
+            settings: ServerSettings::default(),
+        }
+    }
+
+    /// 以当前值为起点构建配置，用于覆盖从配置文件读取的部分项
+    ///
+    /// ```rust,ignore
+    /// let settings = ServerSettings::from_global_settings()
+    ///     .into_builder()
+    ///     .port(3000)
+    ///     .build();
+    /// ```
+    pub fn into_builder(self) -> ServerSettingsBuilder {
+        ServerSettingsBuilder { settings: self }
+    }
+}
+
+/// [`ServerSettings`] 的构建器，由 [`ServerSettings::builder`] 或 [`ServerSettings::into_builder`] 创建
+#[derive(Debug, Clone)]
+pub struct ServerSettingsBuilder {
+    settings: ServerSettings,
+}
+
+impl ServerSettingsBuilder {
+    /// 监听地址
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.settings.host = host.into();
+        self
+    }
+
+    /// 监听端口
+    pub fn port(mut self, port: u16) -> Self {
+        self.settings.port = port;
+        self
+    }
+
+    /// 同时设置监听地址与端口
+    pub fn addr(mut self, addr: SocketAddr) -> Self {
+        self.settings.host = addr.ip().to_string();
+        self.settings.port = addr.port();
+        self
+    }
+
+    /// 全局请求体大小限制，如 `"2MB"`
+    pub fn body_limit(mut self, limit: impl Into<String>) -> Self {
+        self.settings.body_limit = Some(limit.into());
+        self
+    }
+
+    /// 取消全局请求体大小限制
+    pub fn no_body_limit(mut self) -> Self {
+        self.settings.body_limit = None;
+        self
+    }
+
+    /// 全局处理超时，超时返回 504
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.settings.timeout_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// 访问日志落盘配置，`None` 关闭
+    pub fn access_log(mut self, access_log: Option<AccessLogSettings>) -> Self {
+        self.settings.access_log = access_log;
+        self
+    }
+
+    /// 添加一条重定向，已存在的同名路径会被覆盖
+    pub fn redirect(mut self, from: impl Into<String>, rule: RedirectRule) -> Self {
+        self.settings.redirects.insert(from.into(), rule);
+        self
+    }
+
+    /// 替换 HTTP 连接参数
+    pub fn http(mut self, http: HttpSettings) -> Self {
+        self.settings.http = http;
+        self
+    }
+
+    /// 修改 HTTP 连接参数
+    pub fn http_with(mut self, f: impl FnOnce(&mut HttpSettings)) -> Self {
+        f(&mut self.settings.http);
+        self
+    }
+
+    /// 添加一个预热请求
+    pub fn warmup(mut self, request: WarmupRequest) -> Self {
+        self.settings.warmup.push(request);
+        self
+    }
+
+    /// 清空预热请求
+    pub fn no_warmup(mut self) -> Self {
+        self.settings.warmup.clear();
+        self
+    }
+
+    /// 覆盖某个路由的配置，已存在的同一路径会被替换
+    pub fn route(mut self, path: impl Into<String>, route: RouteOverride) -> Self {
+        self.settings.routes.insert(path.into(), route);
+        self
+    }
+
+    /// 为 `path` 添加 `locale` 语言的本地化别名
+    pub fn route_alias(
+        mut self,
+        path: impl Into<String>,
+        locale: impl Into<String>,
+        alias: impl Into<String>,
+    ) -> Self {
+        self.settings
+            .route_aliases
+            .entry(path.into())
+            .or_default()
+            .insert(locale.into(), alias.into());
+        self
+    }
+
+    /// 启动时是否打印路由表
+    pub fn print_routes(mut self, enabled: bool) -> Self {
+        self.settings.print_routes = enabled;
+        self
+    }
+
+    /// 替换健康检查端点配置
+    pub fn health(mut self, health: HealthSettings) -> Self {
+        self.settings.health = health;
+        self
+    }
+
+    /// 不挂载健康检查端点
+    pub fn no_health(mut self) -> Self {
+        self.settings.health.enabled = false;
+        self
+    }
+
+    pub fn build(self) -> ServerSettings {
+        self.settings
+    }
 }
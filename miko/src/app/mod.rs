@@ -1,11 +1,20 @@
-use crate::app::config::ServerSettings;
+use crate::app::config::{HttpMode, HttpSettings, ServerSettings};
+use crate::extractor::ClientAddr;
+use crate::extractor::body_limit::parse_size;
 use crate::handler::Req;
 use crate::http::convert::incoming_to_req::IncomingToInternal;
+use crate::middleware::access_log::{AccessLogLayer, AccessLogWriter};
+use crate::middleware::body_limit::BodyLimitLayer;
+use crate::middleware::extension::ExtensionLayer;
+use crate::middleware::stream_idle::StreamIdleTimeoutLayer;
+use crate::middleware::timeout::TimeoutLayer;
 use crate::router::HttpSvc;
 use crate::router::Router;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
 use hyper::Error as HyperError;
 use hyper_util::{
-    rt::{TokioExecutor, TokioIo},
+    rt::{TokioExecutor, TokioIo, TokioTimer},
     server::conn::auto::Builder as AutoBuilder,
     service::TowerToHyperService,
 };
@@ -15,39 +24,206 @@ use tokio::signal;
 use tokio::time::Duration;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::TaskTracker;
+use tower::Layer;
+use tower::ServiceExt;
+use tower::util::BoxCloneService;
 use tracing;
 
 pub mod config;
+#[cfg(feature = "auto")]
+pub mod plugin;
+#[cfg(feature = "auto")]
+pub mod startup;
+#[cfg(feature = "config-watch")]
+pub mod watcher;
 
 /// 应用程序入口，负责持有配置与路由，并启动 HTTP 服务
 pub struct Application {
     settings: ServerSettings,
+    router: Router,
     svc: HttpSvc<Req>,
+    #[cfg(feature = "migrations")]
+    migrations: Option<crate::ext::migrations::Migrations>,
 }
 
 /// 应用程序
 impl Application {
     /// 使用给定的配置与 Router 构建一个应用实例
+    ///
+    /// 配置中的重定向会在此时注册到 Router，请求体大小限制与处理超时会作用于所有路由
     pub fn new<S: Send + Sync + 'static>(settings: ServerSettings, router: Router<S>) -> Self {
+        // 处理器在注册时已持有状态，这里擦除状态类型以便之后挂载插件
+        let router = router.with_state(());
+        let svc = build_service(&router, &settings);
         Self {
             settings,
-            svc: router.into_tower_service(),
+            router,
+            svc,
+            #[cfg(feature = "migrations")]
+            migrations: None,
         }
     }
 
+    /// 挂载插件：插件的路由合并到应用路由，组件注册到全局依赖容器，并可修改服务配置
+    ///
+    /// 按调用顺序立即执行，在 `#[miko]` 自动发现的插件之后。
+    #[cfg(feature = "auto")]
+    pub fn with_plugin(mut self, plugin: impl plugin::MikoPlugin) -> Self {
+        plugin::configure_plugin(&plugin, &mut self.router, &mut self.settings);
+        self.svc = build_service(&self.router, &self.settings);
+        self
+    }
+
+    /// 挂载数据库迁移，[`run`](Self::run) 会在预热与监听端口前执行
+    ///
+    /// 迁移失败或在 `fail_on_pending` 模式下存在未执行的迁移时，`run` 返回错误，服务不会启动。
+    #[cfg(feature = "migrations")]
+    pub fn with_migrations(mut self, migrations: crate::ext::migrations::Migrations) -> Self {
+        self.migrations = Some(migrations);
+        self
+    }
+
+    /// 接受的 HTTP 协议，默认自动识别
+    pub fn http_mode(mut self, mode: HttpMode) -> Self {
+        self.settings.http.mode = mode;
+        self
+    }
+
+    /// 是否启用 HTTP/1 keep-alive
+    pub fn http1_keep_alive(mut self, enabled: bool) -> Self {
+        self.settings.http.http1_keep_alive = Some(enabled);
+        self
+    }
+
+    /// HTTP/1 单个请求允许的最大请求头数量
+    pub fn http1_max_headers(mut self, max: usize) -> Self {
+        self.settings.http.http1_max_headers = Some(max);
+        self
+    }
+
+    /// HTTP/1 读缓冲区上限（字节），同时限制请求头总大小
+    pub fn http1_max_buf_size(mut self, max: usize) -> Self {
+        self.settings.http.http1_max_buf_size = Some(max);
+        self
+    }
+
+    /// HTTP/1 读取完整请求头的超时
+    pub fn http1_header_read_timeout(mut self, timeout: Duration) -> Self {
+        self.settings.http.http1_header_read_timeout_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// HTTP/2 单连接最大并发流数
+    pub fn http2_max_concurrent_streams(mut self, max: u32) -> Self {
+        self.settings.http.http2_max_concurrent_streams = Some(max);
+        self
+    }
+
+    /// HTTP/2 keep-alive ping 间隔
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.settings.http.http2_keep_alive_interval_ms = Some(interval.as_millis() as u64);
+        self
+    }
+
+    /// HTTP/2 keep-alive ping 的应答超时
+    pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.settings.http.http2_keep_alive_timeout_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// HTTP/2 请求头列表的最大字节数
+    pub fn http2_max_header_list_size(mut self, max: u32) -> Self {
+        self.settings.http.http2_max_header_list_size = Some(max);
+        self
+    }
+
+    /// 流式响应（SSE、分块、文件）连续无数据超过该时间即断开，与请求总时长无关
+    pub fn stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.settings.http.stream_idle_timeout_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
     /// 使用默认/合并后的配置与 Router 构建应用实例
     pub fn new_<S: Send + Sync + 'static>(router: Router<S>) -> Self {
         Self::new(ServerSettings::from_global_settings(), router)
     }
 
+    /// 依次执行配置中的预热请求，返回每个请求的路径与状态码
+    ///
+    /// [`run`](Self::run) 会在监听端口前自动调用；失败的请求只记录警告，不会中断启动。
+    pub async fn warm_up(&self) -> Vec<(String, hyper::StatusCode)> {
+        let mut results = Vec::new();
+        for warmup in &self.settings.warmup {
+            let body = Full::new(Bytes::from(warmup.body.clone().unwrap_or_default()))
+                .map_err(Into::into)
+                .boxed_unsync();
+            let mut builder = hyper::Request::builder()
+                .method(warmup.method.as_str())
+                .uri(miko_core::encode_route(&warmup.path))
+                .header("x-miko-warmup", "1");
+            for (name, value) in &warmup.headers {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            let req: Req = match builder.body(body) {
+                Ok(req) => req,
+                Err(err) => {
+                    tracing::warn!(path = %warmup.path, error = %err, "invalid warm-up request");
+                    continue;
+                }
+            };
+            let start = std::time::Instant::now();
+            match self.svc.clone().oneshot(req).await {
+                Ok(resp) => {
+                    let status = resp.status();
+                    // 读完响应体，确保流式响应中的惰性初始化也被触发
+                    let _ = resp.into_body().collect().await;
+                    if status.is_success() {
+                        tracing::info!(method = %warmup.method, path = %warmup.path, %status, elapsed_ms = start.elapsed().as_millis(), "warm-up request done");
+                    } else {
+                        tracing::warn!(method = %warmup.method, path = %warmup.path, %status, "warm-up request failed");
+                    }
+                    results.push((warmup.path.clone(), status));
+                }
+                Err(err) => {
+                    tracing::warn!(method = %warmup.method, path = %warmup.path, error = ?err, "warm-up request failed");
+                }
+            }
+        }
+        results
+    }
+
     /// 运行应用，基于配置中的地址与端口监听并处理请求
     ///
+    /// 监听前依次执行数据库迁移、`#[on_startup]` 启动任务与预热请求，迁移或启动任务失败时返回错误。
+    ///
     /// 此方法会阻塞当前异步任务，直到出现网络错误或手动终止。
     pub async fn run(self) -> IoResult<()> {
+        if self.settings.print_routes {
+            prepare_router(&self.router, &self.settings).print_routes();
+        }
+        #[cfg(feature = "migrations")]
+        if let Some(migrations) = &self.migrations {
+            migrations.run().await?;
+        }
+        #[cfg(feature = "auto")]
+        startup::run_startup_tasks().await?;
+        self.warm_up().await;
         let addr = format!("{}:{}", self.settings.host, self.settings.port);
         let listener = TcpListener::bind(addr).await?;
-        let executor = TokioExecutor::new();
-        let service_handle = self.svc;
+        let conn_builder = conn_builder(&self.settings.http);
+        let mut service_handle = self.svc;
+        if let Some(access_log) = self.settings.access_log.clone()
+            && access_log.enabled
+        {
+            let writer = AccessLogWriter::file(access_log).await?;
+            service_handle =
+                BoxCloneService::new(AccessLogLayer::new(writer).layer(service_handle));
+        }
+        if let Some(ms) = self.settings.http.stream_idle_timeout_ms {
+            service_handle = BoxCloneService::new(
+                StreamIdleTimeoutLayer::new(Duration::from_millis(ms)).layer(service_handle),
+            );
+        }
         // 创建任务跟踪器以管理连接生命周期
         let tracker = TaskTracker::new();
         // token
@@ -63,7 +239,7 @@ impl Application {
                     break;
                 }
                 r = listener.accept() => {
-                    let (stream, _) = match r {
+                    let (stream, peer) = match r {
                         Ok(pair) => pair,
                         Err(err) =>{
                             tracing::error!("failed to accept connection: {}", err);
@@ -73,14 +249,15 @@ impl Application {
                     let io = TokioIo::new(stream);
 
                     let service_with_conversion = IncomingToInternal {
-                        inner: service_handle.clone(),
+                        inner: BoxCloneService::new(
+                            ExtensionLayer::new(ClientAddr(peer)).layer(service_handle.clone()),
+                        ),
                     };
                     let hyper_service = TowerToHyperService::new(service_with_conversion);
 
-                    let executor = executor.clone();
+                    let builder = conn_builder.clone();
                     let shutdown_token = shutdown_token.clone();
                     tracker.spawn(async move {
-                        let builder = AutoBuilder::new(executor);
                         let conn = builder.serve_connection_with_upgrades(io, hyper_service);
                         tokio::pin!(conn);
                         let res = tokio::select! {
@@ -131,6 +308,76 @@ impl Application {
     }
 }
 
+/// 复制路由并注册配置中的重定向、本地化别名、路由覆盖与健康检查端点
+fn prepare_router(router: &Router, settings: &ServerSettings) -> Router {
+    let mut router = router.clone();
+    router.redirects_from(&settings.redirects);
+    router.localized_aliases_from(&settings.route_aliases);
+    router.route_overrides(&settings.routes);
+    #[cfg(feature = "ext")]
+    if settings.health.enabled {
+        router.health_probes(&settings.health);
+    }
+    router
+}
+
+/// 由路由构建服务，并对所有路由应用请求体大小限制与处理超时
+fn build_service(router: &Router, settings: &ServerSettings) -> HttpSvc<Req> {
+    let mut svc = prepare_router(router, settings).into_tower_service();
+    if let Some(limit) = settings.body_limit.as_deref() {
+        match parse_size(limit) {
+            Some(limit) => svc = BoxCloneService::new(BodyLimitLayer::new(limit).layer(svc)),
+            None => tracing::warn!(limit, "invalid server.body_limit, ignored"),
+        }
+    }
+    if let Some(ms) = settings.timeout_ms {
+        svc = BoxCloneService::new(TimeoutLayer::new(Duration::from_millis(ms)).layer(svc));
+    }
+    svc
+}
+
+/// 按配置构建连接 Builder
+fn conn_builder(http: &HttpSettings) -> AutoBuilder<TokioExecutor> {
+    let mut builder = AutoBuilder::new(TokioExecutor::new());
+    builder.http1().timer(TokioTimer::new());
+    builder.http2().timer(TokioTimer::new());
+    if let Some(enabled) = http.http1_keep_alive {
+        builder.http1().keep_alive(enabled);
+    }
+    if let Some(max) = http.http1_max_headers {
+        builder.http1().max_headers(max);
+    }
+    if let Some(max) = http.http1_max_buf_size {
+        builder.http1().max_buf_size(max);
+    }
+    if let Some(ms) = http.http1_header_read_timeout_ms {
+        builder
+            .http1()
+            .header_read_timeout(Duration::from_millis(ms));
+    }
+    if let Some(max) = http.http2_max_concurrent_streams {
+        builder.http2().max_concurrent_streams(max);
+    }
+    if let Some(ms) = http.http2_keep_alive_interval_ms {
+        builder
+            .http2()
+            .keep_alive_interval(Duration::from_millis(ms));
+    }
+    if let Some(ms) = http.http2_keep_alive_timeout_ms {
+        builder
+            .http2()
+            .keep_alive_timeout(Duration::from_millis(ms));
+    }
+    if let Some(max) = http.http2_max_header_list_size {
+        builder.http2().max_header_list_size(max);
+    }
+    match http.mode {
+        HttpMode::Auto => builder,
+        HttpMode::Http1 => builder.http1_only(),
+        HttpMode::Http2 => builder.http2_only(),
+    }
+}
+
 /// 监听终止信号
 async fn shutdown_signal() {
     let ctrl_c = async {
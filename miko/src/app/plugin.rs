@@ -0,0 +1,110 @@
+//! 插件：打包好的路由、组件与配置，在启动时按顺序挂载到应用
+//!
+//! 插件可通过 `inventory` 自动注册，由 `#[miko]` 在启动时发现并执行：
+//!
+//! ```rust,ignore
+//! struct MetricsPlugin;
+//!
+//! impl MikoPlugin for MetricsPlugin {
+//!     fn name(&self) -> &'static str {
+//!         "metrics"
+//!     }
+//!
+//!     fn configure(&self, router: &mut Router, _: &mut LazyDependencyContainer, _: &mut ServerSettings) {
+//!         router.get("/metrics", || async { "..." });
+//!     }
+//! }
+//!
+//! miko::inventory::submit! {
+//!     miko::app::plugin::PluginRegistration(|| Box::new(MetricsPlugin))
+//! }
+//! ```
+//!
+//! 也可以显式挂载：`Application::new(settings, router).with_plugin(MetricsPlugin)`。
+
+use crate::app::config::ServerSettings;
+use crate::dependency_container::{CONTAINER, LazyDependencyContainer};
+use crate::router::Router;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// 可复用的功能包，在启动时向路由、依赖容器与服务配置注册内容
+pub trait MikoPlugin: Send + Sync + 'static {
+    /// 插件名，用于日志与去重
+    fn name(&self) -> &'static str;
+
+    /// 执行顺序，越小越先执行，默认 0
+    fn order(&self) -> i32 {
+        0
+    }
+
+    /// 注册路由、组件或修改服务配置
+    fn configure(
+        &self,
+        router: &mut Router,
+        container: &mut LazyDependencyContainer,
+        settings: &mut ServerSettings,
+    );
+}
+
+/// 通过 `inventory::submit!` 注册的插件，启动时由 [`configure_registered_plugins`] 执行
+pub struct PluginRegistration(pub fn() -> Box<dyn MikoPlugin>);
+inventory::collect!(PluginRegistration);
+
+/// 所有已注册的插件，按 `order` 排序，相同顺序按名称排序；同名插件只保留一个
+pub fn registered_plugins() -> Vec<Box<dyn MikoPlugin>> {
+    let mut plugins: Vec<Box<dyn MikoPlugin>> = inventory::iter::<PluginRegistration>
+        .into_iter()
+        .map(|r| (r.0)())
+        .collect();
+    plugins.sort_by(|a, b| (a.order(), a.name()).cmp(&(b.order(), b.name())));
+    let mut seen = HashSet::new();
+    plugins.retain(|p| {
+        let first = seen.insert(p.name());
+        if !first {
+            tracing::warn!(
+                plugin = p.name(),
+                "plugin registered more than once, ignored"
+            );
+        }
+        first
+    });
+    plugins
+}
+
+/// 依次执行所有已注册的插件，`#[miko]` 在初始化依赖容器前自动调用
+///
+/// 插件注册的组件会参与随后的依赖校验与预热。
+pub fn configure_registered_plugins<S: Send + Sync + 'static>(
+    router: &mut Router<S>,
+    settings: &mut ServerSettings,
+) {
+    for plugin in registered_plugins() {
+        configure_plugin(plugin.as_ref(), router, settings);
+    }
+}
+
+/// 执行单个插件，插件的路由合并到 `router`
+pub(crate) fn configure_plugin<S: Send + Sync + 'static>(
+    plugin: &dyn MikoPlugin,
+    router: &mut Router<S>,
+    settings: &mut ServerSettings,
+) {
+    let mut container = global_container()
+        .try_write()
+        .expect("dependency container is locked while configuring plugins");
+    let mut routes = Router::new();
+    plugin.configure(&mut routes, &mut container, settings);
+    router.merge(routes);
+    tracing::debug!(plugin = plugin.name(), "plugin configured");
+}
+
+/// 全局依赖容器，尚未初始化时创建（校验与预热仍由 `init_container` 负责）
+fn global_container() -> &'static Arc<RwLock<LazyDependencyContainer>> {
+    if CONTAINER.get().is_none() {
+        // 已被并发初始化时 set 失败，直接使用已有的容器
+        let _ = CONTAINER.set(LazyDependencyContainer::new_());
+    }
+    CONTAINER.get().unwrap()
+}
@@ -0,0 +1,112 @@
+//! 启动任务
+//!
+//! `#[on_startup]` 标注的异步函数在依赖容器初始化之后、开始监听之前执行一次，
+//! 适合缓存预热、数据初始化等工作；参数可以使用 `#[dep]` 与 `#[config]` 注入：
+//!
+//! ```rust,ignore
+//! #[on_startup(order = 10)]
+//! async fn prime_cache(#[dep] cache: Arc<Cache>, #[config("cache.preload")] keys: Vec<String>) -> anyhow::Result<()> {
+//!     cache.load(&keys).await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! 任务按 `order`（默认 0）从小到大依次执行，相同顺序按函数路径排序；
+//! 任一任务返回错误时停止执行，[`Application::run`](crate::app::Application::run) 返回错误，服务不会启动。
+
+use miko_core::BoxError;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 启动任务返回的 Future
+pub type StartupFuture = Pin<Box<dyn Future<Output = Result<(), BoxError>> + Send>>;
+
+/// 由 `#[on_startup]` 通过 `inventory::submit!` 登记的启动任务
+pub struct StartupTask {
+    /// 函数的完整路径，用于日志与排序
+    pub name: &'static str,
+    pub order: i32,
+    /// 定义所在位置（`文件:行`）
+    pub source: &'static str,
+    pub run: fn() -> StartupFuture,
+}
+inventory::collect!(StartupTask);
+
+/// 可作为启动任务返回值的类型：`()` 与 `Result<(), E>`
+pub trait IntoStartupResult {
+    fn into_startup_result(self) -> Result<(), BoxError>;
+}
+
+impl IntoStartupResult for () {
+    fn into_startup_result(self) -> Result<(), BoxError> {
+        Ok(())
+    }
+}
+
+impl<E: Into<BoxError>> IntoStartupResult for Result<(), E> {
+    fn into_startup_result(self) -> Result<(), BoxError> {
+        self.map_err(Into::into)
+    }
+}
+
+/// 启动任务失败
+#[derive(Debug)]
+pub struct StartupError {
+    /// 失败的任务
+    pub task: &'static str,
+    pub source: BoxError,
+}
+
+impl fmt::Display for StartupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "startup task {} failed: {}", self.task, self.source)
+    }
+}
+
+impl std::error::Error for StartupError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+impl From<StartupError> for std::io::Error {
+    fn from(err: StartupError) -> Self {
+        std::io::Error::other(err)
+    }
+}
+
+/// 所有已登记的启动任务，按执行顺序
+pub fn startup_tasks() -> Vec<&'static StartupTask> {
+    let mut tasks: Vec<&'static StartupTask> = inventory::iter::<StartupTask>.into_iter().collect();
+    tasks.sort_by(|a, b| a.order.cmp(&b.order).then(a.name.cmp(b.name)));
+    tasks
+}
+
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+/// 依次执行所有启动任务，进程内只执行一次，之后的调用直接返回
+///
+/// [`Application::run`](crate::app::Application::run) 会自动调用；使用 `#[miko(build)]` 测试时可手动调用。
+pub async fn run_startup_tasks() -> Result<(), StartupError> {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    for task in startup_tasks() {
+        let start = std::time::Instant::now();
+        if let Err(source) = (task.run)().await {
+            tracing::error!(task = task.name, source = task.source, error = %source, "startup task failed");
+            return Err(StartupError {
+                task: task.name,
+                source,
+            });
+        }
+        tracing::info!(
+            task = task.name,
+            elapsed_ms = start.elapsed().as_millis(),
+            "startup task done"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,84 @@
+//! 配置文件热重载（需要 `config-watch` feature）
+//!
+//! [`ConfigWatcher`] 监听工作目录中的 `config.*` 与 `config.{env}.*` 文件，变化后调用
+//! [`reload_settings`](super::config::reload_settings)。之后 `get_settings_value`、`#[config]`
+//! 参数读到的都是新值，订阅者通过 [`on_settings_change`](super::config::on_settings_change)
+//! 收到通知：
+//!
+//! ```rust,ignore
+//! let _watcher = ConfigWatcher::start()?;
+//!
+//! on_settings_change::<String>("log.level", |level| reload_log_filter(&level));
+//! ```
+
+use super::config::{config_env, reload_settings};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// 监听配置文件，drop 后停止
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+    task: JoinHandle<()>,
+}
+
+impl ConfigWatcher {
+    /// 连续修改合并为一次重新加载的等待时间
+    const DEBOUNCE: Duration = Duration::from_millis(200);
+
+    /// 监听当前工作目录，需要在 Tokio 运行时中调用
+    pub fn start() -> notify::Result<Self> {
+        Self::watch_dir(".")
+    }
+
+    /// 监听 `dir` 中的配置文件（不递归）
+    pub fn watch_dir(dir: impl Into<PathBuf>) -> notify::Result<Self> {
+        let dir = dir.into();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+                Ok(event)
+                    if !matches!(event.kind, EventKind::Access(_))
+                        && event.paths.iter().any(|p| is_config_file(p)) =>
+                {
+                    let _ = tx.send(());
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!(error = %e, "config watcher error"),
+            })?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        let task = tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                tokio::time::sleep(Self::DEBOUNCE).await;
+                while rx.try_recv().is_ok() {}
+                match reload_settings() {
+                    Ok(()) => tracing::info!("configuration reloaded"),
+                    Err(e) => {
+                        tracing::error!(error = %e, "failed to reload configuration, keeping previous")
+                    }
+                }
+            }
+        });
+        Ok(Self {
+            _watcher: watcher,
+            task,
+        })
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// `config.<ext>` 或当前环境的 `config.<env>.<ext>`
+fn is_config_file(path: &Path) -> bool {
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    path.extension().is_some() && (stem == "config" || stem == format!("config.{}", config_env()))
+}
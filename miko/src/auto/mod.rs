@@ -2,10 +2,17 @@ mod route;
 pub use route::*;
 
 /// 初始化依赖容器，注册并后台预热所有组件
+///
+/// 依赖图存在问题（缺失依赖、循环依赖等）时 panic，并列出所有问题。
+///
+/// 容器可能已由插件提前创建并注册了组件，此时在已有容器上校验。
 pub async fn init_container() {
-    crate::dependency_container::CONTAINER
+    let container = crate::dependency_container::CONTAINER
         .get_or_init(|| async { crate::dependency_container::LazyDependencyContainer::new_() })
         .await;
+    if let Err(report) = container.read().await.validate() {
+        panic!("{}", report);
+    }
     tokio::spawn(async {
         crate::dependency_container::CONTAINER
             .get()
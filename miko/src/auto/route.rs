@@ -1,7 +1,13 @@
 use crate::router::Router;
 
+/// 向全局路由器注册路由的函数
+pub type RegisterFn = fn(Router) -> Router;
+
+/// 路由宏通过 `inventory::submit!` 提交的注册项，[`collect_global_router`] 依次调用
+///
+/// 自定义路由宏可借助 `miko-macros-core` 生成提交代码。
 pub struct RouteFlag {
-    pub register: fn(Router) -> Router,
+    pub register: RegisterFn,
 }
 
 inventory::collect!(RouteFlag);
@@ -0,0 +1,888 @@
+//! 由 OpenAPI 文档生成类型化客户端
+//!
+//! [`ClientGenerator`] 读取路由宏生成的文档，输出 Rust（基于 `reqwest`）或 TypeScript（基于 `fetch`）客户端：
+//! 每个操作生成一个与处理函数同名的方法（TypeScript 中为驼峰命名），`components.schemas` 中的类型生成对应的
+//! 结构体、枚举或接口。路径参数、查询参数与请求体作为方法参数，`2xx` 响应的 JSON schema 决定返回类型。
+//!
+//! 通常在测试或单独的二进制中生成并提交到仓库，服务端接口变化时重新生成：
+//!
+//! ```rust,ignore
+//! #[test]
+//! fn client_is_up_to_date() {
+//!     let generator = ClientGenerator::new(&miko::openapi::auto_doc()).client_name("PetsClient");
+//!     codegen::write_if_changed("../pets-client/src/api.rs", &generator.rust()).unwrap();
+//!     codegen::write_if_changed("../web/src/api.ts", &generator.typescript()).unwrap();
+//! }
+//! ```
+//!
+//! 生成的 Rust 代码依赖 `reqwest`（`json` feature）、`serde` 与 `serde_json`。
+//! 未通过 `#[u_response]` 等标注响应体的操作返回 `()` / `void`。
+
+use serde_json::{Map, Value};
+use std::fmt::Write;
+use std::path::Path;
+
+/// 生成文件的首行注释
+const HEADER: &str = "// 由 miko 根据 OpenAPI 文档生成，请勿手动修改";
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try",
+    "typeof", "unsized", "virtual", "yield",
+];
+
+/// 客户端生成器
+pub struct ClientGenerator {
+    doc: Value,
+    client_name: String,
+}
+
+struct Param {
+    name: String,
+    schema: Value,
+    required: bool,
+}
+
+enum BodyKind {
+    Json,
+    Form,
+}
+
+enum ResponseKind {
+    Json(Value),
+    Text,
+    Empty,
+}
+
+struct Operation {
+    name: String,
+    method: String,
+    path: String,
+    summary: Option<String>,
+    deprecated: bool,
+    path_params: Vec<Param>,
+    query_params: Vec<Param>,
+    body: Option<(Value, BodyKind)>,
+    response: ResponseKind,
+}
+
+impl ClientGenerator {
+    pub fn new(doc: &utoipa::openapi::OpenApi) -> Self {
+        Self {
+            doc: serde_json::to_value(doc).expect("OpenAPI document is always serializable"),
+            client_name: "ApiClient".to_string(),
+        }
+    }
+
+    /// 客户端类型名，默认 `ApiClient`
+    pub fn client_name(mut self, name: impl Into<String>) -> Self {
+        self.client_name = name.into();
+        self
+    }
+
+    fn schemas(&self) -> Vec<(&String, &Value)> {
+        self.doc
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .map(|m| m.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// 文档中的所有操作，按方法名排序
+    fn operations(&self) -> Vec<Operation> {
+        let mut ops = Vec::new();
+        let Some(paths) = self.doc.get("paths").and_then(Value::as_object) else {
+            return ops;
+        };
+        for (path, item) in paths {
+            let Some(item) = item.as_object() else {
+                continue;
+            };
+            for method in [
+                "get", "post", "put", "patch", "delete", "head", "options", "trace",
+            ] {
+                if let Some(op) = item.get(method) {
+                    ops.push(self.operation(path, method, op, item));
+                }
+            }
+        }
+        ops.sort_by(|a, b| a.name.cmp(&b.name));
+        ops
+    }
+
+    fn operation(
+        &self,
+        path: &str,
+        method: &str,
+        op: &Value,
+        item: &Map<String, Value>,
+    ) -> Operation {
+        let name = op
+            .get("operationId")
+            .and_then(Value::as_str)
+            .map(snake_case)
+            .unwrap_or_else(|| snake_case(&format!("{} {}", method, path)));
+        let mut path_params = Vec::new();
+        let mut query_params = Vec::new();
+        let params = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .into_iter()
+            .chain(op.get("parameters").and_then(Value::as_array))
+            .flatten();
+        for param in params {
+            let param = self.resolve(param);
+            let Some(name) = param.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let p = Param {
+                name: name.to_string(),
+                schema: param.get("schema").cloned().unwrap_or(Value::Null),
+                required: param.get("required") == Some(&Value::Bool(true)),
+            };
+            match param.get("in").and_then(Value::as_str) {
+                Some("path") => path_params.push(p),
+                Some("query") => query_params.push(p),
+                _ => {}
+            }
+        }
+        // 路径参数按在路径中出现的顺序排列
+        path_params.sort_by_key(|p| path.find(&format!("{{{}}}", p.name)).unwrap_or(usize::MAX));
+        query_params.sort_by_key(|p| !p.required);
+        let body = op
+            .get("requestBody")
+            .map(|b| self.resolve(b))
+            .and_then(|b| b.get("content")?.as_object().cloned())
+            .and_then(|content| {
+                if let Some(json) = content.get("application/json") {
+                    Some((json.get("schema").cloned()?, BodyKind::Json))
+                } else {
+                    let form = content.get("application/x-www-form-urlencoded")?;
+                    Some((form.get("schema").cloned()?, BodyKind::Form))
+                }
+            });
+        Operation {
+            name,
+            method: method.to_ascii_uppercase(),
+            path: path.to_string(),
+            summary: op
+                .get("summary")
+                .or_else(|| op.get("description"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            deprecated: op.get("deprecated") == Some(&Value::Bool(true)),
+            path_params,
+            query_params,
+            body,
+            response: self.response(op),
+        }
+    }
+
+    fn response(&self, op: &Value) -> ResponseKind {
+        let Some(responses) = op.get("responses").and_then(Value::as_object) else {
+            return ResponseKind::Empty;
+        };
+        let mut success: Vec<(&String, &Value)> = responses
+            .iter()
+            .filter(|(status, _)| status.starts_with('2'))
+            .collect();
+        success.sort_by_key(|(status, _)| status.as_str());
+        for (_, resp) in success {
+            let resp = self.resolve(resp);
+            let Some(content) = resp.get("content").and_then(Value::as_object) else {
+                continue;
+            };
+            if let Some(schema) = content
+                .get("application/json")
+                .and_then(|c| c.get("schema"))
+            {
+                return ResponseKind::Json(schema.clone());
+            }
+            if content.keys().any(|k| k.starts_with("text/")) {
+                return ResponseKind::Text;
+            }
+        }
+        ResponseKind::Empty
+    }
+
+    /// 解析 components 中的 `$ref`，其余值原样返回
+    fn resolve<'a>(&'a self, value: &'a Value) -> &'a Value {
+        value
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.strip_prefix('#'))
+            .and_then(|p| self.doc.pointer(p))
+            .unwrap_or(value)
+    }
+
+    /// 生成 Rust 客户端源码
+    pub fn rust(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "{}\n", HEADER);
+        out.push_str("use serde::{Deserialize, Serialize};\n");
+        for (name, schema) in self.schemas() {
+            out.push('\n');
+            rust_schema(&mut out, name, schema);
+        }
+        let client = &self.client_name;
+        let _ = write!(
+            out,
+            r#"
+#[derive(Debug, Clone)]
+pub struct {client} {{
+    base_url: String,
+    http: reqwest::Client,
+}}
+
+impl {client} {{
+    pub fn new(base_url: impl Into<String>) -> Self {{
+        Self::with_client(base_url, reqwest::Client::new())
+    }}
+
+    pub fn with_client(base_url: impl Into<String>, http: reqwest::Client) -> Self {{
+        Self {{
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http,
+        }}
+    }}
+"#
+        );
+        for op in self.operations() {
+            out.push('\n');
+            rust_operation(&mut out, &op);
+        }
+        out.push_str(
+            r#"}
+
+fn param<T: Serialize + ?Sized>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(value) => value.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+fn encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
+}
+"#,
+        );
+        out
+    }
+
+    /// 生成 TypeScript 客户端源码
+    pub fn typescript(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "{}", HEADER);
+        for (name, schema) in self.schemas() {
+            out.push('\n');
+            ts_schema(&mut out, name, schema);
+        }
+        let client = &self.client_name;
+        let _ = write!(
+            out,
+            r#"
+export class ApiError extends Error {{
+  constructor(public status: number, public body: string) {{
+    super(`HTTP ${{status}}: ${{body}}`);
+  }}
+}}
+
+export class {client} {{
+  private baseUrl: string;
+
+  constructor(baseUrl: string, private fetchImpl: typeof fetch = fetch) {{
+    this.baseUrl = baseUrl.replace(/\/+$/, "");
+  }}
+
+  private async request(
+    method: string,
+    path: string,
+    query: Record<string, unknown>,
+    body?: {{ json?: unknown; form?: unknown }},
+  ): Promise<Response> {{
+    const params = new URLSearchParams();
+    for (const [key, value] of Object.entries(query)) {{
+      if (value === undefined || value === null) continue;
+      for (const item of Array.isArray(value) ? value : [value]) params.append(key, String(item));
+    }}
+    const search = params.toString();
+    const init: RequestInit = {{ method }};
+    if (body?.json !== undefined) {{
+      init.headers = {{ "content-type": "application/json" }};
+      init.body = JSON.stringify(body.json);
+    }} else if (body?.form !== undefined) {{
+      init.headers = {{ "content-type": "application/x-www-form-urlencoded" }};
+      init.body = new URLSearchParams(body.form as Record<string, string>).toString();
+    }}
+    const resp = await this.fetchImpl(this.baseUrl + path + (search ? "?" + search : ""), init);
+    if (!resp.ok) throw new ApiError(resp.status, await resp.text());
+    return resp;
+  }}
+"#
+        );
+        for op in self.operations() {
+            out.push('\n');
+            ts_operation(&mut out, &op);
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// 内容不同时才写入文件，返回是否写入；会创建缺失的父目录
+pub fn write_if_changed(path: impl AsRef<Path>, content: &str) -> std::io::Result<bool> {
+    let path = path.as_ref();
+    if std::fs::read_to_string(path).is_ok_and(|old| old == content) {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(true)
+}
+
+fn words(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in s.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && prev_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn snake_case(s: &str) -> String {
+    let name = words(s)
+        .iter()
+        .map(|w| w.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+fn pascal_case(s: &str) -> String {
+    let name: String = words(s)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+fn camel_case(s: &str) -> String {
+    let pascal = pascal_case(s);
+    let mut chars = pascal.chars();
+    chars
+        .next()
+        .map(|c| c.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+fn rust_ident(name: &str) -> String {
+    let ident = snake_case(name);
+    if RUST_KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else {
+        ident
+    }
+}
+
+fn ref_name(schema: &Value) -> Option<&str> {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.rsplit('/').next())
+}
+
+/// 去掉 schema 中的 `null` 分支，返回剩余的 schema 与是否可为空
+fn split_nullable(schema: &Value) -> (Value, bool) {
+    if let Some(Value::Array(types)) = schema.get("type") {
+        let rest: Vec<Value> = types.iter().filter(|t| *t != "null").cloned().collect();
+        if rest.len() < types.len() {
+            let mut schema = schema.clone();
+            schema["type"] = match rest.as_slice() {
+                [single] => single.clone(),
+                _ => Value::Array(rest),
+            };
+            return (schema, true);
+        }
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(Value::Array(subs)) = schema.get(key) {
+            let rest: Vec<&Value> = subs
+                .iter()
+                .filter(|s| s.get("type") != Some(&Value::from("null")))
+                .collect();
+            if rest.len() == 1 && subs.len() == 2 {
+                return (rest[0].clone(), true);
+            }
+        }
+    }
+    let nullable = schema.get("nullable") == Some(&Value::Bool(true));
+    (schema.clone(), nullable)
+}
+
+fn rust_type(schema: &Value) -> String {
+    let (schema, nullable) = split_nullable(schema);
+    let ty = if let Some(name) = ref_name(&schema) {
+        pascal_case(name)
+    } else if let Some([single]) = schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+    {
+        rust_type(single)
+    } else {
+        let unsigned = schema
+            .get("minimum")
+            .and_then(Value::as_f64)
+            .is_some_and(|m| m >= 0.0);
+        match (
+            schema.get("type").and_then(Value::as_str),
+            schema.get("format").and_then(Value::as_str),
+        ) {
+            (Some("string"), _) => "String".to_string(),
+            (Some("boolean"), _) => "bool".to_string(),
+            (Some("integer"), Some("int32")) if unsigned => "u32".to_string(),
+            (Some("integer"), Some("int32")) => "i32".to_string(),
+            (Some("integer"), _) if unsigned => "u64".to_string(),
+            (Some("integer"), _) => "i64".to_string(),
+            (Some("number"), Some("float")) => "f32".to_string(),
+            (Some("number"), _) => "f64".to_string(),
+            (Some("array"), _) => format!(
+                "Vec<{}>",
+                schema
+                    .get("items")
+                    .map(rust_type)
+                    .unwrap_or_else(|| "serde_json::Value".to_string())
+            ),
+            (Some("object"), _) => match schema.get("additionalProperties") {
+                Some(inner @ Value::Object(_)) if schema.get("properties").is_none() => {
+                    format!("std::collections::HashMap<String, {}>", rust_type(inner))
+                }
+                _ => "serde_json::Value".to_string(),
+            },
+            _ => "serde_json::Value".to_string(),
+        }
+    };
+    if nullable {
+        format!("Option<{}>", ty)
+    } else {
+        ty
+    }
+}
+
+fn doc_comment(out: &mut String, indent: &str, schema: &Value) {
+    if let Some(desc) = schema.get("description").and_then(Value::as_str) {
+        for line in desc.lines() {
+            let _ = writeln!(out, "{}/// {}", indent, line);
+        }
+    }
+}
+
+fn rust_schema(out: &mut String, name: &str, schema: &Value) {
+    let type_name = pascal_case(name);
+    doc_comment(out, "", schema);
+    let derive = "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]";
+    if let Some(values) = schema.get("enum").and_then(Value::as_array)
+        && values.iter().all(Value::is_string)
+    {
+        let _ = writeln!(out, "{}\npub enum {} {{", derive, type_name);
+        for value in values.iter().filter_map(Value::as_str) {
+            let variant = pascal_case(value);
+            if variant != value {
+                let _ = writeln!(out, "    #[serde(rename = {:?})]", value);
+            }
+            let _ = writeln!(out, "    {},", variant);
+        }
+        out.push_str("}\n");
+    } else if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let _ = writeln!(out, "{}\npub struct {} {{", derive, type_name);
+        for (field, prop) in properties {
+            doc_comment(out, "    ", prop);
+            let ident = rust_ident(field);
+            if ident.trim_start_matches("r#") != field {
+                let _ = writeln!(out, "    #[serde(rename = {:?})]", field);
+            }
+            let mut ty = rust_type(prop);
+            if !required.contains(&field.as_str()) {
+                if !ty.starts_with("Option<") {
+                    ty = format!("Option<{}>", ty);
+                }
+                out.push_str("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+            }
+            let _ = writeln!(out, "    pub {}: {},", ident, ty);
+        }
+        out.push_str("}\n");
+    } else {
+        let _ = writeln!(out, "pub type {} = {};", type_name, rust_type(schema));
+    }
+}
+
+/// 替换路径模板中的参数，`{*rest}` 形式的通配参数同样处理
+fn path_template(path: &str, mut replace: impl FnMut(&str) -> String) -> String {
+    let mut out = String::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = rest[start + 1..start + end].trim_start_matches('*');
+        out.push_str(&replace(name));
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn rust_operation(out: &mut String, op: &Operation) {
+    if let Some(summary) = &op.summary {
+        for line in summary.lines() {
+            let _ = writeln!(out, "    /// {}", line);
+        }
+    }
+    if op.deprecated {
+        out.push_str("    #[deprecated]\n");
+    }
+    let mut args = Vec::new();
+    for p in &op.path_params {
+        args.push(format!(
+            "{}: {}",
+            rust_ident(&p.name),
+            param_type(&rust_type(&p.schema))
+        ));
+    }
+    if let Some((schema, _)) = &op.body {
+        args.push(format!("body: &{}", rust_type(schema)));
+    }
+    for p in &op.query_params {
+        let mut ty = param_type(&rust_type(&p.schema));
+        if !p.required && !ty.starts_with("Option<") {
+            ty = format!("Option<{}>", ty);
+        }
+        args.push(format!("{}: {}", rust_ident(&p.name), ty));
+    }
+    let ret = match &op.response {
+        ResponseKind::Json(schema) => rust_type(schema),
+        ResponseKind::Text => "String".to_string(),
+        ResponseKind::Empty => "()".to_string(),
+    };
+    let mut fn_args = String::from("&self");
+    for arg in &args {
+        fn_args.push_str(", ");
+        fn_args.push_str(arg);
+    }
+    let _ = writeln!(
+        out,
+        "    pub async fn {}({}) -> Result<{}, reqwest::Error> {{",
+        rust_ident(&op.name),
+        fn_args,
+        ret
+    );
+    let mut format_args = String::new();
+    let url = path_template(&op.path, |name| {
+        let _ = write!(format_args, ", encode(&param(&{}))", rust_ident(name));
+        "{}".to_string()
+    });
+    let _ = writeln!(
+        out,
+        "        let url = format!(\"{{}}{}\", self.base_url{});",
+        url, format_args
+    );
+    out.push_str(
+        "        #[allow(unused_mut)]\n        let mut query: Vec<(&str, String)> = Vec::new();\n",
+    );
+    for p in &op.query_params {
+        let ident = rust_ident(&p.name);
+        let ty = rust_type(&p.schema);
+        let optional = !p.required || ty.starts_with("Option<");
+        let inner = ty.strip_prefix("Option<").unwrap_or(&ty);
+        let (indent, value) = if optional {
+            let _ = writeln!(out, "        if let Some(value) = &{} {{", ident);
+            ("            ", "value".to_string())
+        } else {
+            ("        ", format!("&{}", ident))
+        };
+        if inner.starts_with("Vec<") {
+            let _ = writeln!(out, "{}for item in {} {{", indent, value);
+            let _ = writeln!(
+                out,
+                "{}    query.push(({:?}, param(item)));",
+                indent, p.name
+            );
+            let _ = writeln!(out, "{}}}", indent);
+        } else {
+            let _ = writeln!(
+                out,
+                "{}query.push(({:?}, param({})));",
+                indent, p.name, value
+            );
+        }
+        if optional {
+            out.push_str("        }\n");
+        }
+    }
+    let _ = write!(
+        out,
+        "        let req = self.http.request(reqwest::Method::{}, url).query(&query)",
+        op.method
+    );
+    match &op.body {
+        Some((_, BodyKind::Json)) => out.push_str(".json(body)"),
+        Some((_, BodyKind::Form)) => out.push_str(".form(body)"),
+        None => {}
+    }
+    out.push_str(";\n");
+    out.push_str("        let resp = req.send().await?.error_for_status()?;\n");
+    match &op.response {
+        ResponseKind::Json(_) => out.push_str("        resp.json().await\n"),
+        ResponseKind::Text => out.push_str("        resp.text().await\n"),
+        ResponseKind::Empty => out.push_str("        let _ = resp;\n        Ok(())\n"),
+    }
+    out.push_str("    }\n");
+}
+
+/// 参数中的字符串使用 `&str`
+fn param_type(ty: &str) -> String {
+    match ty {
+        "String" => "&str".to_string(),
+        "Option<String>" => "Option<&str>".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn ts_type(schema: &Value) -> String {
+    let (schema, nullable) = split_nullable(schema);
+    let ty = if let Some(name) = ref_name(&schema) {
+        pascal_case(name)
+    } else if let Some([single]) = schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+    {
+        ts_type(single)
+    } else if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        values
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(" | ")
+    } else {
+        match schema.get("type").and_then(Value::as_str) {
+            Some("string") => "string".to_string(),
+            Some("integer") | Some("number") => "number".to_string(),
+            Some("boolean") => "boolean".to_string(),
+            Some("array") => {
+                let item = schema
+                    .get("items")
+                    .map(ts_type)
+                    .unwrap_or_else(|| "unknown".to_string());
+                if item.contains(' ') {
+                    format!("({})[]", item)
+                } else {
+                    format!("{}[]", item)
+                }
+            }
+            Some("object") => {
+                match (schema.get("properties"), schema.get("additionalProperties")) {
+                    (Some(Value::Object(props)), _) => {
+                        let required = required_fields(&schema);
+                        let fields: Vec<String> = props
+                            .iter()
+                            .map(|(name, prop)| {
+                                let opt = if required.contains(&name.as_str()) {
+                                    ""
+                                } else {
+                                    "?"
+                                };
+                                format!("{}{}: {}", ts_key(name), opt, ts_type(prop))
+                            })
+                            .collect();
+                        format!("{{ {} }}", fields.join("; "))
+                    }
+                    (None, Some(inner @ Value::Object(_))) => {
+                        format!("Record<string, {}>", ts_type(inner))
+                    }
+                    _ => "Record<string, unknown>".to_string(),
+                }
+            }
+            _ => "unknown".to_string(),
+        }
+    };
+    if nullable {
+        format!("{} | null", ty)
+    } else {
+        ty
+    }
+}
+
+fn required_fields(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn ts_key(name: &str) -> String {
+    let plain = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+    if plain {
+        name.to_string()
+    } else {
+        format!("{:?}", name)
+    }
+}
+
+fn ts_doc(out: &mut String, indent: &str, text: Option<&str>) {
+    if let Some(text) = text {
+        let _ = writeln!(out, "{}/** {} */", indent, text.replace('\n', " "));
+    }
+}
+
+fn ts_schema(out: &mut String, name: &str, schema: &Value) {
+    let type_name = pascal_case(name);
+    ts_doc(out, "", schema.get("description").and_then(Value::as_str));
+    match schema.get("properties").and_then(Value::as_object) {
+        Some(props) if schema.get("enum").is_none() => {
+            let required = required_fields(schema);
+            let _ = writeln!(out, "export interface {} {{", type_name);
+            for (field, prop) in props {
+                ts_doc(out, "  ", prop.get("description").and_then(Value::as_str));
+                let opt = if required.contains(&field.as_str()) {
+                    ""
+                } else {
+                    "?"
+                };
+                let _ = writeln!(out, "  {}{}: {};", ts_key(field), opt, ts_type(prop));
+            }
+            out.push_str("}\n");
+        }
+        _ => {
+            let _ = writeln!(out, "export type {} = {};", type_name, ts_type(schema));
+        }
+    }
+}
+
+fn ts_operation(out: &mut String, op: &Operation) {
+    let summary = op.summary.as_deref().map(|s| {
+        if op.deprecated {
+            format!("@deprecated {}", s)
+        } else {
+            s.to_string()
+        }
+    });
+    ts_doc(
+        out,
+        "  ",
+        summary
+            .as_deref()
+            .or(op.deprecated.then_some("@deprecated")),
+    );
+    let mut args = Vec::new();
+    for p in &op.path_params {
+        args.push(format!("{}: {}", camel_case(&p.name), ts_type(&p.schema)));
+    }
+    if let Some((schema, _)) = &op.body {
+        args.push(format!("body: {}", ts_type(schema)));
+    }
+    for p in &op.query_params {
+        let opt = if p.required { "" } else { "?" };
+        args.push(format!(
+            "{}{}: {}",
+            camel_case(&p.name),
+            opt,
+            ts_type(&p.schema)
+        ));
+    }
+    let ret = match &op.response {
+        ResponseKind::Json(schema) => ts_type(schema),
+        ResponseKind::Text => "string".to_string(),
+        ResponseKind::Empty => "void".to_string(),
+    };
+    let _ = writeln!(
+        out,
+        "  async {}({}): Promise<{}> {{",
+        camel_case(&op.name),
+        args.join(", "),
+        ret
+    );
+    let path = path_template(&op.path, |name| {
+        format!("${{encodeURIComponent(String({}))}}", camel_case(name))
+    });
+    let query: Vec<String> = op
+        .query_params
+        .iter()
+        .map(|p| format!("{}: {}", ts_key(&p.name), camel_case(&p.name)))
+        .collect();
+    let body = match &op.body {
+        Some((_, BodyKind::Json)) => ", { json: body }",
+        Some((_, BodyKind::Form)) => ", { form: body }",
+        None => "",
+    };
+    let query = if query.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", query.join(", "))
+    };
+    let _ = writeln!(
+        out,
+        "    const resp = await this.request({:?}, `{}`, {}{});",
+        op.method, path, query, body
+    );
+    match &op.response {
+        ResponseKind::Json(_) => {
+            let _ = writeln!(out, "    return (await resp.json()) as {};", ret);
+        }
+        ResponseKind::Text => out.push_str("    return await resp.text();\n"),
+        ResponseKind::Empty => out.push_str("    void resp;\n"),
+    }
+    out.push_str("  }\n");
+}
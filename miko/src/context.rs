@@ -0,0 +1,133 @@
+//! 请求上下文：trace id、截止时间与租户
+//!
+//! 上下文保存在 task-local 中，`tokio::spawn` 出的任务读不到。用 [`spawn_with_context`]
+//! 代替 `tokio::spawn`，后台任务仍能拿到发起请求的 trace id 与 tracing span，日志与错误响应可以关联回原请求：
+//!
+//! ```rust,ignore
+//! #[post("/orders")]
+//! async fn create(Json(order): Json<Order>) -> AppResult<()> {
+//!     miko::spawn_with_context(async move {
+//!         // get_trace_id() 与处理器中相同
+//!         notify_warehouse(order).await;
+//!     });
+//!     Ok(())
+//! }
+//! ```
+//!
+//! 比请求存活更久的任务（推送、订阅等）用 [`spawn_linked`]，任务在独立的 span 中运行，
+//! 并通过 follows-from 关联到发起它的请求。
+//!
+//! 中间件可以用 [`RequestContext::scope`] 设置截止时间与租户：
+//!
+//! ```rust,ignore
+//! let ctx = RequestContext::current().with_tenant(tenant).with_timeout(Duration::from_secs(5));
+//! Box::pin(ctx.scope(inner.call(req)))
+//! ```
+
+use crate::error::app_error::{TRACE_ID, get_trace_id};
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use hyper::http::request::Parts;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::task_local;
+use tokio::time::Instant;
+use tracing::{Instrument, Span};
+
+task_local! {
+    static CONTEXT: RequestContext;
+}
+
+/// 当前请求的关联信息，可作为提取器使用
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestContext {
+    pub trace_id: Option<String>,
+    /// 请求应在此之前完成
+    pub deadline: Option<Instant>,
+    pub tenant: Option<String>,
+}
+
+impl RequestContext {
+    /// 当前任务的上下文，不在请求中时为空
+    pub fn current() -> Self {
+        let mut ctx = CONTEXT.try_with(Clone::clone).unwrap_or_default();
+        if let Some(id) = get_trace_id() {
+            ctx.trace_id = Some(id);
+        }
+        ctx
+    }
+
+    pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// 设置截止时间，已有更早的截止时间时保留原值
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(self.deadline.map_or(deadline, |d| d.min(deadline)));
+        self
+    }
+
+    /// 截止时间设为从现在起 `timeout` 之后
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn with_tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// 距截止时间的剩余时长，已超时为零，没有截止时间为 `None`
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|d| d.saturating_duration_since(Instant::now()))
+    }
+
+    /// 在该上下文中运行 `fut`
+    pub async fn scope<F: Future>(self, fut: F) -> F::Output {
+        match self.trace_id.clone() {
+            Some(id) => TRACE_ID.scope(id, CONTEXT.scope(self, fut)).await,
+            None => CONTEXT.scope(self, fut).await,
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for RequestContext {
+    fn from_request_parts(_req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let ctx = Self::current();
+        Box::pin(async move { Ok(ctx) })
+    }
+}
+
+/// 与 `tokio::spawn` 相同，但任务继承当前请求的 [`RequestContext`]，并在当前 span 下运行
+pub fn spawn_with_context<F>(fut: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(RequestContext::current().scope(fut).in_current_span())
+}
+
+/// 启动长时间运行的后台任务，继承当前请求的 [`RequestContext`]
+///
+/// 任务在 [`task_span`] 创建的新 span 中运行：请求结束后任务仍可继续记录，
+/// 链路经 follows-from 关联回请求，而不会把请求 span 拉长。SSE、WebSocket 等任务由框架以此启动。
+pub fn spawn_linked<F>(name: &'static str, fut: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let span = task_span(name);
+    tokio::spawn(RequestContext::current().scope(fut).instrument(span))
+}
+
+/// 创建名为 `name` 的根 span，并以 follows-from 关联到当前 span
+///
+/// 导出到 OpenTelemetry 时为一条新链路，带有指向发起方的 link。
+pub fn task_span(name: &'static str) -> Span {
+    let span = tracing::info_span!(parent: None, "task", otel.name = name, task = name);
+    span.follows_from(Span::current());
+    span
+}
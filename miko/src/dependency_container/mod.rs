@@ -1,7 +1,9 @@
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use hyper::http::request::Parts;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::OnceCell;
 #[cfg(feature = "auto")]
 use tokio::sync::{RwLock, RwLockReadGuard};
@@ -11,24 +13,66 @@ pub static CONTAINER: OnceCell<Arc<RwLock<LazyDependencyContainer>>> = OnceCell:
 
 type DependencyInstanceFuture = Pin<Box<dyn Future<Output = DependencyInstance> + Send>>;
 type DependencyInstance = Arc<dyn Any + Send + Sync>;
+type DependencyKey = (TypeId, &'static str);
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DependencyLifetime {
     Singleton,
     Transient,
+    /// 每个请求一个实例，同一请求内的所有 `#[dep]` 共享
+    Scoped,
 }
 
 pub struct DependencyDefFn(pub fn() -> DependencyDef);
 pub struct DependencyDef {
     pub type_id: TypeId,
+    /// 类型名，用于诊断信息
+    pub type_name: &'static str,
     pub prewarm: bool,
     pub name: &'static str,
     pub init_fn: fn() -> DependencyInstanceFuture,
     pub lifetime: DependencyLifetime,
+    /// 构造函数依赖的组件，用于启动时校验
+    pub deps: fn() -> Vec<DependencyRef>,
+    /// 定义所在位置（`文件:行`），用于诊断信息
+    pub source: &'static str,
+    /// 有意覆盖同一类型与名称的其他定义（`#[component(replace)]`）
+    pub replace: bool,
 }
 #[cfg(feature = "auto")]
 inventory::collect!(DependencyDefFn);
 
+/// 对某个已注册依赖的引用
+#[derive(Clone, Copy, Debug)]
+pub struct DependencyRef {
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+    pub name: &'static str,
+}
+
+impl DependencyRef {
+    pub fn of<T: ?Sized + 'static>(name: &'static str) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            name,
+        }
+    }
+
+    fn key(&self) -> DependencyKey {
+        (self.type_id, self.name)
+    }
+}
+
+/// 处理器通过 `#[dep]` 声明的依赖，由路由宏登记，用于启动时校验
+pub struct DependencyRequirement {
+    /// 使用依赖的位置，如处理器函数名
+    pub site: &'static str,
+    pub deps: fn() -> Vec<DependencyRef>,
+}
+#[cfg(feature = "auto")]
+inventory::collect!(DependencyRequirement);
+
 type FactoryFuture = Pin<Box<dyn Future<Output = DependencyInstance> + Send>>;
 
 #[derive(Clone)]
@@ -55,6 +99,9 @@ impl DependencyEntry {
     }
 }
 
+mod validate;
+pub use validate::{DependencyIssue, DependencyReport};
+
 pub struct LazyDependencyContainer {
     pub registry: HashMap<(TypeId, &'static str), DependencyEntry>,
 }
@@ -74,9 +121,18 @@ impl LazyDependencyContainer {
             .map(|v| v.0())
             .collect();
 
+        // 同一类型与名称只保留一个定义：`replace` 的定义优先，否则保留先收集到的，
+        // 重复定义由 `validate` 报告
+        let mut replaced = HashMap::new();
         for dep in deps {
+            let key = (dep.type_id, dep.name);
+            match replaced.get(&key) {
+                Some(&prev) if prev || !dep.replace => continue,
+                _ => {}
+            }
+            replaced.insert(key, dep.replace);
             registry.insert(
-                (dep.type_id, dep.name),
+                key,
                 DependencyEntry::new(dep.init_fn, dep.lifetime, dep.prewarm),
             );
         }
@@ -84,6 +140,28 @@ impl LazyDependencyContainer {
         Arc::new(RwLock::new(Self { registry }))
     }
 
+    #[cfg(feature = "auto")]
+    /// 校验自动注册的组件与处理器的 `#[dep]` 依赖，返回发现的所有问题
+    ///
+    /// 检查重复注册的组件、缺失的依赖、构造函数之间的循环依赖，以及单例/瞬态组件依赖请求作用域组件。
+    pub fn validate(&self) -> Result<(), DependencyReport> {
+        let defs: Vec<DependencyDef> = inventory::iter::<DependencyDefFn>
+            .into_iter()
+            .map(|v| v.0())
+            .collect();
+        let requirements: Vec<(&'static str, Vec<DependencyRef>)> =
+            inventory::iter::<DependencyRequirement>
+                .into_iter()
+                .map(|r| (r.site, (r.deps)()))
+                .collect();
+        let issues = validate::check(&defs, &requirements, &self.registry);
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(DependencyReport(issues))
+        }
+    }
+
     fn insert_entry<T: 'static + Send + Sync>(
         &mut self,
         name: &'static str,
@@ -132,18 +210,13 @@ impl LazyDependencyContainer {
         self.register_with_lifetime::<T>(prewarm, DependencyLifetime::Singleton, factory);
     }
     pub async fn get_<T: 'static + Send + Sync>(&self, name: &'static str) -> Arc<T> {
-        let entry = self
-            .registry
-            .get(&(TypeId::of::<T>(), name))
-            .expect("No dependency entry found for type");
-        self.resolve_entry(entry).await.downcast_arc::<T>().unwrap()
+        self.resolve_in(DependencyRef::of::<T>(name), None)
+            .await
+            .downcast_arc::<T>()
+            .unwrap()
     }
     pub async fn get<T: 'static + Send + Sync>(&self) -> Arc<T> {
-        let entry = self
-            .registry
-            .get(&(TypeId::of::<T>(), "___"))
-            .expect("No dependency entry found for type");
-        self.resolve_entry(entry).await.downcast_arc::<T>().unwrap()
+        self.get_::<T>("___").await
     }
 
     async fn resolve_entry(&self, entry: &DependencyEntry) -> DependencyInstance {
@@ -156,6 +229,95 @@ impl LazyDependencyContainer {
                 cell.get_or_init(entry.factory).await.clone()
             }
             DependencyLifetime::Transient => (entry.factory)().await,
+            DependencyLifetime::Scoped => {
+                panic!("Scoped dependency can only be resolved within a request scope")
+            }
+        }
+    }
+
+    /// 在请求作用域内按名称获取依赖，`Scoped` 依赖在同一作用域内只创建一次
+    pub async fn get_in_<T: 'static + Send + Sync>(
+        &self,
+        name: &'static str,
+        scope: &RequestScope,
+    ) -> Arc<T> {
+        self.resolve_in(DependencyRef::of::<T>(name), Some(scope))
+            .await
+            .downcast_arc::<T>()
+            .unwrap()
+    }
+
+    /// 在请求作用域内获取默认名称的依赖
+    pub async fn get_in<T: 'static + Send + Sync>(&self, scope: &RequestScope) -> Arc<T> {
+        self.get_in_::<T>("___", scope).await
+    }
+
+    /// 按名称获取以 `#[component(bind = dyn Trait)]` 绑定的 trait 对象
+    pub async fn get_dyn_<T: ?Sized + 'static>(&self, name: &'static str) -> Arc<T>
+    where
+        Arc<T>: Send + Sync,
+    {
+        Self::unwrap_dyn(self.resolve_in(DependencyRef::of::<T>(name), None).await)
+    }
+
+    pub async fn get_dyn<T: ?Sized + 'static>(&self) -> Arc<T>
+    where
+        Arc<T>: Send + Sync,
+    {
+        self.get_dyn_::<T>("___").await
+    }
+
+    /// 在请求作用域内按名称获取绑定的 trait 对象
+    pub async fn get_dyn_in_<T: ?Sized + 'static>(
+        &self,
+        name: &'static str,
+        scope: &RequestScope,
+    ) -> Arc<T>
+    where
+        Arc<T>: Send + Sync,
+    {
+        Self::unwrap_dyn(
+            self.resolve_in(DependencyRef::of::<T>(name), Some(scope))
+                .await,
+        )
+    }
+
+    pub async fn get_dyn_in<T: ?Sized + 'static>(&self, scope: &RequestScope) -> Arc<T>
+    where
+        Arc<T>: Send + Sync,
+    {
+        self.get_dyn_in_::<T>("___", scope).await
+    }
+
+    /// trait 对象以 `Arc<dyn Trait>` 的形式存放在实例中
+    fn unwrap_dyn<T: ?Sized + 'static>(instance: DependencyInstance) -> Arc<T>
+    where
+        Arc<T>: Send + Sync,
+    {
+        instance
+            .downcast_arc::<Arc<T>>()
+            .expect("Trait binding stored with unexpected type")
+            .as_ref()
+            .clone()
+    }
+
+    async fn resolve_in(
+        &self,
+        dep: DependencyRef,
+        scope: Option<&RequestScope>,
+    ) -> DependencyInstance {
+        let key = dep.key();
+        let entry = self.registry.get(&key).unwrap_or_else(|| {
+            panic!(
+                "No dependency entry found for type `{}` (name \"{}\")",
+                dep.type_name, dep.name
+            )
+        });
+        match scope {
+            Some(scope) if entry.lifetime == DependencyLifetime::Scoped => {
+                scope.cell(key).get_or_init(entry.factory).await.clone()
+            }
+            _ => self.resolve_entry(entry).await,
         }
     }
 
@@ -171,6 +333,40 @@ impl LazyDependencyContainer {
     }
 }
 
+/// 一次请求的依赖作用域，保存 `Scoped` 依赖的实例
+///
+/// 作为提取器使用时取自请求扩展（不存在则创建并写回），
+/// 因此同一请求内的中间件与处理器拿到的是同一个作用域。
+#[derive(Clone, Default)]
+pub struct RequestScope {
+    instances: Arc<Mutex<HashMap<DependencyKey, Arc<OnceCell<DependencyInstance>>>>>,
+}
+
+impl RequestScope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn cell(&self, key: DependencyKey) -> Arc<OnceCell<DependencyInstance>> {
+        self.instances
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(key)
+            .or_default()
+            .clone()
+    }
+}
+
+impl<S> FromRequestParts<S> for RequestScope {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let scope = req
+            .extensions
+            .get_or_insert_with(RequestScope::default)
+            .clone();
+        Box::pin(async move { Ok(scope) })
+    }
+}
+
 pub trait ArcAnyExt {
     fn downcast_arc<T: Any + Send + Sync>(self: Arc<Self>) -> Option<Arc<T>>
     where
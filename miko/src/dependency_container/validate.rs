@@ -0,0 +1,216 @@
+//! 启动时校验依赖图
+//!
+//! 在第一个请求之前找出重复注册的组件、缺失的依赖、构造函数之间的循环依赖，
+//! 以及单例/瞬态组件依赖请求作用域组件的情况，并一次性报告所有问题。
+
+use super::{DependencyDef, DependencyEntry, DependencyKey, DependencyLifetime, DependencyRef};
+use std::collections::HashMap;
+use std::fmt;
+
+/// 依赖图中的一个问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyIssue {
+    /// 同一类型与名称注册了多次，且没有唯一一个以 `replace` 标记
+    Duplicate {
+        component: String,
+        sources: Vec<String>,
+    },
+    /// `required_by` 依赖的组件未注册
+    Missing {
+        required_by: String,
+        dependency: String,
+    },
+    /// 构造函数之间的循环依赖，首尾为同一组件
+    Cycle(Vec<String>),
+    /// 单例或瞬态组件的构造函数依赖了请求作用域组件
+    ScopedDependency {
+        component: String,
+        dependency: String,
+    },
+}
+
+impl fmt::Display for DependencyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyIssue::Duplicate { component, sources } => write!(
+                f,
+                "{} is registered more than once ({}); mark the intended override with #[component(replace)]",
+                component,
+                sources.join(", ")
+            ),
+            DependencyIssue::Missing {
+                required_by,
+                dependency,
+            } => write!(
+                f,
+                "{} depends on {}, which is not registered",
+                required_by, dependency
+            ),
+            DependencyIssue::Cycle(path) => {
+                write!(f, "circular dependency: {}", path.join(" -> "))
+            }
+            DependencyIssue::ScopedDependency {
+                component,
+                dependency,
+            } => write!(
+                f,
+                "{} depends on request-scoped {}, which only exists within a request",
+                component, dependency
+            ),
+        }
+    }
+}
+
+/// 校验发现的所有问题
+#[derive(Debug, Clone)]
+pub struct DependencyReport(pub Vec<DependencyIssue>);
+
+impl fmt::Display for DependencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "found {} dependency problem(s):", self.0.len())?;
+        for issue in &self.0 {
+            write!(f, "\n  - {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DependencyReport {}
+
+fn describe(type_name: &str, name: &str) -> String {
+    if name == "___" {
+        format!("`{}`", type_name)
+    } else {
+        format!("`{}` (name \"{}\")", type_name, name)
+    }
+}
+
+struct Node {
+    label: String,
+    lifetime: DependencyLifetime,
+    deps: Vec<DependencyRef>,
+}
+
+/// 校验组件定义与处理器的依赖声明，`registered` 还包含手动注册的依赖
+pub(super) fn check(
+    defs: &[DependencyDef],
+    requirements: &[(&'static str, Vec<DependencyRef>)],
+    registered: &HashMap<DependencyKey, DependencyEntry>,
+) -> Vec<DependencyIssue> {
+    let mut issues = Vec::new();
+    let mut groups: HashMap<DependencyKey, Vec<&DependencyDef>> = HashMap::new();
+    for def in defs {
+        groups.entry((def.type_id, def.name)).or_default().push(def);
+    }
+    let mut duplicates: Vec<DependencyIssue> = groups
+        .values()
+        .filter(|group| {
+            let replacing = group.iter().filter(|def| def.replace).count();
+            group.len() > 1 && replacing != 1
+        })
+        .map(|group| {
+            let mut sources: Vec<String> = group.iter().map(|def| def.source.to_string()).collect();
+            sources.sort();
+            DependencyIssue::Duplicate {
+                component: describe(group[0].type_name, group[0].name),
+                sources,
+            }
+        })
+        .collect();
+    duplicates.sort_by_key(|issue| issue.to_string());
+    issues.extend(duplicates);
+
+    // 与 `new_` 一致：`replace` 的定义优先，否则保留先收集到的
+    let mut nodes: HashMap<DependencyKey, Node> = HashMap::new();
+    for (key, group) in &groups {
+        let def = group.iter().find(|def| def.replace).unwrap_or(&group[0]);
+        nodes.insert(
+            *key,
+            Node {
+                label: describe(def.type_name, def.name),
+                lifetime: def.lifetime,
+                deps: (def.deps)(),
+            },
+        );
+    }
+    // 按名称排序，保证报告顺序稳定
+    let mut keys: Vec<DependencyKey> = nodes.keys().copied().collect();
+    keys.sort_by(|a, b| nodes[a].label.cmp(&nodes[b].label));
+
+    for key in &keys {
+        let node = &nodes[key];
+        for dep in &node.deps {
+            match registered.get(&dep.key()) {
+                None => issues.push(DependencyIssue::Missing {
+                    required_by: node.label.clone(),
+                    dependency: describe(dep.type_name, dep.name),
+                }),
+                Some(entry)
+                    if entry.lifetime == DependencyLifetime::Scoped
+                        && node.lifetime != DependencyLifetime::Scoped =>
+                {
+                    issues.push(DependencyIssue::ScopedDependency {
+                        component: node.label.clone(),
+                        dependency: describe(dep.type_name, dep.name),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    for (site, deps) in requirements {
+        for dep in deps {
+            if !registered.contains_key(&dep.key()) {
+                issues.push(DependencyIssue::Missing {
+                    required_by: format!("handler `{}`", site),
+                    dependency: describe(dep.type_name, dep.name),
+                });
+            }
+        }
+    }
+
+    // 深度优先搜索，遇到栈上的节点即为一个环
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Visiting,
+        Done,
+    }
+    fn visit(
+        key: DependencyKey,
+        nodes: &HashMap<DependencyKey, Node>,
+        marks: &mut HashMap<DependencyKey, Mark>,
+        stack: &mut Vec<DependencyKey>,
+        issues: &mut Vec<DependencyIssue>,
+    ) {
+        marks.insert(key, Mark::Visiting);
+        stack.push(key);
+        for dep in &nodes[&key].deps {
+            let next = dep.key();
+            if !nodes.contains_key(&next) {
+                continue;
+            }
+            match marks.get(&next) {
+                Some(Mark::Visiting) => {
+                    let start = stack.iter().position(|k| *k == next).unwrap();
+                    let mut path: Vec<String> = stack[start..]
+                        .iter()
+                        .map(|k| nodes[k].label.clone())
+                        .collect();
+                    path.push(nodes[&next].label.clone());
+                    issues.push(DependencyIssue::Cycle(path));
+                }
+                Some(Mark::Done) => {}
+                None => visit(next, nodes, marks, stack, issues),
+            }
+        }
+        stack.pop();
+        marks.insert(key, Mark::Done);
+    }
+    let mut marks = HashMap::new();
+    for key in &keys {
+        if !marks.contains_key(key) {
+            visit(*key, &nodes, &mut marks, &mut Vec::new(), &mut issues);
+        }
+    }
+    issues
+}
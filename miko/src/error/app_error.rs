@@ -39,9 +39,15 @@ pub enum AppError {
     /// 404 Not Found - 资源不存在
     NotFound(String),
 
+    /// 405 Method Not Allowed - 路径存在但不支持该请求方法
+    MethodNotAllowed(String),
+
     /// 409 Conflict - 资源冲突（如重复创建）
     Conflict(String),
 
+    /// 413 Payload Too Large - 请求体超过大小限制
+    PayloadTooLarge(String),
+
     /// 422 Unprocessable Entity - 验证失败
     UnprocessableEntity(String),
 
@@ -136,10 +142,12 @@ impl AppError {
             Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             Self::Forbidden(_) => StatusCode::FORBIDDEN,
             Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::MethodNotAllowed(_) => StatusCode::METHOD_NOT_ALLOWED,
             Self::Conflict(_) => StatusCode::CONFLICT,
             Self::UnprocessableEntity(_) | Self::ValidationError(_) => {
                 StatusCode::UNPROCESSABLE_ENTITY
             }
+            Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
             Self::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
             Self::InternalServerError(_)
             | Self::DatabaseError(_)
@@ -159,8 +167,10 @@ impl AppError {
             Self::Unauthorized(_) => "UNAUTHORIZED",
             Self::Forbidden(_) => "FORBIDDEN",
             Self::NotFound(_) => "NOT_FOUND",
+            Self::MethodNotAllowed(_) => "METHOD_NOT_ALLOWED",
             Self::Conflict(_) => "CONFLICT",
             Self::UnprocessableEntity(_) => "UNPROCESSABLE_ENTITY",
+            Self::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
             Self::TooManyRequests(_) => "TOO_MANY_REQUESTS",
             Self::InternalServerError(_) => "INTERNAL_SERVER_ERROR",
             Self::BadGateway(_) => "BAD_GATEWAY",
@@ -186,8 +196,10 @@ impl AppError {
             | Self::Unauthorized(msg)
             | Self::Forbidden(msg)
             | Self::NotFound(msg)
+            | Self::MethodNotAllowed(msg)
             | Self::Conflict(msg)
             | Self::UnprocessableEntity(msg)
+            | Self::PayloadTooLarge(msg)
             | Self::TooManyRequests(msg)
             | Self::InternalServerError(msg)
             | Self::BadGateway(msg)
@@ -277,7 +289,12 @@ impl From<std::io::Error> for AppError {
 
 impl From<multer::Error> for AppError {
     fn from(err: multer::Error) -> Self {
-        Self::MultipartParseError(err.to_string())
+        match err {
+            multer::Error::StreamSizeExceeded { .. } | multer::Error::FieldSizeExceeded { .. } => {
+                Self::PayloadTooLarge(err.to_string())
+            }
+            _ => Self::MultipartParseError(err.to_string()),
+        }
     }
 }
 
@@ -308,6 +325,71 @@ impl From<Box<dyn std::error::Error + Send + Sync>> for AppError {
     }
 }
 
+// ============ sqlx 集成 ============
+#[cfg(feature = "sqlx")]
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        use sqlx::error::ErrorKind;
+
+        match &err {
+            sqlx::Error::RowNotFound => return Self::NotFound("Record not found".to_string()),
+            sqlx::Error::PoolTimedOut => {
+                tracing::warn!("Database pool timed out");
+                return Self::ServiceUnavailable("Database is busy".to_string());
+            }
+            sqlx::Error::Database(db) => {
+                // 约束冲突属于客户端错误，不暴露具体的约束与表结构
+                let client_error = match db.kind() {
+                    ErrorKind::UniqueViolation => {
+                        Some(Self::Conflict("Resource already exists".to_string()))
+                    }
+                    ErrorKind::ForeignKeyViolation => Some(Self::Conflict(
+                        "Referenced resource does not exist or is still in use".to_string(),
+                    )),
+                    ErrorKind::NotNullViolation | ErrorKind::CheckViolation => Some(
+                        Self::UnprocessableEntity("Data violates a constraint".to_string()),
+                    ),
+                    _ => None,
+                };
+                if let Some(client_error) = client_error {
+                    tracing::debug!(constraint = ?db.constraint(), error = %db, "Database constraint violation");
+                    return client_error;
+                }
+            }
+            _ => {}
+        }
+        tracing::error!(error = %err, "Database error");
+        Self::DatabaseError("Database error".to_string())
+    }
+}
+
+// ============ redis 集成 ============
+#[cfg(feature = "redis")]
+impl From<redis::RedisError> for AppError {
+    fn from(err: redis::RedisError) -> Self {
+        Self::ExternalServiceError {
+            service: "redis".to_string(),
+            message: err.to_string(),
+        }
+    }
+}
+
+// ============ reqwest 集成 ============
+#[cfg(feature = "http-client")]
+impl From<reqwest::Error> for AppError {
+    fn from(err: reqwest::Error) -> Self {
+        let service = err
+            .url()
+            .and_then(|url| url.host_str())
+            .unwrap_or("http")
+            .to_string();
+        Self::ExternalServiceError {
+            service,
+            message: err.without_url().to_string(),
+        }
+    }
+}
+
 // ============ garde 验证库集成 ============
 #[cfg(feature = "validation")]
 impl From<garde::Report> for AppError {
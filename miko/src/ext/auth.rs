@@ -0,0 +1,497 @@
+//! 认证与授权
+//!
+//! [`Authenticator`] 从请求中校验凭据并得到声明（claims），内置 JWT（`jwt` feature）、
+//! API key 与 Basic 三种实现。认证器可以全局安装，也可以通过 [`RequireAuthLayer`] 作用于一组路由：
+//!
+//! ```rust,ignore
+//! auth::install(JwtAuth::<Identity>::hs256(b"secret"));
+//!
+//! #[get("/me")]
+//! async fn me(Claims(user): Claims<Identity>) -> String {
+//!     user.sub
+//! }
+//!
+//! #[delete("/users/{id}")]
+//! #[guard(role = "admin")]
+//! async fn remove_user(#[path] id: u64) { ... }
+//! ```
+//!
+//! 凭据缺失或无效返回 401，`#[guard]` 要求的角色不满足返回 403。
+
+use crate::error::AppError;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use crate::miko_core::{Req, Resp};
+use crate::{AppResult, IntoResponse};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hyper::header::{AUTHORIZATION, HeaderName};
+use hyper::http::request::Parts;
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 认证器返回的 Future
+pub type AuthFuture<'a, T> = Pin<Box<dyn Future<Output = AppResult<T>> + Send + 'a>>;
+
+/// 认证得到的声明，需提供主体与角色供 `#[guard]` 判断
+pub trait AuthClaims: Clone + Send + Sync + 'static {
+    /// 主体标识，如用户 ID
+    fn subject(&self) -> String;
+
+    /// 拥有的角色
+    fn roles(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// 从请求中校验凭据，失败时返回 [`AppError::Unauthorized`]
+pub trait Authenticator: Send + Sync + 'static {
+    type Claims: AuthClaims;
+
+    fn authenticate<'a>(&'a self, parts: &'a Parts) -> AuthFuture<'a, Self::Claims>;
+}
+
+/// 通用的声明类型，也可直接作为 JWT 的 payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Identity {
+    pub sub: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    /// 过期时间（Unix 秒），JWT 默认要求该字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+}
+
+impl Identity {
+    pub fn new(sub: impl Into<String>) -> Self {
+        Self {
+            sub: sub.into(),
+            roles: Vec::new(),
+            exp: None,
+        }
+    }
+
+    pub fn role(mut self, role: impl Into<String>) -> Self {
+        self.roles.push(role.into());
+        self
+    }
+
+    pub fn expires_at(mut self, exp: u64) -> Self {
+        self.exp = Some(exp);
+        self
+    }
+}
+
+impl AuthClaims for Identity {
+    fn subject(&self) -> String {
+        self.sub.clone()
+    }
+
+    fn roles(&self) -> Vec<String> {
+        self.roles.clone()
+    }
+}
+
+/// 已认证的主体，与具体的声明类型无关
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub subject: String,
+    pub roles: Vec<String>,
+}
+
+impl Principal {
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+}
+
+/// 认证声明提取器
+///
+/// 优先使用 [`RequireAuthLayer`] 已校验的结果，否则使用为 `T` 安装的全局认证器。
+#[derive(Debug, Clone)]
+pub struct Claims<T>(pub T);
+
+impl<T> Deref for Claims<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<S, T: AuthClaims> FromRequestParts<S> for Claims<T> {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        Box::pin(async move {
+            if let Some(claims) = req.extensions.get::<Claims<T>>() {
+                return Ok(claims.clone());
+            }
+            let auth = installed()
+                .into_iter()
+                .find(|a| a.claims_type() == TypeId::of::<T>())
+                .ok_or_else(|| {
+                    AppError::InternalServerError(format!(
+                        "No authenticator installed for `{}`",
+                        std::any::type_name::<T>()
+                    ))
+                })?;
+            auth.authenticate_into(req).await?;
+            Ok(req.extensions.get::<Claims<T>>().unwrap().clone())
+        })
+    }
+}
+
+impl<S> FromRequestParts<S> for Principal {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        Box::pin(resolve_principal(req))
+    }
+}
+
+/// 认证后把声明与主体写入请求扩展
+trait ErasedAuthenticator: Send + Sync {
+    fn claims_type(&self) -> TypeId;
+    fn authenticate_into<'a>(&'a self, parts: &'a mut Parts) -> AuthFuture<'a, Principal>;
+}
+
+impl<A: Authenticator> ErasedAuthenticator for A {
+    fn claims_type(&self) -> TypeId {
+        TypeId::of::<A::Claims>()
+    }
+
+    fn authenticate_into<'a>(&'a self, parts: &'a mut Parts) -> AuthFuture<'a, Principal> {
+        Box::pin(async move {
+            let claims = self.authenticate(parts).await?;
+            let principal = Principal {
+                subject: claims.subject(),
+                roles: claims.roles(),
+            };
+            parts.extensions.insert(Claims(claims));
+            parts.extensions.insert(principal.clone());
+            Ok(principal)
+        })
+    }
+}
+
+static INSTALLED: RwLock<Vec<Arc<dyn ErasedAuthenticator>>> = RwLock::new(Vec::new());
+
+/// 安装全局认证器，可安装多个
+///
+/// `Claims<T>` 使用声明类型为 `T` 的认证器；`Principal` 与 `#[guard]` 按安装顺序依次尝试，第一个成功者生效。
+pub fn install(authenticator: impl Authenticator) {
+    INSTALLED.write().unwrap().push(Arc::new(authenticator));
+}
+
+fn installed() -> Vec<Arc<dyn ErasedAuthenticator>> {
+    INSTALLED.read().unwrap().clone()
+}
+
+/// 取出已认证的主体，尚未认证时依次尝试全局认证器
+async fn resolve_principal(parts: &mut Parts) -> AppResult<Principal> {
+    if let Some(principal) = parts.extensions.get::<Principal>() {
+        return Ok(principal.clone());
+    }
+    let authenticators = installed();
+    if authenticators.is_empty() {
+        return Err(AppError::InternalServerError(
+            "No authenticator installed".to_string(),
+        ));
+    }
+    let mut first_err = None;
+    for auth in authenticators {
+        match auth.authenticate_into(parts).await {
+            Ok(principal) => return Ok(principal),
+            Err(err) => {
+                first_err.get_or_insert(err);
+            }
+        }
+    }
+    Err(first_err.unwrap())
+}
+
+/// 从 `Authorization: Bearer <token>` 中取出 token
+pub fn bearer_token(parts: &Parts) -> AppResult<&str> {
+    parts
+        .headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .ok_or_else(|| AppError::Unauthorized("Missing bearer token".to_string()))
+}
+
+/// 请求头中的 API key 认证
+pub struct ApiKeyAuth<C, F> {
+    header: HeaderName,
+    validate: F,
+    _claims: PhantomData<fn() -> C>,
+}
+
+impl<C, F> ApiKeyAuth<C, F>
+where
+    C: AuthClaims,
+    F: Fn(&str) -> Option<C> + Send + Sync + 'static,
+{
+    /// 从请求头 `header` 读取 key，`validate` 返回 `None` 表示 key 无效
+    pub fn header(header: &'static str, validate: F) -> Self {
+        Self {
+            header: HeaderName::from_static(header),
+            validate,
+            _claims: PhantomData,
+        }
+    }
+}
+
+impl<C, F> Authenticator for ApiKeyAuth<C, F>
+where
+    C: AuthClaims,
+    F: Fn(&str) -> Option<C> + Send + Sync + 'static,
+{
+    type Claims = C;
+
+    fn authenticate<'a>(&'a self, parts: &'a Parts) -> AuthFuture<'a, C> {
+        let result = match parts
+            .headers
+            .get(&self.header)
+            .and_then(|v| v.to_str().ok())
+        {
+            None => Err(AppError::Unauthorized("Missing API key".to_string())),
+            Some(key) => (self.validate)(key)
+                .ok_or_else(|| AppError::Unauthorized("Invalid API key".to_string())),
+        };
+        Box::pin(async move { result })
+    }
+}
+
+/// HTTP Basic 认证
+pub struct BasicAuth<C, F> {
+    validate: F,
+    _claims: PhantomData<fn() -> C>,
+}
+
+impl<C, F> BasicAuth<C, F>
+where
+    C: AuthClaims,
+    F: Fn(&str, &str) -> Option<C> + Send + Sync + 'static,
+{
+    /// `validate` 接收用户名与密码，返回 `None` 表示凭据无效
+    pub fn new(validate: F) -> Self {
+        Self {
+            validate,
+            _claims: PhantomData,
+        }
+    }
+}
+
+impl<C, F> Authenticator for BasicAuth<C, F>
+where
+    C: AuthClaims,
+    F: Fn(&str, &str) -> Option<C> + Send + Sync + 'static,
+{
+    type Claims = C;
+
+    fn authenticate<'a>(&'a self, parts: &'a Parts) -> AuthFuture<'a, C> {
+        let credentials = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Basic "))
+            .and_then(|v| STANDARD.decode(v.trim()).ok())
+            .and_then(|v| String::from_utf8(v).ok());
+        let result = match credentials.as_deref().and_then(|c| c.split_once(':')) {
+            None => Err(AppError::Unauthorized(
+                "Missing basic credentials".to_string(),
+            )),
+            Some((user, password)) => (self.validate)(user, password)
+                .ok_or_else(|| AppError::Unauthorized("Invalid credentials".to_string())),
+        };
+        Box::pin(async move { result })
+    }
+}
+
+#[cfg(feature = "jwt")]
+pub use jwt::JwtAuth;
+
+#[cfg(feature = "jwt")]
+mod jwt {
+    use super::{AuthClaims, AuthFuture, Authenticator, bearer_token};
+    use crate::{AppError, AppResult};
+    use hyper::http::request::Parts;
+    use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+    use serde::Serialize;
+    use serde::de::DeserializeOwned;
+    use std::marker::PhantomData;
+
+    /// `Authorization: Bearer` 中的 JWT 认证
+    pub struct JwtAuth<C> {
+        decoding: DecodingKey,
+        encoding: Option<EncodingKey>,
+        validation: Validation,
+        _claims: PhantomData<fn() -> C>,
+    }
+
+    impl<C: AuthClaims + DeserializeOwned> JwtAuth<C> {
+        /// 使用 HS256 共享密钥，可同时用于签发
+        pub fn hs256(secret: &[u8]) -> Self {
+            Self {
+                decoding: DecodingKey::from_secret(secret),
+                encoding: Some(EncodingKey::from_secret(secret)),
+                validation: Validation::new(Algorithm::HS256),
+                _claims: PhantomData,
+            }
+        }
+
+        /// 使用任意密钥与校验规则，此时不能签发
+        pub fn new(decoding: DecodingKey, validation: Validation) -> Self {
+            Self {
+                decoding,
+                encoding: None,
+                validation,
+                _claims: PhantomData,
+            }
+        }
+
+        /// 签发 token
+        pub fn sign(&self, claims: &C) -> AppResult<String>
+        where
+            C: Serialize,
+        {
+            let key = self.encoding.as_ref().ok_or_else(|| {
+                AppError::InternalServerError("JwtAuth has no signing key".to_string())
+            })?;
+            let header = Header::new(self.validation.algorithms[0]);
+            jsonwebtoken::encode(&header, claims, key)
+                .map_err(|e| AppError::InternalServerError(format!("Failed to sign JWT: {}", e)))
+        }
+    }
+
+    impl<C: AuthClaims + DeserializeOwned> Authenticator for JwtAuth<C> {
+        type Claims = C;
+
+        fn authenticate<'a>(&'a self, parts: &'a Parts) -> AuthFuture<'a, C> {
+            let result = bearer_token(parts).and_then(|token| {
+                jsonwebtoken::decode::<C>(token, &self.decoding, &self.validation)
+                    .map(|data| data.claims)
+                    .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))
+            });
+            Box::pin(async move { result })
+        }
+    }
+}
+
+/// 要求请求通过认证，校验结果可由 [`Claims`] 与 [`Principal`] 提取
+#[derive(Clone)]
+pub struct RequireAuthLayer {
+    authenticator: Arc<dyn ErasedAuthenticator>,
+}
+
+impl RequireAuthLayer {
+    pub fn new(authenticator: impl Authenticator) -> Self {
+        Self {
+            authenticator: Arc::new(authenticator),
+        }
+    }
+}
+
+impl<S> Layer<S> for RequireAuthLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = AuthService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthService {
+            inner: BoxCloneService::new(inner),
+            check: Check::Authenticate(self.authenticator.clone()),
+        }
+    }
+}
+
+/// 路由守卫：要求已认证，或拥有指定角色，由 `#[guard]` 生成
+#[derive(Clone)]
+pub struct GuardLayer {
+    role: Option<String>,
+}
+
+impl GuardLayer {
+    /// 只要求已认证
+    pub fn authenticated() -> Self {
+        Self { role: None }
+    }
+
+    /// 要求拥有角色 `role`
+    pub fn role(role: impl Into<String>) -> Self {
+        Self {
+            role: Some(role.into()),
+        }
+    }
+}
+
+impl<S> Layer<S> for GuardLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = AuthService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthService {
+            inner: BoxCloneService::new(inner),
+            check: Check::Guard(self.role.clone()),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Check {
+    Authenticate(Arc<dyn ErasedAuthenticator>),
+    Guard(Option<String>),
+}
+
+/// [`RequireAuthLayer`] 与 [`GuardLayer`] 生成的 Service
+#[derive(Clone)]
+pub struct AuthService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    check: Check,
+}
+
+impl Service<Req> for AuthService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let check = self.check.clone();
+        Box::pin(async move {
+            let (mut parts, body) = req.into_parts();
+            let checked = match &check {
+                Check::Authenticate(auth) => auth.authenticate_into(&mut parts).await.map(drop),
+                Check::Guard(role) => {
+                    resolve_principal(&mut parts)
+                        .await
+                        .and_then(|p| match role {
+                            Some(role) if !p.has_role(role) => {
+                                Err(AppError::Forbidden(format!("Requires role `{}`", role)))
+                            }
+                            _ => Ok(()),
+                        })
+                }
+            };
+            if let Err(err) = checked {
+                return Ok(err.into_response());
+            }
+            inner.call(Req::from_parts(parts, body)).await
+        })
+    }
+}
@@ -0,0 +1,125 @@
+//! 基准测试与性能剖析辅助
+//!
+//! 挂载一组有代表性的路由（纯文本、JSON、路径参数、中间件栈），
+//! 供 `benches/` 与 flamegraph 等工具复用同一套负载。
+
+use crate::extractor::{Json, Path};
+use crate::middleware::{Next, middleware_from_fn};
+use crate::router::{HttpSvc, Router};
+use crate::{AppResult, miko_core::Req, miko_core::Resp};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::{Method, Request, StatusCode};
+use serde::{Deserialize, Serialize};
+use tower::ServiceExt;
+
+/// 中间件栈场景中叠加的中间件层数
+pub const MIDDLEWARE_DEPTH: usize = 4;
+
+#[derive(Serialize, Deserialize)]
+pub struct Message {
+    pub id: u64,
+    pub message: String,
+}
+
+/// 一个基准场景
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub name: &'static str,
+    pub method: Method,
+    pub uri: &'static str,
+    pub body: Option<&'static str>,
+}
+
+impl Scenario {
+    /// 构造该场景对应的请求
+    pub fn request(&self) -> Req {
+        let mut builder = Request::builder().method(self.method.clone()).uri(self.uri);
+        if self.body.is_some() {
+            builder = builder.header(hyper::header::CONTENT_TYPE, "application/json");
+        }
+        let body = Bytes::from_static(self.body.unwrap_or_default().as_bytes());
+        builder
+            .body(Full::new(body).map_err(Into::into).boxed_unsync())
+            .unwrap()
+    }
+}
+
+/// 所有内置场景
+pub fn scenarios() -> Vec<Scenario> {
+    vec![
+        Scenario {
+            name: "plaintext",
+            method: Method::GET,
+            uri: "/plaintext",
+            body: None,
+        },
+        Scenario {
+            name: "json",
+            method: Method::GET,
+            uri: "/json",
+            body: None,
+        },
+        Scenario {
+            name: "json_echo",
+            method: Method::POST,
+            uri: "/json",
+            body: Some(r#"{"id":1,"message":"Hello, World!"}"#),
+        },
+        Scenario {
+            name: "path_params",
+            method: Method::GET,
+            uri: "/users/42/posts/7",
+            body: None,
+        },
+        Scenario {
+            name: "middleware_stack",
+            method: Method::GET,
+            uri: "/stack/plaintext",
+            body: None,
+        },
+    ]
+}
+
+async fn pass_through(req: Req, next: Next) -> AppResult<Resp> {
+    next.run(req).await
+}
+
+/// 构建挂载了全部场景路由的 Router
+pub fn bench_router() -> Router {
+    let mut router = Router::new();
+    router
+        .get("/plaintext", || async { "Hello, World!" })
+        .get("/json", || async {
+            Json(Message {
+                id: 1,
+                message: "Hello, World!".to_string(),
+            })
+        })
+        .post("/json", |Json(msg): Json<Message>| async move { Json(msg) })
+        .get(
+            "/users/{uid}/posts/{pid}",
+            |Path(uid): Path<u64>, Path(pid): Path<u64>| async move { format!("{uid}:{pid}") },
+        );
+
+    let mut stack = Router::new();
+    stack.get("/plaintext", || async { "Hello, World!" });
+    for _ in 0..MIDDLEWARE_DEPTH {
+        stack.with_layer(middleware_from_fn(pass_through));
+    }
+    router.nest("/stack", stack);
+    router
+}
+
+/// 构建已展开为 tower Service 的基准路由
+pub fn bench_service() -> HttpSvc<Req> {
+    bench_router().into_tower_service()
+}
+
+/// 执行一次场景请求并读完响应体，返回状态码
+pub async fn drive(svc: &HttpSvc<Req>, scenario: &Scenario) -> StatusCode {
+    let resp = svc.clone().oneshot(scenario.request()).await.unwrap();
+    let status = resp.status();
+    let _ = resp.into_body().collect().await;
+    status
+}
@@ -0,0 +1,523 @@
+//! 缓存
+//!
+//! [`Cache`] 通过可替换的 [`CacheStore`] 读写带过期时间的键值，可以 `#[dep] cache: Arc<Cache>` 注入，
+//! 注入的实例按 `[cache]` 配置创建：
+//!
+//! ```toml
+//! [cache]
+//! store = "redis"           # 缺省为 "memory"；"redis" 需要 `redis` feature，连接见 `[redis]`
+//! prefix = "app:"           # 所有键的前缀，缺省为 "miko:"
+//! ```
+//!
+//! ```rust,ignore
+//! #[get("/users/{id}")]
+//! async fn get_user(#[dep] cache: Arc<Cache>, #[path] id: u64) -> AppResult<Json<User>> {
+//!     let key = format!("user:{}", id);
+//!     if let Some(user) = cache.get(&key).await? {
+//!         return Ok(Json(user));
+//!     }
+//!     let user = load_user(id).await?;
+//!     cache.set_ex(&key, &user, Duration::from_secs(300)).await?;
+//!     Ok(Json(user))
+//! }
+//! ```
+//!
+//! 路由上的 `#[cached(ttl = 60)]` 使用 [`CacheLayer`] 缓存整个响应。
+
+use crate::app::config::get_settings;
+use crate::http::response::into_response::IntoResponse;
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::HeaderMap;
+use hyper::header::{
+    AUTHORIZATION, CACHE_CONTROL, COOKIE, HeaderName, HeaderValue, SET_COOKIE, VARY,
+};
+use hyper::{Method, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 缓存存储返回的 Future
+pub type CacheFuture<'a, T> = Pin<Box<dyn Future<Output = AppResult<T>> + Send + 'a>>;
+
+/// 缓存存储：按键读写字节，支持过期时间
+pub trait CacheStore: Send + Sync + 'static {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>>;
+
+    /// 写入值，`ttl` 为 `None` 时不过期
+    fn set<'a>(
+        &'a self,
+        key: &'a str,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> CacheFuture<'a, ()>;
+
+    /// 重新设置过期时间，键不存在时返回 `false`
+    fn expire<'a>(&'a self, key: &'a str, ttl: Duration) -> CacheFuture<'a, bool>;
+
+    /// 删除键，键不存在时返回 `false`
+    fn delete<'a>(&'a self, key: &'a str) -> CacheFuture<'a, bool>;
+}
+
+/// 值与过期时刻
+type Entry = (Vec<u8>, Option<Instant>);
+
+/// 进程内的缓存存储，过期的键在读取时清除
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn live<R>(&self, key: &str, f: impl FnOnce(&mut Entry) -> R) -> Option<R> {
+        let mut entries = self.entries.lock().unwrap();
+        let expired = entries
+            .get(key)
+            .is_some_and(|(_, at)| at.is_some_and(|at| at <= Instant::now()));
+        if expired {
+            entries.remove(key);
+        }
+        entries.get_mut(key).map(f)
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>> {
+        let value = self.live(key, |(value, _)| value.clone());
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn set<'a>(
+        &'a self,
+        key: &'a str,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> CacheFuture<'a, ()> {
+        let expires = ttl.map(|ttl| Instant::now() + ttl);
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), (value, expires));
+        Box::pin(async { Ok(()) })
+    }
+
+    fn expire<'a>(&'a self, key: &'a str, ttl: Duration) -> CacheFuture<'a, bool> {
+        let found = self
+            .live(key, |(_, at)| *at = Some(Instant::now() + ttl))
+            .is_some();
+        Box::pin(async move { Ok(found) })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> CacheFuture<'a, bool> {
+        let found = self.live(key, |_| ()).is_some();
+        self.entries.lock().unwrap().remove(key);
+        Box::pin(async move { Ok(found) })
+    }
+}
+
+/// 缓存配置（`[cache]`）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CacheSettings {
+    /// `"memory"`（缺省）或 `"redis"`
+    pub store: Option<String>,
+    /// 所有键的前缀
+    pub prefix: String,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            store: None,
+            prefix: "miko:".to_string(),
+        }
+    }
+}
+
+/// 缓存组件，可通过 `#[dep] cache: Arc<Cache>` 注入
+///
+/// 值以 JSON 序列化后写入存储；`*_bytes` 方法直接读写字节。
+#[derive(Clone)]
+pub struct Cache {
+    store: Arc<dyn CacheStore>,
+    prefix: String,
+}
+
+static GLOBAL: LazyLock<Cache> = LazyLock::new(Cache::from_global_settings);
+
+impl Cache {
+    /// 使用指定存储创建，不带键前缀
+    pub fn new(store: impl CacheStore) -> Self {
+        Self {
+            store: Arc::new(store),
+            prefix: String::new(),
+        }
+    }
+
+    /// 设置键前缀
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// 根据 `[cache]` 配置创建，未知的存储回退为内存存储
+    pub fn from_global_settings() -> Self {
+        let settings: CacheSettings = get_settings().get("cache").unwrap_or_default();
+        Self::from_settings(settings)
+    }
+
+    pub fn from_settings(settings: CacheSettings) -> Self {
+        let cache = match settings.store.as_deref().unwrap_or("memory") {
+            #[cfg(feature = "redis")]
+            "redis" => Self::new(crate::ext::redis::RedisStore::new(
+                crate::ext::redis::Redis::global().clone(),
+            )),
+            "memory" => Self::new(MemoryStore::new()),
+            other => {
+                tracing::warn!(store = other, "unknown cache store, falling back to memory");
+                Self::new(MemoryStore::new())
+            }
+        };
+        cache.prefix(settings.prefix)
+    }
+
+    /// 全局实例，`#[dep]` 注入与 `#[cached]` 使用该实例
+    pub fn global() -> &'static Cache {
+        &GLOBAL
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// 读取并反序列化，键不存在或已过期时返回 `None`
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> AppResult<Option<T>> {
+        match self.get_bytes(key).await? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes).map_err(|e| {
+                AppError::InternalServerError(format!("Invalid cached value for {}: {}", key, e))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 写入不过期的值
+    pub async fn set<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> AppResult<()> {
+        self.set_bytes(key, serde_json::to_vec(value)?, None).await
+    }
+
+    /// 写入在 `ttl` 后过期的值
+    pub async fn set_ex<T: Serialize + ?Sized>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Duration,
+    ) -> AppResult<()> {
+        self.set_bytes(key, serde_json::to_vec(value)?, Some(ttl))
+            .await
+    }
+
+    /// 重新设置过期时间，键不存在时返回 `false`
+    pub async fn expire(&self, key: &str, ttl: Duration) -> AppResult<bool> {
+        self.store.expire(&self.key(key), ttl).await
+    }
+
+    /// 删除键，键不存在时返回 `false`
+    pub async fn delete(&self, key: &str) -> AppResult<bool> {
+        self.store.delete(&self.key(key)).await
+    }
+
+    pub async fn get_bytes(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        self.store.get(&self.key(key)).await
+    }
+
+    pub async fn set_bytes(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> AppResult<()> {
+        self.store.set(&self.key(key), value, ttl).await
+    }
+}
+
+#[cfg(feature = "auto")]
+crate::inventory::submit! {
+    crate::dependency_container::DependencyDefFn(|| {
+        crate::dependency_container::DependencyDef {
+            type_id: std::any::TypeId::of::<Cache>(),
+            type_name: std::any::type_name::<Cache>(),
+            prewarm: false,
+            name: "___",
+            lifetime: crate::dependency_container::DependencyLifetime::Singleton,
+            deps: Vec::new,
+            source: concat!(file!(), ":", line!()),
+            replace: false,
+            init_fn: || {
+                Box::pin(async move {
+                    Arc::new(Cache::global().clone()) as Arc<dyn std::any::Any + Send + Sync>
+                })
+            },
+        }
+    })
+}
+
+static X_CACHE: HeaderName = HeaderName::from_static("x-cache");
+
+/// 缺省的可缓存响应体上限（1 MiB）
+const DEFAULT_MAX_BODY: u64 = 1024 * 1024;
+
+/// 缓存成功的 `GET`/`HEAD` 响应 `ttl` 时长，以方法、路径与查询字符串为键
+///
+/// 命中时不调用处理函数，直接返回缓存并附带 `X-Cache: HIT`；写入缓存的响应附带 `X-Cache: MISS`。
+/// 只缓存 `2xx` 响应，带 `Set-Cookie` 或 `Cache-Control: no-store`/`private` 的响应不会缓存。
+///
+/// - 带 `Authorization` 或 `Cookie` 的请求既不读也不写缓存，
+///   确认响应与调用者无关时可通过 [`allow_credentials`](Self::allow_credentials) 开启
+/// - 响应的 `Vary` 中列出的请求头会并入缓存键，`Vary: *` 的响应不缓存
+/// - 只缓存长度已知且不超过 [`max_body`](Self::max_body) 的响应体，流式响应原样透传
+///
+/// 缓存读写失败只记录日志，请求交由处理函数处理。
+///
+/// 路由宏 `#[cached(ttl = 60)]` 会生成该层。
+#[derive(Clone)]
+pub struct CacheLayer {
+    ttl: Duration,
+    cache: Option<Cache>,
+    allow_credentials: bool,
+    max_body: u64,
+}
+
+impl CacheLayer {
+    /// 将响应缓存到 [`Cache::global`]，缓存时长为 `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            cache: None,
+            allow_credentials: false,
+            max_body: DEFAULT_MAX_BODY,
+        }
+    }
+
+    /// 使用 `cache` 代替全局缓存
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// 是否缓存带 `Authorization` 或 `Cookie` 的请求（默认 false）
+    ///
+    /// 缓存键不包含这些请求头，只应在响应与调用者无关时开启
+    pub fn allow_credentials(mut self, allow: bool) -> Self {
+        self.allow_credentials = allow;
+        self
+    }
+
+    /// 可缓存的响应体上限（字节，默认 1 MiB）
+    pub fn max_body(mut self, bytes: u64) -> Self {
+        self.max_body = bytes;
+        self
+    }
+}
+
+impl<S> Layer<S> for CacheLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = CacheService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CacheService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        }
+    }
+}
+
+/// 由 [`CacheLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct CacheService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: CacheLayer,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    /// base64
+    body: String,
+}
+
+impl CachedResponse {
+    fn into_response(self) -> Option<Resp> {
+        let mut resp = Response::new(
+            Full::new(Bytes::from(STANDARD.decode(self.body).ok()?))
+                .map_err(Into::into)
+                .boxed_unsync(),
+        );
+        *resp.status_mut() = StatusCode::from_u16(self.status).ok()?;
+        for (name, value) in self.headers {
+            resp.headers_mut().append(
+                HeaderName::try_from(name).ok()?,
+                HeaderValue::try_from(value).ok()?,
+            );
+        }
+        resp.headers_mut()
+            .insert(X_CACHE.clone(), HeaderValue::from_static("HIT"));
+        Some(resp)
+    }
+}
+
+fn cacheable(resp: &Resp, max_body: u64) -> bool {
+    let private = resp
+        .headers()
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .any(|v| v.contains("no-store") || v.contains("private"));
+    let small = http_body::Body::size_hint(resp.body())
+        .exact()
+        .is_some_and(|len| len <= max_body);
+    resp.status().is_success() && !private && small && !resp.headers().contains_key(SET_COOKIE)
+}
+
+/// 响应 `Vary` 中的请求头名（小写、排序去重），`Vary: *` 时返回 None
+fn vary_names(headers: &HeaderMap) -> Option<Vec<String>> {
+    let mut names = Vec::new();
+    for value in headers.get_all(VARY) {
+        for name in value.to_str().ok()?.split(',') {
+            let name = name.trim().to_ascii_lowercase();
+            if name == "*" {
+                return None;
+            }
+            if !name.is_empty() {
+                names.push(name);
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    Some(names)
+}
+
+/// 在基础键后追加 `Vary` 请求头的取值
+fn vary_key(base: &str, names: &[String], headers: &HeaderMap) -> String {
+    let mut key = base.to_string();
+    for name in names {
+        let values: Vec<&str> = headers
+            .get_all(name.as_str())
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect();
+        key.push_str(&format!("\n{}: {}", name, values.join(", ")));
+    }
+    key
+}
+
+impl Service<Req> for CacheService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let credentialed =
+            req.headers().contains_key(AUTHORIZATION) || req.headers().contains_key(COOKIE);
+        if !matches!(*req.method(), Method::GET | Method::HEAD)
+            || (credentialed && !self.layer.allow_credentials)
+        {
+            return Box::pin(self.inner.call(req));
+        }
+        let base = format!(
+            "response:{} {}",
+            req.method(),
+            req.uri().path_and_query().map_or("/", |p| p.as_str())
+        );
+        let vary_index = format!("vary:{}", base);
+        let req_headers = req.headers().clone();
+        let mut inner = self.inner.clone();
+        let layer = self.layer.clone();
+        Box::pin(async move {
+            let cache = layer.cache.as_ref().unwrap_or_else(|| Cache::global());
+            // 先取该地址上次响应的 Vary 请求头，再按本次请求的取值查找
+            let key = match cache.get::<Vec<String>>(&vary_index).await {
+                Ok(Some(names)) => vary_key(&base, &names, &req_headers),
+                Ok(None) => base.clone(),
+                Err(e) => {
+                    tracing::warn!(key = %vary_index, error = %e, "response cache read failed");
+                    base.clone()
+                }
+            };
+            match cache.get::<CachedResponse>(&key).await {
+                Ok(Some(cached)) => {
+                    if let Some(resp) = cached.into_response() {
+                        return Ok(resp);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(key = %key, error = %e, "response cache read failed"),
+            }
+
+            let resp = inner.call(req).await.unwrap_or_else(|e| e.into_response());
+            if !cacheable(&resp, layer.max_body) {
+                return Ok(resp);
+            }
+            let Some(names) = vary_names(resp.headers()) else {
+                return Ok(resp);
+            };
+            let key = vary_key(&base, &names, &req_headers);
+            let (mut parts, body) = resp.into_parts();
+            let bytes = body.collect().await?.to_bytes();
+            let headers: Option<Vec<(String, String)>> = parts
+                .headers
+                .iter()
+                .map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_string())))
+                .collect();
+            if let Some(headers) = headers {
+                let cached = CachedResponse {
+                    status: parts.status.as_u16(),
+                    headers,
+                    body: STANDARD.encode(&bytes),
+                };
+                let index = if names.is_empty() {
+                    cache.delete(&vary_index).await.map(|_| ())
+                } else {
+                    cache.set_ex(&vary_index, &names, layer.ttl).await
+                };
+                match index.and(cache.set_ex(&key, &cached, layer.ttl).await) {
+                    Ok(()) => {
+                        parts
+                            .headers
+                            .insert(X_CACHE.clone(), HeaderValue::from_static("MISS"));
+                    }
+                    Err(e) => {
+                        tracing::warn!(key = %key, error = %e, "response cache write failed")
+                    }
+                }
+            }
+            Ok(Response::from_parts(
+                parts,
+                Full::new(bytes).map_err(Into::into).boxed_unsync(),
+            ))
+        })
+    }
+}
@@ -0,0 +1,418 @@
+//! HTTP 客户端组件
+//!
+//! [`HttpClient`] 基于 reqwest，按 `[http_client]` 配置创建，可以 `#[dep] client: Arc<HttpClient>` 注入：
+//!
+//! ```toml
+//! [http_client]
+//! base_url = "http://inventory.internal/api"
+//! service = "inventory"       # 错误中使用的服务名，缺省为请求的主机名
+//! timeout_ms = 10000
+//! headers = { x-api-key = "..." }
+//! ```
+//!
+//! ```rust,ignore
+//! #[get("/products/{id}")]
+//! async fn product(#[dep] client: Arc<HttpClient>, #[path] id: u64) -> AppResult<Json<Product>> {
+//!     let stock: Stock = client.get_json(&format!("/stock/{id}")).await?;
+//!     let product = client
+//!         .post("/products/lookup")
+//!         .json(&Lookup { id })
+//!         .fetch_json::<Product>()
+//!         .await?;
+//!     Ok(Json(product.with_stock(stock)))
+//! }
+//! ```
+//!
+//! 发出的请求会携带当前请求的 trace id（`x-request-id`），启用 `otel` 时还会携带 `traceparent`；
+//! 当前请求设置了截止时间时，超时时间不会超过剩余时长。
+//! 连接失败、超时以及 `fetch*` 遇到的非 2xx 响应都会转换为 [`AppError::ExternalServiceError`]。
+
+use crate::app::config::get_settings;
+use crate::context::RequestContext;
+use crate::{AppError, AppResult};
+use ::reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use ::reqwest::{Body, RequestBuilder};
+use bytes::Bytes;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tracing::Instrument;
+
+pub use ::reqwest::{Method, Response, StatusCode};
+
+/// HTTP 客户端配置（`[http_client]`）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HttpClientSettings {
+    /// 相对路径请求的基础地址
+    pub base_url: Option<String>,
+    /// 错误中使用的服务名，缺省为请求的主机名
+    pub service: Option<String>,
+    /// 单个请求的超时时间
+    pub timeout_ms: u64,
+    /// 建立连接的超时时间
+    pub connect_timeout_ms: u64,
+    pub user_agent: Option<String>,
+    /// 每个请求都携带的请求头
+    pub headers: HashMap<String, String>,
+    /// 传递 trace id 的请求头，设为空字符串时不传递
+    pub trace_header: String,
+}
+
+impl Default for HttpClientSettings {
+    fn default() -> Self {
+        Self {
+            base_url: None,
+            service: None,
+            timeout_ms: 30_000,
+            connect_timeout_ms: 5_000,
+            user_agent: None,
+            headers: HashMap::new(),
+            trace_header: "x-request-id".to_string(),
+        }
+    }
+}
+
+/// 出站 HTTP 客户端，可通过 `#[dep] client: Arc<HttpClient>` 注入
+///
+/// 克隆后共享同一个连接池。
+#[derive(Clone)]
+pub struct HttpClient {
+    inner: ::reqwest::Client,
+    base_url: Option<Arc<str>>,
+    service: Option<Arc<str>>,
+    trace_header: Option<HeaderName>,
+}
+
+static GLOBAL: LazyLock<HttpClient> = LazyLock::new(HttpClient::from_global_settings);
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::from_settings(HttpClientSettings::default())
+    }
+}
+
+impl HttpClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按配置创建，配置中的请求头无效时 panic
+    pub fn from_settings(settings: HttpClientSettings) -> Self {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &settings.headers {
+            let name = HeaderName::try_from(name.as_str())
+                .unwrap_or_else(|e| panic!("invalid header name `{}`: {}", name, e));
+            let value = HeaderValue::try_from(value.as_str())
+                .unwrap_or_else(|e| panic!("invalid value for header `{}`: {}", name, e));
+            headers.insert(name, value);
+        }
+        let mut builder = ::reqwest::Client::builder()
+            .timeout(Duration::from_millis(settings.timeout_ms))
+            .connect_timeout(Duration::from_millis(settings.connect_timeout_ms))
+            .default_headers(headers);
+        if let Some(user_agent) = &settings.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        let inner = builder
+            .build()
+            .unwrap_or_else(|e| panic!("failed to build http client: {}", e));
+        let trace_header = (!settings.trace_header.is_empty()).then(|| {
+            HeaderName::try_from(settings.trace_header.as_str()).unwrap_or_else(|e| {
+                panic!("invalid header name `{}`: {}", settings.trace_header, e)
+            })
+        });
+        Self {
+            inner,
+            base_url: settings.base_url.map(Into::into),
+            service: settings.service.map(Into::into),
+            trace_header,
+        }
+    }
+
+    /// 读取 `[http_client]` 配置创建
+    pub fn from_global_settings() -> Self {
+        let settings: HttpClientSettings = get_settings().get("http_client").unwrap_or_default();
+        Self::from_settings(settings)
+    }
+
+    /// 全局实例，`#[dep]` 注入使用该实例
+    pub fn global() -> &'static HttpClient {
+        &GLOBAL
+    }
+
+    /// 使用新的基础地址，连接池仍与原实例共享
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into().into());
+        self
+    }
+
+    /// 设置错误中使用的服务名
+    pub fn with_service(mut self, service: impl Into<String>) -> Self {
+        self.service = Some(service.into().into());
+        self
+    }
+
+    pub fn inner(&self) -> &::reqwest::Client {
+        &self.inner
+    }
+
+    /// 拼接基础地址与 `path`，`path` 为完整 URL 时原样返回
+    pub fn url(&self, path: &str) -> String {
+        match &self.base_url {
+            Some(base) if !path.contains("://") => {
+                if path.is_empty() {
+                    base.to_string()
+                } else {
+                    format!(
+                        "{}/{}",
+                        base.trim_end_matches('/'),
+                        path.trim_start_matches('/')
+                    )
+                }
+            }
+            _ => path.to_string(),
+        }
+    }
+
+    pub fn request(&self, method: Method, path: &str) -> ClientRequest {
+        let url = self.url(path);
+        let service = self
+            .service
+            .as_deref()
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                ::reqwest::Url::parse(&url)
+                    .ok()
+                    .and_then(|u| u.host_str().map(str::to_string))
+                    .unwrap_or_else(|| "http".to_string())
+            });
+        ClientRequest {
+            inner: self.inner.request(method, url),
+            service,
+            trace_header: self.trace_header.clone(),
+        }
+    }
+
+    pub fn get(&self, path: &str) -> ClientRequest {
+        self.request(Method::GET, path)
+    }
+
+    pub fn post(&self, path: &str) -> ClientRequest {
+        self.request(Method::POST, path)
+    }
+
+    pub fn put(&self, path: &str) -> ClientRequest {
+        self.request(Method::PUT, path)
+    }
+
+    pub fn patch(&self, path: &str) -> ClientRequest {
+        self.request(Method::PATCH, path)
+    }
+
+    pub fn delete(&self, path: &str) -> ClientRequest {
+        self.request(Method::DELETE, path)
+    }
+
+    /// `GET` 并将 2xx 响应体解析为 JSON
+    pub async fn get_json<T: DeserializeOwned>(&self, path: &str) -> AppResult<T> {
+        self.get(path).fetch_json().await
+    }
+
+    /// 以 JSON 请求体 `POST`，并将 2xx 响应体解析为 JSON
+    pub async fn post_json<B, T>(&self, path: &str, body: &B) -> AppResult<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.post(path).json(body).fetch_json().await
+    }
+}
+
+/// 待发送的请求，由 [`HttpClient::request`] 等方法创建
+pub struct ClientRequest {
+    inner: RequestBuilder,
+    service: String,
+    trace_header: Option<HeaderName>,
+}
+
+impl ClientRequest {
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<hyper::http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<hyper::http::Error>,
+    {
+        self.inner = self.inner.header(key, value);
+        self
+    }
+
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.inner = self.inner.headers(headers);
+        self
+    }
+
+    pub fn bearer_auth(mut self, token: impl std::fmt::Display) -> Self {
+        self.inner = self.inner.bearer_auth(token);
+        self
+    }
+
+    /// 追加查询参数
+    pub fn query<T: Serialize + ?Sized>(mut self, query: &T) -> Self {
+        self.inner = self.inner.query(query);
+        self
+    }
+
+    /// 设置 JSON 请求体
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        self.inner = self.inner.json(body);
+        self
+    }
+
+    /// 设置 `application/x-www-form-urlencoded` 请求体
+    pub fn form<T: Serialize + ?Sized>(mut self, form: &T) -> Self {
+        self.inner = self.inner.form(form);
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<Body>) -> Self {
+        self.inner = self.inner.body(body);
+        self
+    }
+
+    /// 覆盖该请求的超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+
+    /// 设置错误中使用的服务名
+    pub fn service(mut self, service: impl Into<String>) -> Self {
+        self.service = service.into();
+        self
+    }
+
+    /// 直接修改底层的 `reqwest::RequestBuilder`
+    pub fn map(mut self, f: impl FnOnce(RequestBuilder) -> RequestBuilder) -> Self {
+        self.inner = f(self.inner);
+        self
+    }
+
+    /// 发送请求，返回任意状态码的响应；只有连接失败、超时等错误会返回 `Err`
+    pub async fn send(self) -> AppResult<Response> {
+        let service = self.service;
+        let (client, request) = self.inner.build_split();
+        let mut request = request.map_err(|e| external_error(&service, e))?;
+
+        let ctx = RequestContext::current();
+        if let (Some(name), Some(trace_id)) = (self.trace_header, &ctx.trace_id)
+            && !request.headers().contains_key(&name)
+            && let Ok(value) = HeaderValue::try_from(trace_id.as_str())
+        {
+            request.headers_mut().insert(name, value);
+        }
+        if let Some(remaining) = ctx.remaining() {
+            let timeout = request.timeout().map_or(remaining, |t| (*t).min(remaining));
+            *request.timeout_mut() = Some(timeout);
+        }
+
+        let span = tracing::info_span!(
+            "HTTP client request",
+            otel.name = %request.method(),
+            otel.kind = "client",
+            http.request.method = %request.method(),
+            server.address = request.url().host_str(),
+            url.path = request.url().path(),
+        );
+        #[cfg(feature = "otel")]
+        span.in_scope(|| crate::ext::otel::inject_current(request.headers_mut()));
+
+        client
+            .execute(request)
+            .instrument(span)
+            .await
+            .map_err(|e| external_error(&service, e))
+    }
+
+    /// 发送请求，非 2xx 响应转换为 [`AppError::ExternalServiceError`]
+    pub async fn fetch(self) -> AppResult<Response> {
+        let service = self.service.clone();
+        let resp = self.send().await?;
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(resp);
+        }
+        tracing::debug!(
+            service = %service,
+            status = status.as_u16(),
+            url = %resp.url(),
+            "external service returned an error status"
+        );
+        Err(AppError::ExternalServiceError {
+            service,
+            message: format!("{} responded with {}", resp.url().path(), status),
+        })
+    }
+
+    /// 发送请求并将 2xx 响应体解析为 JSON
+    pub async fn fetch_json<T: DeserializeOwned>(self) -> AppResult<T> {
+        let service = self.service.clone();
+        self.fetch()
+            .await?
+            .json()
+            .await
+            .map_err(|e| external_error(&service, e))
+    }
+
+    /// 发送请求并读取 2xx 响应体为文本
+    pub async fn fetch_text(self) -> AppResult<String> {
+        let service = self.service.clone();
+        self.fetch()
+            .await?
+            .text()
+            .await
+            .map_err(|e| external_error(&service, e))
+    }
+
+    /// 发送请求并读取 2xx 响应体
+    pub async fn fetch_bytes(self) -> AppResult<Bytes> {
+        let service = self.service.clone();
+        self.fetch()
+            .await?
+            .bytes()
+            .await
+            .map_err(|e| external_error(&service, e))
+    }
+}
+
+fn external_error(service: &str, err: ::reqwest::Error) -> AppError {
+    AppError::ExternalServiceError {
+        service: service.to_string(),
+        message: err.without_url().to_string(),
+    }
+}
+
+#[cfg(feature = "auto")]
+crate::inventory::submit! {
+    crate::dependency_container::DependencyDefFn(|| {
+        crate::dependency_container::DependencyDef {
+            type_id: std::any::TypeId::of::<HttpClient>(),
+            type_name: std::any::type_name::<HttpClient>(),
+            prewarm: false,
+            name: "___",
+            lifetime: crate::dependency_container::DependencyLifetime::Singleton,
+            deps: Vec::new,
+            source: concat!(file!(), ":", line!()),
+            replace: false,
+            init_fn: || {
+                Box::pin(async move {
+                    Arc::new(HttpClient::global().clone()) as Arc<dyn std::any::Any + Send + Sync>
+                })
+            },
+        }
+    })
+}
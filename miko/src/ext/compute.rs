@@ -0,0 +1,186 @@
+//! CPU 密集型任务的工作池
+//!
+//! 哈希、图片缩放等计算会阻塞 async 运行时，应交给 [`ComputePool`] 在阻塞线程上执行。
+//! 池的并发度由信号量限制，超出的任务排队等待，并提供队列深度等指标。
+//!
+//! ```rust,ignore
+//! #[post("/hash")]
+//! async fn hash(#[dep] pool: Arc<ComputePool>, body: String) -> AppResult<String> {
+//!     pool.compute(move || bcrypt::hash(body, 12).unwrap()).await
+//! }
+//!
+//! // 或使用全局池
+//! let digest = compute(move || sha256(&data)).await?;
+//! ```
+//!
+//! 并发度可通过配置指定，缺省为 CPU 核数：
+//!
+//! ```toml
+//! [compute]
+//! parallelism = 4
+//! ```
+
+use crate::app::config::get_settings;
+use crate::{AppError, AppResult};
+use serde::Deserialize;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Instant;
+use tokio::sync::Semaphore;
+
+/// 工作池配置（`[compute]`）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ComputeSettings {
+    /// 最大并行任务数，缺省为 CPU 核数
+    pub parallelism: Option<usize>,
+}
+
+/// 工作池运行指标快照
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComputeStats {
+    /// 最大并行任务数
+    pub parallelism: usize,
+    /// 正在排队等待的任务数
+    pub queued: usize,
+    /// 正在执行的任务数
+    pub running: usize,
+    /// 已完成（含 panic）的任务数
+    pub completed: u64,
+}
+
+/// CPU 密集型任务工作池，可通过 `#[dep] pool: Arc<ComputePool>` 注入
+#[derive(Clone)]
+pub struct ComputePool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    semaphore: Semaphore,
+    parallelism: usize,
+    queued: AtomicUsize,
+    running: AtomicUsize,
+    completed: AtomicU64,
+}
+
+static GLOBAL: LazyLock<ComputePool> = LazyLock::new(ComputePool::from_global_settings);
+
+impl ComputePool {
+    /// 创建并行度为 `parallelism` 的工作池（至少为 1）
+    pub fn new(parallelism: usize) -> Self {
+        let parallelism = parallelism.max(1);
+        Self {
+            inner: Arc::new(PoolInner {
+                semaphore: Semaphore::new(parallelism),
+                parallelism,
+                queued: AtomicUsize::new(0),
+                running: AtomicUsize::new(0),
+                completed: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// 根据 `[compute]` 配置创建工作池
+    pub fn from_global_settings() -> Self {
+        let settings: ComputeSettings = get_settings().get("compute").unwrap_or_default();
+        let parallelism = settings.parallelism.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        });
+        Self::new(parallelism)
+    }
+
+    /// 全局工作池，`#[dep]` 注入与 [`compute`] 共用该实例
+    pub fn global() -> &'static ComputePool {
+        &GLOBAL
+    }
+
+    /// 在阻塞线程上执行 `f`，池满时排队等待
+    ///
+    /// `f` panic 时返回 500 错误
+    pub async fn compute<F, R>(&self, f: F) -> AppResult<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let enqueued = Instant::now();
+        let queued = Counter::inc(&self.inner.queued);
+        let _permit = self
+            .inner
+            .semaphore
+            .acquire()
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        drop(queued);
+
+        let waited = enqueued.elapsed();
+        let _running = Counter::inc(&self.inner.running);
+        let started = Instant::now();
+        let result = tokio::task::spawn_blocking(f).await;
+        self.inner.completed.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(
+            wait_us = waited.as_micros() as u64,
+            run_us = started.elapsed().as_micros() as u64,
+            "compute task finished"
+        );
+        result.map_err(|e| AppError::InternalServerError(format!("compute task failed: {}", e)))
+    }
+
+    /// 当前指标
+    pub fn stats(&self) -> ComputeStats {
+        ComputeStats {
+            parallelism: self.inner.parallelism,
+            queued: self.inner.queued.load(Ordering::Relaxed),
+            running: self.inner.running.load(Ordering::Relaxed),
+            completed: self.inner.completed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 在全局工作池上执行 CPU 密集型任务
+pub async fn compute<F, R>(f: F) -> AppResult<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    ComputePool::global().compute(f).await
+}
+
+/// 计数守卫，future 被取消时也能正确回退计数
+struct Counter<'a>(&'a AtomicUsize);
+
+impl<'a> Counter<'a> {
+    fn inc(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for Counter<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(feature = "auto")]
+crate::inventory::submit! {
+    crate::dependency_container::DependencyDefFn(|| {
+        crate::dependency_container::DependencyDef {
+            type_id: std::any::TypeId::of::<ComputePool>(),
+            type_name: std::any::type_name::<ComputePool>(),
+            prewarm: false,
+            name: "___",
+            lifetime: crate::dependency_container::DependencyLifetime::Singleton,
+            deps: Vec::new,
+            source: concat!(file!(), ":", line!()),
+            replace: false,
+            init_fn: || {
+                Box::pin(async move {
+                    Arc::new(ComputePool::global().clone())
+                        as Arc<dyn std::any::Any + Send + Sync>
+                })
+            },
+        }
+    })
+}
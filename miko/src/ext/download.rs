@@ -0,0 +1,496 @@
+//! 可断点续传的下载响应
+//!
+//! [`ResumableDownload`] 接受任意已知长度的 `AsyncRead + AsyncSeek`（文件、对象存储读取器等），
+//! 自动处理 `Range`、`If-Range`、`If-None-Match`、`If-Modified-Since` 与 `HEAD`，
+//! 行为与 [`StaticSvc`](crate::ext::static_svc::StaticSvc) 一致。
+//! 多范围请求（`bytes=0-99,200-299`）默认按完整内容返回，开启 [`multi_range`](ResumableDownload::multi_range)
+//! 后以 `multipart/byteranges` 返回。
+//!
+//! ```rust,ignore
+//! #[get("/files/{id}")]
+//! async fn download(Path(id): Path<u64>, range: RangeRequest) -> AppResult<Resp> {
+//!     let file = tokio::fs::File::open(format!("./data/{id}.bin")).await?;
+//!     let len = file.metadata().await?.len();
+//!     Ok(ResumableDownload::new(file, len)
+//!         .etag(format!("{id}-{len}"))
+//!         .attachment("data.bin")
+//!         .respond(&range)
+//!         .await?)
+//! }
+//! ```
+
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use crate::http::response::into_response::bytes_to_boxed;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use http_body_util::BodyExt;
+use hyper::http::request::Parts;
+use hyper::{HeaderMap, Method, Response, StatusCode, header};
+use miko_core::Resp;
+use miko_core::fallible_stream_body::FallibleStreamBody;
+use std::collections::VecDeque;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+/// 下载相关的请求信息（方法与条件请求头）
+#[derive(Debug, Clone)]
+pub struct RangeRequest {
+    pub method: Method,
+    pub headers: HeaderMap,
+}
+
+impl RangeRequest {
+    pub fn new(method: Method, headers: HeaderMap) -> Self {
+        Self { method, headers }
+    }
+
+    fn header(&self, name: header::HeaderName) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+}
+
+impl<S> FromRequestParts<S> for RangeRequest {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let mut headers = HeaderMap::new();
+        for name in [
+            header::RANGE,
+            header::IF_RANGE,
+            header::IF_NONE_MATCH,
+            header::IF_MODIFIED_SINCE,
+        ] {
+            if let Some(value) = req.headers.get(&name) {
+                headers.insert(name, value.clone());
+            }
+        }
+        let range = RangeRequest::new(req.method.clone(), headers);
+        Box::pin(async move { Ok(range) })
+    }
+}
+
+/// 可断点续传的下载响应构建器
+pub struct ResumableDownload<R> {
+    reader: R,
+    len: u64,
+    content_type: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<SystemTime>,
+    disposition: Option<String>,
+    multi_range: bool,
+}
+
+/// 一次多范围请求最多返回的范围数，超出时返回完整内容
+const MAX_RANGES: usize = 16;
+
+impl<R> ResumableDownload<R>
+where
+    R: AsyncRead + AsyncSeek + Send + Unpin + 'static,
+{
+    /// `len` 为数据总长度
+    pub fn new(reader: R, len: u64) -> Self {
+        Self {
+            reader,
+            len,
+            content_type: None,
+            etag: None,
+            last_modified: None,
+            disposition: None,
+            multi_range: false,
+        }
+    }
+
+    /// 设置 Content-Type，默认 application/octet-stream
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// 设置 ETag，未加引号时自动补上
+    pub fn etag(mut self, etag: impl Into<String>) -> Self {
+        let etag = etag.into();
+        self.etag = Some(if etag.starts_with('"') || etag.starts_with("W/") {
+            etag
+        } else {
+            format!("\"{}\"", etag)
+        });
+        self
+    }
+
+    /// 设置最后修改时间
+    pub fn last_modified(mut self, time: SystemTime) -> Self {
+        self.last_modified = Some(time);
+        self
+    }
+
+    /// 以附件形式下载，浏览器会提示保存为 `filename`
+    pub fn attachment(mut self, filename: impl AsRef<str>) -> Self {
+        self.disposition = Some(content_disposition("attachment", filename.as_ref()));
+        self
+    }
+
+    /// 在浏览器中直接显示，另存时使用 `filename`
+    pub fn inline(mut self, filename: impl AsRef<str>) -> Self {
+        self.disposition = Some(content_disposition("inline", filename.as_ref()));
+        self
+    }
+
+    /// 是否以 `multipart/byteranges` 响应多范围请求，默认关闭
+    pub fn multi_range(mut self, enabled: bool) -> Self {
+        self.multi_range = enabled;
+        self
+    }
+
+    /// 根据请求的条件头生成 200 / 206 / 304 / 416 响应
+    pub async fn respond(mut self, req: &RangeRequest) -> std::io::Result<Resp> {
+        let (builder, start, length) = match self.prepare(req) {
+            Prepared::Done(resp) => return Ok(resp),
+            Prepared::Body(builder, start, length) => (builder, start, length),
+            Prepared::Multipart(builder, parts) => {
+                return Ok(multipart_body(builder, self.reader, parts));
+            }
+        };
+        if start > 0 {
+            self.reader.seek(SeekFrom::Start(start)).await?;
+        }
+        let stream = ReaderStream::new(self.reader.take(length));
+        let body = FallibleStreamBody::with_size_hint(stream, length);
+        Ok(builder
+            .body(body.map_err(Into::into).boxed_unsync())
+            .unwrap())
+    }
+
+    /// 同 [`respond`](Self::respond)，但定位到 Range 起点推迟到读取响应体时，定位失败表现为响应体错误
+    pub(crate) fn respond_deferred(self, req: &RangeRequest) -> Resp {
+        let (builder, start, length) = match self.prepare(req) {
+            Prepared::Done(resp) => return resp,
+            Prepared::Body(builder, start, length) => (builder, start, length),
+            Prepared::Multipart(builder, parts) => {
+                return multipart_body(builder, self.reader, parts);
+            }
+        };
+        let mut reader = self.reader;
+        let stream = futures::stream::once(async move {
+            if start > 0 {
+                reader.seek(SeekFrom::Start(start)).await?;
+            }
+            Ok::<_, std::io::Error>(ReaderStream::new(reader.take(length)))
+        })
+        .try_flatten();
+        let body = FallibleStreamBody::with_size_hint(Box::pin(stream), length);
+        builder
+            .body(body.map_err(Into::into).boxed_unsync())
+            .unwrap()
+    }
+
+    /// 生成响应头；需要响应体时返回起点与长度
+    fn prepare(&self, req: &RangeRequest) -> Prepared {
+        if self.is_not_modified(req) {
+            let mut builder = Response::builder().status(StatusCode::NOT_MODIFIED);
+            if let Some(etag) = &self.etag {
+                builder = builder.header(header::ETAG, etag);
+            }
+            if let Some(time) = self.last_modified {
+                builder = builder.header(header::LAST_MODIFIED, httpdate::fmt_http_date(time));
+            }
+            return Prepared::Done(builder.body(bytes_to_boxed(Bytes::new())).unwrap());
+        }
+
+        let content_type = self
+            .content_type
+            .as_deref()
+            .unwrap_or("application/octet-stream");
+        let mut builder = Response::builder().header(header::ACCEPT_RANGES, "bytes");
+        if let Some(etag) = &self.etag {
+            builder = builder.header(header::ETAG, etag);
+        }
+        if let Some(time) = self.last_modified {
+            builder = builder.header(header::LAST_MODIFIED, httpdate::fmt_http_date(time));
+        }
+        if let Some(disposition) = &self.disposition {
+            builder = builder.header(header::CONTENT_DISPOSITION, disposition);
+        }
+
+        let range = match req.header(header::RANGE) {
+            Some(range) if self.if_range_matches(req) => parse_ranges(range, self.len),
+            _ => Ok(None),
+        };
+        let range = match range {
+            Ok(Some(ranges)) if ranges.len() == 1 => Ok(Some(ranges[0])),
+            Ok(Some(ranges)) if self.multi_range && ranges.len() <= MAX_RANGES => {
+                return self.prepare_multipart(builder, req, content_type, ranges);
+            }
+            // 未开启多范围或范围过多时返回完整内容
+            Ok(_) => Ok(None),
+            Err(()) => Err(()),
+        };
+        builder = builder.header(header::CONTENT_TYPE, content_type);
+        let (start, length) = match range {
+            Ok(Some((start, end))) => {
+                builder = builder.status(StatusCode::PARTIAL_CONTENT).header(
+                    header::CONTENT_RANGE,
+                    format!("bytes {}-{}/{}", start, end, self.len),
+                );
+                (start, end - start + 1)
+            }
+            Err(()) => {
+                return Prepared::Done(
+                    Response::builder()
+                        .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                        .header(header::CONTENT_RANGE, format!("bytes */{}", self.len))
+                        .body(bytes_to_boxed(Bytes::new()))
+                        .unwrap(),
+                );
+            }
+            // 无 Range、格式不支持或 If-Range 不匹配时返回完整内容
+            Ok(None) => {
+                builder = builder.status(StatusCode::OK);
+                (0, self.len)
+            }
+        };
+        builder = builder.header(header::CONTENT_LENGTH, length);
+
+        if req.method == Method::HEAD {
+            return Prepared::Done(builder.body(bytes_to_boxed(Bytes::new())).unwrap());
+        }
+        Prepared::Body(builder, start, length)
+    }
+
+    fn prepare_multipart(
+        &self,
+        builder: hyper::http::response::Builder,
+        req: &RangeRequest,
+        content_type: &str,
+        ranges: Vec<(u64, u64)>,
+    ) -> Prepared {
+        let boundary = crate::id::new_id();
+        let parts: VecDeque<_> = ranges
+            .into_iter()
+            .map(|(start, end)| {
+                let head = format!(
+                    "\r\n--{}\r\nContent-Type: {}\r\nContent-Range: bytes {}-{}/{}\r\n\r\n",
+                    boundary, content_type, start, end, self.len
+                );
+                (Bytes::from(head), start, end - start + 1)
+            })
+            .collect();
+        let tail = Bytes::from(format!("\r\n--{}--\r\n", boundary));
+        let length = tail.len() as u64
+            + parts
+                .iter()
+                .map(|(head, _, len)| head.len() as u64 + len)
+                .sum::<u64>();
+        let builder = builder
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(
+                header::CONTENT_TYPE,
+                format!("multipart/byteranges; boundary={}", boundary),
+            )
+            .header(header::CONTENT_LENGTH, length);
+        if req.method == Method::HEAD {
+            return Prepared::Done(builder.body(bytes_to_boxed(Bytes::new())).unwrap());
+        }
+        Prepared::Multipart(builder, MultipartParts { parts, tail })
+    }
+
+    /// 有 `If-None-Match` 时只按 ETag 判断，否则 `GET`/`HEAD` 按 `If-Modified-Since` 判断
+    fn is_not_modified(&self, req: &RangeRequest) -> bool {
+        if let Some(if_none_match) = req.header(header::IF_NONE_MATCH) {
+            let Some(etag) = &self.etag else {
+                return false;
+            };
+            return if_none_match.trim() == "*"
+                || if_none_match
+                    .split(',')
+                    .any(|tag| weak_eq(tag.trim(), etag));
+        }
+        if req.method != Method::GET && req.method != Method::HEAD {
+            return false;
+        }
+        match (
+            req.header(header::IF_MODIFIED_SINCE)
+                .and_then(|v| httpdate::parse_http_date(v).ok()),
+            self.last_modified,
+        ) {
+            (Some(since), Some(modified)) => unix_secs(modified) <= unix_secs(since),
+            _ => false,
+        }
+    }
+
+    /// If-Range 只在强 ETag 或最后修改时间完全一致时才允许返回部分内容
+    fn if_range_matches(&self, req: &RangeRequest) -> bool {
+        let Some(if_range) = req.header(header::IF_RANGE) else {
+            return true;
+        };
+        if if_range.starts_with('"') {
+            return self.etag.as_deref() == Some(if_range);
+        }
+        match (httpdate::parse_http_date(if_range), self.last_modified) {
+            (Ok(date), Some(modified)) => unix_secs(date) == unix_secs(modified),
+            _ => false,
+        }
+    }
+}
+
+enum Prepared {
+    Done(Resp),
+    Body(hyper::http::response::Builder, u64, u64),
+    Multipart(hyper::http::response::Builder, MultipartParts),
+}
+
+/// 多范围响应的各段：段头、起点、长度，以及结尾的分隔符
+struct MultipartParts {
+    parts: VecDeque<(Bytes, u64, u64)>,
+    tail: Bytes,
+}
+
+/// 依次输出各段的段头与内容，读取时才定位到各段起点
+fn multipart_body<R>(
+    builder: hyper::http::response::Builder,
+    reader: R,
+    parts: MultipartParts,
+) -> Resp
+where
+    R: AsyncRead + AsyncSeek + Send + Unpin + 'static,
+{
+    let length = parts.tail.len() as u64
+        + parts
+            .parts
+            .iter()
+            .map(|(head, _, len)| head.len() as u64 + len)
+            .sum::<u64>();
+    let stream = futures::stream::try_unfold(
+        (reader, parts, 0u64),
+        |(mut reader, mut parts, remaining)| async move {
+            if remaining > 0 {
+                let mut buf = vec![0; remaining.min(64 * 1024) as usize];
+                let n = reader.read(&mut buf).await?;
+                if n == 0 {
+                    return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+                }
+                buf.truncate(n);
+                return Ok(Some((
+                    Bytes::from(buf),
+                    (reader, parts, remaining - n as u64),
+                )));
+            }
+            if let Some((head, start, len)) = parts.parts.pop_front() {
+                reader.seek(SeekFrom::Start(start)).await?;
+                return Ok(Some((head, (reader, parts, len))));
+            }
+            if parts.tail.is_empty() {
+                return Ok(None);
+            }
+            let tail = std::mem::take(&mut parts.tail);
+            Ok(Some((tail, (reader, parts, 0))))
+        },
+    );
+    let body = FallibleStreamBody::with_size_hint(Box::pin(stream), length);
+    builder
+        .body(body.map_err(Into::into).boxed_unsync())
+        .unwrap()
+}
+
+/// 生成 `Content-Disposition`：`filename` 为 ASCII 回退名，非 ASCII 文件名另以 `filename*` 给出（RFC 6266）
+pub(crate) fn content_disposition(kind: &str, filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() && c != '"' && c != '\\' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if filename.is_ascii() && fallback == filename {
+        return format!("{}; filename=\"{}\"", kind, fallback);
+    }
+    let mut encoded = String::new();
+    for byte in filename.bytes() {
+        if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    format!(
+        "{}; filename=\"{}\"; filename*=UTF-8''{}",
+        kind, fallback, encoded
+    )
+}
+
+fn weak_eq(a: &str, b: &str) -> bool {
+    a.trim_start_matches("W/") == b.trim_start_matches("W/")
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 解析 Range 头，可能包含多个范围
+/// 返回: Ok(Some(ranges)) - 可满足的范围，不可满足的范围会被忽略
+///       Ok(None) - 无 Range 或格式不支持
+///       Err(()) - 所有范围都超出范围 (应返回 416)
+fn parse_ranges(range_header: &str, size: u64) -> Result<Option<Vec<(u64, u64)>>, ()> {
+    let Some(range_str) = range_header.strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    let mut ranges = Vec::new();
+    for spec in range_str.split(',') {
+        match parse_range(spec.trim(), size) {
+            Ok(Some(range)) => ranges.push(range),
+            Ok(None) => return Ok(None),
+            Err(()) => {}
+        }
+    }
+    if ranges.is_empty() {
+        Err(())
+    } else {
+        Ok(Some(ranges))
+    }
+}
+
+/// 解析单个范围（如 `100-200`、`100-`、`-500`）
+/// 返回: Ok(Some((start, end))) - 合法范围
+///       Ok(None) - 格式不支持
+///       Err(()) - 范围超出范围
+fn parse_range(range_str: &str, size: u64) -> Result<Option<(u64, u64)>, ()> {
+    let parts: Vec<&str> = range_str.split('-').collect();
+    if parts.len() != 2 {
+        return Ok(None);
+    }
+
+    // 处理 bytes=-500 (最后 500 字节) 的情况
+    if parts[0].is_empty() {
+        if let Ok(suffix_length) = parts[1].parse::<u64>() {
+            if suffix_length == 0 || size == 0 {
+                return Err(());
+            }
+            let start = size.saturating_sub(suffix_length);
+            return Ok(Some((start, size - 1)));
+        }
+        return Ok(None);
+    }
+
+    let start = parts[0].parse::<u64>().map_err(|_| ())?;
+    if start >= size {
+        return Err(());
+    }
+    let end = if parts[1].is_empty() {
+        // bytes=100- 表示到末尾
+        size - 1
+    } else {
+        // bytes=100-200
+        parts[1].parse::<u64>().map_err(|_| ())?.min(size - 1)
+    };
+
+    if start <= end {
+        Ok(Some((start, end)))
+    } else {
+        Err(()) // start > end，无效范围
+    }
+}
@@ -0,0 +1,316 @@
+//! 编译进二进制的静态资源服务
+//!
+//! [`EmbeddedSvc`] 从内存中的文件表提供资源，ETag、Range、条件请求、SPA 回退与预压缩的行为与
+//! [`StaticSvc`](crate::ext::static_svc::StaticSvc) 一致，单文件部署时不需要磁盘上的静态文件。
+//!
+//! 启用 `embed` feature 后可以直接使用 `include_dir!` 打包的目录（项目中需添加 `include_dir` 依赖）：
+//!
+//! ```rust,ignore
+//! static DIST: include_dir::Dir = include_dir::include_dir!("$CARGO_MANIFEST_DIR/dist");
+//!
+//! router.nest_service(
+//!     "/",
+//!     EmbeddedSvc::builder()
+//!         .dir(&DIST)
+//!         .with_spa_fallback(true)
+//!         .build(),
+//! );
+//! ```
+//!
+//! 使用 rust-embed 时，通过 [`files`](EmbeddedSvcBuilder::files) 传入文件：
+//!
+//! ```rust,ignore
+//! #[derive(rust_embed::Embed)]
+//! #[folder = "dist"]
+//! struct Assets;
+//!
+//! EmbeddedSvc::builder()
+//!     .files(Assets::iter().map(|path| {
+//!         let data = Assets::get(&path).unwrap().data;
+//!         (path.to_string(), data.into_owned())
+//!     }))
+//!     .build();
+//! ```
+
+use crate::AppError;
+use crate::ext::download::{RangeRequest, ResumableDownload};
+use crate::ext::static_svc::{
+    accepted_encodings, cache_control_for, content_type_for, finish_response, insert_cache_control,
+};
+use crate::http::response::into_response::IntoResponse;
+use crate::router::HttpSvc;
+use bytes::Bytes;
+use hyper::HeaderMap;
+use hyper::header::HeaderValue;
+use miko_core::{Req, Resp, decode_path};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::Cursor;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+use tower_http::cors::CorsLayer;
+
+/// 一个内嵌的文件
+#[derive(Clone)]
+struct EmbeddedFile {
+    data: Bytes,
+    /// 按内容计算的强 ETag
+    etag: String,
+}
+
+impl EmbeddedFile {
+    fn new(data: Bytes) -> Self {
+        let digest = Sha256::digest(&data);
+        let etag = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+        Self { data, etag }
+    }
+}
+
+/// 内嵌资源服务，通过 [`EmbeddedSvc::builder`] 创建
+#[derive(Clone)]
+pub struct EmbeddedSvc {
+    files: Arc<HashMap<String, EmbeddedFile>>,
+    spa_fallback: bool,
+    fallback_files: Arc<Vec<String>>,
+    index_files: Arc<Vec<String>>,
+    precompressed: bool,
+    multi_range: bool,
+    cache_control: Arc<HashMap<String, HeaderValue>>,
+}
+
+impl EmbeddedSvc {
+    /// 构建一个内嵌资源服务的 Builder
+    pub fn builder() -> EmbeddedSvcBuilder {
+        EmbeddedSvcBuilder::new()
+    }
+
+    /// 请求路径对应的文件名，去掉 `.`、`..` 与空段
+    fn normalize(uri_path: &str) -> String {
+        decode_path(uri_path)
+            .split('/')
+            .filter(|seg| !seg.is_empty() && *seg != "." && *seg != "..")
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// 依次尝试文件本身、目录索引文件与 SPA 回退文件
+    fn resolve(&self, key: &str) -> Option<String> {
+        if self.files.contains_key(key) {
+            return Some(key.to_string());
+        }
+        let index = self.index_files.iter().find_map(|index| {
+            let candidate = if key.is_empty() {
+                index.clone()
+            } else {
+                format!("{}/{}", key, index)
+            };
+            self.files.contains_key(&candidate).then_some(candidate)
+        });
+        if index.is_some() || !self.spa_fallback {
+            return index;
+        }
+        self.fallback_files
+            .iter()
+            .find(|f| self.files.contains_key(f.as_str()))
+            .cloned()
+    }
+
+    async fn serve(&self, key: &str, req: RangeRequest) -> std::io::Result<Resp> {
+        let (file, encoding) = self.variant(key, &req.headers);
+        let mut resp =
+            ResumableDownload::new(Cursor::new(file.data.clone()), file.data.len() as u64)
+                .content_type(content_type_for(key))
+                .etag(file.etag.as_str())
+                .multi_range(self.multi_range)
+                .respond(&req)
+                .await?;
+        finish_response(
+            &mut resp,
+            encoding,
+            self.precompressed,
+            cache_control_for(&self.cache_control, Path::new(key)),
+        );
+        Ok(resp)
+    }
+
+    /// 按 `Accept-Encoding` 选择预压缩版本
+    fn variant(&self, key: &str, headers: &HeaderMap) -> (&EmbeddedFile, Option<&'static str>) {
+        if self.precompressed {
+            for (ext, encoding) in accepted_encodings(headers) {
+                if let Some(file) = self.files.get(&format!("{}.{}", key, ext)) {
+                    return (file, Some(encoding));
+                }
+            }
+        }
+        (&self.files[key], None)
+    }
+}
+
+/// 内嵌资源服务构建器
+pub struct EmbeddedSvcBuilder {
+    files: HashMap<String, EmbeddedFile>,
+    spa_fallback: bool,
+    fallback_files: Vec<String>,
+    index_files: Vec<String>,
+    precompressed: bool,
+    multi_range: bool,
+    cache_control: HashMap<String, HeaderValue>,
+    cors_layer: Option<CorsLayer>,
+}
+
+impl Default for EmbeddedSvcBuilder {
+    fn default() -> Self {
+        Self {
+            files: HashMap::new(),
+            spa_fallback: false,
+            fallback_files: vec!["index.html".to_string()],
+            index_files: vec!["index.html".to_string(), "index.htm".to_string()],
+            precompressed: false,
+            multi_range: false,
+            cache_control: HashMap::new(),
+            cors_layer: None,
+        }
+    }
+}
+
+impl EmbeddedSvcBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一个文件，`path` 为相对于挂载点的路径，如 `assets/app.js`
+    pub fn file(mut self, path: impl AsRef<str>, data: impl Into<Bytes>) -> Self {
+        let path = EmbeddedSvc::normalize(path.as_ref());
+        self.files.insert(path, EmbeddedFile::new(data.into()));
+        self
+    }
+
+    /// 批量添加文件
+    pub fn files<P, D>(mut self, files: impl IntoIterator<Item = (P, D)>) -> Self
+    where
+        P: AsRef<str>,
+        D: Into<Bytes>,
+    {
+        for (path, data) in files {
+            self = self.file(path, data);
+        }
+        self
+    }
+
+    /// 添加 `include_dir!` 打包的目录下的所有文件，路径相对于该目录
+    #[cfg(feature = "embed")]
+    pub fn dir(mut self, dir: &'static include_dir::Dir<'static>) -> Self {
+        for entry in dir.entries() {
+            match entry {
+                include_dir::DirEntry::Dir(dir) => self = self.dir(dir),
+                include_dir::DirEntry::File(file) => {
+                    let path = file.path().to_string_lossy().replace('\\', "/");
+                    self = self.file(path, Bytes::from_static(file.contents()));
+                }
+            }
+        }
+        self
+    }
+
+    /// 启用/关闭单页应用回退（当命中文件不存在时回退到配置的 fallback 文件）
+    pub fn with_spa_fallback(mut self, spa_fallback: bool) -> Self {
+        self.spa_fallback = spa_fallback;
+        self
+    }
+
+    /// 自定义 SPA 回退文件列表（按顺序尝试）
+    pub fn with_fallback_files(
+        mut self,
+        files: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.fallback_files = files.into_iter().map(|f| f.into()).collect();
+        self
+    }
+
+    /// 配置目录索引文件列表（当访问目录时按顺序尝试）
+    pub fn with_index_files(mut self, files: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.index_files = files.into_iter().map(|f| f.into()).collect();
+        self
+    }
+
+    /// 客户端接受时优先返回内嵌的 `<file>.br` / `<file>.gz`
+    pub fn with_precompressed(mut self, precompressed: bool) -> Self {
+        self.precompressed = precompressed;
+        self
+    }
+
+    /// 以 `multipart/byteranges` 响应多范围请求，关闭时返回完整文件
+    pub fn with_multi_range(mut self, multi_range: bool) -> Self {
+        self.multi_range = multi_range;
+        self
+    }
+
+    /// 为指定扩展名的文件设置 `Cache-Control`
+    ///
+    /// # Panics
+    ///
+    /// `value` 不是合法的头部值时 panic
+    pub fn with_cache_control(
+        mut self,
+        extensions: impl IntoIterator<Item = impl AsRef<str>>,
+        value: &str,
+    ) -> Self {
+        insert_cache_control(&mut self.cache_control, extensions, value);
+        self
+    }
+
+    /// 配置 CORS Layer
+    pub fn with_cors(mut self, cors_layer: CorsLayer) -> Self {
+        self.cors_layer = Some(cors_layer);
+        self
+    }
+
+    /// 构建为可挂载的 Service
+    pub fn build(self) -> HttpSvc<Req> {
+        let service = EmbeddedSvc {
+            files: Arc::new(self.files),
+            spa_fallback: self.spa_fallback,
+            fallback_files: Arc::new(self.fallback_files),
+            index_files: Arc::new(self.index_files),
+            precompressed: self.precompressed,
+            multi_range: self.multi_range,
+            cache_control: Arc::new(self.cache_control),
+        };
+        if let Some(cors_layer) = self.cors_layer {
+            BoxCloneService::new(cors_layer.layer(service))
+        } else {
+            BoxCloneService::new(service)
+        }
+    }
+}
+
+impl Service<Req> for EmbeddedSvc {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let key = self.resolve(&Self::normalize(req.uri().path()));
+        let (parts, _body) = req.into_parts();
+        let range = RangeRequest::new(parts.method, parts.headers);
+        let svc = self.clone();
+        Box::pin(async move {
+            let Some(key) = key else {
+                return Ok(AppError::NotFound("File not found".to_string()).into_response());
+            };
+            Ok(svc
+                .serve(&key, range)
+                .await
+                .unwrap_or_else(|e| AppError::from(e).into_response()))
+        })
+    }
+}
@@ -0,0 +1,287 @@
+//! 健康检查与就绪探针
+//!
+//! [`Application`](crate::app::Application) 默认挂载两个端点，路径可在 `[server.health]` 中修改或关闭：
+//!
+//! - `/healthz`：存活探针，进程能处理请求即返回 200；
+//! - `/readyz`：就绪探针，并发执行 [`HealthRegistry`] 中的所有检查，全部通过返回 200，否则返回 503。
+//!
+//! 检查可随时注册到全局注册表，也可通过 `#[dep] health: Arc<HealthRegistry>` 注入后注册：
+//!
+//! ```rust,ignore
+//! HealthRegistry::global().register("cache", move || {
+//!     let cache = cache.clone();
+//!     async move { cache.ping().await }
+//! });
+//! ```
+//!
+//! 组件提供 `async fn health(&self)` 时，`#[component(health_check)]` 会自动注册，检查名为类型名，
+//! 也可用 `health_check = "db"` 指定：
+//!
+//! ```rust,ignore
+//! #[component(health_check = "db")]
+//! impl Database {
+//!     async fn new() -> Self { /* ... */ }
+//!     async fn health(&self) -> Result<(), sqlx::Error> { self.ping().await }
+//! }
+//! ```
+//!
+//! 就绪探针的响应体：
+//!
+//! ```json
+//! {"status":"down","checks":{"cache":{"status":"down","error":"timed out after 5000ms","duration_ms":5000},"db":{"status":"up","duration_ms":3}}}
+//! ```
+//!
+//! 启用 `migrations` 且执行过迁移时，结果会作为名为 `migrations` 的检查一并报告。
+
+use crate::IntoResponse;
+use crate::extractor::Json;
+use crate::miko_core::Resp;
+use futures::FutureExt;
+use hyper::StatusCode;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::{Duration, Instant};
+
+/// 单项检查返回的 Future，`Err` 为失败原因
+pub type HealthFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+/// 单项检查的缺省超时
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 可作为检查结果的类型：`()`、`bool` 与 `Result<(), E: Display>`
+pub trait IntoHealth {
+    fn into_health(self) -> Result<(), String>;
+}
+
+impl IntoHealth for () {
+    fn into_health(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl IntoHealth for bool {
+    fn into_health(self) -> Result<(), String> {
+        if self {
+            Ok(())
+        } else {
+            Err("unhealthy".to_string())
+        }
+    }
+}
+
+impl<E: Display> IntoHealth for Result<(), E> {
+    fn into_health(self) -> Result<(), String> {
+        self.map_err(|e| e.to_string())
+    }
+}
+
+/// 检查状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+/// 单项检查的结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckReport {
+    pub status: HealthStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 检查耗时（毫秒）
+    pub duration_ms: u64,
+}
+
+/// 一次就绪检查的汇总，任一检查失败时整体为 `down`；作为响应时分别返回 200 / 503
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub checks: BTreeMap<String, CheckReport>,
+}
+
+impl HealthReport {
+    pub fn is_up(&self) -> bool {
+        self.status == HealthStatus::Up
+    }
+}
+
+impl IntoResponse for HealthReport {
+    fn into_response(self) -> Resp {
+        let status = if self.is_up() {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+        (status, Json(self)).into_response()
+    }
+}
+
+/// 由 `#[component(health_check)]` 通过 `inventory::submit!` 登记的检查，
+/// 创建全局注册表时自动注册
+pub struct HealthCheckDef {
+    pub name: &'static str,
+    pub check: fn() -> HealthFuture,
+}
+#[cfg(feature = "auto")]
+crate::inventory::collect!(HealthCheckDef);
+
+type CheckFn = Arc<dyn Fn() -> HealthFuture + Send + Sync>;
+
+/// 健康检查注册表
+#[derive(Clone, Default)]
+pub struct HealthRegistry {
+    checks: Arc<RwLock<BTreeMap<String, CheckFn>>>,
+}
+
+static GLOBAL: LazyLock<HealthRegistry> = LazyLock::new(|| {
+    let registry = HealthRegistry::new();
+    #[cfg(feature = "auto")]
+    for def in crate::inventory::iter::<HealthCheckDef> {
+        registry.register(def.name, def.check);
+    }
+    registry
+});
+
+impl HealthRegistry {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 全局注册表，`#[dep]` 注入与 `/readyz` 共用该实例
+    pub fn global() -> &'static HealthRegistry {
+        &GLOBAL
+    }
+
+    /// 注册检查，同名的检查会被替换
+    pub fn register<F, Fut, R>(&self, name: impl Into<String>, check: F) -> &Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        R: IntoHealth,
+    {
+        let check: CheckFn = Arc::new(move || {
+            let fut = check();
+            Box::pin(async move { fut.await.into_health() })
+        });
+        self.checks.write().unwrap().insert(name.into(), check);
+        self
+    }
+
+    /// 移除检查，返回是否存在
+    pub fn unregister(&self, name: &str) -> bool {
+        self.checks.write().unwrap().remove(name).is_some()
+    }
+
+    /// 已注册的检查名
+    pub fn names(&self) -> Vec<String> {
+        self.checks.read().unwrap().keys().cloned().collect()
+    }
+
+    /// 以缺省超时执行所有检查
+    pub async fn check(&self) -> HealthReport {
+        self.check_within(DEFAULT_TIMEOUT).await
+    }
+
+    /// 并发执行所有检查，单项超过 `timeout` 或 panic 视为失败
+    pub async fn check_within(&self, timeout: Duration) -> HealthReport {
+        let checks: Vec<(String, CheckFn)> = self
+            .checks
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, check)| (name.clone(), check.clone()))
+            .collect();
+        let runs = checks.into_iter().map(|(name, check)| async move {
+            let start = Instant::now();
+            let result = match tokio::time::timeout(
+                timeout,
+                AssertUnwindSafe(async move { check().await }).catch_unwind(),
+            )
+            .await
+            {
+                Ok(Ok(result)) => result,
+                Ok(Err(_)) => Err("health check panicked".to_string()),
+                Err(_) => Err(format!("timed out after {}ms", timeout.as_millis())),
+            };
+            (name, report(result, start.elapsed()))
+        });
+        #[cfg_attr(not(feature = "migrations"), allow(unused_mut))]
+        let mut checks: BTreeMap<String, CheckReport> =
+            futures::future::join_all(runs).await.into_iter().collect();
+        #[cfg(feature = "migrations")]
+        if let Some(status) = crate::ext::migrations::migration_status() {
+            let result = if status.is_ok() {
+                Ok(())
+            } else {
+                Err(status.error.unwrap_or_else(|| {
+                    format!("pending migrations: {}", status.pending.join(", "))
+                }))
+            };
+            checks
+                .entry("migrations".to_string())
+                .or_insert_with(|| report(result, Duration::ZERO));
+        }
+        for (name, check) in &checks {
+            if let Some(error) = &check.error {
+                tracing::warn!(check = %name, %error, "health check failed");
+            }
+        }
+        let status = if checks.values().all(|c| c.status == HealthStatus::Up) {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        };
+        HealthReport { status, checks }
+    }
+}
+
+fn report(result: Result<(), String>, elapsed: Duration) -> CheckReport {
+    CheckReport {
+        status: if result.is_ok() {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        },
+        error: result.err(),
+        duration_ms: elapsed.as_millis() as u64,
+    }
+}
+
+/// 存活探针，总是返回 200 `{"status":"up"}`
+pub async fn liveness_handler() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": HealthStatus::Up }))
+}
+
+/// 就绪探针，以缺省超时检查全局注册表
+pub async fn readiness_handler() -> HealthReport {
+    HealthRegistry::global().check().await
+}
+
+#[cfg(feature = "auto")]
+crate::inventory::submit! {
+    crate::dependency_container::DependencyDefFn(|| {
+        crate::dependency_container::DependencyDef {
+            type_id: std::any::TypeId::of::<HealthRegistry>(),
+            type_name: std::any::type_name::<HealthRegistry>(),
+            prewarm: false,
+            name: "___",
+            lifetime: crate::dependency_container::DependencyLifetime::Singleton,
+            deps: Vec::new,
+            source: concat!(file!(), ":", line!()),
+            replace: false,
+            init_fn: || {
+                Box::pin(async move {
+                    Arc::new(HealthRegistry::global().clone())
+                        as Arc<dyn std::any::Any + Send + Sync>
+                })
+            },
+        }
+    })
+}
@@ -0,0 +1,370 @@
+//! 定期清理临时文件与过期数据
+//!
+//! [`Janitor`] 在后台按固定间隔依次执行清理任务，并累计回收的条目数与字节数。
+//! 内置的 [`FileSweep`] 清理过期的 multipart 临时文件与中断上传留下的 `.part` 文件，
+//! 会话、幂等记录等存储可通过 [`JanitorTask`] 或 [`Janitor::task_fn`] 接入：
+//!
+//! ```toml
+//! [janitor]
+//! interval_secs = 600        # 缺省 3600
+//! max_age_secs = 3600        # 文件超过该时长未修改才会删除，缺省 86400
+//! upload_dirs = ["uploads"]  # 清理其中残留的 `.part` 文件
+//! ```
+//!
+//! ```rust,ignore
+//! let janitor = Janitor::from_global_settings()
+//!     .task_fn("sessions", || async { Ok(Reclaimed::items(sessions().purge_expired())) });
+//! janitor.spawn();
+//!
+//! // 之后可随时查看累计回收量
+//! let stats = janitor.stats();
+//! ```
+
+use crate::app::config::get_settings;
+use crate::ext::uploader::PARTIAL_UPLOAD_SUFFIX;
+use crate::extractor::multipart::MULTIPART_TEMP_PREFIX;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::io;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+
+/// 清理任务返回的 Future
+pub type JanitorFuture<'a> = Pin<Box<dyn Future<Output = io::Result<Reclaimed>> + Send + 'a>>;
+
+/// 一次清理回收的条目数与字节数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Reclaimed {
+    pub items: u64,
+    pub bytes: u64,
+}
+
+impl Reclaimed {
+    /// 只统计条目数（如过期会话）
+    pub fn items(items: u64) -> Self {
+        Self { items, bytes: 0 }
+    }
+
+    fn add(&mut self, other: Reclaimed) {
+        self.items += other.items;
+        self.bytes += other.bytes;
+    }
+}
+
+/// 由 [`Janitor`] 定期执行的清理任务
+pub trait JanitorTask: Send + Sync + 'static {
+    /// 任务名，用于日志
+    fn name(&self) -> &str;
+
+    /// 执行一次清理
+    fn run(&self) -> JanitorFuture<'_>;
+}
+
+/// 删除目录中超过 `max_age` 未修改、且文件名匹配前缀/后缀的文件（不递归）
+#[derive(Debug, Clone)]
+pub struct FileSweep {
+    name: String,
+    dir: PathBuf,
+    max_age: Duration,
+    prefix: Option<String>,
+    suffix: Option<String>,
+}
+
+impl FileSweep {
+    pub fn new(dir: impl Into<PathBuf>, max_age: Duration) -> Self {
+        let dir = dir.into();
+        Self {
+            name: format!("sweep {}", dir.display()),
+            dir,
+            max_age,
+            prefix: None,
+            suffix: None,
+        }
+    }
+
+    /// 系统临时目录中 multipart 解析残留的临时文件
+    pub fn multipart_temp_files(max_age: Duration) -> Self {
+        Self::new(std::env::temp_dir(), max_age)
+            .prefix(MULTIPART_TEMP_PREFIX)
+            .named("multipart temp files")
+    }
+
+    /// 上传目录中中断上传留下的 `.part` 文件
+    pub fn partial_uploads(dir: impl Into<PathBuf>, max_age: Duration) -> Self {
+        let sweep = Self::new(dir, max_age).suffix(PARTIAL_UPLOAD_SUFFIX);
+        let name = format!("partial uploads in {}", sweep.dir.display());
+        sweep.named(name)
+    }
+
+    /// 只删除以 `prefix` 开头的文件
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// 只删除以 `suffix` 结尾的文件
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = Some(suffix.into());
+        self
+    }
+
+    /// 设置日志中显示的任务名
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    fn matches(&self, file_name: &str) -> bool {
+        self.prefix
+            .as_deref()
+            .is_none_or(|p| file_name.starts_with(p))
+            && self
+                .suffix
+                .as_deref()
+                .is_none_or(|s| file_name.ends_with(s))
+    }
+
+    async fn sweep(&self) -> io::Result<Reclaimed> {
+        let mut reclaimed = Reclaimed::default();
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            // 目录尚未创建时无需清理
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(reclaimed),
+            Err(e) => return Err(e),
+        };
+        let now = SystemTime::now();
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_name().to_str().is_some_and(|n| self.matches(n)) {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata().await else {
+                continue;
+            };
+            let expired = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age >= self.max_age);
+            if !metadata.is_file() || !expired {
+                continue;
+            }
+            match tokio::fs::remove_file(entry.path()).await {
+                Ok(()) => reclaimed.add(Reclaimed {
+                    items: 1,
+                    bytes: metadata.len(),
+                }),
+                // 文件可能已被其他进程删除
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    tracing::warn!(path = %entry.path().display(), error = %e, "janitor failed to remove file")
+                }
+            }
+        }
+        Ok(reclaimed)
+    }
+}
+
+impl JanitorTask for FileSweep {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self) -> JanitorFuture<'_> {
+        Box::pin(self.sweep())
+    }
+}
+
+struct FnTask<F> {
+    name: String,
+    f: F,
+}
+
+impl<F, Fut> JanitorTask for FnTask<F>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = io::Result<Reclaimed>> + Send + 'static,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self) -> JanitorFuture<'_> {
+        Box::pin((self.f)())
+    }
+}
+
+/// `[janitor]` 配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JanitorSettings {
+    /// 两次清理之间的间隔（秒）
+    pub interval_secs: u64,
+    /// 文件超过该时长未修改才会被清理（秒）
+    pub max_age_secs: u64,
+    /// 清理其中 `.part` 残片的上传目录
+    pub upload_dirs: Vec<PathBuf>,
+}
+
+impl Default for JanitorSettings {
+    fn default() -> Self {
+        Self {
+            interval_secs: 3600,
+            max_age_secs: 86400,
+            upload_dirs: Vec::new(),
+        }
+    }
+}
+
+/// 累计清理统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct JanitorStats {
+    /// 已完成的清理轮数
+    pub runs: u64,
+    /// 累计删除的条目数
+    pub items: u64,
+    /// 累计回收的字节数
+    pub bytes: u64,
+    /// 累计失败的任务次数
+    pub errors: u64,
+}
+
+#[derive(Default)]
+struct Metrics {
+    runs: AtomicU64,
+    items: AtomicU64,
+    bytes: AtomicU64,
+    errors: AtomicU64,
+}
+
+/// 后台清理服务，克隆后共享任务与统计
+#[derive(Clone)]
+pub struct Janitor {
+    interval: Duration,
+    tasks: Vec<Arc<dyn JanitorTask>>,
+    metrics: Arc<Metrics>,
+}
+
+impl Janitor {
+    /// 创建不含任务的清理服务
+    ///
+    /// # Panics
+    ///
+    /// `interval` 为零时 panic。
+    pub fn new(interval: Duration) -> Self {
+        assert!(
+            !interval.is_zero(),
+            "janitor interval must be greater than zero"
+        );
+        Self {
+            interval,
+            tasks: Vec::new(),
+            metrics: Arc::default(),
+        }
+    }
+
+    /// 按全局 `[janitor]` 配置创建
+    pub fn from_global_settings() -> Self {
+        let settings: JanitorSettings = get_settings().get("janitor").unwrap_or_default();
+        Self::from_settings(settings)
+    }
+
+    /// 按配置创建，包含 multipart 临时文件与各上传目录的 `.part` 清理任务
+    pub fn from_settings(settings: JanitorSettings) -> Self {
+        let max_age = Duration::from_secs(settings.max_age_secs);
+        let mut janitor = Self::new(Duration::from_secs(settings.interval_secs.max(1)))
+            .task(FileSweep::multipart_temp_files(max_age));
+        for dir in settings.upload_dirs {
+            janitor = janitor.task(FileSweep::partial_uploads(dir, max_age));
+        }
+        janitor
+    }
+
+    /// 添加清理任务
+    pub fn task(mut self, task: impl JanitorTask) -> Self {
+        self.tasks.push(Arc::new(task));
+        self
+    }
+
+    /// 以闭包添加清理任务，如清理过期会话或幂等记录
+    pub fn task_fn<F, Fut>(self, name: impl Into<String>, f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = io::Result<Reclaimed>> + Send + 'static,
+    {
+        self.task(FnTask {
+            name: name.into(),
+            f,
+        })
+    }
+
+    /// 立即执行一轮清理，返回本轮的回收量；单个任务失败不影响其他任务
+    pub async fn run_once(&self) -> Reclaimed {
+        let mut total = Reclaimed::default();
+        for task in &self.tasks {
+            let span = tracing::info_span!("janitor task", task = task.name());
+            match task.run().instrument(span).await {
+                Ok(reclaimed) => {
+                    if reclaimed != Reclaimed::default() {
+                        tracing::debug!(
+                            task = task.name(),
+                            items = reclaimed.items,
+                            bytes = reclaimed.bytes,
+                            "janitor task finished"
+                        );
+                    }
+                    total.add(reclaimed);
+                }
+                Err(e) => {
+                    self.metrics.errors.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!(task = task.name(), error = %e, "janitor task failed");
+                }
+            }
+        }
+        self.metrics.runs.fetch_add(1, Ordering::Relaxed);
+        self.metrics.items.fetch_add(total.items, Ordering::Relaxed);
+        self.metrics.bytes.fetch_add(total.bytes, Ordering::Relaxed);
+        if total != Reclaimed::default() {
+            tracing::info!(
+                items = total.items,
+                bytes = total.bytes,
+                "janitor reclaimed space"
+            );
+        }
+        total
+    }
+
+    /// 在后台按间隔循环清理，首轮在一个间隔之后执行
+    ///
+    /// 每轮清理在独立的 `janitor run` span 中执行，并以 follows-from 关联到调用 `spawn` 时的 span。
+    pub fn spawn(&self) -> JoinHandle<()> {
+        let janitor = self.clone();
+        let origin = tracing::Span::current();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval_at(
+                tokio::time::Instant::now() + janitor.interval,
+                janitor.interval,
+            );
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let span = origin.in_scope(|| crate::context::task_span("janitor run"));
+                janitor.run_once().instrument(span).await;
+            }
+        })
+    }
+
+    /// 累计清理统计
+    pub fn stats(&self) -> JanitorStats {
+        JanitorStats {
+            runs: self.metrics.runs.load(Ordering::Relaxed),
+            items: self.metrics.items.load(Ordering::Relaxed),
+            bytes: self.metrics.bytes.load(Ordering::Relaxed),
+            errors: self.metrics.errors.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,450 @@
+//! 发送邮件
+//!
+//! [`Mailer`] 通过可替换的 [`MailTransport`] 发送 [`Email`]，可以 `#[dep] mailer: Arc<Mailer>` 注入，
+//! 注入的实例按 `[mail]` 配置创建：
+//!
+//! ```toml
+//! [mail]
+//! from = "Miko <noreply@example.com>"
+//! transport = "smtp"        # 缺省为 "log"，只记录日志不发送
+//!
+//! [mail.smtp]               # 需要 `mail-smtp` feature
+//! host = "localhost"
+//! port = 587
+//! tls = "starttls"          # 缺省；"tls" 为直接 TLS，"none" 只用于本机中继且不能配置认证
+//! username = "user"         # 可选
+//! password = "secret"
+//! ```
+//!
+//! ```rust,ignore
+//! static WELCOME: MailTemplate = MailTemplate::new("Welcome, {{ name }}", "Hi {{ name }}, thanks for joining.");
+//!
+//! #[post("/signup")]
+//! async fn signup(#[dep] mailer: Arc<Mailer>, Json(user): Json<NewUser>) -> AppResult<()> {
+//!     mailer.send(WELCOME.render(&user).to(&user.email)).await
+//! }
+//! ```
+
+use crate::app::config::get_settings;
+use crate::utils::escape_html;
+use crate::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// 一封待发送的邮件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Email {
+    /// 发件人，缺省使用 [`Mailer`] 的默认发件人
+    pub from: Option<String>,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub reply_to: Option<String>,
+    pub subject: String,
+    pub text: String,
+    pub html: Option<String>,
+}
+
+impl Email {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from(mut self, from: impl Into<String>) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    /// 添加收件人，可多次调用
+    pub fn to(mut self, to: impl Into<String>) -> Self {
+        self.to.push(to.into());
+        self
+    }
+
+    /// 添加抄送人，可多次调用
+    pub fn cc(mut self, cc: impl Into<String>) -> Self {
+        self.cc.push(cc.into());
+        self
+    }
+
+    pub fn reply_to(mut self, reply_to: impl Into<String>) -> Self {
+        self.reply_to = Some(reply_to.into());
+        self
+    }
+
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = subject.into();
+        self
+    }
+
+    /// 纯文本正文
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// HTML 正文，与纯文本正文一起以 `multipart/alternative` 发送
+    pub fn html(mut self, html: impl Into<String>) -> Self {
+        self.html = Some(html.into());
+        self
+    }
+}
+
+/// 邮件模板，`{{ name }}` 占位符由渲染数据中的同名字段替换
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MailTemplate {
+    pub subject: &'static str,
+    pub text: &'static str,
+    pub html: Option<&'static str>,
+}
+
+impl MailTemplate {
+    pub const fn new(subject: &'static str, text: &'static str) -> Self {
+        Self {
+            subject,
+            text,
+            html: None,
+        }
+    }
+
+    pub const fn with_html(mut self, html: &'static str) -> Self {
+        self.html = Some(html);
+        self
+    }
+
+    /// 用 `data` 的字段渲染模板，得到尚未设置收件人的邮件
+    ///
+    /// HTML 正文中替换的值会转义；缺失的字段替换为空字符串。
+    pub fn render<T: Serialize + ?Sized>(&self, data: &T) -> Email {
+        let data = serde_json::to_value(data).unwrap_or_default();
+        Email {
+            subject: fill(self.subject, &data, false),
+            text: fill(self.text, &data, false),
+            html: self.html.map(|html| fill(html, &data, true)),
+            ..Email::default()
+        }
+    }
+}
+
+fn fill(template: &str, data: &serde_json::Value, escape: bool) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let key = rest[start + 2..start + len].trim();
+        let value = match data.pointer(&format!("/{}", key.replace('.', "/"))) {
+            None | Some(serde_json::Value::Null) => String::new(),
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(v) => v.to_string(),
+        };
+        if escape {
+            out.push_str(&escape_html(&value));
+        } else {
+            out.push_str(&value);
+        }
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 发送邮件的方式，如 SMTP 或服务商的 HTTP API
+pub trait MailTransport: Send + Sync + 'static {
+    /// 发送邮件，`email.from` 已由 [`Mailer`] 填充
+    fn send<'a>(
+        &'a self,
+        email: &'a Email,
+    ) -> Pin<Box<dyn Future<Output = AppResult<()>> + Send + 'a>>;
+}
+
+/// 只记录日志、不实际发送的传输方式，适合开发环境；已发送的邮件可通过 [`sent`](Self::sent) 查看
+#[derive(Clone, Default)]
+pub struct LogTransport {
+    sent: Arc<Mutex<Vec<Email>>>,
+}
+
+impl LogTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 已“发送”的邮件
+    pub fn sent(&self) -> Vec<Email> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+impl MailTransport for LogTransport {
+    fn send<'a>(
+        &'a self,
+        email: &'a Email,
+    ) -> Pin<Box<dyn Future<Output = AppResult<()>> + Send + 'a>> {
+        tracing::info!(
+            from = email.from.as_deref().unwrap_or_default(),
+            to = ?email.to,
+            subject = %email.subject,
+            "mail not sent (log transport)"
+        );
+        tracing::debug!(body = %email.text, "mail body");
+        self.sent.lock().unwrap().push(email.clone());
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// 邮件配置（`[mail]`）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MailSettings {
+    /// 默认发件人
+    pub from: String,
+    /// `"log"`（缺省）或 `"smtp"`
+    pub transport: Option<String>,
+    #[cfg(feature = "mail-smtp")]
+    pub smtp: smtp::SmtpSettings,
+}
+
+/// 邮件发送组件，可通过 `#[dep] mailer: Arc<Mailer>` 注入
+#[derive(Clone)]
+pub struct Mailer {
+    from: String,
+    transport: Arc<dyn MailTransport>,
+}
+
+static GLOBAL: LazyLock<Mailer> = LazyLock::new(Mailer::from_global_settings);
+
+impl Mailer {
+    /// 使用默认发件人 `from` 与传输方式创建
+    pub fn new(from: impl Into<String>, transport: impl MailTransport) -> Self {
+        Self {
+            from: from.into(),
+            transport: Arc::new(transport),
+        }
+    }
+
+    /// 根据 `[mail]` 配置创建，未知的传输方式回退为只记录日志
+    pub fn from_global_settings() -> Self {
+        let settings: MailSettings = get_settings().get("mail").unwrap_or_default();
+        Self::from_settings(settings)
+    }
+
+    pub fn from_settings(settings: MailSettings) -> Self {
+        match settings.transport.as_deref().unwrap_or("log") {
+            #[cfg(feature = "mail-smtp")]
+            "smtp" => Self::new(settings.from, smtp::SmtpTransport::new(settings.smtp)),
+            "log" => Self::new(settings.from, LogTransport::new()),
+            other => {
+                tracing::warn!(
+                    transport = other,
+                    "unknown mail transport, falling back to log"
+                );
+                Self::new(settings.from, LogTransport::new())
+            }
+        }
+    }
+
+    /// 全局实例，`#[dep]` 注入使用该实例
+    pub fn global() -> &'static Mailer {
+        &GLOBAL
+    }
+
+    /// 发送邮件，未指定发件人时使用默认发件人
+    pub async fn send(&self, mut email: Email) -> AppResult<()> {
+        if email.to.is_empty() && email.cc.is_empty() {
+            return Err(AppError::InternalServerError(
+                "Email has no recipients".to_string(),
+            ));
+        }
+        if email.from.is_none() {
+            if self.from.is_empty() {
+                return Err(AppError::InternalServerError(
+                    "Email has no sender, set `mail.from`".to_string(),
+                ));
+            }
+            email.from = Some(self.from.clone());
+        }
+        self.transport.send(&email).await
+    }
+}
+
+#[cfg(feature = "auto")]
+crate::inventory::submit! {
+    crate::dependency_container::DependencyDefFn(|| {
+        crate::dependency_container::DependencyDef {
+            type_id: std::any::TypeId::of::<Mailer>(),
+            type_name: std::any::type_name::<Mailer>(),
+            prewarm: false,
+            name: "___",
+            lifetime: crate::dependency_container::DependencyLifetime::Singleton,
+            deps: Vec::new,
+            source: concat!(file!(), ":", line!()),
+            replace: false,
+            init_fn: || {
+                Box::pin(async move {
+                    Arc::new(Mailer::global().clone()) as Arc<dyn std::any::Any + Send + Sync>
+                })
+            },
+        }
+    })
+}
+
+#[cfg(feature = "mail-smtp")]
+pub use smtp::{SmtpSettings, SmtpTls, SmtpTransport};
+
+#[cfg(feature = "mail-smtp")]
+mod smtp {
+    use super::{Email, MailTransport};
+    use crate::{AppError, AppResult};
+    use lettre::message::{Mailbox, MultiPart, SinglePart};
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::transport::smtp::client::{Tls, TlsParameters};
+    use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+    use serde::Deserialize;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::time::Duration;
+
+    /// SMTP 连接的加密方式
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum SmtpTls {
+        /// 必须通过 STARTTLS 升级为加密连接，服务器不支持时发送失败（缺省）
+        #[default]
+        Starttls,
+        /// 连接建立即使用 TLS（通常为 465 端口）
+        Tls,
+        /// 不加密，只适用于本机或内网中继（如 Postfix、Mailpit）；此时拒绝发送认证信息
+        None,
+    }
+
+    /// SMTP 配置（`[mail.smtp]`）
+    #[derive(Debug, Clone, Deserialize)]
+    #[serde(default)]
+    pub struct SmtpSettings {
+        pub host: String,
+        pub port: u16,
+        pub username: Option<String>,
+        pub password: Option<String>,
+        /// `"starttls"`（缺省）、`"tls"` 或 `"none"`
+        pub tls: SmtpTls,
+        /// 连接与每条命令的超时（毫秒）
+        pub timeout_ms: u64,
+    }
+
+    impl Default for SmtpSettings {
+        fn default() -> Self {
+            Self {
+                host: "localhost".to_string(),
+                port: 25,
+                username: None,
+                password: None,
+                tls: SmtpTls::default(),
+                timeout_ms: 10_000,
+            }
+        }
+    }
+
+    /// 基于 lettre 的 SMTP 传输，缺省要求 STARTTLS
+    pub struct SmtpTransport {
+        settings: SmtpSettings,
+    }
+
+    impl SmtpTransport {
+        pub fn new(settings: SmtpSettings) -> Self {
+            if settings.tls == SmtpTls::None && settings.username.is_some() {
+                tracing::warn!(
+                    host = %settings.host,
+                    "SMTP credentials are configured without TLS, sending will be refused"
+                );
+            }
+            Self { settings }
+        }
+
+        fn transport(&self) -> AppResult<AsyncSmtpTransport<Tokio1Executor>> {
+            let settings = &self.settings;
+            let tls = match settings.tls {
+                SmtpTls::None => Tls::None,
+                SmtpTls::Starttls => {
+                    Tls::Required(TlsParameters::new(settings.host.clone()).map_err(smtp_error)?)
+                }
+                SmtpTls::Tls => {
+                    Tls::Wrapper(TlsParameters::new(settings.host.clone()).map_err(smtp_error)?)
+                }
+            };
+            let mut builder =
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(settings.host.as_str())
+                    .port(settings.port)
+                    .tls(tls)
+                    .timeout(Some(Duration::from_millis(settings.timeout_ms)));
+            if let (Some(user), Some(password)) = (&settings.username, &settings.password) {
+                // 认证信息只允许在加密连接上发送
+                if settings.tls == SmtpTls::None {
+                    return Err(AppError::InternalServerError(
+                        "Refusing to send SMTP credentials over an unencrypted connection, set `mail.smtp.tls`".to_string(),
+                    ));
+                }
+                builder = builder.credentials(Credentials::new(user.clone(), password.clone()));
+            }
+            Ok(builder.build())
+        }
+
+        async fn deliver(&self, email: &Email) -> AppResult<()> {
+            let message = build_message(email)?;
+            let transport = self.transport()?;
+            transport.send(message).await.map_err(smtp_error)?;
+            Ok(())
+        }
+    }
+
+    impl MailTransport for SmtpTransport {
+        fn send<'a>(
+            &'a self,
+            email: &'a Email,
+        ) -> Pin<Box<dyn Future<Output = AppResult<()>> + Send + 'a>> {
+            Box::pin(self.deliver(email))
+        }
+    }
+
+    fn smtp_error(e: impl std::fmt::Display) -> AppError {
+        AppError::BadGateway(format!("SMTP error: {}", e))
+    }
+
+    /// 解析 `Name <a@b>` 或 `a@b`，含回车换行的地址直接拒绝，防止注入 SMTP 命令
+    fn mailbox(value: &str) -> AppResult<Mailbox> {
+        if value.contains(['\r', '\n']) {
+            return Err(AppError::BadRequest(format!(
+                "Invalid email address {:?}: contains a line break",
+                value
+            )));
+        }
+        value
+            .parse()
+            .map_err(|e| AppError::BadRequest(format!("Invalid email address {:?}: {}", value, e)))
+    }
+
+    pub(super) fn build_message(email: &Email) -> AppResult<Message> {
+        let mut builder = Message::builder()
+            .from(mailbox(email.from.as_deref().unwrap_or_default())?)
+            .subject(email.subject.replace(['\r', '\n'], " "));
+        for to in &email.to {
+            builder = builder.to(mailbox(to)?);
+        }
+        for cc in &email.cc {
+            builder = builder.cc(mailbox(cc)?);
+        }
+        if let Some(reply_to) = &email.reply_to {
+            builder = builder.reply_to(mailbox(reply_to)?);
+        }
+        let message = match &email.html {
+            None => builder.singlepart(SinglePart::plain(email.text.clone())),
+            Some(html) => builder.multipart(MultiPart::alternative_plain_html(
+                email.text.clone(),
+                html.clone(),
+            )),
+        };
+        message.map_err(|e| AppError::InternalServerError(format!("Invalid email: {}", e)))
+    }
+}
@@ -0,0 +1,322 @@
+//! Prometheus 指标
+//!
+//! [`MetricsLayer`] 按路由模式（如 `/users/{id}`）统计请求数、处理耗时、响应大小与正在处理的请求数，
+//! [`metrics_handler`] 以 Prometheus 文本格式导出：
+//!
+//! ```rust,ignore
+//! router.with_layer(MetricsLayer::new());
+//! router.get("/metrics", metrics_handler);
+//! ```
+//!
+//! 自定义指标注册到同一个 [`MetricsRegistry`]，可通过 `#[dep] metrics: Arc<MetricsRegistry>` 注入：
+//!
+//! ```rust,ignore
+//! static ORDERS: LazyLock<IntCounter> = LazyLock::new(|| {
+//!     MetricsRegistry::global()
+//!         .register(IntCounter::new("orders_total", "Orders placed").unwrap())
+//!         .unwrap()
+//! });
+//! ```
+//!
+//! 导出的内置指标：
+//!
+//! - `http_requests_total{method, route, status}`
+//! - `http_request_duration_seconds{method, route}`：到响应头返回为止的耗时
+//! - `http_response_size_bytes{method, route}`：响应体实际写出的字节数
+//! - `http_requests_in_flight`：正在处理（含仍在流式写出响应体）的请求数
+//!
+//! 未命中任何路由的请求统一记为 `route="<unmatched>"`，避免原始路径导致标签数量无限增长。
+
+use crate::miko_core::{MikoError, Req, Resp};
+use crate::router::MatchedPath;
+use crate::{AppError, AppResult, IntoResponse};
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::BodyExt;
+use http_body_util::combinators::UnsyncBoxBody;
+use hyper::Response;
+use prometheus::core::Collector;
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock};
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+pub use prometheus;
+
+/// 未命中路由的请求使用的 `route` 标签
+const UNMATCHED: &str = "<unmatched>";
+
+/// 指标注册表，包含内置的 HTTP 指标，也可注册应用自定义的指标
+#[derive(Clone)]
+pub struct MetricsRegistry {
+    inner: Arc<RegistryInner>,
+}
+
+struct RegistryInner {
+    registry: Registry,
+    requests: IntCounterVec,
+    duration: HistogramVec,
+    response_size: HistogramVec,
+    in_flight: IntGauge,
+}
+
+static GLOBAL: LazyLock<MetricsRegistry> = LazyLock::new(MetricsRegistry::new);
+
+impl MetricsRegistry {
+    /// 创建独立的注册表并注册内置指标
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "Total number of HTTP requests"),
+            &["method", "route", "status"],
+        )
+        .unwrap();
+        let duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency until response headers are sent",
+            ),
+            &["method", "route"],
+        )
+        .unwrap();
+        let response_size = HistogramVec::new(
+            HistogramOpts::new("http_response_size_bytes", "HTTP response body size")
+                .buckets(prometheus::exponential_buckets(100.0, 10.0, 6).unwrap()),
+            &["method", "route"],
+        )
+        .unwrap();
+        let in_flight = IntGauge::new(
+            "http_requests_in_flight",
+            "Number of HTTP requests currently being served",
+        )
+        .unwrap();
+        registry.register(Box::new(requests.clone())).unwrap();
+        registry.register(Box::new(duration.clone())).unwrap();
+        registry.register(Box::new(response_size.clone())).unwrap();
+        registry.register(Box::new(in_flight.clone())).unwrap();
+        Self {
+            inner: Arc::new(RegistryInner {
+                registry,
+                requests,
+                duration,
+                response_size,
+                in_flight,
+            }),
+        }
+    }
+
+    /// 全局注册表，`#[dep]` 注入、[`MetricsLayer::new`] 与 [`metrics_handler`] 共用该实例
+    pub fn global() -> &'static MetricsRegistry {
+        &GLOBAL
+    }
+
+    /// 底层的 `prometheus::Registry`
+    pub fn registry(&self) -> &Registry {
+        &self.inner.registry
+    }
+
+    /// 注册自定义指标，返回指标本身便于保存；名称重复时返回错误
+    pub fn register<C>(&self, collector: C) -> prometheus::Result<C>
+    where
+        C: Collector + Clone + 'static,
+    {
+        self.inner.registry.register(Box::new(collector.clone()))?;
+        Ok(collector)
+    }
+
+    /// 以 Prometheus 文本格式导出所有指标
+    pub fn render(&self) -> String {
+        TextEncoder::new()
+            .encode_to_string(&self.inner.registry.gather())
+            .unwrap_or_else(|e| {
+                tracing::error!("failed to encode metrics: {}", e);
+                String::new()
+            })
+    }
+}
+
+impl IntoResponse for MetricsRegistry {
+    fn into_response(self) -> Resp {
+        Response::builder()
+            .header("content-type", TextEncoder::new().format_type())
+            .body(crate::http::response::into_response::bytes_to_boxed(
+                Bytes::from(self.render()),
+            ))
+            .unwrap()
+    }
+}
+
+/// 导出全局注册表的处理函数，通常挂载在 `/metrics`
+pub async fn metrics_handler() -> MetricsRegistry {
+    MetricsRegistry::global().clone()
+}
+
+/// 记录 HTTP 指标的中间件，需挂在 Router 上以读取命中的路由模式
+#[derive(Clone)]
+pub struct MetricsLayer {
+    registry: MetricsRegistry,
+}
+
+impl MetricsLayer {
+    /// 记录到全局注册表
+    pub fn new() -> Self {
+        Self::with_registry(MetricsRegistry::global().clone())
+    }
+
+    /// 记录到指定的注册表
+    pub fn with_registry(registry: MetricsRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<S> Layer<S> for MetricsLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = MetricsService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService {
+            inner: BoxCloneService::new(inner),
+            registry: self.registry.clone(),
+        }
+    }
+}
+
+/// [`MetricsLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct MetricsService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    registry: MetricsRegistry,
+}
+
+impl Service<Req> for MetricsService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let method = req.method().clone();
+        let in_flight = InFlight::start(&self.registry.inner.in_flight);
+        let start = Instant::now();
+        let future = self.inner.call(req);
+        let registry = self.registry.clone();
+        Box::pin(async move {
+            let resp = future.await.unwrap_or_else(|e| e.into_response());
+            let metrics = &registry.inner;
+            let route = resp
+                .extensions()
+                .get::<MatchedPath>()
+                .map(|p| p.0.clone())
+                .unwrap_or_else(|| Arc::from(UNMATCHED));
+            let labels = [method.as_str(), &route];
+            metrics
+                .requests
+                .with_label_values(&[method.as_str(), &route, resp.status().as_str()])
+                .inc();
+            metrics
+                .duration
+                .with_label_values(&labels)
+                .observe(start.elapsed().as_secs_f64());
+            let size = metrics.response_size.with_label_values(&labels);
+            let (parts, body) = resp.into_parts();
+            let body = SizedBody {
+                inner: body,
+                bytes: 0,
+                size,
+                _in_flight: in_flight,
+            };
+            Ok(Response::from_parts(parts, body.boxed_unsync()))
+        })
+    }
+}
+
+/// 请求结束（响应体写完或被丢弃）时减少 in-flight 计数
+struct InFlight(IntGauge);
+
+impl InFlight {
+    fn start(gauge: &IntGauge) -> Self {
+        gauge.inc();
+        Self(gauge.clone())
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+/// 统计实际写出的字节数，在 drop 时记录响应大小
+struct SizedBody {
+    inner: UnsyncBoxBody<Bytes, MikoError>,
+    bytes: u64,
+    size: Histogram,
+    _in_flight: InFlight,
+}
+
+impl Body for SizedBody {
+    type Data = Bytes;
+    type Error = MikoError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let polled = Pin::new(&mut self.inner).poll_frame(cx);
+        if let Poll::Ready(Some(Ok(frame))) = &polled
+            && let Some(data) = frame.data_ref()
+        {
+            self.bytes += data.len() as u64;
+        }
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for SizedBody {
+    fn drop(&mut self) {
+        self.size.observe(self.bytes as f64);
+    }
+}
+
+#[cfg(feature = "auto")]
+crate::inventory::submit! {
+    crate::dependency_container::DependencyDefFn(|| {
+        crate::dependency_container::DependencyDef {
+            type_id: std::any::TypeId::of::<MetricsRegistry>(),
+            type_name: std::any::type_name::<MetricsRegistry>(),
+            prewarm: false,
+            name: "___",
+            lifetime: crate::dependency_container::DependencyLifetime::Singleton,
+            deps: Vec::new,
+            source: concat!(file!(), ":", line!()),
+            replace: false,
+            init_fn: || {
+                Box::pin(async move {
+                    Arc::new(MetricsRegistry::global().clone())
+                        as Arc<dyn std::any::Any + Send + Sync>
+                })
+            },
+        }
+    })
+}
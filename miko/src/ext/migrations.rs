@@ -0,0 +1,309 @@
+//! 启动时执行数据库迁移
+//!
+//! [`Application::run`](crate::app::Application::run) 在监听端口前执行挂载的迁移；
+//! 迁移失败或在 `fail_on_pending` 模式下存在未执行的迁移时，服务不会启动。
+//!
+//! ```rust,ignore
+//! let pool = SqlitePool::connect("sqlite://app.db").await?;
+//! Application::new_(router)
+//!     .with_migrations(Migrations::new(SqlxMigrations::new(sqlx::migrate!(), pool)))
+//!     .run()
+//!     .await?;
+//! ```
+//!
+//! 缺省模式为自动执行，可通过配置修改：
+//!
+//! ```toml
+//! [migrations]
+//! mode = "fail_on_pending"   # apply | fail_on_pending | off
+//! ```
+//!
+//! 最近一次执行的结果可通过 [`migration_status`] 获取，用于健康检查。
+
+use crate::app::config::get_settings;
+use miko_core::BoxError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+/// 迁移适配器返回的 Future
+pub type MigrationFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Vec<String>, BoxError>> + Send + 'a>>;
+
+/// 启动时的迁移策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationMode {
+    /// 自动执行未执行的迁移
+    #[default]
+    Apply,
+    /// 只检查，存在未执行的迁移时拒绝启动
+    FailOnPending,
+    /// 跳过迁移
+    Off,
+}
+
+/// 迁移配置（`[migrations]`）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MigrationSettings {
+    pub mode: MigrationMode,
+}
+
+/// 迁移适配器：列出并执行未执行的迁移
+pub trait MigrationRunner: Send + Sync + 'static {
+    /// 未执行的迁移名，按执行顺序
+    fn pending(&self) -> MigrationFuture<'_>;
+
+    /// 执行所有未执行的迁移，返回本次执行的迁移名
+    fn apply(&self) -> MigrationFuture<'_>;
+}
+
+/// 迁移执行结果，可序列化后挂到健康检查中
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationStatus {
+    pub mode: MigrationMode,
+    /// 本次启动执行的迁移
+    pub applied: Vec<String>,
+    /// 仍未执行的迁移
+    pub pending: Vec<String>,
+    /// 迁移失败时的错误信息
+    pub error: Option<String>,
+}
+
+impl MigrationStatus {
+    /// 没有错误且没有未执行的迁移（`off` 模式下视为正常）
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && (self.mode == MigrationMode::Off || self.pending.is_empty())
+    }
+}
+
+/// 迁移失败或存在未执行的迁移
+#[derive(Debug)]
+pub enum MigrationError {
+    /// `fail_on_pending` 模式下存在未执行的迁移
+    Pending(Vec<String>),
+    /// 适配器返回的错误
+    Failed(BoxError),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Pending(names) => {
+                write!(f, "pending database migrations: {}", names.join(", "))
+            }
+            MigrationError::Failed(err) => write!(f, "database migration failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+impl From<MigrationError> for std::io::Error {
+    fn from(err: MigrationError) -> Self {
+        std::io::Error::other(err)
+    }
+}
+
+static STATUS: RwLock<Option<MigrationStatus>> = RwLock::new(None);
+
+/// 最近一次 [`Migrations::run`] 的结果，尚未执行时为 `None`
+pub fn migration_status() -> Option<MigrationStatus> {
+    STATUS.read().unwrap().clone()
+}
+
+/// 挂载到应用上的迁移钩子
+#[derive(Clone)]
+pub struct Migrations {
+    runner: Arc<dyn MigrationRunner>,
+    mode: MigrationMode,
+}
+
+impl Migrations {
+    /// 使用 `[migrations]` 配置中的模式
+    pub fn new(runner: impl MigrationRunner) -> Self {
+        let settings: MigrationSettings = get_settings().get("migrations").unwrap_or_default();
+        Self {
+            runner: Arc::new(runner),
+            mode: settings.mode,
+        }
+    }
+
+    /// 覆盖配置中的模式
+    pub fn mode(mut self, mode: MigrationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// 按模式检查或执行迁移，并记录到 [`migration_status`]
+    pub async fn run(&self) -> Result<MigrationStatus, MigrationError> {
+        let mut status = MigrationStatus {
+            mode: self.mode,
+            applied: Vec::new(),
+            pending: Vec::new(),
+            error: None,
+        };
+        let result = match self.mode {
+            MigrationMode::Off => Ok(()),
+            MigrationMode::FailOnPending => match self.runner.pending().await {
+                Ok(pending) if pending.is_empty() => Ok(()),
+                Ok(pending) => {
+                    status.pending = pending.clone();
+                    Err(MigrationError::Pending(pending))
+                }
+                Err(err) => Err(MigrationError::Failed(err)),
+            },
+            MigrationMode::Apply => match self.runner.apply().await {
+                Ok(applied) => {
+                    status.applied = applied;
+                    Ok(())
+                }
+                Err(err) => {
+                    // 失败时尽量记录仍未执行的迁移
+                    status.pending = self.runner.pending().await.unwrap_or_default();
+                    Err(MigrationError::Failed(err))
+                }
+            },
+        };
+        if let Err(err) = &result {
+            status.error = Some(err.to_string());
+            tracing::error!(error = %err, "database migrations failed");
+        } else if !status.applied.is_empty() {
+            tracing::info!(applied = ?status.applied, "database migrations applied");
+        }
+        *STATUS.write().unwrap() = Some(status.clone());
+        result.map(|_| status)
+    }
+}
+
+#[cfg(feature = "migrations-sqlx")]
+pub use self::sqlx_adapter::SqlxMigrations;
+
+#[cfg(feature = "migrations-sqlx")]
+mod sqlx_adapter {
+    use super::{MigrationFuture, MigrationRunner};
+    use sqlx::migrate::{Migrate, Migrator};
+    use sqlx::{Database, Pool};
+    use std::collections::HashSet;
+
+    /// 基于 `sqlx::migrate::Migrator` 的适配器
+    pub struct SqlxMigrations<DB: Database> {
+        migrator: Migrator,
+        pool: Pool<DB>,
+    }
+
+    impl<DB: Database> SqlxMigrations<DB> {
+        /// `migrator` 通常由 `sqlx::migrate!()` 生成
+        pub fn new(migrator: Migrator, pool: Pool<DB>) -> Self {
+            Self { migrator, pool }
+        }
+    }
+
+    impl<DB> SqlxMigrations<DB>
+    where
+        DB: Database,
+        DB::Connection: Migrate,
+    {
+        async fn pending_names(&self) -> Result<Vec<String>, sqlx::Error> {
+            let mut conn = self.pool.acquire().await?;
+            conn.ensure_migrations_table().await?;
+            let applied: HashSet<i64> = conn
+                .list_applied_migrations()
+                .await?
+                .into_iter()
+                .map(|m| m.version)
+                .collect();
+            Ok(self
+                .migrator
+                .iter()
+                .filter(|m| m.migration_type.is_up_migration() && !applied.contains(&m.version))
+                .map(|m| format!("{}_{}", m.version, m.description))
+                .collect())
+        }
+    }
+
+    impl<DB> MigrationRunner for SqlxMigrations<DB>
+    where
+        DB: Database,
+        DB::Connection: Migrate,
+    {
+        fn pending(&self) -> MigrationFuture<'_> {
+            Box::pin(async move { Ok(self.pending_names().await?) })
+        }
+
+        fn apply(&self) -> MigrationFuture<'_> {
+            Box::pin(async move {
+                let pending = self.pending_names().await?;
+                self.migrator.run(&self.pool).await?;
+                Ok(pending)
+            })
+        }
+    }
+}
+
+#[cfg(feature = "migrations-refinery")]
+pub use self::refinery_adapter::RefineryMigrations;
+
+#[cfg(feature = "migrations-refinery")]
+mod refinery_adapter {
+    use super::{MigrationFuture, MigrationRunner};
+    use refinery_core::{AsyncMigrate, Runner};
+    use std::collections::HashSet;
+    use tokio::sync::Mutex;
+
+    /// 基于 refinery `Runner` 的适配器，持有一个异步连接
+    pub struct RefineryMigrations<C> {
+        runner: Runner,
+        conn: Mutex<C>,
+    }
+
+    impl<C> RefineryMigrations<C> {
+        /// `runner` 通常由 `embed_migrations!()` 生成的 `migrations::runner()` 提供
+        pub fn new(runner: Runner, conn: C) -> Self {
+            Self {
+                runner,
+                conn: Mutex::new(conn),
+            }
+        }
+    }
+
+    impl<C> MigrationRunner for RefineryMigrations<C>
+    where
+        C: AsyncMigrate + Send + 'static,
+    {
+        fn pending(&self) -> MigrationFuture<'_> {
+            Box::pin(async move {
+                let mut conn = self.conn.lock().await;
+                let applied: HashSet<_> = self
+                    .runner
+                    .get_applied_migrations_async(&mut *conn)
+                    .await?
+                    .iter()
+                    .map(|m| m.version())
+                    .collect();
+                Ok(self
+                    .runner
+                    .get_migrations()
+                    .iter()
+                    .filter(|m| !applied.contains(&m.version()))
+                    .map(|m| m.to_string())
+                    .collect())
+            })
+        }
+
+        fn apply(&self) -> MigrationFuture<'_> {
+            Box::pin(async move {
+                let mut conn = self.conn.lock().await;
+                let report = self.runner.run_async(&mut *conn).await?;
+                Ok(report
+                    .applied_migrations()
+                    .iter()
+                    .map(|m| m.to_string())
+                    .collect())
+            })
+        }
+    }
+}
@@ -1,3 +1,29 @@
+pub mod auth;
+pub mod bench;
+pub mod cache;
+#[cfg(feature = "http-client")]
+pub mod client;
+pub mod compute;
 pub mod cors_any;
+pub mod download;
+pub mod embedded;
+pub mod health;
+pub mod janitor;
+pub mod mail;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "migrations")]
+pub mod migrations;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod rate_limit;
+pub mod realtime;
+#[cfg(feature = "redis")]
+pub mod redis;
+pub mod signed_url;
+#[cfg(feature = "sqlx")]
+pub mod sqlx;
 pub mod static_svc;
+#[cfg(feature = "templates")]
+pub mod templates;
 pub mod uploader;
@@ -0,0 +1,266 @@
+//! OpenTelemetry 链路追踪
+//!
+//! [`init`] 按 `[otel]` 配置创建 OTLP（HTTP/protobuf）导出器，并安装带 OpenTelemetry 的
+//! `tracing` 订阅器；[`OtelLayer`] 为每个请求创建服务端 span，并从 `traceparent` 头延续调用方的链路：
+//!
+//! ```rust,ignore
+//! #[tokio::main]
+//! async fn main() {
+//!     let _otel = miko::ext::otel::init(&OtelSettings::from_global_settings()).unwrap();
+//!     let mut router = Router::new();
+//!     router.with_layer(OtelLayer::new());
+//!     Application::new_(router).run().await.unwrap();
+//! }
+//! ```
+//!
+//! ```toml
+//! [otel]
+//! endpoint = "http://localhost:4318/v1/traces"   # 缺省值
+//! service_name = "orders"
+//! sample_ratio = 0.1                              # 只采样 10% 的新链路，已采样的上游链路照常延续
+//! headers = { authorization = "Bearer ..." }
+//! ```
+//!
+//! 已有自己的 `tracing` 订阅器时，改用 [`tracer_provider`] 与 [`layer`] 组合到订阅器中。
+
+use crate::app::config::get_settings;
+use crate::miko_core::{Req, Resp};
+use crate::router::MatchedPath;
+use crate::{AppError, AppResult, IntoResponse};
+use hyper::HeaderMap;
+use hyper::header::{HeaderName, HeaderValue};
+use miko_core::BoxError;
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+use opentelemetry_otlp::{WithExportConfig, WithHttpConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracer, SdkTracerProvider};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+use tracing::Instrument;
+use tracing::field::Empty;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Registry};
+
+/// 链路追踪配置（`[otel]`）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct OtelSettings {
+    /// OTLP/HTTP 导出地址
+    pub endpoint: String,
+    /// 上报的服务名
+    pub service_name: String,
+    /// 新链路的采样比例，0.0 ~ 1.0
+    pub sample_ratio: f64,
+    /// 发送给收集器的额外请求头，如认证信息
+    pub headers: HashMap<String, String>,
+}
+
+impl Default for OtelSettings {
+    fn default() -> Self {
+        Self {
+            endpoint: "http://localhost:4318/v1/traces".to_string(),
+            service_name: "miko".to_string(),
+            sample_ratio: 1.0,
+            headers: HashMap::new(),
+        }
+    }
+}
+
+impl OtelSettings {
+    /// 读取全局配置中的 `[otel]`
+    pub fn from_global_settings() -> Self {
+        get_settings().get("otel").unwrap_or_default()
+    }
+}
+
+/// 按配置创建导出到 OTLP 收集器的 `SdkTracerProvider`，并设为全局 provider
+///
+/// span 由后台线程批量导出，退出前应调用 `shutdown()` 以免丢失最后一批。
+pub fn tracer_provider(settings: &OtelSettings) -> Result<SdkTracerProvider, BoxError> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(settings.endpoint.clone())
+        .with_headers(settings.headers.clone())
+        .build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            settings.sample_ratio,
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(settings.service_name.clone())
+                .build(),
+        )
+        .build();
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    Ok(provider)
+}
+
+/// 将 `tracing` span 导出到 `provider` 的订阅器 layer
+pub fn layer<S>(provider: &SdkTracerProvider) -> OpenTelemetryLayer<S, SdkTracer>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer("miko"))
+}
+
+/// [`init`] 返回的句柄，drop 时导出剩余的 span 并关闭导出器
+pub struct OtelGuard {
+    provider: SdkTracerProvider,
+}
+
+impl OtelGuard {
+    pub fn provider(&self) -> &SdkTracerProvider {
+        &self.provider
+    }
+}
+
+impl Drop for OtelGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            tracing::error!(error = %e, "Failed to shut down OpenTelemetry tracer provider");
+        }
+    }
+}
+
+/// 创建导出器并安装全局 `tracing` 订阅器：按 `RUST_LOG`（缺省 `info`）过滤，输出到终端并导出到 OTLP
+///
+/// 已安装过全局订阅器时返回错误。
+pub fn init(settings: &OtelSettings) -> Result<OtelGuard, BoxError> {
+    let provider = tracer_provider(settings)?;
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    Registry::default()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(layer(&provider))
+        .try_init()?;
+    Ok(OtelGuard { provider })
+}
+
+/// 为每个请求创建 `server` 类型的 span，需挂在 Router 上以读取命中的路由模式
+///
+/// span 名为 `{method} {route}`，带有 `http.request.method`、`url.path`、`http.route`、
+/// `http.response.status_code` 等语义属性；请求带有 W3C `traceparent` 头时作为其子 span。
+/// 5xx 响应标记为错误。
+#[derive(Clone, Default)]
+pub struct OtelLayer;
+
+impl OtelLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for OtelLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = OtelService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        OtelService {
+            inner: BoxCloneService::new(inner),
+        }
+    }
+}
+
+/// [`OtelLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct OtelService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+}
+
+impl Service<Req> for OtelService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let method = req.method().clone();
+        let user_agent = req
+            .headers()
+            .get(hyper::header::USER_AGENT)
+            .and_then(|v| v.to_str().ok());
+        let span = tracing::info_span!(
+            "HTTP request",
+            otel.name = %method,
+            otel.kind = "server",
+            otel.status_code = Empty,
+            http.request.method = %method,
+            http.route = Empty,
+            http.response.status_code = Empty,
+            url.path = req.uri().path(),
+            url.query = req.uri().query(),
+            user_agent.original = user_agent,
+            network.protocol.version = ?req.version(),
+        );
+        let parent = TraceContextPropagator::new().extract(&HeaderExtractor(req.headers()));
+        let _ = span.set_parent(parent);
+
+        let future = self.inner.call(req).instrument(span.clone());
+        Box::pin(async move {
+            let resp = future.await.unwrap_or_else(|e| e.into_response());
+            if let Some(route) = resp.extensions().get::<MatchedPath>() {
+                span.record("http.route", route.as_str());
+                // span 已开始，`otel.name` 字段不再生效，直接修改 OpenTelemetry span
+                span.context()
+                    .span()
+                    .update_name(format!("{} {}", method, route.as_str()));
+            }
+            // 以 i64 记录，u64 会被导出为字符串
+            span.record("http.response.status_code", resp.status().as_u16() as i64);
+            if resp.status().is_server_error() {
+                span.record("otel.status_code", "ERROR");
+            }
+            Ok(resp)
+        })
+    }
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+/// 将当前 span 的链路上下文写入 W3C `traceparent` 头，用于向下游服务延续链路
+///
+/// 当前 span 未关联到有效的链路时不写入。
+pub fn inject_current(headers: &mut HeaderMap) {
+    let cx = tracing::Span::current().context();
+    TraceContextPropagator::new().inject_context(&cx, &mut HeaderInjector(headers));
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
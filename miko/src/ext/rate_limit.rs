@@ -0,0 +1,257 @@
+//! 请求限流
+//!
+//! [`RateLimitLayer`] 按客户端限制单位时间内的请求数，超出时返回 `429 Too Many Requests`
+//! 并附带 `Retry-After`。可作用于整个 Router，也可通过 `#[layer]` 只作用于单个路由：
+//!
+//! ```rust,ignore
+//! // 每个 IP 每秒 10 个请求，允许突发 20 个
+//! router.with_layer(RateLimitLayer::per_second(10).burst(20));
+//!
+//! #[post("/login")]
+//! #[layer(RateLimitLayer::per_minute(5).sliding_window())]
+//! async fn login(...) { ... }
+//!
+//! // 按 API key 限流；所有请求共享一个额度可用 `.global()`
+//! RateLimitLayer::per_minute(600)
+//!     .key(|req| req.headers().get("x-api-key")?.to_str().ok().map(str::to_string));
+//! ```
+//!
+//! 默认按对端 IP（[`ClientAddr`]）区分客户端，无法确定客户端的请求不受限制。
+
+use crate::extractor::ClientAddr;
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult, IntoResponse};
+use hyper::header::{HeaderValue, RETRY_AFTER};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+type KeyFn = Arc<dyn Fn(&Req) -> Option<String> + Send + Sync>;
+
+/// 客户端数超过该值时清理已恢复满额度的记录
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// 限流算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RateLimitAlgorithm {
+    /// 令牌桶：以固定速率补充令牌，允许不超过桶容量的突发
+    TokenBucket,
+    /// 滑动窗口：按上一窗口的剩余比例加权估算当前窗口内的请求数
+    SlidingWindow,
+}
+
+/// 单个客户端的限流状态
+#[derive(Debug, Clone, Copy)]
+enum Bucket {
+    Tokens {
+        tokens: f64,
+        updated: Instant,
+    },
+    Window {
+        start: Instant,
+        previous: u32,
+        current: u32,
+    },
+}
+
+/// 按客户端限制请求速率的中间件，超出时返回 `429` 与 `Retry-After`
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limit: u32,
+    period: Duration,
+    burst: u32,
+    algorithm: RateLimitAlgorithm,
+    key: KeyFn,
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+}
+
+impl RateLimitLayer {
+    /// 每个客户端在 `period` 内最多 `limit` 个请求
+    ///
+    /// # Panics
+    ///
+    /// `limit` 为 0 或 `period` 为零时 panic。
+    pub fn new(limit: u32, period: Duration) -> Self {
+        assert!(limit > 0, "rate limit must be greater than zero");
+        assert!(
+            !period.is_zero(),
+            "rate limit period must be greater than zero"
+        );
+        Self {
+            limit,
+            period,
+            burst: limit,
+            algorithm: RateLimitAlgorithm::TokenBucket,
+            key: Arc::new(|req: &Req| {
+                req.extensions()
+                    .get::<ClientAddr>()
+                    .map(|addr| addr.0.ip().to_string())
+            }),
+            buckets: Arc::default(),
+        }
+    }
+
+    /// 每秒最多 `limit` 个请求
+    pub fn per_second(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(1))
+    }
+
+    /// 每分钟最多 `limit` 个请求
+    pub fn per_minute(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(60))
+    }
+
+    /// 令牌桶容量，即允许的最大突发请求数，默认等于 `limit`
+    pub fn burst(mut self, burst: u32) -> Self {
+        assert!(burst > 0, "rate limit burst must be greater than zero");
+        self.burst = burst;
+        self
+    }
+
+    /// 改用滑动窗口算法，不允许超过 `limit` 的突发
+    pub fn sliding_window(mut self) -> Self {
+        self.algorithm = RateLimitAlgorithm::SlidingWindow;
+        self
+    }
+
+    /// 从请求中取出客户端标识，返回 `None` 的请求不受限制
+    pub fn key(mut self, key: impl Fn(&Req) -> Option<String> + Send + Sync + 'static) -> Self {
+        self.key = Arc::new(key);
+        self
+    }
+
+    /// 所有请求共享同一份额度
+    pub fn global(self) -> Self {
+        self.key(|_| Some(String::new()))
+    }
+
+    /// 为 `key` 消耗一次额度，超出时返回需要等待的时间
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| !self.is_idle(bucket, now));
+        }
+        let bucket = buckets
+            .entry(key.to_string())
+            .or_insert(match self.algorithm {
+                RateLimitAlgorithm::TokenBucket => Bucket::Tokens {
+                    tokens: self.burst as f64,
+                    updated: now,
+                },
+                RateLimitAlgorithm::SlidingWindow => Bucket::Window {
+                    start: now,
+                    previous: 0,
+                    current: 0,
+                },
+            });
+        match bucket {
+            Bucket::Tokens { tokens, updated } => {
+                let rate = self.limit as f64 / self.period.as_secs_f64();
+                *tokens = (*tokens + now.duration_since(*updated).as_secs_f64() * rate)
+                    .min(self.burst as f64);
+                *updated = now;
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    Ok(())
+                } else {
+                    Err(Duration::from_secs_f64((1.0 - *tokens) / rate))
+                }
+            }
+            Bucket::Window {
+                start,
+                previous,
+                current,
+            } => {
+                let windows = (now.duration_since(*start).as_secs_f64() / self.period.as_secs_f64())
+                    .floor() as u32;
+                if windows > 0 {
+                    *previous = if windows == 1 { *current } else { 0 };
+                    *current = 0;
+                    *start += self.period * windows;
+                }
+                let elapsed = now.duration_since(*start).as_secs_f64() / self.period.as_secs_f64();
+                let estimate = *previous as f64 * (1.0 - elapsed) + *current as f64;
+                if estimate + 1.0 <= self.limit as f64 {
+                    *current += 1;
+                    return Ok(());
+                }
+                // 等待上一窗口的权重降到足以放行一个请求，当前窗口已满则等到下一窗口
+                let free = self.limit as f64 - *current as f64 - 1.0;
+                let until = if free >= 0.0 && *previous > 0 {
+                    1.0 - free / *previous as f64
+                } else {
+                    1.0
+                };
+                Err(self.period.mul_f64((until - elapsed).max(0.0)))
+            }
+        }
+    }
+
+    /// 额度已完全恢复，记录可以丢弃
+    fn is_idle(&self, bucket: &Bucket, now: Instant) -> bool {
+        match *bucket {
+            Bucket::Tokens { tokens, updated } => {
+                let rate = self.limit as f64 / self.period.as_secs_f64();
+                tokens + now.duration_since(updated).as_secs_f64() * rate >= self.burst as f64
+            }
+            Bucket::Window { start, .. } => now.duration_since(start) >= self.period * 2,
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = RateLimitService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        }
+    }
+}
+
+/// [`RateLimitLayer`] 生成的 Service
+#[derive(Clone)]
+pub struct RateLimitService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: RateLimitLayer,
+}
+
+impl Service<Req> for RateLimitService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let Some(key) = (self.layer.key)(&req) else {
+            return Box::pin(self.inner.call(req));
+        };
+        match self.layer.check(&key) {
+            Ok(()) => Box::pin(self.inner.call(req)),
+            Err(wait) => {
+                tracing::debug!(client = %key, limit = self.layer.limit, "rate limit exceeded");
+                let mut resp =
+                    AppError::TooManyRequests("Rate limit exceeded".to_string()).into_response();
+                // Retry-After 以整秒表示，向上取整
+                let secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+                resp.headers_mut()
+                    .insert(RETRY_AFTER, HeaderValue::from(secs.max(1)));
+                Box::pin(async move { Ok(resp) })
+            }
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! 统一的实时推送（SSE + WebSocket）
+//!
+//! 处理函数向命名 topic 发布消息，订阅端通过同一个挂载点接入：
+//! 普通 GET 请求走 SSE，WebSocket 升级请求走 WS，两者共享同一套分发逻辑。
+//!
+//! ```rust,ignore
+//! router.realtime("/events");
+//!
+//! #[post("/news")]
+//! async fn publish(body: String) {
+//!     Realtime::global().publish("news", body);
+//! }
+//! // 订阅：GET /events?topic=news&topic=alerts
+//! ```
+
+use crate::handler::{Req, Resp};
+use crate::http::response::into_response::IntoResponse;
+use crate::http::response::sse::{SseEvent, spawn_sse_event};
+use crate::ws::server::{WsMessage, WsOptions, spawn_ws_event_with};
+use crate::ws::toolkit::is_upgrade_request;
+use crate::{AppError, AppResult};
+use hyper::http::request::Parts;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use tokio::sync::{broadcast, mpsc};
+
+/// 每个 topic 的默认缓冲消息数，订阅端落后超过该数量会丢弃旧消息
+pub const DEFAULT_CAPACITY: usize = 64;
+
+/// topic 授权回调，参数为 topic 名与订阅请求的 Parts，返回 false 则拒绝订阅
+pub type TopicAuth = Arc<dyn Fn(&str, &Parts) -> bool + Send + Sync>;
+
+/// 推送给订阅端的一条消息
+#[derive(Debug, Clone, Serialize)]
+pub struct RealtimeMessage {
+    /// 消息序号，同一个 Realtime 内单调递增
+    pub id: u64,
+    pub topic: String,
+    /// 可选的事件名，SSE 下作为 `event:` 字段，缺省为 topic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    pub data: String,
+}
+
+impl RealtimeMessage {
+    fn to_sse_event(&self) -> SseEvent {
+        SseEvent::data(self.data.clone())
+            .event(self.event.clone().unwrap_or_else(|| self.topic.clone()))
+            .id(self.id.to_string())
+    }
+}
+
+/// topic 发布/订阅中心
+#[derive(Clone)]
+pub struct Realtime {
+    inner: Arc<RealtimeInner>,
+}
+
+struct RealtimeInner {
+    topics: Mutex<HashMap<String, broadcast::Sender<RealtimeMessage>>>,
+    auth: RwLock<Vec<(String, TopicAuth)>>,
+    capacity: usize,
+    seq: AtomicU64,
+}
+
+static GLOBAL: LazyLock<Realtime> = LazyLock::new(Realtime::new);
+
+impl Realtime {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// 指定每个 topic 的缓冲消息数
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(RealtimeInner {
+                topics: Mutex::new(HashMap::new()),
+                auth: RwLock::new(Vec::new()),
+                capacity: capacity.max(1),
+                seq: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// 全局实例，[`Router::realtime`](crate::router::Router::realtime) 默认使用它
+    pub fn global() -> &'static Realtime {
+        &GLOBAL
+    }
+
+    /// 为 topic 注册授权回调
+    ///
+    /// `pattern` 为精确的 topic 名，或以 `*` 结尾的前缀（如 `user.*`）；
+    /// 一个 topic 命中的所有回调都必须通过
+    pub fn authorize<F>(&self, pattern: impl Into<String>, f: F) -> &Self
+    where
+        F: Fn(&str, &Parts) -> bool + Send + Sync + 'static,
+    {
+        self.inner
+            .auth
+            .write()
+            .unwrap()
+            .push((pattern.into(), Arc::new(f)));
+        self
+    }
+
+    /// 向 topic 发布消息，返回当前收到该消息的订阅端数量
+    pub fn publish(&self, topic: &str, data: impl Into<String>) -> usize {
+        self.send(topic, None, data.into())
+    }
+
+    /// 向 topic 发布带事件名的消息
+    pub fn publish_event(
+        &self,
+        topic: &str,
+        event: impl Into<String>,
+        data: impl Into<String>,
+    ) -> usize {
+        self.send(topic, Some(event.into()), data.into())
+    }
+
+    /// 以 JSON 形式发布消息
+    pub fn publish_json<T: Serialize>(&self, topic: &str, data: &T) -> AppResult<usize> {
+        Ok(self.publish(topic, serde_json::to_string(data)?))
+    }
+
+    /// topic 当前的订阅端数量
+    pub fn subscribers(&self, topic: &str) -> usize {
+        self.inner
+            .topics
+            .lock()
+            .unwrap()
+            .get(topic)
+            .map(|tx| tx.receiver_count())
+            .unwrap_or(0)
+    }
+
+    /// 直接订阅一个 topic（不经过授权），用于进程内消费
+    pub fn subscribe(&self, topic: &str) -> broadcast::Receiver<RealtimeMessage> {
+        self.sender(topic).subscribe()
+    }
+
+    fn send(&self, topic: &str, event: Option<String>, data: String) -> usize {
+        let tx = {
+            let topics = self.inner.topics.lock().unwrap();
+            match topics.get(topic) {
+                Some(tx) => tx.clone(),
+                // 无人订阅的 topic 不保留消息
+                None => return 0,
+            }
+        };
+        let msg = RealtimeMessage {
+            id: self.inner.seq.fetch_add(1, Ordering::Relaxed) + 1,
+            topic: topic.to_string(),
+            event,
+            data,
+        };
+        tx.send(msg).unwrap_or(0)
+    }
+
+    fn sender(&self, topic: &str) -> broadcast::Sender<RealtimeMessage> {
+        let mut topics = self.inner.topics.lock().unwrap();
+        // 顺带清理已无订阅端的 topic
+        topics.retain(|_, tx| tx.receiver_count() > 0);
+        topics
+            .entry(topic.to_string())
+            .or_insert_with(|| broadcast::channel(self.inner.capacity).0)
+            .clone()
+    }
+
+    fn is_authorized(&self, topic: &str, parts: &Parts) -> bool {
+        self.inner
+            .auth
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(pattern, _)| match pattern.strip_suffix('*') {
+                Some(prefix) => topic.starts_with(prefix),
+                None => pattern == topic,
+            })
+            .all(|(_, auth)| auth(topic, parts))
+    }
+
+    /// 处理一个订阅请求，topic 取自查询参数 `topic`（可重复）
+    pub async fn handle(&self, req: Req) -> AppResult<Resp> {
+        let (parts, body) = req.into_parts();
+        let query = parts.uri.query().unwrap_or_default();
+        let topics: Vec<String> = serde_urlencoded::from_str::<Vec<(String, String)>>(query)?
+            .into_iter()
+            .filter(|(k, _)| k == "topic")
+            .map(|(_, v)| v)
+            .collect();
+        if topics.is_empty() {
+            return Err(AppError::BadRequest(
+                "at least one `topic` query parameter is required".to_string(),
+            ));
+        }
+        if let Some(denied) = topics.iter().find(|t| !self.is_authorized(t, &parts)) {
+            return Err(AppError::Forbidden(format!(
+                "not allowed to subscribe to topic `{}`",
+                denied
+            )));
+        }
+        let receivers: Vec<_> = topics.iter().map(|t| self.subscribe(t)).collect();
+
+        let mut req = Req::from_parts(parts, body);
+        if is_upgrade_request(&req) {
+            return spawn_ws_event_with(
+                move |socket| async move {
+                    let (mut sender, mut receiver, _) = socket.split();
+                    let (tx, mut rx) = mpsc::channel::<RealtimeMessage>(DEFAULT_CAPACITY);
+                    forward(receivers, tx);
+                    loop {
+                        tokio::select! {
+                            msg = rx.recv() => {
+                                let Some(msg) = msg else { break };
+                                let text = serde_json::to_string(&msg).unwrap_or_default();
+                                if sender.send(text).await.is_err() {
+                                    break;
+                                }
+                            }
+                            incoming = receiver.recv() => match incoming {
+                                Some(Ok(WsMessage::Close(_))) | None | Some(Err(_)) => break,
+                                // 订阅端只接收，忽略其发来的数据
+                                Some(Ok(_)) => {}
+                            }
+                        }
+                    }
+                },
+                &mut req,
+                WsOptions::new(),
+            )
+            .map_err(|e| AppError::BadRequest(e.to_string()));
+        }
+
+        Ok(spawn_sse_event(move |sender| async move {
+            let (tx, mut rx) = mpsc::channel::<RealtimeMessage>(DEFAULT_CAPACITY);
+            forward(receivers, tx);
+            while let Some(msg) = rx.recv().await {
+                if sender.inner().send(msg.to_sse_event()).await.is_err() {
+                    break;
+                }
+            }
+        })
+        .into_response())
+    }
+}
+
+impl Default for Realtime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 把多个 topic 的广播汇入同一个管道，管道关闭时各转发任务随之退出
+fn forward(
+    receivers: Vec<broadcast::Receiver<RealtimeMessage>>,
+    tx: mpsc::Sender<RealtimeMessage>,
+) {
+    for mut rx in receivers {
+        let tx = tx.clone();
+        crate::spawn_linked("realtime subscriber", async move {
+            loop {
+                tokio::select! {
+                    msg = rx.recv() => match msg {
+                        Ok(msg) => {
+                            if tx.send(msg).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            tracing::warn!(skipped = n, "realtime subscriber lagged");
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = tx.closed() => break,
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,227 @@
+//! Redis 客户端组件
+//!
+//! 按 `[redis]` 配置创建连接，`#[dep]` 可以注入 [`Redis`] 或 `redis::aio::ConnectionManager`：
+//!
+//! ```toml
+//! [redis]
+//! url = "redis://127.0.0.1:6379/0"
+//! connection_timeout_ms = 5000
+//! response_timeout_ms = 5000
+//! ```
+//!
+//! ```rust,ignore
+//! #[post("/visits")]
+//! async fn visit(#[dep] redis: Arc<Redis>) -> AppResult<String> {
+//!     let mut conn = redis.connection().await?;
+//!     let count: u64 = conn.incr("visits", 1).await?;
+//!     Ok(count.to_string())
+//! }
+//! ```
+//!
+//! [`Redis`] 在首次使用时才建立连接，注入 `ConnectionManager` 时则立即连接，连接失败会 panic。
+//! [`RedisStore`] 可作为 [`Cache`](crate::ext::cache::Cache) 的存储，配置 `cache.store = "redis"` 即可。
+
+use crate::AppResult;
+use crate::app::config::get_settings;
+use crate::ext::cache::{CacheFuture, CacheStore};
+use crate::ext::health::HealthRegistry;
+use ::redis::AsyncCommands;
+use ::redis::aio::{ConnectionManager, ConnectionManagerConfig};
+use serde::Deserialize;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+/// Redis 配置（`[redis]`）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RedisSettings {
+    pub url: String,
+    /// 建立连接的超时时间
+    pub connection_timeout_ms: u64,
+    /// 单条命令的超时时间
+    pub response_timeout_ms: u64,
+}
+
+impl Default for RedisSettings {
+    fn default() -> Self {
+        Self {
+            url: "redis://127.0.0.1:6379/".to_string(),
+            connection_timeout_ms: 5_000,
+            response_timeout_ms: 5_000,
+        }
+    }
+}
+
+/// Redis 客户端，可通过 `#[dep] redis: Arc<Redis>` 注入
+///
+/// 克隆后共享同一个自动重连的连接。
+#[derive(Clone)]
+pub struct Redis {
+    client: ::redis::Client,
+    config: ConnectionManagerConfig,
+    conn: Arc<OnceCell<ConnectionManager>>,
+}
+
+static GLOBAL: LazyLock<Redis> = LazyLock::new(|| {
+    let redis = Redis::from_global_settings();
+    let checked = redis.clone();
+    HealthRegistry::global().register("redis", move || {
+        let redis = checked.clone();
+        async move { redis.ping().await }
+    });
+    redis
+});
+
+impl Redis {
+    /// 按配置创建，`url` 无法解析时 panic
+    pub fn from_settings(settings: RedisSettings) -> Self {
+        let client = ::redis::Client::open(settings.url.as_str())
+            .unwrap_or_else(|e| panic!("invalid `redis.url` {:?}: {}", settings.url, e));
+        let config = ConnectionManagerConfig::new()
+            .set_connection_timeout(Duration::from_millis(settings.connection_timeout_ms))
+            .set_response_timeout(Duration::from_millis(settings.response_timeout_ms));
+        Self {
+            client,
+            config,
+            conn: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// 读取 `[redis]` 配置创建
+    pub fn from_global_settings() -> Self {
+        let settings: RedisSettings = get_settings().get("redis").unwrap_or_default();
+        Self::from_settings(settings)
+    }
+
+    /// 全局实例，`#[dep]` 注入使用该实例，首次访问时注册 `redis` 健康检查
+    pub fn global() -> &'static Redis {
+        &GLOBAL
+    }
+
+    pub fn client(&self) -> &::redis::Client {
+        &self.client
+    }
+
+    /// 共享的连接，首次调用时建立；克隆的开销很小
+    pub async fn connection(&self) -> AppResult<ConnectionManager> {
+        let conn = self
+            .conn
+            .get_or_try_init(|| {
+                ConnectionManager::new_with_config(self.client.clone(), self.config.clone())
+            })
+            .await?;
+        Ok(conn.clone())
+    }
+
+    /// 发送 `PING`
+    pub async fn ping(&self) -> AppResult<()> {
+        let mut conn = self.connection().await?;
+        ::redis::cmd("PING")
+            .query_async::<String>(&mut conn)
+            .await?;
+        Ok(())
+    }
+}
+
+/// 基于 Redis 的缓存存储
+#[derive(Clone)]
+pub struct RedisStore {
+    redis: Redis,
+}
+
+impl RedisStore {
+    pub fn new(redis: Redis) -> Self {
+        Self { redis }
+    }
+}
+
+impl CacheStore for RedisStore {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move {
+            let mut conn = self.redis.connection().await?;
+            Ok(conn.get(key).await?)
+        })
+    }
+
+    fn set<'a>(
+        &'a self,
+        key: &'a str,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> CacheFuture<'a, ()> {
+        Box::pin(async move {
+            let mut conn = self.redis.connection().await?;
+            match ttl {
+                Some(ttl) => {
+                    let ms = (ttl.as_millis() as u64).max(1);
+                    conn.pset_ex::<_, _, ()>(key, value, ms).await?
+                }
+                None => conn.set::<_, _, ()>(key, value).await?,
+            }
+            Ok(())
+        })
+    }
+
+    fn expire<'a>(&'a self, key: &'a str, ttl: Duration) -> CacheFuture<'a, bool> {
+        Box::pin(async move {
+            let mut conn = self.redis.connection().await?;
+            let ms = (ttl.as_millis() as i64).max(1);
+            Ok(conn.pexpire(key, ms).await?)
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> CacheFuture<'a, bool> {
+        Box::pin(async move {
+            let mut conn = self.redis.connection().await?;
+            let removed: usize = conn.del(key).await?;
+            Ok(removed > 0)
+        })
+    }
+}
+
+#[cfg(feature = "auto")]
+crate::inventory::submit! {
+    crate::dependency_container::DependencyDefFn(|| {
+        crate::dependency_container::DependencyDef {
+            type_id: std::any::TypeId::of::<Redis>(),
+            type_name: std::any::type_name::<Redis>(),
+            prewarm: false,
+            name: "___",
+            lifetime: crate::dependency_container::DependencyLifetime::Singleton,
+            deps: Vec::new,
+            source: concat!(file!(), ":", line!()),
+            replace: false,
+            init_fn: || {
+                Box::pin(async move {
+                    Arc::new(Redis::global().clone()) as Arc<dyn std::any::Any + Send + Sync>
+                })
+            },
+        }
+    })
+}
+
+#[cfg(feature = "auto")]
+crate::inventory::submit! {
+    crate::dependency_container::DependencyDefFn(|| {
+        crate::dependency_container::DependencyDef {
+            type_id: std::any::TypeId::of::<ConnectionManager>(),
+            type_name: std::any::type_name::<ConnectionManager>(),
+            prewarm: false,
+            name: "___",
+            lifetime: crate::dependency_container::DependencyLifetime::Singleton,
+            deps: Vec::new,
+            source: concat!(file!(), ":", line!()),
+            replace: false,
+            init_fn: || {
+                Box::pin(async move {
+                    let conn = Redis::global()
+                        .connection()
+                        .await
+                        .unwrap_or_else(|e| panic!("failed to connect to redis: {}", e));
+                    Arc::new(conn) as Arc<dyn std::any::Any + Send + Sync>
+                })
+            },
+        }
+    })
+}
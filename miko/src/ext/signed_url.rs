@@ -0,0 +1,177 @@
+//! 带时效的签名 URL
+//!
+//! 为私有内容（如文件下载）生成带过期时间与 HMAC-SHA256 签名的链接，
+//! 处理函数通过 [`SignedUrl`] 提取器在执行前校验签名与有效期。
+//!
+//! ```rust,ignore
+//! UrlSigner::new("a-long-random-secret").install();
+//!
+//! #[get("/share/{id}")]
+//! async fn share(#[path] id: u64) -> AppResult<String> {
+//!     Ok(UrlSigner::global()?.sign(&format!("/files/{id}"), Duration::from_secs(600)))
+//! }
+//!
+//! #[get("/files/{id}")]
+//! async fn download(_signed: SignedUrl, #[path] id: u64) -> AppResult<Resp> { ... }
+//! ```
+//!
+//! 签名覆盖路径与除 `signature` 外的全部查询参数，修改任意一处都会导致校验失败。
+//! 路由挂载在 `nest_service` 之下时，签名时需使用去掉前缀后的路径。
+
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use crate::{AppError, AppResult};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use hyper::Uri;
+use hyper::http::request::Parts;
+use sha2::Sha256;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 过期时间参数名（Unix 秒）
+pub const EXPIRES_PARAM: &str = "expires";
+/// 签名参数名
+pub const SIGNATURE_PARAM: &str = "signature";
+
+static GLOBAL: OnceLock<UrlSigner> = OnceLock::new();
+
+/// 签名校验失败的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureError {
+    /// 缺少 `expires` 或 `signature` 参数
+    Missing,
+    /// 签名不匹配
+    Invalid,
+    /// 链接已过期
+    Expired,
+}
+
+impl std::fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SignatureError::Missing => "missing url signature",
+            SignatureError::Invalid => "invalid url signature",
+            SignatureError::Expired => "url signature expired",
+        })
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+/// URL 签名器
+#[derive(Clone)]
+pub struct UrlSigner {
+    key: Arc<[u8]>,
+}
+
+impl UrlSigner {
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: Arc::from(secret.as_ref()),
+        }
+    }
+
+    /// 设为全局签名器，只有第一次调用生效
+    pub fn install(self) -> bool {
+        GLOBAL.set(self).is_ok()
+    }
+
+    /// 全局签名器，未安装时从配置项 `signed_url.secret` 创建
+    pub fn global() -> AppResult<&'static UrlSigner> {
+        if let Some(signer) = GLOBAL.get() {
+            return Ok(signer);
+        }
+        let secret: String = crate::app::config::get_settings()
+            .get("signed_url.secret")
+            .map_err(|_| {
+                AppError::InternalServerError(
+                    "url signer is not configured, call UrlSigner::install or set `signed_url.secret`"
+                        .to_string(),
+                )
+            })?;
+        Ok(GLOBAL.get_or_init(|| UrlSigner::new(secret)))
+    }
+
+    /// 为 `url`（路径，可带查询参数）生成 `ttl` 后过期的签名链接
+    pub fn sign(&self, url: &str, ttl: Duration) -> String {
+        self.sign_until(url, SystemTime::now() + ttl)
+    }
+
+    /// 为 `url` 生成在 `expires` 时刻过期的签名链接
+    pub fn sign_until(&self, url: &str, expires: SystemTime) -> String {
+        let expires = expires
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let sep = if url.contains('?') { '&' } else { '?' };
+        let unsigned = format!("{}{}{}={}", url, sep, EXPIRES_PARAM, expires);
+        let signature = self.signature(&unsigned);
+        format!("{}&{}={}", unsigned, SIGNATURE_PARAM, signature)
+    }
+
+    /// 校验请求 URI 的签名与有效期，成功时返回过期时间
+    pub fn verify(&self, uri: &Uri) -> Result<SystemTime, SignatureError> {
+        let query = uri.query().ok_or(SignatureError::Missing)?;
+        let mut signature = None;
+        let mut expires = None;
+        let mut rest = Vec::new();
+        for pair in query.split('&') {
+            match pair.split_once('=') {
+                Some((SIGNATURE_PARAM, v)) => signature = Some(v),
+                Some((EXPIRES_PARAM, v)) => {
+                    expires = v.parse::<u64>().ok();
+                    rest.push(pair);
+                }
+                _ => rest.push(pair),
+            }
+        }
+        let (Some(signature), Some(expires)) = (signature, expires) else {
+            return Err(SignatureError::Missing);
+        };
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| SignatureError::Invalid)?;
+        let unsigned = format!("{}?{}", uri.path(), rest.join("&"));
+        self.mac(&unsigned)
+            .verify_slice(&signature)
+            .map_err(|_| SignatureError::Invalid)?;
+
+        let expires = UNIX_EPOCH + Duration::from_secs(expires);
+        if SystemTime::now() > expires {
+            return Err(SignatureError::Expired);
+        }
+        Ok(expires)
+    }
+
+    fn mac(&self, data: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts any key length");
+        mac.update(data.as_bytes());
+        mac
+    }
+
+    fn signature(&self, data: &str) -> String {
+        URL_SAFE_NO_PAD.encode(self.mac(data).finalize().into_bytes())
+    }
+}
+
+/// 已通过签名校验的请求，校验失败时返回 403
+#[derive(Debug, Clone, Copy)]
+pub struct SignedUrl {
+    /// 链接的过期时间
+    pub expires: SystemTime,
+}
+
+impl<S> FromRequestParts<S> for SignedUrl {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let result = UrlSigner::global().and_then(|signer| {
+            signer
+                .verify(&req.uri)
+                .map(|expires| SignedUrl { expires })
+                .map_err(|e| AppError::Forbidden(e.to_string()))
+        });
+        Box::pin(async move { result })
+    }
+}
@@ -1,20 +1,21 @@
 use crate::AppError;
-use crate::http::response::into_response::{IntoResponse, bytes_to_boxed};
+use crate::ext::download::{RangeRequest, ResumableDownload};
+use crate::extractor::Json;
+use crate::extractor::typed_header::{Accept, Header};
+use crate::http::response::into_response::{Html, IntoResponse};
 use crate::router::HttpSvc;
-use bytes::Bytes;
-use http_body_util::BodyExt;
-use hyper::{HeaderMap, Method, Response, StatusCode, header};
-use miko_core::fallible_stream_body::FallibleStreamBody;
+use crate::utils::escape_html;
+use hyper::header::{self, HeaderValue};
+use hyper::{HeaderMap, Method, StatusCode};
 use miko_core::{Req, Resp, decode_path};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::future::Future;
-use std::io::SeekFrom;
 use std::path::{Component, Path, PathBuf};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use tokio::fs::File;
-use tokio::io::{AsyncReadExt, AsyncSeekExt};
-use tokio_util::io::ReaderStream;
 use tower::util::BoxCloneService;
 use tower::{Layer, Service};
 use tower_http::cors::CorsLayer;
@@ -26,7 +27,30 @@ pub struct StaticSvc {
     pub spa_fallback: bool,
     pub fallback_files: Arc<Vec<String>>,
     pub index_files: Arc<Vec<String>>,
+    /// 优先返回预压缩的 `.br` / `.gz` 文件
+    pub precompressed: bool,
+    /// 以 `multipart/byteranges` 响应多范围请求
+    pub multi_range: bool,
+    /// 按扩展名（小写、不含点）设置的 `Cache-Control`
+    pub cache_control: Arc<HashMap<String, HeaderValue>>,
+    /// 目录下没有索引文件时列出目录内容
+    pub directory_listing: bool,
 }
+
+/// 目录列表中的一项
+#[derive(Serialize)]
+struct ListingEntry {
+    name: String,
+    is_dir: bool,
+    /// 文件大小，目录为 `None`
+    size: Option<u64>,
+    /// 最后修改时间（HTTP 日期格式）
+    modified: Option<String>,
+}
+
+/// 预压缩文件的扩展名与对应的 `Content-Encoding`，同等优先级时靠前者优先
+const PRECOMPRESSED: [(&str, &str); 2] = [("br", "br"), ("gz", "gzip")];
+
 impl StaticSvc {
     /// 构建一个静态服务的 Builder
     pub fn builder(root: impl Into<PathBuf>) -> StaticSvcBuilder {
@@ -68,70 +92,124 @@ impl StaticSvc {
         None
     }
 
-    /// 解析 Range 头
-    /// 返回: Ok(Some((start, end))) - 合法范围
-    ///       Ok(None) - 无 Range 或格式不支持
-    ///       Err(()) - Range 超出文件范围 (应返回 416)
-    fn parse_range(range_header: &str, file_size: u64) -> Result<Option<(u64, u64)>, ()> {
-        if !range_header.starts_with("bytes=") {
-            return Ok(None);
+    /// 按 `Accept-Encoding` 查找存在的预压缩文件
+    async fn precompressed_variant(
+        path: &Path,
+        headers: &HeaderMap,
+    ) -> Option<(PathBuf, &'static str)> {
+        for (ext, encoding) in accepted_encodings(headers) {
+            let mut variant = path.as_os_str().to_owned();
+            variant.push(".");
+            variant.push(ext);
+            let variant = PathBuf::from(variant);
+            if tokio::fs::metadata(&variant)
+                .await
+                .is_ok_and(|m| m.is_file())
+            {
+                return Some((variant, encoding));
+            }
         }
+        None
+    }
 
-        let range_str = &range_header[6..];
-
-        // 暂不支持多范围请求 (如 bytes=0-99,200-299)
-        if range_str.contains(',') {
-            return Ok(None);
+    /// 列出目录内容，`Accept` 偏好 JSON 时返回 JSON，否则返回 HTML；隐藏文件不列出
+    async fn render_listing(
+        dir: &Path,
+        uri_path: &str,
+        headers: &HeaderMap,
+    ) -> Result<Resp, std::io::Error> {
+        let mut entries = Vec::new();
+        let mut read_dir = tokio::fs::read_dir(dir).await?;
+        while let Some(entry) = read_dir.next_entry().await? {
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if name.starts_with('.') {
+                continue;
+            }
+            let Ok(metadata) = tokio::fs::metadata(entry.path()).await else {
+                continue;
+            };
+            entries.push(ListingEntry {
+                name,
+                is_dir: metadata.is_dir(),
+                size: (!metadata.is_dir()).then_some(metadata.len()),
+                modified: metadata.modified().ok().map(httpdate::fmt_http_date),
+            });
         }
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
 
-        let parts: Vec<&str> = range_str.split('-').collect();
-        if parts.len() != 2 {
-            return Ok(None);
+        let wants_json = headers
+            .get(header::ACCEPT)
+            .and_then(|v| Accept::decode(v).ok())
+            .and_then(|accept| {
+                accept
+                    .preferred()
+                    .map(|m| m.essence_str() == "application/json")
+            })
+            .unwrap_or(false);
+        if wants_json {
+            return Ok(Json(entries).into_response());
         }
 
-        // 处理 bytes=-500 (最后 500 字节) 的情况
-        if parts[0].is_empty() {
-            if let Ok(suffix_length) = parts[1].parse::<u64>() {
-                if suffix_length == 0 {
-                    return Err(());
-                }
-                let start = file_size.saturating_sub(suffix_length);
-                return Ok(Some((start, file_size - 1)));
-            }
-            return Ok(None);
+        // 请求路径不以 `/` 结尾时，相对链接需要带上目录名
+        let base = match uri_path.rsplit_once('/') {
+            Some((_, last)) if !last.is_empty() => format!("{}/", last),
+            _ => String::new(),
+        };
+        let title = escape_html(&decode_path(uri_path));
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Index of {0}</title></head>\n\
+             <body><h1>Index of {0}</h1>\n<table>\n\
+             <tr><th>Name</th><th>Size</th><th>Last modified</th></tr>\n",
+            title
+        );
+        if !uri_path.trim_matches('/').is_empty() {
+            html.push_str(&format!(
+                "<tr><td><a href=\"{}..\">../</a></td><td></td><td></td></tr>\n",
+                base
+            ));
         }
-
-        let start = parts[0].parse::<u64>().map_err(|_| ())?;
-        if start >= file_size {
-            return Err(());
+        for entry in &entries {
+            let suffix = if entry.is_dir { "/" } else { "" };
+            html.push_str(&format!(
+                "<tr><td><a href=\"{}{}{}\">{}{}</a></td><td>{}</td><td>{}</td></tr>\n",
+                base,
+                encode_segment(&entry.name),
+                suffix,
+                escape_html(&entry.name),
+                suffix,
+                entry.size.map(|s| s.to_string()).unwrap_or_default(),
+                entry.modified.as_deref().unwrap_or_default(),
+            ));
         }
-        let end = if parts[1].is_empty() {
-            // bytes=100- 表示从 100 到文件末尾
-            file_size - 1
-        } else {
-            // bytes=100-200
-            parts[1].parse::<u64>().map_err(|_| ())?.min(file_size - 1)
-        };
+        html.push_str("</table></body></html>\n");
+        Ok(Html(html).into_response())
+    }
 
-        if start <= end {
-            Ok(Some((start, end)))
-        } else {
-            Err(()) // start > end，无效范围
-        }
+    fn cache_control_for(&self, path: &Path) -> Option<&HeaderValue> {
+        cache_control_for(&self.cache_control, path)
     }
 
     async fn serve_file(
+        &self,
         path: &PathBuf,
         method: &Method,
         headers: &HeaderMap,
     ) -> Result<Resp, std::io::Error> {
-        let mime = mime_guess::from_path(path).first_or_octet_stream();
-        let content_type = if mime.type_() == mime_guess::mime::TEXT {
-            format!("{}; charset=utf-8", mime)
-        } else {
-            mime.to_string()
+        let content_type = content_type_for(path);
+        let (file_path, encoding) = match self.precompressed {
+            true => match Self::precompressed_variant(path, headers).await {
+                Some((variant, encoding)) => (variant, Some(encoding)),
+                None => (path.clone(), None),
+            },
+            false => (path.clone(), None),
         };
-        let metadata = tokio::fs::metadata(path).await?;
+        let file = File::open(&file_path).await?;
+        let metadata = file.metadata().await?;
+        if metadata.is_dir() {
+            return Err(std::io::Error::from(std::io::ErrorKind::NotFound));
+        }
         let file_size = metadata.len();
 
         let etag = if let Ok(modified) = metadata.modified() {
@@ -147,86 +225,23 @@ impl StaticSvc {
             format!("\"{:x}\"", file_size)
         };
 
-        if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH)
-            && let Ok(if_none_match_str) = if_none_match.to_str()
-            && (if_none_match_str == etag || if_none_match_str == "*")
-        {
-            return Ok(Response::builder()
-                .status(StatusCode::NOT_MODIFIED)
-                .header(header::ETAG, etag)
-                .body(bytes_to_boxed(Bytes::new()))
-                .unwrap());
-        }
-
-        let mut builder = Response::builder()
-            .header(header::CONTENT_TYPE, content_type)
-            .header(header::ETAG, etag)
-            .header(header::ACCEPT_RANGES, "bytes");
-
+        let mut download = ResumableDownload::new(file, file_size)
+            .content_type(content_type)
+            .etag(etag)
+            .multi_range(self.multi_range);
         if let Ok(time) = metadata.modified() {
-            let datetime = httpdate::fmt_http_date(time);
-            builder = builder.header(header::LAST_MODIFIED, datetime);
-        }
-
-        // 处理 Range 请求
-        if let Some(range_header) = headers.get(header::RANGE)
-            && let Ok(range_str) = range_header.to_str()
-        {
-            match Self::parse_range(range_str, file_size) {
-                Ok(Some((start, end))) => {
-                    // 合法的 Range 请求
-                    let content_length = end - start + 1;
-                    let mut file = File::open(path).await?;
-                    file.seek(SeekFrom::Start(start)).await?;
-
-                    builder = builder
-                        .status(StatusCode::PARTIAL_CONTENT)
-                        .header(header::CONTENT_LENGTH, content_length)
-                        .header(
-                            header::CONTENT_RANGE,
-                            format!("bytes {}-{}/{}", start, end, file_size),
-                        );
-
-                    if method == Method::HEAD {
-                        return Ok(builder.body(bytes_to_boxed(Bytes::new())).unwrap());
-                    }
-
-                    // 使用 ReaderStream 读取指定范围的数据
-                    let limited_file = file.take(content_length);
-                    let stream = ReaderStream::new(limited_file);
-                    let body = FallibleStreamBody::with_size_hint(stream, content_length);
-                    return Ok(builder
-                        .body(body.map_err(Into::into).boxed_unsync())
-                        .unwrap());
-                }
-                Err(()) => {
-                    // Range 超出范围，返回 416 Range Not Satisfiable
-                    return Ok(Response::builder()
-                        .status(StatusCode::RANGE_NOT_SATISFIABLE)
-                        .header(header::CONTENT_RANGE, format!("bytes */{}", file_size))
-                        .body(bytes_to_boxed(Bytes::new()))
-                        .unwrap());
-                }
-                Ok(None) => {
-                    // 无效的 Range 格式或不支持的格式，忽略 Range 头，返回完整文件
-                }
-            }
+            download = download.last_modified(time);
         }
-
-        builder = builder
-            .status(StatusCode::OK)
-            .header(header::CONTENT_LENGTH, file_size);
-
-        if method == Method::HEAD {
-            return Ok(builder.body(bytes_to_boxed(Bytes::new())).unwrap());
-        }
-
-        let file = File::open(path).await?;
-        let stream = ReaderStream::new(file);
-        let body = FallibleStreamBody::with_size_hint(stream, file_size);
-        Ok(builder
-            .body(body.map_err(Into::into).boxed_unsync())
-            .unwrap())
+        let mut resp = download
+            .respond(&RangeRequest::new(method.clone(), headers.clone()))
+            .await?;
+        finish_response(
+            &mut resp,
+            encoding,
+            self.precompressed,
+            self.cache_control_for(path),
+        );
+        Ok(resp)
     }
 }
 
@@ -237,6 +252,10 @@ pub struct StaticSvcBuilder {
     pub fallback_files: Vec<String>,
     pub index_files: Vec<String>,
     pub cors_layer: Option<CorsLayer>,
+    pub precompressed: bool,
+    pub multi_range: bool,
+    pub cache_control: HashMap<String, HeaderValue>,
+    pub directory_listing: bool,
 }
 impl StaticSvcBuilder {
     /// 创建构建器
@@ -247,6 +266,10 @@ impl StaticSvcBuilder {
             fallback_files: vec!["index.html".to_string()],
             index_files: vec!["index.html".to_string(), "index.htm".to_string()],
             cors_layer: None,
+            precompressed: false,
+            multi_range: false,
+            cache_control: HashMap::new(),
+            directory_listing: false,
         }
     }
     /// 启用/关闭单页应用回退（当命中文件不存在时回退到配置的 fallback 文件）
@@ -284,6 +307,47 @@ impl StaticSvcBuilder {
         self.index_files = files.into_iter().map(|f| f.into()).collect();
         self
     }
+    /// 客户端接受时优先返回同目录下预压缩的 `<file>.br` / `<file>.gz`
+    ///
+    /// 按 `Accept-Encoding` 的权重选择，权重相同时优先 br；响应带 `Content-Encoding` 与 `Vary: accept-encoding`。
+    pub fn with_precompressed(mut self, precompressed: bool) -> Self {
+        self.precompressed = precompressed;
+        self
+    }
+    /// 以 `multipart/byteranges` 响应多范围请求，关闭时返回完整文件
+    pub fn with_multi_range(mut self, multi_range: bool) -> Self {
+        self.multi_range = multi_range;
+        self
+    }
+    /// 为指定扩展名的文件设置 `Cache-Control`
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use miko::ext::static_svc::StaticSvc;
+    /// StaticSvc::builder("./dist")
+    ///     .with_cache_control(["js", "css", "woff2"], "public, max-age=31536000, immutable")
+    ///     .with_cache_control(["html"], "no-cache")
+    ///     .build();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `value` 不是合法的头部值时 panic
+    pub fn with_cache_control(
+        mut self,
+        extensions: impl IntoIterator<Item = impl AsRef<str>>,
+        value: &str,
+    ) -> Self {
+        insert_cache_control(&mut self.cache_control, extensions, value);
+        self
+    }
+    /// 目录下没有索引文件时列出目录内容（名称、大小、修改时间），适合内部文件服务器
+    ///
+    /// 默认返回 HTML，`Accept` 偏好 `application/json` 时返回 JSON；以 `.` 开头的隐藏文件不会列出。
+    pub fn with_directory_listing(mut self, directory_listing: bool) -> Self {
+        self.directory_listing = directory_listing;
+        self
+    }
     /// 配置 CORS Layer
     pub fn with_cors(mut self, cors_layer: CorsLayer) -> Self {
         self.cors_layer = Some(cors_layer);
@@ -301,6 +365,10 @@ impl StaticSvcBuilder {
             spa_fallback: self.spa_fallback,
             fallback_files: Arc::new(self.fallback_files),
             index_files: Arc::new(self.index_files),
+            precompressed: self.precompressed,
+            multi_range: self.multi_range,
+            cache_control: Arc::new(self.cache_control),
+            directory_listing: self.directory_listing,
         };
         if let Some(cors_layer) = self.cors_layer {
             BoxCloneService::new(cors_layer.clone().layer(service))
@@ -321,7 +389,8 @@ impl Service<Req> for StaticSvc {
     fn call(&mut self, req: Req) -> Self::Future {
         let root = self.root.clone();
         let spa_fallback = self.spa_fallback;
-        let mut path = self.resolve_path(req.uri().path());
+        let uri_path = req.uri().path().to_string();
+        let mut path = self.resolve_path(&uri_path);
         let (parts, _body) = req.into_parts();
         let method = parts.method;
         let headers = parts.headers;
@@ -330,16 +399,23 @@ impl Service<Req> for StaticSvc {
         Box::pin(async move {
             if let Some(index_path) = self_clone.resolve_index_file(path.clone()).await {
                 path = index_path;
+            } else if self_clone.directory_listing
+                && tokio::fs::metadata(&path).await.is_ok_and(|m| m.is_dir())
+            {
+                return Ok(StaticSvc::render_listing(&path, &uri_path, &headers)
+                    .await
+                    .unwrap_or_else(|e| AppError::from(e).into_response()));
             }
 
-            match StaticSvc::serve_file(&path, &method, &headers).await {
+            match self_clone.serve_file(&path, &method, &headers).await {
                 Ok(resp) => Ok(resp),
                 Err(e) => {
                     if spa_fallback
                         && e.kind() == std::io::ErrorKind::NotFound
                         && let Some(fallback_path) = self_clone.try_fallback_files(&root).await
-                        && let Ok(resp) =
-                            StaticSvc::serve_file(&fallback_path, &method, &headers).await
+                        && let Ok(resp) = self_clone
+                            .serve_file(&fallback_path, &method, &headers)
+                            .await
                     {
                         return Ok(resp);
                     }
@@ -350,3 +426,111 @@ impl Service<Req> for StaticSvc {
         })
     }
 }
+
+/// 按扩展名推断 `Content-Type`，文本类型补上 utf-8 字符集
+pub(crate) fn content_type_for(path: impl AsRef<Path>) -> String {
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
+    if mime.type_() == mime_guess::mime::TEXT {
+        format!("{}; charset=utf-8", mime)
+    } else {
+        mime.to_string()
+    }
+}
+
+/// 客户端接受的预压缩格式（扩展名与 `Content-Encoding`），按优先级排列
+pub(crate) fn accepted_encodings(headers: &HeaderMap) -> Vec<(&'static str, &'static str)> {
+    let Some(accept) = headers
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return Vec::new();
+    };
+    let mut candidates: Vec<_> = PRECOMPRESSED
+        .iter()
+        .map(|&(ext, encoding)| (ext, encoding, encoding_quality(accept, encoding)))
+        .filter(|(_, _, q)| *q > 0.0)
+        .collect();
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+    candidates
+        .into_iter()
+        .map(|(ext, encoding, _)| (ext, encoding))
+        .collect()
+}
+
+pub(crate) fn insert_cache_control(
+    rules: &mut HashMap<String, HeaderValue>,
+    extensions: impl IntoIterator<Item = impl AsRef<str>>,
+    value: &str,
+) {
+    let value = HeaderValue::from_str(value)
+        .unwrap_or_else(|e| panic!("invalid Cache-Control value `{}`: {}", value, e));
+    for ext in extensions {
+        let ext = ext.as_ref().trim_start_matches('.').to_ascii_lowercase();
+        rules.insert(ext, value.clone());
+    }
+}
+
+pub(crate) fn cache_control_for<'a>(
+    rules: &'a HashMap<String, HeaderValue>,
+    path: &Path,
+) -> Option<&'a HeaderValue> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    rules.get(&ext)
+}
+
+/// 补充预压缩与缓存相关的响应头
+pub(crate) fn finish_response(
+    resp: &mut Resp,
+    encoding: Option<&'static str>,
+    precompressed: bool,
+    cache_control: Option<&HeaderValue>,
+) {
+    let status = resp.status();
+    let headers = resp.headers_mut();
+    if let Some(encoding) = encoding
+        && (status == StatusCode::OK || status == StatusCode::PARTIAL_CONTENT)
+    {
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(encoding));
+    }
+    if precompressed {
+        headers.append(header::VARY, HeaderValue::from_static("accept-encoding"));
+    }
+    if let Some(cache_control) = cache_control
+        && (status.is_success() || status == StatusCode::NOT_MODIFIED)
+    {
+        headers.insert(header::CACHE_CONTROL, cache_control.clone());
+    }
+}
+
+/// 对路径段做百分号编码
+fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// `Accept-Encoding` 中 `encoding` 的权重，未列出时取 `*` 的权重
+fn encoding_quality(accept: &str, encoding: &str) -> f32 {
+    let mut wildcard = 0.0;
+    for item in accept.split(',') {
+        let mut params = item.split(';');
+        let name = params.next().unwrap_or_default().trim();
+        let q = params
+            .filter_map(|p| p.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if name.eq_ignore_ascii_case(encoding) {
+            return q;
+        }
+        if name == "*" {
+            wildcard = q;
+        }
+    }
+    wildcard
+}
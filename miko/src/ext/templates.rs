@@ -0,0 +1,223 @@
+//! 模板渲染（需要 `templates` feature，基于 [Tera](https://keats.github.io/tera/)）
+//!
+//! [`TemplateEngine`] 加载匹配 glob 的模板文件，可以 `#[dep] engine: Arc<TemplateEngine>` 注入；
+//! [`Template`] 作为响应返回时渲染为 `text/html`，渲染失败转换为 500。注入的实例按 `[templates]` 配置创建：
+//!
+//! ```toml
+//! [templates]
+//! path = "templates/**/*"   # 缺省值
+//! reload = true             # 每次渲染前重新读取模板，缺省在 debug 构建中开启
+//! ```
+//!
+//! ```rust,ignore
+//! #[get("/users/{id}")]
+//! async fn profile(#[path] id: u64) -> AppResult<Template> {
+//!     let user = users().find(id).await?;
+//!     Ok(Template::new("profile.html", &user))
+//! }
+//! ```
+
+use crate::app::config::get_settings;
+use crate::handler::Resp;
+use crate::http::response::into_response::{Html, IntoResponse};
+use crate::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock, RwLock};
+use tera::{Context, Tera};
+
+/// 模板配置（`[templates]`）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TemplateSettings {
+    /// 模板文件的 glob
+    pub path: String,
+    /// 每次渲染前重新读取模板，缺省在 debug 构建中开启
+    pub reload: Option<bool>,
+}
+
+impl Default for TemplateSettings {
+    fn default() -> Self {
+        Self {
+            path: "templates/**/*".to_string(),
+            reload: None,
+        }
+    }
+}
+
+struct State {
+    tera: Tera,
+    /// 以代码添加的模板，重新加载后需要补回
+    raw: Vec<(String, String)>,
+}
+
+/// 模板引擎组件，可通过 `#[dep] engine: Arc<TemplateEngine>` 注入，克隆后共享模板
+#[derive(Clone)]
+pub struct TemplateEngine {
+    state: Arc<RwLock<State>>,
+    reload: bool,
+}
+
+static GLOBAL: LazyLock<TemplateEngine> = LazyLock::new(TemplateEngine::from_global_settings);
+
+impl TemplateEngine {
+    /// 加载匹配 `glob` 的模板，目录不存在时为空
+    pub fn new(glob: &str) -> AppResult<Self> {
+        Ok(Self::with_tera(Tera::new(glob)?))
+    }
+
+    /// 不含模板文件的引擎，模板通过 [`add_template`](Self::add_template) 添加
+    pub fn empty() -> Self {
+        Self::with_tera(Tera::default())
+    }
+
+    fn with_tera(tera: Tera) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(State {
+                tera,
+                raw: Vec::new(),
+            })),
+            reload: false,
+        }
+    }
+
+    /// 根据 `[templates]` 配置创建，加载失败时记录错误并返回空引擎
+    pub fn from_global_settings() -> Self {
+        let settings: TemplateSettings = get_settings().get("templates").unwrap_or_default();
+        Self::from_settings(settings).unwrap_or_else(|e| {
+            tracing::error!(error = %e, "failed to load templates");
+            Self::empty()
+        })
+    }
+
+    pub fn from_settings(settings: TemplateSettings) -> AppResult<Self> {
+        Ok(Self::new(&settings.path)?.reload(settings.reload.unwrap_or(cfg!(debug_assertions))))
+    }
+
+    /// 全局实例，`#[dep]` 注入与 [`Template::new`] 使用该实例
+    pub fn global() -> &'static TemplateEngine {
+        &GLOBAL
+    }
+
+    /// 是否在每次渲染前重新读取模板文件
+    pub fn reload(mut self, enabled: bool) -> Self {
+        self.reload = enabled;
+        self
+    }
+
+    /// 以字符串添加模板，重新加载时保留
+    pub fn add_template(&self, name: &str, content: &str) -> AppResult<()> {
+        let mut state = self.state.write().unwrap();
+        state.tera.add_raw_template(name, content)?;
+        state.raw.push((name.to_string(), content.to_string()));
+        Ok(())
+    }
+
+    /// 修改底层的 [`Tera`]，如注册过滤器与函数
+    pub fn configure(&self, f: impl FnOnce(&mut Tera)) {
+        f(&mut self.state.write().unwrap().tera);
+    }
+
+    /// 以可序列化的数据渲染模板
+    pub fn render<T: Serialize + ?Sized>(&self, name: &str, data: &T) -> AppResult<String> {
+        let context = Context::from_serialize(data)?;
+        self.render_context(name, &context)
+    }
+
+    fn render_context(&self, name: &str, context: &Context) -> AppResult<String> {
+        if self.reload {
+            let mut state = self.state.write().unwrap();
+            let State { tera, raw } = &mut *state;
+            // 未使用 glob 创建的引擎没有可重新读取的文件
+            if tera.full_reload().is_ok() {
+                for (name, content) in raw.iter() {
+                    tera.add_raw_template(name, content)?;
+                }
+            }
+            return Ok(tera.render(name, context)?);
+        }
+        Ok(self.state.read().unwrap().tera.render(name, context)?)
+    }
+
+    /// 创建使用该引擎渲染的 [`Template`] 响应
+    pub fn template<T: Serialize + ?Sized>(&self, name: impl Into<String>, data: &T) -> Template {
+        Template::new(name, data).engine(self)
+    }
+}
+
+/// 模板响应，渲染为 `text/html; charset=utf-8`
+pub struct Template {
+    name: String,
+    context: Result<Context, tera::Error>,
+    engine: Option<TemplateEngine>,
+}
+
+impl Template {
+    /// 使用全局引擎渲染 `name`
+    pub fn new<T: Serialize + ?Sized>(name: impl Into<String>, data: &T) -> Self {
+        Self {
+            name: name.into(),
+            context: Context::from_serialize(data),
+            engine: None,
+        }
+    }
+
+    /// 改用指定的引擎渲染
+    pub fn engine(mut self, engine: &TemplateEngine) -> Self {
+        self.engine = Some(engine.clone());
+        self
+    }
+
+    /// 立即渲染为字符串
+    pub fn render(&self) -> AppResult<String> {
+        let context = self.context.as_ref().map_err(|e| template_error(e))?;
+        self.engine
+            .as_ref()
+            .unwrap_or_else(|| TemplateEngine::global())
+            .render_context(&self.name, context)
+    }
+}
+
+impl IntoResponse for Template {
+    fn into_response(self) -> Resp {
+        self.render().map(Html).into_response()
+    }
+}
+
+/// 拼接错误链：Tera 的外层错误通常只有 "Failed to render"，原因在 source 中
+pub(crate) fn template_error(err: &(dyn std::error::Error + 'static)) -> AppError {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    AppError::InternalServerError(message)
+}
+
+impl From<tera::Error> for AppError {
+    fn from(err: tera::Error) -> Self {
+        template_error(&err)
+    }
+}
+
+#[cfg(feature = "auto")]
+crate::inventory::submit! {
+    crate::dependency_container::DependencyDefFn(|| {
+        crate::dependency_container::DependencyDef {
+            type_id: std::any::TypeId::of::<TemplateEngine>(),
+            type_name: std::any::type_name::<TemplateEngine>(),
+            prewarm: false,
+            name: "___",
+            lifetime: crate::dependency_container::DependencyLifetime::Singleton,
+            deps: Vec::new,
+            source: concat!(file!(), ":", line!()),
+            replace: false,
+            init_fn: || {
+                Box::pin(async move {
+                    Arc::new(TemplateEngine::global().clone()) as Arc<dyn std::any::Any + Send + Sync>
+                })
+            },
+        }
+    })
+}
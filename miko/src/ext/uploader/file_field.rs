@@ -7,6 +7,9 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 use tokio::io::AsyncWriteExt;
 
+/// 上传过程中临时文件的后缀，写入完成后改名为最终文件名
+pub const PARTIAL_UPLOAD_SUFFIX: &str = ".part";
+
 /// 表示一个原始上传字段（文件），可作为异步字节流读取
 #[derive(Debug)]
 pub struct FileField {
@@ -56,20 +59,32 @@ impl FileField {
         let path = path.into();
         tokio::fs::create_dir_all(&path).await?;
         let dest = path.join(filename);
-        let mut dest_file = tokio::fs::File::create(&dest).await?;
+        // 先写入 `.part` 文件，完成后再改名；中断留下的残片由 janitor 清理
+        let partial = path.join(format!("{filename}{PARTIAL_UPLOAD_SUFFIX}"));
+        let mut dest_file = tokio::fs::File::create(&partial).await?;
         let mut size = 0;
         while let Some(chunk) = self.next().await {
-            let chunk = chunk?;
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    drop(dest_file);
+                    let _ = tokio::fs::remove_file(&partial).await;
+                    return Err(e.into());
+                }
+            };
             size += chunk.len();
             dest_file.write_all(&chunk).await?;
             if let Some(max_size) = config.max_size
                 && size > max_size
             {
                 dest_file.shutdown().await?;
-                tokio::fs::remove_file(&dest).await?;
+                tokio::fs::remove_file(&partial).await?;
                 return Err(anyhow::anyhow!("File size exceeded"));
             }
         }
+        dest_file.flush().await?;
+        drop(dest_file);
+        tokio::fs::rename(&partial, &dest).await?;
         Ok(UploadedFile {
             original_filename: self.original_filename,
             final_filename: filename.to_string(),
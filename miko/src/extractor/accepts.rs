@@ -0,0 +1,110 @@
+//! `Accept` 媒体范围提取器
+//!
+//! 与 [`TypedHeader<Accept>`](crate::extractor::TypedHeader) 不同，请求没有 `Accept` 头时视为接受任意类型，
+//! 并按 RFC 9110 的规则（最具体的范围决定权重）选择服务端支持的格式：
+//!
+//! ```rust,ignore
+//! async fn report(accepts: Accepts) -> AppResult<Resp> {
+//!     match accepts.negotiate(&["text/csv", "application/json"]) {
+//!         Some("text/csv") => Ok(csv_response()),
+//!         _ => Ok(Json(report).into_response()),
+//!     }
+//! }
+//! ```
+
+use crate::error::AppError;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use crate::extractor::typed_header::{Accept, Header, QualityItem};
+use hyper::HeaderMap;
+use hyper::header::ACCEPT;
+use hyper::http::request::Parts;
+use mime_guess::Mime;
+use mime_guess::mime::STAR;
+use std::sync::Arc;
+
+/// 解析后的 `Accept` 请求头，按权重从高到低排列；为空表示接受任意类型
+#[derive(Debug, Clone, Default)]
+pub struct Accepts(pub Vec<QualityItem>);
+
+impl Accepts {
+    /// 从请求头解析，`Accept` 缺失时返回空列表
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, String> {
+        match headers.get(ACCEPT) {
+            Some(value) => Accept::decode(value).map(|accept| Self(accept.0)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// 客户端列出的媒体范围
+    pub fn media_ranges(&self) -> &[QualityItem] {
+        &self.0
+    }
+
+    /// 客户端对该媒体类型的权重，取最具体的匹配范围；未匹配时为 0
+    pub fn quality(&self, mime: &Mime) -> f32 {
+        if self.0.is_empty() {
+            return 1.0;
+        }
+        self.0
+            .iter()
+            .filter_map(|item| specificity(&item.mime, mime).map(|s| (s, item.quality)))
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map_or(0.0, |(_, quality)| quality)
+    }
+
+    /// 是否接受该媒体类型，如 `"application/json"`
+    pub fn accepts(&self, mime: &str) -> bool {
+        mime.parse().is_ok_and(|mime| self.quality(&mime) > 0.0)
+    }
+
+    /// 从服务端支持的类型中选出客户端最偏好的一个，权重相同时按 `available` 的顺序
+    pub fn negotiate<'a>(&self, available: &[&'a str]) -> Option<&'a str> {
+        let mut best: Option<(&'a str, f32)> = None;
+        for &candidate in available {
+            let Ok(mime) = candidate.parse::<Mime>() else {
+                continue;
+            };
+            let quality = self.quality(&mime);
+            if quality > 0.0 && best.is_none_or(|(_, q)| quality > q) {
+                best = Some((candidate, quality));
+            }
+        }
+        best.map(|(candidate, _)| candidate)
+    }
+}
+
+/// 媒体范围匹配该类型时返回其具体程度：`*/*` < `type/*` < `type/subtype` < 带参数
+fn specificity(range: &Mime, mime: &Mime) -> Option<u8> {
+    if range.type_() == STAR {
+        return Some(0);
+    }
+    if range.type_() != mime.type_() {
+        return None;
+    }
+    if range.subtype() == STAR {
+        return Some(1);
+    }
+    if range.subtype() != mime.subtype() {
+        return None;
+    }
+    let params = range.params().filter(|(name, _)| *name != "q");
+    let mut matched = true;
+    let mut count = 0;
+    for (name, value) in params {
+        count += 1;
+        matched &= mime.get_param(name) == Some(value);
+    }
+    match (matched, count) {
+        (false, _) => None,
+        (true, 0) => Some(2),
+        (true, _) => Some(3),
+    }
+}
+
+impl<S> FromRequestParts<S> for Accepts {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let accepts = Self::from_headers(&req.headers)
+            .map_err(|e| AppError::BadRequest(format!("Invalid header `accept`: {}", e)));
+        Box::pin(async move { accepts })
+    }
+}
@@ -0,0 +1,48 @@
+//! 请求体重复读取检测
+//!
+//! 需要读取请求体的中间件应通过 [`take_body`] 取走请求体，它会在请求扩展中记录
+//! [`BodyConsumed`]。之后内置的请求体提取器（`Json`、`Form`、`String`、`Bytes`、Multipart 等）
+//! 再次读取时返回 500 `request body already consumed by <consumer>`，而不是把空请求体当作无效输入：
+//!
+//! ```rust,ignore
+//! let body = take_body(&mut req, "SignatureLayer")?;
+//! let bytes = body.collect().await?.to_bytes();
+//! verify(&bytes)?;
+//! // 读取后放回，处理器仍可正常提取
+//! restore_body(&mut req, bytes);
+//! ```
+
+use crate::error::AppError;
+use crate::handler::Req;
+use crate::miko_core::ReqBody;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Empty, Full};
+
+/// 请求体已被读取，记录读取者名称
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyConsumed(pub &'static str);
+
+/// 请求体已被读取时返回指明读取者的 500 错误
+pub fn ensure_body_available(req: &Req) -> Result<(), AppError> {
+    match req.extensions().get::<BodyConsumed>() {
+        Some(BodyConsumed(consumer)) => Err(AppError::InternalServerError(format!(
+            "request body already consumed by {}",
+            consumer
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// 取走请求体并记录读取者，请求中留下空请求体
+pub fn take_body(req: &mut Req, consumer: &'static str) -> Result<ReqBody, AppError> {
+    ensure_body_available(req)?;
+    req.extensions_mut().insert(BodyConsumed(consumer));
+    let empty = Empty::new().map_err(Into::into).boxed_unsync();
+    Ok(std::mem::replace(req.body_mut(), empty))
+}
+
+/// 放回已读取的请求体，之后的提取器可以再次读取
+pub fn restore_body(req: &mut Req, bytes: Bytes) {
+    req.extensions_mut().remove::<BodyConsumed>();
+    *req.body_mut() = Full::new(bytes).map_err(Into::into).boxed_unsync();
+}
@@ -0,0 +1,96 @@
+//! 请求体大小限制
+//!
+//! 读取请求体的内置提取器（`Json`、`Form`、`String`、`Bytes`、`ValidatedJson`、Multipart）
+//! 会遵守请求扩展中的 [`BodyLimit`]，超出时返回 413。
+//!
+//! - 全局限制：配置项 `server.body_limit`，或手动挂载
+//!   [`BodyLimitLayer`](crate::middleware::body_limit::BodyLimitLayer)
+//! - 单路由限制：`#[body(limit = "2MB")]`，优先于全局限制
+
+use crate::error::AppError;
+use crate::extractor::body_consumed::ensure_body_available;
+use crate::extractor::from_request::{FRFut, FromRequest};
+use crate::handler::Req;
+use bytes::Bytes;
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use hyper::header::CONTENT_LENGTH;
+use std::sync::Arc;
+
+/// 当前请求允许的最大请求体字节数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimit(pub usize);
+
+/// 解析大小字符串，如 `"1024"`、`"512KB"`、`"2MB"`、`"1GiB"`（均按 1024 进制）
+pub fn parse_size(size: &str) -> Option<usize> {
+    let size = size.trim();
+    let split = size
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return None,
+    };
+    Some((number * multiplier as f64) as usize)
+}
+
+fn too_large(limit: usize) -> AppError {
+    AppError::PayloadTooLarge(format!("Request body exceeds the limit of {} bytes", limit))
+}
+
+/// 按 `Content-Length` 提前拒绝超出限制的请求，返回生效的限制
+///
+/// 请求体已被 [`take_body`](crate::extractor::body_consumed::take_body) 取走时返回 500。
+pub(crate) fn check_content_length(req: &Req) -> Result<Option<usize>, AppError> {
+    ensure_body_available(req)?;
+    let Some(BodyLimit(limit)) = req.extensions().get::<BodyLimit>().copied() else {
+        return Ok(None);
+    };
+    let declared = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    if declared.is_some_and(|len| len > limit as u64) {
+        return Err(too_large(limit));
+    }
+    Ok(Some(limit))
+}
+
+/// 在 [`BodyLimit`] 限制下读取完整请求体
+pub(crate) async fn read_body(req: Req) -> Result<Bytes, AppError> {
+    let limit = check_content_length(&req)?;
+    let body = req.into_body();
+    let collected = match limit {
+        Some(limit) => Limited::new(body, limit).collect().await.map_err(|e| {
+            if e.downcast_ref::<LengthLimitError>().is_some() {
+                too_large(limit)
+            } else {
+                AppError::BadRequest(format!("Failed to read request body: {}", e))
+            }
+        })?,
+        None => body
+            .collect()
+            .await
+            .map_err(|e| AppError::BadRequest(format!("Failed to read request body: {}", e)))?,
+    };
+    Ok(collected.to_bytes())
+}
+
+/// `#[body(limit = "...")]` 展开后的提取器，为内部提取器设置单路由的请求体限制 `LIMIT`（字节）
+pub struct WithBodyLimit<T, const LIMIT: usize>(pub T);
+
+impl<S, T, const LIMIT: usize> FromRequest<S> for WithBodyLimit<T, LIMIT>
+where
+    S: Send + Sync + 'static,
+    T: FromRequest<S> + Send + 'static,
+{
+    fn from_request(mut req: Req, state: Arc<S>) -> FRFut<Self> {
+        req.extensions_mut().insert(BodyLimit(LIMIT));
+        Box::pin(async move { T::from_request(req, state).await.map(WithBodyLimit) })
+    }
+}
@@ -0,0 +1,24 @@
+//! 客户端地址提取器
+//!
+//! [`Application::run`](crate::app::Application::run) 为每个连接记录对端地址，
+//! 处理器与中间件可据此区分客户端。经过反向代理时该地址是代理的地址。
+
+use crate::error::AppError;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use hyper::http::request::Parts;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// 连接的对端地址，由 `Application::run` 放入请求扩展；不经过 `run` 时（如测试客户端）缺失
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientAddr(pub SocketAddr);
+
+impl<S> FromRequestParts<S> for ClientAddr {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let addr =
+            req.extensions.get::<ClientAddr>().copied().ok_or_else(|| {
+                AppError::InternalServerError("Client address not available".into())
+            });
+        Box::pin(async move { addr })
+    }
+}
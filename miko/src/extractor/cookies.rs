@@ -0,0 +1,224 @@
+//! Cookie 提取器与响应写入
+//!
+//! [`CookieJar`] 从请求的 `Cookie` 头解析出所有 cookie；处理函数对其 `add` / `remove`
+//! 后把 jar 随响应一起返回（`(jar, body)`），变更会写成 `Set-Cookie` 头。
+//!
+//! ```rust,ignore
+//! #[get("/visit")]
+//! async fn visit(jar: CookieJar) -> (CookieJar, String) {
+//!     let count: u32 = jar.get("visits").and_then(|c| c.value().parse().ok()).unwrap_or(0);
+//!     let jar = jar.add(Cookie::new("visits", (count + 1).to_string()));
+//!     (jar, format!("visits: {}", count + 1))
+//! }
+//! ```
+//!
+//! 启用 `cookie-signed` feature 后可使用 [`SignedCookieJar`]（防篡改）与
+//! [`PrivateCookieJar`]（加密）。密钥通过 [`set_cookie_key`] 设置，
+//! 或从配置项 `cookie.secret`（至少 32 字节）派生。
+
+#[cfg(feature = "cookie-signed")]
+use crate::error::AppError;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use crate::handler::Resp;
+use crate::http::response::into_response::IntoResponse;
+use hyper::HeaderMap;
+use hyper::header::{COOKIE, HeaderValue, SET_COOKIE};
+use hyper::http::request::Parts;
+use std::sync::Arc;
+
+#[cfg(feature = "cookie-signed")]
+pub use cookie::Key;
+pub use cookie::{Cookie, Expiration, SameSite};
+
+fn parse_jar(headers: &HeaderMap) -> cookie::CookieJar {
+    let mut jar = cookie::CookieJar::new();
+    for value in headers.get_all(COOKIE) {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        for cookie in Cookie::split_parse_encoded(value).flatten() {
+            jar.add_original(cookie.into_owned());
+        }
+    }
+    jar
+}
+
+fn append_delta(jar: &cookie::CookieJar, resp: &mut Resp) {
+    let headers = resp.headers_mut();
+    for cookie in jar.delta() {
+        if let Ok(value) = HeaderValue::from_str(&cookie.encoded().to_string()) {
+            headers.append(SET_COOKIE, value);
+        }
+    }
+}
+
+/// 请求中的 cookie 集合，返回到响应中时写出变更
+#[derive(Debug, Clone, Default)]
+pub struct CookieJar {
+    jar: cookie::CookieJar,
+}
+
+impl CookieJar {
+    /// 从请求头构建
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            jar: parse_jar(headers),
+        }
+    }
+
+    /// 按名称获取 cookie
+    pub fn get(&self, name: &str) -> Option<&Cookie<'static>> {
+        self.jar.get(name)
+    }
+
+    /// 添加或覆盖 cookie
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<C: Into<Cookie<'static>>>(mut self, cookie: C) -> Self {
+        self.jar.add(cookie);
+        self
+    }
+
+    /// 删除 cookie（写出过期的 `Set-Cookie`）
+    ///
+    /// 如果设置 cookie 时指定了 path/domain，删除时需要传入相同的 path/domain
+    pub fn remove<C: Into<Cookie<'static>>>(mut self, cookie: C) -> Self {
+        self.jar.remove(cookie);
+        self
+    }
+
+    /// 遍历当前所有 cookie
+    pub fn iter(&self) -> impl Iterator<Item = &Cookie<'static>> {
+        self.jar.iter()
+    }
+}
+
+impl<S> FromRequestParts<S> for CookieJar {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let jar = CookieJar::from_headers(&req.headers);
+        Box::pin(async move { Ok(jar) })
+    }
+}
+
+#[cfg(feature = "cookie-signed")]
+static COOKIE_KEY: std::sync::OnceLock<Key> = std::sync::OnceLock::new();
+
+/// 设置签名/加密 cookie 使用的全局密钥，只有第一次调用生效
+#[cfg(feature = "cookie-signed")]
+pub fn set_cookie_key(key: Key) -> bool {
+    COOKIE_KEY.set(key).is_ok()
+}
+
+/// 获取全局密钥，未设置时从配置项 `cookie.secret` 派生
+#[cfg(feature = "cookie-signed")]
+pub fn cookie_key() -> Result<&'static Key, AppError> {
+    if let Some(key) = COOKIE_KEY.get() {
+        return Ok(key);
+    }
+    let secret: String = crate::app::config::get_settings()
+        .get("cookie.secret")
+        .map_err(|_| {
+            AppError::InternalServerError(
+                "cookie key is not configured, call set_cookie_key or set `cookie.secret`"
+                    .to_string(),
+            )
+        })?;
+    if secret.len() < 32 {
+        return Err(AppError::InternalServerError(
+            "`cookie.secret` must be at least 32 bytes".to_string(),
+        ));
+    }
+    Ok(COOKIE_KEY.get_or_init(|| Key::derive_from(secret.as_bytes())))
+}
+
+macro_rules! secure_jar {
+    ($(#[$meta:meta])* $name:ident, $view:ident, $view_mut:ident) => {
+        $(#[$meta])*
+        #[cfg(feature = "cookie-signed")]
+        #[derive(Clone)]
+        pub struct $name {
+            jar: cookie::CookieJar,
+            key: Key,
+        }
+
+        #[cfg(feature = "cookie-signed")]
+        impl $name {
+            /// 使用指定密钥从请求头构建
+            pub fn from_headers(headers: &HeaderMap, key: Key) -> Self {
+                Self {
+                    jar: parse_jar(headers),
+                    key,
+                }
+            }
+
+            /// 按名称获取并校验 cookie，校验失败视为不存在
+            pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+                self.jar.$view(&self.key).get(name)
+            }
+
+            /// 添加或覆盖 cookie
+            #[allow(clippy::should_implement_trait)]
+            pub fn add<C: Into<Cookie<'static>>>(mut self, cookie: C) -> Self {
+                self.jar.$view_mut(&self.key).add(cookie);
+                self
+            }
+
+            /// 删除 cookie
+            pub fn remove<C: Into<Cookie<'static>>>(mut self, cookie: C) -> Self {
+                self.jar.$view_mut(&self.key).remove(cookie);
+                self
+            }
+        }
+
+        #[cfg(feature = "cookie-signed")]
+        impl<S> FromRequestParts<S> for $name {
+            fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+                let jar = cookie_key().map(|key| $name::from_headers(&req.headers, key.clone()));
+                Box::pin(async move { jar })
+            }
+        }
+
+        #[cfg(feature = "cookie-signed")]
+        impl IntoResponse for $name {
+            fn into_response(self) -> Resp {
+                (self, ()).into_response()
+            }
+        }
+
+        #[cfg(feature = "cookie-signed")]
+        impl<T: IntoResponse> IntoResponse for ($name, T) {
+            fn into_response(self) -> Resp {
+                let mut resp = self.1.into_response();
+                append_delta(&self.0.jar, &mut resp);
+                resp
+            }
+        }
+    };
+}
+
+secure_jar!(
+    /// 签名 cookie：内容可读但无法被客户端篡改
+    SignedCookieJar,
+    signed,
+    signed_mut
+);
+
+secure_jar!(
+    /// 加密 cookie：内容对客户端不可见且无法篡改
+    PrivateCookieJar,
+    private,
+    private_mut
+);
+
+impl IntoResponse for CookieJar {
+    fn into_response(self) -> Resp {
+        (self, ()).into_response()
+    }
+}
+
+impl<T: IntoResponse> IntoResponse for (CookieJar, T) {
+    fn into_response(self) -> Resp {
+        let mut resp = self.1.into_response();
+        append_delta(&self.0.jar, &mut resp);
+        resp
+    }
+}
@@ -0,0 +1,46 @@
+//! 请求扩展提取器
+//!
+//! [`Extension<T>`] 从请求扩展中取出中间件放入的值（如认证信息、请求 ID），
+//! 共享值可用 [`Router::layer_extension`](crate::router::Router::layer_extension) 放入。
+//!
+//! ```rust,ignore
+//! async fn auth(mut req: Req, next: Next) -> AppResult<Resp> {
+//!     let user = CurrentUser::from_headers(req.headers())?;
+//!     req.extensions_mut().insert(user);
+//!     next.run(req).await
+//! }
+//!
+//! #[get("/me")]
+//! async fn me(Extension(user): Extension<CurrentUser>) -> String {
+//!     user.name
+//! }
+//! ```
+
+use crate::error::AppError;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use hyper::http::request::Parts;
+use std::sync::Arc;
+
+/// 请求扩展提取器，值缺失时返回 500；可选的值使用 `Option<Extension<T>>`
+#[derive(Debug, Clone)]
+pub struct Extension<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for Extension<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let value = req
+            .extensions
+            .get::<T>()
+            .cloned()
+            .map(Extension)
+            .ok_or_else(|| {
+                AppError::InternalServerError(format!(
+                    "Missing request extension `{}`, was it added by a layer?",
+                    std::any::type_name::<T>()
+                ))
+            });
+        Box::pin(async move { value })
+    }
+}
@@ -0,0 +1,86 @@
+//! 延迟执行的提取器
+//!
+//! [`Lazy<T>`] 在处理函数开始时只保存请求，直到第一次调用 [`Lazy::get`] 才真正运行 `T` 的提取逻辑，
+//! 适合命中缓存等可能根本不需要读取、解析请求体的分支。
+
+use crate::error::AppError;
+use crate::extractor::from_request::{FRFut, FromRequest};
+use crate::handler::Req;
+use std::sync::Arc;
+
+enum LazyState<T> {
+    Pending(FRFut<T>),
+    Ready(T),
+    Failed,
+}
+
+/// 延迟提取器，内部提取在首次 `.get().await` 时执行
+///
+/// 与其它消耗请求体的提取器一样，必须作为处理函数的最后一个参数。
+///
+/// ```rust,ignore
+/// #[post("/render")]
+/// async fn render(#[dep] cache: Arc<Cache>, mut body: Lazy<Json<Page>>) -> AppResult<String> {
+///     if let Some(hit) = cache.get("page") {
+///         return Ok(hit); // 请求体不会被读取
+///     }
+///     let Json(page) = body.into_inner().await?;
+///     Ok(page.render())
+/// }
+/// ```
+pub struct Lazy<T> {
+    state: LazyState<T>,
+}
+
+impl<T: Send + 'static> Lazy<T> {
+    /// 执行（或复用已完成的）提取，返回提取结果的可变引用
+    ///
+    /// 提取失败时返回其错误，之后再次调用将返回 500
+    pub async fn get(&mut self) -> Result<&mut T, AppError> {
+        if let LazyState::Pending(fut) = &mut self.state {
+            self.state = match fut.await {
+                Ok(value) => LazyState::Ready(value),
+                Err(e) => {
+                    self.state = LazyState::Failed;
+                    return Err(e);
+                }
+            };
+        }
+        match &mut self.state {
+            LazyState::Ready(value) => Ok(value),
+            _ => Err(AppError::InternalServerError(
+                "lazy extractor already failed".to_string(),
+            )),
+        }
+    }
+
+    /// 执行提取并取得所有权
+    pub async fn into_inner(mut self) -> Result<T, AppError> {
+        self.get().await?;
+        match self.state {
+            LazyState::Ready(value) => Ok(value),
+            _ => unreachable!(),
+        }
+    }
+
+    /// 提取是否已经执行过
+    pub fn is_evaluated(&self) -> bool {
+        !matches!(self.state, LazyState::Pending(_))
+    }
+}
+
+impl<S, T> FromRequest<S> for Lazy<T>
+where
+    S: Send + Sync + 'static,
+    T: FromRequest<S> + Send + 'static,
+{
+    fn from_request(req: Req, state: Arc<S>) -> FRFut<Self> {
+        // 只构造 future，不轮询；请求体直到 get() 时才被读取
+        let fut = Box::pin(async move { T::from_request(req, state).await });
+        Box::pin(async move {
+            Ok(Lazy {
+                state: LazyState::Pending(fut),
+            })
+        })
+    }
+}
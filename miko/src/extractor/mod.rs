@@ -1,20 +1,47 @@
+pub mod accepts;
+pub mod body_consumed;
+pub mod body_limit;
+pub mod client_addr;
+pub mod cookies;
+pub mod extension;
 pub mod from_request;
+pub mod lazy;
 pub mod multipart;
+pub(crate) mod path_de;
 pub mod path_params;
+pub mod query_multi;
+pub mod route_locale;
+pub mod typed_header;
 
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+#[cfg(feature = "validation")]
+pub mod validated;
 #[cfg(feature = "validation")]
 pub mod validated_json;
 
+#[cfg(feature = "protobuf")]
+pub use protobuf::Protobuf;
+#[cfg(feature = "validation")]
+pub use validated::{ValidatedForm, ValidatedPath, ValidatedQuery};
 #[cfg(feature = "validation")]
 pub use validated_json::ValidatedJson;
 
+pub use accepts::Accepts;
+pub use client_addr::ClientAddr;
+pub use cookies::CookieJar;
+pub use extension::Extension;
+pub use lazy::Lazy;
+pub use query_multi::{LenientQuery, QueryMulti};
+pub use route_locale::RouteLocale;
+pub use typed_header::TypedHeader;
+
 use crate::error::AppError;
 use crate::extractor::from_request::FRPFut;
 use crate::extractor::from_request::{FRFut, FromRequest, FromRequestParts};
 use crate::extractor::path_params::PathParams;
 use crate::handler::Req;
 use bytes::Bytes;
-use http_body_util::BodyExt;
 use hyper::http::Extensions;
 use hyper::http::request::Parts;
 use hyper::{Method, Uri};
@@ -27,8 +54,14 @@ use std::sync::Arc;
 pub struct Json<T>(pub T);
 /// URL 查询字符串提取器，将 ?a=1&b=2 解析为 T
 pub struct Query<T>(pub T);
-/// 路径参数提取器，从 PathParams 中提取首个段并转换为 T
+/// 路径参数提取器，从 PathParams 中提取首个段并通过 FromStr 转换为 T
 pub struct Path<T>(pub T);
+/// 基于 serde 的路径参数提取器
+///
+/// - 标量（`u32`、`String`、单元枚举等）取首个尚未提取的参数，可与 `Path` 混用依次取后续参数
+/// - 结构体按参数名读取全部参数，与声明顺序无关
+/// - 元组按顺序读取对应个数的参数
+pub struct PathDe<T>(pub T);
 /// 全局状态提取器，配合 Router::with_state 提供的 Arc<T>
 pub struct State<T>(pub Arc<T>);
 /// application/x-www-form-urlencoded 表单提取器
@@ -38,15 +71,10 @@ impl<S, T> FromRequest<S> for Json<T>
 where
     T: DeserializeOwned + Send + Sync + 'static,
 {
-    fn from_request(mut req: Req, _state: Arc<S>) -> FRFut<Self> {
+    fn from_request(req: Req, _state: Arc<S>) -> FRFut<Self> {
         let _ = _state;
         Box::pin(async move {
-            let body = req
-                .body_mut()
-                .collect()
-                .await
-                .map_err(|e| AppError::BadRequest(format!("Failed to read request body: {}", e)))?
-                .to_bytes();
+            let body = body_limit::read_body(req).await?;
 
             // 直接使用 JsonParseError，包含原始的 serde_json::Error
             let json = serde_json::from_slice::<T>(&body).map_err(AppError::JsonParseError)?;
@@ -62,7 +90,11 @@ where
 {
     fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRFut<Self> {
         let query = req.uri.query().unwrap_or("");
-        let query = serde_urlencoded::from_str(query);
+        let query = if req.extensions.get::<LenientQuery>().is_some() {
+            query_multi::deserialize_query(query, true)
+        } else {
+            serde_urlencoded::from_str(query)
+        };
         Box::pin(async move { query.map(Query).map_err(AppError::UrlEncodedParseError) })
     }
 }
@@ -73,13 +105,15 @@ where
     T::Err: std::fmt::Display,
 {
     fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRFut<Self> {
-        let pp = req.extensions.get_mut::<PathParams>().unwrap();
-        if pp.0.is_empty() {
-            return Box::pin(async move {
-                Err(AppError::BadRequest("No path parameters found".to_string()))
-            });
-        }
-        let path = pp.0.remove(0).1.clone();
+        let pp = match req.extensions.get_mut::<PathParams>() {
+            Some(pp) if !pp.0.is_empty() => pp,
+            _ => {
+                return Box::pin(async move {
+                    Err(AppError::BadRequest("No path parameters found".to_string()))
+                });
+            }
+        };
+        let path = pp.0.remove(0).1;
         Box::pin(async move {
             match path.parse::<T>() {
                 Ok(value) => Ok(Path(value)),
@@ -92,6 +126,21 @@ where
     }
 }
 
+impl<S, T> FromRequestParts<S> for PathDe<T>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRFut<Self> {
+        let result = match req.extensions.get_mut::<PathParams>() {
+            Some(pp) => T::deserialize(path_de::PathDeserializer { params: &mut pp.0 })
+                .map(PathDe)
+                .map_err(|e| AppError::BadRequest(e.to_string())),
+            None => Err(AppError::BadRequest("No path parameters found".to_string())),
+        };
+        Box::pin(async move { result })
+    }
+}
+
 impl<S: Send + Sync + 'static> FromRequestParts<S> for State<S> {
     fn from_request_parts(_req: &mut Parts, state: Arc<S>) -> FRPFut<'_, Self> {
         Box::pin(async move { Ok(State(state.clone())) })
@@ -99,14 +148,9 @@ impl<S: Send + Sync + 'static> FromRequestParts<S> for State<S> {
 }
 
 impl<S> FromRequest<S> for String {
-    fn from_request(mut req: Req, _state: Arc<S>) -> FRFut<Self> {
+    fn from_request(req: Req, _state: Arc<S>) -> FRFut<Self> {
         Box::pin(async move {
-            let body = req
-                .body_mut()
-                .collect()
-                .await
-                .map_err(|e| AppError::BadRequest(format!("Failed to read request body: {}", e)))?
-                .to_bytes();
+            let body = body_limit::read_body(req).await?;
             let string = std::str::from_utf8(&body)
                 .map(|s| s.to_string())
                 .map_err(|e| {
@@ -118,14 +162,9 @@ impl<S> FromRequest<S> for String {
 }
 
 impl<S> FromRequest<S> for Bytes {
-    fn from_request(mut req: Req, _state: Arc<S>) -> FRFut<Self> {
+    fn from_request(req: Req, _state: Arc<S>) -> FRFut<Self> {
         Box::pin(async move {
-            let body = req
-                .body_mut()
-                .collect()
-                .await
-                .map_err(|e| AppError::BadRequest(format!("Failed to read request body: {}", e)))?
-                .to_bytes();
+            let body = body_limit::read_body(req).await?;
             Ok(body)
         })
     }
@@ -135,14 +174,9 @@ impl<S, T> FromRequest<S> for Form<T>
 where
     T: DeserializeOwned + Send + Sync + 'static,
 {
-    fn from_request(mut req: Req, _state: Arc<S>) -> FRFut<Self> {
+    fn from_request(req: Req, _state: Arc<S>) -> FRFut<Self> {
         Box::pin(async move {
-            let body = req
-                .body_mut()
-                .collect()
-                .await
-                .map_err(|e| AppError::BadRequest(format!("Failed to read request body: {}", e)))?
-                .to_bytes();
+            let body = body_limit::read_body(req).await?;
             let form: T =
                 serde_urlencoded::from_bytes(&body).map_err(AppError::UrlEncodedParseError)?;
             Ok(Form(form))
@@ -1,3 +1,5 @@
+use crate::error::AppError;
+use crate::extractor::body_limit::check_content_length;
 use crate::extractor::from_request::{FRFut, FromRequest};
 use crate::handler::Req;
 use bytes::Bytes;
@@ -14,6 +16,9 @@ use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 use tokio_util::io::StreamReader;
 
+/// Multipart 临时文件名前缀，进程异常退出残留的文件可据此清理
+pub const MULTIPART_TEMP_PREFIX: &str = ".miko-multipart-";
+
 /// 原始 Multipart 访问器（底层包装 multer::Multipart），用于自定义解析流程
 pub struct Multipart(pub multer::Multipart<'static>);
 
@@ -75,14 +80,15 @@ impl<S> FromRequest<S> for MultipartResult {
                 return Err(err.into());
             }
             let boundary = boundary.unwrap().to_string();
-            let body = req.into_body().into_data_stream();
-            let mut multipart = multer::Multipart::new(body, boundary);
+            let mut multipart = new_multipart(req, boundary)?;
             while let Some(field) = multipart.next_field().await? {
                 let name = field.name().unwrap().to_string();
                 if let Some(filename) = field.file_name() {
                     let filename = filename.to_string();
                     let content_type = field.content_type().cloned();
-                    let temp_file = NamedTempFile::new()?;
+                    let temp_file = tempfile::Builder::new()
+                        .prefix(MULTIPART_TEMP_PREFIX)
+                        .tempfile()?;
                     let file_path = temp_file.path().to_path_buf();
                     let mut async_file_writer = File::options()
                         .read(true)
@@ -91,7 +97,19 @@ impl<S> FromRequest<S> for MultipartResult {
                         .await?;
                     let mut reader =
                         StreamReader::new(field.into_stream().map_err(std::io::Error::other));
-                    tokio::io::copy(&mut reader, &mut async_file_writer).await?;
+                    tokio::io::copy(&mut reader, &mut async_file_writer)
+                        .await
+                        .map_err(|e| {
+                            match e.get_ref().and_then(|e| e.downcast_ref::<multer::Error>()) {
+                                Some(multer::Error::StreamSizeExceeded { limit }) => {
+                                    AppError::PayloadTooLarge(format!(
+                                        "Request body exceeds the limit of {} bytes",
+                                        limit
+                                    ))
+                                }
+                                _ => AppError::from(e),
+                            }
+                        })?;
                     let fil = MultipartFile {
                         filename,
                         size: async_file_writer.metadata().await?.len() as usize,
@@ -125,13 +143,27 @@ impl<S> FromRequest<S> for Multipart {
                 return Err(err.into());
             }
             let boundary = boundary.unwrap().to_string();
-            let body = req.into_body().into_data_stream();
-            let multipart = multer::Multipart::new(body, boundary);
+            let multipart = new_multipart(req, boundary)?;
             Ok(Multipart(multipart))
         })
     }
 }
 
+/// 创建 multer 解析器，遵守请求中的 [`BodyLimit`](crate::extractor::body_limit::BodyLimit)
+fn new_multipart(req: Req, boundary: String) -> Result<multer::Multipart<'static>, AppError> {
+    let limit = check_content_length(&req)?;
+    let body = req.into_body().into_data_stream();
+    Ok(match limit {
+        Some(limit) => multer::Multipart::with_constraints(
+            body,
+            boundary,
+            multer::Constraints::new()
+                .size_limit(multer::SizeLimit::new().whole_stream(limit as u64)),
+        ),
+        None => multer::Multipart::new(body, boundary),
+    })
+}
+
 fn parse_boundary(headers: &HeaderMap) -> Result<String, anyhow::Error> {
     headers
         .get("Content-Type")
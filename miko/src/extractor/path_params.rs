@@ -1,3 +1,10 @@
+use crate::error::AppError;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use hyper::http::request::Parts;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+
 #[derive(Debug, Clone)]
 /// 路径参数集合，按声明顺序存储 (name, value)
 pub struct PathParams(pub Vec<(String, String)>);
@@ -28,3 +35,80 @@ impl PathParams {
         }
     }
 }
+
+/// 按名称提取的路径参数名，由 `#[path]` 宏为每个参数生成
+pub trait PathKey: Send + 'static {
+    const NAME: &'static str;
+}
+
+/// 取出与 `K::NAME` 同名的参数；没有同名参数且 `positional` 为 true 时取首个尚未提取的参数
+fn take_param<K: PathKey>(pp: &mut PathParams, positional: bool) -> Option<String> {
+    match pp.0.iter().position(|(name, _)| name == K::NAME) {
+        Some(index) => Some(pp.0.remove(index).1),
+        None if positional && !pp.0.is_empty() => Some(pp.0.remove(0).1),
+        None => None,
+    }
+}
+
+fn parse_param<T>(value: String) -> Result<T, AppError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.parse::<T>().map_err(|err| {
+        AppError::BadRequest(format!(
+            "Failed to parse path parameter '{}': {}",
+            value, err
+        ))
+    })
+}
+
+/// `#[path]` 参数展开后的提取器
+///
+/// 按参数名取出对应的路径参数并以 `FromStr` 解析；路由中没有同名参数时，退回取首个尚未提取的参数
+pub struct NamedPath<T, K>(pub T, pub PhantomData<K>);
+
+impl<S, T, K> FromRequestParts<S> for NamedPath<T, K>
+where
+    T: FromStr + Send + 'static,
+    T::Err: std::fmt::Display,
+    K: PathKey,
+{
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let result = match req.extensions.get_mut::<PathParams>() {
+            Some(pp) => match take_param::<K>(pp, true) {
+                Some(value) => parse_param(value).map(|v| NamedPath(v, PhantomData)),
+                None => Err(AppError::BadRequest(format!(
+                    "Missing path parameter '{}'",
+                    K::NAME
+                ))),
+            },
+            None => Err(AppError::BadRequest("No path parameters found".to_string())),
+        };
+        Box::pin(async move { result })
+    }
+}
+
+/// 声明了缺省值的 `#[path(default = ..)]` 参数展开后的提取器
+///
+/// 只按参数名取值，路由中没有同名参数时得到 `None`
+pub struct OptionalNamedPath<T, K>(pub Option<T>, pub PhantomData<K>);
+
+impl<S, T, K> FromRequestParts<S> for OptionalNamedPath<T, K>
+where
+    T: FromStr + Send + 'static,
+    T::Err: std::fmt::Display,
+    K: PathKey,
+{
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let result = match req
+            .extensions
+            .get_mut::<PathParams>()
+            .and_then(|pp| take_param::<K>(pp, false))
+        {
+            Some(value) => parse_param(value).map(|v| OptionalNamedPath(Some(v), PhantomData)),
+            None => Ok(OptionalNamedPath(None, PhantomData)),
+        };
+        Box::pin(async move { result })
+    }
+}
@@ -0,0 +1,86 @@
+//! Protobuf 请求体与响应
+//!
+//! ```rust,ignore
+//! #[derive(Clone, PartialEq, prost::Message)]
+//! struct Ping {
+//!     #[prost(string, tag = "1")]
+//!     name: String,
+//! }
+//!
+//! #[post("/ping")]
+//! async fn ping(Protobuf(ping): Protobuf<Ping>) -> Protobuf<Ping> {
+//!     Protobuf(ping)
+//! }
+//! ```
+
+use crate::error::AppError;
+use crate::extractor::body_limit;
+use crate::extractor::from_request::{FRFut, FromRequest};
+use crate::handler::{Req, Resp};
+use crate::http::response::into_response::IntoResponse;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::StatusCode;
+use hyper::header::{self, HeaderValue};
+use std::sync::Arc;
+
+/// 响应使用的 `Content-Type`
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+/// 请求可使用的 `Content-Type`
+const ACCEPTED_CONTENT_TYPES: &[&str] = &[
+    "application/x-protobuf",
+    "application/protobuf",
+    "application/vnd.google.protobuf",
+];
+
+/// Protobuf 请求体提取器与响应，基于 prost
+///
+/// 请求的 `Content-Type` 不是 protobuf 时返回 415，解码失败时返回 400
+#[derive(Debug, Clone, Default)]
+pub struct Protobuf<T>(pub T);
+
+impl<S, T> FromRequest<S> for Protobuf<T>
+where
+    T: prost::Message + Default + Send + 'static,
+{
+    fn from_request(req: Req, _state: Arc<S>) -> FRFut<Self> {
+        Box::pin(async move {
+            check_content_type(req.headers().get(header::CONTENT_TYPE))?;
+            let body = body_limit::read_body(req).await?;
+            T::decode(body)
+                .map(Protobuf)
+                .map_err(|e| AppError::BadRequest(format!("Invalid protobuf body: {}", e)))
+        })
+    }
+}
+
+fn check_content_type(value: Option<&HeaderValue>) -> Result<(), AppError> {
+    let essence = value.and_then(|v| v.to_str().ok()).map(|v| {
+        v.split(';')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .to_ascii_lowercase()
+    });
+    match essence {
+        Some(essence) if ACCEPTED_CONTENT_TYPES.contains(&essence.as_str()) => Ok(()),
+        _ => Err(AppError::custom(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "UNSUPPORTED_MEDIA_TYPE",
+            format!("Expected Content-Type `{}`", PROTOBUF_CONTENT_TYPE),
+        )),
+    }
+}
+
+impl<T: prost::Message> IntoResponse for Protobuf<T> {
+    fn into_response(self) -> Resp {
+        let body = Bytes::from(self.0.encode_to_vec());
+        let mut resp = Resp::new(Full::new(body).map_err(Into::into).boxed_unsync());
+        resp.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(PROTOBUF_CONTENT_TYPE),
+        );
+        resp
+    }
+}
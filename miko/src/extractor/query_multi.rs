@@ -0,0 +1,302 @@
+//! 支持重复键的查询字符串提取器
+//!
+//! `serde_urlencoded` 无法把 `?id=1&id=2` 反序列化为 `Vec<T>`，
+//! [`QueryMulti`] 会先按键聚合所有值，再交给 serde：
+//! 序列类型的字段得到全部值，标量字段取最后一个值。
+//!
+//! 路由带有 [`LenientQuery`] 标记时（`#[get("/search", lenient_query)]`），`Query` 与
+//! `QueryMulti` 按 HTML 表单的习惯宽松解析：布尔值接受 `1`/`on`/`yes`，数字两侧的空白被忽略，
+//! `Option` 字段的空字符串视为 `None`。
+
+use crate::error::AppError;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use hyper::http::request::Parts;
+use serde::de::value::{Error as DeError, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, MapAccess, Visitor};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 多值查询参数提取器
+///
+/// ```rust,ignore
+/// #[derive(Deserialize)]
+/// struct Filter {
+///     id: Vec<u32>,
+///     tag: Option<String>,
+/// }
+///
+/// async fn list(QueryMulti(filter): QueryMulti<Filter>) {}
+/// // 或直接拿到所有原始值
+/// async fn raw(QueryMulti(all): QueryMulti) {}
+/// ```
+#[derive(Debug, Clone)]
+pub struct QueryMulti<T = HashMap<String, Vec<String>>>(pub T);
+
+/// 路由级标记：该路由的查询参数按宽松规则解析
+///
+/// 除路由宏的 `lenient_query` 参数外，也可以通过 `[server.routes."/path"] lenient_query = true`
+/// 或 `ExtensionLayer::new(LenientQuery)` 开启。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LenientQuery;
+
+impl<S, T> FromRequestParts<S> for QueryMulti<T>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let query = req.uri.query().unwrap_or("");
+        let lenient = req.extensions.get::<LenientQuery>().is_some();
+        let result = deserialize_query(query, lenient)
+            .map(QueryMulti)
+            .map_err(|e| AppError::BadRequest(format!("Failed to parse query: {}", e)));
+        Box::pin(async move { result })
+    }
+}
+
+/// 按重复键聚合后反序列化查询字符串
+pub fn from_query_str<T: DeserializeOwned>(query: &str) -> Result<T, DeError> {
+    deserialize_query(query, false)
+}
+
+/// 同 [`from_query_str`]，按 [`LenientQuery`] 的规则宽松解析
+pub fn from_query_str_lenient<T: DeserializeOwned>(query: &str) -> Result<T, DeError> {
+    deserialize_query(query, true)
+}
+
+pub(crate) fn deserialize_query<T: DeserializeOwned>(
+    query: &str,
+    lenient: bool,
+) -> Result<T, DeError> {
+    let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+    for (k, v) in serde_urlencoded::from_str::<Vec<(String, String)>>(query)
+        .map_err(|e| de::Error::custom(e.to_string()))?
+    {
+        // 兼容 `id[]=1&id[]=2` 写法
+        let k = k.strip_suffix("[]").map(str::to_string).unwrap_or(k);
+        match entries.iter_mut().find(|(key, _)| *key == k) {
+            Some((_, values)) => values.push(v),
+            None => entries.push((k, vec![v])),
+        }
+    }
+    T::deserialize(MultiMapDeserializer {
+        iter: entries.into_iter(),
+        value: None,
+        lenient,
+    })
+}
+
+struct MultiMapDeserializer {
+    iter: std::vec::IntoIter<(String, Vec<String>)>,
+    value: Option<Vec<String>>,
+    lenient: bool,
+}
+
+impl<'de> de::Deserializer<'de> for MultiMapDeserializer {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_map(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for MultiMapDeserializer {
+    type Error = DeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, DeError> {
+        match self.iter.next() {
+            Some((key, values)) => {
+                self.value = Some(values);
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, DeError> {
+        let values = self.value.take().unwrap_or_default();
+        seed.deserialize(MultiValue(values, self.lenient))
+    }
+}
+
+/// 某个键的全部值
+struct MultiValue(Vec<String>, bool);
+
+/// 标量字段取最后一个值，按目标类型解析
+macro_rules! last_scalar {
+    ($($method:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+                self.last().$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for MultiValue {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        self.last().deserialize_any(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        let lenient = self.1;
+        SeqDeserializer::new(self.0.into_iter().map(|v| Scalar(v, lenient)))
+            .deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if self.1 && self.0.last().is_some_and(|v| v.trim().is_empty()) {
+            return visitor.visit_none();
+        }
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.last().deserialize_enum(name, variants, visitor)
+    }
+
+    last_scalar! {
+        deserialize_bool, deserialize_i8, deserialize_i16, deserialize_i32, deserialize_i64,
+        deserialize_i128, deserialize_u8, deserialize_u16, deserialize_u32, deserialize_u64,
+        deserialize_u128, deserialize_f32, deserialize_f64, deserialize_char,
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl MultiValue {
+    fn last(mut self) -> Scalar {
+        Scalar(self.0.pop().unwrap_or_default(), self.1)
+    }
+}
+
+macro_rules! parse_scalar {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+                let value = if self.1 { self.0.trim() } else { self.0.as_str() };
+                match value.parse() {
+                    Ok(v) => visitor.$visit(v),
+                    Err(e) => Err(de::Error::custom(format!("invalid value `{}`: {}", self.0, e))),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Scalar {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_string(self.0)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if self.1 && self.0.trim().is_empty() {
+            return visitor.visit_none();
+        }
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if !self.1 {
+            return match self.0.parse() {
+                Ok(v) => visitor.visit_bool(v),
+                Err(e) => Err(de::Error::custom(format!(
+                    "invalid value `{}`: {}",
+                    self.0, e
+                ))),
+            };
+        }
+        match self.0.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "on" | "yes" => visitor.visit_bool(true),
+            "0" | "false" | "off" | "no" | "" => visitor.visit_bool(false),
+            _ => Err(de::Error::custom(format!("invalid boolean `{}`", self.0))),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    parse_scalar! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// 单个字符串值，按目标类型解析；第二项为是否宽松解析
+struct Scalar(String, bool);
+
+impl<'de> IntoDeserializer<'de, DeError> for Scalar {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
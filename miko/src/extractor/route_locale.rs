@@ -0,0 +1,43 @@
+//! 本地化路由别名匹配到的语言
+
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use hyper::http::request::Parts;
+use std::sync::Arc;
+
+/// 当前请求命中的本地化别名的语言，命中主路径或普通别名时为 `None`
+///
+/// 由 `#[alias("/über-uns", locale = "de")]`、[`Router::localized_alias`](crate::router::Router::localized_alias)
+/// 或 `[server.route_aliases]` 注册的别名设置：
+///
+/// ```rust,ignore
+/// #[get("/about")]
+/// #[alias("/über-uns", locale = "de")]
+/// #[alias("/a-propos", locale = "fr")]
+/// async fn about(locale: RouteLocale) -> String {
+///     render_about(locale.or("en"))
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteLocale(pub Option<String>);
+
+impl RouteLocale {
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self(Some(locale.into()))
+    }
+
+    /// 语言，未命中本地化别名时返回 `default`
+    pub fn or<'a>(&'a self, default: &'a str) -> &'a str {
+        self.0.as_deref().unwrap_or(default)
+    }
+}
+
+impl<S> FromRequestParts<S> for RouteLocale {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let locale = req
+            .extensions
+            .get::<RouteLocale>()
+            .cloned()
+            .unwrap_or_default();
+        Box::pin(async move { Ok(locale) })
+    }
+}
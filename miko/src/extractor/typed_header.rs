@@ -0,0 +1,357 @@
+//! 类型化请求头提取器
+//!
+//! [`TypedHeader<T>`] 把单个请求头解析为强类型，内置 [`Authorization`]、[`ContentType`]、
+//! [`UserAgent`]、[`Range`] 与 [`Accept`]；缺失或解析失败时返回 400。
+//!
+//! ```rust,ignore
+//! async fn me(TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>) -> String {
+//!     bearer.token().to_string()
+//! }
+//! ```
+//!
+//! 路由宏中也可用 `#[header]` 按名称提取单个请求头（参数名中的 `_` 视为 `-`）：
+//!
+//! ```rust,ignore
+//! #[get("/")]
+//! async fn index(#[header] user_agent: String, #[header("x-request-count")] count: Option<u32>) {}
+//! ```
+
+use crate::error::AppError;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hyper::header::{self, HeaderName, HeaderValue};
+use hyper::http::request::Parts;
+use mime_guess::Mime;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// 可从单个请求头解析的类型
+pub trait Header: Sized {
+    /// 请求头名称
+    fn name() -> HeaderName;
+    /// 从请求头的值解析
+    fn decode(value: &HeaderValue) -> Result<Self, String>;
+}
+
+/// 请求头提取失败的原因
+#[derive(Debug)]
+pub enum HeaderError {
+    Missing,
+    Invalid(String),
+}
+
+/// `#[header]` 参数支持的类型：实现 [`Header`] 的类型、`String`、数字、`bool` 及其 `Option`
+pub trait FromHeader: Sized {
+    fn from_header(value: Option<&HeaderValue>) -> Result<Self, HeaderError>;
+}
+
+impl<T: Header> FromHeader for T {
+    fn from_header(value: Option<&HeaderValue>) -> Result<Self, HeaderError> {
+        T::decode(value.ok_or(HeaderError::Missing)?).map_err(HeaderError::Invalid)
+    }
+}
+
+impl<T: FromHeader> FromHeader for Option<T> {
+    fn from_header(value: Option<&HeaderValue>) -> Result<Self, HeaderError> {
+        match value {
+            Some(value) => T::from_header(Some(value)).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+macro_rules! from_str_header {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl FromHeader for $ty {
+                fn from_header(value: Option<&HeaderValue>) -> Result<Self, HeaderError> {
+                    header_str(value.ok_or(HeaderError::Missing)?)
+                        .map_err(HeaderError::Invalid)?
+                        .trim()
+                        .parse::<$ty>()
+                        .map_err(|e| HeaderError::Invalid(e.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+from_str_header!(
+    String, bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+fn header_str(value: &HeaderValue) -> Result<&str, String> {
+    value.to_str().map_err(|e| e.to_string())
+}
+
+/// 类型化请求头提取器，缺失或解析失败时返回 400
+///
+/// 请求头可选时使用 `Option<TypedHeader<T>>`。
+#[derive(Debug, Clone)]
+pub struct TypedHeader<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for TypedHeader<T>
+where
+    T: Header + Send + 'static,
+{
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let name = T::name();
+        let value = extract(&name, T::from_header(req.headers.get(&name))).map(TypedHeader);
+        Box::pin(async move { value })
+    }
+}
+
+/// `#[header]` 参数对应的请求头名称
+pub trait HeaderKey: Send + 'static {
+    const NAME: &'static str;
+}
+
+/// `#[header]` 参数展开后的提取器
+pub struct NamedHeader<T, K>(pub T, pub PhantomData<K>);
+
+impl<S, T, K> FromRequestParts<S> for NamedHeader<T, K>
+where
+    T: FromHeader + Send + 'static,
+    K: HeaderKey,
+{
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let value = extract(K::NAME, T::from_header(req.headers.get(K::NAME)))
+            .map(|v| NamedHeader(v, PhantomData));
+        Box::pin(async move { value })
+    }
+}
+
+fn extract<T>(name: impl std::fmt::Display, result: Result<T, HeaderError>) -> Result<T, AppError> {
+    result.map_err(|e| match e {
+        HeaderError::Missing => AppError::BadRequest(format!("Missing header `{}`", name)),
+        HeaderError::Invalid(e) => {
+            AppError::BadRequest(format!("Invalid header `{}`: {}", name, e))
+        }
+    })
+}
+
+/// `Authorization` 中的凭据类型
+pub trait Credentials: Sized {
+    /// 认证方案，如 `Bearer`
+    const SCHEME: &'static str;
+    /// 解析方案名之后的部分
+    fn decode(value: &str) -> Option<Self>;
+}
+
+/// `Authorization` 请求头
+#[derive(Debug, Clone)]
+pub struct Authorization<C>(pub C);
+
+impl<C: Credentials> Header for Authorization<C> {
+    fn name() -> HeaderName {
+        header::AUTHORIZATION
+    }
+
+    fn decode(value: &HeaderValue) -> Result<Self, String> {
+        let value = header_str(value)?;
+        let (scheme, rest) = value.split_once(' ').unwrap_or((value, ""));
+        if !scheme.eq_ignore_ascii_case(C::SCHEME) {
+            return Err(format!("expected `{}` scheme", C::SCHEME));
+        }
+        C::decode(rest.trim())
+            .map(Authorization)
+            .ok_or_else(|| format!("malformed `{}` credentials", C::SCHEME))
+    }
+}
+
+/// Bearer 令牌
+#[derive(Debug, Clone)]
+pub struct Bearer(String);
+
+impl Bearer {
+    pub fn token(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Credentials for Bearer {
+    const SCHEME: &'static str = "Bearer";
+
+    fn decode(value: &str) -> Option<Self> {
+        (!value.is_empty()).then(|| Bearer(value.to_string()))
+    }
+}
+
+/// Basic 认证的用户名与密码
+#[derive(Debug, Clone)]
+pub struct Basic {
+    username: String,
+    password: String,
+}
+
+impl Basic {
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+}
+
+impl Credentials for Basic {
+    const SCHEME: &'static str = "Basic";
+
+    fn decode(value: &str) -> Option<Self> {
+        let decoded = String::from_utf8(STANDARD.decode(value).ok()?).ok()?;
+        let (username, password) = decoded.split_once(':')?;
+        Some(Basic {
+            username: username.to_string(),
+            password: password.to_string(),
+        })
+    }
+}
+
+/// `Content-Type` 请求头
+#[derive(Debug, Clone)]
+pub struct ContentType(pub Mime);
+
+impl Header for ContentType {
+    fn name() -> HeaderName {
+        header::CONTENT_TYPE
+    }
+
+    fn decode(value: &HeaderValue) -> Result<Self, String> {
+        header_str(value)?
+            .parse::<Mime>()
+            .map(ContentType)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// `User-Agent` 请求头
+#[derive(Debug, Clone)]
+pub struct UserAgent(pub String);
+
+impl Header for UserAgent {
+    fn name() -> HeaderName {
+        header::USER_AGENT
+    }
+
+    fn decode(value: &HeaderValue) -> Result<Self, String> {
+        header_str(value).map(|s| UserAgent(s.to_string()))
+    }
+}
+
+/// `Range` 中的单个字节范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// `start-end`（闭区间）
+    FromTo(u64, u64),
+    /// `start-`
+    From(u64),
+    /// `-n`，最后 n 个字节
+    Last(u64),
+}
+
+impl ByteRange {
+    /// 针对长度为 `len` 的内容计算实际范围 `(start, end)`（闭区间），不可满足时返回 None
+    pub fn resolve(&self, len: u64) -> Option<(u64, u64)> {
+        let (start, end) = match *self {
+            ByteRange::FromTo(start, end) => (start, end.min(len.checked_sub(1)?)),
+            ByteRange::From(start) => (start, len.checked_sub(1)?),
+            ByteRange::Last(0) => return None,
+            ByteRange::Last(n) => (len.saturating_sub(n), len.checked_sub(1)?),
+        };
+        (start <= end).then_some((start, end))
+    }
+}
+
+/// `Range` 请求头（仅支持 `bytes` 单位）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range(pub Vec<ByteRange>);
+
+impl Header for Range {
+    fn name() -> HeaderName {
+        header::RANGE
+    }
+
+    fn decode(value: &HeaderValue) -> Result<Self, String> {
+        let ranges = header_str(value)?
+            .trim()
+            .strip_prefix("bytes=")
+            .ok_or("only `bytes` ranges are supported")?;
+        ranges
+            .split(',')
+            .map(|spec| {
+                let (start, end) = spec.trim().split_once('-').ok_or("missing `-`")?;
+                let parse = |s: &str| s.parse::<u64>().map_err(|e| e.to_string());
+                match (start.is_empty(), end.is_empty()) {
+                    (true, false) => Ok(ByteRange::Last(parse(end)?)),
+                    (false, true) => Ok(ByteRange::From(parse(start)?)),
+                    (false, false) => {
+                        let (start, end) = (parse(start)?, parse(end)?);
+                        if start > end {
+                            return Err(format!("invalid range `{}`", spec));
+                        }
+                        Ok(ByteRange::FromTo(start, end))
+                    }
+                    (true, true) => Err(format!("invalid range `{}`", spec)),
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map(Range)
+    }
+}
+
+/// `Accept` 中带权重的媒体类型
+#[derive(Debug, Clone)]
+pub struct QualityItem {
+    pub mime: Mime,
+    pub quality: f32,
+}
+
+/// `Accept` 请求头，按权重从高到低排列
+#[derive(Debug, Clone)]
+pub struct Accept(pub Vec<QualityItem>);
+
+impl Accept {
+    /// 客户端最偏好的媒体类型
+    pub fn preferred(&self) -> Option<&Mime> {
+        self.0.first().map(|item| &item.mime)
+    }
+
+    /// 是否接受指定的媒体类型（含 `*/*` 与 `type/*` 通配）
+    pub fn accepts(&self, mime: &Mime) -> bool {
+        self.0.iter().any(|item| {
+            item.quality > 0.0
+                && (item.mime.type_() == mime_guess::mime::STAR
+                    || (item.mime.type_() == mime.type_()
+                        && (item.mime.subtype() == mime_guess::mime::STAR
+                            || item.mime.subtype() == mime.subtype())))
+        })
+    }
+}
+
+impl Header for Accept {
+    fn name() -> HeaderName {
+        header::ACCEPT
+    }
+
+    fn decode(value: &HeaderValue) -> Result<Self, String> {
+        let mut items = header_str(value)?
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| {
+                let mime = s.trim().parse::<Mime>().map_err(|e| e.to_string())?;
+                let quality = match mime.get_param("q") {
+                    Some(q) => q
+                        .as_str()
+                        .parse::<f32>()
+                        .map_err(|_| format!("invalid quality in `{}`", s.trim()))?,
+                    None => 1.0,
+                };
+                Ok(QualityItem { mime, quality })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        // 稳定排序，同权重保持原始顺序
+        items.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+        Ok(Accept(items))
+    }
+}
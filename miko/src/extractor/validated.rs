@@ -0,0 +1,112 @@
+//! ValidatedQuery / ValidatedForm / ValidatedPath 提取器
+//!
+//! 分别基于 [`Query`]、[`Form`]、[`PathDe`] 解析后执行 garde 验证，
+//! 验证失败时与 [`ValidatedJson`](super::ValidatedJson) 一样转换为 AppError::ValidationError
+//!
+//! 需要启用 `validation` feature
+
+use crate::error::AppError;
+use crate::extractor::from_request::{FRFut, FRPFut, FromRequest, FromRequestParts};
+use crate::extractor::{Form, PathDe, Query};
+use crate::handler::Req;
+use hyper::http::request::Parts;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+
+/// ValidatedQuery 提取器
+///
+/// 按 [`Query`] 的规则解析查询字符串并执行验证
+///
+/// # Example
+/// ```rust,ignore
+/// #[derive(Deserialize, Validate)]
+/// struct Pagination {
+///     #[garde(range(min = 1))]
+///     page: u32,
+///     #[garde(range(min = 1, max = 100))]
+///     per_page: u32,
+/// }
+///
+/// async fn list(ValidatedQuery(p): ValidatedQuery<Pagination>) -> String {
+///     format!("page {} / {}", p.page, p.per_page)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ValidatedQuery<T>(pub T);
+
+/// ValidatedForm 提取器
+///
+/// 按 [`Form`] 的规则解析 `application/x-www-form-urlencoded` 请求体并执行验证
+#[derive(Debug)]
+pub struct ValidatedForm<T>(pub T);
+
+/// ValidatedPath 提取器
+///
+/// 按 [`PathDe`] 的规则反序列化路径参数并执行验证
+#[derive(Debug)]
+pub struct ValidatedPath<T>(pub T);
+
+fn validate<T>(value: T) -> Result<T, AppError>
+where
+    T: garde::Validate,
+    T::Context: Default,
+{
+    value.validate().map_err(AppError::from)?;
+    Ok(value)
+}
+
+impl<S, T> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + garde::Validate + Send + Sync + 'static,
+    T::Context: Default,
+    S: Send + Sync + 'static,
+{
+    fn from_request_parts(req: &mut Parts, state: Arc<S>) -> FRPFut<'_, Self> {
+        let query = Query::<T>::from_request_parts(req, state);
+        Box::pin(async move { validate(query.await?.0).map(ValidatedQuery) })
+    }
+}
+
+impl<S, T> FromRequest<S> for ValidatedForm<T>
+where
+    T: DeserializeOwned + garde::Validate + Send + Sync + 'static,
+    T::Context: Default,
+    S: Send + Sync + 'static,
+{
+    fn from_request(req: Req, state: Arc<S>) -> FRFut<Self> {
+        let form = Form::<T>::from_request(req, state);
+        Box::pin(async move { validate(form.await?.0).map(ValidatedForm) })
+    }
+}
+
+impl<S, T> FromRequestParts<S> for ValidatedPath<T>
+where
+    T: DeserializeOwned + garde::Validate + Send + Sync + 'static,
+    T::Context: Default,
+    S: Send + Sync + 'static,
+{
+    fn from_request_parts(req: &mut Parts, state: Arc<S>) -> FRPFut<'_, Self> {
+        let path = PathDe::<T>::from_request_parts(req, state);
+        Box::pin(async move { validate(path.await?.0).map(ValidatedPath) })
+    }
+}
+
+macro_rules! impl_deref {
+    ($($name:ident),*) => {$(
+        impl<T> std::ops::Deref for $name<T> {
+            type Target = T;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<T> std::ops::DerefMut for $name<T> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+    )*};
+}
+
+impl_deref!(ValidatedQuery, ValidatedForm, ValidatedPath);
@@ -1,8 +1,8 @@
-/// ValidatedJson 提取器
-///
-/// 自动解析 JSON 并验证，验证失败时自动转换为 AppError::ValidationError
-///
-/// 需要启用 `validation` feature
+//! ValidatedJson 提取器
+//!
+//! 自动解析 JSON 并验证，验证失败时自动转换为 AppError::ValidationError
+//!
+//! 需要启用 `validation` feature
 
 #[cfg(feature = "validation")]
 use crate::error::AppError;
@@ -11,7 +11,6 @@ use crate::extractor::from_request::{FRFut, FromRequest};
 #[cfg(feature = "validation")]
 use crate::handler::Req;
 #[cfg(feature = "validation")]
-use http_body_util::BodyExt;
 #[cfg(feature = "validation")]
 use serde::de::DeserializeOwned;
 #[cfg(feature = "validation")]
@@ -61,19 +60,13 @@ where
     <T as garde::Validate>::Context: Default,
     S: Send + Sync + 'static,
 {
-    fn from_request(mut req: Req, _state: Arc<S>) -> FRFut<Self> {
+    fn from_request(req: Req, _state: Arc<S>) -> FRFut<Self> {
         Box::pin(async move {
-            let body = req
-                .body_mut()
-                .collect()
-                .await
-                .map_err(|e| AppError::BadRequest(format!("Failed to read request body: {}", e)))?
-                .to_bytes();
+            let body = crate::extractor::body_limit::read_body(req).await?;
 
-            let value: T =
-                serde_json::from_slice(&body).map_err(|e| AppError::JsonParseError(e))?;
+            let value: T = serde_json::from_slice(&body).map_err(AppError::JsonParseError)?;
 
-            value.validate().map_err(|report| AppError::from(report))?;
+            value.validate().map_err(AppError::from)?;
 
             Ok(ValidatedJson(value))
         })
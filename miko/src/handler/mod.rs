@@ -1,6 +1,8 @@
 #![allow(non_snake_case)]
 use crate::extractor::from_request::{FRFut, FRPFut, FromRequest, FromRequestParts};
 use crate::http::response::into_response::IntoResponse;
+use crate::http::response::negotiate;
+use hyper::header::ACCEPT;
 use hyper::http::request::Parts;
 use std::convert::Infallible;
 use std::marker::PhantomData;
@@ -122,11 +124,13 @@ where
         let f = self.f.clone();
         let state = self.state.clone();
         Box::pin(async move {
+            let accept = req.headers().get(ACCEPT).cloned();
             let args = A::from_request(req, state.clone()).await;
             match args {
                 Ok(args) => {
-                    let resp = f.call(args).await;
-                    resp.into_response()
+                    let mut resp = f.call(args).await.into_response();
+                    negotiate::finalize(&mut resp, accept.as_ref());
+                    resp
                 }
                 Err(app_error) => app_error.into_response(),
             }
@@ -0,0 +1,72 @@
+//! 统一响应信封
+//!
+//! 成功响应统一包装为 `{ "data": ..., "meta": {...}, "trace_id": "..." }`，
+//! 与错误响应 [`ErrorResponse`](crate::ErrorResponse) 的结构保持一致。
+//!
+//! - 单个处理函数：返回 [`Envelope<T>`]
+//! - 整个 Router / 模块：挂载 [`EnvelopeLayer`](crate::middleware::envelope::EnvelopeLayer)
+
+use crate::error::app_error::get_trace_id;
+use crate::extractor::Json;
+use crate::handler::Resp;
+use crate::http::response::into_response::IntoResponse;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// 信封 JSON 结构
+#[derive(Debug, Serialize)]
+pub struct EnvelopeBody<T> {
+    pub data: T,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub meta: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+}
+
+/// 响应扩展标记：响应体已是信封，[`EnvelopeLayer`](crate::middleware::envelope::EnvelopeLayer) 不再重复包装
+#[derive(Debug, Clone, Copy)]
+pub struct Enveloped;
+
+/// 把成功数据包装为统一信封的响应器
+///
+/// ```rust,ignore
+/// async fn list() -> Envelope<Vec<User>> {
+///     Envelope::new(users).meta("total", 42)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Envelope<T> {
+    data: T,
+    meta: Map<String, Value>,
+}
+
+impl<T> Envelope<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            data,
+            meta: Map::new(),
+        }
+    }
+
+    /// 追加一项 `meta` 字段（如分页信息）
+    pub fn meta(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        self.meta.insert(
+            key.into(),
+            serde_json::to_value(value).unwrap_or(Value::Null),
+        );
+        self
+    }
+}
+
+impl<T: Serialize> IntoResponse for Envelope<T> {
+    fn into_response(self) -> Resp {
+        let mut resp = Json(EnvelopeBody {
+            data: self.data,
+            meta: self.meta,
+            trace_id: get_trace_id(),
+        })
+        .into_response();
+        resp.extensions_mut().insert(Enveloped);
+        resp
+    }
+}
@@ -4,6 +4,7 @@ use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use http_body_util::{BodyExt, Full, StreamBody};
 use hyper::HeaderMap;
+use hyper::header::{HeaderValue, LOCATION};
 use hyper::{Response, StatusCode, body::Frame};
 use serde::Serialize;
 
@@ -15,6 +16,39 @@ pub trait IntoResponse {
     fn into_response(self) -> Resp;
 }
 
+/// [`IntoResponse`] 的对象安全版本，用于在运行时决定返回哪种响应
+///
+/// 所有 `IntoResponse + Send` 类型都自动实现，装箱后即可在不同分支返回不同类型：
+///
+/// ```rust,ignore
+/// #[get("/users/{id}")]
+/// async fn user(#[path] id: u64, #[query] q: Format) -> BoxedResponse {
+///     if q.html {
+///         Box::new(Html(render(id)))
+///     } else {
+///         Box::new(Json(load(id)))
+///     }
+/// }
+/// ```
+pub trait DynIntoResponse: Send {
+    fn into_response_boxed(self: Box<Self>) -> Resp;
+}
+
+impl<T: IntoResponse + Send> DynIntoResponse for T {
+    fn into_response_boxed(self: Box<Self>) -> Resp {
+        (*self).into_response()
+    }
+}
+
+/// 装箱后的任意响应
+pub type BoxedResponse = Box<dyn DynIntoResponse>;
+
+impl IntoResponse for Box<dyn DynIntoResponse> {
+    fn into_response(self) -> Resp {
+        self.into_response_boxed()
+    }
+}
+
 pub fn bytes_to_boxed(bytes: Bytes) -> RespBody {
     Full::new(bytes).map_err(Into::into).boxed_unsync()
 }
@@ -49,6 +83,13 @@ impl<T: Serialize> IntoResponse for Json<T> {
     }
 }
 
+impl<T> Json<T> {
+    /// 以指定状态码返回 JSON，如 `Json(user).with_status(StatusCode::CREATED)`
+    pub fn with_status(self, status: StatusCode) -> (StatusCode, Self) {
+        (status, self)
+    }
+}
+
 /// HTML 响应包装器
 ///
 /// 用于返回 HTML 内容，自动设置 content-type 为 text/html
@@ -123,6 +164,160 @@ where
     }
 }
 
+/// `Some` 返回内部响应，`None` 返回 404
+///
+/// 需要 `None` 时返回 204 的场景可使用 [`OrNoContent`]
+impl<T: IntoResponse> IntoResponse for Option<T> {
+    fn into_response(self) -> Resp {
+        match self {
+            Some(t) => t.into_response(),
+            None => {
+                crate::error::AppError::NotFound("Resource not found".to_string()).into_response()
+            }
+        }
+    }
+}
+
+/// 可选响应：`Some` 返回内部响应，`None` 返回 204 No Content
+///
+/// ```rust,ignore
+/// #[delete("/sessions/{id}")]
+/// async fn logout(Path(id): Path<u64>) -> OrNoContent<Json<Session>> {
+///     sessions().remove(id).map(Json).into()
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrNoContent<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for OrNoContent<T> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: IntoResponse> IntoResponse for OrNoContent<T> {
+    fn into_response(self) -> Resp {
+        match self.0 {
+            Some(t) => t.into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
+        }
+    }
+}
+
+/// 重定向响应，目标写入 `Location` 头
+///
+/// ```rust,ignore
+/// #[post("/login")]
+/// async fn login(Form(form): Form<Login>) -> AppResult<Redirect> {
+///     auth(form).await?;
+///     Ok(Redirect::see_other("/dashboard"))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Redirect {
+    status: StatusCode,
+    location: String,
+}
+
+impl Redirect {
+    /// 308 Permanent Redirect，保留请求方法与请求体
+    pub fn permanent(url: impl Into<String>) -> Self {
+        Self::with_status(StatusCode::PERMANENT_REDIRECT, url)
+    }
+
+    /// 307 Temporary Redirect，保留请求方法与请求体
+    pub fn temporary(url: impl Into<String>) -> Self {
+        Self::with_status(StatusCode::TEMPORARY_REDIRECT, url)
+    }
+
+    /// 303 See Other，客户端改用 GET 访问目标，常用于表单提交之后
+    pub fn see_other(url: impl Into<String>) -> Self {
+        Self::with_status(StatusCode::SEE_OTHER, url)
+    }
+
+    fn with_status(status: StatusCode, url: impl Into<String>) -> Self {
+        Self {
+            status,
+            location: url.into(),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+}
+
+impl IntoResponse for Redirect {
+    fn into_response(self) -> Resp {
+        match HeaderValue::try_from(self.location) {
+            Ok(location) => {
+                let mut response = self.status.into_response();
+                response.headers_mut().insert(LOCATION, location);
+                response
+            }
+            Err(_) => invalid_location(),
+        }
+    }
+}
+
+/// 201 Created，`Location` 指向新建的资源
+///
+/// ```rust,ignore
+/// #[post("/users")]
+/// async fn create(Json(input): Json<NewUser>) -> AppResult<Created<Json<User>>> {
+///     let user = users().insert(input).await?;
+///     Ok(Created(format!("/users/{}", user.id), Json(user)))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Created<T = ()>(pub String, pub T);
+
+impl<T: IntoResponse> IntoResponse for Created<T> {
+    fn into_response(self) -> Resp {
+        let mut response = self.1.into_response();
+        *response.status_mut() = StatusCode::CREATED;
+        match HeaderValue::try_from(self.0) {
+            Ok(location) => {
+                response.headers_mut().insert(LOCATION, location);
+                response
+            }
+            Err(_) => invalid_location(),
+        }
+    }
+}
+
+/// 202 Accepted，请求已受理但尚未处理完成
+#[derive(Debug, Clone, Default)]
+pub struct Accepted<T = ()>(pub T);
+
+impl<T: IntoResponse> IntoResponse for Accepted<T> {
+    fn into_response(self) -> Resp {
+        let mut response = self.0.into_response();
+        *response.status_mut() = StatusCode::ACCEPTED;
+        response
+    }
+}
+
+/// 204 No Content
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoContent;
+
+impl IntoResponse for NoContent {
+    fn into_response(self) -> Resp {
+        StatusCode::NO_CONTENT.into_response()
+    }
+}
+
+/// `Location` 不是合法的头部值（如含换行）时返回 500
+fn invalid_location() -> Resp {
+    crate::error::AppError::InternalServerError("Invalid Location header".to_string())
+        .into_response()
+}
+
 impl IntoResponse for () {
     fn into_response(self) -> Resp {
         Response::builder()
@@ -146,11 +341,9 @@ where
     T: IntoResponse,
 {
     fn into_response(self) -> Resp {
-        let body = self.1.into_response();
-        Response::builder()
-            .status(self.0)
-            .body(body.into_body())
-            .unwrap()
+        let mut response = self.1.into_response();
+        *response.status_mut() = self.0;
+        response
     }
 }
 
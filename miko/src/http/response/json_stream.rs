@@ -0,0 +1,97 @@
+//! 流式 JSON 数组响应
+//!
+//! 逐项序列化 `Stream` 中的元素并写出 `[item,item,...]`，无需先收集成 `Vec`：
+//!
+//! ```rust,ignore
+//! #[get("/rows")]
+//! async fn rows(pool: Dep<Pool>) -> impl IntoResponse {
+//!     JsonArrayStream::new(pool.fetch_rows())
+//! }
+//! ```
+
+use crate::handler::Resp;
+use crate::http::response::into_response::IntoResponse;
+use bytes::Bytes;
+use futures::Stream;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::Response;
+use hyper::body::Frame;
+use miko_core::MikoError;
+use serde::Serialize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// 单个数据帧的目标大小，已就绪的元素会合并写出直到超过该值
+const FRAME_SIZE: usize = 8 * 1024;
+
+/// 将 `Stream<Item = T>` 以 JSON 数组流式写出的响应
+///
+/// 只在客户端读取时才拉取下一批元素，慢客户端会自然地让上游等待。
+/// 元素序列化失败时记录错误并中断响应，客户端会收到不完整的 body 而不是看似合法的数组。
+pub struct JsonArrayStream<S>(pub S);
+
+impl<S> JsonArrayStream<S> {
+    pub fn new(stream: S) -> Self {
+        Self(stream)
+    }
+}
+
+impl<S, T> IntoResponse for JsonArrayStream<S>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: Serialize,
+{
+    fn into_response(self) -> Resp {
+        let encoder = ArrayEncoder {
+            items: Box::pin(self.0),
+            started: false,
+            done: false,
+        };
+        Response::builder()
+            .header("content-type", "application/json")
+            .body(StreamBody::new(encoder).boxed_unsync())
+            .unwrap()
+    }
+}
+
+struct ArrayEncoder<S> {
+    items: Pin<Box<S>>,
+    started: bool,
+    done: bool,
+}
+
+impl<S, T> Stream for ArrayEncoder<S>
+where
+    S: Stream<Item = T>,
+    T: Serialize,
+{
+    type Item = Result<Frame<Bytes>, MikoError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        let mut buf = Vec::new();
+        while buf.len() < FRAME_SIZE {
+            match self.items.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    buf.push(if self.started { b',' } else { b'[' });
+                    self.started = true;
+                    if let Err(e) = serde_json::to_writer(&mut buf, &item) {
+                        tracing::error!("JSON array stream item serialization failed: {}", e);
+                        self.done = true;
+                        return Poll::Ready(Some(Err(MikoError(Box::new(e)))));
+                    }
+                }
+                Poll::Ready(None) => {
+                    buf.extend_from_slice(if self.started { b"]" } else { b"[]" });
+                    self.done = true;
+                    break;
+                }
+                Poll::Pending if buf.is_empty() => return Poll::Pending,
+                Poll::Pending => break,
+            }
+        }
+        Poll::Ready(Some(Ok(Frame::data(Bytes::from(buf)))))
+    }
+}
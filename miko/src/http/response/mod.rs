@@ -1,2 +1,7 @@
+pub mod envelope;
 pub mod into_response;
+pub mod json_stream;
+#[cfg(feature = "ext")]
+pub mod named_file;
+pub mod negotiate;
 pub mod sse;
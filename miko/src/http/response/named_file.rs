@@ -0,0 +1,160 @@
+//! 磁盘文件响应
+//!
+//! [`NamedFile`] 以流的方式返回文件，按扩展名推断 `Content-Type`，并设置 `Content-Length`、
+//! `Content-Disposition`、`ETag` 与 `Last-Modified`。传入 [`RangeRequest`] 后支持断点续传，
+//! 行为与 [`StaticSvc`](crate::ext::static_svc::StaticSvc) 一致：
+//!
+//! ```rust,ignore
+//! #[route("/reports/{id}", method = "get,head")]
+//! async fn report(#[path] id: u64, range: RangeRequest) -> AppResult<NamedFile> {
+//!     Ok(NamedFile::open(format!("./reports/{id}.pdf"))
+//!         .await?
+//!         .attachment()
+//!         .filename(format!("报告-{id}.pdf"))
+//!         .range(range))
+//! }
+//!
+//! // 简单场景直接返回路径，文件不存在时为 404
+//! #[get("/logo")]
+//! async fn logo() -> PathBuf {
+//!     PathBuf::from("./assets/logo.png")
+//! }
+//! ```
+
+use crate::AppError;
+use crate::ext::download::{RangeRequest, ResumableDownload};
+use crate::handler::Resp;
+use crate::http::response::into_response::IntoResponse;
+use hyper::{HeaderMap, Method};
+use std::fs::Metadata;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::fs::File;
+
+/// 磁盘文件响应，默认 `inline`
+#[derive(Debug)]
+pub struct NamedFile {
+    file: File,
+    len: u64,
+    modified: Option<SystemTime>,
+    content_type: String,
+    filename: Option<String>,
+    attachment: bool,
+    range: Option<RangeRequest>,
+}
+
+impl NamedFile {
+    /// 打开文件并读取元数据
+    pub async fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).await?;
+        let metadata = file.metadata().await?;
+        Self::from_file(file, &metadata, path)
+    }
+
+    fn from_file(file: File, metadata: &Metadata, path: &Path) -> io::Result<Self> {
+        if !metadata.is_file() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "not a file"));
+        }
+        let mime = mime_guess::from_path(path).first_or_octet_stream();
+        let content_type = if mime.type_() == mime_guess::mime::TEXT {
+            format!("{}; charset=utf-8", mime)
+        } else {
+            mime.to_string()
+        };
+        Ok(Self {
+            file,
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+            content_type,
+            filename: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            attachment: false,
+            range: None,
+        })
+    }
+
+    /// 覆盖按扩展名推断的 Content-Type
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    /// 设置下载时的文件名，默认取路径中的文件名
+    pub fn filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    /// 以附件形式下载，浏览器会提示保存
+    pub fn attachment(mut self) -> Self {
+        self.attachment = true;
+        self
+    }
+
+    /// 在浏览器中直接显示（默认）
+    pub fn inline(mut self) -> Self {
+        self.attachment = false;
+        self
+    }
+
+    /// 按请求的 `Range`、`If-Range`、`If-None-Match` 与方法生成 206 / 304 / 416 / HEAD 响应
+    pub fn range(mut self, range: RangeRequest) -> Self {
+        self.range = Some(range);
+        self
+    }
+}
+
+impl IntoResponse for NamedFile {
+    fn into_response(self) -> Resp {
+        let etag = match self.modified {
+            Some(modified) => format!(
+                "{:x}-{:x}",
+                modified
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                self.len
+            ),
+            None => format!("{:x}", self.len),
+        };
+        let mut download = ResumableDownload::new(self.file, self.len)
+            .content_type(self.content_type)
+            .etag(etag);
+        if let Some(time) = self.modified {
+            download = download.last_modified(time);
+        }
+        if let Some(filename) = &self.filename {
+            download = if self.attachment {
+                download.attachment(filename)
+            } else {
+                download.inline(filename)
+            };
+        }
+        let range = self
+            .range
+            .unwrap_or_else(|| RangeRequest::new(Method::GET, HeaderMap::new()));
+        download.respond_deferred(&range)
+    }
+}
+
+/// 返回该路径的文件（`inline`，不支持 Range），不存在或不是文件时返回 404
+///
+/// 文件在转换响应时同步打开，大量并发下载请使用 [`NamedFile::open`]。
+impl IntoResponse for PathBuf {
+    fn into_response(self) -> Resp {
+        let opened = std::fs::File::open(&self).and_then(|file| {
+            let metadata = file.metadata()?;
+            NamedFile::from_file(File::from_std(file), &metadata, &self)
+        });
+        match opened {
+            Ok(file) => file.into_response(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                AppError::NotFound("File not found".to_string()).into_response()
+            }
+            Err(e) => AppError::from(e).into_response(),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! 内容协商响应器
+//!
+//! [`Negotiate<T>`] 按请求的 `Accept` 头把 `T` 序列化为 JSON、MessagePack（`msgpack` feature）
+//! 或 CBOR（`cbor` feature），处理函数无需分支：
+//!
+//! ```rust,ignore
+//! async fn user(#[path] id: u64) -> AppResult<Negotiate<User>> {
+//!     Ok(Negotiate(find_user(id).await?))
+//! }
+//! ```
+//!
+//! 未带 `Accept` 或接受 JSON 时返回 JSON；客户端不接受任何支持的格式时返回 406。
+
+use crate::error::AppError;
+use crate::extractor::accepts::Accepts;
+use crate::extractor::typed_header::{Accept, Header};
+use crate::handler::Resp;
+use crate::http::response::into_response::IntoResponse;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::StatusCode;
+use hyper::header::{self, HeaderValue};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// 支持的序列化格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl Format {
+    /// 当前启用的格式，JSON 在前
+    pub const ALL: &'static [Format] = &[
+        Format::Json,
+        #[cfg(feature = "msgpack")]
+        Format::MessagePack,
+        #[cfg(feature = "cbor")]
+        Format::Cbor,
+    ];
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            #[cfg(feature = "msgpack")]
+            Format::MessagePack => "application/msgpack",
+            #[cfg(feature = "cbor")]
+            Format::Cbor => "application/cbor",
+        }
+    }
+
+    /// 按 `Accept` 选择格式，客户端不接受任何启用的格式时返回 `None`
+    pub fn negotiate(accepts: &Accepts) -> Option<Format> {
+        let available: Vec<&str> = Self::ALL.iter().map(|f| f.content_type()).collect();
+        let chosen = accepts.negotiate(&available)?;
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|f| f.content_type() == chosen)
+    }
+
+    /// 以该格式序列化
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Bytes, String> {
+        match self {
+            Format::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            #[cfg(feature = "msgpack")]
+            Format::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| e.to_string()),
+            #[cfg(feature = "cbor")]
+            Format::Cbor => {
+                let mut buf = Vec::new();
+                ciborium::into_writer(value, &mut buf)
+                    .map(|_| buf)
+                    .map_err(|e| e.to_string())
+            }
+        }
+        .map(Bytes::from)
+    }
+}
+
+/// 按 `Accept` 选择序列化格式的响应器
+#[derive(Debug)]
+pub struct Negotiate<T>(pub T);
+
+type Encoder = Arc<dyn Fn(Format) -> Result<Bytes, String> + Send + Sync>;
+
+/// 响应扩展：处理函数返回后按请求的 `Accept` 重新编码
+#[derive(Clone)]
+pub(crate) struct Renegotiate(Encoder);
+
+impl<T: Serialize + Send + Sync + 'static> IntoResponse for Negotiate<T> {
+    fn into_response(self) -> Resp {
+        let value = Arc::new(self.0);
+        let encoder: Encoder = Arc::new(move |format| format.encode(&*value));
+        let mut resp = encoded(Format::Json, &encoder);
+        resp.extensions_mut().insert(Renegotiate(encoder));
+        resp
+    }
+}
+
+fn encoded(format: Format, encoder: &Encoder) -> Resp {
+    match encoder(format) {
+        Ok(body) => {
+            let mut resp = Resp::new(Full::new(body).map_err(Into::into).boxed_unsync());
+            let headers = resp.headers_mut();
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(format.content_type()),
+            );
+            headers.insert(header::VARY, HeaderValue::from_static("accept"));
+            resp
+        }
+        Err(e) => AppError::InternalServerError(format!("Failed to serialize response: {}", e))
+            .into_response(),
+    }
+}
+
+/// 处理函数返回 [`Negotiate`] 时，按请求的 `Accept` 重新编码响应体
+pub(crate) fn finalize(resp: &mut Resp, accept: Option<&HeaderValue>) {
+    let Some(Renegotiate(encoder)) = resp.extensions_mut().remove::<Renegotiate>() else {
+        return;
+    };
+    let Some(accept) = accept else {
+        return;
+    };
+    let format = match Accept::decode(accept) {
+        Ok(accept) => Format::negotiate(&Accepts(accept.0)),
+        // 无法解析的 Accept 按未携带处理
+        Err(_) => Some(Format::Json),
+    };
+    let replacement = match format {
+        Some(format) if format != Format::Json => encoded(format, &encoder),
+        Some(_) => return,
+        None => {
+            let supported: Vec<&str> = Format::ALL.iter().map(|f| f.content_type()).collect();
+            AppError::custom(
+                StatusCode::NOT_ACCEPTABLE,
+                "NOT_ACCEPTABLE",
+                format!("Supported media types: {}", supported.join(", ")),
+            )
+            .into_response()
+        }
+    };
+    let status = if replacement.status().is_success() {
+        resp.status()
+    } else {
+        replacement.status()
+    };
+    let (mut parts, _) = std::mem::take(resp).into_parts();
+    let (new_parts, body) = replacement.into_parts();
+    for name in [header::CONTENT_TYPE, header::CONTENT_LENGTH] {
+        parts.headers.remove(&name);
+    }
+    for (name, value) in new_parts.headers.iter() {
+        parts.headers.insert(name, value.clone());
+    }
+    parts.status = status;
+    *resp = Resp::from_parts(parts, body);
+}
@@ -2,18 +2,22 @@ use crate::extractor::Json;
 use crate::handler::Resp;
 use crate::http::response::into_response::{IntoResponse, SSE};
 use bytes::Bytes;
-use futures::StreamExt;
+use futures::{Stream, StreamExt};
 use serde::Serialize;
 use std::convert::Infallible;
+use std::marker::PhantomData;
 use std::panic;
 use std::panic::{PanicHookInfo, panic_any};
+use std::pin::pin;
+use std::time::Duration;
 use tokio::sync::mpsc::error::SendError;
-use tokio::sync::mpsc::{Sender, channel};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
 use tokio_stream::wrappers::ReceiverStream;
 
 /// 一个 Server-Sent Event 事件对象
 ///
 /// 使用 SseSender::send(…)/event(…) 时可直接传入 &str/String/Json<T>，也可手动构建 SseEvent。
+#[derive(Debug, Clone, PartialEq)]
 pub struct SseEvent {
     pub data: String,
     pub event: Option<String>,
@@ -72,6 +76,10 @@ impl SseEvent {
         self.retry = Some(retry);
         self
     }
+    /// 以 JSON 序列化 `value` 作为 data 字段
+    pub fn json<T: Serialize + ?Sized>(value: &T) -> Self {
+        Self::data(serde_json::to_string(value).unwrap_or_default())
+    }
     pub(crate) fn to_bytes(&self) -> Bytes {
         let mut buf = String::new();
         if let Some(event) = &self.event {
@@ -106,18 +114,193 @@ impl<T: Serialize> From<Json<T>> for SseEvent {
     }
 }
 
+/// 空闲时发送的保活注释，避免代理因长时间无数据断开连接
+#[derive(Debug, Clone)]
+pub struct KeepAlive {
+    interval: Duration,
+    text: String,
+}
+
+impl Default for KeepAlive {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(15),
+            text: "keep-alive".to_string(),
+        }
+    }
+}
+
+impl KeepAlive {
+    /// 默认空闲 15 秒发送一次 `: keep-alive`
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// 设置空闲间隔
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+    /// 设置注释内容
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+    fn to_bytes(&self) -> Bytes {
+        let mut buf = String::new();
+        for line in self.text.lines() {
+            buf.push_str(&format!(": {}\n", line));
+        }
+        if buf.is_empty() {
+            buf.push_str(":\n");
+        }
+        buf.push('\n');
+        Bytes::from(buf)
+    }
+}
+
+/// SSE 连接选项
+#[derive(Debug, Clone)]
+pub struct SseOptions {
+    capacity: usize,
+    keep_alive: Option<KeepAlive>,
+}
+
+impl Default for SseOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 32,
+            keep_alive: Some(KeepAlive::default()),
+        }
+    }
+}
+
+impl SseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// 内部通道容量，缓冲满时 `send` 会等待客户端读取（默认 32）
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+    /// 设置保活策略，`None` 关闭保活（默认每 15 秒）
+    pub fn keep_alive(mut self, keep_alive: Option<KeepAlive>) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+}
+
+/// 由事件流构建的 SSE 响应
+///
+/// ```rust,ignore
+/// let events = tokio_stream::iter(["a", "b"]).map(SseEvent::data);
+/// Sse::from_stream(events).keep_alive(KeepAlive::new().interval(Duration::from_secs(5)))
+/// ```
+pub struct Sse<S> {
+    stream: S,
+    options: SseOptions,
+}
+
+impl<S> Sse<S>
+where
+    S: Stream<Item = SseEvent> + Send + 'static,
+{
+    pub fn from_stream(stream: S) -> Self {
+        Self {
+            stream,
+            options: SseOptions::default(),
+        }
+    }
+    /// 设置保活策略
+    pub fn keep_alive(mut self, keep_alive: KeepAlive) -> Self {
+        self.options.keep_alive = Some(keep_alive);
+        self
+    }
+    /// 关闭保活
+    pub fn no_keep_alive(mut self) -> Self {
+        self.options.keep_alive = None;
+        self
+    }
+    /// 替换全部选项
+    pub fn options(mut self, options: SseOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl<St, E> Sse<futures::stream::Map<St, fn(E) -> SseEvent>>
+where
+    St: Stream<Item = E> + Send + 'static,
+    E: IntoSseEvent + 'static,
+{
+    /// 由可转换为事件的类型（如派生了 `IntoSseEvent` 的枚举）组成的流构建
+    pub fn from_events(stream: St) -> Self {
+        Sse::from_stream(stream.map(E::into_sse_event as fn(E) -> SseEvent))
+    }
+}
+
+impl<S> IntoResponse for Sse<S>
+where
+    S: Stream<Item = SseEvent> + Send + 'static,
+{
+    fn into_response(self) -> Resp {
+        let rx = pump(self.stream, self.options);
+        SSE(ReceiverStream::new(rx).map(Ok::<Bytes, Infallible>)).into_response()
+    }
+}
+
+/// 在后台任务中编码事件并插入保活注释，客户端断开后停止拉取事件流
+fn pump<S>(stream: S, options: SseOptions) -> Receiver<Bytes>
+where
+    S: Stream<Item = SseEvent> + Send + 'static,
+{
+    let (tx, rx) = channel::<Bytes>(options.capacity);
+    crate::spawn_linked("sse stream", async move {
+        let mut stream = pin!(stream);
+        loop {
+            let next = async {
+                match &options.keep_alive {
+                    Some(ka) => match tokio::time::timeout(ka.interval, stream.next()).await {
+                        Ok(event) => event.map(|e| e.to_bytes()),
+                        Err(_) => Some(ka.to_bytes()),
+                    },
+                    None => stream.next().await.map(|e| e.to_bytes()),
+                }
+            };
+            let bytes = tokio::select! {
+                _ = tx.closed() => break,
+                bytes = next => bytes,
+            };
+            let Some(bytes) = bytes else { break };
+            if tx.send(bytes).await.is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
 /// 启动一个 SSE 任务并返回响应
 ///
 /// 参数为一个闭包，框架会创建 SseSender 并在后台任务中运行你的逻辑。
+/// 使用默认的 [`SseOptions`]（空闲 15 秒发送保活注释）。
 pub fn spawn_sse_event<F, Fut>(task: F) -> impl IntoResponse
 where
     F: FnOnce(SseSender) -> Fut + Send + 'static,
     Fut: Future<Output = ()> + Send + 'static,
 {
-    let (tx, rx) = channel::<SseEvent>(32);
-    tokio::spawn(task(SseSender::new(tx)));
-    let stream = ReceiverStream::new(rx).map(|event| Ok::<Bytes, Infallible>(event.to_bytes()));
-    SSE(stream)
+    spawn_sse_event_with(task, SseOptions::default())
+}
+
+/// 以指定选项启动一个 SSE 任务并返回响应
+pub fn spawn_sse_event_with<F, Fut>(task: F, options: SseOptions) -> impl IntoResponse
+where
+    F: FnOnce(SseSender) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let (tx, rx) = channel::<SseEvent>(options.capacity);
+    crate::spawn_linked("sse task", task(SseSender::new(tx)));
+    Sse::from_stream(ReceiverStream::new(rx)).options(options)
 }
 
 impl<F, Fut> IntoResponse for F
@@ -169,6 +352,37 @@ impl SseSender {
 pub trait IntoSseEvent {
     fn into_sse_event(self) -> SseEvent;
 }
+
+/// 事件类型枚举的全部事件名，由 `#[derive(IntoSseEvent)]` 生成，用于 OpenAPI 文档
+pub trait SseEventTypes {
+    const EVENT_NAMES: &'static [&'static str];
+}
+
+/// 只能发送事件枚举 `E` 的 SSE 发送端，用于 `#[sse]` 处理器
+pub struct TypedSseSender<E> {
+    inner: SseSender,
+    _marker: PhantomData<fn(E)>,
+}
+impl<E: IntoSseEvent> TypedSseSender<E> {
+    pub fn new(sender: SseSender) -> Self {
+        Self {
+            inner: sender,
+            _marker: PhantomData,
+        }
+    }
+    /// 发送一个事件，返回结果可调用 or_break()
+    pub async fn send(&self, event: E) -> SseSendResult {
+        self.inner.send_event(event.into_sse_event()).await
+    }
+    /// 渠道是否已关闭（客户端断开）
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+    /// 访问无类型的发送端
+    pub fn untyped(&self) -> &SseSender {
+        &self.inner
+    }
+}
 impl IntoSseEvent for SseEvent {
     fn into_sse_event(self) -> SseEvent {
         self
@@ -0,0 +1,250 @@
+//! 统一的 ID 生成：ULID、UUIDv7 与雪花 ID
+//!
+//! trace id、[`RequestId`](crate::middleware::request_id::RequestId) 等框架内部生成的 ID
+//! 都来自同一个全局生成器，默认 ULID，可在配置中切换：
+//!
+//! ```toml
+//! [id]
+//! generator = "snowflake"   # ulid（默认）/ uuidv7 / snowflake
+//! node_id = 3               # 雪花 ID 的节点号，0..=1023
+//! ```
+//!
+//! 也可在启动时以代码安装，处理器通过 [`Ids`] 提取器或 [`new_id`] 取得新 ID：
+//!
+//! ```rust,ignore
+//! miko::id::install(Snowflake::new(3));
+//!
+//! #[post("/orders")]
+//! async fn create(ids: Ids) -> String {
+//!     ids.generate()
+//! }
+//! ```
+
+use crate::app::config::get_settings;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use hyper::http::request::Parts;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// ID 生成器
+pub trait IdGenerator: Send + Sync + 'static {
+    fn generate(&self) -> String;
+}
+
+impl<F> IdGenerator for F
+where
+    F: Fn() -> String + Send + Sync + 'static,
+{
+    fn generate(&self) -> String {
+        self()
+    }
+}
+
+/// ULID：48 位毫秒时间戳 + 80 位随机数，26 位 Crockford Base32，按时间排序
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ulid;
+
+impl IdGenerator for Ulid {
+    fn generate(&self) -> String {
+        const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+        let random = ((random_u64() as u128) << 64 | random_u64() as u128) & ((1 << 80) - 1);
+        let value = (now_ms() as u128 & ((1 << 48) - 1)) << 80 | random;
+        (0..26)
+            .map(|i| ALPHABET[(value >> (125 - 5 * i)) as usize & 31] as char)
+            .collect()
+    }
+}
+
+/// UUIDv7：以毫秒时间戳开头的 UUID，按时间排序
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidV7;
+
+impl IdGenerator for UuidV7 {
+    fn generate(&self) -> String {
+        let high = (now_ms() & ((1 << 48) - 1)) << 16 | 0x7000 | (random_u64() & 0x0fff);
+        let low = random_u64() & (u64::MAX >> 2) | 1 << 63;
+        format!(
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            high >> 32,
+            (high >> 16) & 0xffff,
+            high & 0xffff,
+            low >> 48,
+            low & ((1 << 48) - 1)
+        )
+    }
+}
+
+/// 雪花 ID：41 位毫秒时间戳 + 10 位节点号 + 12 位序号，十进制输出
+///
+/// 同一毫秒内序号用尽时等待下一毫秒；时钟回拨时沿用上次的时间戳。
+#[derive(Debug)]
+pub struct Snowflake {
+    node_id: u64,
+    epoch_ms: u64,
+    state: Mutex<(u64, u64)>,
+}
+
+impl Snowflake {
+    /// 默认纪元 2020-01-01T00:00:00Z
+    pub const DEFAULT_EPOCH_MS: u64 = 1_577_836_800_000;
+
+    /// # Panics
+    ///
+    /// `node_id` 大于 1023 时 panic。
+    pub fn new(node_id: u16) -> Self {
+        assert!(node_id < 1024, "snowflake node id must be in 0..=1023");
+        Self {
+            node_id: node_id as u64,
+            epoch_ms: Self::DEFAULT_EPOCH_MS,
+            state: Mutex::new((0, 0)),
+        }
+    }
+
+    /// 自定义纪元（Unix 毫秒）
+    pub fn epoch_ms(mut self, epoch_ms: u64) -> Self {
+        self.epoch_ms = epoch_ms;
+        self
+    }
+
+    pub fn next_id(&self) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let (last, seq) = *state;
+        let mut now = now_ms().saturating_sub(self.epoch_ms).max(last);
+        let seq = if now == last {
+            let seq = (seq + 1) & 0xfff;
+            if seq == 0 {
+                while now <= last {
+                    std::thread::yield_now();
+                    now = now_ms().saturating_sub(self.epoch_ms);
+                }
+            }
+            seq
+        } else {
+            0
+        };
+        *state = (now, seq);
+        (now & ((1 << 41) - 1)) << 22 | self.node_id << 12 | seq
+    }
+}
+
+impl IdGenerator for Snowflake {
+    fn generate(&self) -> String {
+        self.next_id().to_string()
+    }
+}
+
+/// 配置中可选的生成器
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdKind {
+    #[default]
+    Ulid,
+    Uuidv7,
+    Snowflake,
+}
+
+/// `[id]` 配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdSettings {
+    pub generator: IdKind,
+    /// 雪花 ID 的节点号
+    pub node_id: u16,
+    /// 雪花 ID 的纪元（Unix 毫秒）
+    pub epoch_ms: u64,
+}
+
+impl Default for IdSettings {
+    fn default() -> Self {
+        Self {
+            generator: IdKind::Ulid,
+            node_id: 0,
+            epoch_ms: Snowflake::DEFAULT_EPOCH_MS,
+        }
+    }
+}
+
+impl IdSettings {
+    /// 按配置创建生成器，节点号无效时退回 ULID 并记录错误
+    pub fn build(&self) -> Arc<dyn IdGenerator> {
+        match self.generator {
+            IdKind::Ulid => Arc::new(Ulid),
+            IdKind::Uuidv7 => Arc::new(UuidV7),
+            IdKind::Snowflake if self.node_id < 1024 => {
+                Arc::new(Snowflake::new(self.node_id).epoch_ms(self.epoch_ms))
+            }
+            IdKind::Snowflake => {
+                tracing::error!(
+                    node_id = self.node_id,
+                    "snowflake node id must be in 0..=1023, using ULID"
+                );
+                Arc::new(Ulid)
+            }
+        }
+    }
+}
+
+static INSTALLED: RwLock<Option<Arc<dyn IdGenerator>>> = RwLock::new(None);
+
+/// 安装全局生成器，替换配置中的设置
+pub fn install(generator: impl IdGenerator) {
+    *INSTALLED.write().unwrap() = Some(Arc::new(generator));
+}
+
+/// 当前的全局生成器：已安装的，否则按 `[id]` 配置创建
+pub fn generator() -> Arc<dyn IdGenerator> {
+    static CONFIGURED: OnceLock<Arc<dyn IdGenerator>> = OnceLock::new();
+    if let Some(generator) = INSTALLED.read().unwrap().as_ref() {
+        return generator.clone();
+    }
+    CONFIGURED
+        .get_or_init(|| {
+            get_settings()
+                .get::<IdSettings>("id")
+                .unwrap_or_default()
+                .build()
+        })
+        .clone()
+}
+
+/// 用全局生成器生成一个 ID
+pub fn new_id() -> String {
+    generator().generate()
+}
+
+/// 全局 ID 生成器提取器
+#[derive(Clone)]
+pub struct Ids(pub Arc<dyn IdGenerator>);
+
+impl Ids {
+    pub fn generate(&self) -> String {
+        self.0.generate()
+    }
+}
+
+impl<S> FromRequestParts<S> for Ids {
+    fn from_request_parts(_req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let ids = Ids(generator());
+        Box::pin(async move { Ok(ids) })
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// 非密码学安全的随机数，进程内以随机密钥对计数器做哈希
+fn random_u64() -> u64 {
+    static STATE: OnceLock<RandomState> = OnceLock::new();
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    STATE
+        .get_or_init(RandomState::new)
+        .hash_one(COUNTER.fetch_add(1, Ordering::Relaxed))
+}
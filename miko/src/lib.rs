@@ -8,14 +8,22 @@ pub use miko_macros as macros;
 
 #[cfg(feature = "auto")]
 pub mod auto;
+#[cfg(feature = "utoipa")]
+pub mod codegen;
+pub mod context;
 pub mod dependency_container;
 pub mod endpoint;
 pub mod error;
 pub mod extractor;
 pub mod http;
+pub mod id;
+#[cfg(all(feature = "auto", feature = "utoipa"))]
+pub mod openapi;
 pub mod router;
 #[cfg(feature = "test")]
 pub mod test;
+#[cfg(feature = "ext")]
+pub(crate) mod utils;
 pub mod ws;
 
 pub mod middleware;
@@ -38,8 +46,10 @@ pub use utoipa::{self, IntoParams, OpenApi, ToResponse, ToSchema};
 #[cfg(feature = "validation")]
 pub use garde::{self, Validate};
 
+pub use context::{spawn_linked, spawn_with_context};
+
 // 导出常用的响应类型
-pub use http::response::into_response::IntoResponse;
+pub use http::response::into_response::{BoxedResponse, DynIntoResponse, IntoResponse};
 
 // 导出错误处理类型
 pub use error::{AppError, AppResult, ErrorResponse, ValidationErrorDetail};
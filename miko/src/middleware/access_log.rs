@@ -0,0 +1,300 @@
+use crate::app::config::{AccessLogSettings, LogRotation};
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult};
+use http_body::Body;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::mpsc;
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 访问日志输出端的非阻塞句柄
+///
+/// 日志行先放入有界队列，由后台任务写入，磁盘慢时不会阻塞请求处理；
+/// 队列已满时丢弃该行并计数
+#[derive(Clone)]
+pub struct AccessLogWriter {
+    tx: mpsc::Sender<String>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl AccessLogWriter {
+    /// 按 `settings` 启动后台文件写入任务
+    ///
+    /// 需要在 tokio 运行时中调用
+    pub async fn file(settings: AccessLogSettings) -> io::Result<Self> {
+        let appender = FileAppender::open(settings.clone()).await?;
+        let (tx, rx) = mpsc::channel(settings.buffer.max(1));
+        tokio::spawn(appender.run(rx, Duration::from_millis(settings.flush_interval_ms)));
+        Ok(Self {
+            tx,
+            dropped: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// 将一行日志放入队列，不等待写入
+    pub fn write_line(&self, line: String) {
+        if self.tx.try_send(line).is_err() {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if dropped.is_power_of_two() {
+                tracing::warn!(dropped, "access log queue full, dropping lines");
+            }
+        }
+    }
+
+    /// 因队列已满被丢弃的行数
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+struct FileAppender {
+    settings: AccessLogSettings,
+    path: PathBuf,
+    writer: BufWriter<File>,
+    size: u64,
+    period: u64,
+}
+
+impl FileAppender {
+    async fn open(settings: AccessLogSettings) -> io::Result<Self> {
+        let path = PathBuf::from(&settings.path);
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let (writer, size) = open_append(&path).await?;
+        Ok(Self {
+            period: current_period(settings.rotation),
+            settings,
+            path,
+            writer,
+            size,
+        })
+    }
+
+    async fn run(mut self, mut rx: mpsc::Receiver<String>, flush_interval: Duration) {
+        let mut ticker = tokio::time::interval(flush_interval.max(Duration::from_millis(10)));
+        loop {
+            tokio::select! {
+                line = rx.recv() => match line {
+                    Some(line) => {
+                        if let Err(err) = self.write(line.as_bytes()).await {
+                            tracing::error!(error = %err, "failed to write access log");
+                        }
+                    }
+                    None => break,
+                },
+                _ = ticker.tick() => {
+                    let _ = self.writer.flush().await;
+                }
+            }
+        }
+        let _ = self.writer.flush().await;
+    }
+
+    async fn write(&mut self, line: &[u8]) -> io::Result<()> {
+        if self.should_rotate(line.len() as u64 + 1) {
+            self.rotate().await?;
+        }
+        self.writer.write_all(line).await?;
+        self.writer.write_all(b"\n").await?;
+        self.size += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn should_rotate(&self, incoming: u64) -> bool {
+        if let Some(max_size) = self.settings.max_size
+            && self.size > 0
+            && self.size + incoming > max_size
+        {
+            return true;
+        }
+        self.settings.rotation != LogRotation::Never
+            && current_period(self.settings.rotation) != self.period
+    }
+
+    async fn rotate(&mut self) -> io::Result<()> {
+        self.writer.flush().await?;
+        let secs = unix_secs();
+        let mut rotated = rotated_name(&self.path, secs, 0);
+        let mut n = 0;
+        while tokio::fs::try_exists(&rotated).await.unwrap_or(false) {
+            n += 1;
+            rotated = rotated_name(&self.path, secs, n);
+        }
+        tokio::fs::rename(&self.path, &rotated).await?;
+        let (writer, size) = open_append(&self.path).await?;
+        self.writer = writer;
+        self.size = size;
+        self.period = current_period(self.settings.rotation);
+        if self.settings.max_files > 0 {
+            self.prune().await?;
+        }
+        Ok(())
+    }
+
+    /// 删除超出 max_files 的最旧历史文件
+    async fn prune(&self) -> io::Result<()> {
+        let Some(file_name) = self.path.file_name().and_then(|f| f.to_str()) else {
+            return Ok(());
+        };
+        let prefix = format!("{}.", file_name);
+        let dir = match self.path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let mut rotated = Vec::new();
+        let mut entries = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with(&prefix) {
+                let modified = entry.metadata().await?.modified().unwrap_or(UNIX_EPOCH);
+                rotated.push((modified, entry.path()));
+            }
+        }
+        if rotated.len() > self.settings.max_files {
+            rotated.sort();
+            let excess = rotated.len() - self.settings.max_files;
+            for (_, path) in rotated.into_iter().take(excess) {
+                tokio::fs::remove_file(path).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn open_append(path: &Path) -> io::Result<(BufWriter<File>, u64)> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    let size = file.metadata().await?.len();
+    Ok((BufWriter::new(file), size))
+}
+
+fn rotated_name(path: &Path, secs: u64, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    if n == 0 {
+        name.push(format!(".{}", secs));
+    } else {
+        name.push(format!(".{}-{}", secs, n));
+    }
+    PathBuf::from(name)
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn current_period(rotation: LogRotation) -> u64 {
+    match rotation {
+        LogRotation::Never => 0,
+        LogRotation::Hourly => unix_secs() / 3600,
+        LogRotation::Daily => unix_secs() / 86400,
+    }
+}
+
+/// 每个请求向 [`AccessLogWriter`] 记录一行访问日志的 Layer
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::app::config::AccessLogSettings;
+/// use miko::middleware::access_log::{AccessLogLayer, AccessLogWriter};
+///
+/// let writer = AccessLogWriter::file(AccessLogSettings::default()).await?;
+/// router.with_layer(AccessLogLayer::new(writer));
+/// ```
+#[derive(Clone)]
+pub struct AccessLogLayer {
+    writer: AccessLogWriter,
+}
+
+impl AccessLogLayer {
+    pub fn new(writer: AccessLogWriter) -> Self {
+        Self { writer }
+    }
+}
+
+impl<S> Layer<S> for AccessLogLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = AccessLogService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessLogService {
+            inner: BoxCloneService::new(inner),
+            writer: self.writer.clone(),
+        }
+    }
+}
+
+/// 由 [`AccessLogLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct AccessLogService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    writer: AccessLogWriter,
+}
+
+impl Service<Req> for AccessLogService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let writer = self.writer.clone();
+        let request_line = format!("{} {} {:?}", req.method(), req.uri(), req.version());
+        let user_agent = req
+            .headers()
+            .get(hyper::header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("-")
+            .to_string();
+        let start = Instant::now();
+        Box::pin(async move {
+            let result = inner.call(req).await;
+            let (status, bytes) = match &result {
+                Ok(resp) => (
+                    resp.status().as_u16(),
+                    resp.body()
+                        .size_hint()
+                        .exact()
+                        .map(|n| n.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Err(err) => (err.status_code().as_u16(), "-".to_string()),
+            };
+            writer.write_line(format!(
+                "[{}] \"{}\" {} {} {}ms \"{}\"",
+                httpdate::fmt_http_date(SystemTime::now()),
+                request_line,
+                status,
+                bytes,
+                start.elapsed().as_millis(),
+                user_agent
+            ));
+            result
+        })
+    }
+}
@@ -0,0 +1,69 @@
+use crate::AppError;
+use crate::extractor::body_limit::BodyLimit;
+use crate::miko_core::{Req, Resp};
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 为所有请求设置默认的请求体大小上限
+///
+/// 读取请求体的提取器遇到超出上限的请求体时返回 `413 Payload Too Large`。
+/// 路由上的 `#[body(limit = "...")]` 优先级更高
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::body_limit::BodyLimitLayer;
+///
+/// router.with_layer(BodyLimitLayer::new(2 * 1024 * 1024));
+/// ```
+#[derive(Clone, Copy)]
+pub struct BodyLimitLayer {
+    limit: usize,
+}
+
+impl BodyLimitLayer {
+    /// 创建上限为 `limit` 字节的 Layer
+    pub fn new(limit: usize) -> Self {
+        Self { limit }
+    }
+}
+
+impl<S> Layer<S> for BodyLimitLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = BodyLimitService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BodyLimitService {
+            inner: BoxCloneService::new(inner),
+            limit: self.limit,
+        }
+    }
+}
+
+/// 由 [`BodyLimitLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct BodyLimitService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    limit: usize,
+}
+
+impl Service<Req> for BodyLimitService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = <BoxCloneService<Req, Resp, AppError> as Service<Req>>::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Req) -> Self::Future {
+        if req.extensions().get::<BodyLimit>().is_none() {
+            req.extensions_mut().insert(BodyLimit(self.limit));
+        }
+        self.inner.call(req)
+    }
+}
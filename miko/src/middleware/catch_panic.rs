@@ -0,0 +1,189 @@
+use crate::http::response::sse::SseClientDisconnected;
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult, IntoResponse};
+use futures::FutureExt;
+use hyper::{Method, Uri};
+use std::any::Any;
+use std::fmt;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+type Responder = Arc<dyn Fn(&CaughtPanic) -> Resp + Send + Sync>;
+type Reporter = Arc<dyn Fn(&CaughtPanic) + Send + Sync>;
+
+/// 将处理函数中的 panic 转换为响应的 Layer
+///
+/// 未启用时 panic 会沿 hyper 展开并直接断开连接；启用后客户端收到 500
+/// [`AppError::InternalServerError`]，panic 以 error 级别上报到 `tracing`。
+/// 两者分别可以通过 [`responder`](Self::responder) 与 [`on_panic`](Self::on_panic) 替换。
+///
+/// [`SseSendResult::or_break`](crate::http::response::sse::SseSendResult::or_break) 触发的 panic
+/// 只表示客户端已断开，不会被上报，与 [`set_sse_panic_hook`](crate::http::response::sse::set_sse_panic_hook) 的行为一致
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::catch_panic::CatchPanicLayer;
+///
+/// router.with_layer(
+///     CatchPanicLayer::new()
+///         .on_panic(|panic| metrics::counter!("panics").increment(1))
+///         .responder(|panic| AppError::ServiceUnavailable(panic.message().into()).into_response()),
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct CatchPanicLayer {
+    responder: Option<Responder>,
+    reporter: Option<Reporter>,
+}
+
+impl CatchPanicLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 自定义捕获到 panic 后返回的响应，替代默认的 500
+    pub fn responder<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&CaughtPanic) -> Resp + Send + Sync + 'static,
+    {
+        self.responder = Some(Arc::new(f));
+        self
+    }
+
+    /// 自定义 panic 的上报方式，替代默认的 `tracing::error!` 日志
+    pub fn on_panic<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&CaughtPanic) + Send + Sync + 'static,
+    {
+        self.reporter = Some(Arc::new(f));
+        self
+    }
+}
+
+impl<S> Layer<S> for CatchPanicLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = CatchPanicService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CatchPanicService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        }
+    }
+}
+
+/// 由 [`CatchPanicLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct CatchPanicService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: CatchPanicLayer,
+}
+
+impl Service<Req> for CatchPanicService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let layer = self.layer.clone();
+        let method = req.method().clone();
+        let uri = req.uri().clone();
+        Box::pin(async move {
+            // 处理函数在构造 future 时也可能 panic
+            let result = match std::panic::catch_unwind(AssertUnwindSafe(|| inner.call(req))) {
+                Ok(fut) => AssertUnwindSafe(fut).catch_unwind().await,
+                Err(payload) => Err(payload),
+            };
+            match result {
+                Ok(resp) => resp,
+                Err(payload) => Ok(layer.handle(CaughtPanic {
+                    payload,
+                    method,
+                    uri,
+                })),
+            }
+        })
+    }
+}
+
+impl CatchPanicLayer {
+    fn handle(&self, panic: CaughtPanic) -> Resp {
+        if !panic.is_client_disconnected() {
+            match &self.reporter {
+                Some(report) => report(&panic),
+                None => tracing::error!(
+                    method = %panic.method,
+                    uri = %panic.uri,
+                    "handler panicked: {}",
+                    panic.message()
+                ),
+            }
+        }
+        match &self.responder {
+            Some(respond) => respond(&panic),
+            None => AppError::from_panic(panic.payload).into_response(),
+        }
+    }
+}
+
+/// [`CatchPanicLayer`] 捕获到的 panic 及其所属请求的信息
+pub struct CaughtPanic {
+    payload: Box<dyn Any + Send>,
+    method: Method,
+    uri: Uri,
+}
+
+impl CaughtPanic {
+    /// 传给 `panic!` 的原始值
+    pub fn payload(&self) -> &(dyn Any + Send) {
+        &*self.payload
+    }
+
+    /// panic 消息，载荷不是字符串时为 `"Unknown panic"`
+    pub fn message(&self) -> &str {
+        if let Some(s) = self.payload.downcast_ref::<&str>() {
+            s
+        } else if let Some(s) = self.payload.downcast_ref::<String>() {
+            s
+        } else {
+            "Unknown panic"
+        }
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// 是否为 SSE `or_break` 在客户端断开时发出的信号
+    pub fn is_client_disconnected(&self) -> bool {
+        self.payload.is::<SseClientDisconnected>()
+    }
+}
+
+impl fmt::Debug for CaughtPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CaughtPanic")
+            .field("message", &self.message())
+            .field("method", &self.method)
+            .field("uri", &self.uri)
+            .finish()
+    }
+}
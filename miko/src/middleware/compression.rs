@@ -0,0 +1,186 @@
+//! 响应压缩与请求解压
+//!
+//! 对 `tower_http` 压缩层的薄封装，输入输出均为 Miko 的请求/响应体类型，
+//! 可以直接传给 [`Router::with_layer`](crate::router::Router::with_layer)：
+//!
+//! ```rust,ignore
+//! // 使用默认设置：gzip/br/zstd 压缩响应，并解压请求体
+//! router.with_compression();
+//!
+//! // 或者自选算法与阈值
+//! router.with_layer(
+//!     CompressionLayer::new()
+//!         .zstd(false)
+//!         .min_size(1024)
+//!         .content_types(["text/", "application/json"]),
+//! );
+//! router.with_layer(DecompressionLayer::new());
+//! ```
+
+use crate::AppError;
+use crate::miko_core::{MikoError, Req, Resp};
+use crate::router::HttpSvc;
+use http_body::Body;
+use http_body_util::BodyExt;
+use hyper::header::CONTENT_TYPE;
+use hyper::{Request, Response};
+use std::sync::Arc;
+use tower::util::BoxCloneService;
+use tower::{Layer, Service, ServiceExt};
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+use tower_http::decompression::{DecompressionBody, RequestDecompressionLayer};
+
+/// 按客户端的 `Accept-Encoding` 压缩响应体
+///
+/// 默认启用 gzip、brotli 与 zstd。小于 [`min_size`](Self::min_size) 的响应、图片、gRPC 与 SSE 不会被压缩
+#[derive(Debug, Clone)]
+pub struct CompressionLayer {
+    gzip: bool,
+    br: bool,
+    zstd: bool,
+    min_size: u16,
+    content_types: Option<Arc<[String]>>,
+}
+
+impl Default for CompressionLayer {
+    fn default() -> Self {
+        Self {
+            gzip: true,
+            br: true,
+            zstd: true,
+            min_size: 32,
+            content_types: None,
+        }
+    }
+}
+
+impl CompressionLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 启用或禁用 gzip
+    pub fn gzip(mut self, enable: bool) -> Self {
+        self.gzip = enable;
+        self
+    }
+
+    /// 启用或禁用 brotli
+    pub fn br(mut self, enable: bool) -> Self {
+        self.br = enable;
+        self
+    }
+
+    /// 启用或禁用 zstd
+    pub fn zstd(mut self, enable: bool) -> Self {
+        self.zstd = enable;
+        self
+    }
+
+    /// 小于 `bytes` 的响应不压缩（默认 32）
+    pub fn min_size(mut self, bytes: u16) -> Self {
+        self.min_size = bytes;
+        self
+    }
+
+    /// 只压缩 `Content-Type` 以 `prefixes` 之一开头的响应
+    pub fn content_types<I, T>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.content_types = Some(prefixes.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+/// 由 [`CompressionLayer`] 的设置构建的压缩判定条件
+#[derive(Clone)]
+struct ShouldCompress {
+    min_size: u16,
+    content_types: Option<Arc<[String]>>,
+}
+
+impl Predicate for ShouldCompress {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: Body,
+    {
+        let defaults = SizeAbove::new(self.min_size)
+            .and(NotForContentType::GRPC)
+            .and(NotForContentType::IMAGES)
+            .and(NotForContentType::SSE);
+        if !defaults.should_compress(response) {
+            return false;
+        }
+        let Some(prefixes) = &self.content_types else {
+            return true;
+        };
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+        prefixes
+            .iter()
+            .any(|p| content_type.starts_with(p.as_str()))
+    }
+}
+
+impl<S> Layer<S> for CompressionLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = HttpSvc<Req>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        let compression = tower_http::compression::CompressionLayer::new()
+            .gzip(self.gzip)
+            .br(self.br)
+            .zstd(self.zstd)
+            .compress_when(ShouldCompress {
+                min_size: self.min_size,
+                content_types: self.content_types.clone(),
+            })
+            .layer(inner)
+            .map_response(|resp: Response<_>| {
+                resp.map(|body| body.map_err(MikoError::from).boxed_unsync())
+            });
+        BoxCloneService::new(compression)
+    }
+}
+
+/// 解压 `Content-Encoding` 为 `gzip`、`br` 或 `zstd` 的请求体
+///
+/// 不支持的编码返回 `415 Unsupported Media Type`。请求体大小限制由提取器在读取时检查，
+/// 因此作用于解压后的大小
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecompressionLayer;
+
+impl DecompressionLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for DecompressionLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = HttpSvc<Req>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        let inner = inner.map_request(|req: Request<DecompressionBody<_>>| {
+            req.map(|body| body.map_err(MikoError::from).boxed_unsync())
+        });
+        let decompression =
+            RequestDecompressionLayer::new()
+                .layer(inner)
+                .map_response(|resp: Response<_>| {
+                    resp.map(|body| body.map_err(MikoError::from).boxed_unsync())
+                });
+        BoxCloneService::new(decompression)
+    }
+}
@@ -0,0 +1,169 @@
+use crate::IntoResponse;
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult};
+use cookie::time::{Date, Month};
+use hyper::header::{HeaderName, HeaderValue, LINK};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::task::{Context, Poll};
+use std::time::SystemTime;
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+static DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+static SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+static HITS: LazyLock<Mutex<BTreeMap<String, Arc<AtomicU64>>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// 启动以来各废弃路由处理的请求数，键为传给 [`DeprecationLayer::new`] 的路由名
+///
+/// 从未被调用的路由记为 `0`，计数一直为零说明该端点已没有调用方，可以移除
+pub fn deprecated_route_hits() -> BTreeMap<String, u64> {
+    HITS.lock()
+        .unwrap()
+        .iter()
+        .map(|(route, hits)| (route.clone(), hits.load(Ordering::Relaxed)))
+        .collect()
+}
+
+/// 将路由标记为已废弃
+///
+/// 所有响应（包括错误响应）都会带上 `Deprecation: true`；设置了移除日期时附加 `Sunset`，
+/// 设置了迁移文档时附加 `Link: <url>; rel="deprecation"`。每个请求都会累加该路由在
+/// [`deprecated_route_hits`] 中的计数
+///
+/// 路由宏会根据 `#[deprecated_route(...)]` 生成该 Layer
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::deprecation::DeprecationLayer;
+///
+/// router.get("/v1/users", list_users).with_layer(
+///     DeprecationLayer::new("/v1/users")
+///         .sunset("2025-12-31")
+///         .link("https://example.com/migrate-to-v2"),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct DeprecationLayer {
+    sunset: Option<HeaderValue>,
+    link: Option<HeaderValue>,
+    hits: Arc<AtomicU64>,
+}
+
+impl DeprecationLayer {
+    /// 废弃路由，`route` 作为其在 [`deprecated_route_hits`] 中的名称
+    pub fn new(route: &str) -> Self {
+        let hits = HITS
+            .lock()
+            .unwrap()
+            .entry(route.to_string())
+            .or_default()
+            .clone();
+        Self {
+            sunset: None,
+            link: None,
+            hits,
+        }
+    }
+
+    /// 路由可能被移除的日期，格式为 `YYYY-MM-DD`（当天结束，UTC）或 HTTP 日期
+    ///
+    /// # Panics
+    ///
+    /// `date` 不属于以上两种格式时 panic
+    pub fn sunset(mut self, date: &str) -> Self {
+        let at = parse_sunset(date)
+            .unwrap_or_else(|| panic!("invalid sunset date `{}`, expected YYYY-MM-DD", date));
+        self.sunset = Some(HeaderValue::try_from(httpdate::fmt_http_date(at)).unwrap());
+        self
+    }
+
+    /// 说明替代方案的文档地址，以 `Link` 头发送
+    ///
+    /// # Panics
+    ///
+    /// `url` 不能作为头部值时 panic
+    pub fn link(mut self, url: &str) -> Self {
+        self.link = Some(
+            HeaderValue::try_from(format!("<{}>; rel=\"deprecation\"", url))
+                .unwrap_or_else(|e| panic!("invalid deprecation link `{}`: {}", url, e)),
+        );
+        self
+    }
+
+    fn apply(&self, resp: &mut Resp) {
+        let headers = resp.headers_mut();
+        headers.insert(DEPRECATION.clone(), HeaderValue::from_static("true"));
+        if let Some(sunset) = &self.sunset {
+            headers.insert(SUNSET.clone(), sunset.clone());
+        }
+        if let Some(link) = &self.link {
+            headers.append(LINK, link.clone());
+        }
+    }
+}
+
+fn parse_sunset(date: &str) -> Option<SystemTime> {
+    if let Ok(at) = httpdate::parse_http_date(date) {
+        return Some(at);
+    }
+    let mut parts = date.trim().splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = Month::try_from(parts.next()?.parse::<u8>().ok()?).ok()?;
+    let day = parts.next()?.parse().ok()?;
+    let end_of_day = Date::from_calendar_date(year, month, day)
+        .ok()?
+        .with_hms(23, 59, 59)
+        .ok()?
+        .assume_utc();
+    Some(end_of_day.into())
+}
+
+impl<S> Layer<S> for DeprecationLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = DeprecationService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DeprecationService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        }
+    }
+}
+
+/// 由 [`DeprecationLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct DeprecationService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: DeprecationLayer,
+}
+
+impl Service<Req> for DeprecationService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        self.layer.hits.fetch_add(1, Ordering::Relaxed);
+        let future = self.inner.call(req);
+        let layer = self.layer.clone();
+        Box::pin(async move {
+            let mut resp = future.await.unwrap_or_else(|e| e.into_response());
+            layer.apply(&mut resp);
+            Ok(resp)
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use crate::error::app_error::get_trace_id;
+use crate::http::response::envelope::{EnvelopeBody, Enveloped};
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 将成功的 JSON 响应包装为 `{ "data": ..., "meta": {...}, "trace_id": ... }`
+///
+/// 只包装 2xx 的 `application/json` 响应；错误响应保持 `ErrorResponse` 结构，
+/// 已由 `Envelope<T>` 构建的响应原样返回。`trace_id` 取自当前请求作用域，
+/// 缺失时退回请求头 `x-trace-id` / `x-request-id`
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::envelope::EnvelopeLayer;
+///
+/// api.with_layer(EnvelopeLayer::new().meta("api_version", "2"));
+///
+/// #[layer(EnvelopeLayer::new())]
+/// mod users { /* ... */ }
+/// ```
+#[derive(Clone, Default)]
+pub struct EnvelopeLayer {
+    meta: Arc<Map<String, Value>>,
+}
+
+impl EnvelopeLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 为每个信封追加一项固定的 `meta` 字段
+    pub fn meta(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        Arc::make_mut(&mut self.meta).insert(
+            key.into(),
+            serde_json::to_value(value).unwrap_or(Value::Null),
+        );
+        self
+    }
+}
+
+impl<S> Layer<S> for EnvelopeLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = EnvelopeService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        EnvelopeService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        }
+    }
+}
+
+/// 由 [`EnvelopeLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct EnvelopeService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: EnvelopeLayer,
+}
+
+impl Service<Req> for EnvelopeService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let meta = self.layer.meta.clone();
+        let header_trace_id = req
+            .headers()
+            .get("x-trace-id")
+            .or_else(|| req.headers().get("x-request-id"))
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        Box::pin(async move {
+            let resp = inner.call(req).await?;
+            if !resp.status().is_success()
+                || resp.extensions().get::<Enveloped>().is_some()
+                || !is_json(&resp)
+            {
+                return Ok(resp);
+            }
+            let (mut parts, body) = resp.into_parts();
+            let bytes = body.collect().await?.to_bytes();
+            let Ok(data) = serde_json::from_slice::<Value>(&bytes) else {
+                let body = Full::new(bytes).map_err(Into::into).boxed_unsync();
+                return Ok(Resp::from_parts(parts, body));
+            };
+            let envelope = EnvelopeBody {
+                data,
+                meta: (*meta).clone(),
+                trace_id: get_trace_id().or(header_trace_id),
+            };
+            let bytes = serde_json::to_vec(&envelope).map_err(AppError::JsonParseError)?;
+            parts.headers.remove(CONTENT_LENGTH);
+            parts.extensions.insert(Enveloped);
+            let body = Full::new(Bytes::from(bytes))
+                .map_err(Into::into)
+                .boxed_unsync();
+            Ok(Resp::from_parts(parts, body))
+        })
+    }
+}
+
+fn is_json(resp: &Resp) -> bool {
+    resp.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|ct| ct.split(';').next())
+        .is_some_and(|mime| mime.trim() == "application/json")
+}
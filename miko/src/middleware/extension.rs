@@ -0,0 +1,71 @@
+use crate::miko_core::Req;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// 向每个请求的扩展中写入 `value` 的克隆
+///
+/// 处理函数通过 [`Extension`](crate::extractor::Extension) 提取器读取。
+/// 只有在该层之后添加的中间件（以及处理函数）能看到这个值
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::extension::ExtensionLayer;
+///
+/// router.with_layer(ExtensionLayer::new(Arc::new(FeatureFlags::load())));
+/// // 或者直接
+/// router.layer_extension(Arc::new(FeatureFlags::load()));
+/// ```
+#[derive(Clone)]
+pub struct ExtensionLayer<T> {
+    value: T,
+}
+
+impl<T> ExtensionLayer<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+}
+
+impl<S, T> Layer<S> for ExtensionLayer<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type Service = ExtensionService<S, T>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ExtensionService {
+            inner,
+            value: self.value.clone(),
+        }
+    }
+}
+
+/// 由 [`ExtensionLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct ExtensionService<S, T> {
+    inner: S,
+    value: T,
+}
+
+impl<S, T> Service<Req> for ExtensionService<S, T>
+where
+    S: Service<Req>,
+    T: Clone + Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Req) -> Self::Future {
+        req.extensions_mut().insert(self.value.clone());
+        self.inner.call(req)
+    }
+}
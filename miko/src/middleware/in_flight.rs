@@ -0,0 +1,185 @@
+use crate::extractor::ClientAddr;
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult, IntoResponse};
+use http_body_util::BodyExt;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+type KeyFn = Arc<dyn Fn(&Req) -> Option<String> + Send + Sync>;
+type Slots = Arc<Mutex<HashMap<String, Arc<Semaphore>>>>;
+
+/// 限制每个客户端同时处理中的请求数
+///
+/// 请求按客户端键分组，默认为对端 IP（见 [`ClientAddr`]）。某个客户端已有 `max` 个请求在处理时，
+/// 后续请求直接返回 `429 Too Many Requests`，或最多等待 [`InFlightLimitLayer::wait`] 指定的时长获取槽位。
+/// 槽位在响应体发送完毕后才释放，因此长时间的流式响应也计入在内。没有键的请求不受限制
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::in_flight::InFlightLimitLayer;
+///
+/// router.with_layer(
+///     InFlightLimitLayer::new(8)
+///         .wait(Duration::from_millis(200))
+///         // 按 API token 而不是 IP 限制
+///         .key(|req| req.headers().get("x-api-key")?.to_str().ok().map(str::to_string)),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct InFlightLimitLayer {
+    max: usize,
+    wait: Option<Duration>,
+    key: KeyFn,
+    slots: Slots,
+}
+
+impl InFlightLimitLayer {
+    /// 每个客户端最多同时处理 `max` 个请求
+    ///
+    /// # Panics
+    ///
+    /// `max` 为 0 时 panic
+    pub fn new(max: usize) -> Self {
+        assert!(max > 0, "in-flight limit must be greater than zero");
+        Self {
+            max,
+            wait: None,
+            key: Arc::new(|req: &Req| {
+                req.extensions()
+                    .get::<ClientAddr>()
+                    .map(|addr| addr.0.ip().to_string())
+            }),
+            slots: Arc::default(),
+        }
+    }
+
+    /// 超出上限的请求最多排队等待 `timeout`，超时后再拒绝
+    pub fn wait(mut self, timeout: Duration) -> Self {
+        self.wait = Some(timeout);
+        self
+    }
+
+    /// 自定义从请求中取客户端键的方式，例如使用已认证的用户
+    ///
+    /// 返回 `None` 的请求不受限制
+    pub fn key(mut self, key: impl Fn(&Req) -> Option<String> + Send + Sync + 'static) -> Self {
+        self.key = Arc::new(key);
+        self
+    }
+
+    /// `key` 当前处理中的请求数
+    pub fn in_flight(&self, key: &str) -> usize {
+        let slots = self.slots.lock().unwrap();
+        slots
+            .get(key)
+            .map_or(0, |sem| self.max - sem.available_permits())
+    }
+
+    fn semaphore(&self, key: &str) -> Arc<Semaphore> {
+        let mut slots = self.slots.lock().unwrap();
+        slots
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.max)))
+            .clone()
+    }
+}
+
+impl<S> Layer<S> for InFlightLimitLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = InFlightLimitService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        InFlightLimitService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        }
+    }
+}
+
+/// 由 [`InFlightLimitLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct InFlightLimitService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: InFlightLimitLayer,
+}
+
+impl Service<Req> for InFlightLimitService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let Some(key) = (self.layer.key)(&req) else {
+            return Box::pin(self.inner.call(req));
+        };
+        let mut inner = self.inner.clone();
+        let layer = self.layer.clone();
+        Box::pin(async move {
+            let sem = layer.semaphore(&key);
+            let permit = match layer.wait {
+                None => sem.clone().try_acquire_owned().ok(),
+                Some(timeout) => tokio::time::timeout(timeout, sem.clone().acquire_owned())
+                    .await
+                    .ok()
+                    .and_then(Result::ok),
+            };
+            let guard = SlotGuard {
+                permit,
+                sem,
+                key,
+                slots: layer.slots.clone(),
+            };
+            if guard.permit.is_none() {
+                tracing::debug!(client = %guard.key, max = layer.max, "too many in-flight requests");
+                return Ok(
+                    AppError::TooManyRequests("Too many concurrent requests".to_string())
+                        .into_response(),
+                );
+            }
+            let resp = inner.call(req).await?;
+            // 槽位随响应体一起释放
+            let (parts, body) = resp.into_parts();
+            let body = body
+                .map_frame(move |frame| {
+                    let _ = &guard;
+                    frame
+                })
+                .boxed_unsync();
+            Ok(Resp::from_parts(parts, body))
+        })
+    }
+}
+
+/// 释放槽位，客户端的表项不再被引用时一并移除
+struct SlotGuard {
+    permit: Option<OwnedSemaphorePermit>,
+    sem: Arc<Semaphore>,
+    key: String,
+    slots: Slots,
+}
+
+impl Drop for SlotGuard {
+    fn drop(&mut self) {
+        self.permit.take();
+        let mut slots = self.slots.lock().unwrap();
+        // 只剩表中与本守卫持有的引用时，说明没有进行中或排队的请求
+        if Arc::strong_count(&self.sem) == 2 {
+            slots.remove(&self.key);
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::app::config::config_env;
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult};
+use bytes::Bytes;
+use http_body::Body;
+use http_body_util::{BodyExt, Full};
+use hyper::header::{CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE};
+use serde::de::IgnoredAny;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// HTML 压缩时内容原样保留的元素
+const RAW_ELEMENTS: [&str; 4] = ["pre", "textarea", "script", "style"];
+
+/// 响应精简层：精简 HTML 响应并去除 JSON 中的格式化空白
+///
+/// HTML 会移除注释（保留条件注释），连续空白折叠为一个空格，
+/// `<pre>`、`<textarea>`、`<script>`、`<style>` 内的内容除外。
+/// JSON 保持键的顺序，只移除字符串之外的空白。
+///
+/// 只处理长度已知且不小于 [`min_size`](Self::min_size) 字节的响应体，流式响应与已编码的响应原样透传。
+/// 配置环境为 `prod` 时生效（见 [`config_env`]），可通过 [`enabled`](Self::enabled) 强制开关。
+///
+/// 需在 [`Router::with_compression`](crate::router::Router::with_compression) 之前添加，
+/// 保证先精简再压缩
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::minify::MinifyLayer;
+///
+/// router.with_layer(MinifyLayer::new().json(false).min_size(512));
+/// router.with_compression();
+/// ```
+#[derive(Debug, Clone)]
+pub struct MinifyLayer {
+    enabled: bool,
+    html: bool,
+    json: bool,
+    min_size: u64,
+}
+
+impl Default for MinifyLayer {
+    fn default() -> Self {
+        Self {
+            enabled: config_env() == "prod",
+            html: true,
+            json: true,
+            min_size: 256,
+        }
+    }
+}
+
+impl MinifyLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 忽略配置环境，强制开启或关闭
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// 是否精简 `text/html` 响应（默认 true）
+    pub fn html(mut self, enable: bool) -> Self {
+        self.html = enable;
+        self
+    }
+
+    /// 是否精简 `application/json` 与 `+json` 响应（默认 true）
+    pub fn json(mut self, enable: bool) -> Self {
+        self.json = enable;
+        self
+    }
+
+    /// 小于 `bytes` 的响应体不处理（默认 256）
+    pub fn min_size(mut self, bytes: u64) -> Self {
+        self.min_size = bytes;
+        self
+    }
+
+    fn kind(&self, resp: &Resp) -> Option<Kind> {
+        if resp.headers().contains_key(CONTENT_ENCODING) {
+            return None;
+        }
+        let len = resp.body().size_hint().exact()?;
+        if len < self.min_size {
+            return None;
+        }
+        let mime = resp
+            .headers()
+            .get(CONTENT_TYPE)?
+            .to_str()
+            .ok()?
+            .split(';')
+            .next()?
+            .trim();
+        if self.html && mime == "text/html" {
+            Some(Kind::Html)
+        } else if self.json && (mime == "application/json" || mime.ends_with("+json")) {
+            Some(Kind::Json)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Html,
+    Json,
+}
+
+impl<S> Layer<S> for MinifyLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = BoxCloneService<Req, Resp, AppError>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        if !self.enabled {
+            return BoxCloneService::new(inner);
+        }
+        BoxCloneService::new(MinifyService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        })
+    }
+}
+
+/// 由 [`MinifyLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct MinifyService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: MinifyLayer,
+}
+
+impl Service<Req> for MinifyService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let layer = self.layer.clone();
+        Box::pin(async move {
+            let resp = inner.call(req).await?;
+            let Some(kind) = layer.kind(&resp) else {
+                return Ok(resp);
+            };
+            let (mut parts, body) = resp.into_parts();
+            let bytes = body.collect().await?.to_bytes();
+            let minified = match kind {
+                Kind::Html => std::str::from_utf8(&bytes)
+                    .ok()
+                    .map(|html| minify_html(html).into_bytes()),
+                Kind::Json => serde_json::from_slice::<IgnoredAny>(&bytes)
+                    .ok()
+                    .map(|_| compact_json(&bytes)),
+            };
+            let bytes = match minified {
+                Some(minified) => {
+                    parts.headers.remove(CONTENT_LENGTH);
+                    Bytes::from(minified)
+                }
+                None => bytes,
+            };
+            let body = Full::new(bytes).map_err(Into::into).boxed_unsync();
+            Ok(Resp::from_parts(parts, body))
+        })
+    }
+}
+
+/// 移除注释并折叠原样保留元素之外的空白
+pub fn minify_html(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html.trim();
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("<!--") && !rest.starts_with("<!--[if") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            continue;
+        }
+        if c == '<'
+            && let Some(len) = raw_element_len(rest)
+        {
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
+        if c.is_whitespace() {
+            let trimmed = rest.trim_start();
+            if !out.ends_with(' ') {
+                out.push(' ');
+            }
+            rest = trimmed;
+            continue;
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// 以 `html` 开头的原样保留元素（含结束标签）的长度
+fn raw_element_len(html: &str) -> Option<usize> {
+    let tag = html.as_bytes().get(1..)?;
+    let name = RAW_ELEMENTS.into_iter().find(|name| {
+        tag.len() > name.len()
+            && tag[..name.len()].eq_ignore_ascii_case(name.as_bytes())
+            && (tag[name.len()] == b'>'
+                || tag[name.len()] == b'/'
+                || tag[name.len()].is_ascii_whitespace())
+    })?;
+    let close = format!("</{}", name);
+    let end = html
+        .as_bytes()
+        .windows(close.len())
+        .position(|w| w.eq_ignore_ascii_case(close.as_bytes()))?;
+    let tag_end = html[end..].find('>')?;
+    Some(end + tag_end + 1)
+}
+
+/// 移除 JSON 字符串之外的空白
+pub fn compact_json(json: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for &byte in json {
+        if in_string {
+            out.push(byte);
+            if escaped {
+                escaped = false;
+            } else if byte == b'\\' {
+                escaped = true;
+            } else if byte == b'"' {
+                in_string = false;
+            }
+        } else if byte == b'"' {
+            in_string = true;
+            out.push(byte);
+        } else if !byte.is_ascii_whitespace() {
+            out.push(byte);
+        }
+    }
+    out
+}
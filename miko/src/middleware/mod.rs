@@ -1,3 +1,20 @@
+pub mod access_log;
+pub mod body_limit;
+pub mod catch_panic;
+#[cfg(feature = "compression")]
+pub mod compression;
+pub mod deprecation;
+pub mod envelope;
+pub mod extension;
+pub mod in_flight;
+pub mod minify;
+pub mod progress;
+pub mod request_id;
+pub mod require_header;
+pub mod scrub;
+pub mod stream_idle;
+pub mod timeout;
+
 use crate::miko_core::{Req, Resp};
 use crate::{AppError, AppResult};
 use std::future::Future;
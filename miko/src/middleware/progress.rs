@@ -0,0 +1,309 @@
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use crate::miko_core::{MikoError, Req, Resp};
+use crate::{AppError, AppResult};
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::BodyExt;
+use http_body_util::combinators::UnsyncBoxBody;
+use hyper::http::request::Parts;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 响应体传输的当前状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferState {
+    /// 刚写出一个数据块
+    Streaming,
+    /// 响应体已全部写出
+    Completed,
+    /// 响应体在完成前被丢弃或出错（如客户端断开）
+    Aborted,
+    /// 在设置的停滞超时内没有拉取任何数据块
+    Stalled,
+}
+
+/// 传给进度回调的快照
+#[derive(Debug, Clone, Copy)]
+pub struct TransferProgress {
+    /// 目前已交给连接的响应体字节数
+    pub bytes_written: u64,
+    /// 响应开始传输以来经过的时间
+    pub elapsed: Duration,
+    pub state: TransferState,
+}
+
+type ProgressCallback = Arc<dyn Fn(&TransferProgress) + Send + Sync>;
+
+/// 当前响应传输的取消句柄
+///
+/// 客户端在传输中途断开或停滞时被取消，为响应体生产数据的任务可以据此提前结束。
+/// 不在 [`ProgressLayer`] 之下时永远不会被取消
+#[derive(Debug, Clone, Default)]
+pub struct TransferToken(CancellationToken);
+
+impl TransferToken {
+    /// 传输中止或停滞时完成
+    pub async fn cancelled(&self) {
+        self.0.cancelled().await
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
+
+impl<S> FromRequestParts<S> for TransferToken {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let token = req
+            .extensions
+            .get::<TransferToken>()
+            .cloned()
+            .unwrap_or_default();
+        Box::pin(async move { Ok(token) })
+    }
+}
+
+/// 在响应体发往客户端的过程中报告传输进度的 Layer
+///
+/// 每写出一个数据块调用一次回调，结束时再以最终状态调用一次。
+/// 处理函数可以提取 [`TransferToken`]，在传输中止或停滞时停止服务端的工作
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::progress::{ProgressLayer, TransferState};
+///
+/// router.with_layer(
+///     ProgressLayer::new(|p| {
+///         if p.state != TransferState::Streaming {
+///             tracing::info!(bytes = p.bytes_written, ?p.elapsed, ?p.state, "download finished");
+///         }
+///     })
+///     .stall_timeout(Duration::from_secs(30)),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct ProgressLayer {
+    callback: ProgressCallback,
+    stall_timeout: Option<Duration>,
+}
+
+impl ProgressLayer {
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&TransferProgress) + Send + Sync + 'static,
+    {
+        Self {
+            callback: Arc::new(callback),
+            stall_timeout: None,
+        }
+    }
+
+    /// 超过 `timeout` 没有拉取数据块时视为停滞
+    ///
+    /// 停滞的传输会取消其 [`TransferToken`] 并让响应体出错
+    pub fn stall_timeout(mut self, timeout: Duration) -> Self {
+        self.stall_timeout = Some(timeout);
+        self
+    }
+}
+
+impl<S> Layer<S> for ProgressLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = ProgressService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ProgressService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        }
+    }
+}
+
+/// 由 [`ProgressLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct ProgressService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: ProgressLayer,
+}
+
+impl Service<Req> for ProgressService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Req) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let layer = self.layer.clone();
+        let token = CancellationToken::new();
+        req.extensions_mut().insert(TransferToken(token.clone()));
+        Box::pin(async move {
+            let resp = inner.call(req).await?;
+            let (parts, body) = resp.into_parts();
+            let body = ProgressBody::new(body, layer, token);
+            Ok(Resp::from_parts(parts, body.boxed_unsync()))
+        })
+    }
+}
+
+struct Shared {
+    start: Instant,
+    bytes: AtomicU64,
+    /// 最近一次拉取数据块时距 `start` 的毫秒数
+    last_ms: AtomicU64,
+    finished: AtomicBool,
+    stalled: AtomicBool,
+}
+
+impl Shared {
+    fn progress(&self, state: TransferState) -> TransferProgress {
+        TransferProgress {
+            bytes_written: self.bytes.load(Ordering::Relaxed),
+            elapsed: self.start.elapsed(),
+            state,
+        }
+    }
+
+    fn touch(&self) {
+        self.last_ms
+            .store(self.start.elapsed().as_millis() as u64, Ordering::Relaxed);
+    }
+}
+
+struct ProgressBody {
+    inner: UnsyncBoxBody<Bytes, MikoError>,
+    shared: Arc<Shared>,
+    callback: ProgressCallback,
+    token: CancellationToken,
+}
+
+impl ProgressBody {
+    fn new(
+        inner: UnsyncBoxBody<Bytes, MikoError>,
+        layer: ProgressLayer,
+        token: CancellationToken,
+    ) -> Self {
+        let shared = Arc::new(Shared {
+            start: Instant::now(),
+            bytes: AtomicU64::new(0),
+            last_ms: AtomicU64::new(0),
+            finished: AtomicBool::new(false),
+            stalled: AtomicBool::new(false),
+        });
+        if let Some(timeout) = layer.stall_timeout {
+            tokio::spawn(watch_stall(
+                shared.clone(),
+                layer.callback.clone(),
+                token.clone(),
+                timeout,
+            ));
+        }
+        Self {
+            inner,
+            shared,
+            callback: layer.callback,
+            token,
+        }
+    }
+
+    fn finish(&self, state: TransferState) {
+        if !self.shared.finished.swap(true, Ordering::Relaxed) {
+            if state != TransferState::Completed {
+                self.token.cancel();
+            }
+            (self.callback)(&self.shared.progress(state));
+        }
+    }
+}
+
+async fn watch_stall(
+    shared: Arc<Shared>,
+    callback: ProgressCallback,
+    token: CancellationToken,
+    timeout: Duration,
+) {
+    let timeout_ms = timeout.as_millis() as u64;
+    loop {
+        let idle = (shared.start.elapsed().as_millis() as u64)
+            .saturating_sub(shared.last_ms.load(Ordering::Relaxed));
+        if idle >= timeout_ms {
+            break;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(timeout_ms - idle)) => {}
+            _ = token.cancelled() => return,
+        }
+        if shared.finished.load(Ordering::Relaxed) {
+            return;
+        }
+    }
+    if !shared.finished.swap(true, Ordering::Relaxed) {
+        shared.stalled.store(true, Ordering::Relaxed);
+        token.cancel();
+        callback(&shared.progress(TransferState::Stalled));
+    }
+}
+
+impl Body for ProgressBody {
+    type Data = Bytes;
+    type Error = MikoError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        if self.shared.stalled.load(Ordering::Relaxed) {
+            return Poll::Ready(Some(Err(MikoError("response transfer stalled".into()))));
+        }
+        let polled = Pin::new(&mut self.inner).poll_frame(cx);
+        match &polled {
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(data) = frame.data_ref() {
+                    self.shared
+                        .bytes
+                        .fetch_add(data.len() as u64, Ordering::Relaxed);
+                    self.shared.touch();
+                    (self.callback)(&self.shared.progress(TransferState::Streaming));
+                }
+            }
+            Poll::Ready(Some(Err(_))) => self.finish(TransferState::Aborted),
+            Poll::Ready(None) => self.finish(TransferState::Completed),
+            Poll::Pending => {}
+        }
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for ProgressBody {
+    fn drop(&mut self) {
+        // 空响应体可能永远不会被轮询到结束
+        let state = if self.inner.is_end_stream() {
+            TransferState::Completed
+        } else {
+            TransferState::Aborted
+        };
+        self.finish(state);
+    }
+}
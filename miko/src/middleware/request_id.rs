@@ -0,0 +1,162 @@
+use crate::error::app_error::{TRACE_ID, get_trace_id};
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult, IntoResponse};
+use hyper::header::{HeaderName, HeaderValue};
+use hyper::http::request::Parts;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 沿用的外部请求 ID 的最大长度，超出时重新生成
+const MAX_ID_LEN: usize = 128;
+
+/// 当前请求的 ID，由 [`RequestIdLayer`] 写入请求扩展
+///
+/// 也可以作为提取器使用；未挂载该层时退回为路由器给每个请求分配的 trace_id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<S> FromRequestParts<S> for RequestId {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let id = req
+            .extensions
+            .get::<RequestId>()
+            .cloned()
+            .or_else(|| get_trace_id().map(RequestId))
+            .ok_or_else(|| AppError::InternalServerError("No request id available".into()));
+        Box::pin(async move { id })
+    }
+}
+
+/// 使用全局 [`IdGenerator`](crate::id::IdGenerator) 生成请求 ID（默认 ULID）
+pub fn generate_request_id() -> String {
+    crate::id::new_id()
+}
+
+/// 为每个请求分配 ID，并将其作为该请求的 trace_id
+///
+/// 请求头 `x-request-id`（或通过 [`RequestIdLayer::header`] 指定的请求头）为不超过 128 字节的
+/// 可打印 ASCII 时沿用其值，否则生成新的 ID。之后：
+///
+/// - 以 [`RequestId`] 写入请求扩展
+/// - 在请求剩余的处理过程中作为 task-local 的 trace_id，出现在 `ErrorResponse::trace_id`、
+///   响应信封与日志中，内层中间件产生的错误也包括在内
+/// - 通过同名响应头返回
+///
+/// trace_id 的作用域限定在请求的 future 内，跨 `.await` 保持正确，响应生成后即失效
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::request_id::RequestIdLayer;
+///
+/// router.with_layer(RequestIdLayer::new());
+/// ```
+#[derive(Clone)]
+pub struct RequestIdLayer {
+    header: HeaderName,
+    generator: Arc<dyn Fn() -> String + Send + Sync>,
+}
+
+impl Default for RequestIdLayer {
+    fn default() -> Self {
+        Self {
+            header: HeaderName::from_static("x-request-id"),
+            generator: Arc::new(generate_request_id),
+        }
+    }
+}
+
+impl RequestIdLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 读取与回写 ID 所用的请求头，默认 `x-request-id`
+    ///
+    /// # Panics
+    ///
+    /// `name` 不是合法的请求头名称时 panic
+    pub fn header(mut self, name: &str) -> Self {
+        self.header = HeaderName::try_from(name)
+            .unwrap_or_else(|e| panic!("invalid header name `{}`: {}", name, e));
+        self
+    }
+
+    /// 自定义 ID 生成函数（如 UUID），用于没有可用 ID 的请求
+    pub fn generator(mut self, generator: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        self.generator = Arc::new(generator);
+        self
+    }
+
+    fn incoming(&self, req: &Req) -> Option<String> {
+        let value = req.headers().get(&self.header)?.to_str().ok()?;
+        (!value.is_empty() && value.len() <= MAX_ID_LEN).then(|| value.to_string())
+    }
+}
+
+impl<S> Layer<S> for RequestIdLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = RequestIdService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        }
+    }
+}
+
+/// 由 [`RequestIdLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct RequestIdService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: RequestIdLayer,
+}
+
+impl Service<Req> for RequestIdService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Req) -> Self::Future {
+        let id = self
+            .layer
+            .incoming(&req)
+            .unwrap_or_else(|| (self.layer.generator)());
+        req.extensions_mut().insert(RequestId(id.clone()));
+        let header = self.layer.header.clone();
+        let future = self.inner.call(req);
+        Box::pin(TRACE_ID.scope(id.clone(), async move {
+            // 在作用域内转换错误，使错误响应带上 trace_id
+            let mut resp = future.await.unwrap_or_else(|e| e.into_response());
+            if let Ok(value) = HeaderValue::from_str(&id) {
+                resp.headers_mut().insert(header, value);
+            }
+            Ok(resp)
+        }))
+    }
+}
@@ -0,0 +1,132 @@
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult};
+use hyper::StatusCode;
+use hyper::header::HeaderName;
+use regex::Regex;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 拒绝缺少指定请求头，或请求头值不匹配模式的请求
+///
+/// 在所有提取器之前执行。缺少请求头时返回 `400 Bad Request`；值不能完整匹配模式时同样返回 `400`，
+/// 或返回 [`RequireHeaderLayer::status`] 设置的状态码（如 API 版本不符时的 `426 Upgrade Required`）
+///
+/// 路由宏会根据 `#[require_header(...)]` 生成该 Layer
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::require_header::RequireHeaderLayer;
+///
+/// router.with_layer(
+///     RequireHeaderLayer::new("x-api-version")
+///         .matches(r"2\.\d+")
+///         .status(426),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct RequireHeaderLayer {
+    name: HeaderName,
+    pattern: Option<Regex>,
+    status: StatusCode,
+}
+
+impl RequireHeaderLayer {
+    /// 要求请求携带 `name` 请求头
+    ///
+    /// # Panics
+    ///
+    /// `name` 不是合法的请求头名称时 panic
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: HeaderName::try_from(name)
+                .unwrap_or_else(|e| panic!("invalid header name `{}`: {}", name, e)),
+            pattern: None,
+            status: StatusCode::BAD_REQUEST,
+        }
+    }
+
+    /// 要求请求头的完整值匹配正则表达式 `pattern`
+    ///
+    /// # Panics
+    ///
+    /// `pattern` 不是合法的正则表达式时 panic
+    pub fn matches(mut self, pattern: &str) -> Self {
+        self.pattern = Some(
+            Regex::new(&format!("^(?:{})$", pattern))
+                .unwrap_or_else(|e| panic!("invalid header pattern `{}`: {}", pattern, e)),
+        );
+        self
+    }
+
+    /// 值不匹配时返回的状态码，默认为 `400`
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = StatusCode::from_u16(status)
+            .unwrap_or_else(|_| panic!("invalid status code {}", status));
+        self
+    }
+
+    fn check(&self, req: &Req) -> Result<(), AppError> {
+        let Some(value) = req.headers().get(&self.name) else {
+            return Err(AppError::BadRequest(format!(
+                "Missing header `{}`",
+                self.name
+            )));
+        };
+        let matched = match &self.pattern {
+            Some(pattern) => value.to_str().is_ok_and(|v| pattern.is_match(v)),
+            None => true,
+        };
+        if matched {
+            Ok(())
+        } else {
+            Err(AppError::custom(
+                self.status,
+                "INVALID_HEADER",
+                format!("Header `{}` does not match the required format", self.name),
+            ))
+        }
+    }
+}
+
+impl<S> Layer<S> for RequireHeaderLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = RequireHeaderService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireHeaderService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        }
+    }
+}
+
+/// 由 [`RequireHeaderLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct RequireHeaderService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: RequireHeaderLayer,
+}
+
+impl Service<Req> for RequireHeaderService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        match self.layer.check(&req) {
+            Ok(()) => Box::pin(self.inner.call(req)),
+            Err(err) => Box::pin(async move { Err(err) }),
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 脱敏规则匹配到值之后的处理方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Redaction {
+    /// 替换为 `"***"`
+    Mask,
+    /// 保留字符串的前 `n` 个字符并追加 `"..."`；非字符串的值按 Mask 处理
+    Truncate(usize),
+    /// 直接移除该字段（或数组元素）
+    Remove,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    path: Vec<String>,
+    action: Redaction,
+}
+
+/// 出站响应脱敏层，在 JSON 响应发出前处理敏感字段
+///
+/// 路径以 `.` 分隔：`*` 匹配任意一个键或数组元素，`**` 匹配任意层级。
+/// 例如 `*.password` 只处理根下一层的 `password`，`**.password` 处理任意深度的 `password`。
+/// 非 `application/json`（或 `+json`）的响应原样透传。
+///
+/// 可通过 `#[layer(...)]` 挂到指定路由，或挂到嵌套的 Router 上
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::scrub::ScrubLayer;
+///
+/// let scrub = ScrubLayer::new()
+///     .mask("**.password")
+///     .truncate("*.token", 4)
+///     .remove("internal_id");
+/// users_router.with_layer(scrub);
+/// ```
+#[derive(Clone, Default)]
+pub struct ScrubLayer {
+    rules: Arc<Vec<Rule>>,
+}
+
+impl ScrubLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一条规则，对所有匹配 `path` 的值执行 `action`
+    pub fn rule(mut self, path: &str, action: Redaction) -> Self {
+        Arc::make_mut(&mut self.rules).push(Rule {
+            path: path.split('.').map(str::to_string).collect(),
+            action,
+        });
+        self
+    }
+
+    /// 将匹配的值替换为 `"***"`
+    pub fn mask(self, path: &str) -> Self {
+        self.rule(path, Redaction::Mask)
+    }
+
+    /// 匹配的字符串只保留前 `keep` 个字符
+    pub fn truncate(self, path: &str, keep: usize) -> Self {
+        self.rule(path, Redaction::Truncate(keep))
+    }
+
+    /// 移除匹配的字段
+    pub fn remove(self, path: &str) -> Self {
+        self.rule(path, Redaction::Remove)
+    }
+
+    /// 对 `value` 原地应用全部规则
+    pub fn scrub(&self, value: &mut Value) {
+        for rule in self.rules.iter() {
+            apply(value, &rule.path, &rule.action);
+        }
+    }
+}
+
+impl<S> Layer<S> for ScrubLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = ScrubService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ScrubService {
+            inner: BoxCloneService::new(inner),
+            layer: self.clone(),
+        }
+    }
+}
+
+/// 由 [`ScrubLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct ScrubService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    layer: ScrubLayer,
+}
+
+impl Service<Req> for ScrubService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let layer = self.layer.clone();
+        Box::pin(async move {
+            let resp = inner.call(req).await?;
+            if !is_json(&resp) {
+                return Ok(resp);
+            }
+            let (mut parts, body) = resp.into_parts();
+            let bytes = body.collect().await?.to_bytes();
+            let bytes = match serde_json::from_slice::<Value>(&bytes) {
+                Ok(mut value) => {
+                    layer.scrub(&mut value);
+                    parts.headers.remove(CONTENT_LENGTH);
+                    Bytes::from(serde_json::to_vec(&value).map_err(AppError::JsonParseError)?)
+                }
+                Err(_) => bytes,
+            };
+            let body = Full::new(bytes).map_err(Into::into).boxed_unsync();
+            Ok(Resp::from_parts(parts, body))
+        })
+    }
+}
+
+fn is_json(resp: &Resp) -> bool {
+    resp.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|ct| ct.split(';').next())
+        .map(str::trim)
+        .is_some_and(|mime| mime == "application/json" || mime.ends_with("+json"))
+}
+
+fn apply(value: &mut Value, path: &[String], action: &Redaction) {
+    let Some((head, rest)) = path.split_first() else {
+        return;
+    };
+    if head == "**" {
+        // 匹配零层
+        apply(value, rest, action);
+        // 匹配一层或多层
+        for child in children(value) {
+            apply(child, path, action);
+        }
+        return;
+    }
+    if rest.is_empty() {
+        redact_children(value, head, action);
+        return;
+    }
+    match value {
+        Value::Object(map) if head == "*" => map.values_mut().for_each(|v| apply(v, rest, action)),
+        Value::Object(map) => {
+            if let Some(v) = map.get_mut(head.as_str()) {
+                apply(v, rest, action);
+            }
+        }
+        Value::Array(items) if head == "*" || head.parse::<usize>().is_ok() => {
+            for (i, v) in items.iter_mut().enumerate() {
+                if head == "*" || head.parse() == Ok(i) {
+                    apply(v, rest, action);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn children(value: &mut Value) -> Vec<&mut Value> {
+    match value {
+        Value::Object(map) => map.values_mut().collect(),
+        Value::Array(items) => items.iter_mut().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 处理 `value` 下名为 `key` 的子节点（`*` 时处理全部子节点）
+fn redact_children(value: &mut Value, key: &str, action: &Redaction) {
+    match value {
+        Value::Object(map) => {
+            if *action == Redaction::Remove {
+                map.retain(|k, _| key != "*" && k != key);
+            } else {
+                map.iter_mut()
+                    .filter(|(k, _)| key == "*" || k.as_str() == key)
+                    .for_each(|(_, v)| redact(v, action));
+            }
+        }
+        Value::Array(items) => {
+            let matches = |i: usize| key == "*" || key.parse() == Ok(i);
+            if *action == Redaction::Remove {
+                let mut i = 0;
+                items.retain(|_| {
+                    i += 1;
+                    !matches(i - 1)
+                });
+            } else {
+                for (i, v) in items.iter_mut().enumerate() {
+                    if matches(i) {
+                        redact(v, action);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn redact(value: &mut Value, action: &Redaction) {
+    *value = match (action, &*value) {
+        (Redaction::Truncate(n), Value::String(s)) if s.chars().count() <= *n => return,
+        (Redaction::Truncate(n), Value::String(s)) => {
+            Value::String(format!("{}...", s.chars().take(*n).collect::<String>()))
+        }
+        _ => Value::String("***".to_string()),
+    };
+}
@@ -0,0 +1,152 @@
+use crate::miko_core::{MikoError, Req, Resp};
+use crate::{AppError, AppResult};
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::BodyExt;
+use http_body_util::combinators::UnsyncBoxBody;
+use hyper::{Method, Uri};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::{Instant, Sleep};
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 响应体空闲超时 Layer，响应体长时间没有产出数据时将其中止
+///
+/// 与请求总超时不同，长连接流（SSE、分块下载、文件）只要持续产出数据就可以运行任意长时间。
+/// 响应体空闲超过 `timeout` 时会记录原因并让响应体出错，从而关闭连接（HTTP/2 下重置该流）。
+///
+/// 也可以通过 `[server.http] stream_idle_timeout_ms` 配置
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::stream_idle::StreamIdleTimeoutLayer;
+///
+/// router.with_layer(StreamIdleTimeoutLayer::new(Duration::from_secs(60)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct StreamIdleTimeoutLayer {
+    timeout: Duration,
+}
+
+impl StreamIdleTimeoutLayer {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl<S> Layer<S> for StreamIdleTimeoutLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = StreamIdleTimeoutService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        StreamIdleTimeoutService {
+            inner: BoxCloneService::new(inner),
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// 由 [`StreamIdleTimeoutLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct StreamIdleTimeoutService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    timeout: Duration,
+}
+
+impl Service<Req> for StreamIdleTimeoutService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let timeout = self.timeout;
+        let method = req.method().clone();
+        let uri = req.uri().clone();
+        Box::pin(async move {
+            let resp = inner.call(req).await?;
+            let (parts, body) = resp.into_parts();
+            let body = IdleTimeoutBody {
+                inner: body,
+                timeout,
+                sleep: Box::pin(tokio::time::sleep(timeout)),
+                waiting: false,
+                bytes: 0,
+                method,
+                uri,
+            };
+            Ok(Resp::from_parts(parts, body.boxed_unsync()))
+        })
+    }
+}
+
+struct IdleTimeoutBody {
+    inner: UnsyncBoxBody<Bytes, MikoError>,
+    timeout: Duration,
+    sleep: Pin<Box<Sleep>>,
+    /// 空闲计时是否在进行，即响应体自上一帧之后返回过 `Pending`
+    ///
+    /// 等待慢速客户端读取的时间不算空闲
+    waiting: bool,
+    bytes: u64,
+    method: Method,
+    uri: Uri,
+}
+
+impl Body for IdleTimeoutBody {
+    type Data = Bytes;
+    type Error = MikoError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let polled = Pin::new(&mut self.inner).poll_frame(cx);
+        match &polled {
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(data) = frame.data_ref() {
+                    self.bytes += data.len() as u64;
+                }
+                self.waiting = false;
+            }
+            Poll::Pending if !self.waiting => {
+                let deadline = Instant::now() + self.timeout;
+                self.sleep.as_mut().reset(deadline);
+                self.waiting = true;
+                // 将计时器注册到当前任务
+                let _ = self.sleep.as_mut().poll(cx);
+            }
+            Poll::Pending if self.sleep.as_mut().poll(cx).is_ready() => {
+                tracing::warn!(
+                    method = %self.method,
+                    uri = %self.uri,
+                    idle_ms = self.timeout.as_millis() as u64,
+                    bytes_written = self.bytes,
+                    "response stream idle timeout, closing connection"
+                );
+                return Poll::Ready(Some(Err(MikoError("response stream idle timeout".into()))));
+            }
+            _ => {}
+        }
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
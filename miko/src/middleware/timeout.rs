@@ -0,0 +1,136 @@
+use crate::context::RequestContext;
+use crate::miko_core::{Req, Resp};
+use crate::{AppError, AppResult, IntoResponse};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::Instant;
+use tower::util::BoxCloneService;
+use tower::{Layer, Service};
+
+/// 请求超时 Layer，超过给定时长后取消处理函数的 future
+///
+/// 未能按时完成的请求会被丢弃并返回 504 [`AppError::Timeout`]。
+/// 截止时间同时写入 [`RequestContext`]，处理函数发起的下游调用可以据此控制耗时。
+///
+/// 超时不会叠加：外层已有 `TimeoutLayer`（如全局的 `[server] timeout_ms`）在计时时，
+/// 内层会替换其截止时间，因此单个路由可以设置比默认值更长或更短的超时。
+///
+/// 也可以通过 `[server] timeout_ms`、路由级的 `[server.routes."/path"] timeout_ms`
+/// 或 `#[layer(timeout("5s"))]` 配置
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use miko::middleware::timeout::TimeoutLayer;
+///
+/// router.with_layer(TimeoutLayer::new(Duration::from_secs(30)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TimeoutLayer {
+    timeout: Duration,
+}
+
+impl TimeoutLayer {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl<S> Layer<S> for TimeoutLayer
+where
+    S: Service<Req, Response = Resp, Error = AppError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = TimeoutService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimeoutService {
+            inner: BoxCloneService::new(inner),
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// 由 [`TimeoutLayer`] 创建的 Service
+#[derive(Clone)]
+pub struct TimeoutService {
+    inner: BoxCloneService<Req, Resp, AppError>,
+    timeout: Duration,
+}
+
+/// 同一请求内嵌套的超时层共享的截止时间
+///
+/// 最外层负责计时，内层只调整截止时间
+#[derive(Clone)]
+struct TimeoutSlot(Arc<watch::Sender<(Instant, Duration)>>);
+
+impl TimeoutSlot {
+    fn new(timeout: Duration) -> Self {
+        Self(Arc::new(watch::Sender::new((
+            Instant::now() + timeout,
+            timeout,
+        ))))
+    }
+
+    fn deadline(&self) -> Instant {
+        self.0.borrow().0
+    }
+
+    fn set(&self, timeout: Duration) -> Instant {
+        let deadline = Instant::now() + timeout;
+        self.0.send_replace((deadline, timeout));
+        deadline
+    }
+
+    /// 当前截止时间到达后完成，返回对应的超时时长
+    async fn expired(&self) -> Duration {
+        let mut rx = self.0.subscribe();
+        loop {
+            let (deadline, timeout) = *rx.borrow_and_update();
+            tokio::select! {
+                _ = tokio::time::sleep_until(deadline) => return timeout,
+                // 发送端由 `self` 持有，这里只会在截止时间更新时触发
+                _ = rx.changed() => {}
+            }
+        }
+    }
+}
+
+impl Service<Req> for TimeoutService {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = AppResult<Resp>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Req) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let mut ctx = RequestContext::current();
+
+        // 外层超时已在计时：调整其截止时间，而不是再启动一个计时器
+        if let Some(slot) = req.extensions().get::<TimeoutSlot>().cloned() {
+            ctx.deadline = Some(slot.set(self.timeout));
+            return Box::pin(ctx.scope(inner.call(req)));
+        }
+
+        let slot = TimeoutSlot::new(self.timeout);
+        ctx.deadline = Some(slot.deadline());
+        req.extensions_mut().insert(slot.clone());
+        Box::pin(async move {
+            tokio::select! {
+                resp = ctx.scope(inner.call(req)) => resp,
+                timeout = slot.expired() => Ok(AppError::Timeout(format!(
+                    "Request timed out after {} ms",
+                    timeout.as_millis()
+                ))
+                .into_response()),
+            }
+        })
+    }
+}
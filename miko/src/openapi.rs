@@ -0,0 +1,197 @@
+//! OpenAPI 文档自动汇总
+//!
+//! 同时开启 `auto` 与 `utoipa` 时，路由宏生成的每个操作都会登记到全局表，
+//! [`auto_doc`] 据此构建完整文档，无需手写 `#[derive(OpenApi)]` 的 `paths(...)` 列表。
+//!
+//! ```rust,ignore
+//! router.serve_openapi("/openapi.json").serve_scalar("/scalar");
+//!
+//! // 需要自定义 info、tags 时，以派生的文档为基础合并
+//! let doc = miko::openapi::auto_doc_with(ApiDoc::openapi());
+//! ```
+//!
+//! 路由可用 `#[u_doc("internal")]` 归入其他文档，[`auto_doc_for`] 按名称单独生成，
+//! 未标记的路由属于默认文档 [`DEFAULT_DOC`]。
+
+use utoipa::openapi::path::{HttpMethod, Operation, PathItem};
+use utoipa::openapi::schema::Schema;
+use utoipa::openapi::{ComponentsBuilder, Deprecated, Info, OpenApi, OpenApiBuilder, RefOr};
+
+/// 默认文档名，未使用 `#[u_doc]` 的路由属于该文档
+pub const DEFAULT_DOC: &str = "default";
+
+/// 路由宏登记的一个 OpenAPI 操作
+pub struct OpenApiPath {
+    pub path: fn() -> String,
+    pub methods: fn() -> Vec<HttpMethod>,
+    pub operation: fn() -> Operation,
+    pub tags: fn() -> Vec<&'static str>,
+    pub schemas: fn(&mut Vec<(String, RefOr<Schema>)>),
+    /// 所属文档，为空时属于默认文档
+    pub docs: &'static [&'static str],
+    /// SSE 路由的事件名，写入 200 响应的描述
+    pub sse_events: &'static [&'static str],
+    /// 是否标记为 deprecated
+    pub deprecated: bool,
+}
+
+inventory::collect!(OpenApiPath);
+
+impl OpenApiPath {
+    /// 从 `#[utoipa::path]` 生成的 `__path_xxx` 类型创建
+    pub const fn of<P>() -> Self
+    where
+        P: utoipa::Path + utoipa::__dev::SchemaReferences + utoipa::__dev::Tags<'static>,
+    {
+        Self {
+            path: P::path,
+            methods: P::methods,
+            operation: P::operation,
+            tags: <P as utoipa::__dev::Tags<'static>>::tags,
+            schemas: <P as utoipa::__dev::SchemaReferences>::schemas,
+            docs: &[],
+            sse_events: &[],
+            deprecated: false,
+        }
+    }
+
+    /// 指定所属文档
+    pub const fn docs(mut self, docs: &'static [&'static str]) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    /// 记录 SSE 路由推送的事件名
+    pub const fn sse_events(mut self, events: &'static [&'static str]) -> Self {
+        self.sse_events = events;
+        self
+    }
+
+    /// 标记操作已弃用
+    pub const fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+
+    /// 是否属于名为 `name` 的文档
+    pub fn in_doc(&self, name: &str) -> bool {
+        if self.docs.is_empty() {
+            name == DEFAULT_DOC
+        } else {
+            self.docs.contains(&name)
+        }
+    }
+
+    fn operation(&self) -> Operation {
+        let mut operation = (self.operation)();
+        let tags = operation.tags.get_or_insert_with(Vec::new);
+        for tag in (self.tags)() {
+            if !tags.iter().any(|t| t == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+        if tags.is_empty() {
+            operation.tags = None;
+        }
+        if self.deprecated {
+            operation.deprecated = Some(Deprecated::True);
+        }
+        if !self.sse_events.is_empty()
+            && let Some(RefOr::T(resp)) = operation.responses.responses.get_mut("200")
+        {
+            let events: Vec<String> = self.sse_events.iter().map(|e| format!("`{}`", e)).collect();
+            if !resp.description.is_empty() {
+                resp.description.push_str("\n\n");
+            }
+            resp.description
+                .push_str(&format!("Events: {}", events.join(", ")));
+        }
+        operation
+    }
+}
+
+/// 由默认文档的操作构建文档，info 为占位的 `API 1.0.0`，需自定义时使用 [`auto_doc_with`]
+pub fn auto_doc() -> OpenApi {
+    auto_doc_for(DEFAULT_DOC)
+}
+
+/// 将默认文档的操作与引用的 schema 合并进 `base`
+pub fn auto_doc_with(base: OpenApi) -> OpenApi {
+    auto_doc_for_with(DEFAULT_DOC, base)
+}
+
+/// 由名为 `name` 的文档的操作构建文档
+pub fn auto_doc_for(name: &str) -> OpenApi {
+    auto_doc_for_with(
+        name,
+        OpenApiBuilder::new()
+            .info(Info::new("API", "1.0.0"))
+            .build(),
+    )
+}
+
+/// 所有登记过的文档名，按名称排序
+pub fn doc_names() -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = inventory::iter::<OpenApiPath>
+        .into_iter()
+        .flat_map(|entry| {
+            if entry.docs.is_empty() {
+                &[DEFAULT_DOC][..]
+            } else {
+                entry.docs
+            }
+        })
+        .copied()
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// 将名为 `name` 的文档的操作与引用的 schema 合并进 `base`
+pub fn auto_doc_for_with(name: &str, mut base: OpenApi) -> OpenApi {
+    let mut schemas = Vec::new();
+    for entry in inventory::iter::<OpenApiPath>
+        .into_iter()
+        .filter(|e| e.in_doc(name))
+    {
+        let path = (entry.path)();
+        let item = PathItem::from_http_methods((entry.methods)(), entry.operation());
+        match base.paths.paths.get_mut(&path) {
+            Some(existing) => existing.merge_operations(item),
+            None => {
+                base.paths.paths.insert(path, item);
+            }
+        }
+        (entry.schemas)(&mut schemas);
+    }
+    if !schemas.is_empty() {
+        let components = base.components.take().unwrap_or_default();
+        let mut builder: ComponentsBuilder = components.into();
+        builder = builder.schemas_from_iter(schemas);
+        base.components = Some(builder.build());
+    }
+    base
+}
+
+/// 内嵌文档的 Scalar 页面
+pub fn scalar_html(doc: &OpenApi) -> String {
+    let spec = doc
+        .to_json()
+        .unwrap_or_else(|_| "{}".to_string())
+        .replace("</", "<\\/");
+    format!(
+        r#"<!doctype html>
+<html>
+<head>
+  <title>API Reference</title>
+  <meta charset="utf-8" />
+  <meta name="viewport" content="width=device-width, initial-scale=1" />
+</head>
+<body>
+  <script id="api-reference" type="application/json">{spec}</script>
+  <script src="https://cdn.jsdelivr.net/npm/@scalar/api-reference"></script>
+</body>
+</html>"#
+    )
+}
@@ -0,0 +1,202 @@
+//! 路由冲突处理
+//!
+//! 默认情况下，注册与已有路由冲突的路径（如重复注册 `GET /`，或 `/users/{id}` 与 `/users/{name}`）会 panic。
+//! `try_route` / `try_nest` / `try_merge` 改为返回 [`RouteError`]，出错时路由器保持调用前的状态：
+//!
+//! ```rust,ignore
+//! if let Err(e) = router.try_nest("/admin", admin_router()) {
+//!     eprintln!("{}", e); // route `GET /admin/` conflicts with `GET /admin/` registered by my_app::admin::index (src/admin.rs:12)
+//! }
+//! ```
+//!
+//! 设置 [`RouteConflictPolicy::Override`] 后，后注册的路由替换与之冲突的路由：
+//!
+//! ```rust,ignore
+//! router.on_conflict(RouteConflictPolicy::Override);
+//! router.merge(plugin_routes()); // 插件中的同名路由覆盖已有路由
+//! ```
+
+use super::{HttpSvc, Router};
+use crate::handler::Req;
+use crate::router::introspect::RouteSource;
+use hyper::Method;
+use matchit::{InsertError, Router as MRouter};
+use miko_core::encode_route;
+use std::fmt;
+
+/// 注册的路由与已有路由冲突时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RouteConflictPolicy {
+    /// `route` / `nest` / `merge` 等 panic，`try_*` 返回错误
+    #[default]
+    Panic,
+    /// 移除冲突的已有路由，注册新路由
+    Override,
+}
+
+/// 路由注册失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    /// 与已注册的路由冲突
+    Conflict {
+        method: Method,
+        path: String,
+        /// 冲突的已有路由模式
+        existing: String,
+        /// 已有路由的处理函数与注册位置
+        existing_source: Option<RouteSource>,
+    },
+    /// 路由模式不合法，如 `{*rest}` 不在末尾
+    InvalidPattern { path: String, message: String },
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Conflict {
+                method,
+                path,
+                existing,
+                existing_source,
+            } => {
+                write!(
+                    f,
+                    "route `{} {}` conflicts with `{} {}`",
+                    method, path, method, existing
+                )?;
+                if let Some(source) = existing_source {
+                    write!(f, " registered by {}", source)?;
+                }
+                Ok(())
+            }
+            Self::InvalidPattern { path, message } => {
+                write!(f, "invalid route `{}`: {}", path, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+impl<S> Router<S> {
+    /// 设置路由冲突的处理方式，只影响之后的注册
+    pub fn on_conflict(&mut self, policy: RouteConflictPolicy) -> &mut Self {
+        self.conflict_policy = policy;
+        self
+    }
+
+    /// 注册一条路由并同步 `path_map` 与 `sources`，冲突时按 [`RouteConflictPolicy`] 处理
+    pub(crate) fn insert_route(
+        &mut self,
+        method: Method,
+        path: String,
+        svc: HttpSvc<Req>,
+        source: Option<RouteSource>,
+    ) -> Result<(), RouteError> {
+        let table = self.routes.entry(method.clone()).or_default();
+        match table.insert(encode_route(&path), svc.clone()) {
+            Ok(()) => {}
+            Err(InsertError::Conflict { with }) => {
+                let existing = self.registered_path(&method, &with).unwrap_or(with);
+                if self.conflict_policy != RouteConflictPolicy::Override {
+                    let existing_source = self
+                        .sources
+                        .get(&method)
+                        .and_then(|sources| sources.get(&existing))
+                        .cloned();
+                    return Err(RouteError::Conflict {
+                        method,
+                        path,
+                        existing,
+                        existing_source,
+                    });
+                }
+                self.override_route(&method, &path, svc.clone(), existing)?;
+            }
+            Err(e) => {
+                return Err(RouteError::InvalidPattern {
+                    path,
+                    message: e.to_string(),
+                });
+            }
+        }
+        self.path_map
+            .entry(method.clone())
+            .or_default()
+            .insert(path.clone(), svc);
+        if let Some(source) = source {
+            self.sources.entry(method).or_default().insert(path, source);
+        }
+        Ok(())
+    }
+
+    /// 匹配表中的路由模式对应的 `path_map` 键
+    fn registered_path(&self, method: &Method, route: &str) -> Option<String> {
+        self.path_map
+            .get(method)?
+            .keys()
+            .find(|p| encode_route(p) == route)
+            .cloned()
+    }
+
+    /// 依次移除与 `path` 冲突的路由，直到 `path` 可以注册，然后重建该方法的匹配表
+    fn override_route(
+        &mut self,
+        method: &Method,
+        path: &str,
+        svc: HttpSvc<Req>,
+        mut existing: String,
+    ) -> Result<(), RouteError> {
+        loop {
+            tracing::debug!(%method, path, existing, "route overridden");
+            let paths = self.path_map.entry(method.clone()).or_default();
+            if paths.remove(&existing).is_none() {
+                // 冲突的路由不在索引中，无法移除
+                return Err(RouteError::Conflict {
+                    method: method.clone(),
+                    path: path.to_string(),
+                    existing,
+                    existing_source: None,
+                });
+            }
+            if let Some(sources) = self.sources.get_mut(method) {
+                sources.remove(&existing);
+            }
+
+            let mut table = MRouter::new();
+            for (p, s) in paths.iter() {
+                table
+                    .insert(encode_route(p), s.clone())
+                    .expect("routes were already registered without conflicts");
+            }
+            match table.insert(encode_route(path), svc.clone()) {
+                Ok(()) => {
+                    self.routes.insert(method.clone(), table);
+                    return Ok(());
+                }
+                Err(InsertError::Conflict { with }) => {
+                    existing = self.registered_path(method, &with).unwrap_or(with);
+                }
+                Err(e) => {
+                    return Err(RouteError::InvalidPattern {
+                        path: path.to_string(),
+                        message: e.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// 执行一组注册，失败时恢复到执行前的状态
+    pub(crate) fn transaction(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<(), RouteError>,
+    ) -> Result<(), RouteError> {
+        let snapshot = self.clone();
+        let result = f(self);
+        if result.is_err() {
+            *self = snapshot;
+        }
+        result
+    }
+}
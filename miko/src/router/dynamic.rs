@@ -0,0 +1,293 @@
+//! 运行时增删路由
+//!
+//! [`DynamicRouter`] 维护一张可在运行时修改的路由表。每次修改都会重新构建一份不可变快照并原子替换，
+//! 请求只读取开始处理时的快照，因此修改不会影响处理中的请求，也无需重启服务。
+//!
+//! ```rust,ignore
+//! let plugins = DynamicRouter::new();
+//! router.mount_dynamic("/plugins", &plugins);
+//! router.nest("/admin/plugins", plugins.admin_router());
+//!
+//! // 之后在任意位置（插件加载、管理接口……）
+//! plugins.add("GET", "/hello/{name}", |Path(name): Path<String>| async move { format!("hi {name}") })?;
+//! plugins.remove(&Method::GET, "/hello/{name}");
+//! ```
+
+use crate::AppError;
+use crate::extractor::Json;
+use crate::extractor::Query;
+use crate::extractor::from_request::FromRequest;
+use crate::extractor::path_params::PathParams;
+use crate::handler::{FnOnceTuple, Req, Resp, TypedHandler, handler_to_svc};
+use crate::http::response::into_response::IntoResponse;
+use crate::router::{HttpSvc, Router};
+use hyper::Method;
+use matchit::Router as MRouter;
+use miko_core::{IntoMethods, encode_route};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, RwLock};
+use std::task::{Context, Poll};
+use tower::Service;
+
+/// 路由定义的键：(方法, 路径)
+type RouteKey = (String, String);
+/// `HttpSvc` 不是 `Sync`，放入锁中才能在快照里跨线程共享，取用时克隆
+type SharedSvc = Arc<Mutex<HttpSvc<Req>>>;
+
+/// 某一时刻的不可变路由表
+struct Snapshot {
+    epoch: u64,
+    routes: HashMap<Method, MRouter<SharedSvc>>,
+}
+
+impl Snapshot {
+    fn build(epoch: u64, defs: &BTreeMap<RouteKey, SharedSvc>) -> Result<Self, DynamicRouteError> {
+        let mut routes: HashMap<Method, MRouter<SharedSvc>> = HashMap::new();
+        for ((method, path), svc) in defs {
+            let m = Method::from_bytes(method.as_bytes()).expect("stored methods are valid");
+            routes
+                .entry(m)
+                .or_default()
+                .insert(encode_route(path), svc.clone())
+                .map_err(|e| DynamicRouteError {
+                    method: method.clone(),
+                    path: path.clone(),
+                    reason: e.to_string(),
+                })?;
+        }
+        Ok(Self { epoch, routes })
+    }
+}
+
+/// 路由与现有路由冲突或路径无效
+#[derive(Debug, Clone)]
+pub struct DynamicRouteError {
+    pub method: String,
+    pub path: String,
+    pub reason: String,
+}
+
+impl fmt::Display for DynamicRouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot add route {} {}: {}",
+            self.method, self.path, self.reason
+        )
+    }
+}
+
+impl std::error::Error for DynamicRouteError {}
+
+/// 运行时注册的一条路由
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DynamicRoute {
+    pub method: String,
+    pub path: String,
+}
+
+struct Inner {
+    /// 路由定义，修改时持锁，保证快照按顺序构建
+    defs: Mutex<BTreeMap<RouteKey, SharedSvc>>,
+    snapshot: RwLock<Arc<Snapshot>>,
+}
+
+/// 可在运行时增删路由的路由表，克隆后共享同一张表
+#[derive(Clone)]
+pub struct DynamicRouter {
+    inner: Arc<Inner>,
+}
+
+impl Default for DynamicRouter {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                defs: Mutex::new(BTreeMap::new()),
+                snapshot: RwLock::new(Arc::new(Snapshot {
+                    epoch: 0,
+                    routes: HashMap::new(),
+                })),
+            }),
+        }
+    }
+}
+
+impl DynamicRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册处理函数，同一方法与路径已存在时替换
+    pub fn add<F, A, Fut, R, M>(
+        &self,
+        method: impl IntoMethods,
+        path: &str,
+        handler: F,
+    ) -> Result<(), DynamicRouteError>
+    where
+        F: FnOnceTuple<A, Output = Fut> + Clone + Send + Sync + 'static,
+        A: FromRequest<(), M> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        R: IntoResponse,
+        M: Send + Sync + 'static,
+    {
+        let svc = handler_to_svc(Arc::new(TypedHandler::new(handler, Arc::new(()))));
+        self.add_service(method, path, svc)
+    }
+
+    /// 注册一个 Service，同一方法与路径已存在时替换
+    pub fn add_service(
+        &self,
+        method: impl IntoMethods,
+        path: &str,
+        svc: HttpSvc<Req>,
+    ) -> Result<(), DynamicRouteError> {
+        let methods = method.into_methods();
+        let svc = Arc::new(Mutex::new(svc));
+        self.update(|defs| {
+            for m in &methods {
+                defs.insert((m.to_string(), path.to_string()), svc.clone());
+            }
+        })
+    }
+
+    /// 移除一条路由，返回该路由是否存在
+    pub fn remove(&self, method: &Method, path: &str) -> bool {
+        let mut removed = false;
+        self.update(|defs| {
+            removed = defs
+                .remove(&(method.to_string(), path.to_string()))
+                .is_some();
+        })
+        .expect("removing a route cannot introduce a conflict");
+        removed
+    }
+
+    /// 移除路径下所有方法的路由，返回移除的数量
+    pub fn remove_path(&self, path: &str) -> usize {
+        let mut removed = 0;
+        self.update(|defs| {
+            let before = defs.len();
+            defs.retain(|(_, p), _| p != path);
+            removed = before - defs.len();
+        })
+        .expect("removing a route cannot introduce a conflict");
+        removed
+    }
+
+    /// 当前注册的路由，按路径与方法排序
+    pub fn routes(&self) -> Vec<DynamicRoute> {
+        let defs = self.inner.defs.lock().unwrap();
+        let mut routes: Vec<DynamicRoute> = defs
+            .keys()
+            .map(|(method, path)| DynamicRoute {
+                method: method.clone(),
+                path: path.clone(),
+            })
+            .collect();
+        routes.sort_by(|a, b| (&a.path, &a.method).cmp(&(&b.path, &b.method)));
+        routes
+    }
+
+    /// 路由表版本，每次修改加一
+    pub fn epoch(&self) -> u64 {
+        self.current().epoch
+    }
+
+    /// 处理请求的 Service，每个请求使用开始处理时的快照
+    pub fn service(&self) -> HttpSvc<Req> {
+        HttpSvc::new(DynamicSvc {
+            router: self.clone(),
+        })
+    }
+
+    /// 管理接口：`GET /routes` 列出路由，`DELETE /routes?method=GET&path=/x` 移除路由
+    ///
+    /// 处理函数无法通过 HTTP 提交，新增路由仍需在代码中调用 [`add`](Self::add)。
+    pub fn admin_router(&self) -> Router {
+        let list = self.clone();
+        let remove = self.clone();
+        let mut router = Router::new();
+        router
+            .get("/routes", move || {
+                let list = list.clone();
+                async move { Json(list.routes()) }
+            })
+            .delete("/routes", move |Query(route): Query<DynamicRoute>| {
+                let remove = remove.clone();
+                async move {
+                    let method = Method::from_bytes(route.method.to_ascii_uppercase().as_bytes())
+                        .map_err(|_| {
+                        AppError::BadRequest(format!("invalid method `{}`", route.method))
+                    })?;
+                    if remove.remove(&method, &route.path) {
+                        Ok(Json(remove.routes()))
+                    } else {
+                        Err(AppError::NotFound(format!(
+                            "route {} {} is not registered",
+                            method, route.path
+                        )))
+                    }
+                }
+            });
+        router
+    }
+
+    fn current(&self) -> Arc<Snapshot> {
+        self.inner.snapshot.read().unwrap().clone()
+    }
+
+    /// 在定义副本上修改并构建新快照，成功后才提交，冲突时保持原状
+    fn update(
+        &self,
+        f: impl FnOnce(&mut BTreeMap<RouteKey, SharedSvc>),
+    ) -> Result<(), DynamicRouteError> {
+        let mut defs = self.inner.defs.lock().unwrap();
+        let mut next = defs.clone();
+        f(&mut next);
+        let snapshot = Snapshot::build(self.current().epoch + 1, &next)?;
+        *defs = next;
+        *self.inner.snapshot.write().unwrap() = Arc::new(snapshot);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct DynamicSvc {
+    router: DynamicRouter,
+}
+
+impl Service<Req> for DynamicSvc {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = Result<Resp, AppError>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut req: Req) -> Self::Future {
+        let snapshot = self.router.current();
+        let found = snapshot
+            .routes
+            .get(req.method())
+            .and_then(|routes| routes.at(req.uri().path()).ok())
+            .map(|matched| {
+                let svc = matched.value.lock().unwrap().clone();
+                (svc, PathParams::from(&matched.params))
+            });
+        match found {
+            Some((mut svc, params)) => {
+                req.extensions_mut().insert(params);
+                Box::pin(async move { svc.call(req).await })
+            }
+            None => Box::pin(async move {
+                Ok(AppError::NotFound("404 Not Found".to_string()).into_response())
+            }),
+        }
+    }
+}
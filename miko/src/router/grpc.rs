@@ -0,0 +1,100 @@
+//! gRPC 服务挂载
+//!
+//! 把 tonic 生成的 gRPC 服务挂载到 Router 的某个前缀下，与 REST 路由共用同一个端口：
+//!
+//! ```rust,ignore
+//! router.get("/health", health);
+//! router.mount_grpc("/grpc", GreeterServer::new(MyGreeter));
+//! // POST /grpc/helloworld.Greeter/SayHello -> tonic 服务看到的路径为 /helloworld.Greeter/SayHello
+//! ```
+
+use super::Router;
+use crate::AppError;
+use crate::handler::{Req, Resp};
+use http_body_util::BodyExt;
+use hyper::header::CONTENT_TYPE;
+use hyper::{Method, Request, Response, StatusCode, Version};
+use miko_core::MikoError;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tonic::body::Body as GrpcBody;
+use tower::Service;
+use tower::util::BoxCloneService;
+
+/// gRPC-Web 请求的 Content-Type 前缀，可以走 HTTP/1.1
+const GRPC_WEB_CONTENT_TYPE: &str = "application/grpc-web";
+
+impl<S: Send + Sync + 'static> Router<S> {
+    /// 将 tonic gRPC 服务挂载到前缀下，只响应 POST
+    ///
+    /// 前缀会在转发前剥离；非 HTTP/2 的 gRPC 请求返回 505，`application/grpc-web*` 请求不受此限制
+    #[track_caller]
+    pub fn mount_grpc<G>(&mut self, prefix: &str, svc: G) -> &mut Self
+    where
+        G: Service<Request<GrpcBody>, Response = Response<GrpcBody>, Error = Infallible>
+            + Clone
+            + Send
+            + 'static,
+        G::Future: Send + 'static,
+    {
+        self.nest_service_with(
+            prefix,
+            Method::POST,
+            BoxCloneService::new(GrpcService { inner: svc }),
+        );
+        self
+    }
+}
+
+/// 在 Miko 与 tonic 的请求/响应体之间转换的适配 Service
+#[derive(Clone)]
+struct GrpcService<G> {
+    inner: G,
+}
+
+impl<G> Service<Req> for GrpcService<G>
+where
+    G: Service<Request<GrpcBody>, Response = Response<GrpcBody>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    G::Future: Send + 'static,
+{
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = Result<Resp, AppError>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            if req.version() != Version::HTTP_2 && !is_grpc_web(&req) {
+                return Err(AppError::custom(
+                    StatusCode::HTTP_VERSION_NOT_SUPPORTED,
+                    "HTTP_VERSION_NOT_SUPPORTED",
+                    "gRPC requires HTTP/2",
+                ));
+            }
+            let resp = match inner.call(req.map(GrpcBody::new)).await {
+                Ok(resp) => resp,
+                Err(never) => match never {},
+            };
+            Ok(resp.map(|body| {
+                body.map_err(|status| MikoError(Box::new(status)))
+                    .boxed_unsync()
+            }))
+        })
+    }
+}
+
+fn is_grpc_web(req: &Req) -> bool {
+    req.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with(GRPC_WEB_CONTENT_TYPE))
+}
@@ -0,0 +1,146 @@
+//! 路由自省：列出已注册路由及其处理函数
+//!
+//! ```rust,ignore
+//! for (method, path, source) in router.routes_iter() {
+//!     println!("{} {} -> {}", method, path, source);
+//! }
+//! router.print_routes();
+//! ```
+
+use crate::router::Router;
+use hyper::Method;
+use miko_core::IntoMethods;
+use std::borrow::Cow;
+use std::fmt;
+use std::panic::Location;
+
+/// 路由的来源：处理函数名与注册位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteSource {
+    /// 处理函数的类型名，如 `my_app::users::list`；直接挂载的 Service 为 `<service>`
+    pub handler: Cow<'static, str>,
+    /// 注册调用所在的源码位置，路由宏注册的路由指向宏所在行
+    pub location: Option<&'static Location<'static>>,
+}
+
+/// 未记录来源（如直接修改 `path_map`）的路由
+static UNKNOWN: RouteSource = RouteSource {
+    handler: Cow::Borrowed("<unknown>"),
+    location: None,
+};
+
+impl RouteSource {
+    pub fn new(
+        handler: impl Into<Cow<'static, str>>,
+        location: Option<&'static Location<'static>>,
+    ) -> Self {
+        Self {
+            handler: handler.into(),
+            location,
+        }
+    }
+
+    /// 以处理函数类型 `F` 的类型名与调用位置创建
+    #[track_caller]
+    pub(crate) fn handler<F>() -> Self {
+        Self::new(std::any::type_name::<F>(), Some(Location::caller()))
+    }
+
+    /// 直接挂载的 Service
+    #[track_caller]
+    pub(crate) fn service() -> Self {
+        Self::new("<service>", Some(Location::caller()))
+    }
+}
+
+impl fmt::Display for RouteSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some(location) => write!(f, "{} ({})", self.handler, location),
+            None => f.write_str(&self.handler),
+        }
+    }
+}
+
+impl<S> Router<S> {
+    /// 按路径、方法排序遍历所有路由：`(方法, 路由模式, 来源)`
+    pub fn routes_iter(&self) -> impl Iterator<Item = (&Method, &str, &RouteSource)> {
+        let mut routes: Vec<(&Method, &str, &RouteSource)> = self
+            .path_map
+            .iter()
+            .flat_map(|(method, paths)| {
+                paths.keys().map(move |path| {
+                    let source = self
+                        .sources
+                        .get(method)
+                        .and_then(|sources| sources.get(path))
+                        .unwrap_or(&UNKNOWN);
+                    (method, path.as_str(), source)
+                })
+            })
+            .collect();
+        routes.sort_by(|a, b| (a.1, a.0.as_str()).cmp(&(b.1, b.0.as_str())));
+        routes.into_iter()
+    }
+
+    /// 修改 `routes_iter` 中显示的处理函数名，`path` 下未注册的方法被忽略
+    ///
+    /// 用于经 [`service_with`](Router::service_with) 挂载的处理函数，路由宏会自动调用。
+    pub fn name_route(
+        &mut self,
+        method: impl IntoMethods,
+        path: &str,
+        handler: impl Into<Cow<'static, str>>,
+    ) -> &mut Self {
+        let handler = handler.into();
+        for method in method.into_methods() {
+            if let Some(source) = self
+                .sources
+                .get_mut(&method)
+                .and_then(|sources| sources.get_mut(path))
+            {
+                source.handler = handler.clone();
+            }
+        }
+        self
+    }
+
+    /// 路由表文本，每行一条路由：方法、路径、处理函数与注册位置
+    pub fn routes_table(&self) -> String {
+        let rows: Vec<[String; 4]> = self
+            .routes_iter()
+            .map(|(method, path, source)| {
+                [
+                    method.to_string(),
+                    path.to_string(),
+                    source.handler.to_string(),
+                    source.location.map(|l| l.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect();
+        let header = ["METHOD", "PATH", "HANDLER", "LOCATION"].map(String::from);
+        let mut widths = [0; 4];
+        for row in std::iter::once(&header).chain(&rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let mut table = String::new();
+        for row in std::iter::once(&header).chain(&rows) {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ");
+            table.push_str(line.trim_end());
+            table.push('\n');
+        }
+        table
+    }
+
+    /// 将 [`routes_table`](Router::routes_table) 打印到标准输出
+    pub fn print_routes(&self) {
+        print!("{}", self.routes_table());
+    }
+}
@@ -0,0 +1,42 @@
+use hyper::Method;
+use miko_core::IntoMethods;
+use std::sync::LazyLock;
+
+/// 内部使用的“任意方法”路由表键，按方法查找未命中时回退到该表
+pub(crate) static ANY_METHOD: LazyLock<Method> =
+    LazyLock::new(|| Method::from_bytes(b"*").unwrap());
+
+/// 挂载 Service 时响应的 HTTP 方法集合
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MethodSet {
+    /// 常用方法：GET/POST/PUT/DELETE/PATCH/HEAD/OPTIONS
+    Common,
+    /// 任意方法，包括 TRACE/CONNECT 与扩展方法（如 PROPFIND）
+    Any,
+    /// 仅指定的方法
+    Only(Vec<Method>),
+}
+
+impl MethodSet {
+    pub(crate) fn methods(&self) -> Vec<Method> {
+        match self {
+            MethodSet::Common => vec![
+                Method::GET,
+                Method::POST,
+                Method::PUT,
+                Method::DELETE,
+                Method::PATCH,
+                Method::HEAD,
+                Method::OPTIONS,
+            ],
+            MethodSet::Any => vec![ANY_METHOD.clone()],
+            MethodSet::Only(methods) => methods.clone(),
+        }
+    }
+}
+
+impl<T: IntoMethods> From<T> for MethodSet {
+    fn from(methods: T) -> Self {
+        MethodSet::Only(methods.into_methods())
+    }
+}
@@ -1,23 +1,45 @@
+pub mod conflict;
+#[cfg(feature = "dynamic-routes")]
+pub mod dynamic;
+#[cfg(feature = "grpc")]
+mod grpc;
+pub mod introspect;
+pub mod method_set;
 pub mod nested;
+mod overrides;
+pub mod redirect;
 pub mod router_svc;
+pub mod trailing_slash;
 
 use crate::AppError;
+use crate::app::config::RedirectRule;
 #[cfg(feature = "ext")]
 use crate::ext::static_svc::StaticSvcBuilder;
+use crate::extractor::RouteLocale;
 use crate::extractor::{from_request::FromRequest, path_params::PathParams};
 use crate::handler::{DynHandler, handler_to_svc};
 use crate::handler::{FnOnceTuple, Req, Resp, TypedHandler};
 use crate::http::response::into_response::IntoResponse;
+use crate::middleware::extension::ExtensionLayer;
+use crate::router::conflict::{RouteConflictPolicy, RouteError};
+use crate::router::introspect::RouteSource;
+use crate::router::method_set::{ANY_METHOD, MethodSet};
+use crate::router::redirect::RedirectSvc;
 use crate::router::router_svc::RouterSvc;
+use crate::router::trailing_slash::TrailingSlash;
 use bytes::Bytes;
 use http_body_util::{BodyExt, Full};
+use hyper::header::{ALLOW, HeaderValue};
 use hyper::{Method, Request, Response, body::Incoming};
 use matchit::Router as MRouter;
-use miko_core::{BoxError, IntoMethods, MikoError, encode_route};
+use miko_core::{BoxError, IntoMethods, MikoError};
 use nested::NestLayer;
 #[cfg(feature = "ext")]
 use std::path::PathBuf;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 use tower::{Layer, Service, util::BoxCloneService};
 
 /// 生成各 HTTP 方法的简化注册函数（如 get/post/...）
@@ -26,6 +48,7 @@ use tower::{Layer, Service, util::BoxCloneService};
 macro_rules! define_method {
     ($name:ident, $m:ident) => {
         /// 将处理函数绑定到给定路径上（此函数注册指定的 HTTP 方法）
+        #[track_caller]
         pub fn $name<F, A, Fut, R, M>(&mut self, path: &str, handler: F) -> &mut Self
         where
             F: FnOnceTuple<A, Output = Fut> + Clone + Send + Sync + 'static,
@@ -35,15 +58,14 @@ macro_rules! define_method {
             M: Send + Sync + 'static,
         {
             let handler = Arc::new(TypedHandler::new(handler, self.state.clone())) as DynHandler;
-            self.routes
-                .entry(Method::$m)
-                .or_default()
-                .insert(encode_route(path), handler_to_svc(handler.clone()))
-                .unwrap();
-            self.path_map
-                .entry(Method::$m)
-                .or_default()
-                .insert(path.to_string(), handler_to_svc(handler.clone()));
+            if let Err(e) = self.insert_route(
+                Method::$m,
+                path.to_string(),
+                handler_to_svc(handler),
+                Some(RouteSource::handler::<F>()),
+            ) {
+                panic!("{}", e);
+            }
             self
         }
     };
@@ -53,16 +75,16 @@ macro_rules! define_method {
 macro_rules! define_handle_service {
     ($name:ident, $m:ident) => {
         /// 将一个 Service 直接挂载到给定路径（此函数注册指定的 HTTP 方法）
+        #[track_caller]
         pub fn $name(&mut self, path: &str, svc: HttpSvc<Req>) -> &mut Self {
-            self.routes
-                .entry(Method::$m.clone())
-                .or_insert_with(|| MRouter::new())
-                .insert(encode_route(path), svc.clone())
-                .unwrap();
-            self.path_map
-                .entry(Method::$m.clone())
-                .or_insert_with(|| HashMap::new())
-                .insert(path.to_string(), svc.clone());
+            if let Err(e) = self.insert_route(
+                Method::$m,
+                path.to_string(),
+                svc,
+                Some(RouteSource::service()),
+            ) {
+                panic!("{}", e);
+            }
             self
         }
     };
@@ -73,6 +95,19 @@ pub type HttpReq = Request<Incoming>;
 /// Tower 兼容的 Service 类型别名
 pub type HttpSvc<T = HttpReq> = BoxCloneService<T, Resp, AppError>;
 
+/// 请求命中的路由模式，如 `/users/{id}`
+///
+/// 路由器会写入请求与响应的 extensions：处理函数可通过 `Extension<MatchedPath>` 提取，
+/// 外层中间件可从响应中读取，按路由而不是按原始路径统计。未命中路由的请求没有该扩展。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchedPath(pub Arc<str>);
+
+impl MatchedPath {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 type MikoLayer<T = Req> = Arc<dyn Fn(HttpSvc<T>) -> HttpSvc<T> + Send + Sync>;
 /// 路由器，负责注册路由、挂载中间件/服务并进行请求分发
 pub struct Router<S = ()> {
@@ -84,6 +119,20 @@ pub struct Router<S = ()> {
     pub layers: Vec<MikoLayer>,
     /// 用于 nest/merge 的路径映射索引
     pub path_map: HashMap<Method, HashMap<String, HttpSvc<Req>>>,
+    /// 各路由的处理函数名与注册位置，键与 `path_map` 一致
+    pub sources: HashMap<Method, HashMap<String, RouteSource>>,
+    /// 已追加中间件的类型名（用于路由清单），nest 进来的带有前缀
+    pub layer_names: Vec<String>,
+    /// 未匹配任何路由时的处理 Service（前缀，Service），nest 进来的带有前缀
+    pub fallbacks: Vec<(String, HttpSvc<Req>)>,
+    /// 路径存在但方法不匹配时的处理 Service（前缀，Service）
+    pub method_not_allowed: Vec<(String, HttpSvc<Req>)>,
+    /// 注册的路由与已有路由冲突时的处理方式
+    pub conflict_policy: RouteConflictPolicy,
+    /// 路径存在但方法不匹配且没有 method_not_allowed 处理函数时，是否返回 405
+    pub auto_method_not_allowed: bool,
+    /// 请求路径只差末尾斜杠时的处理方式
+    pub trailing_slash: TrailingSlash,
 }
 impl<S> Clone for Router<S> {
     fn clone(&self) -> Self {
@@ -92,36 +141,77 @@ impl<S> Clone for Router<S> {
             state: self.state.clone(),
             layers: self.layers.clone(),
             path_map: self.path_map.clone(),
+            sources: self.sources.clone(),
+            layer_names: self.layer_names.clone(),
+            fallbacks: self.fallbacks.clone(),
+            method_not_allowed: self.method_not_allowed.clone(),
+            conflict_policy: self.conflict_policy,
+            auto_method_not_allowed: self.auto_method_not_allowed,
+            trailing_slash: self.trailing_slash,
         }
     }
 }
 
 impl<S: Send + Sync + 'static> Router<S> {
     /// 根据方法与路径查找对应的处理 Service，并返回路径参数
+    ///
+    /// 按方法未命中时回退到以 [`MethodSet::Any`] 挂载的路由
     pub fn find_handler(&self, method: &Method, path: &str) -> Option<(HttpSvc<Req>, PathParams)> {
-        if let Some(router) = self.routes.get(method) {
-            match router.at(path) {
-                Ok(matched) => {
-                    let handler = matched.value.clone();
-                    Some((handler, PathParams::from(&matched.params)))
-                }
-                Err(_e) => None,
-            }
-        } else {
-            None
+        [method, &*ANY_METHOD]
+            .into_iter()
+            .filter_map(|m| self.routes.get(m))
+            .find_map(|router| router.at(path).ok())
+            .map(|matched| (matched.value.clone(), PathParams::from(&matched.params)))
+    }
+    /// 路径在其他方法下已注册时，返回这些方法（已排序，不含 [`MethodSet::Any`]）
+    pub fn allowed_methods(&self, path: &str) -> Vec<Method> {
+        let mut methods: Vec<Method> = self
+            .routes
+            .iter()
+            .filter(|(m, router)| **m != *ANY_METHOD && router.at(path).is_ok())
+            .map(|(m, _)| m.clone())
+            .collect();
+        methods.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        methods
+    }
+
+    /// 查找未匹配请求的处理 Service：路径存在但方法不匹配时优先使用 method_not_allowed，
+    /// 未注册时返回默认的 405（见 [`Router::auto_method_not_allowed`]），其次使用 fallback；
+    /// 均按最长前缀选择。第二项为需要写入的 `Allow` 头
+    pub fn find_unmatched(&self, path: &str) -> Option<(HttpSvc<Req>, Option<HeaderValue>)> {
+        let allowed = self.allowed_methods(path);
+        if !allowed.is_empty()
+            && let Some(svc) = longest_prefix(&self.method_not_allowed, path).or_else(|| {
+                self.auto_method_not_allowed
+                    .then(default_method_not_allowed)
+            })
+        {
+            let allow = allowed
+                .iter()
+                .map(Method::as_str)
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Some((svc, HeaderValue::from_str(&allow).ok()));
         }
+        longest_prefix(&self.fallbacks, path).map(|svc| (svc, None))
     }
+
     /// 直接处理一个请求（内部使用），会自动写入 PathParams 并执行 Service
     pub async fn handle(&self, method: &Method, path: &str, mut req: Req) -> Resp {
-        if let Some(router) = self.routes.get(method) {
-            match router.at(path) {
-                Ok(matched) => {
-                    req.extensions_mut()
-                        .insert(PathParams::from(&matched.params));
-                    let mut handler = matched.value.clone();
-                    handler.call(req).await.into_response()
+        match self.find_handler(method, path) {
+            Some((mut handler, params)) => {
+                req.extensions_mut().insert(params);
+                handler.call(req).await.into_response()
+            }
+            None => match self.find_unmatched(path) {
+                Some((mut svc, allow)) => {
+                    let mut resp = svc.call(req).await.into_response();
+                    if let Some(allow) = allow {
+                        resp.headers_mut().entry(ALLOW).or_insert(allow);
+                    }
+                    resp
                 }
-                Err(_e) => Response::builder()
+                None => Response::builder()
                     .status(hyper::StatusCode::NOT_FOUND)
                     .body(
                         Full::new(Bytes::from("Not Found"))
@@ -129,20 +219,36 @@ impl<S: Send + Sync + 'static> Router<S> {
                             .boxed_unsync(),
                     )
                     .unwrap(),
-            }
-        } else {
-            Response::builder()
-                .status(hyper::StatusCode::NOT_FOUND)
-                .body(
-                    Full::new(Bytes::from("Not Found"))
-                        .map_err(Into::into)
-                        .boxed_unsync(),
-                )
-                .unwrap()
+            },
         }
     }
 }
 
+/// 默认的 405 响应
+fn default_method_not_allowed() -> HttpSvc<Req> {
+    BoxCloneService::new(tower::service_fn(|_req: Req| async {
+        Ok(AppError::MethodNotAllowed("405 Method Not Allowed".to_string()).into_response())
+    }))
+}
+
+/// 按段匹配前缀（`{name}` 段匹配任意一段），返回段数最多的一项
+fn longest_prefix(entries: &[(String, HttpSvc<Req>)], path: &str) -> Option<HttpSvc<Req>> {
+    let path_segs: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    entries
+        .iter()
+        .filter_map(|(prefix, svc)| {
+            let segs: Vec<&str> = prefix.split('/').filter(|s| !s.is_empty()).collect();
+            let matched = segs.len() <= path_segs.len()
+                && segs
+                    .iter()
+                    .zip(&path_segs)
+                    .all(|(p, s)| (p.starts_with('{') && p.ends_with('}')) || p == s);
+            matched.then_some((segs.len(), svc))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, svc)| svc.clone())
+}
+
 impl Router {
     /// 创建一个空路由器
     pub fn new() -> Self {
@@ -151,6 +257,13 @@ impl Router {
             state: Arc::new(()),
             layers: Vec::new(),
             path_map: HashMap::new(),
+            sources: HashMap::new(),
+            layer_names: Vec::new(),
+            fallbacks: Vec::new(),
+            method_not_allowed: Vec::new(),
+            conflict_policy: RouteConflictPolicy::default(),
+            auto_method_not_allowed: true,
+            trailing_slash: TrailingSlash::default(),
         }
     }
 }
@@ -160,6 +273,8 @@ impl<S: Send + Sync + 'static> Router<S> {
     ///
     /// - 支持一次性注册多个方法：get/post/put/delete/head/options/trace/connect/patch
     /// - 处理函数参数由一组 Extractor 决定，返回值需实现 IntoResponse
+    /// - 与已有路由冲突时按 [`RouteConflictPolicy`] 处理，默认 panic
+    #[track_caller]
     pub fn route<F, A, Fut, R, M>(
         &mut self,
         method: impl IntoMethods,
@@ -174,20 +289,102 @@ impl<S: Send + Sync + 'static> Router<S> {
         M: Send + Sync + 'static,
     {
         let handler = Arc::new(TypedHandler::new(handler, self.state.clone())) as DynHandler;
+        let source = RouteSource::handler::<F>();
         for m in method.into_methods() {
-            self.routes
-                .entry(m.clone())
-                .or_default()
-                .insert(encode_route(path), handler_to_svc(handler.clone()))
-                .unwrap();
-            self.path_map
-                .entry(m.clone())
-                .or_default()
-                .insert(path.to_string(), handler_to_svc(handler.clone()));
+            let svc = handler_to_svc(handler.clone());
+            if let Err(e) = self.insert_route(m, path.to_string(), svc, Some(source.clone())) {
+                panic!("{}", e);
+            }
         }
         self
     }
 
+    /// 同 [`Router::route`]，但冲突时返回错误而不是 panic，此时不注册任何方法
+    #[track_caller]
+    pub fn try_route<F, A, Fut, R, M>(
+        &mut self,
+        method: impl IntoMethods,
+        path: &str,
+        handler: F,
+    ) -> Result<&mut Self, RouteError>
+    where
+        F: FnOnceTuple<A, Output = Fut> + Clone + Send + Sync + 'static,
+        A: FromRequest<S, M> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        R: IntoResponse,
+        M: Send + Sync + 'static,
+    {
+        let handler = Arc::new(TypedHandler::new(handler, self.state.clone())) as DynHandler;
+        let source = RouteSource::handler::<F>();
+        self.transaction(|router| {
+            for m in method.into_methods() {
+                let svc = handler_to_svc(handler.clone());
+                router.insert_route(m, path.to_string(), svc, Some(source.clone()))?;
+            }
+            Ok(())
+        })?;
+        Ok(self)
+    }
+
+    /// 为任意（包括扩展）方法注册处理函数，如 `router.method("PROPFIND", "/dav/{*path}", handler)`
+    ///
+    /// 方法名大小写不敏感，支持逗号分隔多个方法
+    #[track_caller]
+    pub fn method<F, A, Fut, R, M>(&mut self, method: &str, path: &str, handler: F) -> &mut Self
+    where
+        F: FnOnceTuple<A, Output = Fut> + Clone + Send + Sync + 'static,
+        A: FromRequest<S, M> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        R: IntoResponse,
+        M: Send + Sync + 'static,
+    {
+        self.route(method, path, handler)
+    }
+
+    /// 注册未匹配任何路由时的处理函数（支持提取器），替代默认的 404 响应
+    ///
+    /// 被 nest 的 Router 的 fallback 只处理其前缀下的请求，前缀最长者优先
+    pub fn fallback<F, A, Fut, R, M>(&mut self, handler: F) -> &mut Self
+    where
+        F: FnOnceTuple<A, Output = Fut> + Clone + Send + Sync + 'static,
+        A: FromRequest<S, M> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        R: IntoResponse,
+        M: Send + Sync + 'static,
+    {
+        let svc = handler_to_svc(Arc::new(TypedHandler::new(handler, self.state.clone())));
+        self.fallbacks.retain(|(prefix, _)| !prefix.is_empty());
+        self.fallbacks.push((String::new(), svc));
+        self
+    }
+
+    /// 注册路径存在但方法不匹配时的处理函数（支持提取器）
+    ///
+    /// 响应会自动带上列出该路径已注册方法的 `Allow` 头（处理函数已设置时保留原值）
+    pub fn method_not_allowed<F, A, Fut, R, M>(&mut self, handler: F) -> &mut Self
+    where
+        F: FnOnceTuple<A, Output = Fut> + Clone + Send + Sync + 'static,
+        A: FromRequest<S, M> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        R: IntoResponse,
+        M: Send + Sync + 'static,
+    {
+        let svc = handler_to_svc(Arc::new(TypedHandler::new(handler, self.state.clone())));
+        self.method_not_allowed
+            .retain(|(prefix, _)| !prefix.is_empty());
+        self.method_not_allowed.push((String::new(), svc));
+        self
+    }
+
+    /// 设置路径存在但方法不匹配时是否自动返回带 `Allow` 头的 405，默认开启
+    ///
+    /// 关闭后这类请求与未匹配的路径一样交给 fallback 或返回 404；
+    /// 已注册的 method_not_allowed 处理函数不受影响。只在最外层 Router 上生效
+    pub fn auto_method_not_allowed(&mut self, enabled: bool) -> &mut Self {
+        self.auto_method_not_allowed = enabled;
+        self
+    }
+
     define_method!(get, GET);
     define_method!(post, POST);
     define_method!(put, PUT);
@@ -218,126 +415,179 @@ impl<S: Send + Sync + 'static> Router<S> {
             state: Arc::new(state),
             layers: self.layers,
             path_map: self.path_map,
+            sources: self.sources,
+            layer_names: self.layer_names,
+            fallbacks: self.fallbacks,
+            method_not_allowed: self.method_not_allowed,
+            conflict_policy: self.conflict_policy,
+            auto_method_not_allowed: self.auto_method_not_allowed,
+            trailing_slash: self.trailing_slash,
         }
     }
 
     /// 合并另一个 Router，所有路由与索引一并合并
-    pub fn merge<T>(&mut self, mut other: Router<T>) -> &mut Self {
+    ///
+    /// 与已有路由冲突时按 [`RouteConflictPolicy`] 处理，默认 panic
+    #[track_caller]
+    pub fn merge<T>(&mut self, other: Router<T>) -> &mut Self {
+        if let Err(e) = self.merge_routes(other) {
+            panic!("{}", e);
+        }
+        self
+    }
+
+    /// 同 [`Router::merge`]，但冲突时返回错误而不是 panic，此时路由器保持不变
+    pub fn try_merge<T>(&mut self, other: Router<T>) -> Result<&mut Self, RouteError> {
+        self.transaction(|router| router.merge_routes(other))?;
+        Ok(self)
+    }
+
+    fn merge_routes<T>(&mut self, mut other: Router<T>) -> Result<(), RouteError> {
         let layers = std::mem::take(&mut other.layers);
+        self.layer_names.append(&mut other.layer_names);
+        let layered = |(prefix, mut svc): (String, HttpSvc<Req>)| {
+            for apply in &layers {
+                svc = apply(svc);
+            }
+            (prefix, svc)
+        };
+        self.fallbacks
+            .extend(other.fallbacks.drain(..).map(layered));
+        self.method_not_allowed
+            .extend(other.method_not_allowed.drain(..).map(layered));
 
         for (method, _) in other.routes.drain() {
+            let mut sources = other.sources.remove(&method).unwrap_or_default();
             for (path, mut svc) in other.path_map.get_mut(&method).unwrap().drain() {
                 for apply in &layers {
                     svc = apply(svc);
                 }
                 let boxed: HttpSvc<Req> = BoxCloneService::new(svc);
-                self.routes
-                    .entry(method.clone())
-                    .or_default()
-                    .insert(&path, boxed.clone())
-                    .unwrap();
-                self.path_map
-                    .entry(method.clone())
-                    .or_default()
-                    .insert(path, boxed.clone());
+                let source = sources.remove(&path);
+                self.insert_route(method.clone(), path, boxed, source)?;
             }
         }
-        self
+        Ok(())
     }
 
     /// 将另一个 Router 挂载到指定前缀
     ///
     /// 被挂载的 Router 内部匹配到的是去除前缀后的路径与参数
     /// 被挂载 Router 的 layers 会自动应用到其所有路由
-    pub fn nest<T>(&mut self, prefix: &str, mut other: Router<T>) -> &mut Self {
+    /// 与已有路由冲突时按 [`RouteConflictPolicy`] 处理，默认 panic
+    #[track_caller]
+    pub fn nest<T>(&mut self, prefix: &str, other: Router<T>) -> &mut Self {
+        if let Err(e) = self.nest_routes(prefix, other) {
+            panic!("{}", e);
+        }
+        self
+    }
+
+    /// 同 [`Router::nest`]，但冲突时返回错误而不是 panic，此时路由器保持不变
+    pub fn try_nest<T>(&mut self, prefix: &str, other: Router<T>) -> Result<&mut Self, RouteError> {
+        self.transaction(|router| router.nest_routes(prefix, other))?;
+        Ok(self)
+    }
+
+    fn nest_routes<T>(&mut self, prefix: &str, mut other: Router<T>) -> Result<(), RouteError> {
         let prefix = prefix.trim_end_matches('/').to_string();
         let layers = std::mem::take(&mut other.layers);
+        self.layer_names.extend(
+            other
+                .layer_names
+                .drain(..)
+                .map(|name| format!("{} {}", prefix, name)),
+        );
+        let nested = |(sub, mut svc): (String, HttpSvc<Req>)| {
+            for apply in &layers {
+                svc = apply(svc);
+            }
+            let svc: HttpSvc<Req> = BoxCloneService::new(NestLayer::new(&prefix).layer(svc));
+            (format!("{}{}", prefix, sub), svc)
+        };
+        self.fallbacks.extend(other.fallbacks.drain(..).map(nested));
+        self.method_not_allowed
+            .extend(other.method_not_allowed.drain(..).map(nested));
 
         for (method, _) in other.routes.drain() {
+            let mut sources = other.sources.remove(&method).unwrap_or_default();
             for (path, mut svc) in other.path_map.get_mut(&method).unwrap().drain() {
                 for apply in &layers {
                     svc = apply(svc);
                 }
                 let layered = NestLayer::new(&prefix).layer(svc);
                 let boxed: HttpSvc<Req> = BoxCloneService::new(layered);
-                let new_path = format!("{}{}", prefix, path);
-                self.routes
-                    .entry(method.clone())
-                    .or_default()
-                    .insert(&new_path, boxed.clone())
-                    .unwrap();
-                self.path_map
-                    .entry(method.clone())
-                    .or_default()
-                    .insert(new_path, boxed.clone());
+                let source = sources.remove(&path);
+                self.insert_route(method.clone(), format!("{}{}", prefix, path), boxed, source)?;
             }
         }
-        self
+        Ok(())
     }
 
     /// 将一个 Service 挂载到前缀下的所有路由（常用方法）
     ///
     /// 无需显式声明 `{*rest}`，会自动追加；如需手动控制，请使用 [`Router::service`]
+    #[track_caller]
     pub fn nest_service(&mut self, prefix: &str, svc: HttpSvc<Req>) {
+        self.nest_service_with(prefix, MethodSet::Common, svc);
+    }
+
+    /// 同 [`Router::nest_service`]，但可指定响应的方法集合
+    ///
+    /// 代理、WebDAV 等需要 TRACE/CONNECT 或扩展方法的服务可使用 [`MethodSet::Any`]
+    #[track_caller]
+    pub fn nest_service_with(
+        &mut self,
+        prefix: &str,
+        methods: impl Into<MethodSet>,
+        svc: HttpSvc<Req>,
+    ) {
         let prefix = prefix.trim_end_matches('/').to_string();
         let layered = NestLayer::new(&prefix).layer(svc);
         let boxed: HttpSvc<Req> = BoxCloneService::new(layered);
-        let methods = [
-            Method::GET,
-            Method::POST,
-            Method::PUT,
-            Method::DELETE,
-            Method::PATCH,
-            Method::HEAD,
-            Method::OPTIONS,
-        ];
         let new_path = format!("{}{}", prefix, "/{*rest}");
         let new_path_index = format!("{}{}", prefix, "/");
-        for method in methods {
-            self.routes
-                .entry(method.clone())
-                .or_default()
-                .insert(&new_path, boxed.clone())
-                .unwrap();
-            self.path_map
-                .entry(method.clone())
-                .or_default()
-                .insert(new_path.clone(), boxed.clone());
-            self.routes
-                .entry(method.clone())
-                .or_default()
-                .insert(&new_path_index, boxed.clone())
-                .unwrap();
-            self.path_map
-                .entry(method.clone())
-                .or_default()
-                .insert(new_path_index.clone(), boxed.clone());
+        let source = RouteSource::service();
+        for method in methods.into().methods() {
+            for path in [&new_path, &new_path_index] {
+                if let Err(e) = self.insert_route(
+                    method.clone(),
+                    path.clone(),
+                    boxed.clone(),
+                    Some(source.clone()),
+                ) {
+                    panic!("{}", e);
+                }
+            }
         }
     }
 
+    /// 将 [`DynamicRouter`](dynamic::DynamicRouter) 挂载到前缀下，响应任意方法
+    #[cfg(feature = "dynamic-routes")]
+    #[track_caller]
+    pub fn mount_dynamic(&mut self, prefix: &str, dynamic: &dynamic::DynamicRouter) -> &mut Self {
+        self.nest_service_with(prefix, MethodSet::Any, dynamic.service());
+        self
+    }
+
     /// 将一个 Service 同时挂载到所有常用 HTTP 方法
     ///
     /// 同时也派生了若干单方法版本（如 get_service 等）
+    #[track_caller]
     pub fn service(&mut self, path: &str, svc: HttpSvc<Req>) {
-        let methods = [
-            Method::GET,
-            Method::POST,
-            Method::PUT,
-            Method::DELETE,
-            Method::PATCH,
-            Method::HEAD,
-            Method::OPTIONS,
-        ];
-        for method in methods {
-            self.routes
-                .entry(method.clone())
-                .or_default()
-                .insert(encode_route(path), svc.clone())
-                .unwrap();
-            self.path_map
-                .entry(method.clone())
-                .or_default()
-                .insert(path.to_string(), svc.clone());
+        self.service_with(path, MethodSet::Common, svc);
+    }
+
+    /// 同 [`Router::service`]，但可指定响应的方法集合
+    #[track_caller]
+    pub fn service_with(&mut self, path: &str, methods: impl Into<MethodSet>, svc: HttpSvc<Req>) {
+        let source = RouteSource::service();
+        for method in methods.into().methods() {
+            if let Err(e) =
+                self.insert_route(method, path.to_string(), svc.clone(), Some(source.clone()))
+            {
+                panic!("{}", e);
+            }
         }
     }
 
@@ -351,6 +601,8 @@ impl<S: Send + Sync + 'static> Router<S> {
         B: http_body::Body<Data = Bytes> + Send + 'static,
         B::Error: Into<BoxError>,
     {
+        self.layer_names
+            .push(std::any::type_name::<L>().to_string());
         self.layers.push(Arc::new(move |svc: HttpSvc<Req>| {
             let wrapped = layer.layer(svc);
             let standardized = tower::ServiceBuilder::new()
@@ -366,19 +618,36 @@ impl<S: Send + Sync + 'static> Router<S> {
         self
     }
 
-    /// 开启捕获panic
+    /// 为每个请求插入 `value` 的克隆，处理器通过 [`Extension`](crate::extractor::Extension) 提取
+    pub fn layer_extension<T>(&mut self, value: T) -> &mut Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.with_layer(ExtensionLayer::new(value))
+    }
+
+    /// 开启捕获panic，处理器 panic 时返回 500 并记录到 tracing
+    ///
+    /// 需要自定义响应或上报方式时改用 [`CatchPanicLayer`](crate::middleware::catch_panic::CatchPanicLayer)。
     #[cfg(feature = "catch_panic")]
     pub fn with_catch_panic(&mut self) -> &mut Self {
-        use tower_http::catch_panic::CatchPanicLayer;
-        self.with_layer(CatchPanicLayer::custom(|payload| {
-            AppError::from_panic(payload).into_response()
-        }))
+        self.with_layer(crate::middleware::catch_panic::CatchPanicLayer::new())
+    }
+
+    /// 按 `Accept-Encoding` 压缩响应（gzip/br/zstd），并解压带 `Content-Encoding` 的请求体
+    ///
+    /// 需要自定义算法或阈值时改用 [`CompressionLayer`](crate::middleware::compression::CompressionLayer)。
+    #[cfg(feature = "compression")]
+    pub fn with_compression(&mut self) -> &mut Self {
+        use crate::middleware::compression::{CompressionLayer, DecompressionLayer};
+        self.with_layer(DecompressionLayer::new())
+            .with_layer(CompressionLayer::new())
     }
 
     /// 将路由器转换为 Tower Service，自动应用之前注册的 Layer
     pub fn into_tower_service(mut self) -> HttpSvc<Req> {
         let layers = std::mem::take(&mut self.layers);
-        let router_svc = RouterSvc { router: self };
+        let router_svc = RouterSvc::new(self);
         let mut svc: HttpSvc<Req> = BoxCloneService::new(router_svc);
         for apply in layers {
             svc = apply(svc);
@@ -395,22 +664,179 @@ impl<S: Send + Sync + 'static> Router<S> {
                 state: self.state.clone(),
                 layers: Vec::new(),
                 path_map: HashMap::new(),
+                sources: HashMap::new(),
+                layer_names: Vec::new(),
+                fallbacks: Vec::new(),
+                method_not_allowed: Vec::new(),
+                conflict_policy: self.conflict_policy,
+                auto_method_not_allowed: self.auto_method_not_allowed,
+                trailing_slash: self.trailing_slash,
             },
         )
     }
 }
 
+impl<S: Send + Sync + 'static> Router<S> {
+    /// 注册重定向，`from` 中的路径参数可在 `to` 中以 `{name}` 引用
+    ///
+    /// `status` 必须是 301/302/303/307/308 之一，否则 panic
+    pub fn redirect(&mut self, from: &str, to: &str, status: u16) -> &mut Self {
+        if let Err(e) = self.try_redirect(from, to, status) {
+            panic!("{}", e);
+        }
+        self
+    }
+
+    /// 注册配置中的重定向（`[server.redirects]`），非法或冲突的条目会被跳过并记录错误
+    pub fn redirects_from(&mut self, redirects: &BTreeMap<String, RedirectRule>) -> &mut Self {
+        for (from, rule) in redirects {
+            if let Err(e) = self.try_redirect(from, rule.to(), rule.status()) {
+                tracing::error!("skip redirect {}: {}", from, e);
+            }
+        }
+        self
+    }
+
+    /// 为已注册的 `path` 添加本地化别名，`alias` 下所有方法使用与 `path` 相同的处理函数，
+    /// 处理器可通过 [`RouteLocale`](crate::extractor::RouteLocale) 取得 `locale`
+    ///
+    /// `path` 未注册或 `alias` 与现有路由冲突时 panic
+    pub fn localized_alias(&mut self, path: &str, locale: &str, alias: &str) -> &mut Self {
+        if let Err(e) = self.try_localized_alias(path, locale, alias) {
+            panic!("{}", e);
+        }
+        self
+    }
+
+    /// 注册配置中的本地化别名（`[server.route_aliases]`），非法或冲突的条目会被跳过并记录错误
+    pub fn localized_aliases_from(
+        &mut self,
+        aliases: &BTreeMap<String, BTreeMap<String, String>>,
+    ) -> &mut Self {
+        for (path, locales) in aliases {
+            for (locale, alias) in locales {
+                if let Err(e) = self.try_localized_alias(path, locale, alias) {
+                    tracing::error!("skip localized alias {}: {}", alias, e);
+                }
+            }
+        }
+        self
+    }
+
+    fn try_localized_alias(&mut self, path: &str, locale: &str, alias: &str) -> Result<(), String> {
+        let svcs: Vec<(Method, HttpSvc<Req>)> = self
+            .path_map
+            .iter()
+            .filter_map(|(method, paths)| Some((method.clone(), paths.get(path)?.clone())))
+            .collect();
+        if svcs.is_empty() {
+            return Err(format!("route {} is not registered", path));
+        }
+        let layer = ExtensionLayer::new(RouteLocale::new(locale));
+        for (method, svc) in svcs {
+            let svc: HttpSvc<Req> = BoxCloneService::new(layer.clone().layer(svc));
+            let source = self.sources.get(&method).and_then(|s| s.get(path)).cloned();
+            self.insert_route(method, alias.to_string(), svc, source)
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn try_redirect(&mut self, from: &str, to: &str, status: u16) -> Result<(), String> {
+        let svc: HttpSvc<Req> = BoxCloneService::new(RedirectSvc::new(to, status)?);
+        let source = RouteSource::new(format!("<redirect to {}>", to), None);
+        for method in MethodSet::Common.methods() {
+            self.insert_route(method, from.to_string(), svc.clone(), Some(source.clone()))
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(feature = "test")]
 impl<S: Send + Sync + 'static> Router<S> {
     /// 创建一个测试客户端，用于发送请求并断言响应
     pub fn test_client(self) -> crate::test::test_client::TestClient {
         crate::test::test_client::TestClient::new(self.into_tower_service())
     }
+
+    /// 导出路由清单（方法、路径与中间件），用于比较两次构建之间的路由变化
+    pub fn export_manifest(&self) -> crate::test::manifest::RouteManifest {
+        crate::test::manifest::RouteManifest::from_router(self)
+    }
+
+    /// 创建静态快照导出器，将 GET 路由的响应写成静态文件
+    pub fn snapshot(self) -> crate::test::snapshot::Snapshot {
+        crate::test::snapshot::Snapshot::from_router(self)
+    }
+}
+
+#[cfg(all(feature = "auto", feature = "utoipa"))]
+impl<S: Send + Sync + 'static> Router<S> {
+    /// 在 path 上提供 [`auto_doc`](crate::openapi::auto_doc) 生成的 OpenAPI JSON
+    #[track_caller]
+    pub fn serve_openapi(&mut self, path: &str) -> &mut Self {
+        self.serve_openapi_with(path, crate::openapi::auto_doc())
+    }
+
+    /// 在 path 上提供名为 `doc` 的文档（见 `#[u_doc]`）
+    #[track_caller]
+    pub fn serve_openapi_for(&mut self, path: &str, doc: &str) -> &mut Self {
+        self.serve_openapi_with(path, crate::openapi::auto_doc_for(doc))
+    }
+
+    /// 在 path 上提供指定的 OpenAPI JSON
+    #[track_caller]
+    pub fn serve_openapi_with(&mut self, path: &str, doc: utoipa::openapi::OpenApi) -> &mut Self {
+        let doc = serde_json::to_value(&doc).unwrap_or_default();
+        self.get(path, move || {
+            let doc = doc.clone();
+            async move { doc }
+        })
+    }
+
+    /// 在 path 上提供内嵌自动文档的 Scalar 页面
+    #[track_caller]
+    pub fn serve_scalar(&mut self, path: &str) -> &mut Self {
+        self.serve_scalar_with(path, crate::openapi::auto_doc())
+    }
+
+    /// 在 path 上提供内嵌名为 `doc` 的文档的 Scalar 页面
+    #[track_caller]
+    pub fn serve_scalar_for(&mut self, path: &str, doc: &str) -> &mut Self {
+        self.serve_scalar_with(path, crate::openapi::auto_doc_for(doc))
+    }
+
+    /// 在 path 上提供内嵌指定文档的 Scalar 页面
+    #[track_caller]
+    pub fn serve_scalar_with(&mut self, path: &str, doc: utoipa::openapi::OpenApi) -> &mut Self {
+        let html = crate::openapi::scalar_html(&doc);
+        self.get(path, move || {
+            let html = crate::http::response::into_response::Html(html.clone());
+            async move { html }
+        })
+    }
+
+    /// 在 path 上提供由自动文档生成的 TypeScript 客户端（见 [`codegen`](crate::codegen)）
+    #[track_caller]
+    pub fn serve_typescript_client(&mut self, path: &str) -> &mut Self {
+        let source = crate::codegen::ClientGenerator::new(&crate::openapi::auto_doc()).typescript();
+        self.get(path, move || {
+            let mut headers = hyper::HeaderMap::new();
+            headers.insert(
+                hyper::header::CONTENT_TYPE,
+                hyper::header::HeaderValue::from_static("text/plain; charset=utf-8"),
+            );
+            let source = source.clone();
+            async move { (headers, source) }
+        })
+    }
 }
 
 #[cfg(feature = "ext")]
 impl<S: Send + Sync + 'static> Router<S> {
     /// 简易的静态文件服务
+    #[track_caller]
     pub fn static_svc<F>(
         &mut self,
         prefix: &str,
@@ -428,6 +854,42 @@ impl<S: Send + Sync + 'static> Router<S> {
         self.nest_service(prefix, builder.build())
     }
 
+    /// 在 path 上挂载全局 [`Realtime`](crate::ext::realtime::Realtime) 的订阅端点（SSE / WebSocket）
+    #[track_caller]
+    pub fn realtime(&mut self, path: &str) -> &mut Self {
+        self.realtime_with(path, crate::ext::realtime::Realtime::global().clone())
+    }
+
+    /// 在 path 上挂载指定 Realtime 实例的订阅端点
+    #[track_caller]
+    pub fn realtime_with(&mut self, path: &str, hub: crate::ext::realtime::Realtime) -> &mut Self {
+        self.get(path, move |req: Req| {
+            let hub = hub.clone();
+            async move { hub.handle(req).await }
+        })
+    }
+
+    /// 挂载存活与就绪探针，见 [`health`](crate::ext::health)；已注册 GET 的路径保持不变
+    pub fn health_probes(&mut self, settings: &crate::app::config::HealthSettings) -> &mut Self {
+        use crate::ext::health::{HealthRegistry, liveness_handler};
+        let registered = |router: &Self, path: &str| {
+            router
+                .path_map
+                .get(&Method::GET)
+                .is_some_and(|paths| paths.contains_key(path))
+        };
+        if !registered(self, &settings.liveness_path) {
+            self.get(&settings.liveness_path, liveness_handler);
+        }
+        if !registered(self, &settings.readiness_path) {
+            let timeout = std::time::Duration::from_millis(settings.timeout_ms);
+            self.get(&settings.readiness_path, move || async move {
+                HealthRegistry::global().check_within(timeout).await
+            });
+        }
+        self
+    }
+
     /// 允许任意跨域（permissive），适合开发或简单场景
     pub fn cors_any(&mut self) {
         use tower_http::cors::CorsLayer;
@@ -0,0 +1,104 @@
+//! 按配置覆盖单个路由的中间件（`[server.routes."/path"]`）
+
+use super::{HttpSvc, Router};
+use crate::app::config::RouteOverride;
+use crate::extractor::LenientQuery;
+use crate::extractor::body_limit::{BodyLimit, parse_size};
+use crate::handler::Req;
+use crate::middleware::extension::ExtensionLayer;
+use crate::middleware::timeout::TimeoutLayer;
+use matchit::Router as MRouter;
+use miko_core::encode_route;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tower::Layer;
+use tower::util::BoxCloneService;
+
+impl<S: Send + Sync + 'static> Router<S> {
+    /// 按配置为匹配的路由包裹超时、请求体限制、宽松查询解析、限流与认证中间件
+    ///
+    /// 键须与注册时的路由模式一致（如 `/users/{id}`），未注册的路径与无效的配置值会被跳过并记录警告。
+    /// [`Application`](crate::app::Application) 启动时会自动应用 `server.routes`。
+    pub fn route_overrides(&mut self, routes: &BTreeMap<String, RouteOverride>) -> &mut Self {
+        for (path, route) in routes {
+            if !self.wrap_route(path, |svc| apply_override(path, route, svc)) {
+                tracing::warn!(path, "route override for unregistered path, ignored");
+            }
+        }
+        self
+    }
+
+    /// 用 `f` 包裹 `path` 下所有方法的 Service，返回路径是否存在
+    fn wrap_route(&mut self, path: &str, f: impl Fn(HttpSvc<Req>) -> HttpSvc<Req>) -> bool {
+        let mut found = false;
+        for (method, paths) in self.path_map.iter_mut() {
+            let Some(svc) = paths.get_mut(path) else {
+                continue;
+            };
+            found = true;
+            *svc = f(svc.clone());
+            // matchit 不支持替换，按索引重建该方法的路由表
+            let mut routes = MRouter::new();
+            for (p, svc) in paths.iter() {
+                routes
+                    .insert(encode_route(p), svc.clone())
+                    .expect("routes were already registered without conflicts");
+            }
+            self.routes.insert(method.clone(), routes);
+        }
+        found
+    }
+}
+
+fn apply_override(path: &str, route: &RouteOverride, mut svc: HttpSvc<Req>) -> HttpSvc<Req> {
+    if let Some(ms) = route.timeout_ms {
+        svc = BoxCloneService::new(TimeoutLayer::new(Duration::from_millis(ms)).layer(svc));
+    }
+    if let Some(limit) = route.body_limit.as_deref() {
+        match parse_size(limit) {
+            Some(limit) => {
+                svc = BoxCloneService::new(ExtensionLayer::new(BodyLimit(limit)).layer(svc))
+            }
+            None => tracing::warn!(path, limit, "invalid route body_limit, ignored"),
+        }
+    }
+    if route.lenient_query {
+        svc = BoxCloneService::new(ExtensionLayer::new(LenientQuery).layer(svc));
+    }
+    #[cfg(feature = "ext")]
+    {
+        use crate::ext::auth::GuardLayer;
+        use crate::ext::rate_limit::RateLimitLayer;
+        if let Some(role) = &route.role {
+            svc = BoxCloneService::new(GuardLayer::role(role.clone()).layer(svc));
+        } else if route.auth {
+            svc = BoxCloneService::new(GuardLayer::authenticated().layer(svc));
+        }
+        if let Some(rate) = &route.rate_limit {
+            if rate.limit == 0 || rate.period_secs == 0 || rate.burst == Some(0) {
+                tracing::warn!(path, "invalid route rate_limit, ignored");
+            } else {
+                let mut layer =
+                    RateLimitLayer::new(rate.limit, Duration::from_secs(rate.period_secs));
+                if let Some(burst) = rate.burst {
+                    layer = layer.burst(burst);
+                }
+                match rate.key.as_deref() {
+                    None | Some("ip") => {}
+                    Some("global") => layer = layer.global(),
+                    Some(key) => match key.strip_prefix("header:") {
+                        Some(name) => {
+                            let name = name.trim().to_string();
+                            layer = layer.key(move |req| {
+                                Some(req.headers().get(&name)?.to_str().ok()?.to_string())
+                            });
+                        }
+                        None => tracing::warn!(path, key, "invalid route rate_limit key, using ip"),
+                    },
+                }
+                svc = BoxCloneService::new(layer.layer(svc));
+            }
+        }
+    }
+    svc
+}
@@ -0,0 +1,70 @@
+use crate::AppError;
+use crate::extractor::path_params::PathParams;
+use crate::handler::{Req, Resp};
+use http_body_util::{BodyExt, Empty};
+use hyper::header::LOCATION;
+use hyper::{Response, StatusCode};
+use std::future::{Ready, ready};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::Service;
+
+/// 重定向 Service，目标中的 `{name}` 会替换为同名路径参数
+///
+/// 目标不含查询字符串时保留原请求的查询字符串。
+#[derive(Clone)]
+pub struct RedirectSvc {
+    to: Arc<str>,
+    status: StatusCode,
+}
+
+impl RedirectSvc {
+    /// 创建重定向，`status` 必须是 301/302/303/307/308 之一
+    pub fn new(to: &str, status: u16) -> Result<Self, String> {
+        let status = StatusCode::from_u16(status)
+            .ok()
+            .filter(|s| matches!(s.as_u16(), 301 | 302 | 303 | 307 | 308))
+            .ok_or_else(|| format!("invalid redirect status {}", status))?;
+        Ok(Self {
+            to: Arc::from(to),
+            status,
+        })
+    }
+
+    fn location(&self, req: &Req) -> String {
+        let mut location = self.to.to_string();
+        if let Some(params) = req.extensions().get::<PathParams>() {
+            for (name, value) in &params.0 {
+                location = location
+                    .replace(&format!("{{*{}}}", name), value)
+                    .replace(&format!("{{{}}}", name), value);
+            }
+        }
+        if let Some(query) = req.uri().query()
+            && !location.contains('?')
+        {
+            location.push('?');
+            location.push_str(query);
+        }
+        location
+    }
+}
+
+impl Service<Req> for RedirectSvc {
+    type Response = Resp;
+    type Error = AppError;
+    type Future = Ready<Result<Resp, AppError>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let resp = Response::builder()
+            .status(self.status)
+            .header(LOCATION, self.location(&req))
+            .body(Empty::new().map_err(Into::into).boxed_unsync())
+            .map_err(|e| AppError::InternalServerError(e.to_string()));
+        ready(resp)
+    }
+}
@@ -1,7 +1,16 @@
 use crate::error::app_error::TRACE_ID;
 use crate::handler::{Req, Resp};
-use crate::router::Router;
+use crate::middleware::request_id::RequestId;
+use crate::router::method_set::ANY_METHOD;
+use crate::router::trailing_slash::{self, TrailingSlash};
+use crate::router::{MatchedPath, Router};
 use crate::{AppError, IntoResponse};
+use hyper::Method;
+use hyper::header::ALLOW;
+use matchit::Router as MRouter;
+use miko_core::encode_route;
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::{
     future::Future,
     pin::Pin,
@@ -11,15 +20,43 @@ use tower::Service;
 
 pub struct RouterSvc<S> {
     pub router: Router<S>,
+    /// 与 `router.routes` 结构相同，值为路由模式，用于写入 [`MatchedPath`]
+    patterns: Arc<HashMap<Method, MRouter<MatchedPath>>>,
 }
 impl<S> Clone for RouterSvc<S> {
     fn clone(&self) -> Self {
         Self {
             router: self.router.clone(),
+            patterns: self.patterns.clone(),
         }
     }
 }
 
+impl<S> RouterSvc<S> {
+    pub fn new(router: Router<S>) -> Self {
+        let mut patterns = HashMap::new();
+        for (method, paths) in &router.path_map {
+            let mut table = MRouter::new();
+            for path in paths.keys() {
+                let _ = table.insert(encode_route(path), MatchedPath(Arc::from(path.as_str())));
+            }
+            patterns.insert(method.clone(), table);
+        }
+        Self {
+            router,
+            patterns: Arc::new(patterns),
+        }
+    }
+
+    fn matched_path(&self, method: &Method, path: &str) -> Option<MatchedPath> {
+        [method, &*ANY_METHOD]
+            .into_iter()
+            .filter_map(|m| self.patterns.get(m))
+            .find_map(|table| table.at(path).ok())
+            .map(|matched| matched.value.clone())
+    }
+}
+
 impl<S: Send + Sync + 'static> Service<Req> for RouterSvc<S> {
     type Response = Resp;
     type Error = AppError;
@@ -31,8 +68,24 @@ impl<S: Send + Sync + 'static> Service<Req> for RouterSvc<S> {
 
     fn call(&mut self, mut req: Req) -> Self::Future {
         let method = req.method().clone();
-        let path = req.uri().path().to_string();
-        let result = self.router.find_handler(&method, &path);
+        let mut path = req.uri().path().to_string();
+        let mut result = self.router.find_handler(&method, &path);
+        let mut redirect_to = None;
+        if result.is_none()
+            && let Some(alternative) = self.router.trailing_slash_alternative(&method, &path)
+        {
+            if self.router.trailing_slash == TrailingSlash::Redirect {
+                redirect_to = Some(alternative);
+            } else {
+                trailing_slash::replace_path(&mut req, &alternative);
+                path = alternative;
+                result = self.router.find_handler(&method, &path);
+            }
+        }
+        let (matched_path, unmatched) = match result {
+            Some(_) => (self.matched_path(&method, &path), None),
+            None => (None, self.router.find_unmatched(&path)),
+        };
 
         // 自动设置 trace_id
         // 优先从请求头获取,如果没有则生成新的
@@ -48,12 +101,24 @@ impl<S: Send + Sync + 'static> Service<Req> for RouterSvc<S> {
                 trace_id = %trace_id,
                 "Request started"
             );
-            let resp_result = match result {
-                Some((mut handler, params)) => {
+            let resp_result = match (redirect_to, result) {
+                (Some(location), _) => trailing_slash::redirect(&location, req.uri().query()),
+                (None, Some((mut handler, params))) => {
                     req.extensions_mut().insert(params);
+                    if let Some(matched_path) = matched_path.clone() {
+                        req.extensions_mut().insert(matched_path);
+                    }
                     handler.call(req).await
                 }
-                None => Ok(AppError::NotFound("404 Not Found".to_string()).into_response()),
+                (None, None) => match unmatched {
+                    Some((mut svc, allow)) => svc.call(req).await.map(|mut resp| {
+                        if let Some(allow) = allow {
+                            resp.headers_mut().entry(ALLOW).or_insert(allow);
+                        }
+                        resp
+                    }),
+                    None => Ok(AppError::NotFound("404 Not Found".to_string()).into_response()),
+                },
             };
             // 记录请求完成
             let elapsed = start.elapsed();
@@ -80,7 +145,11 @@ impl<S: Send + Sync + 'static> Service<Req> for RouterSvc<S> {
                     );
                 }
             }
-            Ok(resp_result.unwrap_or_else(|e| e.into_response()))
+            let mut resp = resp_result.unwrap_or_else(|e| e.into_response());
+            if let Some(matched_path) = matched_path {
+                resp.extensions_mut().insert(matched_path);
+            }
+            Ok(resp)
         };
         Box::pin(TRACE_ID.scope(trace_id_clone, task_future))
     }
@@ -89,33 +158,18 @@ impl<S: Send + Sync + 'static> Service<Req> for RouterSvc<S> {
 /// 从请求中提取或生成 trace_id
 ///
 /// 按优先级尝试:
-/// 1. 从 `x-trace-id` 请求头获取
-/// 2. 从 `x-request-id` 请求头获取
-/// 3. 生成基于时间戳的 trace_id
+/// 1. 使用 `RequestIdLayer` 分配的 id
+/// 2. 从 `x-trace-id` 请求头获取
+/// 3. 从 `x-request-id` 请求头获取
+/// 4. 生成基于时间戳的 trace_id
 fn extract_or_generate_trace_id(req: &Req) -> String {
+    if let Some(id) = req.extensions().get::<RequestId>() {
+        return id.0.clone();
+    }
     req.headers()
         .get("x-trace-id")
         .or_else(|| req.headers().get("x-request-id"))
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string())
-        .unwrap_or_else(generate_trace_id)
-}
-
-/// 生成 trace_id
-///
-/// 格式: `trace-{timestamp_micros}-{random}`
-fn generate_trace_id() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_micros();
-
-    // 使用线程ID和时间戳组合,避免冲突
-    let thread_id = std::thread::current().id();
-    format!("trace-{:x}-{:?}", timestamp, thread_id)
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '-')
-        .collect()
+        .unwrap_or_else(crate::id::new_id)
 }
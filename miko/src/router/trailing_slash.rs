@@ -0,0 +1,83 @@
+//! 末尾斜杠处理
+//!
+//! 默认 `/users/` 与 `/users` 是两条不同的路由，只注册其一时另一种写法返回 404。
+//! 通过 [`Router::trailing_slash`] 让两种写法得到一致的处理：
+//!
+//! ```rust,ignore
+//! router.get("/users", list_users);
+//! router.trailing_slash(TrailingSlash::Redirect); // GET /users/ -> 308 Location: /users
+//! ```
+
+use super::Router;
+use crate::AppError;
+use crate::handler::{Req, Resp};
+use http_body_util::{BodyExt, Empty};
+use hyper::header::LOCATION;
+use hyper::{Method, Response, StatusCode, Uri};
+
+/// 请求路径只差末尾斜杠时的处理方式
+///
+/// 只在请求路径本身没有命中任何路由（包括其他方法）时生效，根路径 `/` 不受影响
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingSlash {
+    /// 308 重定向到已注册的写法，保留查询字符串与请求方法
+    Redirect,
+    /// 去掉（或补上）末尾斜杠后直接匹配已注册的写法，不重定向
+    Strip,
+    /// 严格区分，只差末尾斜杠的请求按未匹配处理
+    #[default]
+    Strict,
+}
+
+impl<S: Send + Sync + 'static> Router<S> {
+    /// 设置末尾斜杠的处理方式，默认 [`TrailingSlash::Strict`]，只在最外层 Router 上生效
+    pub fn trailing_slash(&mut self, policy: TrailingSlash) -> &mut Self {
+        self.trailing_slash = policy;
+        self
+    }
+
+    /// 请求路径未命中、而切换末尾斜杠后能命中时，返回切换后的路径
+    pub(crate) fn trailing_slash_alternative(&self, method: &Method, path: &str) -> Option<String> {
+        if self.trailing_slash == TrailingSlash::Strict
+            || path == "/"
+            || !self.allowed_methods(path).is_empty()
+        {
+            return None;
+        }
+        let alternative = match path.strip_suffix('/') {
+            Some(stripped) => stripped.to_string(),
+            None => format!("{}/", path),
+        };
+        let routed = self.find_handler(method, &alternative).is_some()
+            || !self.allowed_methods(&alternative).is_empty();
+        routed.then_some(alternative)
+    }
+}
+
+/// 替换请求的路径，保留查询字符串
+pub(crate) fn replace_path(req: &mut Req, path: &str) {
+    let mut parts = req.uri().clone().into_parts();
+    let path_and_query = match req.uri().query() {
+        Some(query) => format!("{}?{}", path, query),
+        None => path.to_string(),
+    };
+    if let Ok(path_and_query) = path_and_query.parse() {
+        parts.path_and_query = Some(path_and_query);
+        if let Ok(uri) = Uri::from_parts(parts) {
+            *req.uri_mut() = uri;
+        }
+    }
+}
+
+/// 重定向到切换末尾斜杠后的路径
+pub(crate) fn redirect(path: &str, query: Option<&str>) -> Result<Resp, AppError> {
+    let location = match query {
+        Some(query) => format!("{}?{}", path, query),
+        None => path.to_string(),
+    };
+    Response::builder()
+        .status(StatusCode::PERMANENT_REDIRECT)
+        .header(LOCATION, location)
+        .body(Empty::new().map_err(Into::into).boxed_unsync())
+        .map_err(|e| AppError::InternalServerError(e.to_string()))
+}
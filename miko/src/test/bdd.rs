@@ -0,0 +1,380 @@
+//! BDD 风格的测试 DSL
+//!
+//! 在 [`TestClient`] 之上以 given / when / then 组织请求与断言：
+//!
+//! ```rust,ignore
+//! client
+//!     .given()
+//!     .header("authorization", "Bearer token")
+//!     .query("page", 1)
+//!     .when()
+//!     .get("/items")
+//!     .then()
+//!     .status(200)
+//!     .json_path("$.items[0].id", 1)
+//!     .json_path("$.items[*].name", ["a", "b"])
+//!     .matches_openapi(&miko::openapi::auto_doc())
+//!     .await;
+//! ```
+//!
+//! `then()` 之后的断言在 `.await` 时发送请求并统一检查，任一不符时 panic 并列出所有失败的断言与响应体；
+//! `.await` 返回 [`TestResponse`] 以便继续检查。JSONPath 语法见 [`json_path`](crate::test::json_path)。
+
+use crate::test::json_path;
+use crate::test::test_client::TestClient;
+use crate::test::test_response::TestResponse;
+use hyper::header::CONTENT_TYPE;
+use hyper::http::{HeaderName, HeaderValue};
+use hyper::{Method, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::{Debug, Display};
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+
+impl TestClient {
+    /// 以 given / when / then 描述一次请求
+    pub fn given(&self) -> Given {
+        Given {
+            client: self.clone(),
+            headers: Vec::new(),
+            cookies: Vec::new(),
+            query: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// 不需要前置条件时直接进入 when
+    pub fn when(&self) -> When {
+        self.given().when()
+    }
+}
+
+/// 请求的前置条件：请求头、cookie、查询参数与请求体
+pub struct Given {
+    client: TestClient,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    cookies: Vec<(String, String)>,
+    query: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+}
+
+impl Given {
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName>,
+        V: TryInto<HeaderValue>,
+        K::Error: Debug,
+        V::Error: Debug,
+    {
+        self.headers.push((
+            key.try_into().expect("Invalid header name"),
+            value.try_into().expect("Invalid header value"),
+        ));
+        self
+    }
+
+    /// 仅为本次请求附加 cookie
+    pub fn cookie(mut self, name: &str, value: &str) -> Self {
+        self.cookies.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// 追加查询参数，与路径中已有的查询字符串合并
+    pub fn query(mut self, name: &str, value: impl Display) -> Self {
+        self.query.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn json<T: Serialize>(self, json: &T) -> Self {
+        self.header(CONTENT_TYPE, "application/json")
+            .bytes(serde_json::to_vec(json).expect("Failed to serialize JSON"))
+    }
+
+    pub fn form<T: Serialize>(self, form: &T) -> Self {
+        self.header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .bytes(serde_urlencoded::to_string(form).expect("Failed to serialize form"))
+    }
+
+    pub fn text(self, text: &str) -> Self {
+        self.bytes(text)
+    }
+
+    pub fn bytes(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    pub fn when(self) -> When {
+        When { given: self }
+    }
+}
+
+macro_rules! define_when_method {
+    ($($name:ident => $method:ident),* $(,)?) => {
+        $(
+            pub fn $name(self, uri: &str) -> Action {
+                self.request(Method::$method, uri)
+            }
+        )*
+    };
+}
+
+/// 发出的请求
+pub struct When {
+    given: Given,
+}
+
+impl When {
+    define_when_method! {
+        get => GET,
+        post => POST,
+        put => PUT,
+        delete => DELETE,
+        patch => PATCH,
+        head => HEAD,
+        options => OPTIONS,
+    }
+
+    /// 以任意方法请求 `uri`
+    pub fn request(self, method: Method, uri: &str) -> Action {
+        Action {
+            given: self.given,
+            method,
+            uri: uri.to_string(),
+        }
+    }
+}
+
+/// 待发送的请求，通过 [`then`](Self::then) 开始描述预期
+pub struct Action {
+    given: Given,
+    method: Method,
+    uri: String,
+}
+
+impl Action {
+    pub fn then(self) -> Then {
+        Then {
+            action: self,
+            expectations: Vec::new(),
+        }
+    }
+}
+
+enum Expectation {
+    Status(StatusCode),
+    Header(String, String),
+    Text(String),
+    Json(Value),
+    JsonPath(String, Value),
+    JsonPathExists(String),
+    #[cfg(feature = "utoipa")]
+    OpenApi(Box<utoipa::openapi::OpenApi>),
+}
+
+/// 对响应的预期，`.await` 时发送请求并检查
+#[must_use = "expectations are only checked when awaited"]
+pub struct Then {
+    action: Action,
+    expectations: Vec<Expectation>,
+}
+
+impl Then {
+    /// 响应状态码，可传 `200` 或 `StatusCode::OK`
+    pub fn status<S>(mut self, status: S) -> Self
+    where
+        S: TryInto<StatusCode>,
+        S::Error: Debug,
+    {
+        let status = status.try_into().expect("Invalid status code");
+        self.expectations.push(Expectation::Status(status));
+        self
+    }
+
+    /// 响应头的值
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        self.expectations
+            .push(Expectation::Header(key.to_string(), value.to_string()));
+        self
+    }
+
+    /// 完整的响应文本
+    pub fn text(mut self, text: &str) -> Self {
+        self.expectations.push(Expectation::Text(text.to_string()));
+        self
+    }
+
+    /// 完整的 JSON 响应体
+    pub fn json<T: Serialize>(mut self, expected: T) -> Self {
+        self.expectations
+            .push(Expectation::Json(to_value(expected)));
+        self
+    }
+
+    /// JSONPath 选中的值；含 `*` 或 `..` 的路径与所有命中节点组成的数组比较
+    pub fn json_path<T: Serialize>(mut self, path: &str, expected: T) -> Self {
+        self.expectations
+            .push(Expectation::JsonPath(path.to_string(), to_value(expected)));
+        self
+    }
+
+    /// JSONPath 至少命中一个节点
+    pub fn json_path_exists(mut self, path: &str) -> Self {
+        self.expectations
+            .push(Expectation::JsonPathExists(path.to_string()));
+        self
+    }
+
+    /// 响应体符合 OpenAPI 文档中该路由、该状态码的响应 schema
+    #[cfg(feature = "utoipa")]
+    pub fn matches_openapi(mut self, doc: &utoipa::openapi::OpenApi) -> Self {
+        self.expectations
+            .push(Expectation::OpenApi(Box::new(doc.clone())));
+        self
+    }
+
+    async fn run(self) -> TestResponse {
+        let Action { given, method, uri } = self.action;
+        let uri = with_query(&uri, &given.query);
+        let mut builder = given.client.build(method.clone(), &uri);
+        for (key, value) in given.headers {
+            builder = builder.header(key, value);
+        }
+        for (name, value) in &given.cookies {
+            builder = builder.cookie(name, value);
+        }
+        if let Some(body) = given.body {
+            builder = builder.bytes(body);
+        }
+        #[cfg_attr(not(feature = "utoipa"), allow(unused_variables))]
+        let (resp, extensions) = builder.send_with_extensions().await;
+
+        let body: Result<Value, String> =
+            serde_json::from_slice(&resp.body).map_err(|e| format!("body is not JSON: {}", e));
+        let mut failures = Vec::new();
+        for expectation in self.expectations {
+            match expectation {
+                Expectation::Status(status) => {
+                    if resp.status != status {
+                        failures.push(format!("expected status {}, got {}", status, resp.status));
+                    }
+                }
+                Expectation::Header(key, value) => {
+                    match resp
+                        .headers
+                        .get(&key)
+                        .map(|v| v.to_str().unwrap_or("<binary>"))
+                    {
+                        Some(actual) if actual == value => {}
+                        Some(actual) => failures.push(format!(
+                            "expected header {} to be {:?}, got {:?}",
+                            key, value, actual
+                        )),
+                        None => failures.push(format!("header {} not found", key)),
+                    }
+                }
+                Expectation::Text(text) => {
+                    if resp.text() != text {
+                        failures.push(format!("expected text {:?}", text));
+                    }
+                }
+                Expectation::Json(expected) => match &body {
+                    Ok(actual) if *actual == expected => {}
+                    Ok(_) => failures.push(format!("expected JSON {}", expected)),
+                    Err(e) => failures.push(e.clone()),
+                },
+                Expectation::JsonPath(path, expected) => {
+                    match body
+                        .as_ref()
+                        .map_err(Clone::clone)
+                        .and_then(|b| json_path::query(b, &path))
+                    {
+                        Ok(Some(actual)) if actual == expected => {}
+                        Ok(Some(actual)) => failures.push(format!(
+                            "expected {} to be {}, got {}",
+                            path, expected, actual
+                        )),
+                        Ok(None) => failures.push(format!("{} not found", path)),
+                        Err(e) => failures.push(e),
+                    }
+                }
+                Expectation::JsonPathExists(path) => {
+                    match body
+                        .as_ref()
+                        .map_err(Clone::clone)
+                        .and_then(|b| json_path::select(b, &path))
+                    {
+                        Ok(nodes) if !nodes.is_empty() => {}
+                        Ok(_) => failures.push(format!("{} not found", path)),
+                        Err(e) => failures.push(e),
+                    }
+                }
+                #[cfg(feature = "utoipa")]
+                Expectation::OpenApi(doc) => {
+                    failures.extend(check_openapi(&doc, &method, &extensions, &resp, &body))
+                }
+            }
+        }
+        if !failures.is_empty() {
+            panic!(
+                "{} {} failed {} expectation(s):\n  - {}\nstatus: {}\nbody: {}",
+                method,
+                uri,
+                failures.len(),
+                failures.join("\n  - "),
+                resp.status,
+                resp.text()
+            );
+        }
+        resp
+    }
+}
+
+impl IntoFuture for Then {
+    type Output = TestResponse;
+    type IntoFuture = Pin<Box<dyn Future<Output = TestResponse> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.run())
+    }
+}
+
+fn to_value<T: Serialize>(value: T) -> Value {
+    serde_json::to_value(value).expect("Failed to serialize expected value")
+}
+
+fn with_query(uri: &str, query: &[(String, String)]) -> String {
+    if query.is_empty() {
+        return uri.to_string();
+    }
+    let encoded = serde_urlencoded::to_string(query).expect("Failed to encode query");
+    let sep = if uri.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", uri, sep, encoded)
+}
+
+#[cfg(feature = "utoipa")]
+fn check_openapi(
+    doc: &utoipa::openapi::OpenApi,
+    method: &Method,
+    extensions: &hyper::http::Extensions,
+    resp: &TestResponse,
+    body: &Result<Value, String>,
+) -> Vec<String> {
+    use crate::test::openapi::{response_schema, validate_json};
+    let Some(route) = extensions.get::<crate::router::MatchedPath>() else {
+        return vec!["request did not match any route".to_string()];
+    };
+    let Some(schema) = response_schema(doc, method, route.as_str(), resp.status.as_u16()) else {
+        return vec![format!(
+            "no response schema for {} {} {} in the OpenAPI document",
+            method,
+            route.as_str(),
+            resp.status.as_u16()
+        )];
+    };
+    match body {
+        Ok(body) => validate_json(doc, &schema, body),
+        Err(e) => vec![e.clone()],
+    }
+}
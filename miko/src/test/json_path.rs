@@ -0,0 +1,151 @@
+//! 测试断言使用的 JSONPath 子集
+//!
+//! 支持 `$`、`.name`、`['name']`、`[0]`、`[-1]`（倒数）、`.*` / `[*]` 与递归下降 `..name`：
+//!
+//! ```rust,ignore
+//! let ids = select(&body, "$.items[*].id").unwrap();
+//! ```
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Key(String),
+    Index(i64),
+    Wildcard,
+    /// 当前节点及其所有后代
+    Descendants,
+}
+
+/// 解析后的路径
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    steps: Vec<Step>,
+}
+
+impl JsonPath {
+    /// 解析路径，语法错误时返回说明
+    pub fn parse(path: &str) -> Result<Self, String> {
+        let err = |msg: &str| format!("invalid JSONPath {:?}: {}", path, msg);
+        let rest = path
+            .strip_prefix('$')
+            .ok_or_else(|| err("must start with `$`"))?;
+        let chars: Vec<char> = rest.chars().collect();
+        let mut steps = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '.' => {
+                    i += 1;
+                    if chars.get(i) == Some(&'.') {
+                        steps.push(Step::Descendants);
+                        i += 1;
+                        if chars.get(i) == Some(&'[') {
+                            continue;
+                        }
+                    }
+                    if chars.get(i) == Some(&'*') {
+                        steps.push(Step::Wildcard);
+                        i += 1;
+                        continue;
+                    }
+                    let start = i;
+                    while i < chars.len() && !matches!(chars[i], '.' | '[') {
+                        i += 1;
+                    }
+                    if start == i {
+                        return Err(err("empty member name"));
+                    }
+                    steps.push(Step::Key(chars[start..i].iter().collect()));
+                }
+                '[' => {
+                    let end = chars[i..]
+                        .iter()
+                        .position(|c| *c == ']')
+                        .map(|p| p + i)
+                        .ok_or_else(|| err("unclosed `[`"))?;
+                    let inner: String = chars[i + 1..end].iter().collect();
+                    let inner = inner.trim();
+                    let step = if inner == "*" {
+                        Step::Wildcard
+                    } else if let Some(key) = inner
+                        .strip_prefix('\'')
+                        .and_then(|s| s.strip_suffix('\''))
+                        .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')))
+                    {
+                        Step::Key(key.to_string())
+                    } else {
+                        Step::Index(inner.parse().map_err(|_| err("invalid index"))?)
+                    };
+                    steps.push(step);
+                    i = end + 1;
+                }
+                _ => return Err(err("expected `.` or `[`")),
+            }
+        }
+        Ok(Self { steps })
+    }
+
+    /// 路径是否只会选中单个节点（不含通配与递归下降）
+    pub fn is_definite(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|s| matches!(s, Step::Key(_) | Step::Index(_)))
+    }
+
+    /// 选中的所有节点，按文档顺序
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut nodes = vec![value];
+        for step in &self.steps {
+            let mut next = Vec::new();
+            for node in nodes {
+                match step {
+                    Step::Key(key) => next.extend(node.get(key)),
+                    Step::Index(index) => {
+                        if let Value::Array(items) = node {
+                            let index = if *index < 0 {
+                                items.len() as i64 + index
+                            } else {
+                                *index
+                            };
+                            next.extend(usize::try_from(index).ok().and_then(|i| items.get(i)));
+                        }
+                    }
+                    Step::Wildcard => match node {
+                        Value::Array(items) => next.extend(items),
+                        Value::Object(map) => next.extend(map.values()),
+                        _ => {}
+                    },
+                    Step::Descendants => descendants(node, &mut next),
+                }
+            }
+            nodes = next;
+        }
+        nodes
+    }
+}
+
+fn descendants<'a>(node: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(node);
+    match node {
+        Value::Array(items) => items.iter().for_each(|v| descendants(v, out)),
+        Value::Object(map) => map.values().for_each(|v| descendants(v, out)),
+        _ => {}
+    }
+}
+
+/// 解析 `path` 并选中 `value` 中的节点
+pub fn select<'a>(value: &'a Value, path: &str) -> Result<Vec<&'a Value>, String> {
+    Ok(JsonPath::parse(path)?.select(value))
+}
+
+/// 按路径取值：单节点路径返回该节点，未命中时为 `None`；其余路径将所有命中节点收集为数组
+pub fn query(value: &Value, path: &str) -> Result<Option<Value>, String> {
+    let path = JsonPath::parse(path)?;
+    let nodes = path.select(value);
+    Ok(if path.is_definite() {
+        nodes.first().map(|v| (*v).clone())
+    } else {
+        Some(Value::Array(nodes.into_iter().cloned().collect()))
+    })
+}
@@ -0,0 +1,157 @@
+//! 路由清单与差异比较
+//!
+//! [`Router::export_manifest`] 导出路由的方法、路径与中间件列表（可选附带 OpenAPI 操作的哈希），
+//! 序列化为 JSON 后保存；CI 中与新构建的清单比较，即可发现被删除或修改的路由等破坏性变更。
+//!
+//! ```rust,ignore
+//! let current = build_router().export_manifest();
+//! let baseline = RouteManifest::from_json(&std::fs::read_to_string("routes.json")?)?;
+//! let diff = baseline.diff(&current);
+//! assert!(!diff.is_breaking(), "{}", diff);
+//! ```
+
+use crate::router::Router;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// 清单中的一条路由
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteEntry {
+    pub method: String,
+    pub path: String,
+    /// 对应 OpenAPI 操作的哈希，未附带文档时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub openapi_hash: Option<String>,
+}
+
+/// 路由清单，路由按路径、方法排序
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteManifest {
+    pub routes: Vec<RouteEntry>,
+    /// 中间件类型名，按追加顺序
+    #[serde(default)]
+    pub middleware: Vec<String>,
+}
+
+impl RouteManifest {
+    pub fn from_router<S>(router: &Router<S>) -> Self {
+        let mut routes: Vec<RouteEntry> = router
+            .path_map
+            .iter()
+            .flat_map(|(method, paths)| {
+                paths.keys().map(move |path| RouteEntry {
+                    method: method.to_string(),
+                    path: path.clone(),
+                    openapi_hash: None,
+                })
+            })
+            .collect();
+        routes.sort_by(|a, b| (&a.path, &a.method).cmp(&(&b.path, &b.method)));
+        Self {
+            routes,
+            middleware: router.layer_names.clone(),
+        }
+    }
+
+    /// 为每条路由附上 OpenAPI 中对应操作的哈希，文档变化也会被视为路由变化
+    #[cfg(feature = "utoipa")]
+    pub fn with_openapi(mut self, doc: &utoipa::openapi::OpenApi) -> Self {
+        let Ok(serde_json::Value::Object(paths)) = serde_json::to_value(&doc.paths.paths) else {
+            return self;
+        };
+        for route in &mut self.routes {
+            route.openapi_hash = paths
+                .get(&route.path)
+                .and_then(|item| item.get(route.method.to_ascii_lowercase()))
+                .map(|op| format!("{:016x}", fnv1a(op.to_string().as_bytes())));
+        }
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("route manifest is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// 比较 `self`（旧）与 `new`（新）之间的差异
+    pub fn diff(&self, new: &RouteManifest) -> ManifestDiff {
+        let index = |m: &RouteManifest| -> BTreeMap<(String, String), RouteEntry> {
+            m.routes
+                .iter()
+                .map(|r| ((r.path.clone(), r.method.clone()), r.clone()))
+                .collect()
+        };
+        let (old_routes, new_routes) = (index(self), index(new));
+        let mut diff = ManifestDiff {
+            middleware_changed: self.middleware != new.middleware,
+            ..Default::default()
+        };
+        for (key, old) in &old_routes {
+            match new_routes.get(key) {
+                None => diff.removed.push(old.clone()),
+                Some(new) if new != old => diff.changed.push((old.clone(), new.clone())),
+                Some(_) => {}
+            }
+        }
+        diff.added = new_routes
+            .into_iter()
+            .filter(|(key, _)| !old_routes.contains_key(key))
+            .map(|(_, route)| route)
+            .collect();
+        diff
+    }
+}
+
+/// 两份清单之间的差异
+#[derive(Debug, Clone, Default)]
+pub struct ManifestDiff {
+    pub added: Vec<RouteEntry>,
+    pub removed: Vec<RouteEntry>,
+    /// 方法与路径相同但 OpenAPI 哈希不同的路由（旧，新）
+    pub changed: Vec<(RouteEntry, RouteEntry)>,
+    pub middleware_changed: bool,
+}
+
+impl ManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && !self.middleware_changed
+    }
+
+    /// 是否包含破坏性变更（删除或修改了路由）
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || !self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for ManifestDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in &self.added {
+            writeln!(f, "+ {} {}", r.method, r.path)?;
+        }
+        for r in &self.removed {
+            writeln!(f, "- {} {}", r.method, r.path)?;
+        }
+        for (r, _) in &self.changed {
+            writeln!(f, "~ {} {}", r.method, r.path)?;
+        }
+        if self.middleware_changed {
+            writeln!(f, "~ middleware")?;
+        }
+        Ok(())
+    }
+}
+
+/// 稳定的 FNV-1a 哈希，保证不同构建、不同 Rust 版本之间结果一致
+#[cfg(feature = "utoipa")]
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
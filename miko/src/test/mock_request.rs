@@ -0,0 +1,183 @@
+//! 构造单独的请求，用于在不启动 Router 的情况下测试提取器
+//!
+//! ```rust,ignore
+//! use miko::test::request;
+//!
+//! let Json(user) = request()
+//!     .header("authorization", "Bearer abc")
+//!     .path_param("id", "7")
+//!     .json(&body)
+//!     .extract::<Json<User>, _>()
+//!     .await?;
+//! ```
+
+use crate::error::AppError;
+use crate::extractor::from_request::{FromRequest, FromRequestParts};
+use crate::extractor::path_params::PathParams;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::Method;
+use hyper::http::Extensions;
+use hyper::http::request::Parts;
+use hyper::http::{HeaderName, HeaderValue, request};
+use miko_core::Req;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// 创建一个默认为 `GET /` 的 [`MockRequest`]
+pub fn request() -> MockRequest {
+    MockRequest::default()
+}
+
+/// 请求构建器，产出已填好路径参数与扩展的 [`Req`] 或 [`Parts`]
+pub struct MockRequest {
+    builder: request::Builder,
+    path: String,
+    query: Vec<(String, String)>,
+    params: Vec<(String, String)>,
+    extensions: Extensions,
+    body: Vec<u8>,
+}
+
+impl Default for MockRequest {
+    fn default() -> Self {
+        Self {
+            builder: request::Builder::new(),
+            path: "/".to_string(),
+            query: Vec::new(),
+            params: Vec::new(),
+            extensions: Extensions::new(),
+            body: Vec::new(),
+        }
+    }
+}
+
+impl MockRequest {
+    pub fn method(mut self, method: Method) -> Self {
+        self.builder = self.builder.method(method);
+        self
+    }
+
+    /// 设置请求路径，可带查询字符串
+    pub fn uri(mut self, uri: &str) -> Self {
+        match uri.split_once('?') {
+            Some((path, query)) => {
+                self.path = path.to_string();
+                self.query.extend(
+                    serde_urlencoded::from_str::<Vec<(String, String)>>(query)
+                        .expect("Invalid query string"),
+                );
+            }
+            None => self.path = uri.to_string(),
+        }
+        self
+    }
+
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName>,
+        V: TryInto<HeaderValue>,
+        K::Error: std::fmt::Debug,
+        V::Error: std::fmt::Debug,
+    {
+        self.builder = self.builder.header(
+            key.try_into().expect("Invalid header name"),
+            value.try_into().expect("Invalid header value"),
+        );
+        self
+    }
+
+    /// 追加一个查询参数
+    pub fn query_param(mut self, key: &str, value: impl ToString) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// 以可序列化的结构追加查询参数
+    pub fn query<T: Serialize>(mut self, query: &T) -> Self {
+        let encoded = serde_urlencoded::to_string(query).expect("Failed to serialize query");
+        self.query.extend(
+            serde_urlencoded::from_str::<Vec<(String, String)>>(&encoded)
+                .expect("Invalid query string"),
+        );
+        self
+    }
+
+    /// 追加一个路径参数，顺序即 `Path<T>` 的提取顺序
+    pub fn path_param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn extension<T: Clone + Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+
+    pub fn json<T: Serialize>(self, json: &T) -> Self {
+        self.header("content-type", "application/json")
+            .bytes(serde_json::to_vec(json).expect("Failed to serialize JSON"))
+    }
+
+    pub fn form<T: Serialize>(self, form: &T) -> Self {
+        self.header("content-type", "application/x-www-form-urlencoded")
+            .bytes(serde_urlencoded::to_string(form).expect("Failed to serialize form"))
+    }
+
+    pub fn text(self, text: &str) -> Self {
+        self.bytes(text)
+    }
+
+    pub fn bytes(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// 构建完整请求
+    pub fn build(self) -> Req {
+        let mut uri = self.path;
+        if !self.query.is_empty() {
+            uri.push('?');
+            uri.push_str(&serde_urlencoded::to_string(&self.query).expect("Invalid query"));
+        }
+        let body = Full::new(Bytes::from(self.body))
+            .map_err(Into::into)
+            .boxed_unsync();
+        let mut req = self
+            .builder
+            .uri(uri)
+            .body(body)
+            .expect("Failed to build request");
+        req.extensions_mut().extend(self.extensions);
+        req.extensions_mut().insert(PathParams(self.params));
+        req
+    }
+
+    /// 构建请求头部分，丢弃请求体
+    pub fn into_parts(self) -> Parts {
+        self.build().into_parts().0
+    }
+
+    /// 以 `()` 为状态运行提取器
+    pub async fn extract<T: FromRequest<(), M>, M>(self) -> Result<T, AppError> {
+        self.extract_with_state(()).await
+    }
+
+    /// 以指定状态运行提取器
+    pub async fn extract_with_state<S, T: FromRequest<S, M>, M>(
+        self,
+        state: S,
+    ) -> Result<T, AppError> {
+        T::from_request(self.build(), Arc::new(state)).await
+    }
+
+    /// 以指定状态运行 [`FromRequestParts`] 提取器，返回提取结果与提取后的 Parts
+    pub async fn extract_parts<S, T: FromRequestParts<S>>(
+        self,
+        state: S,
+    ) -> (Result<T, AppError>, Parts) {
+        let mut parts = self.into_parts();
+        let result = T::from_request_parts(&mut parts, Arc::new(state)).await;
+        (result, parts)
+    }
+}
@@ -1,2 +1,16 @@
+pub mod bdd;
+pub mod json_path;
+pub mod manifest;
+pub mod mock_request;
+#[cfg(feature = "utoipa")]
+pub mod openapi;
+pub mod snapshot;
 pub mod test_client;
 pub mod test_response;
+pub mod test_socket;
+pub mod test_sse;
+
+pub use mock_request::{MockRequest, request};
+pub use test_client::{MultipartForm, TestClient};
+pub use test_socket::TestWebSocket;
+pub use test_sse::TestSse;
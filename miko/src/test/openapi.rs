@@ -0,0 +1,377 @@
+//! OpenAPI 文档快照测试
+//!
+//! [`canonical_json`] 与 [`canonical_yaml`] 以排序后的键输出文档，结果与构建顺序、
+//! inventory 收集顺序无关；[`assert_openapi_snapshot`] 将其与提交在仓库中的快照比较，
+//! 不一致时打印逐行差异。设置环境变量 `MIKO_UPDATE_SNAPSHOTS=1` 可重写快照。
+//!
+//! ```rust,ignore
+//! #[test]
+//! fn openapi_is_stable() {
+//!     assert_openapi_snapshot(&miko::openapi::auto_doc(), "tests/snapshots/openapi.yaml");
+//! }
+//! ```
+//!
+//! [`response_schema`] 取出文档中某个操作的响应 schema，[`validate_json`] 按该 schema 校验响应体，
+//! 供 [`Then::matches_openapi`](crate::test::bdd::Then::matches_openapi) 使用。
+
+use serde_json::Value;
+use std::fmt::Write;
+use std::path::Path;
+
+/// 快照更新开关的环境变量名
+pub const UPDATE_ENV: &str = "MIKO_UPDATE_SNAPSHOTS";
+
+fn to_value(doc: &utoipa::openapi::OpenApi) -> Value {
+    serde_json::to_value(doc).expect("OpenAPI document is always serializable")
+}
+
+/// 以排序后的键输出缩进 JSON
+pub fn canonical_json(doc: &utoipa::openapi::OpenApi) -> String {
+    let mut out = String::new();
+    write_json(&mut out, &to_value(doc), 0);
+    out.push('\n');
+    out
+}
+
+/// 以排序后的键输出 YAML
+pub fn canonical_yaml(doc: &utoipa::openapi::OpenApi) -> String {
+    let mut out = String::new();
+    write_yaml(&mut out, &to_value(doc), 0);
+    out
+}
+
+fn sorted(map: &serde_json::Map<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+fn write_json(out: &mut String, value: &Value, indent: usize) {
+    let pad = "  ".repeat(indent + 1);
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            out.push_str("{\n");
+            let entries = sorted(map);
+            for (i, (key, value)) in entries.iter().enumerate() {
+                let _ = write!(out, "{}{}: ", pad, Value::String(key.to_string()));
+                write_json(out, value, indent + 1);
+                out.push_str(if i + 1 < entries.len() { ",\n" } else { "\n" });
+            }
+            let _ = write!(out, "{}}}", "  ".repeat(indent));
+        }
+        Value::Array(items) if !items.is_empty() => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                out.push_str(&pad);
+                write_json(out, item, indent + 1);
+                out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+            }
+            let _ = write!(out, "{}]", "  ".repeat(indent));
+        }
+        _ => out.push_str(&value.to_string()),
+    }
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => {
+            let plain = !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "_-./{}".contains(c))
+                && !s.starts_with(['-', '.', '{'])
+                && s.parse::<f64>().is_err()
+                && !matches!(
+                    s.as_str(),
+                    "true" | "false" | "null" | "yes" | "no" | "on" | "off"
+                );
+            if plain {
+                s.clone()
+            } else {
+                // JSON 字符串字面量同时是合法的 YAML 双引号字符串
+                value.to_string()
+            }
+        }
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn is_block(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => !map.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+fn write_yaml(out: &mut String, value: &Value, indent: usize) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in sorted(map) {
+                let key = yaml_scalar(&Value::String(key.clone()));
+                if is_block(value) {
+                    let _ = writeln!(out, "{}{}:", pad, key);
+                    write_yaml(out, value, indent + 1);
+                } else {
+                    let _ = writeln!(out, "{}{}: {}", pad, key, yaml_scalar(value));
+                }
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for item in items {
+                if is_block(item) {
+                    // 子块整体缩进一层，首行接在 `- ` 之后
+                    let mut nested = String::new();
+                    write_yaml(&mut nested, item, indent + 1);
+                    let _ = write!(out, "{}- {}", pad, &nested[pad.len() + 2..]);
+                } else {
+                    let _ = writeln!(out, "{}- {}", pad, yaml_scalar(item));
+                }
+            }
+        }
+        other => {
+            let _ = writeln!(out, "{}{}", pad, yaml_scalar(other));
+        }
+    }
+}
+
+/// 比较两段文本，返回逐行差异（`-` 为预期，`+` 为实际），相同时返回 None
+pub fn diff_lines(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut out = String::new();
+    let context = 3;
+    let start = prefix.saturating_sub(context);
+    let _ = writeln!(out, "@@ line {} @@", prefix + 1);
+    for line in &old[start..prefix] {
+        let _ = writeln!(out, "  {}", line);
+    }
+    for line in &old[prefix..old.len() - suffix] {
+        let _ = writeln!(out, "- {}", line);
+    }
+    for line in &new[prefix..new.len() - suffix] {
+        let _ = writeln!(out, "+ {}", line);
+    }
+    let end = (old.len() - suffix + context).min(old.len());
+    for line in &old[old.len() - suffix..end] {
+        let _ = writeln!(out, "  {}", line);
+    }
+    Some(out)
+}
+
+/// 将文档与快照文件比较，`.json` 后缀使用 JSON，其余使用 YAML
+///
+/// 快照不存在或设置了 `MIKO_UPDATE_SNAPSHOTS` 时写入快照；否则不一致即 panic 并打印差异。
+pub fn assert_openapi_snapshot(doc: &utoipa::openapi::OpenApi, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let actual = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => canonical_json(doc),
+        _ => canonical_yaml(doc),
+    };
+    let update = std::env::var_os(UPDATE_ENV).is_some_and(|v| v != "0");
+    if update || !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("Failed to create snapshot directory");
+        }
+        std::fs::write(path, &actual).expect("Failed to write snapshot");
+        return;
+    }
+    let expected = std::fs::read_to_string(path).expect("Failed to read snapshot");
+    // 兼容在 Windows 上检出时的换行符
+    let expected = expected.replace("\r\n", "\n");
+    if let Some(diff) = diff_lines(&expected, &actual) {
+        panic!(
+            "OpenAPI snapshot {} does not match (rerun with {}=1 to update):\n{}",
+            path.display(),
+            UPDATE_ENV,
+            diff
+        );
+    }
+}
+
+/// 文档中 `method path` 在 `status` 下的 JSON 响应 schema
+///
+/// `path` 为路由模式（如 `/users/{id}`）；依次查找精确状态码、`2XX` 形式的范围与 `default`。
+pub fn response_schema(
+    doc: &utoipa::openapi::OpenApi,
+    method: &hyper::Method,
+    path: &str,
+    status: u16,
+) -> Option<Value> {
+    let doc = to_value(doc);
+    let responses = doc
+        .get("paths")?
+        .get(path)?
+        .get(method.as_str().to_ascii_lowercase())?
+        .get("responses")?;
+    let response = [
+        status.to_string(),
+        format!("{}XX", status / 100),
+        "default".to_string(),
+    ]
+    .iter()
+    .find_map(|key| responses.get(key))?;
+    let content = response.get("content")?.as_object()?;
+    content
+        .get("application/json")
+        .or_else(|| content.values().next())?
+        .get("schema")
+        .cloned()
+}
+
+/// 按 `schema` 校验 `instance`，返回所有不符合之处；`$ref` 在 `doc` 的 components 中解析
+///
+/// 支持 `type`（含 `nullable` 与类型数组）、`enum`、`const`、`properties`、`required`、
+/// `additionalProperties`、`items` 以及 `allOf` / `anyOf` / `oneOf`。
+pub fn validate_json(
+    doc: &utoipa::openapi::OpenApi,
+    schema: &Value,
+    instance: &Value,
+) -> Vec<String> {
+    let root = to_value(doc);
+    let mut errors = Vec::new();
+    validate(&root, schema, instance, "$", &mut errors);
+    errors
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn is_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        _ => type_name(value) == ty,
+    }
+}
+
+fn validate(root: &Value, schema: &Value, instance: &Value, at: &str, errors: &mut Vec<String>) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match reference.strip_prefix('#').and_then(|p| root.pointer(p)) {
+            Some(target) => validate(root, target, instance, at, errors),
+            None => errors.push(format!("{}: unresolved reference {}", at, reference)),
+        }
+        return;
+    }
+    if instance.is_null() && schema.get("nullable") == Some(&Value::Bool(true)) {
+        return;
+    }
+    if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+        for sub in all {
+            validate(root, sub, instance, at, errors);
+        }
+    }
+    for (key, exactly_one) in [("anyOf", false), ("oneOf", true)] {
+        if let Some(subs) = schema.get(key).and_then(Value::as_array) {
+            let passed = subs
+                .iter()
+                .filter(|sub| {
+                    let mut sub_errors = Vec::new();
+                    validate(root, sub, instance, at, &mut sub_errors);
+                    sub_errors.is_empty()
+                })
+                .count();
+            if passed == 0 || (exactly_one && passed > 1) {
+                errors.push(format!(
+                    "{}: matches {} of the {} schemas, expected {}",
+                    at,
+                    passed,
+                    key,
+                    if exactly_one {
+                        "exactly one"
+                    } else {
+                        "at least one"
+                    }
+                ));
+            }
+        }
+    }
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(tys)) => tys.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && !types.iter().any(|ty| is_type(instance, ty)) {
+        errors.push(format!(
+            "{}: expected {}, got {}",
+            at,
+            types.join(" | "),
+            type_name(instance)
+        ));
+        return;
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array)
+        && !values.contains(instance)
+    {
+        errors.push(format!(
+            "{}: {} is not one of {}",
+            at,
+            instance,
+            Value::Array(values.clone())
+        ));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != instance
+    {
+        errors.push(format!("{}: expected {}, got {}", at, expected, instance));
+    }
+    match instance {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(name) {
+                        errors.push(format!("{}: missing required property `{}`", at, name));
+                    }
+                }
+            }
+            for (name, value) in map {
+                let path = format!("{}.{}", at, name);
+                match (
+                    properties.and_then(|p| p.get(name)),
+                    schema.get("additionalProperties"),
+                ) {
+                    (Some(sub), _) => validate(root, sub, value, &path, errors),
+                    (None, Some(Value::Bool(false))) => {
+                        errors.push(format!("{}: unexpected property", path))
+                    }
+                    (None, Some(sub @ Value::Object(_))) => {
+                        validate(root, sub, value, &path, errors)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(sub) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate(root, sub, item, &format!("{}[{}]", at, i), errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,174 @@
+//! 静态快照导出
+//!
+//! 遍历 Router 上注册的 GET 路由，通过进程内 [`TestClient`] 逐一请求，
+//! 并把响应写入目录，可用于文档站点或预渲染前端。
+//!
+//! ```rust,ignore
+//! let report = router
+//!     .snapshot()
+//!     .param("slug", ["intro", "install"])
+//!     .export("dist")
+//!     .await?;
+//! println!("{} files written", report.written.len());
+//! ```
+//!
+//! 文件路径规则：
+//! - 最后一段带扩展名时原样写出，如 `/feed.xml` → `feed.xml`
+//! - 否则写成目录下的索引文件，扩展名由 `Content-Type` 决定，
+//!   如 `/` → `index.html`，`/docs/intro` → `docs/intro/index.html`，`/api/users` → `api/users/index.json`
+
+use crate::router::Router;
+use crate::test::test_client::TestClient;
+use hyper::StatusCode;
+use hyper::header::CONTENT_TYPE;
+use miko_core::encode_route;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 快照导出器，由 [`Router::snapshot`] 创建
+pub struct Snapshot {
+    client: TestClient,
+    routes: Vec<String>,
+    params: HashMap<String, Vec<String>>,
+    extra: Vec<String>,
+}
+
+/// 导出结果
+#[derive(Debug, Default)]
+pub struct SnapshotReport {
+    /// 成功写出的请求路径与文件
+    pub written: Vec<(String, PathBuf)>,
+    /// 存在未提供取值的路径参数而跳过的路由
+    pub skipped: Vec<String>,
+    /// 返回非 2xx 状态的请求路径
+    pub failed: Vec<(String, StatusCode)>,
+}
+
+impl Snapshot {
+    pub fn from_router<S: Send + Sync + 'static>(router: Router<S>) -> Self {
+        let mut routes: Vec<String> = router
+            .path_map
+            .get(&hyper::Method::GET)
+            .map(|m| m.keys().cloned().collect())
+            .unwrap_or_default();
+        routes.sort();
+        Self {
+            client: TestClient::new(router.into_tower_service()),
+            routes,
+            params: HashMap::new(),
+            extra: Vec::new(),
+        }
+    }
+
+    /// 为路径参数 `name` 提供可枚举的取值，多个参数时按笛卡尔积展开
+    pub fn param<I, V>(mut self, name: &str, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        self.params
+            .entry(name.to_string())
+            .or_default()
+            .extend(values.into_iter().map(Into::into));
+        self
+    }
+
+    /// 额外导出一个具体路径（如 catch-all 路由下的页面）
+    pub fn path(mut self, path: &str) -> Self {
+        self.extra.push(path.to_string());
+        self
+    }
+
+    /// 将要请求的全部具体路径，以及因缺少参数取值而跳过的路由
+    pub fn plan(&self) -> (Vec<String>, Vec<String>) {
+        let mut paths = Vec::new();
+        let mut skipped = Vec::new();
+        for route in &self.routes {
+            match expand(route, &self.params) {
+                Some(expanded) => paths.extend(expanded),
+                None => skipped.push(route.clone()),
+            }
+        }
+        paths.extend(self.extra.iter().cloned());
+        let mut seen = HashSet::new();
+        paths.retain(|p| seen.insert(p.clone()));
+        (paths, skipped)
+    }
+
+    /// 请求所有路径并把 2xx 响应写入 `dir`
+    pub async fn export(self, dir: impl AsRef<Path>) -> io::Result<SnapshotReport> {
+        let dir = dir.as_ref();
+        let (paths, skipped) = self.plan();
+        let mut report = SnapshotReport {
+            skipped,
+            ..Default::default()
+        };
+        for path in paths {
+            let resp = self.client.get(&encode_route(&path)).send().await;
+            if !resp.status().is_success() {
+                tracing::warn!(path = %path, status = %resp.status(), "snapshot request failed");
+                report.failed.push((path, resp.status()));
+                continue;
+            }
+            let content_type = resp
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok());
+            let file = dir.join(output_path(&path, content_type)?);
+            if let Some(parent) = file.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&file, resp.bytes()).await?;
+            report.written.push((path, file));
+        }
+        Ok(report)
+    }
+}
+
+/// 用参数取值替换路由中的 `{name}` / `{*name}`，存在未提供取值的参数时返回 None
+fn expand(route: &str, params: &HashMap<String, Vec<String>>) -> Option<Vec<String>> {
+    let mut results = vec![String::new()];
+    let mut rest = route;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        let name = rest[start + 1..end].trim_start_matches('*');
+        let values = params.get(name).filter(|v| !v.is_empty())?;
+        let prefix = &rest[..start];
+        results = results
+            .iter()
+            .flat_map(|r| values.iter().map(move |v| format!("{}{}{}", r, prefix, v)))
+            .collect();
+        rest = &rest[end + 1..];
+    }
+    for r in &mut results {
+        r.push_str(rest);
+    }
+    Some(results)
+}
+
+/// 请求路径对应的相对文件路径
+fn output_path(path: &str, content_type: Option<&str>) -> io::Result<PathBuf> {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if segments.iter().any(|s| *s == ".." || *s == ".") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("snapshot path escapes output directory: {}", path),
+        ));
+    }
+    let mut file: PathBuf = segments.iter().collect();
+    if segments.last().is_some_and(|s| s.contains('.')) {
+        return Ok(file);
+    }
+    let ext = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(str::trim)
+        .and_then(|mime| match mime {
+            "text/html" => Some("html"),
+            "text/plain" => Some("txt"),
+            _ => mime_guess::get_mime_extensions_str(mime)?.first().copied(),
+        })
+        .unwrap_or("html");
+    file.push(format!("index.{}", ext));
+    Ok(file)
+}
@@ -1,14 +1,45 @@
+//! 在进程内向 Router 发送请求的测试客户端
+//!
+//! ```rust,ignore
+//! let client = router.test_client();
+//!
+//! client.post("/login").form(&login).send().await.assert_ok();
+//! // 登录返回的 Set-Cookie 保存在客户端中，之后的请求自动携带
+//! let me: User = client.get("/me").send().await.json();
+//!
+//! client
+//!     .post("/avatar")
+//!     .header("x-request-id", "42")
+//!     .multipart(MultipartForm::new().text("alt", "me").file("file", "a.png", "image/png", png))
+//!     .send()
+//!     .await
+//!     .assert_status(201);
+//! ```
+
 use crate::router::HttpSvc;
 use crate::test::test_response::TestResponse;
+use crate::test::test_socket::TestWebSocket;
+use crate::test::test_sse::TestSse;
 use bytes::Bytes;
+use cookie::time::{Duration, OffsetDateTime};
+use cookie::{Cookie, CookieJar};
 use http_body_util::{BodyExt, Full};
-use hyper::Method;
-use hyper::http::{HeaderName, HeaderValue, request};
-use miko_core::Req;
+use hyper::header::{ACCEPT, CONTENT_TYPE, COOKIE, SET_COOKIE};
+use hyper::http::{Extensions, HeaderName, HeaderValue, request};
+use hyper::{HeaderMap, Method};
+use miko_core::{Req, Resp};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
 use tower::ServiceExt;
 
+/// 测试客户端，克隆后共享 cookie
+///
+/// 响应中的 `Set-Cookie` 会写入客户端的 cookie 存储（忽略 `Domain` 与 `Path`），
+/// 过期或 `Max-Age=0` 的 cookie 被删除；之后未手动设置 `Cookie` 头的请求自动携带存储中的 cookie。
+#[derive(Clone)]
 pub struct TestClient {
     svc: HttpSvc<Req>,
+    jar: Arc<Mutex<CookieJar>>,
 }
 
 macro_rules! define_mock_method {
@@ -27,12 +58,17 @@ macro_rules! define_mock_method {
 
 impl TestClient {
     pub fn new(svc: HttpSvc<Req>) -> Self {
-        Self { svc }
+        Self {
+            svc,
+            jar: Arc::new(Mutex::new(CookieJar::new())),
+        }
     }
-    fn build(&self, method: Method, uri: &str) -> TestRequestBuilder {
+    pub(crate) fn build(&self, method: Method, uri: &str) -> TestRequestBuilder {
         TestRequestBuilder {
             svc: self.svc.clone(),
+            jar: self.jar.clone(),
             builder: request::Builder::new().method(method).uri(uri),
+            cookies: Vec::new(),
             body: Vec::new(),
         }
     }
@@ -47,45 +83,246 @@ impl TestClient {
         trace => TRACE,
         connect => CONNECT
     }
+
+    /// 以 WebSocket 连接 `uri`，需要额外请求头时使用 `get(uri).header(..).websocket()`
+    pub async fn websocket(&self, uri: &str) -> TestWebSocket {
+        self.get(uri).websocket().await
+    }
+
+    /// 以 `Accept: text/event-stream` 请求 `uri` 并返回事件流
+    pub async fn sse(&self, uri: &str) -> TestSse {
+        self.get(uri).sse().await
+    }
+
+    /// 读取存储中的 cookie 值
+    pub fn cookie(&self, name: &str) -> Option<String> {
+        let jar = self.jar.lock().unwrap();
+        jar.get(name).map(|c| c.value().to_string())
+    }
+
+    /// 向存储中写入 cookie，之后的请求都会携带
+    pub fn set_cookie(&self, name: &str, value: &str) {
+        let cookie = Cookie::new(name.to_string(), value.to_string());
+        self.jar.lock().unwrap().add_original(cookie);
+    }
+
+    /// 清空 cookie 存储
+    pub fn clear_cookies(&self) {
+        *self.jar.lock().unwrap() = CookieJar::new();
+    }
 }
 
 pub struct TestRequestBuilder {
     svc: HttpSvc<Req>,
+    jar: Arc<Mutex<CookieJar>>,
     builder: request::Builder,
+    cookies: Vec<(String, String)>,
     body: Vec<u8>,
 }
 
 impl TestRequestBuilder {
     pub fn header<K, V>(mut self, key: K, value: V) -> Self
     where
-        K: Into<HeaderName>,
-        V: Into<HeaderValue>,
+        K: TryInto<HeaderName>,
+        V: TryInto<HeaderValue>,
+        K::Error: std::fmt::Debug,
+        V::Error: std::fmt::Debug,
     {
-        self.builder = self.builder.header(key.into(), value.into());
+        self.builder = self.builder.header(
+            key.try_into().expect("Invalid header name"),
+            value.try_into().expect("Invalid header value"),
+        );
         self
     }
 
-    pub fn json<T: serde::Serialize>(mut self, json: &T) -> Self {
-        self.body = serde_json::to_vec(json).expect("Failed to serialize JSON");
+    /// 仅为本次请求附加 cookie，同名时覆盖存储中的值
+    pub fn cookie(mut self, name: &str, value: &str) -> Self {
+        self.cookies.push((name.to_string(), value.to_string()));
         self
     }
 
-    pub fn text(mut self, text: &str) -> Self {
-        self.body = text.as_bytes().to_vec();
+    pub fn json<T: Serialize>(self, json: &T) -> Self {
+        self.header(CONTENT_TYPE, "application/json")
+            .bytes(serde_json::to_vec(json).expect("Failed to serialize JSON"))
+    }
+
+    pub fn form<T: Serialize>(self, form: &T) -> Self {
+        self.header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .bytes(serde_urlencoded::to_string(form).expect("Failed to serialize form"))
+    }
+
+    #[cfg(feature = "protobuf")]
+    pub fn protobuf<T: prost::Message>(self, message: &T) -> Self {
+        self.header(CONTENT_TYPE, "application/x-protobuf")
+            .bytes(message.encode_to_vec())
+    }
+
+    pub fn multipart(self, form: MultipartForm) -> Self {
+        let content_type = format!("multipart/form-data; boundary={}", form.boundary);
+        self.header(CONTENT_TYPE, content_type).bytes(form.finish())
+    }
+
+    pub fn text(self, text: &str) -> Self {
+        self.bytes(text)
+    }
+
+    pub fn bytes(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
         self
     }
 
     pub async fn send(self) -> TestResponse {
-        let body = Full::new(Bytes::from(self.body))
-            .map_err(Into::into)
-            .boxed_unsync();
+        self.send_with_extensions().await.0
+    }
 
-        let req = self.builder.body(body).expect("Failed to build request");
-        let resp = self
-            .svc
+    /// 发送请求，同时返回响应的扩展（如命中的 [`MatchedPath`](crate::router::MatchedPath)）
+    pub(crate) async fn send_with_extensions(self) -> (TestResponse, Extensions) {
+        let jar = self.jar.clone();
+        let mut resp = self.dispatch().await;
+        let extensions = std::mem::take(resp.extensions_mut());
+        let resp = TestResponse::from_response(resp).await;
+        store_cookies(&jar, &resp.headers);
+        (resp, extensions)
+    }
+
+    /// 完成 WebSocket 握手，之后通过返回的连接收发消息
+    ///
+    /// 握手失败（例如路由未升级）时 panic 并输出响应状态。
+    pub async fn websocket(mut self) -> TestWebSocket {
+        let req = self.build_request();
+        TestWebSocket::connect(self.svc, &req.uri().to_string(), req.headers().clone()).await
+    }
+
+    /// 发送请求并按事件读取 SSE 响应体
+    pub async fn sse(self) -> TestSse {
+        let jar = self.jar.clone();
+        let resp = self.header(ACCEPT, "text/event-stream").dispatch().await;
+        store_cookies(&jar, resp.headers());
+        TestSse::from_response(resp)
+    }
+
+    async fn dispatch(mut self) -> Resp {
+        let req = self.build_request();
+        self.svc
             .oneshot(req)
             .await
-            .expect("Failed to execute request");
-        TestResponse::from_response(resp).await
+            .expect("Failed to execute request")
+    }
+
+    fn build_request(&mut self) -> Req {
+        let body = Full::new(Bytes::from(std::mem::take(&mut self.body)))
+            .map_err(Into::into)
+            .boxed_unsync();
+
+        let mut builder = std::mem::take(&mut self.builder);
+        let cookie_header = {
+            let mut jar = self.jar.lock().unwrap().clone();
+            for (name, value) in self.cookies.drain(..) {
+                jar.add_original(Cookie::new(name, value));
+            }
+            jar.iter()
+                .map(|c| c.encoded().stripped().to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        };
+        // 手动设置了 Cookie 头时不再附加存储中的 cookie
+        let explicit = builder
+            .headers_ref()
+            .is_some_and(|headers| headers.contains_key(COOKIE));
+        if !cookie_header.is_empty() && !explicit {
+            builder = builder.header(COOKIE, cookie_header);
+        }
+
+        builder.body(body).expect("Failed to build request")
+    }
+}
+
+/// 按响应的 `Set-Cookie` 更新存储
+fn store_cookies(jar: &Mutex<CookieJar>, headers: &HeaderMap) {
+    let mut jar = jar.lock().unwrap();
+    for value in headers.get_all(SET_COOKIE) {
+        let Some(cookie) = value
+            .to_str()
+            .ok()
+            .and_then(|v| Cookie::parse_encoded(v.to_string()).ok())
+        else {
+            continue;
+        };
+        let expired = cookie.max_age().is_some_and(|age| age <= Duration::ZERO)
+            || cookie
+                .expires_datetime()
+                .is_some_and(|at| at <= OffsetDateTime::now_utc());
+        if expired {
+            jar.force_remove(cookie.name());
+        } else {
+            jar.add_original(Cookie::new(
+                cookie.name().to_string(),
+                cookie.value().to_string(),
+            ));
+        }
+    }
+}
+
+/// `multipart/form-data` 请求体构建器
+pub struct MultipartForm {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Default for MultipartForm {
+    fn default() -> Self {
+        Self {
+            boundary: "miko-test-boundary-4f7c2a9d".to_string(),
+            body: Vec::new(),
+        }
+    }
+}
+
+impl MultipartForm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加文本字段
+    pub fn text(mut self, name: &str, value: impl AsRef<str>) -> Self {
+        self.part(&format!("form-data; name=\"{}\"", name), None);
+        self.body.extend_from_slice(value.as_ref().as_bytes());
+        self.body.extend_from_slice(b"\r\n");
+        self
+    }
+
+    /// 添加文件字段
+    pub fn file(
+        mut self,
+        name: &str,
+        filename: &str,
+        content_type: &str,
+        data: impl AsRef<[u8]>,
+    ) -> Self {
+        self.part(
+            &format!("form-data; name=\"{}\"; filename=\"{}\"", name, filename),
+            Some(content_type),
+        );
+        self.body.extend_from_slice(data.as_ref());
+        self.body.extend_from_slice(b"\r\n");
+        self
+    }
+
+    fn part(&mut self, disposition: &str, content_type: Option<&str>) {
+        self.body
+            .extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+        self.body
+            .extend_from_slice(format!("Content-Disposition: {}\r\n", disposition).as_bytes());
+        if let Some(content_type) = content_type {
+            self.body
+                .extend_from_slice(format!("Content-Type: {}\r\n", content_type).as_bytes());
+        }
+        self.body.extend_from_slice(b"\r\n");
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.body
+            .extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        self.body
     }
 }
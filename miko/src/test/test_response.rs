@@ -47,6 +47,14 @@ impl TestResponse {
         })
     }
 
+    #[cfg(feature = "protobuf")]
+    #[track_caller]
+    pub fn protobuf<T: prost::Message + Default>(&self) -> T {
+        T::decode(self.body.clone()).unwrap_or_else(|e| {
+            panic!("Failed to decode protobuf response\nerror={:?}", e);
+        })
+    }
+
     #[track_caller]
     /// 断言响应状态为成功 (2xx)
     pub fn assert_success(&self) {
@@ -0,0 +1,129 @@
+//! 在进程内连接 WebSocket 路由
+//!
+//! 请求经由内存中的双工管道交给 hyper 处理，握手与升级流程与真实连接一致，但不绑定端口：
+//!
+//! ```rust,ignore
+//! let mut socket = router.test_client().websocket("/ws").await;
+//! socket.send("hello").await;
+//! assert_eq!(socket.recv_text().await, "echo: hello");
+//! socket.close().await;
+//! ```
+
+use crate::http::convert::incoming_to_req::IncomingToInternal;
+use crate::router::HttpSvc;
+use crate::ws::server::{IntoMessage, WsMessage};
+use futures::{SinkExt, StreamExt};
+use hyper::HeaderMap;
+use hyper_util::rt::TokioIo;
+use hyper_util::service::TowerToHyperService;
+use miko_core::Req;
+use serde::de::DeserializeOwned;
+use tokio::io::DuplexStream;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::WebSocketStream;
+use tungstenite::client::IntoClientRequest;
+
+/// 客户端一侧的 WebSocket 连接，drop 后断开
+pub struct TestWebSocket {
+    stream: WebSocketStream<DuplexStream>,
+    protocol: Option<String>,
+    conn: JoinHandle<()>,
+}
+
+impl TestWebSocket {
+    pub(crate) async fn connect(svc: HttpSvc<Req>, uri: &str, headers: HeaderMap) -> Self {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let svc = TowerToHyperService::new(IncomingToInternal { inner: svc });
+        let conn = tokio::spawn(async move {
+            let _ = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(server_io), svc)
+                .with_upgrades()
+                .await;
+        });
+
+        let mut request = format!("ws://localhost{}", uri)
+            .into_client_request()
+            .expect("Invalid WebSocket URI");
+        request.headers_mut().extend(headers);
+        let (stream, resp) = match tokio_tungstenite::client_async(request, client_io).await {
+            Ok(connected) => connected,
+            Err(tungstenite::Error::Http(resp)) => {
+                let body = resp
+                    .body()
+                    .as_deref()
+                    .map(String::from_utf8_lossy)
+                    .unwrap_or_default();
+                panic!(
+                    "WebSocket upgrade rejected with {}: {}",
+                    resp.status(),
+                    body
+                )
+            }
+            Err(e) => panic!("WebSocket handshake failed: {}", e),
+        };
+        let protocol = resp
+            .headers()
+            .get("sec-websocket-protocol")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        Self {
+            stream,
+            protocol,
+            conn,
+        }
+    }
+
+    /// 服务端选定的子协议
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
+    /// 发送文本、二进制或 `Json(..)` 消息
+    pub async fn send(&mut self, msg: impl IntoMessage) {
+        self.stream
+            .send(msg.into_message())
+            .await
+            .expect("Failed to send WebSocket message");
+    }
+
+    /// 接收下一条消息，忽略 ping/pong；连接结束时返回 `None`
+    pub async fn recv(&mut self) -> Option<WsMessage> {
+        loop {
+            let msg = self.stream.next().await?.ok()?;
+            if let Some(msg) = WsMessage::from_data(msg) {
+                return Some(msg);
+            }
+        }
+    }
+
+    /// 接收下一条消息并要求为文本
+    pub async fn recv_text(&mut self) -> String {
+        match self.recv().await {
+            Some(WsMessage::Text(text)) => text.to_string(),
+            other => panic!("Expected a text message, got {:?}", other),
+        }
+    }
+
+    /// 接收下一条文本消息并反序列化为 `T`
+    pub async fn recv_json<T: DeserializeOwned>(&mut self) -> T {
+        let text = self.recv_text().await;
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            panic!(
+                "Failed to deserialize message\nerror={:?}\ntext={}",
+                e, text
+            )
+        })
+    }
+
+    /// 发送关闭帧并等待服务端确认
+    pub async fn close(mut self) {
+        let _ = self.stream.close(None).await;
+        while let Some(Ok(_)) = self.stream.next().await {}
+    }
+}
+
+impl Drop for TestWebSocket {
+    fn drop(&mut self) {
+        self.conn.abort();
+    }
+}
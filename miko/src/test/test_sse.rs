@@ -0,0 +1,135 @@
+//! 逐个读取 SSE 响应中的事件
+//!
+//! ```rust,ignore
+//! let mut events = router.test_client().sse("/events").await;
+//! assert_eq!(events.next_event().await.unwrap().data, "tick 0");
+//! ```
+
+use crate::http::response::sse::SseEvent;
+use bytes::{Buf, BytesMut};
+use futures::Stream;
+use hyper::body::Body;
+use hyper::{HeaderMap, StatusCode};
+use miko_core::{Resp, RespBody};
+use serde::de::DeserializeOwned;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// 解析后的 SSE 事件流，响应结束时流结束
+///
+/// 仅含注释的块（如 keep-alive）会被跳过。
+pub struct TestSse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: RespBody,
+    buf: BytesMut,
+    done: bool,
+}
+
+impl TestSse {
+    pub(crate) fn from_response(resp: Resp) -> Self {
+        let (parts, body) = resp.into_parts();
+        Self {
+            status: parts.status,
+            headers: parts.headers,
+            body,
+            buf: BytesMut::new(),
+            done: false,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// 等待下一个事件
+    pub async fn next_event(&mut self) -> Option<SseEvent> {
+        futures::StreamExt::next(self).await
+    }
+
+    /// 等待下一个事件并将 data 反序列化为 `T`
+    pub async fn next_json<T: DeserializeOwned>(&mut self) -> T {
+        let event = self.next_event().await.expect("SSE stream ended");
+        serde_json::from_str(&event.data).unwrap_or_else(|e| {
+            panic!(
+                "Failed to deserialize event\nerror={:?}\ndata={}",
+                e, event.data
+            )
+        })
+    }
+
+    /// 从缓冲区取出一个完整事件
+    fn take_event(&mut self) -> Option<SseEvent> {
+        loop {
+            let end = self.buf.windows(2).position(|w| w == b"\n\n")?;
+            let block = self.buf.split_to(end);
+            self.buf.advance(2);
+            if let Some(event) = parse_event(&String::from_utf8_lossy(&block)) {
+                return Some(event);
+            }
+        }
+    }
+}
+
+impl Stream for TestSse {
+    type Item = SseEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<SseEvent>> {
+        loop {
+            if let Some(event) = self.take_event() {
+                return Poll::Ready(Some(event));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+            match Pin::new(&mut self.body).poll_frame(cx) {
+                Poll::Ready(Some(Ok(frame))) => {
+                    if let Ok(data) = frame.into_data() {
+                        // 统一换行符，便于按空行切分
+                        let text = String::from_utf8_lossy(&data).replace("\r\n", "\n");
+                        self.buf.extend_from_slice(text.as_bytes());
+                    }
+                }
+                Poll::Ready(Some(Err(_))) | Poll::Ready(None) => {
+                    self.done = true;
+                    self.buf.extend_from_slice(b"\n\n");
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// 解析单个事件块，没有任何字段时返回 `None`
+fn parse_event(block: &str) -> Option<SseEvent> {
+    let mut data: Option<Vec<&str>> = None;
+    let mut event = None;
+    let mut id = None;
+    let mut retry = None;
+    for line in block.lines() {
+        if line.is_empty() || line.starts_with(':') {
+            continue;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "data" => data.get_or_insert_with(Vec::new).push(value),
+            "event" => event = Some(value.to_string()),
+            "id" => id = Some(value.to_string()),
+            "retry" => retry = value.parse().ok(),
+            _ => {}
+        }
+    }
+    if data.is_none() && event.is_none() && id.is_none() && retry.is_none() {
+        return None;
+    }
+    Some(SseEvent {
+        data: data.map(|d| d.join("\n")).unwrap_or_default(),
+        event,
+        id,
+        retry,
+    })
+}
@@ -0,0 +1,10 @@
+//! crate 内部共用的小工具函数
+
+/// 转义 HTML 文本中的特殊字符
+pub(crate) fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
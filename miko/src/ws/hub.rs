@@ -0,0 +1,244 @@
+//! WebSocket 连接中心
+//!
+//! [`WsHub`] 记录已连接的发送端，支持命名房间、广播与定向发送；
+//! 连接断开（[`HubMember`] 被释放或发送失败）时自动清理。
+//!
+//! ```rust,ignore
+//! let hub = WsHub::new();
+//! router.get("/chat/{room}", move |upgrade: WsUpgrade, Path(room): Path<String>| {
+//!     let hub = hub.clone();
+//!     async move {
+//!         upgrade.on(move |socket| async move {
+//!             let (member, mut rx) = hub.attach(socket);
+//!             member.join(&room);
+//!             while let Some(Ok(WsMessage::Text(text))) = rx.recv().await {
+//!                 hub.broadcast_to(&room, text.to_string()).await;
+//!             }
+//!         })
+//!     }
+//! });
+//! ```
+
+use crate::ws::server::{IntoMessage, WsReceiver, WsSender, WsSocket};
+use futures::future::join_all;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tungstenite::Message;
+
+/// 连接在 [`WsHub`] 内的编号
+pub type ConnId = u64;
+
+/// WebSocket 连接中心，克隆后共享同一份连接表
+#[derive(Clone, Default)]
+pub struct WsHub {
+    inner: Arc<HubInner>,
+}
+
+#[derive(Default)]
+struct HubInner {
+    next_id: AtomicU64,
+    state: Mutex<HubState>,
+}
+
+#[derive(Default)]
+struct HubState {
+    conns: HashMap<ConnId, Conn>,
+    rooms: HashMap<String, HashSet<ConnId>>,
+}
+
+struct Conn {
+    sender: WsSender,
+    rooms: HashSet<String>,
+}
+
+impl HubState {
+    fn remove(&mut self, id: ConnId) {
+        let Some(conn) = self.conns.remove(&id) else {
+            return;
+        };
+        for room in conn.rooms {
+            if let Some(members) = self.rooms.get_mut(&room) {
+                members.remove(&id);
+                if members.is_empty() {
+                    self.rooms.remove(&room);
+                }
+            }
+        }
+    }
+}
+
+impl WsHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册一个发送端，返回的 [`HubMember`] 被释放时连接从中心移除
+    pub fn register(&self, sender: WsSender) -> HubMember {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        self.state().conns.insert(
+            id,
+            Conn {
+                sender,
+                rooms: HashSet::new(),
+            },
+        );
+        HubMember {
+            hub: self.clone(),
+            id,
+        }
+    }
+
+    /// 分离 socket 并注册其发送端，返回成员句柄与接收端
+    pub fn attach(&self, socket: WsSocket) -> (HubMember, WsReceiver) {
+        let (sender, receiver, _) = socket.split();
+        (self.register(sender), receiver)
+    }
+
+    /// 将连接加入房间，连接不存在时返回 false
+    pub fn join(&self, id: ConnId, room: &str) -> bool {
+        let mut state = self.state();
+        let Some(conn) = state.conns.get_mut(&id) else {
+            return false;
+        };
+        conn.rooms.insert(room.to_string());
+        state.rooms.entry(room.to_string()).or_default().insert(id);
+        true
+    }
+
+    /// 将连接移出房间
+    pub fn leave(&self, id: ConnId, room: &str) {
+        let mut state = self.state();
+        if let Some(conn) = state.conns.get_mut(&id) {
+            conn.rooms.remove(room);
+        }
+        if let Some(members) = state.rooms.get_mut(room) {
+            members.remove(&id);
+            if members.is_empty() {
+                state.rooms.remove(room);
+            }
+        }
+    }
+
+    /// 从中心移除连接
+    pub fn remove(&self, id: ConnId) {
+        self.state().remove(id);
+    }
+
+    /// 当前连接数
+    pub fn len(&self) -> usize {
+        self.state().conns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 房间内的连接
+    pub fn members(&self, room: &str) -> Vec<ConnId> {
+        self.state()
+            .rooms
+            .get(room)
+            .map(|members| members.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// 当前所有非空房间
+    pub fn rooms(&self) -> Vec<String> {
+        self.state().rooms.keys().cloned().collect()
+    }
+
+    /// 发送给指定连接，连接不存在或已断开时返回 false
+    pub async fn send_to(&self, id: ConnId, msg: impl IntoMessage) -> bool {
+        self.deliver(vec![id], msg.into_message()).await == 1
+    }
+
+    /// 发送给所有连接，返回成功送达的数量
+    pub async fn broadcast(&self, msg: impl IntoMessage) -> usize {
+        let ids = self.state().conns.keys().copied().collect();
+        self.deliver(ids, msg.into_message()).await
+    }
+
+    /// 发送给房间内的所有连接，返回成功送达的数量
+    pub async fn broadcast_to(&self, room: &str, msg: impl IntoMessage) -> usize {
+        self.deliver(self.members(room), msg.into_message()).await
+    }
+
+    /// 发送给房间内除 `except` 外的所有连接
+    pub async fn broadcast_to_except(
+        &self,
+        room: &str,
+        except: ConnId,
+        msg: impl IntoMessage,
+    ) -> usize {
+        let mut ids = self.members(room);
+        ids.retain(|id| *id != except);
+        self.deliver(ids, msg.into_message()).await
+    }
+
+    /// 并发发送，发送失败的连接视为已断开并移除
+    async fn deliver(&self, ids: Vec<ConnId>, msg: Message) -> usize {
+        let targets: Vec<(ConnId, WsSender)> = {
+            let state = self.state();
+            ids.into_iter()
+                .filter_map(|id| state.conns.get(&id).map(|c| (id, c.sender.clone())))
+                .collect()
+        };
+        let results = join_all(targets.into_iter().map(|(id, mut sender)| {
+            let msg = msg.clone();
+            async move { (id, sender.send(msg).await.is_ok()) }
+        }))
+        .await;
+        let mut delivered = 0;
+        let mut state = self.state();
+        for (id, ok) in results {
+            if ok {
+                delivered += 1;
+            } else {
+                state.remove(id);
+            }
+        }
+        delivered
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, HubState> {
+        self.inner.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 连接在 [`WsHub`] 中的成员句柄，释放时自动移除连接
+pub struct HubMember {
+    hub: WsHub,
+    id: ConnId,
+}
+
+impl HubMember {
+    pub fn id(&self) -> ConnId {
+        self.id
+    }
+
+    pub fn hub(&self) -> &WsHub {
+        &self.hub
+    }
+
+    /// 加入房间
+    pub fn join(&self, room: &str) {
+        self.hub.join(self.id, room);
+    }
+
+    /// 离开房间
+    pub fn leave(&self, room: &str) {
+        self.hub.leave(self.id, room);
+    }
+
+    /// 发送给自己
+    pub async fn send(&self, msg: impl IntoMessage) -> bool {
+        self.hub.send_to(self.id, msg).await
+    }
+}
+
+impl Drop for HubMember {
+    fn drop(&mut self) {
+        self.hub.remove(self.id);
+    }
+}
@@ -1,2 +1,7 @@
+pub mod hub;
 pub mod server;
 pub mod toolkit;
+pub mod upgrade;
+
+pub use hub::WsHub;
+pub use upgrade::WsUpgrade;
@@ -1,11 +1,11 @@
 use crate::extractor::Json;
 use crate::handler::{Req, Resp};
-use crate::ws::toolkit::upgrade_websocket;
+use crate::ws::toolkit::{select_protocol, upgrade_websocket_with_protocol};
 use anyhow::anyhow;
 use bytes::Bytes;
 use futures::stream::{SplitSink, SplitStream};
 use futures::{SinkExt, StreamExt};
-use hyper::upgrade::Upgraded;
+use hyper::upgrade::{OnUpgrade, Upgraded};
 use hyper_util::rt::TokioIo;
 use serde::Serialize;
 use tokio::sync::mpsc;
@@ -13,16 +13,29 @@ use tokio::sync::mpsc::error::SendError;
 use tokio::task::JoinHandle;
 use tokio_tungstenite::WebSocketStream;
 use tungstenite::protocol::{Role, WebSocketConfig};
+
+pub use tungstenite::protocol::CloseFrame;
+pub use tungstenite::protocol::frame::coding::CloseCode;
 use tungstenite::{Error, Message, Utf8Bytes};
 
 /// WebSocket 连接封装，提供便捷的发送/接收/split
 pub struct WsSocket {
     io: WebSocketStream<TokioIo<Upgraded>>,
+    protocol: Option<String>,
 }
 impl WsSocket {
     /// 基于底层流创建
     pub fn new(io: WebSocketStream<TokioIo<Upgraded>>) -> WsSocket {
-        Self { io }
+        Self { io, protocol: None }
+    }
+    /// 设置握手时协商出的子协议
+    pub fn with_protocol(mut self, protocol: Option<String>) -> WsSocket {
+        self.protocol = protocol;
+        self
+    }
+    /// 握手时协商出的子协议
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
     }
     /// 发送一条消息
     pub async fn send(&mut self, msg: impl IntoMessage) -> tungstenite::Result<()> {
@@ -32,10 +45,39 @@ impl WsSocket {
     pub async fn next(&mut self) -> Option<Result<Message, Error>> {
         self.io.next().await
     }
+    /// 接收下一条数据或关闭消息，Ping/Pong 由底层自动处理
+    ///
+    /// 收到对端的关闭帧后会自动回复关闭帧（RFC 6455 §5.5.1），之后返回 `None`
+    pub async fn recv(&mut self) -> Option<Result<WsMessage, Error>> {
+        loop {
+            match self.io.next().await? {
+                Ok(Message::Close(frame)) => {
+                    // 底层已排队回复帧，这里确保其被写出
+                    let _ = self.io.flush().await;
+                    return Some(Ok(WsMessage::Close(frame)));
+                }
+                Ok(msg) => {
+                    if let Some(msg) = WsMessage::from_data(msg) {
+                        return Some(Ok(msg));
+                    }
+                }
+                Err(Error::ConnectionClosed) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
     /// 主动关闭连接
     pub async fn close(&mut self) -> tungstenite::Result<()> {
         self.io.close(None).await
     }
+    /// 以指定关闭码与原因关闭连接
+    pub async fn close_with(
+        &mut self,
+        code: impl Into<CloseCode>,
+        reason: impl Into<String>,
+    ) -> tungstenite::Result<()> {
+        self.io.close(Some(close_frame(code, reason))).await
+    }
     /// 分离底层读写端
     pub fn split_inner(self) -> (WsSendSink, WsRecvStream) {
         self.io.split()
@@ -44,8 +86,9 @@ impl WsSocket {
     pub fn split(self) -> (WsSender, WsReceiver, JoinHandle<()>) {
         let (mut w, r) = self.io.split();
         let (tx, mut rx) = mpsc::channel::<Message>(100);
-        let handle = tokio::spawn(async move {
+        let handle = crate::spawn_with_context(async move {
             while let Some(msg) = rx.recv().await {
+                let closing = msg.is_close();
                 if let Err(e) = w.send(msg).await {
                     match e {
                         Error::ConnectionClosed | Error::Protocol(_) => {
@@ -57,9 +100,46 @@ impl WsSocket {
                     }
                     break;
                 }
+                if closing {
+                    return;
+                }
             }
+            // 所有发送端都已释放，按正常关闭通知对端
+            let _ = w.close().await;
         });
-        (WsSender::new(tx), WsReceiver::new(r), handle)
+        let receiver = WsReceiver {
+            inner: r,
+            closer: Some(tx.clone()),
+        };
+        (WsSender::new(tx), receiver, handle)
+    }
+}
+
+/// 应用层关心的 WebSocket 消息，关闭帧以结构化形式给出
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsMessage {
+    Text(Utf8Bytes),
+    Binary(Bytes),
+    /// 对端发起的关闭，包含关闭码与原因（如有）
+    Close(Option<CloseFrame>),
+}
+
+impl WsMessage {
+    pub(crate) fn from_data(msg: Message) -> Option<Self> {
+        match msg {
+            Message::Text(text) => Some(Self::Text(text)),
+            Message::Binary(data) => Some(Self::Binary(data)),
+            Message::Close(frame) => Some(Self::Close(frame)),
+            Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => None,
+        }
+    }
+}
+
+/// 构造关闭帧
+pub fn close_frame(code: impl Into<CloseCode>, reason: impl Into<String>) -> CloseFrame {
+    CloseFrame {
+        code: code.into(),
+        reason: Utf8Bytes::from(reason.into()),
     }
 }
 
@@ -105,6 +185,45 @@ impl IntoMessage for &[u8] {
     }
 }
 
+/// WebSocket 升级选项
+///
+/// 注意：不支持 permessage-deflate。底层 tungstenite 0.28 未实现该扩展，因此这里也不提供压缩选项；
+/// 握手响应不会确认客户端在 `Sec-WebSocket-Extensions` 中的压缩请求，连接按 RFC 6455 以不压缩的方式建立
+#[derive(Debug, Clone, Default)]
+pub struct WsOptions {
+    /// 底层连接配置
+    pub config: Option<WebSocketConfig>,
+    /// 服务端支持的子协议
+    pub protocols: Vec<String>,
+    /// 客户端未声明任何受支持的子协议时拒绝升级
+    pub require_protocol: bool,
+}
+
+impl WsOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// 设置底层连接配置
+    pub fn config(mut self, config: WebSocketConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+    /// 声明服务端支持的子协议
+    pub fn protocols<I, P>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<String>,
+    {
+        self.protocols = protocols.into_iter().map(Into::into).collect();
+        self
+    }
+    /// 要求必须协商出子协议
+    pub fn require_protocol(mut self, require: bool) -> Self {
+        self.require_protocol = require;
+        self
+    }
+}
+
 /// 将当前请求升级为 WebSocket 并在后台运行你的异步任务
 pub fn spawn_ws_event<F, Fut>(
     task: F,
@@ -115,24 +234,64 @@ where
     F: FnOnce(WsSocket) -> Fut + Send + 'static,
     Fut: Future<Output = ()> + Send + 'static,
 {
-    let Ok((resp, upgrade)) = upgrade_websocket(req) else {
+    spawn_ws_event_with(
+        task,
+        req,
+        WsOptions {
+            config: options,
+            ..Default::default()
+        },
+    )
+}
+
+/// 同 [`spawn_ws_event`]，但支持子协议协商等完整选项
+pub fn spawn_ws_event_with<F, Fut>(
+    task: F,
+    req: &mut Req,
+    options: WsOptions,
+) -> Result<Resp, anyhow::Error>
+where
+    F: FnOnce(WsSocket) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let protocol = select_protocol(req, &options.protocols);
+    if protocol.is_none() && options.require_protocol {
+        return Err(anyhow!(
+            "no supported websocket subprotocol, expected one of {:?}",
+            options.protocols
+        ));
+    }
+    let Ok((resp, upgrade)) = upgrade_websocket_with_protocol(req, protocol.as_deref()) else {
         return Err(anyhow!("failed to upgrade websocket"));
     };
-    tokio::spawn(async move {
+    spawn_upgraded(upgrade, options.config, protocol, task);
+    Ok(resp)
+}
+
+/// 等待连接升级完成后运行任务
+pub(crate) fn spawn_upgraded<F, Fut>(
+    upgrade: OnUpgrade,
+    config: Option<WebSocketConfig>,
+    protocol: Option<String>,
+    task: F,
+) where
+    F: FnOnce(WsSocket) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    crate::spawn_linked("websocket", async move {
         let upgraded = upgrade.await;
         match upgraded {
             Ok(upgraded) => {
                 let io =
-                    WebSocketStream::from_raw_socket(TokioIo::new(upgraded), Role::Server, options)
+                    WebSocketStream::from_raw_socket(TokioIo::new(upgraded), Role::Server, config)
                         .await;
-                task(WsSocket::new(io)).await;
+                task(WsSocket::new(io).with_protocol(protocol)).await;
             }
             Err(_e) => {
                 panic!("failed to upgrade websocket");
             }
         }
     });
-    Ok(resp)
 }
 
 /// 底层 Split 类型别名
@@ -154,10 +313,14 @@ impl WsSender {
 /// WebSocket 接收端（包装 SplitStream）
 pub struct WsReceiver {
     inner: WsRecvStream,
+    closer: Option<mpsc::Sender<Message>>,
 }
 impl WsReceiver {
     pub fn new(inner: WsRecvStream) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            closer: None,
+        }
     }
 }
 impl WsSender {
@@ -165,10 +328,41 @@ impl WsSender {
     pub async fn send(&mut self, msg: impl IntoMessage) -> Result<(), SendError<Message>> {
         self.inner.send(msg.into_message()).await
     }
+    /// 以指定关闭码与原因关闭连接，之后的消息不会再被发送
+    pub async fn close_with(
+        &mut self,
+        code: impl Into<CloseCode>,
+        reason: impl Into<String>,
+    ) -> Result<(), SendError<Message>> {
+        self.inner
+            .send(Message::Close(Some(close_frame(code, reason))))
+            .await
+    }
 }
 impl WsReceiver {
     /// 接收下一条消息
     pub async fn next(&mut self) -> Option<Result<Message, Error>> {
         self.inner.next().await
     }
+    /// 接收下一条数据或关闭消息，语义同 [`WsSocket::recv`]
+    pub async fn recv(&mut self) -> Option<Result<WsMessage, Error>> {
+        loop {
+            match self.inner.next().await? {
+                Ok(Message::Close(frame)) => {
+                    // 写端由发送任务持有，通过它写出已排队的回复帧
+                    if let Some(closer) = self.closer.take() {
+                        let _ = closer.send(Message::Close(None)).await;
+                    }
+                    return Some(Ok(WsMessage::Close(frame)));
+                }
+                Ok(msg) => {
+                    if let Some(msg) = WsMessage::from_data(msg) {
+                        return Some(Ok(msg));
+                    }
+                }
+                Err(Error::ConnectionClosed) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
 }
@@ -10,6 +10,14 @@ pub type WsStream = WebSocketStream<TokioIo<hyper::upgrade::Upgraded>>;
 
 /// 执行协议握手并返回 101 Switching Protocols 响应与升级句柄
 pub fn upgrade_websocket(req: &mut Req) -> Result<(Resp, OnUpgrade), anyhow::Error> {
+    upgrade_websocket_with_protocol(req, None)
+}
+
+/// 执行协议握手，并在响应中回显选定的子协议（`Sec-WebSocket-Protocol`）
+pub fn upgrade_websocket_with_protocol(
+    req: &mut Req,
+    protocol: Option<&str>,
+) -> Result<(Resp, OnUpgrade), anyhow::Error> {
     let key = req
         .headers()
         .get(hyper::header::SEC_WEBSOCKET_KEY)
@@ -22,16 +30,59 @@ pub fn upgrade_websocket(req: &mut Req) -> Result<(Resp, OnUpgrade), anyhow::Err
     {
         return Err(ProtocolError::MissingSecWebSocketVersionHeader.into());
     }
-    let accept = tungstenite::handshake::derive_accept_key(key.as_bytes());
-    let resp = Response::builder()
+    let resp = switching_protocols(key.as_bytes(), protocol);
+    let on_upgrade = hyper::upgrade::on(req);
+    Ok((resp, on_upgrade))
+}
+
+/// 构造握手成功的 101 Switching Protocols 响应，`protocol` 为选定的子协议
+pub fn switching_protocols(key: &[u8], protocol: Option<&str>) -> Resp {
+    let accept = tungstenite::handshake::derive_accept_key(key);
+    let mut builder = Response::builder()
         .status(StatusCode::SWITCHING_PROTOCOLS)
         .header(CONNECTION, "Upgrade")
         .header(hyper::header::UPGRADE, "websocket")
-        .header(hyper::header::SEC_WEBSOCKET_ACCEPT, accept)
+        .header(hyper::header::SEC_WEBSOCKET_ACCEPT, accept);
+    if let Some(protocol) = protocol {
+        builder = builder.header(hyper::header::SEC_WEBSOCKET_PROTOCOL, protocol);
+    }
+    builder
         .body(Empty::new().map_err(Into::into).boxed_unsync())
-        .expect("failed to build response");
-    let on_upgrade = hyper::upgrade::on(req);
-    Ok((resp, on_upgrade))
+        .expect("failed to build response")
+}
+
+/// 读取客户端在 `Sec-WebSocket-Protocol` 中声明的子协议（按声明顺序）
+pub fn requested_protocols<B>(request: &hyper::Request<B>) -> Vec<String> {
+    header_values(request.headers(), hyper::header::SEC_WEBSOCKET_PROTOCOL)
+}
+
+/// 读取客户端在 `Sec-WebSocket-Extensions` 中声明的扩展名（不含参数）
+pub fn requested_extensions<B>(request: &hyper::Request<B>) -> Vec<String> {
+    header_values(request.headers(), hyper::header::SEC_WEBSOCKET_EXTENSIONS)
+        .into_iter()
+        .filter_map(|ext| ext.split(';').next().map(|name| name.trim().to_string()))
+        .collect()
+}
+
+/// 按客户端的优先顺序选出服务端也支持的第一个子协议
+pub fn select_protocol<B>(request: &hyper::Request<B>, supported: &[String]) -> Option<String> {
+    requested_protocols(request)
+        .into_iter()
+        .find(|p| supported.iter().any(|s| s == p))
+}
+
+fn header_values(
+    headers: &hyper::HeaderMap,
+    header: impl hyper::header::AsHeaderName,
+) -> Vec<String> {
+    headers
+        .get_all(header)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
 }
 
 /// 判断请求是否为 WebSocket 升级请求
@@ -0,0 +1,129 @@
+//! 提取器风格的 WebSocket 升级
+//!
+//! ```rust,ignore
+//! #[get("/ws")]
+//! async fn ws(upgrade: WsUpgrade, State(state): State<AppState>) -> impl IntoResponse {
+//!     upgrade.protocols(["chat"]).on(|mut socket| async move {
+//!         while let Some(Ok(msg)) = socket.recv().await {
+//!             // ...
+//!         }
+//!     })
+//! }
+//! ```
+
+use crate::error::AppError;
+use crate::extractor::from_request::{FRPFut, FromRequestParts};
+use crate::handler::Resp;
+use crate::http::response::into_response::IntoResponse;
+use crate::ws::server::{WsOptions, WsSocket, spawn_upgraded};
+use crate::ws::toolkit::switching_protocols;
+use hyper::header::{self, HeaderValue};
+use hyper::http::request::Parts;
+use hyper::upgrade::OnUpgrade;
+use std::sync::Arc;
+use tungstenite::protocol::WebSocketConfig;
+
+/// WebSocket 升级提取器，非升级请求返回 400
+///
+/// 调用 [`WsUpgrade::on`] 返回 101 响应，并在升级完成后运行传入的任务。
+pub struct WsUpgrade {
+    key: HeaderValue,
+    on_upgrade: OnUpgrade,
+    requested: Vec<String>,
+    options: WsOptions,
+}
+
+impl<S> FromRequestParts<S> for WsUpgrade {
+    fn from_request_parts(req: &mut Parts, _state: Arc<S>) -> FRPFut<'_, Self> {
+        let result = upgrade_from_parts(req);
+        Box::pin(async move { result })
+    }
+}
+
+fn upgrade_from_parts(req: &mut Parts) -> Result<WsUpgrade, AppError> {
+    // 复用基于 Request 的握手工具函数
+    let mut request = hyper::Request::new(());
+    *request.headers_mut() = req.headers.clone();
+    if !crate::ws::toolkit::is_upgrade_request(&request) {
+        return Err(AppError::BadRequest(
+            "Expected a WebSocket upgrade request".to_string(),
+        ));
+    }
+    if req
+        .headers
+        .get(header::SEC_WEBSOCKET_VERSION)
+        .map(|v| v.as_bytes())
+        != Some(b"13")
+    {
+        return Err(AppError::BadRequest(
+            "Unsupported Sec-WebSocket-Version".to_string(),
+        ));
+    }
+    let key = req
+        .headers
+        .get(header::SEC_WEBSOCKET_KEY)
+        .cloned()
+        .ok_or_else(|| AppError::BadRequest("Missing Sec-WebSocket-Key".to_string()))?;
+    let on_upgrade = req
+        .extensions
+        .remove::<OnUpgrade>()
+        .ok_or_else(|| AppError::BadRequest("Connection does not support upgrades".to_string()))?;
+    Ok(WsUpgrade {
+        key,
+        on_upgrade,
+        requested: crate::ws::toolkit::requested_protocols(&request),
+        options: WsOptions::default(),
+    })
+}
+
+impl WsUpgrade {
+    /// 客户端在 `Sec-WebSocket-Protocol` 中声明的子协议
+    pub fn requested_protocols(&self) -> &[String] {
+        &self.requested
+    }
+
+    /// 声明服务端支持的子协议，按客户端的优先顺序协商
+    pub fn protocols<I, P>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<String>,
+    {
+        self.options = self.options.protocols(protocols);
+        self
+    }
+
+    /// 要求必须协商出子协议，否则 [`on`](Self::on) 返回 400
+    pub fn require_protocol(mut self, require: bool) -> Self {
+        self.options = self.options.require_protocol(require);
+        self
+    }
+
+    /// 设置底层连接配置
+    pub fn config(mut self, config: WebSocketConfig) -> Self {
+        self.options = self.options.config(config);
+        self
+    }
+
+    /// 完成握手：返回 101 响应，升级成功后在后台运行 `task`
+    pub fn on<F, Fut>(self, task: F) -> Resp
+    where
+        F: FnOnce(WsSocket) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let protocol = self
+            .requested
+            .iter()
+            .find(|p| self.options.protocols.contains(p))
+            .cloned();
+        if protocol.is_none() && self.options.require_protocol {
+            return AppError::BadRequest(format!(
+                "No supported WebSocket subprotocol, expected one of {:?}",
+                self.options.protocols
+            ))
+            .into_response();
+        }
+        let resp = switching_protocols(self.key.as_bytes(), protocol.as_deref());
+        spawn_upgraded(self.on_upgrade, self.options.config, protocol, task);
+        resp
+    }
+}
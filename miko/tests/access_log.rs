@@ -0,0 +1,36 @@
+use miko::app::config::AccessLogSettings;
+use miko::middleware::access_log::{AccessLogLayer, AccessLogWriter};
+use miko::router::Router;
+use std::time::Duration;
+
+#[tokio::test]
+async fn access_log_writes_and_rotates_by_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("access.log");
+    let writer = AccessLogWriter::file(AccessLogSettings {
+        path: path.to_string_lossy().to_string(),
+        max_size: Some(120),
+        flush_interval_ms: 10,
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut router = Router::new();
+    router.get("/hello", || async { "world" });
+    router.with_layer(AccessLogLayer::new(writer));
+    let client = router.test_client();
+    for _ in 0..3 {
+        client.get("/hello").send().await.assert_ok();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let current = std::fs::read_to_string(&path).unwrap();
+    assert!(current.contains("\"GET /hello HTTP/1.1\" 200 5"));
+    let rotated = std::fs::read_dir(dir.path())
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with("access.log."))
+        .count();
+    assert!(rotated >= 1, "expected at least one rotated file");
+}
@@ -0,0 +1,120 @@
+use hyper::StatusCode;
+use hyper::header::{AUTHORIZATION, HeaderName, HeaderValue};
+use miko::ext::auth::{
+    self, ApiKeyAuth, BasicAuth, Claims, Identity, JwtAuth, Principal, RequireAuthLayer,
+};
+use miko::macros::*;
+use miko::router::Router;
+use std::sync::Once;
+
+const SECRET: &[u8] = b"test-secret";
+
+#[get("/auth/me")]
+async fn me(Claims(user): Claims<Identity>) -> String {
+    user.sub
+}
+
+#[delete("/auth/users/{id}")]
+#[guard(role = "admin")]
+async fn remove_user(#[path] id: u32) -> String {
+    format!("removed {}", id)
+}
+
+fn install() -> JwtAuth<Identity> {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| auth::install(JwtAuth::<Identity>::hs256(SECRET)));
+    JwtAuth::hs256(SECRET)
+}
+
+fn bearer(token: &str) -> HeaderValue {
+    HeaderValue::from_str(&format!("Bearer {}", token)).unwrap()
+}
+
+#[tokio::test]
+async fn jwt_claims_and_role_guard() {
+    let jwt = install();
+    let exp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        + 600;
+    let user = jwt.sign(&Identity::new("alice").expires_at(exp)).unwrap();
+    let admin = jwt
+        .sign(&Identity::new("root").role("admin").expires_at(exp))
+        .unwrap();
+    let client = miko::auto::collect_global_router().test_client();
+
+    client
+        .get("/auth/me")
+        .header(AUTHORIZATION, bearer(&user))
+        .send()
+        .await
+        .assert_text("alice");
+    client
+        .get("/auth/me")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    client
+        .get("/auth/me")
+        .header(AUTHORIZATION, bearer("not-a-token"))
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    client
+        .delete("/auth/users/7")
+        .header(AUTHORIZATION, bearer(&user))
+        .send()
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+    client
+        .delete("/auth/users/7")
+        .header(AUTHORIZATION, bearer(&admin))
+        .send()
+        .await
+        .assert_text("removed 7");
+}
+
+#[tokio::test]
+async fn require_auth_layer_with_api_key_and_basic() {
+    let mut api = Router::new();
+    api.get("/whoami", |p: Principal| async move { p.subject })
+        .with_layer(RequireAuthLayer::new(ApiKeyAuth::header(
+            "x-api-key",
+            |key| (key == "k1").then(|| Identity::new("service")),
+        )));
+    let mut basic = Router::new();
+    basic
+        .get("/whoami", |Claims(user): Claims<Identity>| async move {
+            user.sub
+        })
+        .with_layer(RequireAuthLayer::new(BasicAuth::new(|user, password| {
+            (password == "pw").then(|| Identity::new(user))
+        })));
+    let mut router = Router::new();
+    router.nest("/api", api).nest("/basic", basic);
+    let client = router.test_client();
+
+    let key = HeaderName::from_static("x-api-key");
+    client
+        .get("/api/whoami")
+        .header(key.clone(), HeaderValue::from_static("k1"))
+        .send()
+        .await
+        .assert_text("service");
+    client
+        .get("/api/whoami")
+        .header(key, HeaderValue::from_static("nope"))
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    // bob:pw
+    client
+        .get("/basic/whoami")
+        .header(AUTHORIZATION, HeaderValue::from_static("Basic Ym9iOnB3"))
+        .send()
+        .await
+        .assert_text("bob");
+}
@@ -2,6 +2,10 @@ use hyper::StatusCode;
 use serde_json::json;
 
 #[path = "../examples/basic.rs"]
+#[expect(
+    dead_code,
+    reason = "the example's main is only used when it runs as an example"
+)]
 mod basic_example;
 
 #[tokio::test]
@@ -0,0 +1,111 @@
+use miko::extractor::Json;
+use miko::macros::*;
+use miko::test::json_path;
+use miko::{OpenApi, ToSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+struct Item {
+    id: u32,
+    name: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+struct ItemPage {
+    items: Vec<Item>,
+    page: u32,
+}
+
+#[get("/bdd/items")]
+#[u_response(status = 200, description = "ok", body = ItemPage)]
+async fn list_items(#[query] page: u32, #[header] authorization: String) -> Json<ItemPage> {
+    assert_eq!(authorization, "Bearer token");
+    Json(ItemPage {
+        items: vec![
+            Item {
+                id: 1,
+                name: "a".into(),
+            },
+            Item {
+                id: 2,
+                name: "b".into(),
+            },
+        ],
+        page,
+    })
+}
+
+#[get("/bdd/broken")]
+#[u_response(status = 200, description = "ok", body = ItemPage)]
+async fn broken() -> Json<serde_json::Value> {
+    Json(json!({ "items": [{ "id": "one" }], "page": 1 }))
+}
+
+#[derive(OpenApi)]
+#[openapi(info(title = "Items", version = "1.0.0"))]
+struct ApiDoc;
+
+#[tokio::test]
+async fn given_when_then_checks_json_path_and_schema() {
+    let doc = miko::openapi::auto_doc_with(ApiDoc::openapi());
+    let client = miko::auto::collect_global_router().test_client();
+
+    let resp = client
+        .given()
+        .header("authorization", "Bearer token")
+        .query("page", 2)
+        .when()
+        .get("/bdd/items")
+        .then()
+        .status(200)
+        .header("content-type", "application/json")
+        .json_path("$.page", 2)
+        .json_path("$.items[0].id", 1)
+        .json_path("$.items[-1].name", "b")
+        .json_path("$..name", ["a", "b"])
+        .json_path_exists("$.items[1]")
+        .matches_openapi(&doc)
+        .await;
+    assert_eq!(resp.json::<ItemPage>().items.len(), 2);
+
+    let failure = tokio::spawn(async move {
+        client
+            .when()
+            .get("/bdd/broken")
+            .then()
+            .status(201)
+            .json_path("$.missing", 1)
+            .matches_openapi(&doc)
+            .await;
+    })
+    .await
+    .unwrap_err()
+    .into_panic();
+    let message = failure.downcast_ref::<String>().unwrap();
+    assert!(message.contains("failed 4 expectation(s)"), "{}", message);
+    assert!(message.contains("expected status 201 Created, got 200 OK"));
+    assert!(message.contains("$.missing not found"));
+    assert!(message.contains("$.items[0].id: expected integer, got string"));
+    assert!(message.contains("$.items[0]: missing required property `name`"));
+}
+
+#[test]
+fn json_path_selects_nodes() {
+    let value = json!({ "a": { "b": [1, 2, { "c": 3 }] }, "d e": true });
+    assert_eq!(
+        json_path::query(&value, "$.a.b[1]").unwrap(),
+        Some(json!(2))
+    );
+    assert_eq!(
+        json_path::query(&value, "$['d e']").unwrap(),
+        Some(json!(true))
+    );
+    assert_eq!(
+        json_path::query(&value, "$.a.b[*]").unwrap(),
+        Some(json!([1, 2, { "c": 3 }]))
+    );
+    assert_eq!(json_path::query(&value, "$..c").unwrap(), Some(json!([3])));
+    assert_eq!(json_path::query(&value, "$.a.x").unwrap(), None);
+    assert!(json_path::query(&value, "a.b").is_err());
+}
@@ -0,0 +1,13 @@
+use miko::ext::bench::{bench_service, drive, scenarios};
+
+#[tokio::test]
+async fn bench_scenarios_succeed() {
+    let svc = bench_service();
+    for scenario in scenarios() {
+        assert!(
+            drive(&svc, &scenario).await.is_success(),
+            "scenario {} failed",
+            scenario.name
+        );
+    }
+}
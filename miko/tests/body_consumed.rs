@@ -0,0 +1,54 @@
+use hyper::StatusCode;
+use miko::AppResult;
+use miko::extractor::Json;
+use miko::extractor::body_consumed::{restore_body, take_body};
+use miko::handler::{Req, Resp};
+use miko::http_body_util::BodyExt;
+use miko::middleware::{Next, middleware_from_fn};
+use miko::router::Router;
+
+async fn drain(mut req: Req, next: Next) -> AppResult<Resp> {
+    let body = take_body(&mut req, "drain middleware")?;
+    let _ = body.collect().await;
+    next.run(req).await
+}
+
+async fn peek(mut req: Req, next: Next) -> AppResult<Resp> {
+    let body = take_body(&mut req, "peek middleware")?;
+    let bytes = body.collect().await?.to_bytes();
+    restore_body(&mut req, bytes);
+    next.run(req).await
+}
+
+#[tokio::test]
+async fn reading_a_consumed_body_names_the_consumer() {
+    let mut router = Router::new();
+    router.post(
+        "/",
+        |Json(v): Json<Vec<u32>>| async move { v.len().to_string() },
+    );
+    router.with_layer(middleware_from_fn(drain));
+    let client = router.test_client();
+
+    let resp = client.post("/").json(&vec![1, 2, 3]).send().await;
+    resp.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(
+        resp.text()
+            .contains("request body already consumed by drain middleware")
+    );
+}
+
+#[tokio::test]
+async fn restored_body_can_be_extracted() {
+    let mut router = Router::new();
+    router.post("/", |body: String| async move { body });
+    router.with_layer(middleware_from_fn(peek));
+
+    router
+        .test_client()
+        .post("/")
+        .text("hello")
+        .send()
+        .await
+        .assert_text("hello");
+}
@@ -0,0 +1,69 @@
+use hyper::StatusCode;
+use miko::extractor::Json;
+use miko::extractor::body_limit::parse_size;
+use miko::macros::*;
+use miko::middleware::body_limit::BodyLimitLayer;
+use miko::router::Router;
+
+#[post("/limited/echo")]
+async fn limited_echo(#[body(str, limit = "16B")] body: String) -> String {
+    body
+}
+
+#[tokio::test]
+async fn global_limit_rejects_oversized_bodies() {
+    let mut router = Router::new();
+    router
+        .post("/text", |body: String| async move { body })
+        .post("/json", |Json(v): Json<Vec<u32>>| async move {
+            v.len().to_string()
+        });
+    router.with_layer(BodyLimitLayer::new(8));
+    let client = router.test_client();
+
+    client
+        .post("/text")
+        .text("short")
+        .send()
+        .await
+        .assert_text("short");
+    client
+        .post("/text")
+        .text("definitely too long")
+        .send()
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+    client
+        .post("/json")
+        .json(&vec![1, 2, 3, 4, 5, 6])
+        .send()
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn route_limit_from_body_attribute() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    client
+        .post("/limited/echo")
+        .text("fits")
+        .send()
+        .await
+        .assert_text("fits");
+    client
+        .post("/limited/echo")
+        .text("this body is longer than sixteen bytes")
+        .send()
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[test]
+fn parses_size_units() {
+    assert_eq!(parse_size("512"), Some(512));
+    assert_eq!(parse_size("2KB"), Some(2048));
+    assert_eq!(parse_size("1.5 MB"), Some(1536 * 1024));
+    assert_eq!(parse_size("1GiB"), Some(1 << 30));
+    assert_eq!(parse_size("lots"), None);
+}
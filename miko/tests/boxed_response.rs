@@ -0,0 +1,33 @@
+use hyper::StatusCode;
+use miko::extractor::{Json, Path};
+use miko::http::response::into_response::{Html, Redirect};
+use miko::router::Router;
+use miko::{AppResult, BoxedResponse};
+
+async fn page(kind: &str) -> AppResult<BoxedResponse> {
+    Ok(match kind {
+        "html" => Box::new(Html("<h1>miko</h1>".into())),
+        "json" => Box::new(Json(vec![1, 2, 3])),
+        "old" => Box::new(Redirect::see_other("/pages/html")),
+        _ => Box::new((StatusCode::NOT_FOUND, "no such page")),
+    })
+}
+
+#[tokio::test]
+async fn branches_return_different_response_types() {
+    let mut router = Router::new();
+    router.get("/pages/{kind}", |Path(kind): Path<String>| async move {
+        page(&kind).await
+    });
+    let client = router.test_client();
+
+    let resp = client.get("/pages/html").send().await;
+    resp.assert_text("<h1>miko</h1>");
+    let resp = client.get("/pages/json").send().await;
+    resp.assert_json(vec![1, 2, 3]);
+    let resp = client.get("/pages/old").send().await;
+    resp.assert_status(StatusCode::SEE_OTHER);
+    let resp = client.get("/pages/missing").send().await;
+    resp.assert_status(StatusCode::NOT_FOUND);
+    resp.assert_text("no such page");
+}
@@ -0,0 +1,176 @@
+use bytes::Bytes;
+use futures::TryStreamExt;
+use http_body::Frame;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::HeaderMap;
+use miko::ext::cache::{Cache, CacheLayer, MemoryStore};
+use miko::handler::Resp;
+use miko::macros::*;
+use miko::router::Router;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[get("/cached/products")]
+#[cached(ttl = 60)]
+async fn products(#[query] page: u32) -> String {
+    let n = CALLS.fetch_add(1, Ordering::SeqCst);
+    format!("page {} call {}", page, n)
+}
+
+#[tokio::test]
+async fn cached_route_serves_repeated_requests_from_cache() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    let first = client.get("/cached/products?page=1").send().await;
+    assert_eq!(first.text(), "page 1 call 0");
+    assert_eq!(first.headers["x-cache"], "MISS");
+
+    let second = client.get("/cached/products?page=1").send().await;
+    assert_eq!(second.text(), "page 1 call 0");
+    assert_eq!(second.headers["x-cache"], "HIT");
+    assert_eq!(
+        second.headers["content-type"],
+        first.headers["content-type"]
+    );
+
+    let other = client.get("/cached/products?page=2").send().await;
+    assert_eq!(other.text(), "page 2 call 1");
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cache_get_set_expire_and_delete() {
+    let cache = Cache::new(MemoryStore::new()).prefix("test:");
+    cache.set("user", &vec![1, 2, 3]).await.unwrap();
+    assert_eq!(
+        cache.get::<Vec<i32>>("user").await.unwrap(),
+        Some(vec![1, 2, 3])
+    );
+
+    assert!(
+        cache
+            .expire("user", Duration::from_millis(20))
+            .await
+            .unwrap()
+    );
+    assert!(
+        !cache
+            .expire("missing", Duration::from_secs(1))
+            .await
+            .unwrap()
+    );
+    tokio::time::sleep(Duration::from_millis(40)).await;
+    assert_eq!(cache.get::<Vec<i32>>("user").await.unwrap(), None);
+
+    cache
+        .set_ex("token", "abc", Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert!(cache.delete("token").await.unwrap());
+    assert!(!cache.delete("token").await.unwrap());
+}
+
+fn counting_router(layer: CacheLayer) -> (Router, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let mut router = Router::new();
+    router
+        .get("/me", {
+            let counter = counter.clone();
+            move || {
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                async move { format!("call {}", n) }
+            }
+        })
+        .get("/lang", {
+            let counter = counter.clone();
+            move |headers: HeaderMap| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let lang = headers
+                    .get("accept-language")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("en")
+                    .to_string();
+                let mut vary = HeaderMap::new();
+                vary.insert("vary", "Accept-Language".parse().unwrap());
+                async move { (vary, lang) }
+            }
+        })
+        .get("/stream", move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                let chunks = futures::stream::iter([Ok::<_, std::io::Error>(Bytes::from("chunk"))]);
+                Resp::new(BodyExt::boxed_unsync(BodyExt::map_err(
+                    StreamBody::new(chunks.map_ok(Frame::data)),
+                    Into::into,
+                )))
+            }
+        })
+        .with_layer(layer.cache(Cache::new(MemoryStore::new())));
+    (router, calls)
+}
+
+#[tokio::test]
+async fn credentialed_requests_bypass_the_cache() {
+    let (router, calls) = counting_router(CacheLayer::new(Duration::from_secs(60)));
+    let client = router.test_client();
+
+    let resp = client
+        .get("/me")
+        .header("authorization", "Bearer a")
+        .send()
+        .await;
+    assert_eq!(resp.text(), "call 0");
+    assert!(resp.headers.get("x-cache").is_none());
+    // 匿名请求不会拿到带凭据请求的响应
+    assert_eq!(client.get("/me").send().await.text(), "call 1");
+    let resp = client.get("/me").header("cookie", "sid=b").send().await;
+    assert_eq!(resp.text(), "call 2");
+    assert!(resp.headers.get("x-cache").is_none());
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    let (router, _) =
+        counting_router(CacheLayer::new(Duration::from_secs(60)).allow_credentials(true));
+    let client = router.test_client();
+    client
+        .get("/me")
+        .header("authorization", "Bearer a")
+        .send()
+        .await;
+    let resp = client
+        .get("/me")
+        .header("authorization", "Bearer a")
+        .send()
+        .await;
+    assert_eq!(resp.headers["x-cache"], "HIT");
+}
+
+#[tokio::test]
+async fn vary_headers_are_part_of_the_key() {
+    let (router, calls) = counting_router(CacheLayer::new(Duration::from_secs(60)));
+    let client = router.test_client();
+    let get = |lang: &'static str| client.get("/lang").header("accept-language", lang).send();
+
+    assert_eq!(get("fr").await.text(), "fr");
+    assert_eq!(get("de").await.text(), "de");
+    let resp = get("fr").await;
+    assert_eq!(resp.text(), "fr");
+    assert_eq!(resp.headers["x-cache"], "HIT");
+    assert_eq!(get("de").await.headers["x-cache"], "HIT");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn bodies_without_known_length_are_not_cached() {
+    let (router, calls) = counting_router(CacheLayer::new(Duration::from_secs(60)));
+    let client = router.test_client();
+    for _ in 0..2 {
+        let resp = client.get("/stream").send().await;
+        assert_eq!(resp.text(), "chunk");
+        assert!(resp.headers.get("x-cache").is_none());
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
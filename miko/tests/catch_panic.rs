@@ -0,0 +1,66 @@
+use hyper::StatusCode;
+use miko::http::response::sse::SseClientDisconnected;
+use miko::middleware::catch_panic::CatchPanicLayer;
+use miko::router::Router;
+use miko::{AppError, IntoResponse};
+use std::sync::{Arc, Mutex};
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router
+        .get("/ok", || async { "ok" })
+        .get("/boom", || async {
+            panic!("boom");
+            #[allow(unreachable_code)]
+            ""
+        })
+        .get("/gone", || async {
+            std::panic::panic_any(SseClientDisconnected);
+            #[allow(unreachable_code)]
+            ""
+        });
+    router
+}
+
+#[tokio::test]
+async fn panics_become_internal_server_errors() {
+    let mut router = router();
+    router.with_catch_panic();
+    let client = router.test_client();
+
+    client.get("/ok").send().await.assert_text("ok");
+
+    let resp = client.get("/boom").send().await;
+    resp.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    let body: serde_json::Value = resp.json();
+    assert_eq!(body["error"], "INTERNAL_SERVER_ERROR");
+    assert_eq!(body["message"], "Server panicked: boom");
+}
+
+#[tokio::test]
+async fn custom_responder_and_reporter() {
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let log = reported.clone();
+    let mut router = router();
+    router.with_layer(
+        CatchPanicLayer::new()
+            .on_panic(move |panic| {
+                log.lock()
+                    .unwrap()
+                    .push(format!("{} {}", panic.uri().path(), panic.message()))
+            })
+            .responder(|panic| {
+                AppError::ServiceUnavailable(format!("oops: {}", panic.message())).into_response()
+            }),
+    );
+    let client = router.test_client();
+
+    let resp = client.get("/boom").send().await;
+    resp.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    let body: serde_json::Value = resp.json();
+    assert_eq!(body["message"], "oops: boom");
+
+    // SSE 断连信号不上报
+    client.get("/gone").send().await;
+    assert_eq!(*reported.lock().unwrap(), ["/boom boom"]);
+}
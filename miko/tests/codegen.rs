@@ -0,0 +1,81 @@
+use miko::ToSchema;
+use miko::codegen::{ClientGenerator, write_if_changed};
+use miko::extractor::Json;
+use miko::macros::*;
+use miko::router::Router;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct Pet {
+    pet_name: String,
+    age: Option<u32>,
+    kind: Kind,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+enum Kind {
+    Cat,
+    Dog,
+}
+
+/// 获取宠物
+#[get("/pets/{id}")]
+#[u_response(status = 200, description = "ok", body = Pet)]
+async fn get_pet(#[path] id: u32, #[query] verbose: Option<bool>) -> Json<Pet> {
+    let _ = verbose;
+    Json(Pet {
+        pet_name: id.to_string(),
+        age: None,
+        kind: Kind::Cat,
+        tags: vec![],
+    })
+}
+
+/// 创建宠物
+#[post("/pets")]
+async fn create_pet(#[body] pet: Pet) -> Json<Pet> {
+    Json(pet)
+}
+
+#[test]
+fn generates_rust_client() {
+    let code = ClientGenerator::new(&miko::openapi::auto_doc())
+        .client_name("PetsClient")
+        .rust();
+    assert!(code.contains("pub struct PetsClient {"));
+    assert!(code.contains("    #[serde(rename = \"petName\")]\n    pub pet_name: String,"));
+    assert!(code.contains("pub age: Option<u32>,"));
+    assert!(code.contains("pub tags: Vec<String>,"));
+    assert!(code.contains("pub enum Kind {\n    Cat,\n    Dog,\n}"));
+    assert!(code.contains(
+        "pub async fn get_pet(&self, id: u32, verbose: Option<bool>) -> Result<Pet, reqwest::Error>"
+    ));
+    assert!(
+        code.contains("let url = format!(\"{}/pets/{}\", self.base_url, encode(&param(&id)));")
+    );
+    assert!(
+        code.contains("pub async fn create_pet(&self, body: &Pet) -> Result<(), reqwest::Error>")
+    );
+}
+
+#[tokio::test]
+async fn generates_and_serves_typescript_client() {
+    let code = ClientGenerator::new(&miko::openapi::auto_doc()).typescript();
+    assert!(code.contains("export interface Pet {"));
+    assert!(code.contains("  age?: number | null;"));
+    assert!(code.contains("export type Kind = \"Cat\" | \"Dog\";"));
+    assert!(code.contains("  async getPet(id: number, verbose?: boolean): Promise<Pet> {"));
+    assert!(code.contains("`/pets/${encodeURIComponent(String(id))}`, { verbose: verbose }"));
+
+    let mut router = Router::new();
+    router.serve_typescript_client("/client.ts");
+    let served = router.test_client().get("/client.ts").send().await.text();
+    assert_eq!(served, code);
+
+    let path = std::env::temp_dir().join(format!("miko-codegen-{}/api.ts", std::process::id()));
+    assert!(write_if_changed(&path, &code).unwrap());
+    assert!(!write_if_changed(&path, &code).unwrap());
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
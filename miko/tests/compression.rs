@@ -0,0 +1,100 @@
+use bytes::Bytes;
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use http_body_util::{BodyExt, Full};
+use hyper::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE, HeaderValue};
+use hyper::{Request, StatusCode};
+use miko::middleware::compression::CompressionLayer;
+use miko::router::Router;
+use std::io::{Read, Write};
+use tower::ServiceExt;
+
+fn long_text() -> String {
+    "miko compresses responses. ".repeat(100)
+}
+
+#[tokio::test]
+async fn compresses_responses_by_accept_encoding() {
+    let mut router = Router::new();
+    router
+        .get("/text", || async { long_text() })
+        .get("/short", || async { "tiny" })
+        .with_compression();
+    let client = router.test_client();
+
+    let resp = client
+        .get("/text")
+        .header(ACCEPT_ENCODING, HeaderValue::from_static("gzip"))
+        .send()
+        .await;
+    resp.assert_header(CONTENT_ENCODING, "gzip");
+    let mut decoded = String::new();
+    GzDecoder::new(&resp.bytes()[..])
+        .read_to_string(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, long_text());
+
+    let resp = client
+        .get("/short")
+        .header(ACCEPT_ENCODING, HeaderValue::from_static("gzip"))
+        .send()
+        .await;
+    assert!(resp.headers().get(CONTENT_ENCODING).is_none());
+    resp.assert_text("tiny");
+
+    client.get("/text").send().await.assert_text(&long_text());
+}
+
+#[tokio::test]
+async fn content_type_filter_limits_compression() {
+    let mut router = Router::new();
+    router
+        .get("/text", || async { long_text() })
+        .with_layer(CompressionLayer::new().content_types(["application/json"]));
+    let resp = router
+        .test_client()
+        .get("/text")
+        .header(ACCEPT_ENCODING, HeaderValue::from_static("br"))
+        .send()
+        .await;
+    assert!(resp.headers().get(CONTENT_ENCODING).is_none());
+    resp.assert_text(&long_text());
+}
+
+#[tokio::test]
+async fn decompresses_request_bodies() {
+    let mut router = Router::new();
+    router
+        .post("/echo", |body: String| async move { body })
+        .with_compression();
+    let svc = router.into_tower_service();
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(b"hello gzip").unwrap();
+    let gzipped = encoder.finish().unwrap();
+    let req = Request::post("/echo")
+        .header(CONTENT_ENCODING, "gzip")
+        .header(CONTENT_TYPE, "text/plain")
+        .body(
+            Full::new(Bytes::from(gzipped))
+                .map_err(Into::into)
+                .boxed_unsync(),
+        )
+        .unwrap();
+    let resp = svc.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "hello gzip");
+
+    let req = Request::post("/echo")
+        .header(CONTENT_ENCODING, "compress")
+        .body(
+            Full::new(Bytes::from("x"))
+                .map_err(Into::into)
+                .boxed_unsync(),
+        )
+        .unwrap();
+    let resp = svc.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
@@ -0,0 +1,60 @@
+use miko::AppResult;
+use miko::auto::init_container;
+use miko::ext::compute::{ComputePool, compute};
+use miko::macros::*;
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[get("/compute/square/{n}")]
+async fn square(#[dep] pool: Arc<ComputePool>, #[path] n: u64) -> AppResult<String> {
+    let squared = pool.compute(move || n * n).await?;
+    Ok(squared.to_string())
+}
+
+async fn wait_until(pool: &ComputePool, f: impl Fn(&miko::ext::compute::ComputeStats) -> bool) {
+    for _ in 0..200 {
+        if f(&pool.stats()) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    panic!("unexpected stats: {:?}", pool.stats());
+}
+
+#[tokio::test]
+async fn pool_limits_parallelism_and_reports_queue_depth() {
+    let pool = ComputePool::new(1);
+    let (tx, rx) = mpsc::channel::<()>();
+
+    let first = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.compute(move || rx.recv().is_ok()).await }
+    });
+    wait_until(&pool, |s| s.running == 1).await;
+
+    let second = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.compute(|| 2).await }
+    });
+    wait_until(&pool, |s| s.queued == 1).await;
+
+    tx.send(()).unwrap();
+    assert!(first.await.unwrap().unwrap());
+    assert_eq!(second.await.unwrap().unwrap(), 2);
+
+    let stats = pool.stats();
+    assert_eq!((stats.queued, stats.running, stats.completed), (0, 0, 2));
+
+    // panic 转换为错误而不是拖垮调用方
+    assert!(pool.compute(|| panic!("boom")).await.is_err());
+    assert_eq!(compute(|| 21 * 2).await.unwrap(), 42);
+}
+
+#[tokio::test]
+async fn pool_is_injectable() {
+    init_container().await;
+    let client = miko::auto::collect_global_router().test_client();
+    let resp = client.get("/compute/square/12").send().await;
+    resp.assert_text("144");
+    assert!(ComputePool::global().stats().completed >= 1);
+}
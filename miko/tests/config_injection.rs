@@ -0,0 +1,74 @@
+use hyper::StatusCode;
+use miko::macros::*;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct DatabaseConfig {
+    url: String,
+    max_connections: u32,
+    pool: PoolConfig,
+    replicas: Vec<Replica>,
+}
+
+#[derive(Deserialize)]
+struct PoolConfig {
+    idle_timeout: u64,
+}
+
+#[derive(Deserialize)]
+struct Replica {
+    url: String,
+    weight: u32,
+}
+
+#[get("/cfg/db")]
+async fn db_info(#[config("database")] db: DatabaseConfig) -> String {
+    let replicas: Vec<String> = db
+        .replicas
+        .iter()
+        .map(|r| format!("{}*{}", r.url, r.weight))
+        .collect();
+    format!(
+        "{} {} {} [{}]",
+        db.url,
+        db.max_connections,
+        db.pool.idle_timeout,
+        replicas.join(",")
+    )
+}
+
+#[get("/cfg/optional")]
+async fn optional(
+    #[config("cache")] cache: Option<PoolConfig>,
+    #[config("cache.url:redis://localhost:6379")] url: String,
+) -> String {
+    format!("{} {}", cache.is_none(), url)
+}
+
+#[get("/cfg/missing")]
+async fn missing(#[config("cache")] cache: PoolConfig) -> String {
+    cache.idle_timeout.to_string()
+}
+
+#[tokio::test]
+async fn config_injects_structs_from_subtrees() {
+    // 配置在首次读取时从当前目录加载
+    std::env::set_current_dir(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/config"
+    ))
+    .unwrap();
+    let client = miko::auto::collect_global_router().test_client();
+
+    let resp = client.get("/cfg/db").send().await;
+    resp.assert_text(
+        "postgres://localhost/app 8 30 [postgres://replica-1/app*2,postgres://replica-2/app*1]",
+    );
+
+    let resp = client.get("/cfg/optional").send().await;
+    resp.assert_text("true redis://localhost:6379");
+
+    // 缺失的必填配置返回 500，而不是要求处理函数返回 Result
+    let resp = client.get("/cfg/missing").send().await;
+    resp.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+}
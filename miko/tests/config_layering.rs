@@ -0,0 +1,61 @@
+use miko::app::config::{Config, config_env, get_settings_value, override_setting};
+use miko::router::Router;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Settings {
+    app: AppSection,
+}
+
+#[derive(Deserialize)]
+struct AppSection {
+    name: String,
+    region: String,
+    replicas: u32,
+    debug: bool,
+}
+
+#[tokio::test]
+async fn sources_merge_in_precedence_order() {
+    let dir = std::env::temp_dir().join(format!("miko-config-layering-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("config.toml"),
+        "[app]\nname = \"base\"\nregion = \"us\"\nreplicas = 1\ndebug = false\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("config.staging.toml"),
+        "[app]\nregion = \"ap\"\nreplicas = 2\n",
+    )
+    .unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+    // SAFETY: 本测试文件只有这一个测试，设置时没有其他线程读取环境变量
+    unsafe {
+        std::env::set_var("MIKO_ENV", "staging");
+        std::env::set_var("MIKO__APP__REPLICAS", "3");
+    }
+    override_setting("app.debug", true).unwrap();
+
+    assert_eq!(config_env(), "staging");
+    assert_eq!(get_settings_value::<String>("app.name").unwrap(), "base");
+    assert_eq!(get_settings_value::<String>("app.region").unwrap(), "ap");
+    assert_eq!(get_settings_value::<u32>("app.replicas").unwrap(), 3);
+    assert!(get_settings_value::<bool>("app.debug").unwrap());
+
+    // 已加载后的覆盖立即生效，且优先于环境变量
+    override_setting("app.replicas", 5).unwrap();
+    assert_eq!(get_settings_value::<u32>("app.replicas").unwrap(), 5);
+
+    let mut router = Router::new();
+    router.get("/app", |Config(settings): Config<Settings>| async move {
+        let app = settings.app;
+        format!("{} {} {} {}", app.name, app.region, app.replicas, app.debug)
+    });
+    router
+        .test_client()
+        .get("/app")
+        .send()
+        .await
+        .assert_text("base ap 5 true");
+}
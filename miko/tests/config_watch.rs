@@ -0,0 +1,54 @@
+use miko::app::config::{get_settings_value, on_settings_change, subscribe_settings};
+use miko::app::watcher::ConfigWatcher;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn file_changes_reload_settings_and_notify_subscribers() {
+    let dir = std::env::temp_dir().join(format!("miko-config-watch-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("config.toml"),
+        "[feature]\nlimit = 1\nother = \"a\"\n",
+    )
+    .unwrap();
+    // 配置在首次读取时从当前目录加载
+    std::env::set_current_dir(&dir).unwrap();
+    assert_eq!(get_settings_value::<u32>("feature.limit").unwrap(), 1);
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let _task = on_settings_change::<u32, _>("feature.limit", move |limit| {
+        tx.send(limit).unwrap();
+    });
+    let _watcher = ConfigWatcher::start().unwrap();
+    // 给文件系统监听留出注册时间
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // 只改动其他键时不通知
+    let mut changes = subscribe_settings();
+    std::fs::write(
+        dir.join("config.toml"),
+        "[feature]\nlimit = 1\nother = \"b\"\n",
+    )
+    .unwrap();
+    tokio::time::timeout(Duration::from_secs(5), changes.changed())
+        .await
+        .expect("reload after edit")
+        .unwrap();
+    assert_eq!(get_settings_value::<String>("feature.other").unwrap(), "b");
+
+    std::fs::write(
+        dir.join("config.toml"),
+        "[feature]\nlimit = 5\nother = \"b\"\n",
+    )
+    .unwrap();
+    let limit = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("change notification")
+        .unwrap();
+    assert_eq!(limit, 5);
+    assert_eq!(get_settings_value::<u32>("feature.limit").unwrap(), 5);
+    assert!(rx.try_recv().is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
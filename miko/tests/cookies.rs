@@ -0,0 +1,98 @@
+use hyper::header::{COOKIE, HeaderValue, SET_COOKIE};
+use miko::extractor::CookieJar;
+use miko::extractor::cookies::{Cookie, Key, PrivateCookieJar, SignedCookieJar, set_cookie_key};
+use miko::router::Router;
+
+fn set_cookies(resp: &miko::test::test_response::TestResponse) -> Vec<String> {
+    resp.headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .map(|v| v.to_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn cookie_jar_reads_and_writes_cookies() {
+    let mut router = Router::new();
+    router
+        .get("/visit", |jar: CookieJar| async move {
+            let count: u32 = jar
+                .get("visits")
+                .and_then(|c| c.value().parse().ok())
+                .unwrap_or(0);
+            let jar = jar
+                .add(Cookie::new("visits", (count + 1).to_string()))
+                .add(Cookie::build(("theme", "dark")).path("/").http_only(true));
+            (jar, format!("visits: {}", count))
+        })
+        .get("/logout", |jar: CookieJar| async move {
+            jar.remove(Cookie::from("session"))
+        });
+    let client = router.test_client();
+
+    let resp = client
+        .get("/visit")
+        .header(COOKIE, HeaderValue::from_static("visits=2; other=x"))
+        .send()
+        .await;
+    resp.assert_text("visits: 2");
+    let cookies = set_cookies(&resp);
+    assert_eq!(cookies.len(), 2);
+    assert!(cookies.contains(&"visits=3".to_string()));
+    assert!(cookies.contains(&"theme=dark; HttpOnly; Path=/".to_string()));
+
+    let resp = client
+        .get("/logout")
+        .header(COOKIE, HeaderValue::from_static("session=abc"))
+        .send()
+        .await;
+    let cookies = set_cookies(&resp);
+    assert!(cookies[0].starts_with("session=;"));
+    assert!(cookies[0].contains("Max-Age=0"));
+}
+
+#[tokio::test]
+async fn signed_and_private_jars_reject_tampering() {
+    set_cookie_key(Key::generate());
+    let mut router = Router::new();
+    router
+        .get("/signed/set", |jar: SignedCookieJar| async move {
+            jar.add(Cookie::new("user", "alice"))
+        })
+        .get("/signed/get", |jar: SignedCookieJar| async move {
+            jar.get("user")
+                .map(|c| c.value().to_string())
+                .unwrap_or_default()
+        })
+        .get("/private/set", |jar: PrivateCookieJar| async move {
+            jar.add(Cookie::new("token", "secret"))
+        })
+        .get("/private/get", |jar: PrivateCookieJar| async move {
+            jar.get("token")
+                .map(|c| c.value().to_string())
+                .unwrap_or_default()
+        });
+    let client = router.test_client();
+
+    for (kind, plain) in [("signed", "alice"), ("private", "secret")] {
+        let resp = client.get(&format!("/{kind}/set")).send().await;
+        let cookie = set_cookies(&resp).remove(0);
+        let pair = cookie.split(';').next().unwrap().to_string();
+        assert_eq!(pair.contains(plain), kind == "signed");
+
+        let resp = client
+            .get(&format!("/{kind}/get"))
+            .header(COOKIE, HeaderValue::from_str(&pair).unwrap())
+            .send()
+            .await;
+        resp.assert_text(plain);
+
+        let tampered = format!("{}x", pair);
+        let resp = client
+            .get(&format!("/{kind}/get"))
+            .header(COOKIE, HeaderValue::from_str(&tampered).unwrap())
+            .send()
+            .await;
+        resp.assert_text("");
+    }
+}
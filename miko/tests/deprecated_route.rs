@@ -0,0 +1,58 @@
+use hyper::StatusCode;
+use miko::macros::*;
+use miko::middleware::deprecation::{DeprecationLayer, deprecated_route_hits};
+use miko::router::Router;
+
+#[get("/v1/users")]
+#[deprecated_route(sunset = "2025-12-31", link = "https://example.com/migrate")]
+async fn list_users_v1() -> &'static str {
+    "[]"
+}
+
+#[deprecated_route]
+mod legacy {
+    use miko::macros::*;
+
+    #[get("/legacy/ping")]
+    async fn ping() -> &'static str {
+        "pong"
+    }
+}
+
+#[tokio::test]
+async fn deprecated_routes_send_headers_and_count_calls() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    let resp = client.get("/v1/users").send().await;
+    resp.assert_text("[]");
+    resp.assert_header("deprecation", "true");
+    resp.assert_header("sunset", "Wed, 31 Dec 2025 23:59:59 GMT");
+    resp.assert_header("link", "<https://example.com/migrate>; rel=\"deprecation\"");
+
+    let resp = client.get("/legacy/ping").send().await;
+    resp.assert_header("deprecation", "true");
+    assert!(resp.headers().get("sunset").is_none());
+
+    client.get("/v1/users").send().await;
+    let hits = deprecated_route_hits();
+    assert_eq!(hits["/v1/users"], 2);
+    assert_eq!(hits["/legacy/ping"], 1);
+
+    let doc = serde_json::to_value(miko::openapi::auto_doc()).unwrap();
+    assert_eq!(doc["paths"]["/v1/users"]["get"]["deprecated"], true);
+}
+
+#[tokio::test]
+async fn layer_marks_error_responses() {
+    let mut router = Router::new();
+    router
+        .get("/old", || async {
+            Err::<(), _>(miko::AppError::NotFound("gone".into()))
+        })
+        .with_layer(DeprecationLayer::new("/old").sunset("Sat, 01 Nov 2025 00:00:00 GMT"));
+
+    let resp = router.test_client().get("/old").send().await;
+    resp.assert_status(StatusCode::NOT_FOUND);
+    resp.assert_header("deprecation", "true");
+    resp.assert_header("sunset", "Sat, 01 Nov 2025 00:00:00 GMT");
+}
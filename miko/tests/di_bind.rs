@@ -0,0 +1,60 @@
+use miko::auto::init_container;
+use miko::macros::*;
+use std::sync::Arc;
+
+trait Greeter: Send + Sync {
+    fn greet(&self, name: &str) -> String;
+}
+
+struct English;
+
+#[component(bind = dyn Greeter)]
+impl English {
+    async fn new() -> Self {
+        English
+    }
+}
+
+impl Greeter for English {
+    fn greet(&self, name: &str) -> String {
+        format!("hello {}", name)
+    }
+}
+
+struct Welcome {
+    greeter: Arc<dyn Greeter>,
+}
+
+#[component]
+impl Welcome {
+    async fn new(greeter: Arc<dyn Greeter>) -> Self {
+        Self { greeter }
+    }
+}
+
+#[get("/greet/{name}")]
+async fn greet(
+    #[path] name: String,
+    #[dep] greeter: Arc<dyn Greeter>,
+    #[dep] english: Arc<English>,
+    #[dep] welcome: Arc<Welcome>,
+) -> String {
+    // 单例绑定与具体类型共享同一个实例
+    assert!(std::ptr::addr_eq(
+        Arc::as_ptr(&greeter),
+        Arc::as_ptr(&english)
+    ));
+    format!(
+        "{}, {}",
+        greeter.greet(&name),
+        welcome.greeter.greet("again")
+    )
+}
+
+#[tokio::test]
+async fn trait_binding_resolves_concrete_component() {
+    init_container().await;
+    let client = miko::auto::collect_global_router().test_client();
+    let resp = client.get("/greet/miko").send().await;
+    assert_eq!(resp.text(), "hello miko, hello again");
+}
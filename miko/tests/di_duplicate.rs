@@ -0,0 +1,53 @@
+use miko::dependency_container::{DependencyIssue, LazyDependencyContainer};
+use miko::macros::*;
+
+struct Clock(&'static str);
+
+#[component]
+impl Clock {
+    async fn new() -> Self {
+        Clock("system")
+    }
+}
+
+#[component(constructor = "fixed")]
+impl Clock {
+    async fn fixed() -> Self {
+        Clock("fixed")
+    }
+}
+
+struct Store(&'static str);
+
+#[component]
+impl Store {
+    async fn new() -> Self {
+        Store("disk")
+    }
+}
+
+#[component(replace, constructor = "memory")]
+impl Store {
+    async fn memory() -> Self {
+        Store("memory")
+    }
+}
+
+#[tokio::test]
+async fn duplicates_fail_validation_and_replace_wins() {
+    let container = LazyDependencyContainer::new_();
+    let container = container.read().await;
+    let report = container.validate().unwrap_err();
+    assert_eq!(report.0.len(), 1, "{}", report);
+    match &report.0[0] {
+        DependencyIssue::Duplicate { component, sources } => {
+            assert!(component.contains("Clock"));
+            assert_eq!(sources.len(), 2);
+            assert!(sources.iter().all(|s| s.contains("di_duplicate.rs")));
+        }
+        other => panic!("unexpected issue: {}", other),
+    }
+
+    assert_eq!(container.get_::<Store>("___").await.0, "memory");
+    assert!(["system", "fixed"].contains(&container.get_::<Clock>("___").await.0));
+}
@@ -1,3 +1,4 @@
+use miko::auto::init_container;
 use miko::dependency_container::LazyDependencyContainer;
 use miko::macros::*;
 use std::sync::Arc;
@@ -5,6 +6,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 
 static TRANSIENT_CONSTRUCTS: AtomicUsize = AtomicUsize::new(0);
 static SINGLETON_CONSTRUCTS: AtomicUsize = AtomicUsize::new(0);
+static SCOPED_CONSTRUCTS: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Debug, PartialEq, Eq)]
 struct TransientProbe {
@@ -32,6 +34,23 @@ impl SingletonProbe {
     }
 }
 
+struct ScopedProbe {
+    id: usize,
+}
+
+#[component(lifetime = "scoped")]
+impl ScopedProbe {
+    async fn new() -> Self {
+        let id = SCOPED_CONSTRUCTS.fetch_add(1, Ordering::SeqCst) + 1;
+        Self { id }
+    }
+}
+
+#[get("/scoped")]
+async fn scoped(#[dep] a: Arc<ScopedProbe>, #[dep] b: Arc<ScopedProbe>) -> String {
+    format!("{} {}", a.id, Arc::ptr_eq(&a, &b))
+}
+
 #[tokio::test]
 async fn component_lifetimes_behave_as_configured() {
     TRANSIENT_CONSTRUCTS.store(0, Ordering::SeqCst);
@@ -50,3 +69,14 @@ async fn component_lifetimes_behave_as_configured() {
     assert_eq!(Arc::as_ptr(&singleton_a), Arc::as_ptr(&singleton_b));
     assert_eq!(SINGLETON_CONSTRUCTS.load(Ordering::SeqCst), 1);
 }
+
+#[tokio::test]
+async fn scoped_components_are_shared_within_a_request() {
+    init_container().await;
+    let client = miko::auto::collect_global_router().test_client();
+    let first = client.get("/scoped").send().await.text();
+    let second = client.get("/scoped").send().await.text();
+    assert!(first.ends_with(" true"));
+    assert!(second.ends_with(" true"));
+    assert_ne!(first, second);
+}
@@ -0,0 +1,61 @@
+use miko::auto::init_container;
+use miko::macros::*;
+use std::sync::Arc;
+
+struct Pool {
+    url: &'static str,
+}
+
+#[component(name = "primary", constructor = "primary")]
+impl Pool {
+    async fn primary() -> Self {
+        Self {
+            url: "db://primary",
+        }
+    }
+}
+
+#[component(name = "replica", constructor = "replica")]
+impl Pool {
+    async fn replica() -> Self {
+        Self {
+            url: "db://replica",
+        }
+    }
+}
+
+struct Repo {
+    write: Arc<Pool>,
+    read: Arc<Pool>,
+}
+
+#[component]
+impl Repo {
+    async fn new(
+        #[dep(name = "primary")] write: Arc<Pool>,
+        #[dep(name = "replica")] read: Arc<Pool>,
+    ) -> Self {
+        Self { write, read }
+    }
+}
+
+#[get("/pools")]
+async fn pools(
+    #[dep(name = "primary")] primary: Arc<Pool>,
+    #[dep(name = "replica")] replica: Arc<Pool>,
+    #[dep] repo: Arc<Repo>,
+) -> String {
+    assert!(Arc::ptr_eq(&primary, &repo.write));
+    format!("{} {} {}", primary.url, replica.url, repo.read.url)
+}
+
+#[tokio::test]
+async fn named_components_are_injected_by_name() {
+    init_container().await;
+    let client = miko::auto::collect_global_router().test_client();
+    client
+        .get("/pools")
+        .send()
+        .await
+        .assert_text("db://primary db://replica db://replica");
+}
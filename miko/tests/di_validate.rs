@@ -0,0 +1,75 @@
+use miko::dependency_container::{DependencyIssue, LazyDependencyContainer};
+use miko::macros::*;
+use std::sync::Arc;
+
+struct Ping(#[allow(dead_code)] Arc<Pong>);
+struct Pong(#[allow(dead_code)] Arc<Ping>);
+
+#[component]
+impl Ping {
+    async fn new(pong: Arc<Pong>) -> Self {
+        Ping(pong)
+    }
+}
+
+#[component]
+impl Pong {
+    async fn new(ping: Arc<Ping>) -> Self {
+        Pong(ping)
+    }
+}
+
+struct Unregistered;
+struct PerRequest;
+struct Cache(#[allow(dead_code)] Arc<PerRequest>);
+
+#[component(scoped)]
+impl PerRequest {
+    async fn new() -> Self {
+        PerRequest
+    }
+}
+
+#[component]
+impl Cache {
+    async fn new(req: Arc<PerRequest>) -> Self {
+        Cache(req)
+    }
+}
+
+#[get("/broken")]
+async fn broken(#[dep(name = "missing")] _dep: Arc<Unregistered>) {}
+
+#[tokio::test]
+async fn reports_all_problems_at_startup() {
+    let container = LazyDependencyContainer::new_();
+    let report = container.read().await.validate().unwrap_err();
+    let issues = &report.0;
+    assert_eq!(issues.len(), 3, "{}", report);
+
+    assert!(issues.iter().any(|i| matches!(
+        i,
+        DependencyIssue::Missing { required_by, dependency }
+            if required_by == "handler `broken`"
+                && dependency.contains("Unregistered") && dependency.contains("\"missing\"")
+    )));
+    assert!(issues.iter().any(|i| matches!(
+        i,
+        DependencyIssue::ScopedDependency { component, .. } if component.contains("Cache")
+    )));
+    let cycle = issues
+        .iter()
+        .find_map(|i| match i {
+            DependencyIssue::Cycle(path) => Some(path),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(cycle.len(), 3);
+    assert_eq!(cycle.first(), cycle.last());
+
+    let message = report.to_string();
+    assert!(message.starts_with("found 3 dependency problem(s):"));
+    assert!(message.contains(
+        "circular dependency: `di_validate::Ping` -> `di_validate::Pong` -> `di_validate::Ping`"
+    ));
+}
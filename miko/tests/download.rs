@@ -0,0 +1,64 @@
+use hyper::StatusCode;
+use hyper::header::{self, HeaderValue};
+use miko::ext::download::{RangeRequest, ResumableDownload};
+use miko::router::Router;
+use std::io::Cursor;
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router.route("GET,HEAD", "/file", |range: RangeRequest| async move {
+        ResumableDownload::new(Cursor::new(b"0123456789".to_vec()), 10)
+            .content_type("text/plain")
+            .etag("v1")
+            .respond(&range)
+            .await
+            .map_err(miko::AppError::from)
+    });
+    router
+}
+
+#[tokio::test]
+async fn serves_ranges_and_conditional_requests() {
+    let client = router().test_client();
+
+    let full = client.get("/file").send().await;
+    full.assert_ok();
+    full.assert_header("accept-ranges", "bytes");
+    full.assert_text("0123456789");
+
+    let partial = client
+        .get("/file")
+        .header(header::RANGE, HeaderValue::from_static("bytes=2-4"))
+        .send()
+        .await;
+    partial.assert_status(StatusCode::PARTIAL_CONTENT);
+    partial.assert_header("content-range", "bytes 2-4/10");
+    partial.assert_text("234");
+
+    let stale = client
+        .get("/file")
+        .header(header::RANGE, HeaderValue::from_static("bytes=2-4"))
+        .header(header::IF_RANGE, HeaderValue::from_static("\"v0\""))
+        .send()
+        .await;
+    stale.assert_ok();
+    stale.assert_text("0123456789");
+
+    client
+        .get("/file")
+        .header(header::RANGE, HeaderValue::from_static("bytes=20-"))
+        .send()
+        .await
+        .assert_status(StatusCode::RANGE_NOT_SATISFIABLE);
+
+    client
+        .get("/file")
+        .header(header::IF_NONE_MATCH, HeaderValue::from_static("\"v1\""))
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_MODIFIED);
+
+    let head = client.head("/file").send().await;
+    head.assert_header("content-length", "10");
+    head.assert_text("");
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "dynamic-routes")]
+
+use hyper::{Method, StatusCode};
+use miko::extractor::Path;
+use miko::router::Router;
+use miko::router::dynamic::{DynamicRoute, DynamicRouter};
+
+#[tokio::test]
+async fn routes_can_be_added_and_removed_at_runtime() {
+    let plugins = DynamicRouter::new();
+    let mut router = Router::new();
+    router.mount_dynamic("/plugins", &plugins);
+    let client = router.test_client();
+
+    client
+        .get("/plugins/hello/miko")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    plugins
+        .add(
+            "GET",
+            "/hello/{name}",
+            |Path(name): Path<String>| async move { format!("hi {}", name) },
+        )
+        .unwrap();
+    assert_eq!(plugins.epoch(), 1);
+    client
+        .get("/plugins/hello/miko")
+        .send()
+        .await
+        .assert_text("hi miko");
+
+    // 冲突的路由被拒绝，路由表保持不变
+    let err = plugins
+        .add("GET", "/hello/{other}", || async { "clash" })
+        .unwrap_err();
+    assert_eq!(err.path, "/hello/{other}");
+    assert_eq!(plugins.epoch(), 1);
+    client
+        .get("/plugins/hello/miko")
+        .send()
+        .await
+        .assert_text("hi miko");
+
+    assert!(plugins.remove(&Method::GET, "/hello/{name}"));
+    assert!(!plugins.remove(&Method::GET, "/hello/{name}"));
+    assert_eq!(plugins.epoch(), 3);
+    client
+        .get("/plugins/hello/miko")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn admin_router_lists_and_removes_routes() {
+    let plugins = DynamicRouter::new();
+    plugins
+        .add("GET, POST", "/items", || async { "items" })
+        .unwrap();
+    let mut router = Router::new();
+    router
+        .mount_dynamic("/plugins", &plugins)
+        .nest("/admin", plugins.admin_router());
+    let client = router.test_client();
+
+    let routes: Vec<DynamicRoute> = client.get("/admin/routes").send().await.json();
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[0].method, "GET");
+
+    let resp = client
+        .delete("/admin/routes?method=post&path=/items")
+        .send()
+        .await;
+    resp.assert_ok();
+    assert_eq!(resp.json::<Vec<DynamicRoute>>().len(), 1);
+    client
+        .post("/plugins/items")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    client
+        .get("/plugins/items")
+        .send()
+        .await
+        .assert_text("items");
+
+    client
+        .delete("/admin/routes?method=POST&path=/items")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,83 @@
+use hyper::StatusCode;
+use hyper::header;
+use include_dir::{Dir, include_dir};
+use miko::ext::embedded::EmbeddedSvc;
+use miko::router::Router;
+
+static ASSETS: Dir = include_dir!("$CARGO_MANIFEST_DIR/tests/fixtures/embedded");
+
+#[tokio::test]
+async fn serves_include_dir_with_etag_range_and_spa_fallback() {
+    let mut router = Router::new();
+    router.nest_service(
+        "/app",
+        EmbeddedSvc::builder()
+            .dir(&ASSETS)
+            .with_spa_fallback(true)
+            .with_cache_control(["css"], "max-age=60")
+            .build(),
+    );
+    let client = router.test_client();
+
+    let css = client.get("/app/css/app.css").send().await;
+    css.assert_text("body { color: red; }\n");
+    css.assert_header("content-type", "text/css; charset=utf-8");
+    css.assert_header("cache-control", "max-age=60");
+    let etag = css.headers["etag"].to_str().unwrap().to_string();
+
+    client
+        .get("/app/css/app.css")
+        .header(header::IF_NONE_MATCH, etag.as_str())
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_MODIFIED);
+
+    let partial = client
+        .get("/app/css/app.css")
+        .header(header::RANGE, "bytes=0-3")
+        .send()
+        .await;
+    partial.assert_status(StatusCode::PARTIAL_CONTENT);
+    partial.assert_text("body");
+
+    client
+        .get("/app/")
+        .send()
+        .await
+        .assert_text("<h1>embedded</h1>\n");
+    client
+        .get("/app/users/42")
+        .send()
+        .await
+        .assert_text("<h1>embedded</h1>\n");
+}
+
+#[tokio::test]
+async fn serves_precompressed_files_and_404_without_fallback() {
+    let mut router = Router::new();
+    router.nest_service(
+        "/",
+        EmbeddedSvc::builder()
+            .files([("app.js", &b"plain"[..]), ("app.js.gz", &b"gzip"[..])])
+            .with_precompressed(true)
+            .build(),
+    );
+    let client = router.test_client();
+
+    let gzip = client
+        .get("/app.js")
+        .header(header::ACCEPT_ENCODING, "gzip")
+        .send()
+        .await;
+    gzip.assert_text("gzip");
+    gzip.assert_header("content-encoding", "gzip");
+    gzip.assert_header("content-type", "text/javascript; charset=utf-8");
+
+    client.get("/app.js").send().await.assert_text("plain");
+    client.get("/../app.js").send().await.assert_text("plain");
+    client
+        .get("/missing.js")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,44 @@
+use hyper::StatusCode;
+use hyper::header::{HeaderName, HeaderValue};
+use miko::AppError;
+use miko::extractor::Json;
+use miko::http::response::envelope::Envelope;
+use miko::middleware::envelope::EnvelopeLayer;
+use miko::router::Router;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn envelope_responder_and_layer() {
+    let mut router = Router::new();
+    router
+        .get("/items", || async { Json(json!([1, 2])) })
+        .get("/paged", || async {
+            Envelope::new(vec!["a"]).meta("total", 1)
+        })
+        .get("/text", || async { "plain" })
+        .get("/missing", || async {
+            Err::<Json<Value>, _>(AppError::NotFound("gone".into()))
+        });
+    router.with_layer(EnvelopeLayer::new().meta("version", "2"));
+    let client = router.test_client();
+
+    let resp = client
+        .get("/items")
+        .header(
+            HeaderName::from_static("x-trace-id"),
+            HeaderValue::from_static("t-1"),
+        )
+        .send()
+        .await;
+    resp.assert_json(json!({ "data": [1, 2], "meta": { "version": "2" }, "trace_id": "t-1" }));
+
+    let body: Value = client.get("/paged").send().await.json();
+    assert_eq!(body["data"], json!(["a"]));
+    assert_eq!(body["meta"], json!({ "total": 1 }));
+
+    client.get("/text").send().await.assert_text("plain");
+    let resp = client.get("/missing").send().await;
+    resp.assert_status(StatusCode::NOT_FOUND);
+    let body: Value = resp.json();
+    assert_eq!(body["error"], "NOT_FOUND");
+}
@@ -0,0 +1,55 @@
+use miko::AppResult;
+use miko::extractor::Extension;
+use miko::handler::{Req, Resp};
+use miko::hyper::header::{HeaderName, HeaderValue};
+use miko::middleware::{Next, middleware_from_fn};
+use miko::router::Router;
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct CurrentUser(String);
+
+struct Flags {
+    beta: bool,
+}
+
+async fn auth(mut req: Req, next: Next) -> AppResult<Resp> {
+    if let Some(name) = req.headers().get("x-user") {
+        let user = CurrentUser(name.to_str().unwrap().to_string());
+        req.extensions_mut().insert(user);
+    }
+    next.run(req).await
+}
+
+#[tokio::test]
+async fn handlers_read_values_from_layers() {
+    let mut router = Router::new();
+    router
+        .get(
+            "/me",
+            |Extension(user): Extension<CurrentUser>, Extension(flags): Extension<Arc<Flags>>| async move {
+                format!("{} beta={}", user.0, flags.beta)
+            },
+        )
+        .get("/maybe", |user: Option<Extension<CurrentUser>>| async move {
+            user.map(|Extension(u)| u.0).unwrap_or_else(|| "anonymous".to_string())
+        })
+        .layer_extension(Arc::new(Flags { beta: true }))
+        .with_layer(middleware_from_fn(auth));
+    let client = router.test_client();
+
+    let resp = client
+        .get("/me")
+        .header(
+            HeaderName::from_static("x-user"),
+            HeaderValue::from_static("miko"),
+        )
+        .send()
+        .await;
+    assert_eq!(resp.text(), "miko beta=true");
+    assert_eq!(client.get("/maybe").send().await.text(), "anonymous");
+
+    // 缺失的扩展是服务端配置错误
+    let resp = client.get("/me").send().await;
+    assert_eq!(resp.status(), 500);
+}
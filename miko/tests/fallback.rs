@@ -0,0 +1,41 @@
+use hyper::StatusCode;
+use hyper::header::ALLOW;
+use miko::extractor::Json;
+use miko::handler::Req;
+use miko::router::Router;
+use serde_json::json;
+
+#[tokio::test]
+async fn custom_fallback_and_method_not_allowed() {
+    let mut api = Router::new();
+    api.get("/items", || async { "items" })
+        .fallback(|| async { (StatusCode::NOT_FOUND, "api: no such endpoint") });
+
+    let mut router = Router::new();
+    router
+        .get("/users", || async { "list" })
+        .post("/users", || async { "created" })
+        .fallback(|req: Req| async move {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "missing": req.uri().path() })),
+            )
+        })
+        .method_not_allowed(|| async { (StatusCode::METHOD_NOT_ALLOWED, "wrong method") })
+        .nest("/api", api);
+    let client = router.test_client();
+
+    client
+        .get("/nope")
+        .send()
+        .await
+        .assert_json(json!({ "missing": "/nope" }));
+    let resp = client.get("/api/nope").send().await;
+    resp.assert_status(StatusCode::NOT_FOUND);
+    resp.assert_text("api: no such endpoint");
+
+    let resp = client.delete("/users").send().await;
+    resp.assert_status(StatusCode::METHOD_NOT_ALLOWED);
+    resp.assert_header(ALLOW.as_str(), "GET, POST");
+    resp.assert_text("wrong method");
+}
@@ -0,0 +1,129 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use http_body_util::{BodyExt, Full};
+use hyper::header::CONTENT_TYPE;
+use hyper::{Request, Response, StatusCode, Version};
+use miko::router::Router;
+use prost::Message;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use tonic::body::Body as GrpcBody;
+use tonic::server::{Grpc, UnaryService};
+use tower::ServiceExt;
+use tower::service_fn;
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct HelloRequest {
+    #[prost(string, tag = "1")]
+    name: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct HelloReply {
+    #[prost(string, tag = "1")]
+    message: String,
+}
+
+struct SayHello;
+
+impl UnaryService<HelloRequest> for SayHello {
+    type Response = HelloReply;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<tonic::Response<HelloReply>, tonic::Status>> + Send>>;
+
+    fn call(&mut self, request: tonic::Request<HelloRequest>) -> Self::Future {
+        let name = request.into_inner().name;
+        Box::pin(async move {
+            Ok(tonic::Response::new(HelloReply {
+                message: format!("Hello {}", name),
+            }))
+        })
+    }
+}
+
+/// 模拟 tonic 生成的 GreeterServer：按路径分发到各个 RPC
+fn greeter() -> impl tower::Service<
+    Request<GrpcBody>,
+    Response = Response<GrpcBody>,
+    Error = Infallible,
+    Future = impl Send,
+> + Clone
++ Send
++ 'static {
+    service_fn(|req: Request<GrpcBody>| async move {
+        let resp = match req.uri().path() {
+            "/helloworld.Greeter/SayHello" => {
+                let mut grpc = Grpc::new(tonic_prost::ProstCodec::default());
+                grpc.unary(SayHello, req).await
+            }
+            _ => tonic::Status::unimplemented("").into_http(),
+        };
+        Ok::<_, Infallible>(resp)
+    })
+}
+
+fn grpc_request(uri: &str, version: Version, msg: &HelloRequest) -> miko::handler::Req {
+    let payload = msg.encode_to_vec();
+    let mut frame = BytesMut::new();
+    frame.put_u8(0);
+    frame.put_u32(payload.len() as u32);
+    frame.put_slice(&payload);
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .version(version)
+        .header(CONTENT_TYPE, "application/grpc")
+        .header("te", "trailers")
+        .body(Full::new(frame.freeze()).map_err(Into::into).boxed_unsync())
+        .unwrap()
+}
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router.get("/health", || async { "ok" });
+    router.mount_grpc("/grpc", greeter());
+    router
+}
+
+#[tokio::test]
+async fn serves_unary_call_under_prefix() {
+    let req = grpc_request(
+        "/grpc/helloworld.Greeter/SayHello",
+        Version::HTTP_2,
+        &HelloRequest {
+            name: "miko".into(),
+        },
+    );
+    let resp = router().into_tower_service().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[CONTENT_TYPE], "application/grpc");
+
+    let collected = resp.into_body().collect().await.unwrap();
+    assert_eq!(collected.trailers().unwrap()["grpc-status"], "0");
+    let mut body: Bytes = collected.to_bytes();
+    assert_eq!(body.get_u8(), 0);
+    let len = body.get_u32() as usize;
+    let reply = HelloReply::decode(&body[..len]).unwrap();
+    assert_eq!(reply.message, "Hello miko");
+}
+
+#[tokio::test]
+async fn rejects_grpc_over_http1_and_keeps_rest_routes() {
+    let router = router();
+    let req = grpc_request(
+        "/grpc/helloworld.Greeter/SayHello",
+        Version::HTTP_11,
+        &HelloRequest::default(),
+    );
+    let resp = router
+        .clone()
+        .into_tower_service()
+        .oneshot(req)
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::HTTP_VERSION_NOT_SUPPORTED);
+
+    let client = router.test_client();
+    let resp = client.get("/health").send().await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
@@ -0,0 +1,98 @@
+use hyper::StatusCode;
+use miko::app::Application;
+use miko::app::config::{HealthSettings, ServerSettings};
+use miko::auto::init_container;
+use miko::ext::health::{HealthRegistry, HealthStatus};
+use miko::macros::*;
+use miko::router::Router;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+static DB_UP: AtomicBool = AtomicBool::new(true);
+
+struct Database;
+
+#[component(health_check = "db")]
+impl Database {
+    async fn new() -> Self {
+        Database
+    }
+
+    async fn health(&self) -> Result<(), String> {
+        if DB_UP.load(Ordering::SeqCst) {
+            Ok(())
+        } else {
+            Err("connection refused".to_string())
+        }
+    }
+}
+
+#[tokio::test]
+async fn readiness_aggregates_checks_with_timeout() {
+    let registry = HealthRegistry::new();
+    registry
+        .register("ok", || async {})
+        .register("flag", || async { false })
+        .register("slow", || async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok::<(), String>(())
+        });
+
+    let report = registry.check_within(Duration::from_millis(50)).await;
+    assert_eq!(report.status, HealthStatus::Down);
+    assert_eq!(report.checks["ok"].status, HealthStatus::Up);
+    assert_eq!(report.checks["flag"].error.as_deref(), Some("unhealthy"));
+    assert_eq!(
+        report.checks["slow"].error.as_deref(),
+        Some("timed out after 50ms")
+    );
+
+    registry.unregister("flag");
+    registry.unregister("slow");
+    assert!(registry.check().await.is_up());
+}
+
+#[tokio::test]
+async fn probes_are_mounted_and_report_component_checks() {
+    init_container().await;
+    let mut router = Router::new();
+    router.get("/readyz", || async { "custom" });
+    let mut app = Application::new(
+        ServerSettings::builder()
+            .health(HealthSettings {
+                liveness_path: "/live".to_string(),
+                ..HealthSettings::default()
+            })
+            .build(),
+        router,
+    );
+    let client = app.test_client();
+
+    let resp = client.get("/live").send().await;
+    resp.assert_ok();
+    resp.assert_json(json!({ "status": "up" }));
+    // 已注册的路径不会被覆盖
+    client.get("/readyz").send().await.assert_text("custom");
+
+    let mut app = Application::new(ServerSettings::default(), Router::new());
+    let client = app.test_client();
+    let resp = client.get("/readyz").send().await;
+    resp.assert_ok();
+    assert_eq!(resp.json::<Value>()["checks"]["db"]["status"], "up");
+
+    DB_UP.store(false, Ordering::SeqCst);
+    let resp = client.get("/readyz").send().await;
+    resp.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    let body: Value = resp.json();
+    assert_eq!(body["status"], "down");
+    assert_eq!(body["checks"]["db"]["error"], "connection refused");
+
+    let mut app = Application::new(ServerSettings::builder().no_health().build(), Router::new());
+    let client = app.test_client();
+    client
+        .get("/healthz")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
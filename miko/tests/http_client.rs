@@ -0,0 +1,112 @@
+#![cfg(feature = "http-client")]
+
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::service::TowerToHyperService;
+use miko::context::RequestContext;
+use miko::error::AppError;
+use miko::ext::client::{HttpClient, HttpClientSettings};
+use miko::extractor::Json;
+use miko::handler::Req;
+use miko::http::convert::incoming_to_req::IncomingToInternal;
+use miko::router::Router;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+
+async fn serve(router: Router) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let svc = router.into_tower_service();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let svc = TowerToHyperService::new(IncomingToInternal { inner: svc.clone() });
+            tokio::spawn(async move {
+                let _ = Builder::new(TokioExecutor::new())
+                    .serve_connection(TokioIo::new(stream), svc)
+                    .await;
+            });
+        }
+    });
+    addr
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Echo {
+    request_id: Option<String>,
+    name: String,
+}
+
+fn upstream() -> Router {
+    let mut router = Router::new();
+    router.post("/api/echo", |req: Req| async move {
+        let request_id = req
+            .headers()
+            .get("x-request-id")
+            .map(|v| v.to_str().unwrap().to_string());
+        Json(json!({ "request_id": request_id, "name": "miko" }))
+    });
+    router.get("/api/down", || async {
+        AppError::ServiceUnavailable("maintenance".into())
+    });
+    router
+}
+
+#[tokio::test]
+async fn joins_base_url_and_propagates_trace_id() {
+    let addr = serve(upstream()).await;
+    let client = HttpClient::new().with_base_url(format!("http://{addr}/api/"));
+    assert_eq!(client.url("/echo"), format!("http://{addr}/api/echo"));
+
+    let echo: Echo = RequestContext::default()
+        .with_trace_id("trace-123")
+        .scope(client.post_json("/echo", &json!({})))
+        .await
+        .unwrap();
+    assert_eq!(
+        echo,
+        Echo {
+            request_id: Some("trace-123".into()),
+            name: "miko".into()
+        }
+    );
+
+    let echo: Echo = client.post_json("echo", &json!({})).await.unwrap();
+    assert_eq!(echo.request_id, None);
+}
+
+#[tokio::test]
+async fn error_status_and_connect_failure_become_external_service_errors() {
+    let addr = serve(upstream()).await;
+    let client = HttpClient::from_settings(HttpClientSettings {
+        base_url: Some(format!("http://{addr}/api")),
+        service: Some("inventory".into()),
+        ..Default::default()
+    });
+
+    let err = client.get_json::<Echo>("/down").await.unwrap_err();
+    match err {
+        AppError::ExternalServiceError { service, message } => {
+            assert_eq!(service, "inventory");
+            assert!(message.contains("503"), "{message}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    let resp = client.get("/down").send().await.unwrap();
+    assert_eq!(resp.status(), 503);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let closed = listener.local_addr().unwrap();
+    drop(listener);
+    let err = HttpClient::new()
+        .get(&format!("http://{closed}/"))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, AppError::ExternalServiceError { service, .. } if service == "127.0.0.1"),
+        "{err:?}"
+    );
+}
@@ -0,0 +1,31 @@
+use miko::app::Application;
+use miko::app::config::{HttpMode, ServerSettings};
+use miko::router::Router;
+use std::time::Duration;
+
+#[test]
+fn http_settings_from_config() {
+    let settings: ServerSettings = serde_json::from_value(serde_json::json!({
+        "host": "127.0.0.1",
+        "port": 0,
+        "http": {
+            "mode": "http2",
+            "http2_max_concurrent_streams": 64,
+            "http1_header_read_timeout_ms": 5000
+        }
+    }))
+    .unwrap();
+    assert_eq!(settings.http.mode, HttpMode::Http2);
+    assert_eq!(settings.http.http2_max_concurrent_streams, Some(64));
+    assert_eq!(settings.http.http1_header_read_timeout_ms, Some(5000));
+    assert_eq!(settings.http.http1_keep_alive, None);
+
+    let defaults = ServerSettings::default();
+    assert_eq!(defaults.http.mode, HttpMode::Auto);
+
+    // builder 方法可链式覆盖配置
+    let _app = Application::new(settings, Router::new())
+        .http_mode(HttpMode::Http1)
+        .http1_keep_alive(false)
+        .http2_keep_alive_interval(Duration::from_secs(20));
+}
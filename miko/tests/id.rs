@@ -0,0 +1,42 @@
+use miko::id::{self, IdGenerator, Ids, Snowflake, Ulid, UuidV7};
+use miko::middleware::request_id::RequestId;
+use miko::router::Router;
+
+#[test]
+fn builtin_generators_produce_sortable_ids() {
+    let a = Ulid.generate();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    let b = Ulid.generate();
+    assert_eq!(a.len(), 26);
+    assert!(a < b, "{a} {b}");
+    assert_ne!(Ulid.generate(), Ulid.generate());
+
+    let uuid = UuidV7.generate();
+    let groups: Vec<&str> = uuid.split('-').collect();
+    assert_eq!(
+        groups.iter().map(|g| g.len()).collect::<Vec<_>>(),
+        [8, 4, 4, 4, 12]
+    );
+    assert!(groups[2].starts_with('7'));
+    assert!(matches!(&groups[3][..1], "8" | "9" | "a" | "b"));
+
+    let snowflake = Snowflake::new(5);
+    let ids: Vec<u64> = (0..5000).map(|_| snowflake.next_id()).collect();
+    assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    assert!(ids.iter().all(|id| (id >> 12) & 0x3ff == 5));
+}
+
+#[tokio::test]
+async fn installed_generator_drives_trace_ids_and_extractor() {
+    id::install(|| "fixed-id".to_string());
+
+    let mut router = Router::new();
+    router
+        .get("/trace", |id: RequestId| async move { id.0 })
+        .get("/new", |ids: Ids| async move { ids.generate() });
+    let client = router.test_client();
+
+    client.get("/trace").send().await.assert_text("fixed-id");
+    client.get("/new").send().await.assert_text("fixed-id");
+    assert_eq!(id::new_id(), "fixed-id");
+}
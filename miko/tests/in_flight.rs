@@ -0,0 +1,80 @@
+use miko::hyper::header::{HeaderName, HeaderValue};
+use miko::middleware::in_flight::InFlightLimitLayer;
+use miko::router::Router;
+use miko::test::test_client::TestClient;
+use std::time::Duration;
+
+fn client(layer: InFlightLimitLayer) -> TestClient {
+    let mut router = Router::new();
+    router
+        .get("/slow", || async {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            "done"
+        })
+        .with_layer(layer);
+    router.test_client()
+}
+
+fn user(name: &'static str) -> (HeaderName, HeaderValue) {
+    (
+        HeaderName::from_static("x-user"),
+        HeaderValue::from_static(name),
+    )
+}
+
+fn by_user() -> InFlightLimitLayer {
+    InFlightLimitLayer::new(1).key(|req| {
+        req.headers()
+            .get("x-user")?
+            .to_str()
+            .ok()
+            .map(str::to_string)
+    })
+}
+
+#[tokio::test]
+async fn rejects_requests_beyond_the_per_client_cap() {
+    let layer = by_user();
+    let client = client(layer.clone());
+    let (a, a2) = user("a");
+    let (b, b2) = user("b");
+
+    let first = client.get("/slow").header(a.clone(), a2.clone()).send();
+    let others = async {
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(layer.in_flight("a"), 1);
+        let same = client
+            .get("/slow")
+            .header(a.clone(), a2.clone())
+            .send()
+            .await;
+        let other = client.get("/slow").header(b, b2).send().await;
+        let anonymous = client.get("/slow").send().await;
+        (same, other, anonymous)
+    };
+    let (first, (same, other, anonymous)) = tokio::join!(first, others);
+    assert_eq!(first.status(), 200);
+    assert_eq!(same.status(), 429);
+    assert_eq!(other.status(), 200);
+    assert_eq!(anonymous.status(), 200);
+    assert_eq!(layer.in_flight("a"), 0);
+}
+
+#[tokio::test]
+async fn queues_for_a_free_slot_when_waiting_is_enabled() {
+    let client = client(by_user().wait(Duration::from_secs(1)));
+    let (a, a2) = user("a");
+
+    let first = client.get("/slow").header(a.clone(), a2.clone()).send();
+    let second = async {
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        client
+            .get("/slow")
+            .header(a.clone(), a2.clone())
+            .send()
+            .await
+    };
+    let (first, second) = tokio::join!(first, second);
+    assert_eq!(first.status(), 200);
+    assert_eq!(second.status(), 200);
+}
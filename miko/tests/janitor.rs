@@ -0,0 +1,62 @@
+use miko::ext::janitor::{FileSweep, Janitor, JanitorStats, Reclaimed};
+use std::io;
+use std::time::Duration;
+
+#[tokio::test]
+async fn sweeps_partial_uploads_and_records_stats() {
+    let dir = std::env::temp_dir().join(format!("miko-janitor-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("avatar.png.part"), b"half-written").unwrap();
+    std::fs::write(dir.join("avatar.png"), b"complete").unwrap();
+
+    let janitor = Janitor::new(Duration::from_secs(60))
+        .task(FileSweep::partial_uploads(&dir, Duration::ZERO))
+        .task(FileSweep::partial_uploads(
+            dir.join("missing"),
+            Duration::ZERO,
+        ));
+    let reclaimed = janitor.run_once().await;
+
+    assert_eq!(
+        reclaimed,
+        Reclaimed {
+            items: 1,
+            bytes: 12
+        }
+    );
+    assert!(!dir.join("avatar.png.part").exists());
+    assert!(dir.join("avatar.png").exists());
+
+    // 未过期的文件保留
+    std::fs::write(dir.join("fresh.png.part"), b"x").unwrap();
+    let janitor = Janitor::new(Duration::from_secs(60))
+        .task(FileSweep::partial_uploads(&dir, Duration::from_secs(3600)));
+    assert_eq!(janitor.run_once().await, Reclaimed::default());
+    assert!(dir.join("fresh.png.part").exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn custom_tasks_and_failures_are_counted() {
+    let janitor = Janitor::new(Duration::from_millis(20))
+        .task_fn("sessions", || async { Ok(Reclaimed::items(3)) })
+        .task_fn("idempotency", || async {
+            Err(io::Error::other("store unavailable"))
+        });
+
+    let handle = janitor.spawn();
+    tokio::time::sleep(Duration::from_millis(70)).await;
+    handle.abort();
+
+    let JanitorStats {
+        runs,
+        items,
+        bytes,
+        errors,
+    } = janitor.stats();
+    assert!(runs >= 2);
+    assert_eq!(items, runs * 3);
+    assert_eq!(bytes, 0);
+    assert_eq!(errors, runs);
+}
@@ -0,0 +1,54 @@
+use futures::StreamExt;
+use http_body_util::BodyExt;
+use miko::IntoResponse;
+use miko::http::response::json_stream::JsonArrayStream;
+use miko::router::Router;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Serialize)]
+struct Row {
+    id: u32,
+}
+
+#[tokio::test]
+async fn streams_well_formed_arrays() {
+    let mut router = Router::new();
+    router
+        .get("/rows", || async {
+            JsonArrayStream::new(futures::stream::iter((0..20_000).map(|id| Row { id })))
+        })
+        .get("/empty", || async {
+            JsonArrayStream::new(futures::stream::empty::<Row>())
+        });
+    let client = router.test_client();
+
+    let resp = client.get("/rows").send().await;
+    resp.assert_header("content-type", "application/json");
+    let rows: Vec<serde_json::Value> = resp.json();
+    assert_eq!(rows.len(), 20_000);
+    assert_eq!(rows[19_999]["id"], 19_999);
+
+    client.get("/empty").send().await.assert_text("[]");
+}
+
+#[tokio::test]
+async fn pulls_items_only_as_the_body_is_read() {
+    let pulled = Arc::new(AtomicUsize::new(0));
+    let counter = pulled.clone();
+    let items = futures::stream::iter(0..100_000u32).inspect(move |_| {
+        counter.fetch_add(1, Ordering::Relaxed);
+    });
+    let mut body = JsonArrayStream::new(items).into_response().into_body();
+
+    let first = body.frame().await.unwrap().unwrap().into_data().unwrap();
+    assert!(first.starts_with(b"[0,1,2"));
+    assert!(pulled.load(Ordering::Relaxed) < 100_000);
+
+    // 序列化失败时中断响应而不是写出闭合的数组
+    let bad = futures::stream::iter([HashMap::from([((1, 2), 3)])]);
+    let mut body = JsonArrayStream::new(bad).into_response().into_body();
+    assert!(body.frame().await.unwrap().is_err());
+}
@@ -0,0 +1,50 @@
+use hyper::{HeaderMap, StatusCode};
+use miko::AppResult;
+use miko::extractor::{Json, Lazy};
+use miko::router::Router;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Page {
+    title: String,
+}
+
+async fn render(headers: HeaderMap, mut body: Lazy<Json<Page>>) -> AppResult<String> {
+    if headers.contains_key("x-cache-hit") {
+        assert!(!body.is_evaluated());
+        return Ok("cached".to_string());
+    }
+    let first = body.get().await?.0.title.clone();
+    // 第二次调用复用已解析的结果
+    let Json(page) = body.into_inner().await?;
+    Ok(format!("{} {}", first, page.title))
+}
+
+#[tokio::test]
+async fn lazy_defers_body_extraction() {
+    let mut router = Router::new();
+    router.post("/render", render);
+    let client = router.test_client();
+
+    let resp = client
+        .post("/render")
+        .json(&serde_json::json!({ "title": "home" }))
+        .send()
+        .await;
+    resp.assert_text("home home");
+
+    // 命中缓存时不解析请求体，非法 JSON 也不会报错
+    let resp = client
+        .post("/render")
+        .header(
+            hyper::header::HeaderName::from_static("x-cache-hit"),
+            hyper::header::HeaderValue::from_static("1"),
+        )
+        .text("not json")
+        .send()
+        .await;
+    resp.assert_text("cached");
+
+    let resp = client.post("/render").text("not json").send().await;
+    resp.assert_status(StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,68 @@
+use hyper::StatusCode;
+use miko::app::config::RouteOverride;
+use miko::extractor::Query;
+use miko::macros::*;
+use miko::router::Router;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[get("/lenient/search", lenient_query)]
+async fn search(#[query] active: bool, #[query] page: u32, #[query] tag: Option<String>) -> String {
+    format!("{} {} {:?}", active, page, tag)
+}
+
+#[get("/strict/search")]
+async fn strict_search(#[query] active: bool, #[query] page: u32) -> String {
+    format!("{} {}", active, page)
+}
+
+#[tokio::test]
+async fn route_flag_coerces_form_style_values() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    client
+        .get("/lenient/search?active=on&page=%202%20&tag=")
+        .send()
+        .await
+        .assert_text("true 2 None");
+    client
+        .get("/lenient/search?active=0&page=3&tag=rust")
+        .send()
+        .await
+        .assert_text("false 3 Some(\"rust\")");
+    client
+        .get("/strict/search?active=on&page=2")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[derive(Deserialize)]
+struct Filter {
+    published: bool,
+    limit: Option<u8>,
+}
+
+#[tokio::test]
+async fn route_override_enables_lenient_query() {
+    let mut router = Router::new();
+    router.get("/posts", |Query(f): Query<Filter>| async move {
+        format!("{} {:?}", f.published, f.limit)
+    });
+    let mut routes = BTreeMap::new();
+    routes.insert(
+        "/posts".to_string(),
+        RouteOverride {
+            lenient_query: true,
+            ..Default::default()
+        },
+    );
+    router.route_overrides(&routes);
+
+    router
+        .test_client()
+        .get("/posts?published=yes&limit=")
+        .send()
+        .await
+        .assert_text("true None");
+}
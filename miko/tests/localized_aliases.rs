@@ -0,0 +1,75 @@
+use miko::app::Application;
+use miko::app::config::ServerSettings;
+use miko::extractor::RouteLocale;
+use miko::macros::*;
+use miko::router::Router;
+
+#[get("/i18n/about")]
+#[alias("/i18n/über-uns", locale = "de")]
+#[alias("/i18n/a-propos", locale = "fr")]
+#[alias("/i18n/about-us")]
+async fn about(locale: RouteLocale) -> String {
+    format!("about ({})", locale.or("en"))
+}
+
+#[tokio::test]
+async fn macro_aliases_expose_locale() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    client
+        .get("/i18n/about")
+        .send()
+        .await
+        .assert_text("about (en)");
+    client
+        .get("/i18n/%C3%BCber-uns")
+        .send()
+        .await
+        .assert_text("about (de)");
+    client
+        .get("/i18n/a-propos")
+        .send()
+        .await
+        .assert_text("about (fr)");
+    client
+        .get("/i18n/about-us")
+        .send()
+        .await
+        .assert_text("about (en)");
+}
+
+#[tokio::test]
+async fn config_and_router_aliases_share_handlers() {
+    let mut router = Router::new();
+    router
+        .get("/contact", |locale: RouteLocale| async move {
+            format!("{:?}", locale.0)
+        })
+        .post("/contact", |locale: RouteLocale| async move {
+            format!("sent {}", locale.or("en"))
+        })
+        .get("/pricing", |locale: RouteLocale| async move {
+            locale.or("en").to_string()
+        });
+    router.localized_alias("/pricing", "es", "/precios");
+
+    let settings = ServerSettings::builder()
+        .route_alias("/contact", "de", "/kontakt")
+        .route_alias("/missing", "de", "/fehlt")
+        .build();
+    let client = Application::new(settings, router).test_client();
+
+    client.get("/contact").send().await.assert_text("None");
+    client
+        .get("/kontakt")
+        .send()
+        .await
+        .assert_text("Some(\"de\")");
+    client.post("/kontakt").send().await.assert_text("sent de");
+    client.get("/precios").send().await.assert_text("es");
+    client
+        .get("/fehlt")
+        .send()
+        .await
+        .assert_status(hyper::StatusCode::NOT_FOUND);
+}
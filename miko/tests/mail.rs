@@ -0,0 +1,169 @@
+use hyper::StatusCode;
+use miko::ext::mail::{
+    Email, LogTransport, MailTemplate, Mailer, SmtpSettings, SmtpTls, SmtpTransport,
+};
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+static WELCOME: MailTemplate =
+    MailTemplate::new("Welcome, {{ name }}", "Hi {{ name }}, you are #{{ seq }}.")
+        .with_html("<p>Hi {{ name }}</p>");
+
+#[tokio::test]
+async fn log_transport_records_rendered_templates() {
+    let outbox = LogTransport::new();
+    let mailer = Mailer::new("noreply@example.com", outbox.clone());
+    mailer
+        .send(
+            WELCOME
+                .render(&json!({ "name": "<Ann>", "seq": 3 }))
+                .to("ann@example.com"),
+        )
+        .await
+        .unwrap();
+
+    let sent = outbox.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].from.as_deref(), Some("noreply@example.com"));
+    assert_eq!(sent[0].subject, "Welcome, <Ann>");
+    assert_eq!(sent[0].text, "Hi <Ann>, you are #3.");
+    assert_eq!(sent[0].html.as_deref(), Some("<p>Hi &lt;Ann&gt;</p>"));
+
+    assert!(mailer.send(Email::new().subject("nobody")).await.is_err());
+}
+
+/// 记录收到命令的简易 SMTP 服务器，EHLO 只声明 `extensions`
+async fn fake_smtp(extensions: &'static str) -> (u16, tokio::task::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = BufReader::new(stream);
+        let mut transcript = Vec::new();
+        stream.get_mut().write_all(b"220 test\r\n").await.unwrap();
+        let mut in_data = false;
+        loop {
+            let mut line = String::new();
+            if stream.read_line(&mut line).await.unwrap_or(0) == 0 {
+                break;
+            }
+            let line = line.trim_end().to_string();
+            let reply = if in_data {
+                if line != "." {
+                    transcript.push(line);
+                    continue;
+                }
+                in_data = false;
+                "250 queued\r\n".to_string()
+            } else if line.starts_with("EHLO") {
+                format!("250-test\r\n250 {}\r\n", extensions)
+            } else if line.starts_with("AUTH") {
+                "235 ok\r\n".to_string()
+            } else if line == "DATA" {
+                in_data = true;
+                "354 go\r\n".to_string()
+            } else if line == "QUIT" {
+                stream.get_mut().write_all(b"221 bye\r\n").await.unwrap();
+                transcript.push(line);
+                break;
+            } else {
+                "250 ok\r\n".to_string()
+            };
+            transcript.push(line);
+            if stream.get_mut().write_all(reply.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+        transcript
+    });
+    (port, server)
+}
+
+#[tokio::test]
+async fn smtp_transport_speaks_the_protocol() {
+    let (port, server) = fake_smtp("AUTH PLAIN").await;
+    let transport = SmtpTransport::new(SmtpSettings {
+        host: "127.0.0.1".to_string(),
+        port,
+        tls: SmtpTls::None,
+        ..SmtpSettings::default()
+    });
+    let mailer = Mailer::new("Miko <noreply@example.com>", transport);
+    mailer
+        .send(
+            Email::new()
+                .to("ann@example.com")
+                .subject("Hello\r\nBcc: evil")
+                .text("body"),
+        )
+        .await
+        .unwrap();
+
+    let transcript = server.await.unwrap();
+    assert!(transcript.contains(&"MAIL FROM:<noreply@example.com>".to_string()));
+    assert!(transcript.contains(&"RCPT TO:<ann@example.com>".to_string()));
+    assert!(!transcript.iter().any(|line| line.starts_with("Bcc:")));
+    assert!(transcript.contains(&"body".to_string()));
+    assert!(!transcript.iter().any(|line| line.starts_with("AUTH")));
+    assert_eq!(transcript.last().map(String::as_str), Some("QUIT"));
+}
+
+#[tokio::test]
+async fn smtp_transport_rejects_line_breaks_in_addresses() {
+    let transport = SmtpTransport::new(SmtpSettings {
+        host: "127.0.0.1".to_string(),
+        port: 1,
+        tls: SmtpTls::None,
+        ..SmtpSettings::default()
+    });
+    let mailer = Mailer::new("noreply@example.com", transport);
+    for to in [
+        "ann@example.com>\r\nRCPT TO:<evil@example.com",
+        "Ann\n <ann@example.com>",
+    ] {
+        let err = mailer
+            .send(Email::new().to(to).subject("hi").text("body"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST, "{to:?}");
+    }
+}
+
+#[tokio::test]
+async fn smtp_transport_never_authenticates_in_cleartext() {
+    // 显式关闭 TLS 时配置了认证信息：不连接服务器直接拒绝
+    let transport = SmtpTransport::new(SmtpSettings {
+        host: "127.0.0.1".to_string(),
+        port: 1,
+        username: Some("user".to_string()),
+        password: Some("pw".to_string()),
+        tls: SmtpTls::None,
+        ..SmtpSettings::default()
+    });
+    let mailer = Mailer::new("noreply@example.com", transport);
+    let err = mailer
+        .send(Email::new().to("ann@example.com").text("body"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("unencrypted"), "{err}");
+
+    // 缺省要求 STARTTLS：服务器不支持时在认证之前失败
+    let (port, server) = fake_smtp("AUTH PLAIN").await;
+    let transport = SmtpTransport::new(SmtpSettings {
+        host: "127.0.0.1".to_string(),
+        port,
+        username: Some("user".to_string()),
+        password: Some("pw".to_string()),
+        ..SmtpSettings::default()
+    });
+    let mailer = Mailer::new("noreply@example.com", transport);
+    assert!(
+        mailer
+            .send(Email::new().to("ann@example.com").text("body"))
+            .await
+            .is_err()
+    );
+    let transcript = server.await.unwrap();
+    assert!(!transcript.iter().any(|line| line.starts_with("AUTH")));
+}
@@ -0,0 +1,70 @@
+use miko::router::Router;
+use miko::test::manifest::RouteManifest;
+use tower_http::cors::CorsLayer;
+use utoipa::openapi::path::{HttpMethod, OperationBuilder, PathItem, PathsBuilder};
+use utoipa::openapi::{OpenApi, OpenApiBuilder};
+
+fn v1() -> Router {
+    let mut router = Router::new();
+    router
+        .get("/users", || async { "list" })
+        .post("/users", || async { "create" })
+        .get("/legacy", || async { "old" });
+    router
+}
+
+fn v2() -> Router {
+    let mut router = Router::new();
+    router
+        .get("/users", || async { "list" })
+        .post("/users", || async { "create" })
+        .get("/users/{id}", || async { "one" });
+    router.with_layer(CorsLayer::permissive());
+    router
+}
+
+fn doc(summary: &str) -> OpenApi {
+    OpenApiBuilder::new()
+        .paths(PathsBuilder::new().path(
+            "/users",
+            PathItem::new(
+                HttpMethod::Get,
+                OperationBuilder::new().summary(Some(summary)).build(),
+            ),
+        ))
+        .build()
+}
+
+#[test]
+fn diff_reports_added_removed_and_middleware() {
+    let old = RouteManifest::from_json(&v1().export_manifest().to_json()).unwrap();
+    let new = v2().export_manifest();
+    assert!(new.middleware[0].contains("CorsLayer"));
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].path, "/users/{id}");
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].path, "/legacy");
+    assert!(diff.middleware_changed);
+    assert!(diff.is_breaking());
+    assert_eq!(
+        diff.to_string(),
+        "+ GET /users/{id}\n- GET /legacy\n~ middleware\n"
+    );
+    assert!(new.diff(&new).is_empty());
+}
+
+#[test]
+fn openapi_changes_mark_route_changed() {
+    let old = v1().export_manifest().with_openapi(&doc("list users"));
+    let same = v1().export_manifest().with_openapi(&doc("list users"));
+    let new = v1().export_manifest().with_openapi(&doc("list all users"));
+
+    assert!(old.routes.iter().any(|r| r.openapi_hash.is_some()));
+    assert!(old.diff(&same).is_empty());
+    let diff = old.diff(&new);
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].0.method, "GET");
+    assert_eq!(diff.changed[0].0.path, "/users");
+}
@@ -0,0 +1,39 @@
+use hyper::StatusCode;
+use hyper::header::ALLOW;
+use miko::router::Router;
+
+fn users() -> Router {
+    let mut router = Router::new();
+    router
+        .get("/users/{id}", || async { "user" })
+        .put("/users/{id}", || async { "updated" })
+        .fallback(|| async { (StatusCode::NOT_FOUND, "fallback") });
+    router
+}
+
+#[tokio::test]
+async fn returns_405_with_allow_header_by_default() {
+    let client = users().test_client();
+
+    let resp = client.delete("/users/1").send().await;
+    resp.assert_status(StatusCode::METHOD_NOT_ALLOWED);
+    resp.assert_header(ALLOW.as_str(), "GET, PUT");
+    let body = resp.json::<serde_json::Value>();
+    assert_eq!(body["error"], "METHOD_NOT_ALLOWED", "{body}");
+
+    let resp = client.get("/posts/1").send().await;
+    resp.assert_status(StatusCode::NOT_FOUND);
+    resp.assert_text("fallback");
+}
+
+#[tokio::test]
+async fn opt_out_restores_fallback_for_wrong_method() {
+    let mut router = users();
+    router.auto_method_not_allowed(false);
+    let client = router.test_client();
+
+    let resp = client.delete("/users/1").send().await;
+    resp.assert_status(StatusCode::NOT_FOUND);
+    resp.assert_text("fallback");
+    assert!(resp.headers.get(ALLOW).is_none());
+}
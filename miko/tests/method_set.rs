@@ -0,0 +1,95 @@
+use http_body_util::{BodyExt, Empty};
+use hyper::{Method, Request, StatusCode};
+use miko::extractor::Path;
+use miko::handler::Req;
+use miko::router::method_set::MethodSet;
+use miko::router::{HttpSvc, Router};
+use miko::{AppError, IntoResponse};
+use tower::ServiceExt;
+use tower::service_fn;
+use tower::util::BoxCloneService;
+
+fn echo_method() -> HttpSvc<Req> {
+    BoxCloneService::new(service_fn(|req: Req| async move {
+        Ok::<_, AppError>(req.method().to_string().into_response())
+    }))
+}
+
+async fn call(router: &Router, method: &str, uri: &str) -> (StatusCode, String) {
+    let req = Request::builder()
+        .method(Method::from_bytes(method.as_bytes()).unwrap())
+        .uri(uri)
+        .body(Empty::new().map_err(Into::into).boxed_unsync())
+        .unwrap();
+    let resp = router
+        .clone()
+        .into_tower_service()
+        .oneshot(req)
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8_lossy(&body).to_string())
+}
+
+#[tokio::test]
+async fn any_method_service_accepts_extension_methods() {
+    let mut router = Router::new();
+    router.service_with("/dav", MethodSet::Any, echo_method());
+    router.get("/dav", || async { "explicit get" });
+
+    assert_eq!(
+        call(&router, "PROPFIND", "/dav").await,
+        (StatusCode::OK, "PROPFIND".to_string())
+    );
+    assert_eq!(call(&router, "TRACE", "/dav").await.0, StatusCode::OK);
+    // 显式注册的方法优先
+    assert_eq!(call(&router, "GET", "/dav").await.1, "explicit get");
+}
+
+#[tokio::test]
+async fn nest_service_with_explicit_methods() {
+    let mut router = Router::new();
+    router.nest_service_with("/proxy", vec![Method::GET, Method::CONNECT], echo_method());
+
+    assert_eq!(call(&router, "CONNECT", "/proxy/").await.0, StatusCode::OK);
+    assert_eq!(
+        call(&router, "POST", "/proxy/").await.0,
+        StatusCode::METHOD_NOT_ALLOWED
+    );
+}
+
+#[tokio::test]
+async fn extension_methods_are_routable() {
+    let mut router = Router::new();
+    router
+        .method("propfind", "/files", || async { "listing" })
+        .route(
+            "MKCOL, get",
+            "/files/{name}",
+            |Path(name): Path<String>| async move { name },
+        );
+
+    assert_eq!(
+        call(&router, "PROPFIND", "/files").await,
+        (StatusCode::OK, "listing".to_string())
+    );
+    assert_eq!(call(&router, "MKCOL", "/files/docs").await.1, "docs");
+    assert_eq!(call(&router, "GET", "/files/docs").await.1, "docs");
+    assert_eq!(
+        call(&router, "COPY", "/files").await.0,
+        StatusCode::METHOD_NOT_ALLOWED
+    );
+}
+
+#[miko::macros::route("/macro/dav", method = "propfind, MKCOL")]
+async fn macro_dav(method: Method) -> String {
+    method.to_string()
+}
+
+#[tokio::test]
+async fn route_macro_accepts_extension_methods() {
+    let router = miko::auto::collect_global_router();
+    assert_eq!(call(&router, "PROPFIND", "/macro/dav").await.1, "PROPFIND");
+    assert_eq!(call(&router, "MKCOL", "/macro/dav").await.1, "MKCOL");
+}
@@ -0,0 +1,54 @@
+use miko::AppError;
+use miko::ext::metrics::prometheus::IntCounter;
+use miko::ext::metrics::{MetricsLayer, MetricsRegistry};
+use miko::extractor::{Extension, Path};
+use miko::router::{MatchedPath, Router};
+
+#[tokio::test]
+async fn records_requests_by_route_pattern() {
+    let registry = MetricsRegistry::new();
+    let logins = registry
+        .register(IntCounter::new("app_logins_total", "Logins").unwrap())
+        .unwrap();
+    let metrics = registry.clone();
+
+    let mut router = Router::new();
+    router
+        .get("/users/{id}", |Path(id): Path<u32>| async move {
+            if id == 0 {
+                Err(AppError::NotFound("no such user".into()))
+            } else {
+                Ok(format!("user {}", id))
+            }
+        })
+        .get(
+            "/route",
+            |Extension(path): Extension<MatchedPath>| async move { path.as_str().to_string() },
+        )
+        .get("/metrics", move || {
+            let metrics = metrics.clone();
+            async move { metrics }
+        })
+        .with_layer(MetricsLayer::with_registry(registry.clone()));
+    let client = router.test_client();
+
+    client.get("/users/1").send().await.assert_text("user 1");
+    client.get("/users/2").send().await;
+    client.get("/users/0").send().await;
+    client.get("/nope").send().await;
+    client.get("/route").send().await.assert_text("/route");
+    logins.inc();
+
+    let text = client.get("/metrics").send().await.text();
+    for line in [
+        r#"http_requests_total{method="GET",route="/users/{id}",status="200"} 2"#,
+        r#"http_requests_total{method="GET",route="/users/{id}",status="404"} 1"#,
+        r#"http_requests_total{method="GET",route="<unmatched>",status="404"} 1"#,
+        r#"http_request_duration_seconds_count{method="GET",route="/users/{id}"} 3"#,
+        r#"http_response_size_bytes_sum{method="GET",route="/route"} 6"#,
+        "http_requests_in_flight 1",
+        "app_logins_total 1",
+    ] {
+        assert!(text.contains(line), "missing `{}` in\n{}", line, text);
+    }
+}
@@ -0,0 +1,61 @@
+use miko::ext::migrations::{
+    MigrationError, MigrationMode, Migrations, SqlxMigrations, migration_status,
+};
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::SqlitePoolOptions;
+
+#[tokio::test]
+async fn sqlx_migrations_check_then_apply() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("1_create_users.sql"),
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("2_add_email.sql"),
+        "ALTER TABLE users ADD COLUMN email TEXT;",
+    )
+    .unwrap();
+
+    // 内存数据库只在同一个连接中可见
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let migrator = Migrator::new(dir.path()).await.unwrap();
+    let migrations = Migrations::new(SqlxMigrations::new(migrator, pool.clone()));
+
+    let err = migrations
+        .clone()
+        .mode(MigrationMode::FailOnPending)
+        .run()
+        .await
+        .unwrap_err();
+    match err {
+        MigrationError::Pending(names) => {
+            assert_eq!(names, vec!["1_create users", "2_add email"])
+        }
+        other => panic!("unexpected error: {}", other),
+    }
+    let status = migration_status().unwrap();
+    assert!(!status.is_ok());
+    assert_eq!(status.pending.len(), 2);
+
+    let status = migrations.run().await.unwrap();
+    assert_eq!(status.mode, MigrationMode::Apply);
+    assert_eq!(status.applied, vec!["1_create users", "2_add email"]);
+    sqlx::query("INSERT INTO users (name, email) VALUES ('miko', 'miko@example.com')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let status = migrations
+        .mode(MigrationMode::FailOnPending)
+        .run()
+        .await
+        .unwrap();
+    assert!(status.is_ok());
+    assert_eq!(migration_status(), Some(status));
+}
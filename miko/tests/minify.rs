@@ -0,0 +1,70 @@
+use hyper::HeaderMap;
+use hyper::header::{CONTENT_TYPE, HeaderValue};
+use miko::http::response::into_response::Html;
+use miko::middleware::minify::{MinifyLayer, minify_html};
+use miko::router::Router;
+
+const PAGE: &str = r#"
+<!DOCTYPE html>
+<html>
+  <!-- navigation -->
+  <body>
+    <h1>  Hello,   world  </h1>
+    <pre>
+  keep   this
+    </pre>
+    <script>if (a  <  b) { run(); }</script>
+  </body>
+</html>
+"#;
+
+#[tokio::test]
+async fn minifies_html_and_json_above_threshold() {
+    let mut router = Router::new();
+    router
+        .get("/page", || async { Html(PAGE.to_string()) })
+        .get("/data", || async {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            (headers, "{\n  \"b\": \"x  y\",\n  \"a\": [1, 2]\n}")
+        })
+        .get("/small", || async { Html("<p>  hi  </p>".to_string()) });
+    router.with_layer(MinifyLayer::new().enabled(true).min_size(20));
+    let client = router.test_client();
+
+    client.get("/page").send().await.assert_text(
+        "<!DOCTYPE html> <html> <body> <h1> Hello, world </h1> <pre>\n  keep   this\n    </pre> <script>if (a  <  b) { run(); }</script> </body> </html>",
+    );
+    client
+        .get("/data")
+        .send()
+        .await
+        .assert_text(r#"{"b":"x  y","a":[1,2]}"#);
+    client
+        .get("/small")
+        .send()
+        .await
+        .assert_text("<p>  hi  </p>");
+}
+
+#[tokio::test]
+async fn disabled_layer_passes_through() {
+    let mut router = Router::new();
+    router.get("/page", || async { Html(PAGE.to_string()) });
+    router.with_layer(MinifyLayer::new().enabled(false));
+    router
+        .test_client()
+        .get("/page")
+        .send()
+        .await
+        .assert_text(PAGE);
+
+    assert_eq!(
+        minify_html("<p>a</p><!--[if IE]>x<![endif]-->  <!-- gone -->"),
+        "<p>a</p><!--[if IE]>x<![endif]--> "
+    );
+    assert_eq!(
+        minify_html("<SCRIPT>a  b</Script>  <style>c  d</STYLE>"),
+        "<SCRIPT>a  b</Script> <style>c  d</STYLE>"
+    );
+}
@@ -0,0 +1,70 @@
+use miko::AppError;
+use miko::extractor::from_request::{FRPFut, FromRequestParts};
+use miko::extractor::{Json, Path, Query};
+use miko::hyper::http::request::Parts;
+use miko::test::request;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct User {
+    name: String,
+}
+
+/// 自定义提取器：要求 `x-api-key` 与状态中的密钥一致
+struct ApiKey;
+
+impl FromRequestParts<String> for ApiKey {
+    fn from_request_parts(req: &mut Parts, state: Arc<String>) -> FRPFut<'_, Self> {
+        let ok = req
+            .headers
+            .get("x-api-key")
+            .is_some_and(|v| v == state.as_str());
+        Box::pin(async move {
+            ok.then_some(ApiKey)
+                .ok_or_else(|| AppError::Unauthorized("bad key".into()))
+        })
+    }
+}
+
+#[tokio::test]
+async fn extracts_without_router() {
+    let Json(user) = request()
+        .json(&User { name: "ann".into() })
+        .extract::<Json<User>, _>()
+        .await
+        .unwrap();
+    assert_eq!(user.name, "ann");
+
+    let Path(id) = request()
+        .path_param("id", 7)
+        .extract::<Path<u32>, _>()
+        .await
+        .unwrap();
+    assert_eq!(id, 7);
+
+    let Query(q) = request()
+        .uri("/search?q=rust")
+        .query_param("page", 2)
+        .extract::<Query<std::collections::HashMap<String, String>>, _>()
+        .await
+        .unwrap();
+    assert_eq!(q["q"], "rust");
+    assert_eq!(q["page"], "2");
+}
+
+#[tokio::test]
+async fn runs_custom_parts_extractor_with_state() {
+    let (ok, _) = request()
+        .header("x-api-key", "secret")
+        .extract_parts::<_, ApiKey>("secret".to_string())
+        .await;
+    assert!(ok.is_ok());
+
+    let (err, parts) = request()
+        .extension(42u8)
+        .extract_parts::<_, ApiKey>("secret".to_string())
+        .await;
+    assert!(matches!(err, Err(AppError::Unauthorized(_))));
+    assert_eq!(parts.extensions.get::<u8>(), Some(&42));
+}
@@ -0,0 +1,84 @@
+use hyper::StatusCode;
+use hyper::header::{self, HeaderValue};
+use miko::ext::download::RangeRequest;
+use miko::http::response::named_file::NamedFile;
+use miko::router::Router;
+use std::path::PathBuf;
+
+fn fixture(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("miko-named-file-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[tokio::test]
+async fn streams_file_with_headers_and_ranges() {
+    let path = fixture("notes.txt", "0123456789");
+    let mut router = Router::new();
+    let file = path.clone();
+    router.route("GET,HEAD", "/notes", move |range: RangeRequest| {
+        let file = file.clone();
+        async move {
+            NamedFile::open(file)
+                .await
+                .map(|f| f.attachment().filename("Notizen für März.txt").range(range))
+                .map_err(miko::AppError::from)
+        }
+    });
+    let client = router.test_client();
+
+    let full = client.get("/notes").send().await;
+    full.assert_ok();
+    full.assert_header(header::CONTENT_TYPE, "text/plain; charset=utf-8");
+    full.assert_header(header::CONTENT_LENGTH, "10");
+    full.assert_header(
+        header::CONTENT_DISPOSITION,
+        "attachment; filename=\"Notizen f_r M_rz.txt\"; filename*=UTF-8''Notizen%20f%C3%BCr%20M%C3%A4rz.txt",
+    );
+    full.assert_text("0123456789");
+
+    let partial = client
+        .get("/notes")
+        .header(header::RANGE, HeaderValue::from_static("bytes=3-5"))
+        .send()
+        .await;
+    partial.assert_status(StatusCode::PARTIAL_CONTENT);
+    partial.assert_header(header::CONTENT_RANGE, "bytes 3-5/10");
+    partial.assert_text("345");
+
+    let head = client.head("/notes").send().await;
+    head.assert_header(header::CONTENT_LENGTH, "10");
+    assert!(head.bytes().is_empty());
+}
+
+#[tokio::test]
+async fn path_buf_responds_inline_or_404() {
+    let path = fixture("page.html", "<h1>hi</h1>");
+    let mut router = Router::new();
+    router
+        .get("/page", move || {
+            let path = path.clone();
+            async move { path }
+        })
+        .get("/missing", || async {
+            PathBuf::from("/definitely/missing.txt")
+        });
+    let client = router.test_client();
+
+    let resp = client.get("/page").send().await;
+    resp.assert_ok();
+    resp.assert_header(header::CONTENT_TYPE, "text/html; charset=utf-8");
+    resp.assert_header(
+        header::CONTENT_DISPOSITION,
+        "inline; filename=\"page.html\"",
+    );
+    resp.assert_text("<h1>hi</h1>");
+
+    client
+        .get("/missing")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
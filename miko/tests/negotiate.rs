@@ -0,0 +1,96 @@
+use hyper::StatusCode;
+use hyper::header::ACCEPT;
+use miko::extractor::Accepts;
+use miko::http::response::negotiate::Negotiate;
+use miko::router::Router;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct User {
+    id: u64,
+    name: String,
+}
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router
+        .get("/user", || async {
+            (
+                StatusCode::CREATED,
+                Negotiate(User {
+                    id: 1,
+                    name: "miko".into(),
+                }),
+            )
+        })
+        .get("/prefers", |accepts: Accepts| async move {
+            accepts
+                .negotiate(&["text/csv", "application/json"])
+                .unwrap_or("none")
+                .to_string()
+        });
+    router
+}
+
+#[tokio::test]
+async fn negotiate_encodes_by_accept_header() {
+    let client = router().test_client();
+    let expected = User {
+        id: 1,
+        name: "miko".into(),
+    };
+
+    let json = client.get("/user").send().await;
+    json.assert_status(StatusCode::CREATED);
+    json.assert_header("content-type", "application/json");
+    json.assert_header("vary", "accept");
+    assert_eq!(json.json::<User>(), expected);
+
+    let msgpack = client
+        .get("/user")
+        .header(ACCEPT, "application/json;q=0.5, application/msgpack")
+        .send()
+        .await;
+    msgpack.assert_status(StatusCode::CREATED);
+    msgpack.assert_header("content-type", "application/msgpack");
+    assert_eq!(
+        rmp_serde::from_slice::<User>(&msgpack.body).unwrap(),
+        expected
+    );
+
+    let cbor = client
+        .get("/user")
+        .header(ACCEPT, "application/cbor")
+        .send()
+        .await;
+    cbor.assert_header("content-type", "application/cbor");
+    assert_eq!(
+        ciborium::from_reader::<User, _>(&cbor.body[..]).unwrap(),
+        expected
+    );
+
+    client
+        .get("/user")
+        .header(ACCEPT, "text/html")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_ACCEPTABLE);
+}
+
+#[tokio::test]
+async fn accepts_uses_most_specific_range() {
+    let client = router().test_client();
+    client.get("/prefers").send().await.assert_text("text/csv");
+    client
+        .get("/prefers")
+        .header(ACCEPT, "text/*;q=0.3, */*;q=0.5")
+        .send()
+        .await
+        .assert_text("application/json");
+    client
+        .get("/prefers")
+        .header(ACCEPT, "image/png")
+        .send()
+        .await
+        .assert_text("none");
+}
@@ -0,0 +1,47 @@
+use miko::app::startup::{run_startup_tasks, startup_tasks};
+use miko::auto::init_container;
+use miko::macros::*;
+use std::sync::{Arc, Mutex};
+
+struct Journal {
+    lines: Mutex<Vec<String>>,
+}
+
+#[component]
+impl Journal {
+    async fn new() -> Self {
+        Journal {
+            lines: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[on_startup(order = 10)]
+async fn second(#[dep] journal: Arc<Journal>) {
+    journal.lines.lock().unwrap().push("second".to_string());
+}
+
+#[on_startup(order = -1)]
+async fn first(
+    #[dep] journal: Arc<Journal>,
+    #[config("startup_test.greeting:hello")] greeting: String,
+) -> Result<(), std::io::Error> {
+    journal.lines.lock().unwrap().push(greeting);
+    Ok(())
+}
+
+#[tokio::test]
+async fn startup_tasks_run_once_in_order_with_injection() {
+    let names: Vec<&str> = startup_tasks().iter().map(|t| t.name).collect();
+    assert_eq!(names, vec!["on_startup::first", "on_startup::second"]);
+
+    init_container().await;
+    run_startup_tasks().await.unwrap();
+    run_startup_tasks().await.unwrap();
+
+    let journal = miko::dependency_container::get_global_dc()
+        .await
+        .get::<Journal>()
+        .await;
+    assert_eq!(*journal.lines.lock().unwrap(), vec!["hello", "second"]);
+}
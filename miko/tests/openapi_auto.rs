@@ -0,0 +1,57 @@
+use miko::extractor::Json;
+use miko::macros::*;
+use miko::router::Router;
+use miko::{OpenApi, ToSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+struct Pet {
+    name: String,
+}
+
+/// 获取宠物
+#[get("/auto_doc/pets/{id}")]
+#[u_tag("pets")]
+#[u_response(status = 200, description = "ok", body = Pet)]
+async fn get_pet(#[path] id: u32) -> Json<Pet> {
+    Json(Pet {
+        name: id.to_string(),
+    })
+}
+
+/// 创建宠物
+#[post("/auto_doc/pets")]
+async fn create_pet(#[body] pet: Pet) -> Json<Pet> {
+    Json(pet)
+}
+
+#[derive(OpenApi)]
+#[openapi(info(title = "Pets", version = "2.0.0"))]
+struct ApiDoc;
+
+#[tokio::test]
+async fn collects_macro_routes_and_serves_them() {
+    let doc = miko::openapi::auto_doc_with(ApiDoc::openapi());
+    assert_eq!(doc.info.title, "Pets");
+    let get = doc.paths.paths["/auto_doc/pets/{id}"].get.as_ref().unwrap();
+    assert_eq!(get.summary.as_deref(), Some("获取宠物"));
+    assert_eq!(get.tags.as_deref(), Some(&["pets".to_string()][..]));
+    assert!(doc.paths.paths["/auto_doc/pets"].post.is_some());
+    assert!(doc.components.unwrap().schemas.contains_key("Pet"));
+
+    let mut router = Router::new();
+    router
+        .serve_openapi("/openapi.json")
+        .serve_scalar("/scalar");
+    let client = router.test_client();
+
+    let json: Value = client.get("/openapi.json").send().await.json();
+    assert_eq!(
+        json["paths"]["/auto_doc/pets/{id}"]["get"]["summary"],
+        "获取宠物"
+    );
+    let html = client.get("/scalar").send().await.text();
+    assert!(html.contains(r#"id="api-reference""#));
+    assert!(html.contains("/auto_doc/pets/{id}"));
+}
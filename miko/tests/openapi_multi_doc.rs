@@ -0,0 +1,47 @@
+use miko::macros::*;
+use miko::openapi::{DEFAULT_DOC, auto_doc, auto_doc_for, doc_names};
+use miko::router::Router;
+use serde_json::Value;
+
+#[get("/multi_doc/public")]
+async fn public_route() -> &'static str {
+    "public"
+}
+
+#[get("/multi_doc/internal")]
+#[u_doc("internal")]
+async fn internal_route() -> &'static str {
+    "internal"
+}
+
+#[get("/multi_doc/shared")]
+#[u_doc("internal", "admin")]
+async fn shared_route() -> &'static str {
+    "shared"
+}
+
+#[tokio::test]
+async fn splits_routes_into_documents() {
+    assert_eq!(doc_names(), vec!["admin", DEFAULT_DOC, "internal"]);
+
+    let paths = |doc: miko::utoipa::openapi::OpenApi| {
+        let mut paths: Vec<String> = doc.paths.paths.keys().cloned().collect();
+        paths.sort();
+        paths
+    };
+    assert_eq!(paths(auto_doc()), vec!["/multi_doc/public"]);
+    assert_eq!(
+        paths(auto_doc_for("internal")),
+        vec!["/multi_doc/internal", "/multi_doc/shared"]
+    );
+    assert_eq!(paths(auto_doc_for("admin")), vec!["/multi_doc/shared"]);
+
+    let mut router = Router::new();
+    router
+        .serve_openapi("/openapi.json")
+        .serve_openapi_for("/internal/openapi.json", "internal");
+    let client = router.test_client();
+    let json: Value = client.get("/internal/openapi.json").send().await.json();
+    assert!(json["paths"]["/multi_doc/internal"].is_object());
+    assert!(json["paths"]["/multi_doc/public"].is_null());
+}
@@ -0,0 +1,49 @@
+use miko::OpenApi;
+use miko::test::openapi::{assert_openapi_snapshot, canonical_json, canonical_yaml, diff_lines};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Snap", version = "1.0.0"),
+    tags((name = "zoo", description = "b"), (name = "app", description = "a: with colon"))
+)]
+struct ApiDoc;
+
+#[test]
+fn canonical_output_is_sorted() {
+    let json = canonical_json(&ApiDoc::openapi());
+    assert!(json.find("\"info\"").unwrap() < json.find("\"openapi\"").unwrap());
+    assert!(json.find("\"description\": \"b\"").unwrap() < json.find("\"name\": \"zoo\"").unwrap());
+
+    let yaml = canonical_yaml(&ApiDoc::openapi());
+    assert!(yaml.contains("  title: Snap\n  version: 1.0.0\nopenapi: 3.1.0\npaths: {}\n"));
+    assert!(yaml.ends_with(
+        "tags:\n  - description: b\n    name: zoo\n  - description: \"a: with colon\"\n    name: app\n"
+    ));
+}
+
+#[test]
+fn snapshot_is_written_then_compared() {
+    let dir = std::env::temp_dir().join(format!("miko-openapi-snap-{}", std::process::id()));
+    let path = dir.join("openapi.yaml");
+    let _ = std::fs::remove_file(&path);
+
+    assert_openapi_snapshot(&ApiDoc::openapi(), &path);
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        canonical_yaml(&ApiDoc::openapi())
+    );
+    assert_openapi_snapshot(&ApiDoc::openapi(), &path);
+
+    std::fs::write(
+        &path,
+        canonical_yaml(&ApiDoc::openapi()).replace("Snap", "Old"),
+    )
+    .unwrap();
+    let err = std::panic::catch_unwind(|| assert_openapi_snapshot(&ApiDoc::openapi(), &path))
+        .unwrap_err();
+    let msg = err.downcast_ref::<String>().unwrap();
+    assert!(msg.contains("-   title: Old\n+   title: Snap\n"));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(diff_lines("a\nb\n", "a\nb\n"), None);
+}
@@ -0,0 +1,31 @@
+use hyper::StatusCode;
+use miko::extractor::{Json, Path};
+use miko::http::response::into_response::OrNoContent;
+use miko::router::Router;
+
+fn find(id: u32) -> Option<String> {
+    (id == 1).then(|| "alice".to_string())
+}
+
+#[tokio::test]
+async fn option_maps_none_to_404_or_204() {
+    let mut router = Router::new();
+    router
+        .get("/users/{id}", |Path(id): Path<u32>| async move {
+            find(id).map(Json)
+        })
+        .delete("/users/{id}", |Path(id): Path<u32>| async move {
+            OrNoContent::from(find(id))
+        });
+    let client = router.test_client();
+
+    let resp = client.get("/users/1").send().await;
+    resp.assert_json("alice".to_string());
+    let resp = client.get("/users/2").send().await;
+    resp.assert_status(StatusCode::NOT_FOUND);
+
+    let resp = client.delete("/users/1").send().await;
+    resp.assert_text("alice");
+    let resp = client.delete("/users/2").send().await;
+    resp.assert_status(StatusCode::NO_CONTENT);
+}
@@ -0,0 +1,66 @@
+use miko::AppError;
+use miko::ext::otel::{OtelLayer, layer};
+use miko::extractor::Path;
+use miko::router::Router;
+use opentelemetry::trace::{SpanKind, Status};
+use opentelemetry::{Key, Value};
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+use tracing_subscriber::layer::SubscriberExt;
+
+fn attr(span: &opentelemetry_sdk::trace::SpanData, key: &str) -> Option<Value> {
+    span.attributes
+        .iter()
+        .find(|kv| kv.key == Key::from(key.to_string()))
+        .map(|kv| kv.value.clone())
+}
+
+#[tokio::test]
+async fn server_span_continues_incoming_trace() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry().with(layer(&provider));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let mut router = Router::new();
+    router
+        .get("/users/{id}", |Path(id): Path<u32>| async move {
+            if id == 0 {
+                Err(AppError::InternalServerError("boom".into()))
+            } else {
+                Ok(format!("user {}", id))
+            }
+        })
+        .with_layer(OtelLayer::new());
+    let client = router.test_client();
+
+    client
+        .get("/users/7")
+        .header(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )
+        .send()
+        .await
+        .assert_text("user 7");
+    client.get("/users/0").send().await;
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+    let ok = &spans[0];
+    assert_eq!(ok.name, "GET /users/{id}");
+    assert_eq!(ok.span_kind, SpanKind::Server);
+    assert_eq!(
+        ok.span_context.trace_id().to_string(),
+        "4bf92f3577b34da6a3ce929d0e0e4736"
+    );
+    assert_eq!(ok.parent_span_id.to_string(), "00f067aa0ba902b7");
+    assert_eq!(attr(ok, "http.route"), Some(Value::from("/users/{id}")));
+    assert_eq!(attr(ok, "url.path"), Some(Value::from("/users/7")));
+    assert_eq!(attr(ok, "http.response.status_code"), Some(Value::I64(200)));
+
+    let failed = &spans[1];
+    assert_ne!(failed.span_context.trace_id(), ok.span_context.trace_id());
+    assert!(matches!(failed.status, Status::Error { .. }));
+}
@@ -0,0 +1,57 @@
+use hyper::StatusCode;
+use miko::macros::*;
+
+fn default_size() -> u32 {
+    20
+}
+
+#[get("/defaults/items")]
+async fn items(
+    #[query(default = 1)] page: u32,
+    #[query(default_fn = "default_size")] size: u32,
+    #[query(default = "asc")] sort: String,
+) -> String {
+    format!("{} {} {}", page, size, sort)
+}
+
+#[get("/defaults/posts")]
+#[alias("/defaults/posts/{category}")]
+async fn posts(#[path(default = "all")] category: String, #[query] tag: Option<String>) -> String {
+    format!("{} {}", category, tag.unwrap_or_default())
+}
+
+#[tokio::test]
+async fn query_defaults_fill_missing_params() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    client
+        .get("/defaults/items")
+        .send()
+        .await
+        .assert_text("1 20 asc");
+    client
+        .get("/defaults/items?page=3&size=5&sort=desc")
+        .send()
+        .await
+        .assert_text("3 5 desc");
+
+    // 提供了值但解析失败时仍返回 400
+    let resp = client.get("/defaults/items?page=x").send().await;
+    resp.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn path_default_applies_when_route_lacks_param() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    client
+        .get("/defaults/posts?tag=rust")
+        .send()
+        .await
+        .assert_text("all rust");
+    client
+        .get("/defaults/posts/news")
+        .send()
+        .await
+        .assert_text("news ");
+}
@@ -0,0 +1,142 @@
+use hyper::StatusCode;
+use miko::ToSchema;
+use miko::extractor::{Path, PathDe};
+use miko::macros::get;
+use miko::router::Router;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Ids {
+    user_id: u64,
+    org: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Posts,
+    Comments,
+}
+
+#[tokio::test]
+async fn path_deserializes_structs_tuples_and_scalars() {
+    let mut router = Router::new();
+    router
+        .get(
+            "/orgs/{org}/users/{user_id}",
+            |PathDe(ids): PathDe<Ids>| async move { format!("{}:{}", ids.org, ids.user_id) },
+        )
+        .get(
+            "/pairs/{a}/{b}",
+            |PathDe((a, b)): PathDe<(u32, String)>| async move { format!("{a}-{b}") },
+        )
+        .get(
+            "/feed/{kind}/{page}",
+            |PathDe(kind): PathDe<Kind>, Path(page): Path<u8>| async move {
+                let kind = match kind {
+                    Kind::Posts => "posts",
+                    Kind::Comments => "comments",
+                };
+                format!("{kind} {page}")
+            },
+        );
+    let client = router.test_client();
+
+    client
+        .get("/orgs/acme/users/42")
+        .send()
+        .await
+        .assert_text("acme:42");
+    client.get("/pairs/1/x").send().await.assert_text("1-x");
+    client
+        .get("/feed/comments/3")
+        .send()
+        .await
+        .assert_text("comments 3");
+
+    let resp = client.get("/orgs/acme/users/nope").send().await;
+    resp.assert_status(StatusCode::BAD_REQUEST);
+    assert!(resp.text().contains("`user_id`"), "{}", resp.text());
+    client
+        .get("/feed/likes/3")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[get("/macro/orgs/{org}/members/{id}")]
+async fn member(#[path] id: u32, #[path] org: String) -> String {
+    format!("{org}/{id}")
+}
+
+#[get("/macro/teams/{team_id}")]
+async fn team(#[path("team_id")] team: u32) -> String {
+    team.to_string()
+}
+
+#[tokio::test]
+async fn path_attribute_args_match_by_name() {
+    let client = miko::auto::collect_global_router().test_client();
+    client
+        .get("/macro/orgs/acme/members/7")
+        .send()
+        .await
+        .assert_text("acme/7");
+    client.get("/macro/teams/9").send().await.assert_text("9");
+}
+
+#[derive(ToSchema)]
+struct Slug(String);
+
+impl std::str::FromStr for Slug {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+            Ok(Slug(s.to_string()))
+        } else {
+            Err(format!("invalid slug `{s}`"))
+        }
+    }
+}
+
+#[tokio::test]
+async fn path_parses_from_str_types() {
+    let mut router = Router::new();
+    router.get(
+        "/posts/{slug}",
+        |Path(slug): Path<Slug>| async move { slug.0 },
+    );
+    let client = router.test_client();
+
+    client
+        .get("/posts/hello-miko")
+        .send()
+        .await
+        .assert_text("hello-miko");
+    client
+        .get("/posts/Hello")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[get("/macro/posts/{slug}")]
+async fn post_by_slug(#[path] slug: Slug) -> String {
+    slug.0
+}
+
+#[tokio::test]
+async fn path_attribute_parses_from_str_types() {
+    let client = miko::auto::collect_global_router().test_client();
+    client
+        .get("/macro/posts/hello-miko")
+        .send()
+        .await
+        .assert_text("hello-miko");
+    client
+        .get("/macro/posts/Hello")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
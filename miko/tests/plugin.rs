@@ -0,0 +1,121 @@
+use hyper::StatusCode;
+use miko::app::Application;
+use miko::app::config::ServerSettings;
+use miko::app::plugin::{MikoPlugin, PluginRegistration, configure_registered_plugins};
+use miko::auto::init_container;
+use miko::dependency_container::LazyDependencyContainer;
+use miko::macros::*;
+use miko::router::Router;
+use std::any::Any;
+use std::sync::Arc;
+
+struct Motd(&'static str);
+
+/// 注册一个组件，供自动注册的处理器通过 `#[dep]` 注入
+struct MotdPlugin;
+
+impl MikoPlugin for MotdPlugin {
+    fn name(&self) -> &'static str {
+        "motd"
+    }
+
+    fn configure(
+        &self,
+        _: &mut Router,
+        container: &mut LazyDependencyContainer,
+        _: &mut ServerSettings,
+    ) {
+        container.register::<Motd>(false, || {
+            Box::pin(async { Arc::new(Motd("be kind")) as Arc<dyn Any + Send + Sync> })
+        });
+    }
+}
+
+/// 顺序靠前，注册路由并修改端口
+struct HealthPlugin;
+
+impl MikoPlugin for HealthPlugin {
+    fn name(&self) -> &'static str {
+        "health"
+    }
+
+    fn order(&self) -> i32 {
+        -10
+    }
+
+    fn configure(
+        &self,
+        router: &mut Router,
+        _: &mut LazyDependencyContainer,
+        settings: &mut ServerSettings,
+    ) {
+        settings.port = 9999;
+        router.get("/health", || async { "ok" });
+    }
+}
+
+miko::inventory::submit! { PluginRegistration(|| Box::new(MotdPlugin)) }
+miko::inventory::submit! { PluginRegistration(|| Box::new(HealthPlugin)) }
+
+#[get("/motd")]
+async fn motd(#[dep] motd: Arc<Motd>) -> &'static str {
+    motd.0
+}
+
+#[tokio::test]
+async fn registered_plugins_run_in_order_before_container_validation() {
+    let names: Vec<_> = miko::app::plugin::registered_plugins()
+        .iter()
+        .map(|p| p.name())
+        .collect();
+    assert_eq!(names, ["health", "motd"]);
+
+    let mut settings = ServerSettings::builder().port(8080).build();
+    let mut router = Router::new();
+    configure_registered_plugins(&mut router, &mut settings);
+    // MotdPlugin 注册了 Motd，依赖校验通过
+    init_container().await;
+    assert_eq!(settings.port, 9999);
+
+    router.merge(miko::auto::collect_global_router());
+    let client = router.test_client();
+    client.get("/health").send().await.assert_text("ok");
+    client.get("/motd").send().await.assert_text("be kind");
+}
+
+struct EchoPlugin;
+
+impl MikoPlugin for EchoPlugin {
+    fn name(&self) -> &'static str {
+        "echo"
+    }
+
+    fn configure(
+        &self,
+        router: &mut Router,
+        _: &mut LazyDependencyContainer,
+        settings: &mut ServerSettings,
+    ) {
+        settings.body_limit = Some("4b".to_string());
+        router.post("/echo", |body: String| async move { body });
+    }
+}
+
+#[tokio::test]
+async fn with_plugin_mounts_routes_and_applies_settings() {
+    let mut app =
+        Application::new(ServerSettings::builder().build(), Router::new()).with_plugin(EchoPlugin);
+    let client = app.test_client();
+    client
+        .post("/echo")
+        .text("hi")
+        .send()
+        .await
+        .assert_text("hi");
+    client
+        .post("/echo")
+        .text("too long")
+        .send()
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+}
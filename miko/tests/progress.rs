@@ -0,0 +1,130 @@
+use bytes::Bytes;
+use futures::StreamExt;
+use http_body_util::{BodyExt, Empty, StreamBody};
+use hyper::Request;
+use hyper::body::Frame;
+use miko::handler::Resp;
+use miko::middleware::progress::{ProgressLayer, TransferProgress, TransferState, TransferToken};
+use miko::miko_core::MikoError;
+use miko::router::Router;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tower::ServiceExt;
+
+fn chunked(n: usize) -> Resp {
+    let stream = futures::stream::iter(0..n).then(|_| async {
+        tokio::task::yield_now().await;
+        Ok::<_, MikoError>(Frame::data(Bytes::from_static(b"0123456789")))
+    });
+    Resp::new(StreamBody::new(stream).boxed_unsync())
+}
+
+fn recording_router(
+    events: Arc<Mutex<Vec<TransferProgress>>>,
+    stall: Option<Duration>,
+) -> (Router, oneshot::Receiver<()>) {
+    let (cancelled_tx, cancelled_rx) = oneshot::channel();
+    let cancelled_tx = Arc::new(Mutex::new(Some(cancelled_tx)));
+    let mut router = Router::new();
+    router.get("/download", move |token: TransferToken| {
+        let cancelled_tx = cancelled_tx.clone();
+        async move {
+            // 模拟为下载持续产生数据的后台工作
+            tokio::spawn(async move {
+                token.cancelled().await;
+                if let Some(tx) = cancelled_tx.lock().unwrap().take() {
+                    let _ = tx.send(());
+                }
+            });
+            chunked(5)
+        }
+    });
+    let mut layer = ProgressLayer::new(move |p| events.lock().unwrap().push(*p));
+    if let Some(timeout) = stall {
+        layer = layer.stall_timeout(timeout);
+    }
+    router.with_layer(layer);
+    (router, cancelled_rx)
+}
+
+fn request() -> miko::handler::Req {
+    Request::get("/download")
+        .body(Empty::new().map_err(Into::into).boxed_unsync())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn reports_progress_until_completion() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let (router, mut cancelled) = recording_router(events.clone(), None);
+    let resp = router
+        .into_tower_service()
+        .oneshot(request())
+        .await
+        .unwrap();
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body.len(), 50);
+
+    let events = events.lock().unwrap();
+    let streamed: Vec<u64> = events
+        .iter()
+        .filter(|p| p.state == TransferState::Streaming)
+        .map(|p| p.bytes_written)
+        .collect();
+    assert_eq!(streamed, vec![10, 20, 30, 40, 50]);
+    let last = events.last().unwrap();
+    assert_eq!(
+        (last.state, last.bytes_written),
+        (TransferState::Completed, 50)
+    );
+    assert!(cancelled.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn client_disconnect_aborts_and_cancels_work() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let (router, cancelled) = recording_router(events.clone(), None);
+    let resp = router
+        .into_tower_service()
+        .oneshot(request())
+        .await
+        .unwrap();
+    let mut body = resp.into_body();
+    body.frame().await.unwrap().unwrap();
+    drop(body);
+
+    tokio::time::timeout(Duration::from_secs(1), cancelled)
+        .await
+        .unwrap()
+        .unwrap();
+    let last = *events.lock().unwrap().last().unwrap();
+    assert_eq!(
+        (last.state, last.bytes_written),
+        (TransferState::Aborted, 10)
+    );
+}
+
+#[tokio::test]
+async fn stalled_transfer_is_cancelled() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let (router, cancelled) = recording_router(events.clone(), Some(Duration::from_millis(50)));
+    let resp = router
+        .into_tower_service()
+        .oneshot(request())
+        .await
+        .unwrap();
+    let mut body = resp.into_body();
+    body.frame().await.unwrap().unwrap();
+
+    // 停止读取，直到被判定为停滞
+    tokio::time::timeout(Duration::from_secs(1), cancelled)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        events.lock().unwrap().last().unwrap().state,
+        TransferState::Stalled
+    );
+    assert!(body.frame().await.unwrap().is_err());
+}
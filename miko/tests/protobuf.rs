@@ -0,0 +1,70 @@
+use hyper::StatusCode;
+use hyper::header::CONTENT_TYPE;
+use miko::extractor::Protobuf;
+use miko::router::Router;
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct Ping {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(uint32, tag = "2")]
+    count: u32,
+}
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router.post("/ping", |Protobuf(ping): Protobuf<Ping>| async move {
+        Protobuf(Ping {
+            name: format!("pong {}", ping.name),
+            count: ping.count + 1,
+        })
+    });
+    router
+}
+
+#[tokio::test]
+async fn decodes_request_and_encodes_response() {
+    let client = router().test_client();
+    let resp = client
+        .post("/ping")
+        .protobuf(&Ping {
+            name: "miko".into(),
+            count: 1,
+        })
+        .send()
+        .await;
+    resp.assert_ok();
+    resp.assert_header("content-type", "application/x-protobuf");
+    assert_eq!(
+        resp.protobuf::<Ping>(),
+        Ping {
+            name: "pong miko".into(),
+            count: 2,
+        }
+    );
+}
+
+#[tokio::test]
+async fn rejects_wrong_content_type_and_invalid_bytes() {
+    let client = router().test_client();
+    client
+        .post("/ping")
+        .header(CONTENT_TYPE, "application/json")
+        .text("{}")
+        .send()
+        .await
+        .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let resp = client
+        .post("/ping")
+        .header(CONTENT_TYPE, "application/protobuf")
+        .bytes(vec![0x0a, 0xff])
+        .send()
+        .await;
+    resp.assert_status(StatusCode::BAD_REQUEST);
+    assert!(
+        resp.text().contains("Invalid protobuf body"),
+        "{}",
+        resp.text()
+    );
+}
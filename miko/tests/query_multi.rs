@@ -0,0 +1,64 @@
+use hyper::StatusCode;
+use miko::extractor::QueryMulti;
+use miko::macros::*;
+use miko::router::Router;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct Filter {
+    id: Vec<u32>,
+    tag: Option<String>,
+}
+
+#[get("/macro/items")]
+async fn macro_items(#[query] id: Vec<u32>, #[query] sort: Option<String>) -> String {
+    format!("{:?} {}", id, sort.unwrap_or_default())
+}
+
+#[tokio::test]
+async fn query_multi_collects_repeated_keys() {
+    let mut router = Router::new();
+    router
+        .get("/raw", |QueryMulti(all): QueryMulti| async move {
+            let mut ids = all.get("id").cloned().unwrap_or_default();
+            ids.sort();
+            ids.join(",")
+        })
+        .get("/typed", |QueryMulti(f): QueryMulti<Filter>| async move {
+            format!("{:?} {}", f.id, f.tag.unwrap_or_default())
+        });
+    let client = router.test_client();
+
+    let resp = client.get("/raw?id=2&id=1&name=x").send().await;
+    resp.assert_text("1,2");
+
+    let resp = client.get("/typed?id=1&id=2&id[]=3&tag=a").send().await;
+    resp.assert_text("[1, 2, 3] a");
+
+    let resp = client.get("/typed?id=7").send().await;
+    resp.assert_text("[7] ");
+
+    let resp = client.get("/typed?id=x").send().await;
+    resp.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn query_macro_accepts_vec_params() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    let resp = client.get("/macro/items?id=3&id=4&sort=asc").send().await;
+    resp.assert_text("[3, 4] asc");
+
+    // 缺省的 Vec 参数为空
+    let resp = client.get("/macro/items").send().await;
+    resp.assert_text("[] ");
+}
+
+#[test]
+fn from_query_str_groups_values() {
+    let map: HashMap<String, Vec<String>> =
+        miko::extractor::query_multi::from_query_str("a=1&b=2&a=3").unwrap();
+    assert_eq!(map["a"], vec!["1", "3"]);
+    assert_eq!(map["b"], vec!["2"]);
+}
@@ -0,0 +1,87 @@
+use hyper::StatusCode;
+use hyper::header::{HeaderName, HeaderValue, RETRY_AFTER};
+use miko::ext::rate_limit::RateLimitLayer;
+use miko::macros::*;
+use miko::router::Router;
+
+#[get("/rate/login")]
+#[layer(RateLimitLayer::per_minute(1).global())]
+async fn login() -> &'static str {
+    "welcome"
+}
+
+fn api_key_router(layer: RateLimitLayer) -> Router {
+    let mut router = Router::new();
+    router
+        .get("/", || async { "ok" })
+        .with_layer(layer.key(|req| {
+            req.headers()
+                .get("x-api-key")?
+                .to_str()
+                .ok()
+                .map(str::to_string)
+        }));
+    router
+}
+
+#[tokio::test]
+async fn token_bucket_limits_each_key_and_sets_retry_after() {
+    let client = api_key_router(RateLimitLayer::per_minute(2)).test_client();
+    let key = HeaderName::from_static("x-api-key");
+
+    for _ in 0..2 {
+        client
+            .get("/")
+            .header(key.clone(), HeaderValue::from_static("a"))
+            .send()
+            .await
+            .assert_ok();
+    }
+    let resp = client
+        .get("/")
+        .header(key.clone(), HeaderValue::from_static("a"))
+        .send()
+        .await;
+    resp.assert_status(StatusCode::TOO_MANY_REQUESTS);
+    let retry: u64 = resp.headers()[RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((29..=30).contains(&retry), "retry-after {}", retry);
+
+    // 其它客户端与无法识别的请求不受影响
+    client
+        .get("/")
+        .header(key, HeaderValue::from_static("b"))
+        .send()
+        .await
+        .assert_ok();
+    client.get("/").send().await.assert_ok();
+}
+
+#[test]
+fn sliding_window_rejects_bursts_beyond_limit() {
+    let layer = RateLimitLayer::per_minute(3)
+        .burst(10)
+        .sliding_window()
+        .global();
+    assert!((0..3).all(|_| layer.check("").is_ok()));
+    let wait = layer.check("").unwrap_err();
+    assert!(wait.as_secs() >= 59);
+}
+
+#[tokio::test]
+async fn route_layer_limits_single_route() {
+    let client = miko::auto::collect_global_router().test_client();
+    client
+        .get("/rate/login")
+        .send()
+        .await
+        .assert_text("welcome");
+    client
+        .get("/rate/login")
+        .send()
+        .await
+        .assert_status(StatusCode::TOO_MANY_REQUESTS);
+}
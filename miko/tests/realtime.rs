@@ -0,0 +1,49 @@
+use http_body_util::BodyExt;
+use hyper::{Request, StatusCode};
+use miko::ext::realtime::Realtime;
+use miko::router::Router;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn sse_subscriber_receives_published_messages() {
+    let hub = Realtime::new();
+    let mut router = Router::new();
+    router.realtime_with("/events", hub.clone());
+    let svc = router.into_tower_service();
+
+    let req = Request::get("/events?topic=news")
+        .body(
+            http_body_util::Empty::new()
+                .map_err(Into::into)
+                .boxed_unsync(),
+        )
+        .unwrap();
+    let resp = svc.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(hub.subscribers("news"), 1);
+
+    assert_eq!(hub.publish("news", "hello"), 1);
+    let mut body = resp.into_body();
+    let frame = body.frame().await.unwrap().unwrap().into_data().unwrap();
+    assert_eq!(frame, "event: news\nid: 1\ndata: hello\n\n");
+}
+
+#[tokio::test]
+async fn topic_auth_rejects_subscription() {
+    let hub = Realtime::new();
+    hub.authorize("admin.*", |_, parts| parts.headers.contains_key("x-admin"));
+    let mut router = Router::new();
+    router.realtime_with("/events", hub);
+    let client = router.test_client();
+
+    client
+        .get("/events?topic=admin.audit")
+        .send()
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+    client
+        .get("/events")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
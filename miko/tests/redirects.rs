@@ -0,0 +1,78 @@
+use hyper::StatusCode;
+use hyper::header::LOCATION;
+use miko::app::Application;
+use miko::app::config::ServerSettings;
+use miko::macros::*;
+use miko::router::Router;
+
+#[get("/alias/users/{id}")]
+#[alias("/alias/legacy/{id}")]
+async fn user(#[path] id: u32) -> String {
+    format!("user {}", id)
+}
+
+#[alias("/alias/old-ping")]
+#[get("/alias/ping")]
+async fn ping() -> &'static str {
+    "pong"
+}
+
+#[tokio::test]
+async fn router_redirect_substitutes_params_and_keeps_query() {
+    let mut router = Router::new();
+    router
+        .redirect("/old", "/new", 301)
+        .redirect("/blog/{slug}", "/posts/{slug}", 308);
+    let client = router.test_client();
+
+    let resp = client.get("/old?page=2").send().await;
+    resp.assert_status(StatusCode::MOVED_PERMANENTLY);
+    resp.assert_header(LOCATION, "/new?page=2");
+
+    let resp = client.post("/blog/hello").send().await;
+    resp.assert_status(StatusCode::PERMANENT_REDIRECT);
+    resp.assert_header(LOCATION, "/posts/hello");
+}
+
+#[test]
+#[should_panic(expected = "invalid redirect status")]
+fn redirect_rejects_non_redirect_status() {
+    Router::new().redirect("/a", "/b", 200);
+}
+
+#[tokio::test]
+async fn config_redirects_and_aliases() {
+    let settings: ServerSettings = serde_json::from_value(serde_json::json!({
+        "host": "127.0.0.1",
+        "port": 0,
+        "redirects": {
+            "/docs": "/guide",
+            "/tmp": { "to": "/elsewhere", "status": 307 }
+        }
+    }))
+    .unwrap();
+    let mut app = Application::new(settings, miko::auto::collect_global_router());
+    let client = app.test_client();
+
+    let resp = client.get("/docs").send().await;
+    resp.assert_status(StatusCode::MOVED_PERMANENTLY);
+    resp.assert_header(LOCATION, "/guide");
+    let resp = client.get("/tmp").send().await;
+    resp.assert_status(StatusCode::TEMPORARY_REDIRECT);
+
+    client
+        .get("/alias/users/1")
+        .send()
+        .await
+        .assert_text("user 1");
+    client
+        .get("/alias/legacy/2")
+        .send()
+        .await
+        .assert_text("user 2");
+    client
+        .get("/alias/old-ping")
+        .send()
+        .await
+        .assert_text("pong");
+}
@@ -0,0 +1,71 @@
+use hyper::header::{HeaderName, HeaderValue};
+use miko::context::RequestContext;
+use miko::error::get_trace_id;
+use miko::http::response::sse::spawn_sse_event;
+use miko::router::Router;
+use std::time::Duration;
+
+#[tokio::test]
+async fn spawned_tasks_keep_the_request_context() {
+    let mut router = Router::new();
+    router
+        .get("/spawn", || async {
+            miko::spawn_with_context(async { get_trace_id().unwrap_or_default() })
+                .await
+                .unwrap()
+        })
+        .get("/plain", || async {
+            tokio::spawn(async { get_trace_id().unwrap_or_default() })
+                .await
+                .unwrap()
+        })
+        .get("/sse", || async {
+            spawn_sse_event(|sender| async move {
+                let id = get_trace_id().unwrap_or_default();
+                sender.send(id).await.or_break();
+            })
+        });
+    let client = router.test_client();
+    let trace = HeaderName::from_static("x-trace-id");
+
+    client
+        .get("/spawn")
+        .header(trace.clone(), HeaderValue::from_static("trace-123"))
+        .send()
+        .await
+        .assert_text("trace-123");
+    client
+        .get("/plain")
+        .header(trace.clone(), HeaderValue::from_static("trace-123"))
+        .send()
+        .await
+        .assert_text("");
+    let body = client
+        .get("/sse")
+        .header(trace, HeaderValue::from_static("trace-sse"))
+        .send()
+        .await
+        .text();
+    assert!(body.contains("data: trace-sse"), "{body}");
+}
+
+#[tokio::test]
+async fn scope_sets_tenant_and_deadline() {
+    let ctx = RequestContext::default()
+        .with_trace_id("t-1")
+        .with_tenant("acme")
+        .with_timeout(Duration::from_secs(5))
+        .with_timeout(Duration::from_secs(60));
+
+    let seen = ctx
+        .clone()
+        .scope(async {
+            miko::spawn_with_context(async { RequestContext::current() })
+                .await
+                .unwrap()
+        })
+        .await;
+    assert_eq!(seen, ctx);
+    assert!(seen.remaining().unwrap() <= Duration::from_secs(5));
+    assert_eq!(RequestContext::current(), RequestContext::default());
+}
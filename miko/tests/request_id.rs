@@ -0,0 +1,60 @@
+use miko::AppError;
+use miko::error::get_trace_id;
+use miko::hyper::header::{HeaderName, HeaderValue};
+use miko::middleware::request_id::{RequestId, RequestIdLayer};
+use miko::router::Router;
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router
+        .get("/id", |id: RequestId| async move {
+            tokio::task::yield_now().await;
+            format!("{} {}", id, get_trace_id().unwrap())
+        })
+        .get("/fail", || async {
+            Err::<(), _>(AppError::Forbidden("no".into()))
+        });
+    router
+}
+
+#[tokio::test]
+async fn propagates_incoming_request_id() {
+    let mut router = router();
+    router.with_layer(RequestIdLayer::new());
+    let client = router.test_client();
+
+    let resp = client
+        .get("/id")
+        .header(
+            HeaderName::from_static("x-request-id"),
+            HeaderValue::from_static("abc-123"),
+        )
+        .send()
+        .await;
+    resp.assert_header("x-request-id", "abc-123");
+    assert_eq!(resp.text(), "abc-123 abc-123");
+}
+
+#[tokio::test]
+async fn generates_id_and_attaches_it_to_errors() {
+    let mut router = router();
+    router.with_layer(RequestIdLayer::new().header("x-correlation-id"));
+    let client = router.test_client();
+
+    let first = client.get("/id").send().await;
+    let second = client.get("/id").send().await;
+    let id = first.headers()["x-correlation-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_ne!(id, second.headers()["x-correlation-id"].to_str().unwrap());
+    assert_eq!(first.text(), format!("{id} {id}"));
+
+    let resp = client.get("/fail").send().await;
+    let id = resp.headers()["x-correlation-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body: serde_json::Value = resp.json();
+    assert_eq!(body["trace_id"], id.as_str());
+}
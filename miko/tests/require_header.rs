@@ -0,0 +1,64 @@
+use hyper::StatusCode;
+use hyper::header::{HeaderName, HeaderValue};
+use miko::macros::*;
+
+#[get("/versioned/items")]
+#[require_header("X-Api-Version", matches = r"2\.\d+", status = 426)]
+async fn versioned_items() -> &'static str {
+    "v2"
+}
+
+#[require_header("X-Tenant")]
+mod tenant {
+    use miko::macros::*;
+
+    #[get("/tenant/orders")]
+    async fn orders() -> &'static str {
+        "orders"
+    }
+}
+
+fn header(name: &'static str, value: &'static str) -> (HeaderName, HeaderValue) {
+    (
+        HeaderName::from_static(name),
+        HeaderValue::from_static(value),
+    )
+}
+
+#[tokio::test]
+async fn require_header_guards_routes_and_modules() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    let (name, value) = header("x-api-version", "2.1");
+    client
+        .get("/versioned/items")
+        .header(name, value)
+        .send()
+        .await
+        .assert_text("v2");
+    let (name, value) = header("x-api-version", "1.9");
+    client
+        .get("/versioned/items")
+        .header(name, value)
+        .send()
+        .await
+        .assert_status(StatusCode::UPGRADE_REQUIRED);
+    client
+        .get("/versioned/items")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    client
+        .get("/tenant/orders")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    let (name, value) = header("x-tenant", "acme");
+    client
+        .get("/tenant/orders")
+        .header(name, value)
+        .send()
+        .await
+        .assert_text("orders");
+}
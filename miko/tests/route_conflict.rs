@@ -0,0 +1,78 @@
+use hyper::StatusCode;
+use miko::router::Router;
+use miko::router::conflict::{RouteConflictPolicy, RouteError};
+
+async fn index() -> &'static str {
+    "main"
+}
+
+fn admin() -> Router {
+    let mut router = Router::new();
+    router.get("/", || async { "admin" });
+    router.get("/users", || async { "users" });
+    router
+}
+
+#[tokio::test]
+async fn try_nest_reports_conflict_and_leaves_router_unchanged() {
+    let mut router = Router::new();
+    router.get("/admin/", index);
+
+    let err = router.try_nest("/admin", admin()).err().unwrap();
+    match &err {
+        RouteError::Conflict {
+            path,
+            existing,
+            existing_source,
+            ..
+        } => {
+            assert_eq!(path, "/admin/");
+            assert_eq!(existing, "/admin/");
+            assert!(existing_source.is_some());
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    let message = err.to_string();
+    assert!(message.contains("GET /admin/"), "{message}");
+    assert!(message.contains("index"), "{message}");
+    assert!(message.contains("route_conflict.rs"), "{message}");
+    assert!(router.try_route("GET", "/other", index).is_ok());
+
+    let client = router.test_client();
+    client.get("/admin/").send().await.assert_text("main");
+    client
+        .get("/admin/users")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn override_policy_replaces_conflicting_routes() {
+    let mut router = Router::new();
+    router.get("/files/{id}", || async { "by id" });
+    router.get("/dav/{*path}", || async { "dav" });
+    router.on_conflict(RouteConflictPolicy::Override);
+    router.get("/files/{name}", || async { "by name" });
+    router.nest("/dav", {
+        let mut dav = Router::new();
+        dav.get("/{*rest}", || async { "nested dav" });
+        dav
+    });
+
+    let client = router.test_client();
+    client.get("/files/a").send().await.assert_text("by name");
+    client
+        .get("/dav/x/y")
+        .send()
+        .await
+        .assert_text("nested dav");
+}
+
+#[test]
+#[should_panic(expected = "conflicts with `GET /`")]
+fn duplicate_route_panics_by_default() {
+    let mut router = Router::new();
+    router.get("/", index);
+    router.get("/", index);
+}
@@ -0,0 +1,122 @@
+use hyper::StatusCode;
+use hyper::header::{HeaderName, HeaderValue};
+use miko::app::Application;
+use miko::app::config::{RouteOverride, RouteRateLimit, ServerSettings};
+use miko::ext::auth::{self, ApiKeyAuth, Identity};
+use miko::router::Router;
+use std::time::Duration;
+
+#[tokio::test]
+async fn config_overrides_apply_to_matching_routes() {
+    let mut router = Router::new();
+    router
+        .post(
+            "/upload",
+            |body: String| async move { body.len().to_string() },
+        )
+        .post("/echo", |body: String| async move { body })
+        .get("/slow", || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            "done"
+        })
+        .get("/limited/{id}", || async { "ok" });
+
+    let settings = ServerSettings::builder()
+        .body_limit("8")
+        .route(
+            "/upload",
+            RouteOverride {
+                body_limit: Some("1KB".into()),
+                ..Default::default()
+            },
+        )
+        .route(
+            "/slow",
+            RouteOverride {
+                timeout_ms: Some(20),
+                ..Default::default()
+            },
+        )
+        .route(
+            "/limited/{id}",
+            RouteOverride {
+                rate_limit: Some(RouteRateLimit {
+                    limit: 1,
+                    period_secs: 60,
+                    burst: None,
+                    key: Some("global".into()),
+                }),
+                ..Default::default()
+            },
+        )
+        .build();
+    let client = Application::new(settings, router).test_client();
+
+    let body = "x".repeat(100);
+    client
+        .post("/upload")
+        .text(&body)
+        .send()
+        .await
+        .assert_text("100");
+    client
+        .post("/echo")
+        .text(&body)
+        .send()
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+
+    client
+        .get("/slow")
+        .send()
+        .await
+        .assert_status(StatusCode::GATEWAY_TIMEOUT);
+
+    client.get("/limited/1").send().await.assert_ok();
+    client
+        .get("/limited/2")
+        .send()
+        .await
+        .assert_status(StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn role_override_guards_route() {
+    auth::install(ApiKeyAuth::header("x-api-key", |key| match key {
+        "root" => Some(Identity::new("root").role("admin")),
+        "guest" => Some(Identity::new("guest")),
+        _ => None,
+    }));
+    let mut router = Router::new();
+    router.get("/admin", || async { "secret" });
+    router.route_overrides(
+        &[(
+            "/admin".to_string(),
+            RouteOverride {
+                role: Some("admin".into()),
+                ..Default::default()
+            },
+        )]
+        .into(),
+    );
+    let client = router.test_client();
+    let key = HeaderName::from_static("x-api-key");
+
+    client
+        .get("/admin")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    client
+        .get("/admin")
+        .header(key.clone(), HeaderValue::from_static("guest"))
+        .send()
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+    client
+        .get("/admin")
+        .header(key, HeaderValue::from_static("root"))
+        .send()
+        .await
+        .assert_text("secret");
+}
@@ -0,0 +1,86 @@
+use hyper::Method;
+use miko::macros::*;
+use miko::router::Router;
+
+async fn list_users() -> &'static str {
+    "[]"
+}
+
+async fn create_user() -> &'static str {
+    "ok"
+}
+
+#[test]
+fn routes_iter_reports_handlers_and_locations() {
+    let mut api = Router::new();
+    api.get("/users", list_users).post("/users", create_user);
+    let mut router = Router::new();
+    router.get("/", || async { "home" });
+    let line = line!() - 1;
+    router.nest("/api", api);
+    router.redirect("/old", "/", 301);
+
+    let routes: Vec<_> = router
+        .routes_iter()
+        .filter(|(method, _, _)| **method == Method::GET || **method == Method::POST)
+        .map(|(method, path, source)| (method.clone(), path.to_string(), source.clone()))
+        .collect();
+    let paths: Vec<_> = routes
+        .iter()
+        .map(|(m, p, _)| format!("{} {}", m, p))
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "GET /",
+            "GET /api/users",
+            "POST /api/users",
+            "GET /old",
+            "POST /old"
+        ]
+    );
+
+    let home = &routes[0].2;
+    assert!(home.handler.contains("{{closure}}"));
+    let location = home.location.unwrap();
+    assert!(location.file().ends_with("routes_iter.rs"));
+    assert_eq!(location.line(), line);
+    assert!(routes[1].2.handler.ends_with("::list_users"));
+    assert!(routes[2].2.handler.ends_with("::create_user"));
+    assert_eq!(routes[3].2.handler, "<redirect to />");
+
+    let table = router.routes_table();
+    assert!(table.starts_with("METHOD"));
+    assert!(
+        table
+            .lines()
+            .any(|l| l.starts_with("POST") && l.contains("::create_user"))
+    );
+}
+
+#[get("/macro/plain")]
+async fn plain() -> &'static str {
+    "plain"
+}
+
+#[get("/macro/layered")]
+#[layer(miko::middleware::extension::ExtensionLayer::new(1u8))]
+async fn layered() -> &'static str {
+    "layered"
+}
+
+#[test]
+fn macro_routes_keep_handler_names() {
+    let router = miko::auto::collect_global_router();
+    let handler = |path: &str| {
+        router
+            .routes_iter()
+            .find(|(_, p, _)| *p == path)
+            .map(|(_, _, source)| source.clone())
+            .unwrap()
+    };
+    assert!(handler("/macro/plain").handler.ends_with("::plain"));
+    let layered = handler("/macro/layered");
+    assert!(layered.handler.ends_with("::layered"));
+    assert!(layered.location.unwrap().file().ends_with("routes_iter.rs"));
+}
@@ -0,0 +1,38 @@
+use miko::extractor::Json;
+use miko::middleware::scrub::ScrubLayer;
+use miko::router::Router;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn scrubs_json_responses() {
+    let mut router = Router::new();
+    router
+        .get("/users", || async {
+            Json(json!({
+                "user": { "name": "ann", "password": "hunter2", "token": "abcdef123456" },
+                "sessions": [{ "password": "x", "internal_id": 7 }]
+            }))
+        })
+        .get("/plain", || async { "password=hunter2" });
+    router.with_layer(
+        ScrubLayer::new()
+            .mask("**.password")
+            .truncate("*.token", 4)
+            .remove("sessions.*.internal_id"),
+    );
+    let client = router.test_client();
+
+    let body: Value = client.get("/users").send().await.json();
+    assert_eq!(
+        body,
+        json!({
+            "user": { "name": "ann", "password": "***", "token": "abcd..." },
+            "sessions": [{ "password": "***" }]
+        })
+    );
+    client
+        .get("/plain")
+        .send()
+        .await
+        .assert_text("password=hunter2");
+}
@@ -0,0 +1,68 @@
+use hyper::StatusCode;
+use hyper::header::LOCATION;
+use miko::extractor::Json;
+use miko::http::response::into_response::{Accepted, Created, NoContent, Redirect};
+use miko::router::Router;
+use serde_json::json;
+
+#[tokio::test]
+async fn redirect_constructors_set_status_and_location() {
+    let mut router = Router::new();
+    router
+        .get("/moved", || async { Redirect::permanent("/new") })
+        .get("/elsewhere", || async { Redirect::temporary("/tmp") })
+        .post("/login", || async { Redirect::see_other("/dashboard") })
+        .get("/broken", || async { Redirect::temporary("/a\nb") });
+    let client = router.test_client();
+
+    let resp = client.get("/moved").send().await;
+    resp.assert_status(StatusCode::PERMANENT_REDIRECT);
+    resp.assert_header(LOCATION, "/new");
+    client
+        .get("/elsewhere")
+        .send()
+        .await
+        .assert_status(StatusCode::TEMPORARY_REDIRECT);
+    let resp = client.post("/login").send().await;
+    resp.assert_status(StatusCode::SEE_OTHER);
+    resp.assert_header(LOCATION, "/dashboard");
+    client
+        .get("/broken")
+        .send()
+        .await
+        .assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn created_accepted_and_no_content() {
+    let mut router = Router::new();
+    router
+        .post("/users", || async {
+            Created("/users/7".to_string(), Json(json!({ "id": 7 })))
+        })
+        .post("/jobs", || async { Accepted("queued") })
+        .delete("/users/7", || async { NoContent })
+        .put("/users/7", || async {
+            Json(json!({ "id": 7 })).with_status(StatusCode::ACCEPTED)
+        });
+    let client = router.test_client();
+
+    let resp = client.post("/users").send().await;
+    resp.assert_status(StatusCode::CREATED);
+    resp.assert_header(LOCATION, "/users/7");
+    resp.assert_json(json!({ "id": 7 }));
+
+    let resp = client.post("/jobs").send().await;
+    resp.assert_status(StatusCode::ACCEPTED);
+    resp.assert_text("queued");
+
+    let resp = client.delete("/users/7").send().await;
+    resp.assert_status(StatusCode::NO_CONTENT);
+    assert!(resp.bytes().is_empty());
+
+    client
+        .put("/users/7")
+        .send()
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+}
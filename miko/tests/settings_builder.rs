@@ -0,0 +1,55 @@
+use miko::app::Application;
+use miko::app::config::{HttpMode, RedirectRule, ServerSettings, WarmupRequest};
+use miko::router::Router;
+use std::collections::BTreeMap;
+
+fn warmup(path: &str) -> WarmupRequest {
+    WarmupRequest {
+        method: "GET".to_string(),
+        path: path.to_string(),
+        body: None,
+        headers: BTreeMap::new(),
+    }
+}
+
+#[test]
+fn builder_overrides_loaded_values() {
+    let loaded: ServerSettings = serde_json::from_value(serde_json::json!({
+        "host": "0.0.0.0",
+        "port": 8080,
+        "body_limit": "1MB",
+        "http": { "http1_keep_alive": false }
+    }))
+    .unwrap();
+    let settings = loaded
+        .into_builder()
+        .addr("127.0.0.1:3000".parse().unwrap())
+        .no_body_limit()
+        .http_with(|http| http.mode = HttpMode::Http1)
+        .build();
+    assert_eq!(settings.host, "127.0.0.1");
+    assert_eq!(settings.port, 3000);
+    assert_eq!(settings.body_limit, None);
+    assert_eq!(settings.http.mode, HttpMode::Http1);
+    // 未覆盖的项保留原值
+    assert_eq!(settings.http.http1_keep_alive, Some(false));
+}
+
+#[tokio::test]
+async fn built_settings_are_applied_by_application() {
+    let settings = ServerSettings::builder()
+        .port(0)
+        .redirect("/old", RedirectRule::To("/new".to_string()))
+        .warmup(warmup("/old"))
+        .warmup(warmup("/new"))
+        .build();
+    let mut router = Router::new();
+    router.get("/new", || async { "new" });
+    let app = Application::new(settings, router);
+    let results = app.warm_up().await;
+    assert_eq!(
+        results[0],
+        ("/old".to_string(), hyper::StatusCode::MOVED_PERMANENTLY)
+    );
+    assert_eq!(results[1], ("/new".to_string(), hyper::StatusCode::OK));
+}
@@ -0,0 +1,60 @@
+use hyper::{StatusCode, Uri};
+use miko::ext::signed_url::{SignatureError, SignedUrl, UrlSigner};
+use miko::extractor::Path;
+use miko::router::Router;
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn sign_and_verify() {
+    let signer = UrlSigner::new("secret");
+    let url = signer.sign("/files/1?name=a.txt", Duration::from_secs(60));
+    assert!(url.starts_with("/files/1?name=a.txt&expires="));
+    assert!(signer.verify(&url.parse::<Uri>().unwrap()).is_ok());
+
+    let tampered = url.replace("a.txt", "b.txt");
+    assert_eq!(
+        signer.verify(&tampered.parse::<Uri>().unwrap()),
+        Err(SignatureError::Invalid)
+    );
+    let other_key = UrlSigner::new("other");
+    assert_eq!(
+        other_key.verify(&url.parse::<Uri>().unwrap()),
+        Err(SignatureError::Invalid)
+    );
+
+    let expired = signer.sign_until("/files/1", SystemTime::now() - Duration::from_secs(1));
+    assert_eq!(
+        signer.verify(&expired.parse::<Uri>().unwrap()),
+        Err(SignatureError::Expired)
+    );
+    assert_eq!(
+        signer.verify(&"/files/1".parse::<Uri>().unwrap()),
+        Err(SignatureError::Missing)
+    );
+}
+
+#[tokio::test]
+async fn signed_url_guards_handler() {
+    UrlSigner::new("download-secret").install();
+    let mut router = Router::new();
+    router.get(
+        "/files/{id}",
+        |_signed: SignedUrl, Path(id): Path<u32>| async move { format!("file {}", id) },
+    );
+    let client = router.test_client();
+
+    let url = UrlSigner::global()
+        .unwrap()
+        .sign("/files/7", Duration::from_secs(60));
+    let resp = client.get(&url).send().await;
+    resp.assert_text("file 7");
+
+    let resp = client.get("/files/7").send().await;
+    resp.assert_status(StatusCode::FORBIDDEN);
+
+    let resp = client
+        .get(&url.replace("/files/7", "/files/8"))
+        .send()
+        .await;
+    resp.assert_status(StatusCode::FORBIDDEN);
+}
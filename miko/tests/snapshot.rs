@@ -0,0 +1,58 @@
+use hyper::StatusCode;
+use miko::extractor::{Json, Path};
+use miko::http::response::into_response::Html;
+use miko::router::Router;
+use serde_json::json;
+
+fn site() -> Router {
+    let mut router = Router::new();
+    router
+        .get("/", || async { Html("home".to_string()) })
+        .get("/docs/{slug}", |Path(slug): Path<String>| async move {
+            Html(format!("doc {}", slug))
+        })
+        .get("/api/users", || async { Json(json!([1, 2])) })
+        .get("/feed.xml", || async { "<rss/>" })
+        .get("/users/{id}/posts/{post}", || async { "post" })
+        .get("/missing", || async { StatusCode::NOT_FOUND })
+        .post("/submit", || async { "ignored" });
+    router
+}
+
+#[tokio::test]
+async fn export_get_routes_to_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let report = site()
+        .snapshot()
+        .param("slug", ["intro", "install"])
+        .export(dir.path())
+        .await
+        .unwrap();
+
+    let read = |p: &str| std::fs::read_to_string(dir.path().join(p)).unwrap();
+    assert_eq!(read("index.html"), "home");
+    assert_eq!(read("docs/intro/index.html"), "doc intro");
+    assert_eq!(read("docs/install/index.html"), "doc install");
+    assert_eq!(read("api/users/index.json"), "[1,2]");
+    assert_eq!(read("feed.xml"), "<rss/>");
+    assert_eq!(report.written.len(), 5);
+    assert_eq!(report.skipped, vec!["/users/{id}/posts/{post}".to_string()]);
+    assert_eq!(
+        report.failed,
+        vec![("/missing".to_string(), StatusCode::NOT_FOUND)]
+    );
+}
+
+#[test]
+fn plan_expands_parameter_combinations() {
+    let (paths, skipped) = site()
+        .snapshot()
+        .param("id", ["1", "2"])
+        .param("post", ["a"])
+        .path("/docs/extra")
+        .plan();
+    assert!(paths.contains(&"/users/1/posts/a".to_string()));
+    assert!(paths.contains(&"/users/2/posts/a".to_string()));
+    assert!(paths.contains(&"/docs/extra".to_string()));
+    assert_eq!(skipped, vec!["/docs/{slug}".to_string()]);
+}
@@ -0,0 +1,64 @@
+use miko::AppResult;
+use miko::app::config::override_setting;
+use miko::auto::init_container;
+use miko::ext::health::HealthRegistry;
+use miko::ext::sqlx::{DatabaseSettings, SqlitePool};
+use miko::macros::*;
+
+#[get("/users/{id}")]
+async fn get_user(#[dep] pool: Arc<SqlitePool>, #[path] id: i64) -> AppResult<String> {
+    let (name,): (String,) = sqlx::query_as("SELECT name FROM users WHERE id = ?")
+        .bind(id)
+        .fetch_one(&*pool)
+        .await?;
+    Ok(name)
+}
+
+#[post("/users/{id}/{name}")]
+async fn create_user(
+    #[dep] pool: Arc<SqlitePool>,
+    #[path] id: i64,
+    #[path] name: String,
+) -> AppResult<()> {
+    sqlx::query("INSERT INTO users (id, name) VALUES (?, ?)")
+        .bind(id)
+        .bind(name)
+        .execute(&*pool)
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn pool_is_injected_and_errors_map_to_status_codes() {
+    // 内存数据库只在同一个连接中可见
+    override_setting("database.url", "sqlite::memory:").unwrap();
+    override_setting("database.max_connections", 1).unwrap();
+    init_container().await;
+
+    let pool = miko::dependency_container::get_global_dc()
+        .await
+        .get::<SqlitePool>()
+        .await;
+    assert_eq!(pool.options().get_max_connections(), 1);
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .execute(&*pool)
+        .await
+        .unwrap();
+    assert!(HealthRegistry::global().check().await.is_up());
+
+    let client = miko::auto::collect_global_router().test_client();
+    assert_eq!(client.post("/users/1/alice").send().await.status, 200);
+    assert_eq!(client.get("/users/1").send().await.text(), "alice");
+    assert_eq!(client.post("/users/1/bob").send().await.status, 409);
+    let resp = client.get("/users/2").send().await;
+    assert_eq!(resp.status, 404);
+    assert_eq!(resp.json::<serde_json::Value>()["error"], "NOT_FOUND");
+}
+
+#[test]
+fn missing_url_is_a_configuration_error() {
+    let err = DatabaseSettings::default()
+        .connect_lazy::<sqlx::Sqlite>()
+        .unwrap_err();
+    assert!(err.to_string().contains("database.url"));
+}
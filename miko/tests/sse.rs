@@ -0,0 +1,50 @@
+use futures::StreamExt;
+use miko::http::response::sse::{KeepAlive, Sse, SseEvent, SseOptions, spawn_sse_event_with};
+use miko::router::Router;
+use std::time::Duration;
+
+fn delayed_events() -> impl futures::Stream<Item = SseEvent> + Send + 'static {
+    futures::stream::iter([SseEvent::data("a")]).chain(futures::stream::once(async {
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        SseEvent::data("b")
+    }))
+}
+
+#[tokio::test]
+async fn sends_keep_alive_comments_while_idle() {
+    let mut router = Router::new();
+    router
+        .get("/ka", || async {
+            Sse::from_stream(delayed_events()).keep_alive(
+                KeepAlive::new()
+                    .interval(Duration::from_millis(20))
+                    .text("ping"),
+            )
+        })
+        .get("/quiet", || async {
+            Sse::from_stream(delayed_events()).no_keep_alive()
+        })
+        .get("/task", || async {
+            spawn_sse_event_with(
+                |sender| async move {
+                    sender.send("one").await.or_break();
+                    sender.send("two").await.or_break();
+                },
+                SseOptions::new().capacity(1).keep_alive(None),
+            )
+        });
+    let client = router.test_client();
+
+    let resp = client.get("/ka").send().await;
+    resp.assert_header("content-type", "text/event-stream");
+    let body = resp.text();
+    assert!(body.starts_with("data: a\n\n"));
+    assert!(body.contains(": ping\n\n"));
+    assert!(body.ends_with("data: b\n\n"));
+
+    let body = client.get("/quiet").send().await.text();
+    assert_eq!(body, "data: a\n\ndata: b\n\n");
+
+    let body = client.get("/task").send().await.text();
+    assert_eq!(body, "data: one\n\ndata: two\n\n");
+}
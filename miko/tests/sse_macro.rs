@@ -0,0 +1,87 @@
+use miko::http::response::sse::{IntoSseEvent, SseEventTypes, TypedSseSender};
+use miko::macros::*;
+use miko::serde::Serialize;
+
+#[derive(Serialize)]
+struct Build {
+    target: String,
+}
+
+#[derive(IntoSseEvent)]
+enum JobEvent {
+    Started(Build),
+    Progress {
+        #[sse_event(id)]
+        seq: u32,
+        percent: u8,
+    },
+    #[sse_event(name = "done", retry = 5000)]
+    Finished,
+}
+
+#[sse("/jobs/{id}/events")]
+async fn job_events(tx: TypedSseSender<JobEvent>, #[path] id: String) {
+    tx.send(JobEvent::Started(Build { target: id }))
+        .await
+        .or_break();
+    tx.send(JobEvent::Progress {
+        seq: 1,
+        percent: 50,
+    })
+    .await
+    .or_break();
+    tx.send(JobEvent::Finished).await.or_break();
+}
+
+#[sse("/jobs/replay")]
+async fn replay(#[query] last: u32) -> impl futures::Stream<Item = JobEvent> {
+    futures::stream::iter((1..=last).map(|seq| JobEvent::Progress { seq, percent: 100 }))
+}
+
+#[test]
+fn derive_generates_event_framing() {
+    assert_eq!(JobEvent::EVENT_NAMES, ["started", "progress", "done"]);
+    let event = JobEvent::Progress {
+        seq: 7,
+        percent: 10,
+    }
+    .into_sse_event();
+    assert_eq!(event.event.as_deref(), Some("progress"));
+    assert_eq!(event.id.as_deref(), Some("7"));
+    assert_eq!(event.data, r#"{"seq":7,"percent":10}"#);
+    let event = JobEvent::Finished.into_sse_event();
+    assert_eq!(event.retry, Some(5000));
+    assert_eq!(event.data, "null");
+}
+
+#[tokio::test]
+async fn sse_macro_streams_typed_events() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    let resp = client.get("/jobs/web/events").send().await;
+    resp.assert_header("content-type", "text/event-stream");
+    assert_eq!(
+        resp.text(),
+        "event: started\ndata: {\"target\":\"web\"}\n\n\
+         event: progress\nid: 1\ndata: {\"seq\":1,\"percent\":50}\n\n\
+         event: done\ndata: null\nretry: 5000\n\n"
+    );
+
+    let body = client.get("/jobs/replay?last=2").send().await.text();
+    assert_eq!(
+        body,
+        "event: progress\nid: 1\ndata: {\"seq\":1,\"percent\":100}\n\n\
+         event: progress\nid: 2\ndata: {\"seq\":2,\"percent\":100}\n\n"
+    );
+
+    let doc = miko::openapi::auto_doc();
+    let op = doc.paths.paths["/jobs/{id}/events"].get.as_ref().unwrap();
+    let json = serde_json::to_value(&op.responses.responses["200"]).unwrap();
+    assert!(json["content"]["text/event-stream"].is_object());
+    assert!(
+        json["description"]
+            .as_str()
+            .unwrap()
+            .ends_with("Events: `started`, `progress`, `done`")
+    );
+}
@@ -0,0 +1,157 @@
+use hyper::StatusCode;
+use hyper::header;
+use miko::ext::static_svc::StaticSvcBuilder;
+use miko::router::Router;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+fn router(root: &Path, options: fn(StaticSvcBuilder) -> StaticSvcBuilder) -> Router {
+    let mut router = Router::new();
+    router.static_svc("/static", root, Some(options));
+    router
+}
+
+#[tokio::test]
+async fn serves_precompressed_variants_with_cache_control() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("app.js"), "plain").unwrap();
+    std::fs::write(dir.path().join("app.js.br"), "brotli").unwrap();
+    std::fs::write(dir.path().join("app.js.gz"), "gzip").unwrap();
+    std::fs::write(dir.path().join("index.html"), "<h1>hi</h1>").unwrap();
+    let client = router(dir.path(), |b| {
+        b.with_precompressed(true)
+            .with_cache_control([".JS"], "public, max-age=31536000, immutable")
+    })
+    .test_client();
+
+    let br = client
+        .get("/static/app.js")
+        .header(header::ACCEPT_ENCODING, "gzip, br")
+        .send()
+        .await;
+    br.assert_text("brotli");
+    br.assert_header("content-encoding", "br");
+    br.assert_header("content-type", "text/javascript; charset=utf-8");
+    br.assert_header("vary", "accept-encoding");
+    br.assert_header("cache-control", "public, max-age=31536000, immutable");
+
+    let gzip = client
+        .get("/static/app.js")
+        .header(header::ACCEPT_ENCODING, "br;q=0, gzip")
+        .send()
+        .await;
+    gzip.assert_text("gzip");
+    gzip.assert_header("content-encoding", "gzip");
+
+    let plain = client.get("/static/app.js").send().await;
+    plain.assert_text("plain");
+    assert!(plain.headers.get("content-encoding").is_none());
+
+    let html = client.get("/static/").send().await;
+    html.assert_text("<h1>hi</h1>");
+    assert!(html.headers.get("cache-control").is_none());
+}
+
+#[tokio::test]
+async fn handles_multi_range_and_if_modified_since() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("data.txt"), "0123456789").unwrap();
+
+    let single = router(dir.path(), |b| b).test_client();
+    let full = single
+        .get("/static/data.txt")
+        .header(header::RANGE, "bytes=0-1,5-6")
+        .send()
+        .await;
+    full.assert_ok();
+    full.assert_text("0123456789");
+
+    let client = router(dir.path(), |b| b.with_multi_range(true)).test_client();
+    let multi = client
+        .get("/static/data.txt")
+        .header(header::RANGE, "bytes=0-1, 5-6, 40-50")
+        .send()
+        .await;
+    multi.assert_status(StatusCode::PARTIAL_CONTENT);
+    let content_type = multi.headers["content-type"].to_str().unwrap().to_string();
+    let boundary = content_type
+        .strip_prefix("multipart/byteranges; boundary=")
+        .unwrap();
+    let body = multi.text();
+    assert_eq!(
+        body,
+        format!(
+            "\r\n--{b}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Range: bytes 0-1/10\r\n\r\n01\
+             \r\n--{b}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Range: bytes 5-6/10\r\n\r\n56\
+             \r\n--{b}--\r\n",
+            b = boundary
+        )
+    );
+    multi.assert_header("content-length", body.len().to_string());
+
+    let later = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(3600));
+    client
+        .get("/static/data.txt")
+        .header(header::IF_MODIFIED_SINCE, later.as_str())
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_MODIFIED);
+
+    let earlier = httpdate::fmt_http_date(SystemTime::now() - Duration::from_secs(3600));
+    client
+        .get("/static/data.txt")
+        .header(header::IF_MODIFIED_SINCE, earlier.as_str())
+        .send()
+        .await
+        .assert_ok();
+}
+
+#[tokio::test]
+async fn lists_directories_as_html_or_json() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("docs")).unwrap();
+    std::fs::create_dir(dir.path().join("docs/sub")).unwrap();
+    std::fs::write(dir.path().join("docs/a b.txt"), "hello").unwrap();
+    std::fs::write(dir.path().join("docs/<x>.md"), "").unwrap();
+    std::fs::write(dir.path().join("docs/.secret"), "").unwrap();
+    std::fs::write(dir.path().join("index.html"), "home").unwrap();
+
+    let plain = router(dir.path(), |b| b).test_client();
+    plain
+        .get("/static/docs/")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    let client = router(dir.path(), |b| b.with_directory_listing(true)).test_client();
+    client.get("/static/").send().await.assert_text("home");
+
+    let html = client.get("/static/docs").send().await;
+    html.assert_ok();
+    html.assert_header("content-type", "text/html; charset=utf-8");
+    let body = html.text();
+    assert!(body.contains("<a href=\"docs/sub/\">sub/</a>"), "{body}");
+    assert!(
+        body.contains("<a href=\"docs/a%20b.txt\">a b.txt</a></td><td>5</td>"),
+        "{body}"
+    );
+    assert!(body.contains("&lt;x&gt;.md"), "{body}");
+    assert!(!body.contains(".secret"), "{body}");
+
+    let json = client
+        .get("/static/docs/")
+        .header(header::ACCEPT, "application/json, text/html;q=0.9")
+        .send()
+        .await
+        .json::<serde_json::Value>();
+    let entries = json.as_array().unwrap();
+    let names: Vec<_> = entries
+        .iter()
+        .map(|e| e["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["sub", "<x>.md", "a b.txt"]);
+    assert_eq!(entries[0]["is_dir"], true);
+    assert_eq!(entries[0]["size"], serde_json::Value::Null);
+    assert_eq!(entries[2]["size"], 5);
+    assert!(entries[2]["modified"].is_string());
+}
@@ -0,0 +1,68 @@
+use bytes::Bytes;
+use futures::StreamExt;
+use http_body_util::{BodyExt, Empty, StreamBody};
+use hyper::Request;
+use hyper::body::Frame;
+use miko::handler::Resp;
+use miko::middleware::stream_idle::StreamIdleTimeoutLayer;
+use miko::miko_core::MikoError;
+use miko::router::Router;
+use std::time::Duration;
+use tower::ServiceExt;
+
+/// 每隔 `gap` 产生一块数据，共 `n` 块，之后（若 `hang`）不再结束
+fn ticking(n: usize, gap: Duration, hang: bool) -> Resp {
+    let stream = futures::stream::iter(0..n)
+        .then(move |_| async move {
+            tokio::time::sleep(gap).await;
+            Ok::<_, MikoError>(Frame::data(Bytes::from_static(b"tick")))
+        })
+        .chain(futures::stream::iter(hang.then_some(())).flat_map(|_| futures::stream::pending()));
+    Resp::new(StreamBody::new(stream).boxed_unsync())
+}
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router.get("/active", || async {
+        ticking(5, Duration::from_millis(30), false)
+    });
+    router.get("/stuck", || async { ticking(1, Duration::ZERO, true) });
+    router.with_layer(StreamIdleTimeoutLayer::new(Duration::from_millis(80)));
+    router
+}
+
+fn request(path: &str) -> miko::handler::Req {
+    Request::get(path)
+        .body(Empty::new().map_err(Into::into).boxed_unsync())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn active_stream_outlives_idle_timeout() {
+    let resp = router()
+        .into_tower_service()
+        .oneshot(request("/active"))
+        .await
+        .unwrap();
+    // 总时长超过超时时间，但每块之间的间隔没有
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body.len(), 20);
+}
+
+#[tokio::test]
+async fn idle_stream_is_terminated() {
+    let resp = router()
+        .into_tower_service()
+        .oneshot(request("/stuck"))
+        .await
+        .unwrap();
+    let mut body = resp.into_body();
+    let first = body.frame().await.unwrap().unwrap();
+    assert_eq!(first.into_data().unwrap(), "tick");
+    let err = tokio::time::timeout(Duration::from_secs(1), body.frame())
+        .await
+        .expect("idle stream was not terminated")
+        .unwrap()
+        .unwrap_err();
+    assert!(err.to_string().contains("idle timeout"));
+}
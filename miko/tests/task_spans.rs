@@ -0,0 +1,68 @@
+use miko::ext::otel::{OtelLayer, layer};
+use miko::http::response::sse::spawn_sse_event;
+use miko::router::Router;
+use miko::spawn_with_context;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+use tracing_subscriber::layer::SubscriberExt;
+
+fn find<'a>(spans: &'a [SpanData], name: &str) -> &'a SpanData {
+    spans
+        .iter()
+        .find(|s| s.name == name)
+        .unwrap_or_else(|| panic!("no span `{}` in {:?}", name, spans))
+}
+
+#[tokio::test]
+async fn spawned_tasks_stay_connected_to_request_trace() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry().with(layer(&provider));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let mut router = Router::new();
+    router
+        .post("/orders", || async {
+            spawn_with_context(async {
+                tracing::info_span!("notify warehouse").in_scope(|| {});
+            })
+            .await
+            .unwrap();
+            "ok"
+        })
+        .get("/events", || async {
+            spawn_sse_event(|sender| async move {
+                tracing::info_span!("load events").in_scope(|| {});
+                let _ = sender.send("done").await;
+            })
+        })
+        .with_layer(OtelLayer::new());
+    let client = router.test_client();
+
+    client.post("/orders").send().await.assert_text("ok");
+    let mut events = client.sse("/events").await;
+    assert_eq!(events.next_event().await.unwrap().data, "done");
+    drop(events);
+    tokio::task::yield_now().await;
+
+    let spans = exporter.get_finished_spans().unwrap();
+    let orders = find(&spans, "POST /orders");
+    let notify = find(&spans, "notify warehouse");
+    assert_eq!(notify.parent_span_id, orders.span_context.span_id());
+    assert_eq!(
+        notify.span_context.trace_id(),
+        orders.span_context.trace_id()
+    );
+
+    let request = find(&spans, "GET /events");
+    let task = find(&spans, "sse task");
+    assert_ne!(
+        task.span_context.trace_id(),
+        request.span_context.trace_id()
+    );
+    assert_eq!(task.links.links.len(), 1);
+    assert_eq!(task.links.links[0].span_context, request.span_context);
+    let load = find(&spans, "load events");
+    assert_eq!(load.parent_span_id, task.span_context.span_id());
+}
@@ -0,0 +1,71 @@
+use hyper::StatusCode;
+use hyper::header::CONTENT_TYPE;
+use miko::ext::templates::{Template, TemplateEngine};
+use miko::router::Router;
+use serde_json::json;
+
+fn template_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("miko-templates-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn renders_templates_and_maps_errors() {
+    let dir = template_dir("render");
+    std::fs::write(dir.join("hello.html"), "<p>Hello, {{ name }}!</p>").unwrap();
+    let engine = TemplateEngine::new(&format!("{}/**/*", dir.display())).unwrap();
+    engine
+        .add_template("broken.html", "{{ missing.field }}")
+        .unwrap();
+
+    let mut router = Router::new();
+    let hello = engine.clone();
+    let broken = engine.clone();
+    router
+        .get("/hello", move || {
+            let engine = hello.clone();
+            async move { engine.template("hello.html", &json!({ "name": "<Miko>" })) }
+        })
+        .get("/broken", move || {
+            let engine = broken.clone();
+            async move { engine.template("broken.html", &json!({})) }
+        });
+    let client = router.test_client();
+
+    let resp = client.get("/hello").send().await;
+    resp.assert_header(CONTENT_TYPE, "text/html; charset=utf-8");
+    resp.assert_text("<p>Hello, &lt;Miko&gt;!</p>");
+    client
+        .get("/broken")
+        .send()
+        .await
+        .assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reload_picks_up_changed_files_and_keeps_raw_templates() {
+    let dir = template_dir("reload");
+    std::fs::write(dir.join("page.html"), "v1").unwrap();
+    let engine = TemplateEngine::new(&format!("{}/**/*", dir.display()))
+        .unwrap()
+        .reload(true);
+    engine
+        .add_template("inline.html", "inline {{ n }}")
+        .unwrap();
+    assert_eq!(engine.render("page.html", &json!({})).unwrap(), "v1");
+
+    std::fs::write(dir.join("page.html"), "v2").unwrap();
+    assert_eq!(engine.render("page.html", &json!({})).unwrap(), "v2");
+    assert_eq!(
+        Template::new("inline.html", &json!({ "n": 1 }))
+            .engine(&engine)
+            .render()
+            .unwrap(),
+        "inline 1"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -52,3 +52,73 @@ async fn test_test_client() {
     r6.assert_ok();
     r6.assert_text("Svc: 42");
 }
+
+#[tokio::test]
+async fn cookies_persist_across_requests() {
+    use miko::extractor::cookies::{Cookie, CookieJar};
+
+    let mut router = Router::new();
+    router
+        .post("/login", |jar: CookieJar| async move {
+            (jar.add(Cookie::new("session", "abc")), "ok")
+        })
+        .get("/me", |jar: CookieJar| async move {
+            jar.get("session")
+                .map(|c| c.value().to_string())
+                .unwrap_or_else(|| "anonymous".to_string())
+        })
+        .post("/logout", |jar: CookieJar| async move {
+            (jar.remove(Cookie::from("session")), "bye")
+        });
+    let client = router.test_client();
+
+    client.get("/me").send().await.assert_text("anonymous");
+    client.post("/login").send().await.assert_ok();
+    assert_eq!(client.cookie("session").as_deref(), Some("abc"));
+    client.get("/me").send().await.assert_text("abc");
+    client
+        .get("/me")
+        .cookie("session", "override")
+        .send()
+        .await
+        .assert_text("override");
+
+    client.post("/logout").send().await.assert_ok();
+    assert_eq!(client.cookie("session"), None);
+    client.get("/me").send().await.assert_text("anonymous");
+}
+
+#[tokio::test]
+async fn multipart_and_headers() {
+    use miko::extractor::multipart::MultipartResult;
+    use miko::test::MultipartForm;
+
+    let mut router = Router::new();
+    router.post(
+        "/upload",
+        |headers: miko::hyper::HeaderMap, form: MultipartResult| async move {
+            let file = &form.files["file"][0];
+            format!(
+                "{} {} {} {}",
+                headers["x-tenant"].to_str().unwrap(),
+                form.fields["title"][0],
+                file.filename,
+                file.size
+            )
+        },
+    );
+
+    router
+        .test_client()
+        .post("/upload")
+        .header("x-tenant", "acme")
+        .multipart(MultipartForm::new().text("title", "report").file(
+            "file",
+            "a.txt",
+            "text/plain",
+            b"hello",
+        ))
+        .send()
+        .await
+        .assert_text("acme report a.txt 5");
+}
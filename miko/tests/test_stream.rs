@@ -0,0 +1,57 @@
+use futures::StreamExt;
+use miko::handler::Req;
+use miko::http::response::sse::{Sse, SseEvent};
+use miko::router::Router;
+use miko::ws::server::{WsMessage, spawn_ws_event};
+
+#[tokio::test]
+async fn websocket_round_trip_without_port() {
+    let mut router = Router::new();
+    router.get("/ws", |mut req: Req| async move {
+        let user = req
+            .headers()
+            .get("x-user")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("anon")
+            .to_string();
+        spawn_ws_event(
+            move |mut socket| async move {
+                while let Some(Ok(WsMessage::Text(text))) = socket.recv().await {
+                    socket.send(format!("{}: {}", user, text)).await.unwrap();
+                }
+            },
+            &mut req,
+            None,
+        )
+    });
+    let client = router.test_client();
+
+    let mut socket = client.websocket("/ws").await;
+    socket.send("hello").await;
+    assert_eq!(socket.recv_text().await, "anon: hello");
+    socket.send(r#"[1,2]"#).await;
+    assert_eq!(socket.recv_text().await, "anon: [1,2]");
+    socket.close().await;
+
+    let mut socket = client.get("/ws").header("x-user", "miko").websocket().await;
+    socket.send("hi").await;
+    assert_eq!(socket.recv_text().await, "miko: hi");
+}
+
+#[tokio::test]
+async fn sse_events_are_parsed_incrementally() {
+    let mut router = Router::new();
+    router.get("/events", || async {
+        Sse::from_stream(futures::stream::iter([
+            SseEvent::data("line1\nline2").event("note").id("1"),
+            SseEvent::json(&[1, 2, 3]).retry(500),
+        ]))
+    });
+
+    let mut events = router.test_client().sse("/events").await;
+    assert!(events.status().is_success());
+    let first = events.next_event().await.unwrap();
+    assert_eq!(first, SseEvent::data("line1\nline2").event("note").id("1"));
+    assert_eq!(events.next_json::<Vec<u8>>().await, vec![1, 2, 3]);
+    assert!(events.next().await.is_none());
+}
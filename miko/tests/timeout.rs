@@ -0,0 +1,83 @@
+use hyper::StatusCode;
+use miko::app::Application;
+use miko::app::config::{RouteOverride, ServerSettings};
+use miko::context::RequestContext;
+use miko::macros::*;
+use miko::middleware::timeout::TimeoutLayer;
+use miko::router::Router;
+use std::time::Duration;
+
+async fn slow() -> &'static str {
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    "slow"
+}
+
+#[get("/timeout/macro")]
+#[layer(timeout("20ms"))]
+async fn macro_slow() -> &'static str {
+    slow().await
+}
+
+#[tokio::test]
+async fn timeout_layer_returns_504() {
+    let mut router = Router::new();
+    router
+        .get("/slow", slow)
+        .get("/fast", |ctx: RequestContext| async move {
+            // 处理器能看到截止时间
+            assert!(ctx.remaining().is_some());
+            "fast"
+        })
+        .with_layer(TimeoutLayer::new(Duration::from_millis(20)));
+    let client = router.test_client();
+
+    client.get("/fast").send().await.assert_text("fast");
+
+    let resp = client.get("/slow").send().await;
+    resp.assert_status(StatusCode::GATEWAY_TIMEOUT);
+    let body: serde_json::Value = resp.json();
+    assert_eq!(body["error"], "TIMEOUT");
+
+    let client = miko::auto::collect_global_router().test_client();
+    let resp = client.get("/timeout/macro").send().await;
+    resp.assert_status(StatusCode::GATEWAY_TIMEOUT);
+}
+
+#[tokio::test]
+async fn route_timeout_replaces_global_timeout() {
+    let mut router = Router::new();
+    router
+        .get("/slow", slow)
+        .get("/upload", slow)
+        .get("/quick", slow);
+    let settings = ServerSettings::builder()
+        .timeout(Duration::from_millis(250))
+        .route(
+            "/upload",
+            RouteOverride {
+                timeout_ms: Some(2000),
+                ..Default::default()
+            },
+        )
+        .route(
+            "/quick",
+            RouteOverride {
+                timeout_ms: Some(20),
+                ..Default::default()
+            },
+        )
+        .build();
+    let client = Application::new(settings, router).test_client();
+
+    let resp = client.get("/slow").send().await;
+    resp.assert_status(StatusCode::GATEWAY_TIMEOUT);
+
+    client.get("/upload").send().await.assert_text("slow");
+
+    // 更短的路由超时不必等到全局超时
+    tokio::select! {
+        biased;
+        resp = client.get("/quick").send() => resp.assert_status(StatusCode::GATEWAY_TIMEOUT),
+        _ = client.get("/slow").send() => panic!("global timeout fired before the route timeout"),
+    }
+}
@@ -0,0 +1,50 @@
+use hyper::StatusCode;
+use hyper::header::LOCATION;
+use miko::extractor::Path;
+use miko::router::Router;
+use miko::router::trailing_slash::TrailingSlash;
+
+fn router(policy: TrailingSlash) -> Router {
+    let mut router = Router::new();
+    router
+        .get("/users", || async { "users" })
+        .get("/users/{id}/", |Path(id): Path<u32>| async move {
+            format!("user {id}")
+        })
+        .trailing_slash(policy);
+    router
+}
+
+#[tokio::test]
+async fn redirect_policy_sends_308_to_registered_form() {
+    let client = router(TrailingSlash::Redirect).test_client();
+
+    let resp = client.get("/users/?page=2").send().await;
+    resp.assert_status(StatusCode::PERMANENT_REDIRECT);
+    resp.assert_header(LOCATION.as_str(), "/users?page=2");
+
+    let resp = client.post("/users/7").send().await;
+    resp.assert_status(StatusCode::PERMANENT_REDIRECT);
+    resp.assert_header(LOCATION.as_str(), "/users/7/");
+
+    client.get("/users").send().await.assert_text("users");
+    client
+        .get("/posts/")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn strip_policy_matches_without_redirect_and_strict_404s() {
+    let client = router(TrailingSlash::Strip).test_client();
+    client.get("/users/").send().await.assert_text("users");
+    client.get("/users/7").send().await.assert_text("user 7");
+
+    let strict = router(TrailingSlash::Strict).test_client();
+    strict
+        .get("/users/")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,123 @@
+use hyper::StatusCode;
+use hyper::header::{AUTHORIZATION, HeaderName, HeaderValue, RANGE};
+use miko::extractor::TypedHeader;
+use miko::extractor::typed_header::{
+    Accept, Authorization, Basic, Bearer, ByteRange, Header, Range,
+};
+use miko::macros::*;
+use miko::router::Router;
+
+#[get("/hdr/agent")]
+async fn agent(#[header] user_agent: String, #[header("X-Retry")] retry: Option<u32>) -> String {
+    format!("{} {:?}", user_agent, retry)
+}
+
+#[tokio::test]
+async fn typed_header_extracts_and_rejects() {
+    let mut router = Router::new();
+    router
+        .get(
+            "/token",
+            |TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>| async move {
+                bearer.token().to_string()
+            },
+        )
+        .get(
+            "/basic",
+            |TypedHeader(Authorization(basic)): TypedHeader<Authorization<Basic>>| async move {
+                format!("{}:{}", basic.username(), basic.password())
+            },
+        )
+        .get("/range", |range: Option<TypedHeader<Range>>| async move {
+            format!("{:?}", range.map(|TypedHeader(r)| r.0))
+        });
+    let client = router.test_client();
+
+    let resp = client
+        .get("/token")
+        .header(AUTHORIZATION, HeaderValue::from_static("Bearer abc"))
+        .send()
+        .await;
+    resp.assert_text("abc");
+
+    client
+        .get("/token")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    client
+        .get("/token")
+        .header(AUTHORIZATION, HeaderValue::from_static("Basic YTpi"))
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    let resp = client
+        .get("/basic")
+        .header(AUTHORIZATION, HeaderValue::from_static("Basic YTpi"))
+        .send()
+        .await;
+    resp.assert_text("a:b");
+
+    let resp = client
+        .get("/range")
+        .header(RANGE, HeaderValue::from_static("bytes=0-9,-5"))
+        .send()
+        .await;
+    resp.assert_text("Some([FromTo(0, 9), Last(5)])");
+    client.get("/range").send().await.assert_text("None");
+}
+
+#[tokio::test]
+async fn header_attribute_in_route_macro() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    let resp = client
+        .get("/hdr/agent")
+        .header(
+            HeaderName::from_static("user-agent"),
+            HeaderValue::from_static("miko-test"),
+        )
+        .header(
+            HeaderName::from_static("x-retry"),
+            HeaderValue::from_static("3"),
+        )
+        .send()
+        .await;
+    resp.assert_text("miko-test Some(3)");
+
+    client
+        .get("/hdr/agent")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    client
+        .get("/hdr/agent")
+        .header(
+            HeaderName::from_static("user-agent"),
+            HeaderValue::from_static("miko-test"),
+        )
+        .header(
+            HeaderName::from_static("x-retry"),
+            HeaderValue::from_static("soon"),
+        )
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn accept_orders_by_quality() {
+    let accept = Accept::decode(&HeaderValue::from_static(
+        "text/html;q=0.5, application/json",
+    ))
+    .unwrap();
+    assert_eq!(
+        accept.preferred().unwrap().essence_str(),
+        "application/json"
+    );
+    assert!(accept.accepts(&"text/html".parse().unwrap()));
+    assert!(!accept.accepts(&"image/png".parse().unwrap()));
+    assert_eq!(ByteRange::Last(5).resolve(3), Some((0, 2)));
+    assert_eq!(ByteRange::From(10).resolve(3), None);
+}
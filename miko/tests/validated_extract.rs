@@ -0,0 +1,128 @@
+use garde::Validate;
+use hyper::StatusCode;
+use miko::extractor::{ValidatedForm, ValidatedPath, ValidatedQuery};
+use miko::macros::*;
+use miko::router::Router;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize, Validate)]
+struct Pagination {
+    #[garde(range(min = 1))]
+    page: u32,
+    #[garde(range(min = 1, max = 100))]
+    per_page: u32,
+}
+
+#[derive(Deserialize, Validate)]
+struct Signup {
+    #[garde(length(min = 3))]
+    username: String,
+}
+
+#[derive(Deserialize, Validate)]
+struct UserPath {
+    #[garde(range(min = 1))]
+    id: u64,
+}
+
+#[get("/macro/search")]
+async fn macro_search(
+    #[query(validate)]
+    #[garde(length(min = 2))]
+    q: String,
+    #[query] sort: Option<String>,
+) -> String {
+    format!("{} {}", q, sort.unwrap_or_default())
+}
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router
+        .get(
+            "/items",
+            |ValidatedQuery(p): ValidatedQuery<Pagination>| async move {
+                format!("{}/{}", p.page, p.per_page)
+            },
+        )
+        .post(
+            "/signup",
+            |ValidatedForm(s): ValidatedForm<Signup>| async move { s.username },
+        )
+        .get(
+            "/users/{id}",
+            |ValidatedPath(p): ValidatedPath<UserPath>| async move { p.id.to_string() },
+        );
+    router
+}
+
+fn fields(body: &Value) -> Vec<&str> {
+    body["details"]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["field"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn validated_extractors_accept_valid_input() {
+    let client = router().test_client();
+
+    client
+        .get("/items?page=2&per_page=20")
+        .send()
+        .await
+        .assert_text("2/20");
+    client
+        .post("/signup")
+        .form(&[("username", "miko")])
+        .send()
+        .await
+        .assert_text("miko");
+    client.get("/users/7").send().await.assert_text("7");
+}
+
+#[tokio::test]
+async fn validated_extractors_report_field_details() {
+    let client = router().test_client();
+
+    let resp = client.get("/items?page=0&per_page=500").send().await;
+    resp.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    let body: Value = resp.json();
+    assert_eq!(body["error"], "VALIDATION_ERROR");
+    let mut names = fields(&body);
+    names.sort();
+    assert_eq!(names, ["page", "per_page"]);
+
+    let resp = client
+        .post("/signup")
+        .form(&[("username", "m")])
+        .send()
+        .await;
+    resp.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(fields(&resp.json()), ["username"]);
+
+    let resp = client.get("/users/0").send().await;
+    resp.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(fields(&resp.json()), ["id"]);
+
+    // 解析失败仍按原提取器返回 400
+    let resp = client.get("/items?page=x&per_page=1").send().await;
+    resp.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn query_macro_validate_flag() {
+    let client = miko::auto::collect_global_router().test_client();
+
+    client
+        .get("/macro/search?q=rust&sort=asc")
+        .send()
+        .await
+        .assert_text("rust asc");
+
+    let resp = client.get("/macro/search?q=r").send().await;
+    resp.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(fields(&resp.json()), ["q"]);
+}
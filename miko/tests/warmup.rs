@@ -0,0 +1,47 @@
+use hyper::StatusCode;
+use miko::app::Application;
+use miko::app::config::ServerSettings;
+use miko::handler::Req;
+use miko::router::Router;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[tokio::test]
+async fn warm_up_runs_configured_requests() {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    let mut router = Router::new();
+    router
+        .get("/", move |req: Req| {
+            let counter = counter.clone();
+            async move {
+                assert_eq!(req.headers()["x-miko-warmup"], "1");
+                counter.fetch_add(1, Ordering::SeqCst);
+                "ok"
+            }
+        })
+        .post("/search", |body: String| async move { body });
+
+    let settings: ServerSettings = serde_json::from_value(serde_json::json!({
+        "host": "127.0.0.1",
+        "port": 0,
+        "warmup": [
+            { "path": "/" },
+            { "method": "POST", "path": "/search", "body": "q" },
+            { "path": "/missing" }
+        ]
+    }))
+    .unwrap();
+    let app = Application::new(settings, router);
+
+    let results = app.warm_up().await;
+    assert_eq!(
+        results,
+        vec![
+            ("/".to_string(), StatusCode::OK),
+            ("/search".to_string(), StatusCode::OK),
+            ("/missing".to_string(), StatusCode::NOT_FOUND),
+        ]
+    );
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,206 @@
+use futures::{SinkExt, StreamExt};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::service::TowerToHyperService;
+use miko::extractor::Query;
+use miko::handler::Req;
+use miko::http::convert::incoming_to_req::IncomingToInternal;
+use miko::router::Router;
+use miko::ws::WsUpgrade;
+use miko::ws::server::{
+    CloseCode, WsMessage, WsOptions, close_frame, spawn_ws_event, spawn_ws_event_with,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+async fn serve(router: Router) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let svc = router.into_tower_service();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let svc = TowerToHyperService::new(IncomingToInternal { inner: svc.clone() });
+            tokio::spawn(async move {
+                let _ = Builder::new(TokioExecutor::new())
+                    .serve_connection_with_upgrades(TokioIo::new(stream), svc)
+                    .await;
+            });
+        }
+    });
+    addr
+}
+
+fn protocol_router() -> Router {
+    let mut router = Router::new();
+    router.get("/ws", |mut req: Req| async move {
+        spawn_ws_event_with(
+            |mut socket| async move {
+                let protocol = socket.protocol().unwrap_or("none").to_string();
+                socket.send(protocol).await.unwrap();
+            },
+            &mut req,
+            WsOptions::new()
+                .protocols(["graphql-ws", "chat"])
+                .require_protocol(true),
+        )
+    });
+    router
+}
+
+#[tokio::test]
+async fn negotiates_subprotocol_in_client_order() {
+    let addr = serve(protocol_router()).await;
+    let mut request = format!("ws://{addr}/ws").into_client_request().unwrap();
+    request.headers_mut().insert(
+        "sec-websocket-protocol",
+        "mqtt, chat, graphql-ws".parse().unwrap(),
+    );
+    let (mut stream, resp) = tokio_tungstenite::connect_async(request).await.unwrap();
+    assert_eq!(resp.headers()["sec-websocket-protocol"], "chat");
+    assert_eq!(stream.next().await.unwrap().unwrap(), Message::text("chat"));
+    let _ = stream.close(None).await;
+}
+
+#[tokio::test]
+async fn rejects_upgrade_without_supported_protocol() {
+    let addr = serve(protocol_router()).await;
+    let mut request = format!("ws://{addr}/ws").into_client_request().unwrap();
+    request
+        .headers_mut()
+        .insert("sec-websocket-protocol", "mqtt".parse().unwrap());
+    assert!(tokio_tungstenite::connect_async(request).await.is_err());
+}
+
+#[tokio::test]
+async fn declines_permessage_deflate_offer() {
+    let addr = serve(protocol_router()).await;
+    let mut request = format!("ws://{addr}/ws").into_client_request().unwrap();
+    let headers = request.headers_mut();
+    headers.insert("sec-websocket-protocol", "chat".parse().unwrap());
+    headers.insert(
+        "sec-websocket-extensions",
+        "permessage-deflate; client_max_window_bits"
+            .parse()
+            .unwrap(),
+    );
+    let (mut stream, resp) = tokio_tungstenite::connect_async(request).await.unwrap();
+    // 未实现压缩扩展，握手不确认该扩展，连接以不压缩的方式建立
+    assert!(!resp.headers().contains_key("sec-websocket-extensions"));
+    assert_eq!(stream.next().await.unwrap().unwrap(), Message::text("chat"));
+    let _ = stream.close(None).await;
+}
+
+#[tokio::test]
+async fn close_with_sends_code_and_reason() {
+    let mut router = Router::new();
+    router.get("/ws", |mut req: Req| async move {
+        spawn_ws_event(
+            |mut socket| async move {
+                socket.close_with(CloseCode::Policy, "bye").await.unwrap();
+            },
+            &mut req,
+            None,
+        )
+    });
+    let addr = serve(router).await;
+    let (mut stream, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+        .await
+        .unwrap();
+    match stream.next().await.unwrap().unwrap() {
+        Message::Close(Some(frame)) => {
+            assert_eq!(frame.code, CloseCode::Policy);
+            assert_eq!(frame.reason, "bye");
+        }
+        other => panic!("unexpected message: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn client_close_is_reported_and_answered() {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let tx = std::sync::Arc::new(std::sync::Mutex::new(Some(tx)));
+    let mut router = Router::new();
+    router.get("/ws", move |mut req: Req| {
+        let tx = tx.clone();
+        async move {
+            spawn_ws_event(
+                move |socket| async move {
+                    let (_sender, mut receiver, _) = socket.split();
+                    let msg = receiver.recv().await.unwrap().unwrap();
+                    tx.lock().unwrap().take().unwrap().send(msg).unwrap();
+                },
+                &mut req,
+                None,
+            )
+        }
+    });
+    let addr = serve(router).await;
+    let (mut stream, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+        .await
+        .unwrap();
+    stream
+        .close(Some(close_frame(CloseCode::Away, "leaving")))
+        .await
+        .unwrap();
+    // 服务端应回复关闭帧
+    assert!(matches!(stream.next().await, Some(Ok(Message::Close(_)))));
+    assert_eq!(
+        rx.await.unwrap(),
+        WsMessage::Close(Some(close_frame(CloseCode::Away, "leaving")))
+    );
+}
+
+#[tokio::test]
+async fn ws_upgrade_extractor_echoes() {
+    let mut router = Router::new();
+    router.get(
+        "/ws",
+        |upgrade: WsUpgrade, Query(q): Query<HashMap<String, String>>| async move {
+            let greeting = q.get("name").cloned().unwrap_or_default();
+            upgrade.protocols(["chat"]).on(|mut socket| async move {
+                socket.send(format!("hi {greeting}")).await.unwrap();
+                while let Some(Ok(WsMessage::Text(text))) = socket.recv().await {
+                    socket.send(text.to_string()).await.unwrap();
+                }
+            })
+        },
+    );
+    let addr = serve(router).await;
+
+    // 普通 HTTP 请求不满足升级条件
+    let resp = raw_get(addr, "/ws").await;
+    assert!(resp.starts_with("HTTP/1.1 400"));
+
+    let mut request = format!("ws://{addr}/ws?name=ann")
+        .into_client_request()
+        .unwrap();
+    request
+        .headers_mut()
+        .insert("sec-websocket-protocol", "chat".parse().unwrap());
+    let (mut stream, resp) = tokio_tungstenite::connect_async(request).await.unwrap();
+    assert_eq!(resp.headers()["sec-websocket-protocol"], "chat");
+    assert_eq!(
+        stream.next().await.unwrap().unwrap(),
+        Message::text("hi ann")
+    );
+    stream.send(Message::text("ping")).await.unwrap();
+    assert_eq!(stream.next().await.unwrap().unwrap(), Message::text("ping"));
+    let _ = stream.close(None).await;
+}
+
+async fn raw_get(addr: SocketAddr, path: &str) -> String {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    let mut conn = tokio::net::TcpStream::connect(addr).await.unwrap();
+    conn.write_all(
+        format!("GET {path} HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n").as_bytes(),
+    )
+    .await
+    .unwrap();
+    let mut out = String::new();
+    conn.read_to_string(&mut out).await.unwrap();
+    out
+}
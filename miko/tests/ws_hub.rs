@@ -0,0 +1,105 @@
+use futures::StreamExt;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::service::TowerToHyperService;
+use miko::extractor::Query;
+use miko::http::convert::incoming_to_req::IncomingToInternal;
+use miko::router::Router;
+use miko::ws::server::WsMessage;
+use miko::ws::{WsHub, WsUpgrade};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+async fn serve(router: Router) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let svc = router.into_tower_service();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let svc = TowerToHyperService::new(IncomingToInternal { inner: svc.clone() });
+            tokio::spawn(async move {
+                let _ = Builder::new(TokioExecutor::new())
+                    .serve_connection_with_upgrades(TokioIo::new(stream), svc)
+                    .await;
+            });
+        }
+    });
+    addr
+}
+
+fn hub_router(hub: WsHub) -> Router {
+    let mut router = Router::new();
+    router.get(
+        "/ws",
+        move |upgrade: WsUpgrade, Query(q): Query<HashMap<String, String>>| {
+            let hub = hub.clone();
+            async move {
+                upgrade.on(move |socket| async move {
+                    let (member, mut rx) = hub.attach(socket);
+                    if let Some(room) = q.get("room") {
+                        member.join(room);
+                    }
+                    member.send("ready").await;
+                    while let Some(Ok(WsMessage::Text(_))) = rx.recv().await {}
+                })
+            }
+        },
+    );
+    router
+}
+
+async fn wait_for(mut cond: impl FnMut() -> bool) {
+    for _ in 0..100 {
+        if cond() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("condition not reached");
+}
+
+#[tokio::test]
+async fn broadcasts_to_rooms_and_cleans_up() {
+    let hub = WsHub::new();
+    let addr = serve(hub_router(hub.clone())).await;
+
+    let mut clients = Vec::new();
+    for room in ["a", "a", "b"] {
+        let (mut stream, _) =
+            tokio_tungstenite::connect_async(format!("ws://{addr}/ws?room={room}"))
+                .await
+                .unwrap();
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            Message::text("ready")
+        );
+        clients.push(stream);
+    }
+    assert_eq!(hub.len(), 3);
+    assert_eq!(hub.members("a").len(), 2);
+
+    assert_eq!(hub.broadcast_to("a", "to a").await, 2);
+    assert_eq!(
+        clients[0].next().await.unwrap().unwrap(),
+        Message::text("to a")
+    );
+    assert_eq!(
+        clients[1].next().await.unwrap().unwrap(),
+        Message::text("to a")
+    );
+
+    assert_eq!(hub.broadcast("all").await, 3);
+    for client in &mut clients {
+        assert_eq!(client.next().await.unwrap().unwrap(), Message::text("all"));
+    }
+
+    let mut gone = clients.remove(2);
+    gone.close(None).await.unwrap();
+    wait_for(|| hub.len() == 2).await;
+    assert!(hub.members("b").is_empty());
+    assert_eq!(hub.rooms(), vec!["a".to_string()]);
+}
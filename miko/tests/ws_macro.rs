@@ -0,0 +1,87 @@
+use futures::{SinkExt, StreamExt};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::service::TowerToHyperService;
+use miko::auto::init_container;
+use miko::http::convert::incoming_to_req::IncomingToInternal;
+use miko::macros::*;
+use miko::router::Router;
+use miko::ws::server::{WsMessage, WsSocket};
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+struct Greeting {
+    prefix: &'static str,
+}
+
+#[component]
+impl Greeting {
+    async fn new() -> Self {
+        Self { prefix: "hi" }
+    }
+}
+
+#[ws("/echo/{room}", protocols = "chat")]
+async fn echo(
+    mut socket: WsSocket,
+    #[path] room: String,
+    #[query] name: String,
+    #[dep] greeting: Arc<Greeting>,
+) {
+    let protocol = socket.protocol().unwrap_or("-").to_string();
+    socket
+        .send(format!(
+            "{} {}@{} ({})",
+            greeting.prefix, name, room, protocol
+        ))
+        .await
+        .unwrap();
+    while let Some(Ok(WsMessage::Text(text))) = socket.recv().await {
+        socket.send(text.to_string()).await.unwrap();
+    }
+}
+
+async fn serve(router: Router) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let svc = router.into_tower_service();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let svc = TowerToHyperService::new(IncomingToInternal { inner: svc.clone() });
+            tokio::spawn(async move {
+                let _ = Builder::new(TokioExecutor::new())
+                    .serve_connection_with_upgrades(TokioIo::new(stream), svc)
+                    .await;
+            });
+        }
+    });
+    addr
+}
+
+#[tokio::test]
+async fn ws_macro_upgrades_with_extractors() {
+    init_container().await;
+    let addr = serve(miko::auto::collect_global_router()).await;
+
+    let mut req = format!("ws://{addr}/echo/lobby?name=miko")
+        .into_client_request()
+        .unwrap();
+    req.headers_mut()
+        .insert("sec-websocket-protocol", "chat".parse().unwrap());
+    let (mut stream, resp) = tokio_tungstenite::connect_async(req).await.unwrap();
+    assert_eq!(resp.headers()["sec-websocket-protocol"], "chat");
+    assert_eq!(
+        stream.next().await.unwrap().unwrap(),
+        Message::text("hi miko@lobby (chat)")
+    );
+    stream.send(Message::text("ping")).await.unwrap();
+    assert_eq!(stream.next().await.unwrap().unwrap(), Message::text("ping"));
+
+    // 非升级请求在提取阶段被拒绝
+    let client = miko::auto::collect_global_router().test_client();
+    let resp = client.get("/echo/lobby?name=miko").send().await;
+    assert_eq!(resp.status(), 400);
+}